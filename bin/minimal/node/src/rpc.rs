@@ -55,6 +55,7 @@ where
 		+ 'static,
 	C::Api: sp_block_builder::BlockBuilder<OpaqueBlock>,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<OpaqueBlock, AccountId, Nonce>,
+	C::Api: sp_api::Metadata<OpaqueBlock>,
 	P: TransactionPool + 'static,
 {
 	let mut module = RpcModule::new(());
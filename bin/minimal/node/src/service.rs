@@ -54,7 +54,10 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 		.clone()
 		.filter(|x| !x.is_empty())
 		.map(|endpoints| -> Result<_, sc_telemetry::Error> {
-			let worker = TelemetryWorker::new(16)?;
+			let mut worker = TelemetryWorker::new(16)?;
+			if let Some(disk_buffer) = config.telemetry_disk_buffer.clone() {
+				worker = worker.with_disk_buffer(disk_buffer);
+			}
 			let telemetry = worker.handle().new_telemetry(endpoints);
 			Ok((worker, telemetry))
 		})
@@ -178,6 +181,7 @@ pub fn new_full(config: Configuration, consensus: Consensus) -> Result<TaskManag
 		sync_service,
 		config,
 		telemetry: telemetry.as_mut(),
+		session_keys_tx_builder: None,
 	})?;
 
 	let proposer = sc_basic_authorship::ProposerFactory::new(
@@ -94,8 +94,13 @@ pub fn run() -> sc_cli::Result<()> {
 			runner.async_run(|config| {
 				let PartialComponents { client, task_manager, backend, .. } =
 					service::new_partial(&config)?;
-				let aux_revert = Box::new(|client, _, blocks| {
-					sc_consensus_grandpa::revert(client, blocks)?;
+				let revert_finalized = cmd.unsafe_revert_finalized;
+				let aux_revert = Box::new(move |client, _, blocks| {
+					if revert_finalized {
+						sc_consensus_grandpa::revert_finalized(client, blocks)?;
+					} else {
+						sc_consensus_grandpa::revert(client, blocks)?;
+					}
 					Ok(())
 				});
 				Ok((cmd.run(client, backend, Some(aux_revert)), task_manager))
@@ -42,7 +42,10 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 		.clone()
 		.filter(|x| !x.is_empty())
 		.map(|endpoints| -> Result<_, sc_telemetry::Error> {
-			let worker = TelemetryWorker::new(16)?;
+			let mut worker = TelemetryWorker::new(16)?;
+			if let Some(disk_buffer) = config.telemetry_disk_buffer.clone() {
+				worker = worker.with_disk_buffer(disk_buffer);
+			}
 			let telemetry = worker.handle().new_telemetry(endpoints);
 			Ok((worker, telemetry))
 		})
@@ -185,6 +188,7 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 	let backoff_authoring_blocks: Option<()> = None;
 	let name = config.network.node_name.clone();
 	let enable_grandpa = !config.disable_grandpa;
+	let grandpa_paused = config.grandpa_paused;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
 	let rpc_extensions_builder = {
@@ -211,6 +215,7 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 		sync_service: sync_service.clone(),
 		config,
 		telemetry: telemetry.as_mut(),
+		session_keys_tx_builder: None,
 	})?;
 
 	if role.is_authority() {
@@ -293,6 +298,7 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 			voting_rule: sc_consensus_grandpa::VotingRulesBuilder::default().build(),
 			prometheus_registry,
 			shared_voter_state: SharedVoterState::empty(),
+			shared_voting_pause: sc_consensus_grandpa::SharedVotingPause::new(grandpa_paused),
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(transaction_pool),
 		};
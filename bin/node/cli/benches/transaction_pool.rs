@@ -82,12 +82,15 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		rpc_message_buffer_capacity: Default::default(),
 		rpc_batch_config: RpcBatchRequestConfig::Unlimited,
 		rpc_rate_limit: None,
+		rpc_slow_call_threshold: None,
 		prometheus_config: None,
 		telemetry_endpoints: None,
+		telemetry_disk_buffer: None,
 		default_heap_pages: None,
 		offchain_worker: OffchainWorkerConfig { enabled: true, indexing_enabled: false },
 		force_authoring: false,
 		disable_grandpa: false,
+		grandpa_paused: false,
 		dev_key_seed: Some(Sr25519Keyring::Alice.to_seed()),
 		tracing_targets: None,
 		tracing_receiver: Default::default(),
@@ -101,7 +104,8 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 	};
 
 	tokio_handle.block_on(async move {
-		node_cli::service::new_full_base(config, None, false, |_, _| ()).expect("Creates node")
+		node_cli::service::new_full_base(config, None, Some(Default::default()), false, |_, _| ())
+			.expect("Creates node")
 	})
 }
 
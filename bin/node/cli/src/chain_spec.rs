@@ -513,7 +513,7 @@ pub(crate) mod tests {
 
 		sc_service_test::connectivity(integration_test_config_with_two_authorities(), |config| {
 			let NewFullBase { task_manager, client, network, sync, transaction_pool, .. } =
-				new_full_base(config, None, false, |_, _| ())?;
+				new_full_base(config, None, Some(Default::default()), false, |_, _| ())?;
 			Ok(sc_service_test::TestNetComponents::new(
 				task_manager,
 				client,
@@ -538,4 +538,19 @@ pub(crate) mod tests {
 	fn test_staging_test_net_chain_spec() {
 		staging_testnet_config().build_storage().unwrap();
 	}
+
+	#[test]
+	fn runtime_presets_match_chain_specs() {
+		use kitchensink_runtime::genesis_config_presets::{DEVELOPMENT, LOCAL_TESTNET};
+		use sc_chain_spec::GenesisConfigBuilderRuntimeCaller;
+
+		let caller = GenesisConfigBuilderRuntimeCaller::<()>::new(wasm_binary_unwrap());
+		assert_eq!(caller.preset_names().unwrap(), vec![DEVELOPMENT, LOCAL_TESTNET]);
+		assert_eq!(
+			caller.get_named_preset(Some(DEVELOPMENT)).unwrap(),
+			development_config_genesis_json()
+		);
+		assert_eq!(caller.get_named_preset(Some(LOCAL_TESTNET)).unwrap(), local_testnet_genesis());
+		assert!(caller.get_named_preset(Some("unknown")).is_err());
+	}
 }
@@ -31,6 +31,10 @@ pub struct Cli {
 	#[clap(flatten)]
 	pub mixnet_params: sc_cli::MixnetParams,
 
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub backoff_authoring_params: sc_cli::BackoffAuthoringParams,
+
 	/// Disable automatic hardware benchmarks.
 	///
 	/// By default these benchmarks are automatically ran at startup and measure
@@ -85,6 +89,9 @@ pub enum Subcommand {
 	/// Validate blocks.
 	CheckBlock(sc_cli::CheckBlockCmd),
 
+	/// Re-execute a block from the database and print an execution report.
+	ExecuteBlock(sc_cli::ExecuteBlockCmd),
+
 	/// Export blocks.
 	ExportBlocks(sc_cli::ExportBlocksCmd),
 
@@ -182,6 +182,14 @@ pub fn run() -> Result<()> {
 				Ok((cmd.run(client, import_queue), task_manager))
 			})
 		},
+		Some(Subcommand::ExecuteBlock(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.async_run(|config| {
+				let PartialComponents { client, task_manager, backend, .. } =
+					new_partial(&config, None)?;
+				Ok((cmd.run(client, backend), task_manager))
+			})
+		},
 		Some(Subcommand::ExportBlocks(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
@@ -213,9 +221,16 @@ pub fn run() -> Result<()> {
 			runner.async_run(|config| {
 				let PartialComponents { client, task_manager, backend, .. } =
 					new_partial(&config, None)?;
-				let aux_revert = Box::new(|client: Arc<FullClient>, backend, blocks| {
-					sc_consensus_babe::revert(client.clone(), backend, blocks)?;
-					grandpa::revert(client, blocks)?;
+				let revert_finalized = cmd.unsafe_revert_finalized;
+				let aux_revert = Box::new(move |client: Arc<FullClient>, backend, blocks| {
+					// GRANDPA goes first, it refuses reverts it can't handle before anything
+					// has been touched.
+					if revert_finalized {
+						grandpa::revert_finalized(client.clone(), blocks)?;
+					} else {
+						grandpa::revert(client.clone(), blocks)?;
+					}
+					sc_consensus_babe::revert(client, backend, blocks)?;
 					Ok(())
 				});
 				Ok((cmd.run(client, backend, Some(aux_revert)), task_manager))
@@ -21,12 +21,12 @@
 //! Service implementation. Specialized wrapper over substrate service.
 
 use crate::Cli;
-use codec::Encode;
+use codec::{Decode, Encode};
 use frame_benchmarking_cli::SUBSTRATE_REFERENCE_HARDWARE;
 use frame_system_rpc_runtime_api::AccountNonceApi;
 use futures::prelude::*;
 use kitchensink_runtime::RuntimeApi;
-use node_primitives::Block;
+use node_primitives::{Block, BlockNumber};
 use sc_client_api::{Backend, BlockBackend};
 use sc_consensus_babe::{self, SlotProportion};
 use sc_network::{event::Event, NetworkEventStream, NetworkService};
@@ -36,8 +36,10 @@ use sc_statement_store::Store as StatementStore;
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_api::ProvideRuntimeApi;
-use sp_core::crypto::Pair;
+use sp_core::crypto::{KeyTypeId, Pair};
+use sp_keystore::{Keystore, KeystoreExt, KeystorePtr};
 use sp_runtime::{generic, traits::Block as BlockT, SaturatedConversion};
+use sp_session::SessionKeys;
 use std::{path::Path, sync::Arc};
 
 /// Host functions required for kitchensink runtime and Substrate node.
@@ -96,11 +98,27 @@ pub fn create_extrinsic(
 	function: impl Into<kitchensink_runtime::RuntimeCall>,
 	nonce: Option<u32>,
 ) -> kitchensink_runtime::UncheckedExtrinsic {
+	let nonce = nonce.unwrap_or_else(|| fetch_nonce(client, sender.clone()));
+	create_extrinsic_signed_with(client, sender.public(), function, nonce, |payload| {
+		Some(sender.sign(payload))
+	})
+	.expect("Signing with a key pair works; qed")
+}
+
+/// Create a transaction using the given `call`, signed as `sender` by `sign`.
+///
+/// Returns `None` if `sign` fails to produce a signature.
+fn create_extrinsic_signed_with(
+	client: &FullClient,
+	sender: sp_core::sr25519::Public,
+	function: impl Into<kitchensink_runtime::RuntimeCall>,
+	nonce: u32,
+	sign: impl FnOnce(&[u8]) -> Option<sp_core::sr25519::Signature>,
+) -> Option<kitchensink_runtime::UncheckedExtrinsic> {
 	let function = function.into();
 	let genesis_hash = client.block_hash(0).ok().flatten().expect("Genesis block exists; qed");
 	let best_hash = client.chain_info().best_hash;
 	let best_block = client.chain_info().best_number;
-	let nonce = nonce.unwrap_or_else(|| fetch_nonce(client, sender.clone()));
 
 	let period = kitchensink_runtime::BlockHashCount::get()
 		.checked_next_power_of_two()
@@ -140,14 +158,60 @@ pub fn create_extrinsic(
 			(),
 		),
 	);
-	let signature = raw_payload.using_encoded(|e| sender.sign(e));
+	let signature = raw_payload.using_encoded(sign)?;
 
-	kitchensink_runtime::UncheckedExtrinsic::new_signed(
+	Some(kitchensink_runtime::UncheckedExtrinsic::new_signed(
 		function,
-		sp_runtime::AccountId32::from(sender.public()).into(),
+		sp_runtime::AccountId32::from(sender).into(),
 		kitchensink_runtime::Signature::Sr25519(signature),
 		extra,
-	)
+	))
+}
+
+/// Creates the `session::set_keys` transaction submitted by `author_rotateAndSubmitKeys`.
+///
+/// The transaction is signed with the first sr25519 key of the requested type in the keystore.
+struct SetKeysTransactionBuilder {
+	client: Arc<FullClient>,
+}
+
+impl sc_rpc::author::SessionKeysTransactionBuilder for SetKeysTransactionBuilder {
+	fn build(
+		&self,
+		encoded_keys: Vec<u8>,
+		key_type: KeyTypeId,
+		keystore: KeystorePtr,
+	) -> Result<Vec<u8>, String> {
+		let keys = kitchensink_runtime::SessionKeys::decode(&mut &encoded_keys[..])
+			.map_err(|e| format!("Invalid session keys: {}", e))?;
+		let signer = keystore
+			.sr25519_public_keys(key_type)
+			.into_iter()
+			.next()
+			.ok_or_else(|| format!("No sr25519 key of type {:?} in the keystore", key_type))?;
+		let best_hash = self.client.chain_info().best_hash;
+		let nonce = self
+			.client
+			.runtime_api()
+			.account_nonce(best_hash, signer.into())
+			.map_err(|e| format!("Failed to fetch the account nonce: {}", e))?;
+
+		// The session keys sign the ownership proof from within the runtime.
+		let mut runtime_api = self.client.runtime_api();
+		runtime_api.register_extension(KeystoreExt::from(keystore.clone()));
+		let owner = sp_runtime::AccountId32::from(signer).encode();
+		let proof = runtime_api
+			.generate_ownership_proof(best_hash, owner, encoded_keys)
+			.map_err(|e| format!("Failed to generate the ownership proof: {}", e))?
+			.ok_or("Private session keys are missing from the keystore")?;
+
+		let call = pallet_session::Call::set_keys { keys, proof };
+		create_extrinsic_signed_with(&self.client, signer, call, nonce, |payload| {
+			keystore.sr25519_sign(key_type, &signer, payload).ok().flatten()
+		})
+		.map(|xt| xt.encode())
+		.ok_or_else(|| "Failed to sign the transaction".into())
+	}
 }
 
 /// Creates a new partial node.
@@ -176,7 +240,7 @@ pub fn new_partial(
 				sc_consensus_babe::BabeLink<Block>,
 				beefy::BeefyVoterLinks<Block>,
 			),
-			grandpa::SharedVoterState,
+			(grandpa::SharedVoterState, grandpa::SharedVotingPause),
 			Option<Telemetry>,
 			Arc<StatementStore>,
 			Option<sc_mixnet::ApiBackend>,
@@ -189,7 +253,10 @@ pub fn new_partial(
 		.clone()
 		.filter(|x| !x.is_empty())
 		.map(|endpoints| -> Result<_, sc_telemetry::Error> {
-			let worker = TelemetryWorker::new(16)?;
+			let mut worker = TelemetryWorker::new(16)?;
+			if let Some(disk_buffer) = config.telemetry_disk_buffer.clone() {
+				worker = worker.with_disk_buffer(disk_buffer);
+			}
 			let telemetry = worker.handle().new_telemetry(endpoints);
 			Ok((worker, telemetry))
 		})
@@ -289,6 +356,8 @@ pub fn new_partial(
 		let shared_authority_set = grandpa_link.shared_authority_set().clone();
 		let shared_voter_state = grandpa::SharedVoterState::empty();
 		let shared_voter_state2 = shared_voter_state.clone();
+		let shared_voting_pause = grandpa::SharedVotingPause::new(config.grandpa_paused);
+		let shared_voting_pause2 = shared_voting_pause.clone();
 
 		let finality_proof_provider = grandpa::FinalityProofProvider::new_for_service(
 			backend.clone(),
@@ -317,6 +386,7 @@ pub fn new_partial(
 					},
 					grandpa: node_rpc::GrandpaDeps {
 						shared_voter_state: shared_voter_state.clone(),
+						shared_voting_pause: shared_voting_pause.clone(),
 						shared_authority_set: shared_authority_set.clone(),
 						justification_stream: justification_stream.clone(),
 						subscription_executor: subscription_executor.clone(),
@@ -339,7 +409,7 @@ pub fn new_partial(
 				node_rpc::create_full(deps).map_err(Into::into)
 			};
 
-		(rpc_extensions_builder, shared_voter_state2)
+		(rpc_extensions_builder, (shared_voter_state2, shared_voting_pause2))
 	};
 
 	Ok(sc_service::PartialComponents {
@@ -381,6 +451,9 @@ pub struct NewFullBase {
 pub fn new_full_base(
 	config: Configuration,
 	mixnet_config: Option<sc_mixnet::Config>,
+	backoff_authoring_blocks: Option<
+		sc_consensus_slots::BackoffAuthoringOnFinalizedHeadLagging<BlockNumber>,
+	>,
 	disable_hardware_benchmarks: bool,
 	with_startup_data: impl FnOnce(
 		&sc_consensus_babe::BabeBlockImport<
@@ -394,8 +467,6 @@ pub fn new_full_base(
 	let is_offchain_indexing_enabled = config.offchain_worker.indexing_enabled;
 	let role = config.role.clone();
 	let force_authoring = config.force_authoring;
-	let backoff_authoring_blocks =
-		Some(sc_consensus_slots::BackoffAuthoringOnFinalizedHeadLagging::default());
 	let name = config.network.node_name.clone();
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
@@ -420,7 +491,7 @@ pub fn new_full_base(
 			(rpc_builder, import_setup, rpc_setup, mut telemetry, statement_store, mixnet_api_backend),
 	} = new_partial(&config, mixnet_config.as_ref())?;
 
-	let shared_voter_state = rpc_setup;
+	let (shared_voter_state, shared_voting_pause) = rpc_setup;
 	let auth_disc_publish_non_global_ips = config.network.allow_non_globals_in_dht;
 	let mut net_config = sc_network::config::FullNetworkConfiguration::new(&config.network);
 	let genesis_hash = client.block_hash(0).ok().flatten().expect("Genesis block exists; qed");
@@ -512,6 +583,9 @@ pub fn new_full_base(
 		tx_handler_controller,
 		sync_service: sync_service.clone(),
 		telemetry: telemetry.as_mut(),
+		session_keys_tx_builder: Some(Arc::new(SetKeysTransactionBuilder {
+			client: client.clone(),
+		})),
 	})?;
 
 	if let Some(hwbench) = hwbench {
@@ -580,6 +654,7 @@ pub fn new_full_base(
 				}
 			},
 			force_authoring,
+			author_secondary_slots: true,
 			backoff_authoring_blocks,
 			babe_link,
 			block_proposal_slot_portion: SlotProportion::new(0.5),
@@ -700,6 +775,7 @@ pub fn new_full_base(
 			voting_rule: grandpa::VotingRulesBuilder::default().build(),
 			prometheus_registry: prometheus_registry.clone(),
 			shared_voter_state,
+			shared_voting_pause,
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(transaction_pool.clone()),
 		};
 
@@ -769,9 +845,16 @@ pub fn new_full_base(
 /// Builds a new service for a full client.
 pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceError> {
 	let mixnet_config = cli.mixnet_params.config(config.role.is_authority());
+	let backoff_authoring_blocks = cli.backoff_authoring_params.strategy();
 	let database_path = config.database.path().map(Path::to_path_buf);
-	let task_manager = new_full_base(config, mixnet_config, cli.no_hardware_benchmarks, |_, _| ())
-		.map(|NewFullBase { task_manager, .. }| task_manager)?;
+	let task_manager = new_full_base(
+		config,
+		mixnet_config,
+		backoff_authoring_blocks,
+		cli.no_hardware_benchmarks,
+		|_, _| (),
+	)
+	.map(|NewFullBase { task_manager, .. }| task_manager)?;
 
 	if let Some(database_path) = database_path {
 		sc_storage_monitor::StorageMonitorService::try_spawn(
@@ -851,6 +934,7 @@ mod tests {
 					new_full_base(
 						config,
 						None,
+						Some(Default::default()),
 						false,
 						|block_import: &sc_consensus_babe::BabeBlockImport<Block, _, _>,
 						 babe_link: &sc_consensus_babe::BabeLink<Block>| {
@@ -1026,7 +1110,7 @@ mod tests {
 			crate::chain_spec::tests::integration_test_config_with_two_authorities(),
 			|config| {
 				let NewFullBase { task_manager, client, network, sync, transaction_pool, .. } =
-					new_full_base(config, None, false, |_, _| ())?;
+					new_full_base(config, None, Some(Default::default()), false, |_, _| ())?;
 				Ok(sc_service_test::TestNetComponents::new(
 					task_manager,
 					client,
@@ -37,3 +37,61 @@ fn build_spec_works() {
 
 	let _value: serde_json::Value = serde_json::from_slice(output.stdout.as_slice()).unwrap();
 }
+
+#[test]
+fn build_spec_lists_runtime_presets() {
+	let base_path = tempdir().expect("could not create a temp dir");
+
+	let output = Command::new(cargo_bin("substrate-node"))
+		.args(&["build-spec", "--dev", "--list-presets", "-d"])
+		.arg(base_path.path())
+		.output()
+		.unwrap();
+	assert!(output.status.success());
+
+	let names = String::from_utf8(output.stdout).unwrap();
+	assert_eq!(names.lines().collect::<Vec<_>>(), vec!["development", "local_testnet"]);
+}
+
+#[test]
+fn build_spec_from_runtime_preset_works() {
+	let base_path = tempdir().expect("could not create a temp dir");
+	let patch_path = base_path.path().join("patch.json");
+	std::fs::write(&patch_path, r#"{ "society": { "pot": 42 } }"#).unwrap();
+
+	let output = Command::new(cargo_bin("substrate-node"))
+		.args(&["build-spec", "--dev", "--preset", "local_testnet", "--patch"])
+		.arg(&patch_path)
+		.arg("-d")
+		.arg(base_path.path())
+		.output()
+		.unwrap();
+	assert!(output.status.success());
+
+	let spec: serde_json::Value = serde_json::from_slice(output.stdout.as_slice()).unwrap();
+	let patch = &spec["genesis"]["runtimeGenesis"]["patch"];
+	assert_eq!(patch["session"]["keys"].as_array().unwrap().len(), 2);
+	assert_eq!(patch["society"]["pot"], 42);
+
+	let output = Command::new(cargo_bin("substrate-node"))
+		.args(&["build-spec", "--dev", "--preset", "unknown", "-d"])
+		.arg(base_path.path())
+		.output()
+		.unwrap();
+	assert!(!output.status.success());
+}
+
+#[test]
+fn build_spec_from_runtime_preset_raw_works() {
+	let base_path = tempdir().expect("could not create a temp dir");
+
+	let output = Command::new(cargo_bin("substrate-node"))
+		.args(&["build-spec", "--dev", "--preset", "development", "--raw", "-d"])
+		.arg(base_path.path())
+		.output()
+		.unwrap();
+	assert!(output.status.success());
+
+	let spec: serde_json::Value = serde_json::from_slice(output.stdout.as_slice()).unwrap();
+	assert!(spec["genesis"]["raw"]["top"].as_object().map_or(false, |top| !top.is_empty()));
+}
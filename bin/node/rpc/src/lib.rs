@@ -42,6 +42,7 @@ use sc_consensus_beefy::communication::notification::{
 };
 use sc_consensus_grandpa::{
 	FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState,
+	SharedVotingPause,
 };
 pub use sc_rpc::SubscriptionTaskExecutor;
 pub use sc_rpc_api::DenyUnsafe;
@@ -65,6 +66,8 @@ pub struct BabeDeps {
 pub struct GrandpaDeps<B> {
 	/// Voting round info.
 	pub shared_voter_state: SharedVoterState,
+	/// Pause and resume local voting.
+	pub shared_voting_pause: SharedVotingPause,
 	/// Authority set info.
 	pub shared_authority_set: SharedAuthoritySet<Hash, BlockNumber>,
 	/// Receives notifications about justification events from Grandpa.
@@ -141,6 +144,7 @@ where
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BabeApi<Block>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: sp_api::Metadata<Block>,
 	P: TransactionPool + 'static,
 	SC: SelectChain<Block> + 'static,
 	B: sc_client_api::Backend<Block> + Send + Sync + 'static,
@@ -166,6 +170,7 @@ where
 	let BabeDeps { keystore, babe_worker_handle } = babe;
 	let GrandpaDeps {
 		shared_voter_state,
+		shared_voting_pause,
 		shared_authority_set,
 		justification_stream,
 		subscription_executor,
@@ -202,6 +207,8 @@ where
 			shared_voter_state,
 			justification_stream,
 			finality_provider,
+			shared_voting_pause,
+			deny_unsafe,
 		)
 		.into_rpc(),
 	)?;
@@ -0,0 +1,183 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Genesis config presets provided through the `GenesisBuilder` runtime API.
+//!
+//! The presets match the `dev` and `local_testnet` chain specs of the node. Key derivation is not
+//! available in the runtime, so the well-known development keys are hardcoded.
+
+use crate::{
+	constants::currency::*, AccountId, AuthorityDiscoveryId, Balance, BeefyId, GrandpaId,
+	ImOnlineId, SessionKeys, StakerStatus, BABE_GENESIS_EPOCH_CONFIG,
+};
+use sp_consensus_babe::AuthorityId as BabeId;
+use sp_core::{ecdsa, ed25519, hex2array, sr25519};
+use sp_genesis_builder::PresetId;
+use sp_mixnet::types::AuthorityId as MixnetId;
+use sp_runtime::Perbill;
+use sp_std::prelude::*;
+
+/// Single validator (Alice) development chain.
+pub const DEVELOPMENT: &str = "development";
+/// Local testnet with two validators (Alice and Bob).
+pub const LOCAL_TESTNET: &str = "local_testnet";
+
+const ENDOWMENT: Balance = 10_000_000 * DOLLARS;
+const STASH: Balance = ENDOWMENT / 1000;
+
+/// Public keys of the `//Alice` to `//Ferdie` sr25519 accounts, followed by their `//stash`
+/// accounts.
+const ENDOWED_ACCOUNTS: [[u8; 32]; 12] = [
+	hex2array!("d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"),
+	hex2array!("8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"),
+	hex2array!("90b5ab205c6974c9ea841be688864633dc9ca8a357843eeacf2314649965fe22"),
+	hex2array!("306721211d5404bd9da88e0204360a1a9ab8b87c66c1bc2fcdd37f3c2222cc20"),
+	hex2array!("e659a7a1628cdd93febc04a4e0646ea20e9f5f0ce097d9a05290d4a9e054df4e"),
+	hex2array!("1cbd2d43530a44705ad088af313e18f80b53ef16b36177cd4b77b846f2a5f07c"),
+	hex2array!("be5ddb1579b72e84524fc29e78609e3caf42e85aa118ebfe0b0ad404b5bdd25f"),
+	hex2array!("fe65717dad0447d715f660a0a58411de509b42e6efb8375f562f58a554d5860e"),
+	hex2array!("1e07379407fecc4b89eb7dbd287c2c781cfb1907a96947a3eb18e4f8e7198625"),
+	hex2array!("e860f1b1c7227f7c22602f53f15af80747814dffd839719731ee3bba6edc126c"),
+	hex2array!("8ac59e11963af19174d0b94d5d78041c233f55d2e19324665bafdfb62925af2d"),
+	hex2array!("101191192fc877c24d725b337120fa3edc63d227bbc92705db1e2cb65f56981a"),
+];
+
+/// A validator of a development chain.
+struct Authority {
+	stash: AccountId,
+	keys: SessionKeys,
+}
+
+impl Authority {
+	/// Create an authority from its stash account and its sr25519, ed25519 and ecdsa session keys.
+	fn new(stash: [u8; 32], sr25519: [u8; 32], ed25519: [u8; 32], ecdsa: [u8; 33]) -> Self {
+		let sr25519 = sr25519::Public::from_raw(sr25519);
+		Authority {
+			stash: stash.into(),
+			keys: SessionKeys {
+				grandpa: GrandpaId::from(ed25519::Public::from_raw(ed25519)),
+				babe: BabeId::from(sr25519),
+				im_online: ImOnlineId::from(sr25519),
+				authority_discovery: AuthorityDiscoveryId::from(sr25519),
+				mixnet: MixnetId::from(sr25519),
+				beefy: BeefyId::from(ecdsa::Public::from_raw(ecdsa)),
+			},
+		}
+	}
+
+	/// The keys derived from `//Alice`.
+	fn alice() -> Self {
+		Self::new(
+			ENDOWED_ACCOUNTS[6],
+			ENDOWED_ACCOUNTS[0],
+			hex2array!("88dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee"),
+			hex2array!("020a1091341fe5664bfa1782d5e04779689068c916b04cb365ec3153755684d9a1"),
+		)
+	}
+
+	/// The keys derived from `//Bob`.
+	fn bob() -> Self {
+		Self::new(
+			ENDOWED_ACCOUNTS[7],
+			ENDOWED_ACCOUNTS[1],
+			hex2array!("d17c2d7823ebf260fd138f2d7e27d114c0145d968b5ff5006125f2414fadae69"),
+			hex2array!("0390084fdbf27d2b79d26a4f13f0ccd982cb755a661969143c37cbc49ef5b91f27"),
+		)
+	}
+}
+
+/// Genesis config patch of a development chain with the given validators and sudo key.
+fn testnet_genesis(authorities: Vec<Authority>, root_key: AccountId) -> serde_json::Value {
+	let endowed_accounts = ENDOWED_ACCOUNTS
+		.iter()
+		.map(|account| AccountId::from(*account))
+		.collect::<Vec<_>>();
+	let num_endowed_accounts = endowed_accounts.len();
+
+	serde_json::json!({
+		"balances": {
+			"balances": endowed_accounts.iter().cloned().map(|x| (x, ENDOWMENT)).collect::<Vec<_>>(),
+		},
+		"session": {
+			"keys": authorities
+				.iter()
+				.map(|x| (x.stash.clone(), x.stash.clone(), x.keys.clone()))
+				.collect::<Vec<_>>(),
+		},
+		"staking": {
+			"validatorCount": authorities.len() as u32,
+			"minimumValidatorCount": authorities.len() as u32,
+			"invulnerables": authorities.iter().map(|x| x.stash.clone()).collect::<Vec<_>>(),
+			"slashRewardFraction": Perbill::from_percent(10),
+			"stakers": authorities
+				.iter()
+				.map(|x| {
+					(x.stash.clone(), x.stash.clone(), STASH, StakerStatus::<AccountId>::Validator)
+				})
+				.collect::<Vec<_>>(),
+		},
+		"elections": {
+			"members": endowed_accounts
+				.iter()
+				.take((num_endowed_accounts + 1) / 2)
+				.cloned()
+				.map(|member| (member, STASH))
+				.collect::<Vec<_>>(),
+		},
+		"technicalCommittee": {
+			"members": endowed_accounts
+				.iter()
+				.take((num_endowed_accounts + 1) / 2)
+				.cloned()
+				.collect::<Vec<_>>(),
+		},
+		"sudo": { "key": Some(root_key.clone()) },
+		"babe": {
+			"epochConfig": Some(BABE_GENESIS_EPOCH_CONFIG),
+		},
+		"society": { "pot": 0 },
+		"assets": {
+			// This asset is used by the NIS pallet as counterpart currency.
+			"assets": vec![(9, root_key, true, 1)],
+		},
+		"nominationPools": {
+			"minCreateBond": 10 * DOLLARS,
+			"minJoinBond": 1 * DOLLARS,
+		},
+	})
+}
+
+/// Returns the genesis config patch of the preset with the given name, encoded as JSON.
+pub fn get_preset(id: &PresetId) -> Option<Vec<u8>> {
+	let alice = AccountId::from(ENDOWED_ACCOUNTS[0]);
+	let patch = match core::str::from_utf8(id.as_ref()).ok()? {
+		DEVELOPMENT => testnet_genesis(vec![Authority::alice()], alice),
+		LOCAL_TESTNET => testnet_genesis(vec![Authority::alice(), Authority::bob()], alice),
+		_ => return None,
+	};
+	Some(
+		serde_json::to_string(&patch)
+			.expect("serialization to json is expected to work. qed.")
+			.into_bytes(),
+	)
+}
+
+/// Returns the names of the presets provided by [`get_preset`].
+pub fn preset_names() -> Vec<PresetId> {
+	vec![PresetId::from(DEVELOPMENT), PresetId::from(LOCAL_TESTNET)]
+}
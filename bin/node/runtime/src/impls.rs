@@ -111,6 +111,16 @@ impl ProposalProvider<AccountId, Hash, RuntimeCall> for AllianceProposalProvider
 	}
 }
 
+/// Pretends a new BABE epoch started, so the lottery can be paid out with fresh randomness.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct LotteryBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_lottery::BenchmarkHelper<crate::BlockNumber> for LotteryBenchmarkHelper {
+	fn refresh_randomness(block: crate::BlockNumber) {
+		pallet_babe::EpochStart::<Runtime>::put((block, block));
+	}
+}
+
 #[cfg(test)]
 mod multiplier_tests {
 	use frame_support::{
@@ -308,7 +318,7 @@ mod multiplier_tests {
 			Weight::from_parts(100, 0);
 
 		// Default substrate weight.
-		let tx_weight = frame_support::weights::constants::ExtrinsicBaseWeight::get();
+		let tx_weight = crate::weights::ExtrinsicBaseWeight::get();
 
 		run_with_system_weight(block_weight, || {
 			// initial value configured on module
@@ -31,7 +31,7 @@ use frame_support::{
 	construct_runtime, derive_impl,
 	dispatch::DispatchClass,
 	dynamic_params::{dynamic_pallet_params, dynamic_params},
-	genesis_builder_helper::{build_config, create_default_config},
+	genesis_builder_helper::{build_config, create_default_config, get_preset},
 	instances::{Instance1, Instance2},
 	ord_parameter_types,
 	pallet_prelude::Get,
@@ -41,7 +41,9 @@ use frame_support::{
 			Balanced, Credit, HoldConsideration, ItemOf, NativeFromLeft, NativeOrWithId, UnionOf,
 		},
 		tokens::{
-			imbalance::ResolveAssetTo, nonfungibles_v2::Inspect, pay::PayAssetFromAccount,
+			imbalance::ResolveAssetTo,
+			nonfungibles_v2::{Inspect, InspectEnumerable},
+			pay::PayAssetFromAccount,
 			GetSalary, PayFromAccount,
 		},
 		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU16, ConstU32, Contains, Currency,
@@ -50,9 +52,7 @@ use frame_support::{
 		OnUnbalanced, WithdrawReasons,
 	},
 	weights::{
-		constants::{
-			BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_REF_TIME_PER_SECOND,
-		},
+		constants::{RocksDbWeight, WEIGHT_REF_TIME_PER_SECOND},
 		ConstantMultiplier, IdentityFee, Weight,
 	},
 	BoundedVec, PalletId,
@@ -72,7 +72,7 @@ use pallet_nfts::PalletFeatures;
 use pallet_nis::WithMaximumOf;
 use pallet_session::historical as pallet_session_historical;
 pub use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustment};
-use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
+use pallet_transaction_payment::{FeeDetails, FeeMultiplierInfo, RuntimeDispatchInfo};
 use pallet_tx_pause::RuntimeCallNameOf;
 use sp_api::impl_runtime_apis;
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
@@ -126,9 +126,16 @@ use sp_runtime::generic::Era;
 /// Generated voter bag information.
 mod voter_bags;
 
+/// Execution overhead weights, generated by `benchmark overhead`.
+pub mod weights;
+use weights::{BlockExecutionWeight, ExtrinsicBaseWeight};
+
 /// Runtime API definition for assets.
 pub mod assets_api;
 
+/// Genesis config presets of the development chains.
+pub mod genesis_config_presets;
+
 // Make the WASM binary available.
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
@@ -307,6 +314,7 @@ impl frame_system::Config for Runtime {
 	type BlockHashCount = BlockHashCount;
 	type Version = Version;
 	type AccountData = pallet_balances::AccountData<Balance>;
+	type ReferenceHolders = (Balances, Session);
 	type SystemWeightInfo = frame_system::weights::SubstrateWeight<Runtime>;
 	type SS58Prefix = ConstU16<42>;
 	type MaxConsumers = ConstU32<16>;
@@ -559,6 +567,7 @@ impl pallet_transaction_payment::Config for Runtime {
 		MinimumMultiplier,
 		MaximumMultiplier,
 	>;
+	type PriorityCurve = pallet_transaction_payment::TipPerResourcePriority;
 }
 
 impl pallet_asset_tx_payment::Config for Runtime {
@@ -586,12 +595,17 @@ impl pallet_skip_feeless_payment::Config for Runtime {
 
 parameter_types! {
 	pub const MinimumPeriod: Moment = SLOT_DURATION / 2;
+	// Authors set the timestamp when they start proposing, early in their slot.
+	pub const MaxTimestampDrift: Option<Moment> = Some(SLOT_DURATION * 2 / 3);
 }
 
 impl pallet_timestamp::Config for Runtime {
 	type Moment = Moment;
 	type OnTimestampSet = Babe;
 	type MinimumPeriod = MinimumPeriod;
+	type ExpectedTimestamp = pallet_babe::SlotStartTimestamp<Self>;
+	type MaxTimestampDrift = MaxTimestampDrift;
+	type OnTimestampDrift = ();
 	type WeightInfo = pallet_timestamp::weights::SubstrateWeight<Runtime>;
 }
 
@@ -620,9 +634,20 @@ impl pallet_session::Config for Runtime {
 	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Self, Staking>;
 	type SessionHandler = <SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
 	type Keys = SessionKeys;
+	type InactiveKeysExpiry = InactiveKeysExpiry;
+	type ValidatorIntent = StakingValidators;
+	type MaxKeysPurgedPerSession = ConstU32<100>;
 	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
 }
 
+/// Stashes that want to validate, i.e. have called `validate` and not chilled since.
+pub struct StakingValidators;
+impl Contains<AccountId> for StakingValidators {
+	fn contains(stash: &AccountId) -> bool {
+		pallet_staking::Validators::<Runtime>::contains_key(stash)
+	}
+}
+
 impl pallet_session::historical::Config for Runtime {
 	type FullIdentification = pallet_staking::Exposure<AccountId, Balance>;
 	type FullIdentificationOf = pallet_staking::ExposureOf<Runtime>;
@@ -642,6 +667,8 @@ pallet_staking_reward_curve::build! {
 parameter_types! {
 	pub const SessionsPerEra: sp_staking::SessionIndex = 6;
 	pub const BondingDuration: sp_staking::EraIndex = 24 * 28;
+	pub const InactiveKeysExpiry: sp_staking::SessionIndex =
+		BondingDuration::get() * SessionsPerEra::get();
 	pub const SlashDeferDuration: sp_staking::EraIndex = 24 * 7; // 1/4 the bonding duration.
 	pub const RewardCurve: &'static PiecewiseLinear<'static> = &REWARD_CURVE;
 	pub const MaxNominators: u32 = 64;
@@ -1302,6 +1329,7 @@ impl pallet_message_queue::Config for Runtime {
 	type HeapSize = ConstU32<{ 64 * 1024 }>;
 	type MaxStale = ConstU32<128>;
 	type ServiceWeight = MessageQueueServiceWeight;
+	type IdleMaxServiceWeight = MessageQueueServiceWeight;
 }
 
 parameter_types! {
@@ -1334,7 +1362,7 @@ parameter_types! {
 
 impl pallet_contracts::Config for Runtime {
 	type Time = Timestamp;
-	type Randomness = RandomnessCollectiveFlip;
+	type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
 	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -1373,6 +1401,8 @@ impl pallet_contracts::Config for Runtime {
 impl pallet_sudo::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
+	type Scheduler = Scheduler;
+	type Preimages = Preimage;
 	type WeightInfo = pallet_sudo::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1383,6 +1413,8 @@ parameter_types! {
 	pub const MaxAuthorities: u32 = 100;
 	pub const MaxKeys: u32 = 10_000;
 	pub const MaxPeerInHeartbeats: u32 = 10_000;
+	pub const OfflineThreshold: Perbill = Perbill::from_percent(10);
+	pub const MaxOfflineSlash: Perbill = Perbill::from_percent(7);
 }
 
 impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
@@ -1451,15 +1483,23 @@ impl pallet_im_online::Config for Runtime {
 	type ValidatorSet = Historical;
 	type ReportUnresponsiveness = Offences;
 	type UnsignedPriority = ImOnlineUnsignedPriority;
+	type OfflineThreshold = OfflineThreshold;
+	type MaxOfflineSlash = MaxOfflineSlash;
 	type WeightInfo = pallet_im_online::weights::SubstrateWeight<Runtime>;
 	type MaxKeys = MaxKeys;
 	type MaxPeerInHeartbeats = MaxPeerInHeartbeats;
 }
 
+parameter_types! {
+	pub MaxOffenceProcessingWeight: Weight = Perbill::from_percent(10) *
+		RuntimeBlockWeights::get().max_block;
+}
+
 impl pallet_offences::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
 	type OnOffenceHandler = Staking;
+	type MaxOffenceProcessingWeight = MaxOffenceProcessingWeight;
 }
 
 impl pallet_authority_discovery::Config for Runtime {
@@ -1603,19 +1643,23 @@ parameter_types! {
 	pub const LotteryPalletId: PalletId = PalletId(*b"py/lotto");
 	pub const MaxCalls: u32 = 10;
 	pub const MaxGenerateRandom: u32 = 10;
+	pub const MaxLotteryWinners: u32 = 10;
 }
 
 impl pallet_lottery::Config for Runtime {
 	type PalletId = LotteryPalletId;
 	type RuntimeCall = RuntimeCall;
 	type Currency = Balances;
-	type Randomness = RandomnessCollectiveFlip;
+	type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
 	type RuntimeEvent = RuntimeEvent;
 	type ManagerOrigin = EnsureRoot<AccountId>;
 	type MaxCalls = MaxCalls;
 	type ValidateCall = Lottery;
 	type MaxGenerateRandom = MaxGenerateRandom;
+	type MaxWinners = MaxLotteryWinners;
 	type WeightInfo = pallet_lottery::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = impls::LotteryBenchmarkHelper;
 }
 
 parameter_types! {
@@ -1640,6 +1684,7 @@ impl pallet_assets::Config<Instance1> for Runtime {
 	type MetadataDepositPerByte = MetadataDepositPerByte;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
+	type MaxBatch = ConstU32<64>;
 	type Freezer = ();
 	type Extra = ();
 	type CallbackHandle = ();
@@ -1667,6 +1712,7 @@ impl pallet_assets::Config<Instance2> for Runtime {
 	type MetadataDepositPerByte = MetadataDepositPerByte;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
+	type MaxBatch = ConstU32<64>;
 	type Freezer = ();
 	type Extra = ();
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
@@ -1714,6 +1760,7 @@ parameter_types! {
 	pub const QueueCount: u32 = 300;
 	pub const MaxQueueLen: u32 = 1000;
 	pub const FifoQueueLen: u32 = 500;
+	pub const MaxBidsPerAccountPerQueue: u32 = 10;
 	pub const NisBasePeriod: BlockNumber = 30 * DAYS;
 	pub const MinBid: Balance = 100 * DOLLARS;
 	pub const MinReceipt: Perquintill = Perquintill::from_percent(1);
@@ -1739,6 +1786,7 @@ impl pallet_nis::Config for Runtime {
 	type QueueCount = QueueCount;
 	type MaxQueueLen = MaxQueueLen;
 	type FifoQueueLen = FifoQueueLen;
+	type MaxBidsPerAccountPerQueue = MaxBidsPerAccountPerQueue;
 	type BasePeriod = NisBasePeriod;
 	type MinBid = MinBid;
 	type MinReceipt = MinReceipt;
@@ -2305,6 +2353,7 @@ type Migrations = (
 	pallet_alliance::migration::Migration<Runtime>,
 	pallet_contracts::Migration<Runtime>,
 	pallet_identity::migration::versioned::V0ToV1<Runtime, IDENTITY_MIGRATION_KEY_LIMIT>,
+	pallet_lottery::migrations::MigrateV0ToV1<Runtime>,
 );
 
 type EventRecord = frame_system::EventRecord<
@@ -2599,6 +2648,28 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl frame_system_rpc_runtime_api::ExtrinsicEventsApi<Block, EventRecord> for Runtime {
+		fn query_events(extrinsic_index: u32) -> Vec<EventRecord> {
+			System::extrinsic_events_no_consensus(extrinsic_index)
+		}
+	}
+
+	impl frame_system_rpc_runtime_api::AccountReferencesApi<
+		Block,
+		AccountId,
+		frame_system::AccountReferences,
+	> for Runtime {
+		fn account_references(account: AccountId) -> frame_system::AccountReferences {
+			System::account_references(&account)
+		}
+	}
+
+	impl pallet_vesting::VestingApi<Block, AccountId, BlockNumber> for Runtime {
+		fn vesting_end(who: AccountId) -> Option<BlockNumber> {
+			Vesting::vesting_end(&who)
+		}
+	}
+
 	impl assets_api::AssetsApi<
 		Block,
 		AccountId,
@@ -2685,6 +2756,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(5)]
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
@@ -2701,6 +2773,9 @@ impl_runtime_apis! {
 		fn query_length_to_fee(length: u32) -> Balance {
 			TransactionPayment::length_to_fee(length)
 		}
+		fn query_fee_multiplier_info() -> FeeMultiplierInfo<Balance> {
+			TransactionPayment::query_fee_multiplier_info()
+		}
 	}
 
 	impl pallet_asset_conversion::AssetConversionApi<
@@ -2722,6 +2797,20 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_asset_tx_payment::AssetTxPaymentApi<Block, u32, Balance> for Runtime {
+		fn query_fee_in_asset(
+			uxt: <Block as BlockT>::Extrinsic,
+			len: u32,
+			asset_id: u32,
+		) -> Option<Balance> {
+			let fee = TransactionPayment::query_fee_details(uxt, len).final_fee();
+			<<Runtime as pallet_asset_tx_payment::Config>::OnChargeAssetTransaction>::asset_fee::<
+				Runtime,
+			>(fee, asset_id)
+			.ok()
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, RuntimeCall>
 		for Runtime
 	{
@@ -2739,6 +2828,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl pallet_nfts_runtime_api::NftsApi<Block, AccountId, u32, u32> for Runtime {
 		fn owner(collection: u32, item: u32) -> Option<AccountId> {
 			<Nfts as Inspect<AccountId>>::owner(&collection, &item)
@@ -2781,6 +2871,10 @@ impl_runtime_apis! {
 		fn collection_attribute(collection: u32, key: Vec<u8>) -> Option<Vec<u8>> {
 			<Nfts as Inspect<AccountId>>::collection_attribute(&collection, &key)
 		}
+
+		fn owned_items(account: AccountId) -> Vec<(u32, u32)> {
+			<Nfts as InspectEnumerable<AccountId>>::owned(&account).collect()
+		}
 	}
 
 	#[api_version(3)]
@@ -2887,6 +2981,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl sp_session::SessionKeys<Block> for Runtime {
 		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
 			SessionKeys::generate(seed)
@@ -2897,6 +2992,13 @@ impl_runtime_apis! {
 		) -> Option<Vec<(Vec<u8>, KeyTypeId)>> {
 			SessionKeys::decode_into_raw_public_keys(&encoded)
 		}
+
+		fn generate_ownership_proof(owner: Vec<u8>, keys: Vec<u8>) -> Option<Vec<u8>> {
+			let keys = SessionKeys::decode(&mut &keys[..]).ok()?;
+			<Runtime as pallet_session::Config>::SessionHandler::generate_ownership_proof(
+				&owner, &keys,
+			)
+		}
 	}
 
 	#[cfg(feature = "try-runtime")]
@@ -2964,7 +3066,15 @@ impl_runtime_apis! {
 			use baseline::Pallet as BaselineBench;
 			use pallet_nomination_pools_benchmarking::Pallet as NominationPoolsBench;
 
-			impl pallet_session_benchmarking::Config for Runtime {}
+			impl pallet_session_benchmarking::Config for Runtime {
+				fn generate_session_keys_and_proof(owner: AccountId) -> (SessionKeys, Vec<u8>) {
+					let keys = SessionKeys::decode(&mut &SessionKeys::generate(None)[..])
+						.expect("freshly generated keys can be decoded; qed");
+					let proof = Session::generate_ownership_proof(&owner, &keys)
+						.expect("freshly generated keys are in the keystore; qed");
+					(keys, proof)
+				}
+			}
 			impl pallet_offences_benchmarking::Config for Runtime {}
 			impl pallet_election_provider_support_benchmarking::Config for Runtime {}
 			impl frame_system_benchmarking::Config for Runtime {}
@@ -2987,6 +3097,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl sp_genesis_builder::GenesisBuilder<Block> for Runtime {
 		fn create_default_config() -> Vec<u8> {
 			create_default_config::<RuntimeGenesisConfig>()
@@ -2995,6 +3106,14 @@ impl_runtime_apis! {
 		fn build_config(config: Vec<u8>) -> sp_genesis_builder::Result {
 			build_config::<RuntimeGenesisConfig>(config)
 		}
+
+		fn get_preset(id: Option<sp_genesis_builder::PresetId>) -> Option<Vec<u8>> {
+			get_preset::<RuntimeGenesisConfig>(&id, genesis_config_presets::get_preset)
+		}
+
+		fn preset_names() -> Vec<sp_genesis_builder::PresetId> {
+			genesis_config_presets::preset_names()
+		}
 	}
 }
 
@@ -0,0 +1,34 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Execution overhead weights of the runtime.
+//!
+//! These files are generated by `benchmark overhead` and should be regenerated on the hardware
+//! the chain is expected to run on:
+//!
+//! ```sh
+//! substrate benchmark overhead --chain=dev --wasm-execution=compiled \
+//!   --weight-path=./bin/node/runtime/src/weights/ --header=./HEADER-APACHE2
+//! ```
+//!
+//! Until then they contain the values shipped with `frame-support`.
+
+pub mod block_weights;
+pub mod extrinsic_weights;
+
+pub use block_weights::BlockExecutionWeight;
+pub use extrinsic_weights::ExtrinsicBaseWeight;
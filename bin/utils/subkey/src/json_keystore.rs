@@ -0,0 +1,514 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Export and import of keys as password protected JSON files, in the format used by polkadot-js.
+//!
+//! The secret key is stored as PKCS#8, encrypted with xsalsa20-poly1305 using a key derived from
+//! the password with scrypt.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use crypto_secretbox::{
+	aead::{Aead, KeyInit},
+	Key, Nonce, XSalsa20Poly1305,
+};
+use rand::RngCore;
+use sc_cli::{
+	clap::Parser,
+	utils::{self, print_from_public, print_from_uri},
+	with_crypto_scheme, CryptoScheme, CryptoSchemeFlag, Error, KeystoreParams, NetworkSchemeFlag,
+	OutputTypeFlag,
+};
+use sc_keystore::LocalKeystore;
+use serde::{Deserialize, Serialize};
+use sp_core::crypto::{
+	unwrap_or_default_ss58_version, KeyTypeId, Pair, SecretString, Ss58AddressFormat, Ss58Codec,
+};
+use sp_keystore::Keystore;
+use sp_runtime::{traits::IdentifyAccount, MultiSigner};
+use std::{
+	path::PathBuf,
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+const PKCS8_HEADER: [u8; 16] = [48, 83, 2, 1, 1, 48, 5, 6, 3, 43, 101, 112, 4, 34, 4, 32];
+const PKCS8_DIVIDER: [u8; 5] = [161, 35, 3, 33, 0];
+
+const SALT_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 24;
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_P: u32 = 1;
+const SCRYPT_R: u32 = 8;
+/// Largest scrypt `N` accepted on import. `N * p` is bounded by it as well, which admits the
+/// parameters polkadot-js uses while bounding the time and memory spent on a key file.
+const MAX_SCRYPT_N: u32 = 1 << 17;
+/// Largest scrypt `r` accepted on import.
+const MAX_SCRYPT_R: u32 = 8;
+
+const ENCODING_VERSION: &str = "3";
+const ENCODING_TYPE: [&str; 2] = ["scrypt", "xsalsa20-poly1305"];
+
+/// The `export` command
+#[derive(Debug, Clone, Parser)]
+#[command(name = "export", about = "Export a key as password protected JSON file")]
+pub struct ExportCmd {
+	/// The secret key URI.
+	/// If the value is a file, the file content is used as URI.
+	/// If not given, you will be prompted for the URI.
+	#[arg(long)]
+	suri: Option<String>,
+
+	/// Name of the account stored in the file.
+	#[arg(long)]
+	name: Option<String>,
+
+	/// Password used to encrypt the file. If not given, you will be prompted for it.
+	#[arg(long)]
+	json_password: Option<String>,
+
+	/// Write the file to the given path instead of stdout.
+	#[arg(long, value_name = "PATH")]
+	output: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub network_scheme: NetworkSchemeFlag,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub crypto_scheme: CryptoSchemeFlag,
+}
+
+impl ExportCmd {
+	/// Run the command
+	pub fn run(&self) -> Result<(), Error> {
+		let suri = utils::read_uri(self.suri.as_ref())?;
+		let password = self.keystore_params.read_password()?;
+		let json_password = match &self.json_password {
+			Some(password) => password.clone(),
+			None => {
+				let password = rpassword::prompt_password("JSON password: ")?;
+				if password != rpassword::prompt_password("Repeat JSON password: ")? {
+					return Err("Passwords do not match".into())
+				}
+				password
+			},
+		};
+
+		let scheme = self.crypto_scheme.scheme;
+		let file = with_crypto_scheme!(
+			scheme,
+			export_key(
+				scheme,
+				&suri,
+				password,
+				self.network_scheme.network,
+				self.name.clone(),
+				json_password.as_bytes(),
+			)
+		)?;
+
+		let file = serde_json::to_string_pretty(&file).expect("Json pretty print failed");
+		match &self.output {
+			Some(path) => std::fs::write(path, file)?,
+			None => println!("{}", file),
+		}
+
+		Ok(())
+	}
+}
+
+/// The `import` command
+#[derive(Debug, Clone, Parser)]
+#[command(name = "import", about = "Import a key from a password protected JSON file")]
+pub struct ImportCmd {
+	/// The JSON file to import.
+	#[arg(value_name = "PATH")]
+	file: PathBuf,
+
+	/// Password used to decrypt the file. If not given, you will be prompted for it.
+	#[arg(long)]
+	json_password: Option<String>,
+
+	/// Insert the key into the keystore at the given path, e.g. the keystore of a node.
+	#[arg(long, value_name = "PATH", requires = "key_type")]
+	keystore_path: Option<PathBuf>,
+
+	/// Key type the key is inserted as, examples: "gran", or "imon".
+	#[arg(long, requires = "keystore_path")]
+	key_type: Option<String>,
+
+	/// Print the secret seed of the imported key.
+	#[arg(long)]
+	print_secret: bool,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub network_scheme: NetworkSchemeFlag,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub output_scheme: OutputTypeFlag,
+}
+
+impl ImportCmd {
+	/// Run the command
+	pub fn run(&self) -> Result<(), Error> {
+		let file: KeyFile = serde_json::from_slice(&std::fs::read(&self.file)?)
+			.map_err(|e| format!("Invalid key file: {}", e))?;
+		let json_password = match &self.json_password {
+			Some(password) => password.clone(),
+			None => rpassword::prompt_password("JSON password: ")?,
+		};
+
+		let (scheme, suri, public) = import_key(&file, json_password.as_bytes())?;
+
+		if let (Some(path), Some(key_type)) = (&self.keystore_path, &self.key_type) {
+			let key_type =
+				KeyTypeId::try_from(key_type.as_str()).map_err(|_| Error::KeyTypeInvalid)?;
+			LocalKeystore::open(path, None)?
+				.insert(key_type, &suri, &public)
+				.map_err(|_| Error::KeystoreOperation)?;
+		}
+
+		if self.print_secret {
+			with_crypto_scheme!(
+				scheme,
+				print_from_uri(
+					&suri,
+					None,
+					self.network_scheme.network,
+					self.output_scheme.output_type,
+				)
+			);
+		} else {
+			with_crypto_scheme!(
+				scheme,
+				print_from_public(
+					&array_bytes::bytes2hex("0x", &public),
+					self.network_scheme.network,
+					self.output_scheme.output_type,
+				)
+			)?;
+		}
+
+		Ok(())
+	}
+}
+
+/// A password protected key, as stored by polkadot-js.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyFile {
+	encoded: String,
+	encoding: Encoding,
+	address: String,
+	#[serde(default)]
+	meta: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Encoding {
+	content: Vec<String>,
+	#[serde(rename = "type")]
+	ty: Vec<String>,
+	version: String,
+}
+
+fn scheme_name(scheme: CryptoScheme) -> &'static str {
+	match scheme {
+		CryptoScheme::Sr25519 => "sr25519",
+		CryptoScheme::Ed25519 => "ed25519",
+		CryptoScheme::Ecdsa => "ecdsa",
+	}
+}
+
+fn scheme_from_name(name: &str) -> Result<CryptoScheme, Error> {
+	match name {
+		"sr25519" => Ok(CryptoScheme::Sr25519),
+		"ed25519" => Ok(CryptoScheme::Ed25519),
+		"ecdsa" => Ok(CryptoScheme::Ecdsa),
+		other => Err(format!("Unsupported key type {}", other).into()),
+	}
+}
+
+fn public_length(scheme: CryptoScheme) -> usize {
+	match scheme {
+		CryptoScheme::Sr25519 | CryptoScheme::Ed25519 => 32,
+		CryptoScheme::Ecdsa => 33,
+	}
+}
+
+/// Encrypt the key given by `suri` into a [`KeyFile`].
+fn export_key<P: Pair>(
+	scheme: CryptoScheme,
+	suri: &str,
+	password: Option<SecretString>,
+	network: Option<Ss58AddressFormat>,
+	name: Option<String>,
+	json_password: &[u8],
+) -> Result<KeyFile, Error>
+where
+	P::Public: Into<MultiSigner>,
+{
+	let pair = utils::pair_from_suri::<P>(suri, password)?;
+	let public = pair.public();
+	let raw = pair.to_raw_vec();
+
+	// polkadot-js stores sr25519 keys in their ed25519 compatible form and ed25519 keys as
+	// seed followed by public key.
+	let secret = match scheme {
+		CryptoScheme::Sr25519 => schnorrkel::SecretKey::from_bytes(&raw)
+			.map_err(|e| format!("Invalid sr25519 secret key: {}", e))?
+			.to_ed25519_bytes()
+			.to_vec(),
+		CryptoScheme::Ed25519 => [&raw[..], public.as_ref()].concat(),
+		CryptoScheme::Ecdsa => raw,
+	};
+
+	let pkcs8 = [&PKCS8_HEADER[..], &secret[..], &PKCS8_DIVIDER[..], public.as_ref()].concat();
+	let when_created = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|d| d.as_millis() as u64)
+		.unwrap_or_default();
+
+	Ok(KeyFile {
+		encoded: BASE64.encode(encrypt(&pkcs8, json_password)?),
+		encoding: Encoding {
+			content: vec!["pkcs8".into(), scheme_name(scheme).into()],
+			ty: ENCODING_TYPE.iter().map(|t| t.to_string()).collect(),
+			version: ENCODING_VERSION.into(),
+		},
+		address: Into::<MultiSigner>::into(public)
+			.into_account()
+			.to_ss58check_with_version(unwrap_or_default_ss58_version(network)),
+		meta: serde_json::json!({
+			"name": name.unwrap_or_default(),
+			"whenCreated": when_created,
+		}),
+	})
+}
+
+/// Decrypt `file`, returning the crypto scheme, a secret URI and the public key of the key.
+fn import_key(
+	file: &KeyFile,
+	json_password: &[u8],
+) -> Result<(CryptoScheme, String, Vec<u8>), Error> {
+	if file.encoding.version != ENCODING_VERSION ||
+		!ENCODING_TYPE.iter().all(|t| file.encoding.ty.iter().any(|ty| ty == t))
+	{
+		return Err(format!(
+			"Unsupported encoding {:?}, version {}",
+			file.encoding.ty, file.encoding.version
+		)
+		.into())
+	}
+	let scheme = match &file.encoding.content[..] {
+		[pkcs8, scheme] if pkcs8 == "pkcs8" => scheme_from_name(scheme)?,
+		content => return Err(format!("Unsupported content {:?}", content).into()),
+	};
+
+	let encoded = BASE64.decode(&file.encoded).map_err(|e| format!("Invalid encoding: {}", e))?;
+	let pkcs8 = decrypt(&encoded, json_password)?;
+
+	let public_len = public_length(scheme);
+	let secret_end = pkcs8
+		.len()
+		.checked_sub(PKCS8_DIVIDER.len() + public_len)
+		.filter(|end| *end > PKCS8_HEADER.len())
+		.ok_or("Invalid PKCS#8 key")?;
+	if pkcs8[..PKCS8_HEADER.len()] != PKCS8_HEADER ||
+		pkcs8[secret_end..secret_end + PKCS8_DIVIDER.len()] != PKCS8_DIVIDER
+	{
+		return Err("Invalid PKCS#8 key".into())
+	}
+	let secret = &pkcs8[PKCS8_HEADER.len()..secret_end];
+	let public = pkcs8[secret_end + PKCS8_DIVIDER.len()..].to_vec();
+
+	let seed = match (scheme, secret.len()) {
+		(CryptoScheme::Sr25519, 64) => schnorrkel::SecretKey::from_ed25519_bytes(secret)
+			.map_err(|e| format!("Invalid sr25519 secret key: {}", e))?
+			.to_bytes()
+			.to_vec(),
+		(CryptoScheme::Ed25519, 64) | (CryptoScheme::Ecdsa, 32) => secret[..32].to_vec(),
+		_ => return Err("Invalid secret key length".into()),
+	};
+	let suri = array_bytes::bytes2hex("0x", seed);
+
+	if with_crypto_scheme!(scheme, public_from_suri(&suri))? != public {
+		return Err("Secret key does not match the public key".into())
+	}
+
+	Ok((scheme, suri, public))
+}
+
+fn public_from_suri<P: Pair>(suri: &str) -> Result<Vec<u8>, Error> {
+	Ok(utils::pair_from_suri::<P>(suri, None)?.public().as_ref().to_vec())
+}
+
+/// Derive the encryption key from `password`.
+fn derive_key(password: &[u8], salt: &[u8], log_n: u8, p: u32, r: u32) -> Result<Key, Error> {
+	let params = scrypt::Params::new(log_n, r, p, 64)
+		.map_err(|e| format!("Invalid scrypt params: {}", e))?;
+	let mut key = [0u8; 64];
+	scrypt::scrypt(password, salt, &params, &mut key).map_err(|e| format!("scrypt: {}", e))?;
+	Ok(Key::clone_from_slice(&key[..32]))
+}
+
+/// Encrypt `data`, prefixing it with the scrypt parameters and the nonce.
+fn encrypt(data: &[u8], password: &[u8]) -> Result<Vec<u8>, Error> {
+	let mut salt = [0u8; SALT_LENGTH];
+	let mut nonce = [0u8; NONCE_LENGTH];
+	rand::thread_rng().fill_bytes(&mut salt);
+	rand::thread_rng().fill_bytes(&mut nonce);
+
+	let key = derive_key(password, &salt, SCRYPT_LOG_N, SCRYPT_P, SCRYPT_R)?;
+	let encrypted = XSalsa20Poly1305::new(&key)
+		.encrypt(Nonce::from_slice(&nonce), data)
+		.map_err(|_| "Encryption failed")?;
+
+	Ok([
+		&salt[..],
+		&(1u32 << SCRYPT_LOG_N).to_le_bytes()[..],
+		&SCRYPT_P.to_le_bytes()[..],
+		&SCRYPT_R.to_le_bytes()[..],
+		&nonce[..],
+		&encrypted[..],
+	]
+	.concat())
+}
+
+/// Decrypt data created by [`encrypt`].
+fn decrypt(data: &[u8], password: &[u8]) -> Result<Vec<u8>, Error> {
+	const HEADER_LENGTH: usize = SALT_LENGTH + 12 + NONCE_LENGTH;
+	if data.len() <= HEADER_LENGTH {
+		return Err("Encrypted data is too short".into())
+	}
+
+	let param = |i: usize| {
+		let start = SALT_LENGTH + i * 4;
+		u32::from_le_bytes(data[start..start + 4].try_into().expect("4 bytes; qed"))
+	};
+	let (n, p, r) = (param(0), param(1), param(2));
+	let too_expensive = n > MAX_SCRYPT_N ||
+		u64::from(n) * u64::from(p) > u64::from(MAX_SCRYPT_N) ||
+		r > MAX_SCRYPT_R;
+	if !n.is_power_of_two() || p == 0 || r == 0 || too_expensive {
+		return Err(format!("Unsupported scrypt parameters N = {}, p = {}, r = {}", n, p, r).into())
+	}
+
+	let key = derive_key(password, &data[..SALT_LENGTH], n.trailing_zeros() as u8, p, r)?;
+	XSalsa20Poly1305::new(&key)
+		.decrypt(Nonce::from_slice(&data[SALT_LENGTH + 12..HEADER_LENGTH]), &data[HEADER_LENGTH..])
+		.map_err(|_| "Invalid password".into())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn roundtrip(scheme: CryptoScheme, suri: &str) {
+		let file = with_crypto_scheme!(
+			scheme,
+			export_key(scheme, suri, None, None, Some("test".into()), b"password")
+		)
+		.unwrap();
+		assert_eq!(file.encoding.content, vec!["pkcs8".to_string(), scheme_name(scheme).into()]);
+
+		let (imported, seed, public) = import_key(&file, b"password").unwrap();
+		assert_eq!(imported, scheme);
+		assert_eq!(public, with_crypto_scheme!(scheme, public_from_suri(suri)).unwrap());
+		assert_eq!(with_crypto_scheme!(scheme, public_from_suri(&seed)).unwrap(), public);
+
+		assert!(import_key(&file, b"wrong").is_err());
+	}
+
+	#[test]
+	fn export_import_roundtrip() {
+		roundtrip(CryptoScheme::Sr25519, "//Alice");
+		roundtrip(CryptoScheme::Ed25519, "//Alice");
+		roundtrip(CryptoScheme::Ecdsa, "//Alice");
+	}
+
+	#[test]
+	fn imports_polkadot_js_key_file() {
+		// The ed25519 key of the RFC 8032 test vector 1, encrypted with the polkadot-js defaults.
+		let file: KeyFile =
+			serde_json::from_slice(include_bytes!("../res/ed25519_key.json")).unwrap();
+
+		let (scheme, seed, public) = import_key(&file, b"correct horse battery staple").unwrap();
+		assert_eq!(scheme, CryptoScheme::Ed25519);
+		assert_eq!(
+			seed,
+			"0x9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60".to_string()
+		);
+		assert_eq!(
+			array_bytes::bytes2hex("", &public),
+			"d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+		);
+		let address = sp_core::ed25519::Public::try_from(&public[..]).unwrap().to_ss58check();
+		assert_eq!(address, file.address);
+
+		assert!(import_key(&file, b"wrong").is_err());
+	}
+
+	#[test]
+	fn rejects_expensive_scrypt_params() {
+		let mut file = export_key::<sp_core::ed25519::Pair>(
+			CryptoScheme::Ed25519,
+			"//Bob",
+			None,
+			None,
+			None,
+			b"password",
+		)
+		.unwrap();
+		let encoded = BASE64.decode(&file.encoded).unwrap();
+
+		for (n, p, r) in
+			[(1u32 << 20, 1u32, 8u32), (1 << 15, 8, 8), (1 << 15, 1, 16), (1 << 15, 0, 8)]
+		{
+			let params = [n.to_le_bytes(), p.to_le_bytes(), r.to_le_bytes()].concat();
+			let mut tampered = encoded.clone();
+			tampered[SALT_LENGTH..SALT_LENGTH + 12].copy_from_slice(&params);
+			file.encoded = BASE64.encode(&tampered);
+
+			let err = import_key(&file, b"password").unwrap_err();
+			assert!(err.to_string().contains("Unsupported scrypt parameters"), "{}", err);
+		}
+	}
+
+	#[test]
+	fn rejects_unsupported_encoding() {
+		let mut file = export_key::<sp_core::sr25519::Pair>(
+			CryptoScheme::Sr25519,
+			"//Bob",
+			None,
+			None,
+			None,
+			b"password",
+		)
+		.unwrap();
+		file.encoding.version = "2".into();
+
+		assert!(import_key(&file, b"password").is_err());
+	}
+}
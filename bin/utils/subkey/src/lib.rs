@@ -278,6 +278,36 @@
 //! Error: SignatureInvalid
 //! ```
 //!
+//! ### Constructing transactions
+//!
+//! `subkey` can build and sign a transaction offline for any runtime, given its metadata (as
+//! returned by `state_getMetadata`). The call is written as JSON, and can be wrapped into
+//! `Proxy::proxy` and `Multisig::as_multi` calls on the way:
+//!
+//! ```bash
+//! subkey tx --metadata metadata.scale --genesis-hash $GENESIS_HASH --nonce 4 --suri $URI \
+//!   --proxy 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY \
+//!   --call '{"Balances": {"transfer_keep_alive": {"dest": {"Id": "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty"}, "value": 1000}}}'
+//! ```
+//!
+//! The output is the hex encoded signed transaction, ready to be submitted with
+//! `author_submitExtrinsic`. Transactions are immortal. The spec and transaction version are taken
+//! from the metadata, unless given with `--spec-version` and `--transaction-version`.
+//!
+//! ### Exporting and importing JSON key files
+//!
+//! Keys can be moved between `subkey`, the keystore of a node and browser wallets such as
+//! polkadot-js without exposing the secret seed, using password protected JSON files:
+//!
+//! ```bash
+//! subkey export --suri "$URI" --name alice --output alice.json
+//! subkey import alice.json --keystore-path /path/to/node/keystore --key-type aura
+//! ```
+//!
+//! The secret key is encrypted with xsalsa20-poly1305, using a key derived from the password with
+//! scrypt. The password is prompted for, unless given with `--json-password`. `import` only prints
+//! the public key of the imported key, unless `--print-secret` is given.
+//!
 //! ### Using the vanity generator
 //!
 //! You can use the included vanity generator to find a seed that provides an address which includes
@@ -314,6 +344,12 @@ use sc_cli::{
 	VerifyCmd,
 };
 
+mod json_keystore;
+mod tx;
+
+pub use json_keystore::{ExportCmd, ImportCmd};
+pub use tx::TxCmd;
+
 #[derive(Debug, Parser)]
 #[command(
 	name = "subkey",
@@ -343,6 +379,15 @@ pub enum Subkey {
 
 	/// Verify a signature for a message, provided on STDIN, with a given (public or secret) key.
 	Verify(VerifyCmd),
+
+	/// Construct and sign a transaction offline
+	Tx(TxCmd),
+
+	/// Export a key as password protected JSON file, compatible with polkadot-js
+	Export(ExportCmd),
+
+	/// Import a key from a password protected JSON file, e.g. exported by polkadot-js
+	Import(ImportCmd),
 }
 
 /// Run the subkey command, given the appropriate runtime.
@@ -355,5 +400,8 @@ pub fn run() -> Result<(), Error> {
 		Subkey::Vanity(cmd) => cmd.run(),
 		Subkey::Verify(cmd) => cmd.run(),
 		Subkey::Sign(cmd) => cmd.run(),
+		Subkey::Tx(cmd) => cmd.run(),
+		Subkey::Export(cmd) => cmd.run(),
+		Subkey::Import(cmd) => cmd.run(),
 	}
 }
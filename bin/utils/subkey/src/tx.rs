@@ -0,0 +1,819 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Offline construction of signed transactions for any runtime, driven by its metadata.
+
+use codec::{Compact, Decode, Encode};
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use sc_cli::{clap::Parser, utils, with_crypto_scheme, CryptoSchemeFlag, Error, KeystoreParams};
+use scale_info::{
+	form::PortableForm, Field, PortableRegistry, Type, TypeDef, TypeDefPrimitive, TypeDefVariant,
+};
+use serde_json::{json, Value};
+use sp_core::crypto::{AccountId32, Pair, SecretString, Ss58Codec};
+use sp_runtime::{traits::IdentifyAccount, MultiSignature, MultiSigner};
+use std::path::PathBuf;
+
+/// The `tx` command
+#[derive(Debug, Clone, Parser)]
+#[command(
+	name = "tx",
+	about = "Construct and sign a transaction offline, using the metadata of the target runtime"
+)]
+pub struct TxCmd {
+	/// File with the SCALE encoded runtime metadata, as returned by `state_getMetadata`.
+	///
+	/// Both raw bytes and hex are accepted.
+	#[arg(long, value_name = "PATH")]
+	metadata: PathBuf,
+
+	/// The call as JSON, e.g. `{"System": {"remark": {"remark": "0x1234"}}}`.
+	///
+	/// Enum values are written as `"Variant"` or `{"Variant": fields}`, byte sequences as hex and
+	/// accounts as SS58 addresses or hex.
+	#[arg(long)]
+	call: String,
+
+	/// The secret key URI of the signer.
+	/// If the value is a file, the file content is used as URI.
+	/// If not given, you will be prompted for the URI.
+	#[arg(long)]
+	suri: Option<String>,
+
+	/// Nonce of the signing account.
+	#[arg(long)]
+	nonce: u64,
+
+	/// Tip for the block author.
+	#[arg(long, default_value_t = 0)]
+	tip: u128,
+
+	/// Hash of the genesis block of the target chain.
+	#[arg(long, value_name = "HASH")]
+	genesis_hash: String,
+
+	/// Spec version of the runtime. Defaults to the version found in the metadata.
+	#[arg(long)]
+	spec_version: Option<u32>,
+
+	/// Transaction version of the runtime. Defaults to the version found in the metadata.
+	#[arg(long)]
+	transaction_version: Option<u32>,
+
+	/// Dispatch the call through `Proxy::proxy` on behalf of the given account.
+	///
+	/// Can be given multiple times to nest proxies, the first account is the outermost one.
+	#[arg(long, value_name = "ACCOUNT")]
+	proxy: Vec<String>,
+
+	/// Dispatch the call through `Multisig::as_multi` with the given threshold.
+	#[arg(long, value_name = "THRESHOLD", requires = "multisig_signatory")]
+	multisig_threshold: Option<u16>,
+
+	/// The other signatories of the multisig account.
+	#[arg(long, value_name = "ACCOUNT")]
+	multisig_signatory: Vec<String>,
+
+	/// Timepoint `HEIGHT:INDEX` of the first approval of the multisig operation.
+	#[arg(long, value_name = "HEIGHT:INDEX")]
+	multisig_timepoint: Option<String>,
+
+	/// Maximum weight `REF_TIME:PROOF_SIZE` the multisig may use to dispatch the call.
+	#[arg(long, value_name = "REF_TIME:PROOF_SIZE")]
+	max_weight: Option<String>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub crypto_scheme: CryptoSchemeFlag,
+}
+
+impl TxCmd {
+	/// Run the command
+	pub fn run(&self) -> Result<(), Error> {
+		println!("{}", self.build()?);
+		Ok(())
+	}
+
+	/// Build the hex encoded signed transaction.
+	fn build(&self) -> Result<String, Error> {
+		let metadata = Metadata::from_bytes(&read_bytes(&std::fs::read(&self.metadata)?)?)?;
+		let call: Value =
+			serde_json::from_str(&self.call).map_err(|e| format!("Invalid call: {}", e))?;
+		let call = self.wrap_call(call)?;
+
+		let (spec_version, transaction_version) = match metadata.runtime_version() {
+			Some((spec, tx)) =>
+				(self.spec_version.unwrap_or(spec), self.transaction_version.unwrap_or(tx)),
+			None => (
+				self.spec_version.ok_or("Missing --spec-version")?,
+				self.transaction_version.ok_or("Missing --transaction-version")?,
+			),
+		};
+		let chain = ChainParams {
+			nonce: self.nonce,
+			tip: self.tip,
+			genesis_hash: self.genesis_hash.clone(),
+			spec_version,
+			transaction_version,
+		};
+
+		let suri = utils::read_uri(self.suri.as_ref())?;
+		let password = self.keystore_params.read_password()?;
+		let xt = with_crypto_scheme!(
+			self.crypto_scheme.scheme,
+			sign_transaction(&metadata, &call, &chain, &suri, password)
+		)?;
+
+		Ok(array_bytes::bytes2hex("0x", xt))
+	}
+
+	/// Wrap `call` into the requested proxy and multisig calls.
+	fn wrap_call(&self, mut call: Value) -> Result<Value, Error> {
+		for real in self.proxy.iter().rev() {
+			call = json!({ "Proxy": { "proxy": {
+				"real": { "Id": account_hex(real)? },
+				"force_proxy_type": null,
+				"call": call,
+			}}});
+		}
+
+		if let Some(threshold) = self.multisig_threshold {
+			let mut signatories = self
+				.multisig_signatory
+				.iter()
+				.map(|a| parse_account(a))
+				.collect::<Result<Vec<_>, _>>()?;
+			signatories.sort();
+			let signatories =
+				signatories.iter().map(|a| array_bytes::bytes2hex("0x", a)).collect::<Vec<_>>();
+
+			call = if threshold == 1 {
+				json!({ "Multisig": { "as_multi_threshold_1": {
+					"other_signatories": signatories,
+					"call": call,
+				}}})
+			} else {
+				let timepoint = self
+					.multisig_timepoint
+					.as_deref()
+					.map(|t| {
+						parse_pair(t)
+							.map(|(height, index)| json!({ "height": height, "index": index }))
+					})
+					.transpose()?;
+				let (ref_time, proof_size) =
+					self.max_weight.as_deref().map(parse_pair).transpose()?.unwrap_or((0, 0));
+				json!({ "Multisig": { "as_multi": {
+					"threshold": threshold,
+					"other_signatories": signatories,
+					"maybe_timepoint": timepoint,
+					"call": call,
+					"max_weight": { "ref_time": ref_time, "proof_size": proof_size },
+				}}})
+			};
+		}
+
+		Ok(call)
+	}
+}
+
+/// Chain specific values that go into the signed extensions.
+struct ChainParams {
+	nonce: u64,
+	tip: u128,
+	genesis_hash: String,
+	spec_version: u32,
+	transaction_version: u32,
+}
+
+/// Sign `call` and return the SCALE encoded extrinsic.
+///
+/// Transactions are immortal, so they can be submitted at any time.
+fn sign_transaction<P: Pair>(
+	metadata: &Metadata,
+	call: &Value,
+	chain: &ChainParams,
+	suri: &str,
+	password: Option<SecretString>,
+) -> Result<Vec<u8>, Error>
+where
+	P::Public: Into<MultiSigner>,
+	P::Signature: Into<MultiSignature>,
+{
+	let encoder = Encoder(&metadata.types);
+	let call = encoder
+		.encode(metadata.call_ty, call)
+		.map_err(|e| format!("Invalid call: {}", e))?;
+
+	let mut extra = Vec::new();
+	let mut additional = Vec::new();
+	for (identifier, extra_ty, additional_ty) in &metadata.extensions {
+		let mut extra_value = encoder.default_value(*extra_ty)?;
+		let mut additional_value = encoder.default_value(*additional_ty)?;
+		match identifier.as_str() {
+			"CheckNonce" => extra_value = json!(chain.nonce),
+			"CheckSpecVersion" => additional_value = json!(chain.spec_version),
+			"CheckTxVersion" => additional_value = json!(chain.transaction_version),
+			"CheckGenesis" | "CheckMortality" | "CheckEra" =>
+				additional_value = json!(chain.genesis_hash),
+			"ChargeTransactionPayment" | "ChargeAssetTxPayment" =>
+				if !set_tip(&mut extra_value, chain.tip) {
+					extra_value = json!(chain.tip.to_string());
+				},
+			_ => {},
+		}
+		let context = |e| format!("Signed extension {}: {}", identifier, e);
+		extra.extend(encoder.encode(*extra_ty, &extra_value).map_err(context)?);
+		additional.extend(encoder.encode(*additional_ty, &additional_value).map_err(context)?);
+	}
+
+	let pair = utils::pair_from_suri::<P>(suri, password)?;
+	let payload = [&call[..], &extra, &additional].concat();
+	let signature: MultiSignature = if payload.len() > 256 {
+		pair.sign(&sp_crypto_hashing::blake2_256(&payload))
+	} else {
+		pair.sign(&payload)
+	}
+	.into();
+
+	let signer: MultiSigner = pair.public().into();
+	let account = array_bytes::bytes2hex("0x", signer.into_account());
+	let address = match metadata.types.resolve(metadata.address_ty).map(|t| &t.type_def) {
+		Some(TypeDef::Variant(_)) => json!({ "Id": account }),
+		_ => json!(account),
+	};
+	let (scheme, signature) = match signature {
+		MultiSignature::Sr25519(s) => ("Sr25519", AsRef::<[u8]>::as_ref(&s).to_vec()),
+		MultiSignature::Ed25519(s) => ("Ed25519", AsRef::<[u8]>::as_ref(&s).to_vec()),
+		MultiSignature::Ecdsa(s) => ("Ecdsa", AsRef::<[u8]>::as_ref(&s).to_vec()),
+	};
+	let signature = array_bytes::bytes2hex("0x", signature);
+	let signature = match metadata.types.resolve(metadata.signature_ty).map(|t| &t.type_def) {
+		Some(TypeDef::Variant(_)) => json!({ scheme: signature }),
+		_ => json!(signature),
+	};
+
+	let mut xt = vec![0b1000_0000 | metadata.version];
+	xt.extend(encoder.encode(metadata.address_ty, &address)?);
+	xt.extend(encoder.encode(metadata.signature_ty, &signature)?);
+	xt.extend(extra);
+	xt.extend(call);
+
+	Ok(xt.encode())
+}
+
+/// Set the first `tip` field found in `value`.
+fn set_tip(value: &mut Value, tip: u128) -> bool {
+	match value {
+		Value::Object(fields) => {
+			if let Some(field) = fields.get_mut("tip") {
+				*field = json!(tip.to_string());
+				return true
+			}
+			fields.values_mut().any(|v| set_tip(v, tip))
+		},
+		Value::Array(items) => items.iter_mut().any(|v| set_tip(v, tip)),
+		_ => false,
+	}
+}
+
+/// Parse hex, with or without `0x` prefix, falling back to raw bytes.
+fn read_bytes(data: &[u8]) -> Result<Vec<u8>, Error> {
+	match std::str::from_utf8(data) {
+		Ok(hex) => array_bytes::hex2bytes(hex.trim())
+			.map_err(|_| Error::Input("Metadata is neither hex nor SCALE encoded".into())),
+		Err(_) => Ok(data.to_vec()),
+	}
+}
+
+fn parse_account(account: &str) -> Result<[u8; 32], Error> {
+	if account.starts_with("0x") {
+		array_bytes::hex2array(account).map_err(|_| format!("Invalid account {}", account).into())
+	} else {
+		AccountId32::from_ss58check(account)
+			.map(Into::into)
+			.map_err(|e| format!("Invalid account {}: {:?}", account, e).into())
+	}
+}
+
+fn account_hex(account: &str) -> Result<String, Error> {
+	Ok(array_bytes::bytes2hex("0x", parse_account(account)?))
+}
+
+fn parse_pair(pair: &str) -> Result<(u64, u64), Error> {
+	let invalid = || Error::Input(format!("Expected `A:B`, got `{}`", pair));
+	let (a, b) = pair.split_once(':').ok_or_else(invalid)?;
+	Ok((a.parse().map_err(|_| invalid())?, b.parse().map_err(|_| invalid())?))
+}
+
+/// The parts of the runtime metadata needed to build transactions.
+struct Metadata {
+	types: PortableRegistry,
+	version: u8,
+	address_ty: u32,
+	call_ty: u32,
+	signature_ty: u32,
+	/// Identifier, extra type and additional signed type of each signed extension.
+	extensions: Vec<(String, u32, u32)>,
+	/// The `System::Version` constant.
+	runtime_version: Option<Vec<u8>>,
+}
+
+impl Metadata {
+	fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &bytes[..])
+			.map_err(|e| format!("Invalid metadata: {}", e))?;
+
+		let system_version = |pallets: Vec<(String, Vec<(String, Vec<u8>)>)>| {
+			pallets
+				.into_iter()
+				.find(|(name, _)| name == "System")
+				.and_then(|(_, constants)| {
+					constants.into_iter().find(|(name, _)| name == "Version")
+				})
+				.map(|(_, value)| value)
+		};
+
+		match metadata.1 {
+			RuntimeMetadata::V14(m) => {
+				let extrinsic_ty =
+					m.types.resolve(m.extrinsic.ty.id).ok_or("Unknown extrinsic type")?;
+				let param = |name: &str| -> Result<u32, Error> {
+					extrinsic_ty
+						.type_params
+						.iter()
+						.find(|p| p.name == name)
+						.and_then(|p| p.ty)
+						.map(|ty| ty.id)
+						.ok_or_else(|| format!("Missing extrinsic type parameter {}", name).into())
+				};
+				let (address_ty, call_ty, signature_ty) =
+					(param("Address")?, param("Call")?, param("Signature")?);
+				let pallets = m
+					.pallets
+					.into_iter()
+					.map(|p| (p.name, p.constants.into_iter().map(|c| (c.name, c.value)).collect()))
+					.collect();
+
+				Ok(Self {
+					version: m.extrinsic.version,
+					address_ty,
+					call_ty,
+					signature_ty,
+					extensions: m
+						.extrinsic
+						.signed_extensions
+						.into_iter()
+						.map(|e| (e.identifier, e.ty.id, e.additional_signed.id))
+						.collect(),
+					runtime_version: system_version(pallets),
+					types: m.types,
+				})
+			},
+			RuntimeMetadata::V15(m) => {
+				let pallets = m
+					.pallets
+					.into_iter()
+					.map(|p| (p.name, p.constants.into_iter().map(|c| (c.name, c.value)).collect()))
+					.collect();
+
+				Ok(Self {
+					version: m.extrinsic.version,
+					address_ty: m.extrinsic.address_ty.id,
+					call_ty: m.extrinsic.call_ty.id,
+					signature_ty: m.extrinsic.signature_ty.id,
+					extensions: m
+						.extrinsic
+						.signed_extensions
+						.into_iter()
+						.map(|e| (e.identifier, e.ty.id, e.additional_signed.id))
+						.collect(),
+					runtime_version: system_version(pallets),
+					types: m.types,
+				})
+			},
+			_ => Err("Only metadata V14 and V15 are supported".into()),
+		}
+	}
+
+	/// Spec and transaction version of the runtime.
+	fn runtime_version(&self) -> Option<(u32, u32)> {
+		sc_cli::RuntimeVersion::decode(&mut &self.runtime_version.as_ref()?[..])
+			.ok()
+			.map(|v| (v.spec_version, v.transaction_version))
+	}
+}
+
+/// Encodes JSON values according to the types of a [`PortableRegistry`].
+struct Encoder<'a>(&'a PortableRegistry);
+
+impl<'a> Encoder<'a> {
+	fn resolve(&self, ty: u32) -> Result<&'a Type<PortableForm>, String> {
+		self.0.resolve(ty).ok_or_else(|| format!("Unknown type {}", ty))
+	}
+
+	fn encode(&self, ty: u32, value: &Value) -> Result<Vec<u8>, String> {
+		let mut out = Vec::new();
+		self.encode_to(ty, value, &mut out)?;
+		Ok(out)
+	}
+
+	fn encode_to(&self, ty: u32, value: &Value, out: &mut Vec<u8>) -> Result<(), String> {
+		let ty = self.resolve(ty)?;
+		match &ty.type_def {
+			TypeDef::Composite(composite) => {
+				if ty.path.segments.last().map_or(false, |s| s == "AccountId32") {
+					if let Some(account) = value.as_str().filter(|a| !a.starts_with("0x")) {
+						let account = AccountId32::from_ss58check(account)
+							.map_err(|e| format!("Invalid account {}: {:?}", account, e))?;
+						out.extend_from_slice(account.as_ref());
+						return Ok(())
+					}
+				}
+				self.encode_fields(&composite.fields, value, out)
+			},
+			TypeDef::Variant(variant) => self.encode_variant(ty, variant, value, out),
+			TypeDef::Sequence(sequence) => {
+				if self.is_u8(sequence.type_param.id) {
+					if let Some(bytes) = value.as_str() {
+						let bytes = string_bytes(bytes)?;
+						Compact(bytes.len() as u32).encode_to(out);
+						out.extend(bytes);
+						return Ok(())
+					}
+				}
+				let items =
+					value.as_array().ok_or_else(|| format!("Expected array, got {}", value))?;
+				Compact(items.len() as u32).encode_to(out);
+				items
+					.iter()
+					.try_for_each(|item| self.encode_to(sequence.type_param.id, item, out))
+			},
+			TypeDef::Array(array) => {
+				if self.is_u8(array.type_param.id) {
+					if let Some(bytes) = value.as_str() {
+						let bytes = string_bytes(bytes)?;
+						if bytes.len() != array.len as usize {
+							return Err(format!("Expected {} bytes, got {}", array.len, bytes.len()))
+						}
+						out.extend(bytes);
+						return Ok(())
+					}
+				}
+				let items =
+					value.as_array().ok_or_else(|| format!("Expected array, got {}", value))?;
+				if items.len() != array.len as usize {
+					return Err(format!("Expected {} items, got {}", array.len, items.len()))
+				}
+				items.iter().try_for_each(|item| self.encode_to(array.type_param.id, item, out))
+			},
+			TypeDef::Tuple(tuple) => {
+				if tuple.fields.is_empty() {
+					return Ok(())
+				}
+				let items =
+					value.as_array().ok_or_else(|| format!("Expected array, got {}", value))?;
+				if items.len() != tuple.fields.len() {
+					return Err(format!(
+						"Expected {} items, got {}",
+						tuple.fields.len(),
+						items.len()
+					))
+				}
+				tuple
+					.fields
+					.iter()
+					.zip(items)
+					.try_for_each(|(field, item)| self.encode_to(field.id, item, out))
+			},
+			TypeDef::Primitive(primitive) => encode_primitive(primitive, value, out),
+			TypeDef::Compact(_) => {
+				Compact(uint(value)?).encode_to(out);
+				Ok(())
+			},
+			TypeDef::BitSequence(_) => Err("Bit sequences are not supported".into()),
+		}
+	}
+
+	fn encode_fields(
+		&self,
+		fields: &[Field<PortableForm>],
+		value: &Value,
+		out: &mut Vec<u8>,
+	) -> Result<(), String> {
+		match fields {
+			[] => Ok(()),
+			[field] if field.name.is_none() => self.encode_to(field.ty.id, value, out),
+			_ if fields.iter().all(|f| f.name.is_some()) => {
+				let object =
+					value.as_object().ok_or_else(|| format!("Expected object, got {}", value))?;
+				fields.iter().try_for_each(|field| {
+					let name = field.name.as_ref().expect("Checked above; qed");
+					self.encode_to(field.ty.id, object.get(name).unwrap_or(&Value::Null), out)
+						.map_err(|e| format!("{}: {}", name, e))
+				})
+			},
+			_ => {
+				let items =
+					value.as_array().ok_or_else(|| format!("Expected array, got {}", value))?;
+				if items.len() != fields.len() {
+					return Err(format!("Expected {} items, got {}", fields.len(), items.len()))
+				}
+				fields
+					.iter()
+					.zip(items)
+					.try_for_each(|(field, item)| self.encode_to(field.ty.id, item, out))
+			},
+		}
+	}
+
+	fn encode_variant(
+		&self,
+		ty: &Type<PortableForm>,
+		variant: &TypeDefVariant<PortableForm>,
+		value: &Value,
+		out: &mut Vec<u8>,
+	) -> Result<(), String> {
+		let is_option = ty.path.segments.last().map_or(false, |s| s == "Option") &&
+			variant.variants.iter().map(|v| v.name.as_str()).eq(["None", "Some"]);
+
+		let (name, fields) = match value {
+			Value::String(name) if !is_option || name == "None" => (name.as_str(), &Value::Null),
+			Value::Object(object)
+				if object.len() == 1 &&
+					(!is_option || object.keys().all(|k| k == "None" || k == "Some")) =>
+			{
+				let (name, fields) = object.iter().next().expect("Length checked above; qed");
+				(name.as_str(), fields)
+			},
+			Value::Null if is_option => ("None", &Value::Null),
+			value if is_option => ("Some", value),
+			value => return Err(format!("Expected enum variant, got {}", value)),
+		};
+
+		let variant = variant
+			.variants
+			.iter()
+			.find(|v| v.name == name)
+			.ok_or_else(|| format!("Unknown variant {}", name))?;
+		out.push(variant.index);
+		self.encode_fields(&variant.fields, fields, out)
+			.map_err(|e| format!("{}: {}", name, e))
+	}
+
+	fn is_u8(&self, ty: u32) -> bool {
+		self.0
+			.resolve(ty)
+			.map_or(false, |t| matches!(t.type_def, TypeDef::Primitive(TypeDefPrimitive::U8)))
+	}
+
+	/// A value of the given type that encodes to its "empty" variant, e.g. zero or `None`.
+	fn default_value(&self, ty: u32) -> Result<Value, String> {
+		let ty = self.resolve(ty)?;
+		Ok(match &ty.type_def {
+			TypeDef::Composite(composite) => self.default_fields(&composite.fields)?,
+			TypeDef::Variant(variant) => {
+				let first = variant.variants.first().ok_or("Empty enum")?;
+				match self.default_fields(&first.fields)? {
+					Value::Null => json!(first.name),
+					fields => json!({ first.name.clone(): fields }),
+				}
+			},
+			TypeDef::Sequence(_) => json!([]),
+			TypeDef::Array(array) => Value::Array(
+				(0..array.len)
+					.map(|_| self.default_value(array.type_param.id))
+					.collect::<Result<_, _>>()?,
+			),
+			TypeDef::Tuple(tuple) => Value::Array(
+				tuple
+					.fields
+					.iter()
+					.map(|f| self.default_value(f.id))
+					.collect::<Result<_, _>>()?,
+			),
+			TypeDef::Primitive(TypeDefPrimitive::Bool) => json!(false),
+			TypeDef::Primitive(TypeDefPrimitive::Str) => json!(""),
+			TypeDef::Primitive(_) | TypeDef::Compact(_) => json!(0),
+			TypeDef::BitSequence(_) => return Err("Bit sequences are not supported".into()),
+		})
+	}
+
+	fn default_fields(&self, fields: &[Field<PortableForm>]) -> Result<Value, String> {
+		Ok(match fields {
+			[] => Value::Null,
+			[field] if field.name.is_none() => self.default_value(field.ty.id)?,
+			_ if fields.iter().all(|f| f.name.is_some()) => Value::Object(
+				fields
+					.iter()
+					.map(|f| {
+						Ok((
+							f.name.clone().expect("Checked above; qed"),
+							self.default_value(f.ty.id)?,
+						))
+					})
+					.collect::<Result<_, String>>()?,
+			),
+			_ => Value::Array(
+				fields.iter().map(|f| self.default_value(f.ty.id)).collect::<Result<_, _>>()?,
+			),
+		})
+	}
+}
+
+/// Bytes of a hex string, or of the UTF-8 string itself if it isn't `0x` prefixed.
+fn string_bytes(value: &str) -> Result<Vec<u8>, String> {
+	if value.starts_with("0x") {
+		array_bytes::hex2bytes(value).map_err(|_| format!("Invalid hex {}", value))
+	} else {
+		Ok(value.as_bytes().to_vec())
+	}
+}
+
+fn uint(value: &Value) -> Result<u128, String> {
+	match value {
+		Value::Number(n) => n.as_u64().map(Into::into),
+		Value::String(s) => s.parse().ok(),
+		_ => None,
+	}
+	.ok_or_else(|| format!("Expected unsigned integer, got {}", value))
+}
+
+fn int(value: &Value) -> Result<i128, String> {
+	match value {
+		Value::Number(n) => n.as_i64().map(Into::into),
+		Value::String(s) => s.parse().ok(),
+		_ => None,
+	}
+	.ok_or_else(|| format!("Expected integer, got {}", value))
+}
+
+fn encode_primitive(
+	primitive: &TypeDefPrimitive,
+	value: &Value,
+	out: &mut Vec<u8>,
+) -> Result<(), String> {
+	let out_of_range = |_| format!("{} is out of range", value);
+	match primitive {
+		TypeDefPrimitive::Bool => value
+			.as_bool()
+			.ok_or_else(|| format!("Expected bool, got {}", value))?
+			.encode_to(out),
+		TypeDefPrimitive::Str => value
+			.as_str()
+			.ok_or_else(|| format!("Expected string, got {}", value))?
+			.encode_to(out),
+		TypeDefPrimitive::U8 => u8::try_from(uint(value)?).map_err(out_of_range)?.encode_to(out),
+		TypeDefPrimitive::U16 => u16::try_from(uint(value)?).map_err(out_of_range)?.encode_to(out),
+		TypeDefPrimitive::U32 => u32::try_from(uint(value)?).map_err(out_of_range)?.encode_to(out),
+		TypeDefPrimitive::U64 => u64::try_from(uint(value)?).map_err(out_of_range)?.encode_to(out),
+		TypeDefPrimitive::U128 => uint(value)?.encode_to(out),
+		TypeDefPrimitive::I8 => i8::try_from(int(value)?).map_err(out_of_range)?.encode_to(out),
+		TypeDefPrimitive::I16 => i16::try_from(int(value)?).map_err(out_of_range)?.encode_to(out),
+		TypeDefPrimitive::I32 => i32::try_from(int(value)?).map_err(out_of_range)?.encode_to(out),
+		TypeDefPrimitive::I64 => i64::try_from(int(value)?).map_err(out_of_range)?.encode_to(out),
+		TypeDefPrimitive::I128 => int(value)?.encode_to(out),
+		TypeDefPrimitive::Char | TypeDefPrimitive::U256 | TypeDefPrimitive::I256 =>
+			return Err(format!("{:?} is not supported", primitive)),
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use scale_info::{MetaType, Registry, TypeInfo};
+
+	#[derive(Encode, TypeInfo)]
+	enum Call {
+		#[codec(index = 4)]
+		Transfer {
+			dest: Address,
+			value: Compact<u128>,
+			memo: Vec<u8>,
+		},
+		Remark(Vec<u8>),
+		Batch {
+			calls: Vec<Call>,
+			maybe: Option<(u32, bool)>,
+		},
+	}
+
+	#[derive(Encode, TypeInfo)]
+	enum Address {
+		Id(AccountId32),
+		Index(u32),
+	}
+
+	fn encode<T: TypeInfo + 'static>(value: Value) -> Result<Vec<u8>, String> {
+		let mut registry = Registry::new();
+		let ty = registry.register_type(&MetaType::new::<T>());
+		let registry: PortableRegistry = registry.into();
+		Encoder(&registry).encode(ty.id, &value)
+	}
+
+	#[test]
+	fn encodes_json_like_scale() {
+		let alice = sp_core::sr25519::Pair::from_string("//Alice", None).unwrap().public();
+		let dest = AccountId32::from(alice);
+
+		let call = Call::Batch {
+			calls: vec![
+				Call::Transfer {
+					dest: Address::Id(dest.clone()),
+					value: 1_000_000_000_000_000_000u128.into(),
+					memo: b"hi".to_vec(),
+				},
+				Call::Remark(vec![1, 2, 3]),
+			],
+			maybe: Some((7, true)),
+		};
+		let json = json!({ "Batch": {
+			"calls": [
+				{ "Transfer": {
+					"dest": { "Id": dest.to_ss58check() },
+					"value": "1000000000000000000",
+					"memo": "hi",
+				}},
+				{ "Remark": "0x010203" },
+			],
+			"maybe": [7, true],
+		}});
+
+		assert_eq!(encode::<Call>(json).unwrap(), call.encode());
+		assert_eq!(encode::<Option<u32>>(Value::Null).unwrap(), None::<u32>.encode(),);
+		assert_eq!(encode::<Option<u32>>(json!({ "Some": 3 })).unwrap(), Some(3u32).encode(),);
+	}
+
+	#[test]
+	fn rejects_invalid_values() {
+		assert!(encode::<Address>(json!({ "Unknown": 1 })).is_err());
+		assert!(encode::<u8>(json!(256)).is_err());
+		assert!(encode::<[u8; 2]>(json!("0x010203")).is_err());
+		assert!(encode::<Call>(json!({ "Remark": [1, 2], "Batch": [] })).is_err());
+	}
+
+	#[test]
+	fn default_value_encodes() {
+		let mut registry = Registry::new();
+		let ty = registry.register_type(&MetaType::new::<Call>());
+		let registry: PortableRegistry = registry.into();
+		let encoder = Encoder(&registry);
+
+		let default = encoder.default_value(ty.id).unwrap();
+		assert!(encoder.encode(ty.id, &default).is_ok());
+	}
+
+	#[test]
+	fn wraps_call_into_proxy_and_multisig() {
+		let cmd = TxCmd::try_parse_from([
+			"tx",
+			"--metadata",
+			"metadata.scale",
+			"--call",
+			"{}",
+			"--nonce",
+			"0",
+			"--genesis-hash",
+			"0x00",
+			"--proxy",
+			"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+			"--multisig-threshold",
+			"2",
+			"--multisig-signatory",
+			"5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty",
+			"--max-weight",
+			"10:20",
+		])
+		.unwrap();
+
+		let call = cmd.wrap_call(json!({ "System": { "remark": { "remark": "0x" } } })).unwrap();
+		let multisig = &call["Multisig"]["as_multi"];
+		assert_eq!(multisig["threshold"], json!(2));
+		assert_eq!(multisig["maybe_timepoint"], Value::Null);
+		assert_eq!(multisig["max_weight"], json!({ "ref_time": 10, "proof_size": 20 }));
+		assert_eq!(
+			multisig["call"]["Proxy"]["proxy"]["call"],
+			json!({ "System": { "remark": { "remark": "0x" } } }),
+		);
+	}
+}
@@ -85,6 +85,12 @@ pub trait BlockchainEvents<Block: BlockT> {
 		filter_keys: Option<&[StorageKey]>,
 		child_filter_keys: Option<&[(StorageKey, Option<Vec<StorageKey>>)]>,
 	) -> sp_blockchain::Result<StorageEventStream<Block::Hash>>;
+
+	/// Get storage changes event stream for all keys starting with one of the given prefixes.
+	fn storage_prefix_changes_notification_stream(
+		&self,
+		filter_prefixes: &[StorageKey],
+	) -> sp_blockchain::Result<StorageEventStream<Block::Hash>>;
 }
 
 /// List of operations to be performed on storage aux data.
@@ -34,6 +34,7 @@ use sp_core::storage::{StorageData, StorageKey};
 use sp_runtime::traits::Block as BlockT;
 
 mod registry;
+mod trie;
 
 use registry::Registry;
 
@@ -55,8 +56,18 @@ pub struct StorageNotification<Hash> {
 pub struct StorageChangeSet {
 	changes: Arc<[(StorageKey, Option<StorageData>)]>,
 	child_changes: Arc<[(StorageKey, Vec<(StorageKey, Option<StorageData>)>)]>,
-	filter: Keys,
-	child_filters: ChildKeys,
+	filter: Arc<Filter>,
+}
+
+/// The storage changes a subscriber is interested in.
+#[derive(Debug, Default)]
+struct Filter {
+	/// Top-level keys, `None` meaning all of them.
+	keys: Keys,
+	/// Top-level key prefixes.
+	prefixes: Vec<StorageKey>,
+	/// Child trie keys by child trie, `None` meaning no child trie at all.
+	child_keys: ChildKeys,
 }
 
 /// Manages storage listeners.
@@ -77,8 +88,10 @@ impl StorageChangeSet {
 		let top = self
 			.changes
 			.iter()
-			.filter(move |&(key, _)| match self.filter {
-				Some(ref filter) => filter.contains(key),
+			.filter(move |&(key, _)| match self.filter.keys {
+				Some(ref filter) =>
+					filter.contains(key) ||
+						self.filter.prefixes.iter().any(|prefix| key.0.starts_with(&prefix.0)),
 				None => true,
 			})
 			.map(move |(k, v)| (None, k, v.as_ref()));
@@ -86,7 +99,7 @@ impl StorageChangeSet {
 			.child_changes
 			.iter()
 			.filter_map(move |(sk, changes)| {
-				self.child_filters.as_ref().and_then(|cf| {
+				self.filter.child_keys.as_ref().and_then(|cf| {
 					cf.get(sk).map(|filter| {
 						changes
 							.iter()
@@ -144,9 +157,29 @@ impl<Block: BlockT> StorageNotifications<Block> {
 		filter_keys: Option<&[StorageKey]>,
 		filter_child_keys: Option<&[(StorageKey, Option<Vec<StorageKey>>)]>,
 	) -> StorageEventStream<Block::Hash> {
-		let receiver = self
-			.0
-			.subscribe(registry::SubscribeOp { filter_keys, filter_child_keys }, 100_000);
+		let receiver = self.0.subscribe(
+			registry::SubscribeOp { filter_keys, filter_prefixes: None, filter_child_keys },
+			100_000,
+		);
+
+		StorageEventStream(receiver)
+	}
+
+	/// Start listening for all storage keys starting with one of the given prefixes.
+	///
+	/// Child trie changes are not reported to prefix listeners.
+	pub fn listen_prefixes(
+		&self,
+		filter_prefixes: &[StorageKey],
+	) -> StorageEventStream<Block::Hash> {
+		let receiver = self.0.subscribe(
+			registry::SubscribeOp {
+				filter_keys: Some(&[]),
+				filter_prefixes: Some(filter_prefixes),
+				filter_child_keys: None,
+			},
+			100_000,
+		);
 
 		StorageEventStream(receiver)
 	}
@@ -28,6 +28,8 @@ use sc_utils::{
 	pubsub::{Dispatch, Subscribe, Unsubscribe},
 };
 
+use super::trie::ListenerTrie;
+
 type SubscribersGauge = CounterVec<U64>;
 
 /// A command to subscribe with the specified filters.
@@ -35,26 +37,25 @@ type SubscribersGauge = CounterVec<U64>;
 /// Used by the implementation of [`Subscribe<Op>`] trait for [`Registry].
 pub(super) struct SubscribeOp<'a> {
 	pub filter_keys: Option<&'a [StorageKey]>,
+	pub filter_prefixes: Option<&'a [StorageKey]>,
 	pub filter_child_keys: Option<&'a [(StorageKey, Option<Vec<StorageKey>>)]>,
 }
 
 #[derive(Debug, Default)]
 pub(super) struct Registry {
 	pub(super) metrics: Option<SubscribersGauge>,
-	pub(super) wildcard_listeners: FnvHashSet<SubscriberId>,
-	pub(super) listeners: HashMap<StorageKey, FnvHashSet<SubscriberId>>,
-	pub(super) child_listeners: HashMap<
-		StorageKey,
-		(HashMap<StorageKey, FnvHashSet<SubscriberId>>, FnvHashSet<SubscriberId>),
-	>,
+	/// Listeners of the top-level trie, wildcard listeners being registered for the empty
+	/// prefix.
+	pub(super) listeners: ListenerTrie,
+	/// Listeners of each child trie.
+	pub(super) child_listeners: HashMap<StorageKey, ListenerTrie>,
 	pub(super) sinks: FnvHashMap<SubscriberId, SubscriberSink>,
 }
 
 #[derive(Debug)]
 pub(super) struct SubscriberSink {
 	subs_id: SubscriberId,
-	keys: Keys,
-	child_keys: ChildKeys,
+	filter: Arc<Filter>,
 	was_triggered: bool,
 }
 
@@ -63,18 +64,19 @@ impl Drop for SubscriberSink {
 		if !self.was_triggered {
 			log::trace!(
 				target: "storage_notifications",
-				"Listener was never triggered: id={}, keys={:?}, child_keys={:?}",
+				"Listener was never triggered: id={}, keys={:?}, prefixes={:?}, child_keys={:?}",
 				self.subs_id,
-				PrintKeys(&self.keys),
-				PrintChildKeys(&self.child_keys),
+				PrintKeys(&self.filter.keys),
+				self.filter.prefixes.iter().map(HexDisplay::from).collect::<Vec<_>>(),
+				PrintChildKeys(&self.filter.child_keys),
 			);
 		}
 	}
 }
 
 impl SubscriberSink {
-	fn new(subs_id: SubscriberId, keys: Keys, child_keys: ChildKeys) -> Self {
-		Self { subs_id, keys, child_keys, was_triggered: false }
+	fn new(subs_id: SubscriberId, filter: Filter) -> Self {
+		Self { subs_id, filter: Arc::new(filter), was_triggered: false }
 	}
 }
 
@@ -104,31 +106,22 @@ impl Unsubscribe for Registry {
 
 impl<'a> Subscribe<SubscribeOp<'a>> for Registry {
 	fn subscribe(&mut self, subs_op: SubscribeOp<'a>, subs_id: SubscriberId) {
-		let SubscribeOp { filter_keys, filter_child_keys } = subs_op;
+		let SubscribeOp { filter_keys, filter_prefixes, filter_child_keys } = subs_op;
+
+		let keys = Self::listen_from(subs_id, filter_keys, &mut self.listeners);
 
-		let keys = Self::listen_from(
-			subs_id,
-			filter_keys.as_ref(),
-			&mut self.listeners,
-			&mut self.wildcard_listeners,
-		);
+		let prefixes = filter_prefixes.unwrap_or_default().to_vec();
+		for prefix in &prefixes {
+			self.listeners.insert_prefix(&prefix.0, subs_id);
+		}
 
 		let child_keys = filter_child_keys.map(|filter_child_keys| {
 			filter_child_keys
 				.iter()
 				.map(|(c_key, o_keys)| {
-					let (c_listeners, c_wildcards) =
-						self.child_listeners.entry(c_key.clone()).or_default();
-
-					(
-						c_key.clone(),
-						Self::listen_from(
-							subs_id,
-							o_keys.as_ref(),
-							&mut *c_listeners,
-							&mut *c_wildcards,
-						),
-					)
+					let c_listeners = self.child_listeners.entry(c_key.clone()).or_default();
+
+					(c_key.clone(), Self::listen_from(subs_id, o_keys.as_deref(), c_listeners))
 				})
 				.collect()
 		});
@@ -137,11 +130,8 @@ impl<'a> Subscribe<SubscribeOp<'a>> for Registry {
 			m.with_label_values(&["added"]).inc();
 		}
 
-		if self
-			.sinks
-			.insert(subs_id, SubscriberSink::new(subs_id, keys, child_keys))
-			.is_some()
-		{
+		let filter = Filter { keys, prefixes, child_keys };
+		if self.sinks.insert(subs_id, SubscriberSink::new(subs_id, filter)).is_some() {
 			log::warn!("The `subscribe`-method has been passed a non-unique subs_id (in `sc-client-api::notifications`)");
 		}
 	}
@@ -179,48 +169,29 @@ impl Registry {
 		Hash: Clone,
 		F: FnMut(&SubscriberId, StorageNotification<Hash>),
 	{
-		let has_wildcard = !self.wildcard_listeners.is_empty();
-
 		// early exit if no listeners
-		if !has_wildcard && self.listeners.is_empty() && self.child_listeners.is_empty() {
+		if self.listeners.is_empty() && self.child_listeners.is_empty() {
 			return
 		}
 
-		let mut subscribers = self.wildcard_listeners.clone();
+		let mut subscribers = FnvHashSet::default();
 		let mut changes = Vec::new();
 		let mut child_changes = Vec::new();
 
 		// Collect subscribers and changes
 		for (k, v) in changeset {
-			let k = StorageKey(k);
-			let listeners = self.listeners.get(&k);
-
-			if let Some(listeners) = listeners {
-				subscribers.extend(listeners.iter());
-			}
-
-			if has_wildcard || listeners.is_some() {
-				changes.push((k, v.map(StorageData)));
+			if self.listeners.collect(&k, &mut subscribers) {
+				changes.push((StorageKey(k), v.map(StorageData)));
 			}
 		}
 		for (sk, changeset) in child_changeset {
 			let sk = StorageKey(sk);
-			if let Some((cl, cw)) = self.child_listeners.get(&sk) {
-				let mut changes = Vec::new();
-				for (k, v) in changeset {
-					let k = StorageKey(k);
-					let listeners = cl.get(&k);
-
-					if let Some(listeners) = listeners {
-						subscribers.extend(listeners.iter());
-					}
+			if let Some(listeners) = self.child_listeners.get(&sk) {
+				let changes = changeset
+					.filter(|(k, _)| listeners.collect(k, &mut subscribers))
+					.map(|(k, v)| (StorageKey(k), v.map(StorageData)))
+					.collect::<Vec<_>>();
 
-					subscribers.extend(cw.iter());
-
-					if !cw.is_empty() || listeners.is_some() {
-						changes.push((k, v.map(StorageData)));
-					}
-				}
 				if !changes.is_empty() {
 					child_changes.push((sk, changes));
 				}
@@ -236,47 +207,40 @@ impl Registry {
 		let child_changes = Arc::<[_]>::from(child_changes);
 
 		// Trigger the events
-		self.sinks.iter_mut().for_each(|(subs_id, sink)| {
-			if subscribers.contains(subs_id) {
+		for subs_id in subscribers {
+			if let Some(sink) = self.sinks.get_mut(&subs_id) {
 				sink.was_triggered = true;
 
 				let storage_change_set = StorageChangeSet {
 					changes: changes.clone(),
 					child_changes: child_changes.clone(),
-					filter: sink.keys.clone(),
-					child_filters: sink.child_keys.clone(),
+					filter: sink.filter.clone(),
 				};
 
 				let notification =
 					StorageNotification { block: hash.clone(), changes: storage_change_set };
 
-				dispatch(subs_id, notification);
+				dispatch(&subs_id, notification);
 			}
-		});
+		}
 	}
 }
 
 impl Registry {
-	fn remove_subscriber(&mut self, subscriber: SubscriberId) -> Option<(Keys, ChildKeys)> {
-		let sink = self.sinks.remove(&subscriber)?;
-
-		Self::remove_subscriber_from(
-			subscriber,
-			&sink.keys,
-			&mut self.listeners,
-			&mut self.wildcard_listeners,
-		);
-		if let Some(child_filters) = &sink.child_keys {
+	fn remove_subscriber(&mut self, subscriber: SubscriberId) {
+		let Some(sink) = self.sinks.remove(&subscriber) else { return };
+		let filter = &sink.filter;
+
+		Self::remove_subscriber_from(subscriber, &filter.keys, &mut self.listeners);
+		for prefix in &filter.prefixes {
+			self.listeners.remove_prefix(&prefix.0, subscriber);
+		}
+		if let Some(child_filters) = &filter.child_keys {
 			for (c_key, filters) in child_filters {
-				if let Some((listeners, wildcards)) = self.child_listeners.get_mut(c_key) {
-					Self::remove_subscriber_from(
-						subscriber,
-						filters,
-						&mut *listeners,
-						&mut *wildcards,
-					);
-
-					if listeners.is_empty() && wildcards.is_empty() {
+				if let Some(listeners) = self.child_listeners.get_mut(c_key) {
+					Self::remove_subscriber_from(subscriber, filters, listeners);
+
+					if listeners.is_empty() {
 						self.child_listeners.remove(c_key);
 					}
 				}
@@ -285,53 +249,36 @@ impl Registry {
 		if let Some(m) = self.metrics.as_ref() {
 			m.with_label_values(&["removed"]).inc();
 		}
-
-		Some((sink.keys.clone(), sink.child_keys.clone()))
 	}
 
 	fn remove_subscriber_from(
 		subscriber: SubscriberId,
 		filters: &Keys,
-		listeners: &mut HashMap<StorageKey, FnvHashSet<SubscriberId>>,
-		wildcards: &mut FnvHashSet<SubscriberId>,
+		listeners: &mut ListenerTrie,
 	) {
 		match filters {
-			None => {
-				wildcards.remove(&subscriber);
-			},
+			None => listeners.remove_prefix(&[], subscriber),
 			Some(filters) =>
 				for key in filters.iter() {
-					let remove_key = match listeners.get_mut(key) {
-						Some(ref mut set) => {
-							set.remove(&subscriber);
-							set.is_empty()
-						},
-						None => false,
-					};
-
-					if remove_key {
-						listeners.remove(key);
-					}
+					listeners.remove_key(&key.0, subscriber);
 				},
 		}
 	}
 
 	fn listen_from(
 		current_id: SubscriberId,
-		filter_keys: Option<impl AsRef<[StorageKey]>>,
-		listeners: &mut HashMap<StorageKey, FnvHashSet<SubscriberId>>,
-		wildcards: &mut FnvHashSet<SubscriberId>,
+		filter_keys: Option<&[StorageKey]>,
+		listeners: &mut ListenerTrie,
 	) -> Keys {
 		match filter_keys {
 			None => {
-				wildcards.insert(current_id);
+				listeners.insert_prefix(&[], current_id);
 				None
 			},
 			Some(keys) => Some(
-				keys.as_ref()
-					.iter()
+				keys.iter()
 					.map(|key| {
-						listeners.entry(key.clone()).or_default().insert(current_id);
+						listeners.insert_key(&key.0, current_id);
 						key.clone()
 					})
 					.collect(),
@@ -38,8 +38,11 @@ impl From<TestChangeSet> for StorageChangeSet {
 		StorageChangeSet {
 			changes: From::from(changes.0),
 			child_changes: From::from(changes.1),
-			filter: None,
-			child_filters,
+			filter: Arc::new(Filter {
+				keys: None,
+				prefixes: Vec::new(),
+				child_keys: child_filters,
+			}),
 		}
 	}
 }
@@ -146,6 +149,55 @@ fn should_only_notify_interested_listeners() {
 	);
 }
 
+#[test]
+fn should_notify_prefix_listeners() {
+	// given
+	let notifications = StorageNotifications::<Block>::new(None);
+	let mut recv1 =
+		futures::executor::block_on_stream(notifications.listen_prefixes(&[StorageKey(vec![1])]));
+	let mut recv2 = futures::executor::block_on_stream(
+		notifications.listen_prefixes(&[StorageKey(vec![2, 1]), StorageKey(vec![3])]),
+	);
+
+	// when
+	let changeset = vec![
+		(vec![1, 1], Some(vec![3])),
+		(vec![1, 2], None),
+		(vec![2, 1, 5], Some(vec![4])),
+		(vec![2, 2], Some(vec![5])),
+	];
+	let c_changeset_1 = vec![(vec![1, 1], Some(vec![4]))];
+	let c_changeset = vec![(vec![4], c_changeset_1)];
+	notifications.trigger(
+		&Hash::from_low_u64_be(1),
+		changeset.into_iter(),
+		c_changeset.into_iter().map(|(a, b)| (a, b.into_iter())),
+	);
+
+	// then
+	assert_eq!(
+		recv1.next().map(StorageNotification::into_fields).unwrap(),
+		(
+			Hash::from_low_u64_be(1),
+			(
+				vec![
+					(StorageKey(vec![1, 1]), Some(StorageData(vec![3]))),
+					(StorageKey(vec![1, 2]), None),
+				],
+				vec![]
+			)
+				.into()
+		)
+	);
+	assert_eq!(
+		recv2.next().map(StorageNotification::into_fields).unwrap(),
+		(
+			Hash::from_low_u64_be(1),
+			(vec![(StorageKey(vec![2, 1, 5]), Some(StorageData(vec![4])))], vec![]).into()
+		)
+	);
+}
+
 #[test]
 fn should_cleanup_subscribers_if_dropped() {
 	// given
@@ -159,10 +211,12 @@ fn should_cleanup_subscribers_if_dropped() {
 			notifications.listen(Some(&[StorageKey(vec![2])]), None),
 		);
 		let _recv3 = futures::executor::block_on_stream(notifications.listen(None, None));
+		let _recv5 = futures::executor::block_on_stream(
+			notifications.listen_prefixes(&[StorageKey(vec![1])]),
+		);
 		let _recv4 =
 			futures::executor::block_on_stream(notifications.listen(None, Some(&child_filter)));
-		assert_eq!(notifications.map_registry(|r| r.listeners.len()), 2);
-		assert_eq!(notifications.map_registry(|r| r.wildcard_listeners.len()), 2);
+		assert!(!notifications.map_registry(|r| r.listeners.is_empty()));
 		assert_eq!(notifications.map_registry(|r| r.child_listeners.len()), 1);
 	}
 
@@ -172,8 +226,7 @@ fn should_cleanup_subscribers_if_dropped() {
 	notifications.trigger(&Hash::from_low_u64_be(1), changeset.into_iter(), c_changeset);
 
 	// then
-	assert_eq!(notifications.map_registry(|r| r.listeners.len()), 0);
-	assert_eq!(notifications.map_registry(|r| r.wildcard_listeners.len()), 0);
+	assert!(notifications.map_registry(|r| r.listeners.is_empty()));
 	assert_eq!(notifications.map_registry(|r| r.child_listeners.len()), 0);
 }
 
@@ -204,6 +257,90 @@ fn should_not_send_empty_notifications() {
 	assert_eq!(recv.next().map(StorageNotification::into_fields), None);
 }
 
+#[test]
+fn should_notify_child_wildcard_and_key_listeners() {
+	// given
+	let notifications = StorageNotifications::<Block>::new(None);
+	let child_filter_1 = [(StorageKey(vec![4]), None)];
+	let child_filter_2 = [(StorageKey(vec![4]), Some(vec![StorageKey(vec![6])]))];
+	let mut recv1 =
+		futures::executor::block_on_stream(notifications.listen(Some(&[]), Some(&child_filter_1)));
+	let mut recv2 =
+		futures::executor::block_on_stream(notifications.listen(Some(&[]), Some(&child_filter_2)));
+
+	// when
+	let c_changeset_1 = vec![(vec![5], Some(vec![4])), (vec![6], None)];
+	let c_changeset_2 = vec![(vec![6], Some(vec![1]))];
+	let c_changeset = vec![(vec![4], c_changeset_1), (vec![7], c_changeset_2)];
+	notifications.trigger(
+		&Hash::from_low_u64_be(1),
+		empty(),
+		c_changeset.into_iter().map(|(a, b)| (a, b.into_iter())),
+	);
+
+	// then
+	assert_eq!(
+		recv1.next().map(StorageNotification::into_fields).unwrap(),
+		(
+			Hash::from_low_u64_be(1),
+			(
+				vec![],
+				vec![(
+					StorageKey(vec![4]),
+					vec![
+						(StorageKey(vec![5]), Some(StorageData(vec![4]))),
+						(StorageKey(vec![6]), None),
+					]
+				)]
+			)
+				.into()
+		)
+	);
+	assert_eq!(
+		recv2.next().map(StorageNotification::into_fields).unwrap(),
+		(
+			Hash::from_low_u64_be(1),
+			(vec![], vec![(StorageKey(vec![4]), vec![(StorageKey(vec![6]), None)])]).into()
+		)
+	);
+}
+
+#[test]
+fn listener_trie_matches_keys_and_prefixes() {
+	let mut ids = sc_utils::id_sequence::IDSequence::new();
+	let (wildcard, key, prefix, nested) =
+		(ids.next_id(), ids.next_id(), ids.next_id(), ids.next_id());
+
+	let mut trie = trie::ListenerTrie::default();
+	trie.insert_prefix(&[], wildcard);
+	trie.insert_key(&[1, 2, 3], key);
+	trie.insert_prefix(&[1, 2], prefix);
+	trie.insert_key(&[1, 2, 3, 4], nested);
+
+	let collect = |listeners: &trie::ListenerTrie, key: &[u8]| {
+		let mut subscribers = fnv::FnvHashSet::default();
+		let found = listeners.collect(key, &mut subscribers);
+		assert_eq!(found, !subscribers.is_empty());
+		let mut subscribers = subscribers.into_iter().collect::<Vec<_>>();
+		subscribers.sort();
+		subscribers
+	};
+
+	assert_eq!(collect(&trie, &[1, 2, 3]), vec![wildcard, key, prefix]);
+	assert_eq!(collect(&trie, &[1, 2, 3, 4]), vec![wildcard, prefix, nested]);
+	assert_eq!(collect(&trie, &[1, 2, 5]), vec![wildcard, prefix]);
+	assert_eq!(collect(&trie, &[1]), vec![wildcard]);
+
+	trie.remove_prefix(&[], wildcard);
+	trie.remove_prefix(&[1, 2], prefix);
+	assert_eq!(collect(&trie, &[1, 2, 5]), vec![]);
+	assert_eq!(collect(&trie, &[1, 2, 3, 4]), vec![nested]);
+
+	trie.remove_key(&[1, 2, 3], key);
+	trie.remove_key(&[1, 2, 3, 4], nested);
+	assert!(trie.is_empty());
+}
+
 impl<B: BlockT> StorageNotifications<B> {
 	fn map_registry<MapF, Ret>(&self, map: MapF) -> Ret
 	where
@@ -0,0 +1,162 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Radix trie over storage keys used to look up the subscribers interested in a change.
+
+use std::collections::BTreeMap;
+
+use fnv::FnvHashSet;
+use sc_utils::id_sequence::SeqID as SubscriberId;
+
+/// Subscribers indexed by the storage keys and key prefixes they listen to.
+///
+/// Looking up the subscribers of a changed key walks the trie along the key only, so the
+/// cost of a lookup does not depend on the number of subscriptions. A subscription to the
+/// empty prefix is a wildcard subscription.
+#[derive(Debug, Default)]
+pub(super) struct ListenerTrie {
+	root: Node,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+	/// Subscribers of the key ending at this node.
+	keys: FnvHashSet<SubscriberId>,
+	/// Subscribers of all keys starting with the key ending at this node.
+	prefixes: FnvHashSet<SubscriberId>,
+	/// Child nodes indexed by the first byte of their edge label.
+	children: BTreeMap<u8, Edge>,
+}
+
+#[derive(Debug)]
+struct Edge {
+	label: Vec<u8>,
+	node: Node,
+}
+
+impl Node {
+	fn has_listeners(&self) -> bool {
+		!self.keys.is_empty() || !self.prefixes.is_empty()
+	}
+
+	fn is_empty(&self) -> bool {
+		!self.has_listeners() && self.children.is_empty()
+	}
+
+	/// Return the node for `key`, creating it (and splitting edges) if needed.
+	fn node_mut(&mut self, key: &[u8]) -> &mut Node {
+		let Some(&first) = key.first() else { return self };
+
+		let edge = self
+			.children
+			.entry(first)
+			.or_insert_with(|| Edge { label: key.to_vec(), node: Node::default() });
+
+		let common = common_prefix_len(&edge.label, key);
+		if common < edge.label.len() {
+			let suffix = edge.label.split_off(common);
+			let old = std::mem::take(&mut edge.node);
+			edge.node.children.insert(suffix[0], Edge { label: suffix, node: old });
+		}
+
+		edge.node.node_mut(&key[common..])
+	}
+
+	/// Apply `f` to the node for `key` if it exists, pruning nodes left without listeners.
+	fn remove(&mut self, key: &[u8], f: impl FnOnce(&mut Node)) {
+		let Some(&first) = key.first() else { return f(self) };
+		let Some(edge) = self.children.get_mut(&first) else { return };
+		if !key.starts_with(&edge.label) {
+			return
+		}
+
+		edge.node.remove(&key[edge.label.len()..], f);
+
+		if edge.node.is_empty() {
+			self.children.remove(&first);
+		} else if !edge.node.has_listeners() && edge.node.children.len() == 1 {
+			// Merge the edge with its only child to keep the trie compressed.
+			let (_, child) = edge.node.children.pop_first().expect("one child is present; qed");
+			edge.label.extend(child.label);
+			edge.node = child.node;
+		}
+	}
+}
+
+impl ListenerTrie {
+	/// Whether no subscriber is registered.
+	pub(super) fn is_empty(&self) -> bool {
+		self.root.is_empty()
+	}
+
+	/// Register `subscriber` as listening to changes of `key`.
+	pub(super) fn insert_key(&mut self, key: &[u8], subscriber: SubscriberId) {
+		self.root.node_mut(key).keys.insert(subscriber);
+	}
+
+	/// Register `subscriber` as listening to changes of all keys starting with `prefix`.
+	pub(super) fn insert_prefix(&mut self, prefix: &[u8], subscriber: SubscriberId) {
+		self.root.node_mut(prefix).prefixes.insert(subscriber);
+	}
+
+	/// Unregister `subscriber` from changes of `key`.
+	pub(super) fn remove_key(&mut self, key: &[u8], subscriber: SubscriberId) {
+		self.root.remove(key, |node| {
+			node.keys.remove(&subscriber);
+		});
+	}
+
+	/// Unregister `subscriber` from changes of all keys starting with `prefix`.
+	pub(super) fn remove_prefix(&mut self, prefix: &[u8], subscriber: SubscriberId) {
+		self.root.remove(prefix, |node| {
+			node.prefixes.remove(&subscriber);
+		});
+	}
+
+	/// Collect all subscribers interested in a change of `key` into `subscribers`.
+	///
+	/// Returns `true` if there is at least one such subscriber.
+	pub(super) fn collect(&self, key: &[u8], subscribers: &mut FnvHashSet<SubscriberId>) -> bool {
+		let mut found = false;
+		let mut node = &self.root;
+		let mut key = key;
+
+		loop {
+			found |= !node.prefixes.is_empty();
+			subscribers.extend(node.prefixes.iter());
+
+			let Some(first) = key.first() else {
+				found |= !node.keys.is_empty();
+				subscribers.extend(node.keys.iter());
+				return found
+			};
+
+			match node.children.get(first) {
+				Some(edge) if key.starts_with(&edge.label) => {
+					key = &key[edge.label.len()..];
+					node = &edge.node;
+				},
+				_ => return found,
+			}
+		}
+	}
+}
+
+fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+	a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
@@ -30,7 +30,7 @@ use futures::{
 use log::{debug, error, info, trace, warn};
 use sc_block_builder::{BlockBuilderApi, BlockBuilderBuilder};
 use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_INFO};
-use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
+use sc_transaction_pool_api::{InPoolTransaction, ReadyTransactions, TransactionPool};
 use sp_api::{ApiExt, CallApiAt, ProvideRuntimeApi};
 use sp_blockchain::{ApplyExtrinsicFailed::Validity, Error::ApplyExtrinsicFailed, HeaderBackend};
 use sp_consensus::{DisableProofRecording, EnableProofRecording, ProofRecording, Proposal};
@@ -38,6 +38,7 @@ use sp_core::traits::SpawnNamed;
 use sp_inherents::InherentData;
 use sp_runtime::{
 	traits::{BlakeTwo256, Block as BlockT, Hash as HashT, Header as HeaderT},
+	transaction_validity::TransactionPriority,
 	Digest, Percent, SaturatedConversion,
 };
 use std::{marker::PhantomData, pin::Pin, sync::Arc, time};
@@ -45,6 +46,8 @@ use std::{marker::PhantomData, pin::Pin, sync::Arc, time};
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_proposer_metrics::{EndProposingReason, MetricsLink as PrometheusMetrics};
 
+use crate::ordering::{OrderedReadyTransactions, TransactionOrdering};
+
 /// Default block size limit in bytes used by [`Proposer`].
 ///
 /// Can be overwritten by [`ProposerFactory::set_default_block_size_limit`].
@@ -56,10 +59,16 @@ pub const DEFAULT_BLOCK_SIZE_LIMIT: usize = 4 * 1024 * 1024 + 512;
 
 const DEFAULT_SOFT_DEADLINE_PERCENT: Percent = Percent::from_percent(50);
 
+/// Default number of transactions that exhaust resources we skip before concluding that the block
+/// is full.
+///
+/// Can be overwritten by [`ProposerFactory::set_max_skipped_transactions`].
+pub const DEFAULT_MAX_SKIPPED_TRANSACTIONS: usize = 8;
+
 const LOG_TARGET: &'static str = "basic-authorship";
 
 /// [`Proposer`] factory.
-pub struct ProposerFactory<A, C, PR> {
+pub struct ProposerFactory<A: TransactionPool, C, PR> {
 	spawn_handle: Box<dyn SpawnNamed>,
 	/// The client instance.
 	client: Arc<C>,
@@ -77,9 +86,16 @@ pub struct ProposerFactory<A, C, PR> {
 	/// The value is used to compute soft deadline during block production.
 	/// The soft deadline indicates where we should stop attempting to add transactions
 	/// to the block, which exhaust resources. After soft deadline is reached,
-	/// we switch to a fixed-amount mode, in which after we see `MAX_SKIPPED_TRANSACTIONS`
+	/// we switch to a fixed-amount mode, in which after we see `DEFAULT_MAX_SKIPPED_TRANSACTIONS`
 	/// transactions which exhaust resources, we will conclude that the block is full.
 	soft_deadline_percent: Percent,
+	/// Number of transactions that exhaust resources we skip before concluding that the block is
+	/// full.
+	max_skipped_transactions: usize,
+	/// Transactions pushed after the soft deadline are restricted by this policy.
+	soft_deadline_policy: SoftDeadlinePolicy,
+	/// Ordering applied to ready transactions of the same priority.
+	transaction_ordering: Option<Arc<dyn TransactionOrdering<A::InPoolTransaction>>>,
 	telemetry: Option<TelemetryHandle>,
 	/// When estimating the block size, should the proof be included?
 	include_proof_in_block_size_estimation: bool,
@@ -87,7 +103,7 @@ pub struct ProposerFactory<A, C, PR> {
 	_phantom: PhantomData<PR>,
 }
 
-impl<A, C, PR> Clone for ProposerFactory<A, C, PR> {
+impl<A: TransactionPool, C, PR> Clone for ProposerFactory<A, C, PR> {
 	fn clone(&self) -> Self {
 		Self {
 			spawn_handle: self.spawn_handle.clone(),
@@ -96,6 +112,9 @@ impl<A, C, PR> Clone for ProposerFactory<A, C, PR> {
 			metrics: self.metrics.clone(),
 			default_block_size_limit: self.default_block_size_limit,
 			soft_deadline_percent: self.soft_deadline_percent,
+			max_skipped_transactions: self.max_skipped_transactions,
+			soft_deadline_policy: self.soft_deadline_policy.clone(),
+			transaction_ordering: self.transaction_ordering.clone(),
 			telemetry: self.telemetry.clone(),
 			include_proof_in_block_size_estimation: self.include_proof_in_block_size_estimation,
 			_phantom: self._phantom,
@@ -103,7 +122,7 @@ impl<A, C, PR> Clone for ProposerFactory<A, C, PR> {
 	}
 }
 
-impl<A, C> ProposerFactory<A, C, DisableProofRecording> {
+impl<A: TransactionPool, C> ProposerFactory<A, C, DisableProofRecording> {
 	/// Create a new proposer factory.
 	///
 	/// Proof recording will be disabled when using proposers built by this instance to build
@@ -121,6 +140,9 @@ impl<A, C> ProposerFactory<A, C, DisableProofRecording> {
 			metrics: PrometheusMetrics::new(prometheus),
 			default_block_size_limit: DEFAULT_BLOCK_SIZE_LIMIT,
 			soft_deadline_percent: DEFAULT_SOFT_DEADLINE_PERCENT,
+			max_skipped_transactions: DEFAULT_MAX_SKIPPED_TRANSACTIONS,
+			soft_deadline_policy: SoftDeadlinePolicy::default(),
+			transaction_ordering: None,
 			telemetry,
 			client,
 			include_proof_in_block_size_estimation: false,
@@ -129,7 +151,7 @@ impl<A, C> ProposerFactory<A, C, DisableProofRecording> {
 	}
 }
 
-impl<A, C> ProposerFactory<A, C, EnableProofRecording> {
+impl<A: TransactionPool, C> ProposerFactory<A, C, EnableProofRecording> {
 	/// Create a new proposer factory with proof recording enabled.
 	///
 	/// Each proposer created by this instance will record a proof while building a block.
@@ -150,6 +172,9 @@ impl<A, C> ProposerFactory<A, C, EnableProofRecording> {
 			metrics: PrometheusMetrics::new(prometheus),
 			default_block_size_limit: DEFAULT_BLOCK_SIZE_LIMIT,
 			soft_deadline_percent: DEFAULT_SOFT_DEADLINE_PERCENT,
+			max_skipped_transactions: DEFAULT_MAX_SKIPPED_TRANSACTIONS,
+			soft_deadline_policy: SoftDeadlinePolicy::default(),
+			transaction_ordering: None,
 			telemetry,
 			include_proof_in_block_size_estimation: true,
 			_phantom: PhantomData,
@@ -162,7 +187,7 @@ impl<A, C> ProposerFactory<A, C, EnableProofRecording> {
 	}
 }
 
-impl<A, C, PR> ProposerFactory<A, C, PR> {
+impl<A: TransactionPool, C, PR> ProposerFactory<A, C, PR> {
 	/// Set the default block size limit in bytes.
 	///
 	/// The default value for the block size limit is:
@@ -179,7 +204,7 @@ impl<A, C, PR> ProposerFactory<A, C, PR> {
 	/// The value is used to compute soft deadline during block production.
 	/// The soft deadline indicates where we should stop attempting to add transactions
 	/// to the block, which exhaust resources. After soft deadline is reached,
-	/// we switch to a fixed-amount mode, in which after we see `MAX_SKIPPED_TRANSACTIONS`
+	/// we switch to a fixed-amount mode, in which after we see `DEFAULT_MAX_SKIPPED_TRANSACTIONS`
 	/// transactions which exhaust resrouces, we will conclude that the block is full.
 	///
 	/// Setting the value too low will significantly limit the amount of transactions
@@ -189,6 +214,59 @@ impl<A, C, PR> ProposerFactory<A, C, PR> {
 	pub fn set_soft_deadline(&mut self, percent: Percent) {
 		self.soft_deadline_percent = percent;
 	}
+
+	/// Set the number of transactions which exhaust resources that are skipped before the block
+	/// is considered full.
+	///
+	/// The default value is [`DEFAULT_MAX_SKIPPED_TRANSACTIONS`]. Before the soft deadline is
+	/// reached this limit is ignored and we keep trying transactions.
+	pub fn set_max_skipped_transactions(&mut self, max: usize) {
+		self.max_skipped_transactions = max;
+	}
+
+	/// Set the policy restricting which transactions are still considered after the soft deadline
+	/// has been reached.
+	///
+	/// By default no restriction is applied.
+	pub fn set_soft_deadline_policy(&mut self, policy: SoftDeadlinePolicy) {
+		self.soft_deadline_policy = policy;
+	}
+
+	/// Set the ordering applied to ready transactions that share the same priority.
+	///
+	/// By default the transactions are pushed in the order returned by the transaction pool.
+	pub fn set_transaction_ordering(
+		&mut self,
+		ordering: impl TransactionOrdering<A::InPoolTransaction> + 'static,
+	) {
+		self.transaction_ordering = Some(Arc::new(ordering));
+	}
+}
+
+/// Restricts the transactions that are still pushed into the block after the soft deadline.
+///
+/// Once the soft deadline is reached there is little time left for block production, so only
+/// transactions that are cheap to execute should still be tried. Transactions that do not match
+/// the policy are skipped, but stay in the pool.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SoftDeadlinePolicy {
+	/// Only transactions with at least this priority are considered.
+	///
+	/// Operational extrinsics get a large priority boost in `pallet-transaction-payment`, so
+	/// setting this above the priority of normal transactions restricts the tail of block
+	/// production to operational extrinsics.
+	pub min_priority: Option<TransactionPriority>,
+	/// Only transactions whose encoded size does not exceed this value are considered.
+	pub max_transaction_size: Option<usize>,
+}
+
+impl SoftDeadlinePolicy {
+	/// Returns `true` if a transaction with the given `priority` and `encoded_size` may still be
+	/// pushed after the soft deadline.
+	pub fn allows(&self, priority: TransactionPriority, encoded_size: usize) -> bool {
+		self.min_priority.map_or(true, |min| priority >= min) &&
+			self.max_transaction_size.map_or(true, |max| encoded_size <= max)
+	}
 }
 
 impl<Block, C, A, PR> ProposerFactory<A, C, PR>
@@ -217,6 +295,9 @@ where
 			metrics: self.metrics.clone(),
 			default_block_size_limit: self.default_block_size_limit,
 			soft_deadline_percent: self.soft_deadline_percent,
+			max_skipped_transactions: self.max_skipped_transactions,
+			soft_deadline_policy: self.soft_deadline_policy.clone(),
+			transaction_ordering: self.transaction_ordering.clone(),
 			telemetry: self.telemetry.clone(),
 			_phantom: PhantomData,
 			include_proof_in_block_size_estimation: self.include_proof_in_block_size_estimation,
@@ -255,6 +336,9 @@ pub struct Proposer<Block: BlockT, C, A: TransactionPool, PR> {
 	default_block_size_limit: usize,
 	include_proof_in_block_size_estimation: bool,
 	soft_deadline_percent: Percent,
+	max_skipped_transactions: usize,
+	soft_deadline_policy: SoftDeadlinePolicy,
+	transaction_ordering: Option<Arc<dyn TransactionOrdering<A::InPoolTransaction>>>,
 	telemetry: Option<TelemetryHandle>,
 	_phantom: PhantomData<PR>,
 }
@@ -305,11 +389,6 @@ where
 	}
 }
 
-/// If the block is full we will attempt to push at most
-/// this number of transactions before quitting for real.
-/// It allows us to increase block utilization.
-const MAX_SKIPPED_TRANSACTIONS: usize = 8;
-
 impl<A, Block, C, PR> Proposer<Block, C, A, PR>
 where
 	A: TransactionPool<Block = Block>,
@@ -408,6 +487,7 @@ where
 		let left_micros: u64 = left.as_micros().saturated_into();
 		let soft_deadline =
 			now + time::Duration::from_micros(self.soft_deadline_percent.mul_floor(left_micros));
+		let max_skipped = self.max_skipped_transactions;
 		let mut skipped = 0;
 		let mut unqueue_invalid = Vec::new();
 
@@ -415,7 +495,7 @@ where
 		let mut t2 =
 			futures_timer::Delay::new(deadline.saturating_duration_since((self.now)()) / 8).fuse();
 
-		let mut pending_iterator = select! {
+		let pending_iterator = select! {
 			res = t1 => res,
 			_ = t2 => {
 				warn!(target: LOG_TARGET,
@@ -426,6 +506,12 @@ where
 				self.transaction_pool.ready()
 			},
 		};
+		let mut pending_iterator: Box<dyn ReadyTransactions<Item = _>> =
+			match self.transaction_ordering.clone() {
+				Some(ordering) =>
+					Box::new(OrderedReadyTransactions::new(pending_iterator, ordering)),
+				None => pending_iterator,
+			};
 
 		let block_size_limit = block_size_limit.unwrap_or(self.default_block_size_limit);
 
@@ -457,18 +543,32 @@ where
 
 			let pending_tx_data = pending_tx.data().clone();
 			let pending_tx_hash = pending_tx.hash().clone();
+			let pending_tx_size = pending_tx_data.encoded_size();
+
+			if now >= soft_deadline &&
+				!self.soft_deadline_policy.allows(*pending_tx.priority(), pending_tx_size)
+			{
+				pending_iterator.report_invalid(&pending_tx);
+				self.metrics.report(|metrics| metrics.soft_deadline_skipped_transactions.inc());
+				trace!(
+					target: LOG_TARGET,
+					"[{:?}] Soft deadline reached, skipping transaction not matching the policy.",
+					pending_tx_hash,
+				);
+				continue
+			}
 
 			let block_size =
 				block_builder.estimate_block_size(self.include_proof_in_block_size_estimation);
-			if block_size + pending_tx_data.encoded_size() > block_size_limit {
+			if block_size + pending_tx_size > block_size_limit {
 				pending_iterator.report_invalid(&pending_tx);
-				if skipped < MAX_SKIPPED_TRANSACTIONS {
+				if skipped < max_skipped {
 					skipped += 1;
 					debug!(
 						target: LOG_TARGET,
 						"Transaction would overflow the block size limit, \
 					 but will try {} more transactions before quitting.",
-						MAX_SKIPPED_TRANSACTIONS - skipped,
+						max_skipped - skipped,
 					);
 					continue
 				} else if now < soft_deadline {
@@ -496,11 +596,11 @@ where
 				},
 				Err(ApplyExtrinsicFailed(Validity(e))) if e.exhausted_resources() => {
 					pending_iterator.report_invalid(&pending_tx);
-					if skipped < MAX_SKIPPED_TRANSACTIONS {
+					if skipped < max_skipped {
 						skipped += 1;
 						debug!(target: LOG_TARGET,
 							"Block seems full, but will try {} more transactions before quitting.",
-							MAX_SKIPPED_TRANSACTIONS - skipped,
+							max_skipped - skipped,
 						);
 					} else if (self.now)() < soft_deadline {
 						debug!(target: LOG_TARGET,
@@ -526,6 +626,18 @@ where
 			}
 		};
 
+		let overshoot = (self.now)().saturating_duration_since(deadline);
+		if !overshoot.is_zero() {
+			debug!(
+				target: LOG_TARGET,
+				"Pushing transactions overshot the deadline by {} ms.",
+				overshoot.as_millis(),
+			);
+		}
+		self.metrics.report(|metrics| {
+			metrics.deadline_overshoot.observe(overshoot.as_secs_f64());
+		});
+
 		if matches!(end_reason, EndProposingReason::HitBlockSizeLimit) && !transaction_pushed {
 			warn!(
 				target: LOG_TARGET,
@@ -1021,12 +1133,12 @@ mod tests {
 			txpool.submit_at(
 				genesis_hash,
 				SOURCE,
-				// add 2 * MAX_SKIPPED_TRANSACTIONS that exhaust resources
-				(0..MAX_SKIPPED_TRANSACTIONS * 2)
+				// add 2 * DEFAULT_MAX_SKIPPED_TRANSACTIONS that exhaust resources
+				(0..DEFAULT_MAX_SKIPPED_TRANSACTIONS * 2)
 					.into_iter()
 					.map(huge)
 					// and some transactions that are okay.
-					.chain((0..MAX_SKIPPED_TRANSACTIONS as u64).into_iter().map(tiny))
+					.chain((0..DEFAULT_MAX_SKIPPED_TRANSACTIONS as u64).into_iter().map(tiny))
 					.collect(),
 			),
 		)
@@ -1035,7 +1147,7 @@ mod tests {
 		block_on(txpool.maintain(chain_event(
 			client.expect_header(genesis_hash).expect("there should be header"),
 		)));
-		assert_eq!(txpool.ready().count(), MAX_SKIPPED_TRANSACTIONS * 3);
+		assert_eq!(txpool.ready().count(), DEFAULT_MAX_SKIPPED_TRANSACTIONS * 3);
 
 		let mut proposer_factory =
 			ProposerFactory::new(spawner.clone(), client.clone(), txpool.clone(), None, None);
@@ -1060,7 +1172,7 @@ mod tests {
 				.unwrap();
 
 		// then block should have all non-exhaust resources extrinsics (+ the first one).
-		assert_eq!(block.extrinsics().len(), MAX_SKIPPED_TRANSACTIONS + 1);
+		assert_eq!(block.extrinsics().len(), DEFAULT_MAX_SKIPPED_TRANSACTIONS + 1);
 	}
 
 	#[test]
@@ -1093,11 +1205,11 @@ mod tests {
 			txpool.submit_at(
 				genesis_hash,
 				SOURCE,
-				(0..MAX_SKIPPED_TRANSACTIONS + 2)
+				(0..DEFAULT_MAX_SKIPPED_TRANSACTIONS + 2)
 					.into_iter()
 					.map(huge)
 					// and some transactions that are okay.
-					.chain((0..MAX_SKIPPED_TRANSACTIONS + 2).into_iter().map(tiny))
+					.chain((0..DEFAULT_MAX_SKIPPED_TRANSACTIONS + 2).into_iter().map(tiny))
 					.collect(),
 			),
 		)
@@ -1106,7 +1218,7 @@ mod tests {
 		block_on(txpool.maintain(chain_event(
 			client.expect_header(genesis_hash).expect("there should be header"),
 		)));
-		assert_eq!(txpool.ready().count(), MAX_SKIPPED_TRANSACTIONS * 2 + 4);
+		assert_eq!(txpool.ready().count(), DEFAULT_MAX_SKIPPED_TRANSACTIONS * 2 + 4);
 
 		let mut proposer_factory =
 			ProposerFactory::new(spawner.clone(), client.clone(), txpool.clone(), None, None);
@@ -1145,8 +1257,65 @@ mod tests {
 			"Block shall contain one or two extrinsics."
 		);
 		assert!(
-			cell2.lock().0 > MAX_SKIPPED_TRANSACTIONS,
+			cell2.lock().0 > DEFAULT_MAX_SKIPPED_TRANSACTIONS,
 			"Not enough calls to current time, which indicates the test might have ended because of deadline, not soft deadline"
 		);
 	}
+
+	#[test]
+	fn soft_deadline_policy_skips_transactions_not_matching() {
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let txpool = BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			spawner.clone(),
+			client.clone(),
+		);
+		let genesis_hash = client.info().genesis_hash;
+
+		block_on(txpool.submit_at(genesis_hash, SOURCE, vec![extrinsic(0), extrinsic(1)])).unwrap();
+		block_on(txpool.maintain(chain_event(
+			client.expect_header(genesis_hash).expect("there should be header"),
+		)));
+
+		let mut proposer_factory =
+			ProposerFactory::new(spawner.clone(), client.clone(), txpool.clone(), None, None);
+		// soft deadline is reached right away.
+		proposer_factory.set_soft_deadline(Percent::from_percent(0));
+		proposer_factory.set_soft_deadline_policy(SoftDeadlinePolicy {
+			min_priority: Some(TransactionPriority::max_value()),
+			max_transaction_size: None,
+		});
+
+		let proposer = block_on(
+			proposer_factory
+				.init(&client.expect_header(genesis_hash).expect("there should be header")),
+		)
+		.unwrap();
+
+		// when
+		let deadline = time::Duration::from_secs(300);
+		let block =
+			block_on(proposer.propose(Default::default(), Default::default(), deadline, None))
+				.map(|r| r.block)
+				.unwrap();
+
+		// then no transaction is included, but they are all kept in the pool.
+		assert!(block.extrinsics().is_empty());
+		assert_eq!(txpool.ready().count(), 2);
+	}
+
+	#[test]
+	fn soft_deadline_policy_allows_works() {
+		let policy = SoftDeadlinePolicy::default();
+		assert!(policy.allows(0, usize::MAX));
+
+		let policy = SoftDeadlinePolicy { min_priority: Some(10), max_transaction_size: Some(100) };
+		assert!(policy.allows(10, 100));
+		assert!(!policy.allows(9, 100));
+		assert!(!policy.allows(10, 101));
+	}
 }
@@ -71,5 +71,12 @@
 //! ```
 
 mod basic_authorship;
+mod ordering;
 
-pub use crate::basic_authorship::{Proposer, ProposerFactory, DEFAULT_BLOCK_SIZE_LIMIT};
+pub use crate::{
+	basic_authorship::{
+		Proposer, ProposerFactory, SoftDeadlinePolicy, DEFAULT_BLOCK_SIZE_LIMIT,
+		DEFAULT_MAX_SKIPPED_TRANSACTIONS,
+	},
+	ordering::{RoundRobinOrdering, TransactionOrdering},
+};
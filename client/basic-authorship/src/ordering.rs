@@ -0,0 +1,319 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Customizable ordering of ready transactions sharing the same priority.
+//!
+//! The transaction pool hands out ready transactions ordered by priority. Transactions within the
+//! same priority band are returned in the order chosen by the pool. A [`TransactionOrdering`]
+//! passed to the [`ProposerFactory`](crate::ProposerFactory) can reorder each band before the
+//! transactions are pushed into the block.
+
+use sc_transaction_pool_api::{InPoolTransaction, ReadyTransactions};
+use sp_runtime::transaction_validity::TransactionTag;
+use std::{
+	cmp::Reverse,
+	collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+	hash::Hash,
+	sync::Arc,
+};
+
+/// Orders ready transactions that share the same priority.
+pub trait TransactionOrdering<T>: Send + Sync {
+	/// Reorder the given `band` of ready transactions.
+	///
+	/// All transactions in `band` have the same priority. The order of the band when passed in is
+	/// the order chosen by the transaction pool.
+	///
+	/// Implementations don't need to care about dependencies between transactions; a transaction
+	/// is never pushed before the transactions in the band it depends on.
+	fn order(&self, band: &mut Vec<Arc<T>>);
+}
+
+/// Round-robin ordering across senders.
+///
+/// Transactions are grouped by the key returned by the given function, usually the sender of a
+/// transaction. One transaction of every group is taken in turn, so that a single sender with many
+/// transactions can not crowd out other senders with the same priority.
+pub struct RoundRobinOrdering<F> {
+	key: F,
+}
+
+impl<F> RoundRobinOrdering<F> {
+	/// Create a new instance using `key` to group transactions.
+	pub fn new(key: F) -> Self {
+		Self { key }
+	}
+}
+
+impl<T, K, F> TransactionOrdering<T> for RoundRobinOrdering<F>
+where
+	K: Hash + Eq,
+	F: Fn(&T) -> K + Send + Sync,
+{
+	fn order(&self, band: &mut Vec<Arc<T>>) {
+		let mut groups: Vec<VecDeque<Arc<T>>> = Vec::new();
+		let mut group_of_key = HashMap::new();
+
+		for tx in band.drain(..) {
+			let index = *group_of_key.entry((self.key)(&tx)).or_insert_with(|| {
+				groups.push(VecDeque::new());
+				groups.len() - 1
+			});
+			groups[index].push_back(tx);
+		}
+
+		while !groups.is_empty() {
+			groups.retain_mut(|group| {
+				band.extend(group.pop_front());
+				!group.is_empty()
+			});
+		}
+	}
+}
+
+/// Wraps the ready transactions of the pool and applies a [`TransactionOrdering`] to every
+/// priority band.
+pub(crate) struct OrderedReadyTransactions<T: InPoolTransaction> {
+	inner: Box<dyn ReadyTransactions<Item = Arc<T>> + Send>,
+	ordering: Arc<dyn TransactionOrdering<T>>,
+	/// First transaction of the next band, already taken from `inner`.
+	next_band_start: Option<Arc<T>>,
+	/// Transactions of the current band in the order they are returned.
+	band: VecDeque<Arc<T>>,
+	/// Tags provided by transactions reported as invalid.
+	invalid_tags: HashSet<TransactionTag>,
+}
+
+impl<T: InPoolTransaction> OrderedReadyTransactions<T> {
+	pub(crate) fn new(
+		inner: Box<dyn ReadyTransactions<Item = Arc<T>> + Send>,
+		ordering: Arc<dyn TransactionOrdering<T>>,
+	) -> Self {
+		Self {
+			inner,
+			ordering,
+			next_band_start: None,
+			band: VecDeque::new(),
+			invalid_tags: HashSet::new(),
+		}
+	}
+
+	/// Take the next priority band from the inner iterator and order it.
+	fn fill_band(&mut self) {
+		let Some(first) = self.next_band_start.take().or_else(|| self.inner.next()) else { return };
+
+		let priority = *first.priority();
+		let mut band = vec![first];
+		for tx in self.inner.by_ref() {
+			if *tx.priority() != priority {
+				self.next_band_start = Some(tx);
+				break
+			}
+			band.push(tx);
+		}
+
+		self.ordering.order(&mut band);
+		self.band = respect_dependencies(band);
+	}
+}
+
+/// Returns the transactions in the given order, but makes sure a transaction is only returned after
+/// the transactions in the band that provide its requirements.
+fn respect_dependencies<T: InPoolTransaction>(band: Vec<Arc<T>>) -> VecDeque<Arc<T>> {
+	let provider_of_tag: HashMap<&TransactionTag, usize> = band
+		.iter()
+		.enumerate()
+		.flat_map(|(index, tx)| tx.provides().iter().map(move |tag| (tag, index)))
+		.collect();
+
+	let mut dependants = vec![Vec::new(); band.len()];
+	let mut missing = vec![0usize; band.len()];
+	for (index, tx) in band.iter().enumerate() {
+		let providers = tx
+			.requires()
+			.iter()
+			.filter_map(|tag| provider_of_tag.get(tag).copied())
+			.filter(|provider| *provider != index)
+			.collect::<HashSet<_>>();
+		missing[index] = providers.len();
+		for provider in providers {
+			dependants[provider].push(index);
+		}
+	}
+
+	let mut unlocked = (0..band.len())
+		.filter(|index| missing[*index] == 0)
+		.map(Reverse)
+		.collect::<BinaryHeap<_>>();
+	let mut order = Vec::with_capacity(band.len());
+	while let Some(Reverse(index)) = unlocked.pop() {
+		order.push(index);
+		for dependant in &dependants[index] {
+			missing[*dependant] -= 1;
+			if missing[*dependant] == 0 {
+				unlocked.push(Reverse(*dependant));
+			}
+		}
+	}
+
+	let mut band = band.into_iter().map(Some).collect::<Vec<_>>();
+	order.into_iter().filter_map(|index| band[index].take()).collect()
+}
+
+impl<T: InPoolTransaction> Iterator for OrderedReadyTransactions<T> {
+	type Item = Arc<T>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if self.band.is_empty() {
+				self.fill_band();
+			}
+
+			let tx = self.band.pop_front()?;
+			if tx.requires().iter().any(|tag| self.invalid_tags.contains(tag)) {
+				// The transaction depends on an invalid one that was reported after the band was
+				// taken from the pool.
+				self.report_invalid(&tx);
+				continue
+			}
+
+			return Some(tx)
+		}
+	}
+}
+
+impl<T: InPoolTransaction> ReadyTransactions for OrderedReadyTransactions<T> {
+	fn report_invalid(&mut self, tx: &Self::Item) {
+		self.invalid_tags.extend(tx.provides().iter().cloned());
+		self.inner.report_invalid(tx);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::transaction_validity::{TransactionLongevity, TransactionPriority};
+
+	#[derive(Debug, PartialEq)]
+	struct Tx {
+		id: u64,
+		sender: u8,
+		priority: TransactionPriority,
+		requires: Vec<TransactionTag>,
+		provides: Vec<TransactionTag>,
+	}
+
+	impl InPoolTransaction for Tx {
+		type Transaction = u64;
+		type Hash = u64;
+
+		fn data(&self) -> &u64 {
+			&self.id
+		}
+		fn hash(&self) -> &u64 {
+			&self.id
+		}
+		fn priority(&self) -> &TransactionPriority {
+			&self.priority
+		}
+		fn longevity(&self) -> &TransactionLongevity {
+			&TransactionLongevity::MAX
+		}
+		fn requires(&self) -> &[TransactionTag] {
+			&self.requires
+		}
+		fn provides(&self) -> &[TransactionTag] {
+			&self.provides
+		}
+		fn is_propagable(&self) -> bool {
+			true
+		}
+	}
+
+	fn tx(id: u64, sender: u8, nonce: u8, priority: TransactionPriority) -> Arc<Tx> {
+		let requires = if nonce == 0 { vec![] } else { vec![vec![sender, nonce - 1]] };
+		Arc::new(Tx { id, sender, priority, requires, provides: vec![vec![sender, nonce]] })
+	}
+
+	struct Ready(std::vec::IntoIter<Arc<Tx>>);
+
+	impl Iterator for Ready {
+		type Item = Arc<Tx>;
+
+		fn next(&mut self) -> Option<Self::Item> {
+			self.0.next()
+		}
+	}
+
+	impl ReadyTransactions for Ready {
+		fn report_invalid(&mut self, _tx: &Self::Item) {}
+	}
+
+	struct Reversed;
+
+	impl TransactionOrdering<Tx> for Reversed {
+		fn order(&self, band: &mut Vec<Arc<Tx>>) {
+			band.reverse();
+		}
+	}
+
+	fn ordered(
+		txs: Vec<Arc<Tx>>,
+		ordering: impl TransactionOrdering<Tx> + 'static,
+	) -> OrderedReadyTransactions<Tx> {
+		OrderedReadyTransactions::new(Box::new(Ready(txs.into_iter())), Arc::new(ordering))
+	}
+
+	fn ids(iter: impl Iterator<Item = Arc<Tx>>) -> Vec<u64> {
+		iter.map(|tx| tx.id).collect()
+	}
+
+	#[test]
+	fn orders_within_priority_band_only() {
+		let txs = vec![tx(1, 1, 0, 10), tx(2, 2, 0, 10), tx(3, 3, 0, 5), tx(4, 4, 0, 5)];
+
+		assert_eq!(ids(ordered(txs, Reversed)), vec![2, 1, 4, 3]);
+	}
+
+	#[test]
+	fn round_robin_across_senders() {
+		let txs = vec![tx(1, 1, 0, 10), tx(2, 1, 1, 10), tx(3, 1, 2, 10), tx(4, 2, 0, 10)];
+
+		let ordering = RoundRobinOrdering::new(|tx: &Tx| tx.sender);
+		assert_eq!(ids(ordered(txs, ordering)), vec![1, 4, 2, 3]);
+	}
+
+	#[test]
+	fn dependencies_are_respected() {
+		let txs = vec![tx(1, 1, 0, 10), tx(2, 1, 1, 10), tx(3, 2, 0, 10)];
+
+		assert_eq!(ids(ordered(txs, Reversed)), vec![3, 1, 2]);
+	}
+
+	#[test]
+	fn dependants_of_invalid_transactions_are_skipped() {
+		let txs = vec![tx(1, 1, 0, 10), tx(2, 1, 1, 10), tx(3, 2, 0, 10)];
+		let mut iter = ordered(txs, RoundRobinOrdering::new(|tx: &Tx| tx.sender));
+
+		let first = iter.next().unwrap();
+		assert_eq!(first.id, 1);
+		iter.report_invalid(&first);
+
+		assert_eq!(ids(iter), vec![3]);
+	}
+}
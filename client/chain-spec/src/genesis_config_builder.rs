@@ -26,7 +26,7 @@ use sp_core::{
 	storage::Storage,
 	traits::{CallContext, CodeExecutor, Externalities, FetchRuntimeCode, RuntimeCode},
 };
-use sp_genesis_builder::Result as BuildResult;
+use sp_genesis_builder::{PresetId, Result as BuildResult};
 use sp_state_machine::BasicExternalities;
 use std::borrow::Cow;
 
@@ -94,6 +94,38 @@ where
 		Ok(from_slice(&default_config[..]).expect("returned value is json. qed."))
 	}
 
+	/// Returns the names of the `GenesisConfig` presets provided by the `runtime`.
+	///
+	/// Calls [`GenesisBuilder::preset_names`](sp_genesis_builder::GenesisBuilder::preset_names) in
+	/// the `runtime`, which is only available since version 2 of the API.
+	pub fn preset_names(&self) -> core::result::Result<Vec<String>, String> {
+		let mut t = BasicExternalities::new_empty();
+		let call_result = self
+			.call(&mut t, "GenesisBuilder_preset_names", &[])
+			.map_err(|e| format!("wasm call error {e}"))?;
+		let names = Vec::<PresetId>::decode(&mut &call_result[..])
+			.map_err(|e| format!("scale codec error: {e}"))?;
+		Ok(names.into_iter().map(String::from).collect())
+	}
+
+	/// Returns the `GenesisConfig` preset with the given name, or the default `GenesisConfig` if
+	/// no name is given.
+	///
+	/// Calls [`GenesisBuilder::get_preset`](sp_genesis_builder::GenesisBuilder::get_preset) in the
+	/// `runtime`, which is only available since version 2 of the API. A named preset is a patch to
+	/// be applied on top of the default config.
+	pub fn get_named_preset(&self, name: Option<&str>) -> core::result::Result<Value, String> {
+		let mut t = BasicExternalities::new_empty();
+		let id = name.map(|name| PresetId::Owned(name.to_string()));
+		let call_result = self
+			.call(&mut t, "GenesisBuilder_get_preset", &id.encode())
+			.map_err(|e| format!("wasm call error {e}"))?;
+		let preset = Option::<Vec<u8>>::decode(&mut &call_result[..])
+			.map_err(|e| format!("scale codec error: {e}"))?
+			.ok_or_else(|| format!("preset {:?} is not provided by the runtime", name))?;
+		from_slice(&preset[..]).map_err(|e| format!("preset is not a valid json: {e}"))
+	}
+
 	/// Build the given `GenesisConfig` and returns the genesis state.
 	///
 	/// Calls [`GenesisBuilder::build_config`](sp_genesis_builder::GenesisBuilder::build_config)
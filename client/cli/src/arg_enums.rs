@@ -296,3 +296,57 @@ impl Into<sc_network::config::SyncMode> for SyncMode {
 		}
 	}
 }
+
+/// Format of the periodic status written by the informant.
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+#[value(rename_all = "kebab-case")]
+pub enum InformantFormat {
+	/// A human readable line in the log.
+	Console,
+	/// A JSON record per line.
+	Json,
+}
+
+/// How the interval between authored blocks grows while finality is lagging.
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+#[value(rename_all = "kebab-case")]
+pub enum BackoffGrowth {
+	/// Grow the interval by one slot every `--backoff-authoring-bias` unfinalized blocks.
+	Linear,
+	/// Double the interval every `--backoff-authoring-bias` unfinalized blocks.
+	Exponential,
+}
+
+impl From<BackoffGrowth> for sc_consensus_slots::BackoffGrowth {
+	fn from(growth: BackoffGrowth) -> Self {
+		match growth {
+			BackoffGrowth::Linear => sc_consensus_slots::BackoffGrowth::Linear,
+			BackoffGrowth::Exponential => sc_consensus_slots::BackoffGrowth::Exponential,
+		}
+	}
+}
+
+/// Class of chain data that can be removed by `purge-chain`.
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+#[value(rename_all = "kebab-case")]
+pub enum ChainData {
+	/// The state of all blocks. The node has to re-sync the state afterwards.
+	State,
+	/// Block bodies and indexed transactions.
+	Bodies,
+	/// Block justifications.
+	Justifications,
+	/// Offchain worker local storage.
+	Offchain,
+}
+
+impl From<ChainData> for sc_client_db::ChainData {
+	fn from(data: ChainData) -> Self {
+		match data {
+			ChainData::State => sc_client_db::ChainData::State,
+			ChainData::Bodies => sc_client_db::ChainData::Bodies,
+			ChainData::Justifications => sc_client_db::ChainData::Justifications,
+			ChainData::Offchain => sc_client_db::ChainData::Offchain,
+		}
+	}
+}
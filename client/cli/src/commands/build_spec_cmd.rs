@@ -23,12 +23,16 @@ use crate::{
 };
 use clap::Parser;
 use log::info;
+use sc_chain_spec::{json_merge, GenesisConfigBuilderRuntimeCaller};
 use sc_network::config::build_multiaddr;
 use sc_service::{
 	config::{MultiaddrWithPeerId, NetworkConfiguration},
 	ChainSpec,
 };
-use std::io::Write;
+use serde_json::Value;
+use sp_core::storage::well_known_keys;
+use sp_runtime::BuildStorage;
+use std::{io::Write, path::PathBuf};
 
 /// The `build-spec` command used to build a specification.
 #[derive(Debug, Clone, Parser)]
@@ -43,6 +47,28 @@ pub struct BuildSpecCmd {
 	#[arg(long)]
 	pub disable_default_bootnode: bool,
 
+	/// Runtime wasm blob used to build the genesis config through its `GenesisBuilder` API.
+	///
+	/// Defaults to the runtime code of the chain specification. Only used together with
+	/// `--preset`, `--patch` or `--list-presets`.
+	#[arg(long, value_name = "PATH")]
+	pub runtime: Option<PathBuf>,
+
+	/// Build the genesis config from the runtime provided preset with the given name.
+	///
+	/// The runtime's default genesis config is used when only `--patch` is given.
+	#[arg(long, value_name = "NAME")]
+	pub preset: Option<String>,
+
+	/// JSON fragment merged into the genesis config. May be given multiple times, later fragments
+	/// take precedence. `null` values remove keys.
+	#[arg(long, value_name = "PATH")]
+	pub patch: Vec<PathBuf>,
+
+	/// Print the names of the genesis config presets provided by the runtime and exit.
+	#[arg(long)]
+	pub list_presets: bool,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub shared_params: SharedParams,
@@ -72,7 +98,14 @@ impl BuildSpecCmd {
 			spec.add_boot_node(addr)
 		}
 
-		let json = sc_service::chain_ops::build_spec(&*spec, raw_output)?;
+		let json = if self.uses_genesis_builder() {
+			match self.build_with_genesis_builder(spec, raw_output)? {
+				Some(json) => json,
+				None => return Ok(()),
+			}
+		} else {
+			sc_service::chain_ops::build_spec(&*spec, raw_output)?
+		};
 		if std::io::stdout().write_all(json.as_bytes()).is_err() {
 			let _ = std::io::stderr().write_all(b"Error writing to stdout\n");
 		}
@@ -80,6 +113,74 @@ impl BuildSpecCmd {
 	}
 }
 
+impl BuildSpecCmd {
+	fn uses_genesis_builder(&self) -> bool {
+		self.runtime.is_some() ||
+			self.preset.is_some() ||
+			!self.patch.is_empty() ||
+			self.list_presets
+	}
+
+	/// Build the genesis config through the runtime's `GenesisBuilder` API.
+	///
+	/// Returns `None` if only the preset names were requested.
+	fn build_with_genesis_builder(
+		&self,
+		mut spec: Box<dyn ChainSpec>,
+		raw: bool,
+	) -> error::Result<Option<String>> {
+		let code = match &self.runtime {
+			Some(path) => std::fs::read(path)?,
+			None => spec
+				.as_storage_builder()
+				.build_storage()?
+				.top
+				.remove(well_known_keys::CODE)
+				.ok_or("Chain specification does not contain runtime code, use `--runtime`")?,
+		};
+		let caller = GenesisConfigBuilderRuntimeCaller::<()>::new(&code[..]);
+
+		if self.list_presets {
+			for name in caller.preset_names()? {
+				println!("{}", name);
+			}
+			return Ok(None)
+		}
+
+		let mut patch = match &self.preset {
+			Some(name) => caller.get_named_preset(Some(name))?,
+			None => Value::Object(Default::default()),
+		};
+		for path in &self.patch {
+			let fragment = std::fs::read(path)?;
+			let fragment = serde_json::from_slice::<Value>(&fragment)
+				.map_err(|e| format!("Patch file {} is not a valid json: {}", path.display(), e))?;
+			json_merge(&mut patch, fragment);
+		}
+
+		if raw {
+			let mut storage = caller.get_storage_for_patch(patch)?;
+			storage.top.insert(well_known_keys::CODE.to_vec(), code);
+			spec.set_storage(storage);
+			return Ok(Some(sc_service::chain_ops::build_spec(&*spec, true)?))
+		}
+
+		let mut json =
+			serde_json::from_str::<Value>(&sc_service::chain_ops::build_spec(&*spec, false)?)
+				.map_err(|e| format!("Error parsing spec json: {}", e))?;
+		json["genesis"] = serde_json::json!({
+			"runtimeGenesis": {
+				"code": sp_core::bytes::to_hex(&code, false),
+				"patch": patch,
+			}
+		});
+		Ok(Some(
+			serde_json::to_string_pretty(&json)
+				.map_err(|e| format!("Error generating spec json: {}", e))?,
+		))
+	}
+}
+
 impl CliConfiguration for BuildSpecCmd {
 	fn shared_params(&self) -> &SharedParams {
 		&self.shared_params
@@ -0,0 +1,121 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{BlockNumberOrHash, DatabaseParams, PruningParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use sc_client_api::{backend::Backend, BlockBackend, HeaderBackend};
+use sc_service::chain_ops::BlockTraceFilter;
+use sp_api::{Core, Metadata, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use std::{fmt::Debug, io::Write, str::FromStr, sync::Arc};
+
+/// The `execute-block` command used to re-execute a block from the database.
+///
+/// Prints the outcome of every extrinsic, the storage changes and the computed state root, which
+/// helps to investigate blocks that fail to import because of a state root mismatch.
+#[derive(Debug, Clone, Parser)]
+pub struct ExecuteBlockCmd {
+	/// Block hash or number.
+	#[arg(value_name = "HASH or NUMBER")]
+	pub input: BlockNumberOrHash,
+
+	/// Record the spans and events emitted while executing the block and add them to the report.
+	#[arg(long)]
+	pub trace: bool,
+
+	/// Comma separated tracing targets of the recorded spans.
+	#[arg(long, value_name = "TARGETS", default_value = "pallet,frame,state")]
+	pub trace_targets: String,
+
+	/// Comma separated hex encoded storage key prefixes of the recorded events. Records the
+	/// events of all keys by default.
+	#[arg(long, value_name = "PREFIXES", default_value = "")]
+	pub trace_storage_keys: String,
+
+	/// Comma separated host function names of the recorded events. Records all host functions by
+	/// default.
+	#[arg(long, value_name = "METHODS", default_value = "")]
+	pub trace_methods: String,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ExecuteBlockCmd {
+	/// Run the `execute-block` command
+	///
+	/// Fails after printing the report if the block could not be executed or the state root
+	/// does not match.
+	pub async fn run<B, BA, C>(&self, client: Arc<C>, backend: Arc<BA>) -> error::Result<()>
+	where
+		B: BlockT + 'static,
+		BA: Backend<B>,
+		C: BlockBackend<B> + HeaderBackend<B> + ProvideRuntimeApi<B> + Send + Sync + 'static,
+		C::Api: Core<B> + BlockBuilder<B> + Metadata<B>,
+		<B::Hash as FromStr>::Err: Debug,
+		<<B::Header as HeaderT>::Number as FromStr>::Err: Debug,
+	{
+		let hash = client.expect_block_hash_from_id(&self.input.parse()?)?;
+		let trace = self.trace.then(|| BlockTraceFilter {
+			targets: self.trace_targets.clone(),
+			storage_keys: self.trace_storage_keys.clone(),
+			methods: self.trace_methods.clone(),
+		});
+
+		let report = sc_service::chain_ops::execute_block(client, backend, hash, trace)?;
+		if std::io::stdout().write_all(report.to_string().as_bytes()).is_err() {
+			let _ = std::io::stderr().write_all(b"Error writing to stdout\n");
+		}
+
+		if let Some(error) = &report.execution_error {
+			return Err(format!("Block execution failed: {}", error).into())
+		}
+		if !report.state_root_matches() {
+			return Err("State root mismatch".into())
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ExecuteBlockCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
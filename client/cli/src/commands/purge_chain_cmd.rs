@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
+	arg_enums::ChainData,
 	error,
 	params::{DatabaseParams, SharedParams},
 	CliConfiguration,
@@ -29,13 +30,23 @@ use std::{
 	io::{self, Write},
 };
 
-/// The `purge-chain` command used to remove the whole chain.
+/// The `purge-chain` command used to remove the whole chain or parts of its data.
 #[derive(Debug, Clone, Parser)]
 pub struct PurgeChainCmd {
 	/// Skip interactive prompt by answering yes automatically.
 	#[arg(short = 'y')]
 	pub yes: bool,
 
+	/// Only remove the given classes of data, keeping the rest of the database.
+	///
+	/// Can be given multiple times or as a comma separated list.
+	#[arg(long, value_name = "DATA", value_enum, value_delimiter = ',')]
+	pub only: Vec<ChainData>,
+
+	/// Report how much data would be removed by `--only` without removing anything.
+	#[arg(long, requires = "only")]
+	pub dry_run: bool,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub shared_params: SharedParams,
@@ -48,25 +59,17 @@ pub struct PurgeChainCmd {
 impl PurgeChainCmd {
 	/// Run the purge command
 	pub fn run(&self, database_config: DatabaseSource) -> error::Result<()> {
+		if !self.only.is_empty() {
+			return self.purge_data(&database_config)
+		}
+
 		let db_path = database_config.path().and_then(|p| p.parent()).ok_or_else(|| {
 			error::Error::Input("Cannot purge custom database implementation".into())
 		})?;
 
-		if !self.yes {
-			print!("Are you sure to remove {:?}? [y/N]: ", &db_path);
-			io::stdout().flush().expect("failed to flush stdout");
-
-			let mut input = String::new();
-			io::stdin().read_line(&mut input)?;
-			let input = input.trim();
-
-			match input.chars().next() {
-				Some('y') | Some('Y') => {},
-				_ => {
-					println!("Aborted");
-					return Ok(())
-				},
-			}
+		if !self.yes && !confirm(&format!("Are you sure to remove {:?}?", &db_path))? {
+			println!("Aborted");
+			return Ok(())
 		}
 
 		match fs::remove_dir_all(&db_path) {
@@ -81,6 +84,46 @@ impl PurgeChainCmd {
 			Err(err) => Result::Err(err.into()),
 		}
 	}
+
+	/// Remove the classes of data selected with `--only`.
+	fn purge_data(&self, database_config: &DatabaseSource) -> error::Result<()> {
+		let data = self.only.iter().map(|d| (*d).into()).collect::<Vec<_>>();
+
+		let reports = sc_client_db::purge_chain_data(database_config, &data, true)?;
+		for report in &reports {
+			println!("{}: {} entries, {} bytes", report.data, report.entries, report.bytes);
+		}
+
+		if self.dry_run {
+			return Ok(())
+		}
+
+		if self.only.contains(&ChainData::State) {
+			eprintln!(
+				"Purging the state requires the node to re-sync the state (e.g. with `--sync warp`) \
+				 before importing blocks."
+			);
+		}
+
+		if !self.yes && !confirm("Are you sure to remove the data listed above?")? {
+			println!("Aborted");
+			return Ok(())
+		}
+
+		sc_client_db::purge_chain_data(database_config, &data, false)?;
+		println!("Removed {} bytes.", reports.iter().map(|r| r.bytes).sum::<u64>());
+		Ok(())
+	}
+}
+
+fn confirm(question: &str) -> io::Result<bool> {
+	print!("{} [y/N]: ", question);
+	io::stdout().flush().expect("failed to flush stdout");
+
+	let mut input = String::new();
+	io::stdin().read_line(&mut input)?;
+
+	Ok(matches!(input.trim().chars().next(), Some('y') | Some('Y')))
 }
 
 impl CliConfiguration for PurgeChainCmd {
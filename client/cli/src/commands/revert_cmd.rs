@@ -22,6 +22,7 @@ use crate::{
 	CliConfiguration,
 };
 use clap::Parser;
+use log::warn;
 use sc_client_api::{Backend, UsageProvider};
 use sc_service::chain_ops::revert_chain;
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor};
@@ -34,6 +35,14 @@ pub struct RevertCmd {
 	#[arg(default_value = "256")]
 	pub num: GenericNumber,
 
+	/// Also revert finalized blocks.
+	///
+	/// This is meant for recovering from a finality bug and may leave the node inconsistent with
+	/// the rest of the network. The consensus data passed to the revert handler must support it,
+	/// e.g. GRANDPA can not revert past the last authority set change.
+	#[arg(long)]
+	pub unsafe_revert_finalized: bool,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub shared_params: SharedParams,
@@ -66,10 +75,15 @@ impl RevertCmd {
 		<<<B as BlockT>::Header as HeaderT>::Number as FromStr>::Err: Debug,
 	{
 		let blocks = self.num.parse()?;
+		if self.unsafe_revert_finalized {
+			warn!(
+				"Reverting finalized blocks, the node may end up on a different chain than its peers"
+			);
+		}
 		if let Some(aux_revert) = aux_revert {
 			aux_revert(client.clone(), backend.clone(), blocks)?;
 		}
-		revert_chain(client, backend, blocks)?;
+		revert_chain(client, backend, blocks, self.unsafe_revert_finalized)?;
 
 		Ok(())
 	}
@@ -17,7 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	arg_enums::{Cors, RpcMethods},
+	arg_enums::{Cors, InformantFormat, RpcMethods},
 	error::{Error, Result},
 	params::{
 		ImportParams, KeystoreParams, NetworkParams, OffchainWorkerParams, SharedParams,
@@ -30,13 +30,18 @@ use crate::{
 use clap::Parser;
 use regex::Regex;
 use sc_service::{
-	config::{BasePath, PrometheusConfig, RpcBatchRequestConfig, TransactionPoolOptions},
+	config::{
+		BasePath, PrometheusConfig, RpcBatchRequestConfig, StatusOutput, TransactionPoolOptions,
+	},
 	ChainSpec, Role,
 };
-use sc_telemetry::TelemetryEndpoints;
+use sc_telemetry::{DiskBufferConfig, TelemetryEndpoints};
+#[cfg(unix)]
+use std::path::PathBuf;
 use std::{
 	net::{IpAddr, Ipv4Addr, SocketAddr},
 	num::NonZeroU32,
+	time::Duration,
 };
 
 /// The `run` command used to run a node.
@@ -57,6 +62,13 @@ pub struct RunCmd {
 	#[arg(long)]
 	pub no_grandpa: bool,
 
+	/// Start with GRANDPA voting paused.
+	///
+	/// The node follows GRANDPA as a non-voter until voting is resumed through the
+	/// `grandpa_resumeVoting` RPC.
+	#[arg(long)]
+	pub grandpa_paused: bool,
+
 	/// Listen to all RPC interfaces (default: local).
 	///
 	/// Not all RPC methods are safe to be exposed publicly.
@@ -94,6 +106,14 @@ pub struct RunCmd {
 	#[arg(long)]
 	pub rpc_rate_limit: Option<NonZeroU32>,
 
+	/// Log RPC calls taking longer than the given number of milliseconds.
+	///
+	/// Such calls are logged as warnings together with their (truncated) parameters. Only the
+	/// parameters of the `chain`, `state` and similar methods that take public data are logged.
+	/// This is disabled by default.
+	#[arg(long, value_name = "MS")]
+	pub rpc_log_slow_calls: Option<u64>,
+
 	/// Set the maximum RPC request payload size for both HTTP and WS in megabytes.
 	#[arg(long, default_value_t = RPC_DEFAULT_MAX_REQUEST_SIZE_MB)]
 	pub rpc_max_request_size: u32,
@@ -236,6 +256,20 @@ pub struct RunCmd {
 	#[arg(long)]
 	pub force_authoring: bool,
 
+	/// Format of the periodic status written by the informant.
+	///
+	/// `json` replaces the status line in the log by a JSON record per line, written to stdout or
+	/// to `--informant-socket`, for consumption by supervisors and log pipelines.
+	#[arg(long, value_name = "FORMAT", value_enum, default_value_t = InformantFormat::Console)]
+	pub informant_format: InformantFormat,
+
+	/// Write the JSON status records to the unix socket at the given path instead of stdout.
+	///
+	/// The socket must be bound by the consumer. Records are dropped while it is not connected.
+	#[cfg(unix)]
+	#[arg(long, value_name = "PATH")]
+	pub informant_socket: Option<PathBuf>,
+
 	/// Run a temporary node.
 	///
 	/// A temporary directory will be created to store the configuration and will be deleted
@@ -341,6 +375,13 @@ impl CliConfiguration for RunCmd {
 		})
 	}
 
+	fn telemetry_disk_buffer(&self, config_dir: &PathBuf) -> Result<Option<DiskBufferConfig>> {
+		Ok(self.telemetry_params.telemetry_disk_buffer.map(|mib| DiskBufferConfig {
+			directory: config_dir.join("telemetry"),
+			max_bytes_per_endpoint: mib.saturating_mul(1024 * 1024),
+		}))
+	}
+
 	fn role(&self, is_dev: bool) -> Result<Role> {
 		let keyring = self.get_keyring();
 		let is_authority = self.validator || is_dev || keyring.is_some();
@@ -353,6 +394,24 @@ impl CliConfiguration for RunCmd {
 		Ok(self.shared_params.dev || self.force_authoring)
 	}
 
+	fn informant_status_output(&self) -> Result<StatusOutput> {
+		#[cfg(unix)]
+		if let Some(path) = &self.informant_socket {
+			if self.informant_format != InformantFormat::Json {
+				return Err(Error::Input(
+					"--informant-socket requires --informant-format json".into(),
+				))
+			}
+
+			return Ok(StatusOutput::JsonUnixSocket(path.clone()))
+		}
+
+		Ok(match self.informant_format {
+			InformantFormat::Console => StatusOutput::Console,
+			InformantFormat::Json => StatusOutput::JsonStdout,
+		})
+	}
+
 	fn prometheus_config(
 		&self,
 		default_listen_port: u16,
@@ -367,6 +426,10 @@ impl CliConfiguration for RunCmd {
 		Ok(self.no_grandpa)
 	}
 
+	fn grandpa_paused(&self) -> Result<bool> {
+		Ok(self.grandpa_paused)
+	}
+
 	fn rpc_max_connections(&self) -> Result<u32> {
 		Ok(self.rpc_max_connections)
 	}
@@ -439,6 +502,10 @@ impl CliConfiguration for RunCmd {
 		Ok(self.rpc_rate_limit)
 	}
 
+	fn rpc_slow_call_threshold(&self) -> Result<Option<Duration>> {
+		Ok(self.rpc_log_slow_calls.map(Duration::from_millis))
+	}
+
 	fn transaction_pool(&self, is_dev: bool) -> Result<TransactionPoolOptions> {
 		Ok(self.pool_config.transaction_pool(is_dev))
 	}
@@ -28,13 +28,13 @@ use sc_service::{
 	config::{
 		BasePath, Configuration, DatabaseSource, KeystoreConfig, NetworkConfiguration,
 		NodeKeyConfig, OffchainWorkerConfig, OutputFormat, PrometheusConfig, PruningMode, Role,
-		RpcBatchRequestConfig, RpcMethods, TelemetryEndpoints, TransactionPoolOptions,
-		WasmExecutionMethod,
+		RpcBatchRequestConfig, RpcMethods, StatusOutput, TelemetryDiskBufferConfig,
+		TelemetryEndpoints, TransactionPoolOptions, WasmExecutionMethod,
 	},
 	BlocksPruning, ChainSpec, TracingReceiver,
 };
 use sc_tracing::logging::LoggerBuilder;
-use std::{net::SocketAddr, num::NonZeroU32, path::PathBuf};
+use std::{net::SocketAddr, num::NonZeroU32, path::PathBuf, time::Duration};
 
 /// The maximum number of characters for a node name.
 pub(crate) const NODE_NAME_MAX_LENGTH: usize = 64;
@@ -349,6 +349,11 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(None)
 	}
 
+	/// Threshold above which RPC calls are logged as slow.
+	fn rpc_slow_call_threshold(&self) -> Result<Option<Duration>> {
+		Ok(None)
+	}
+
 	/// Get the prometheus configuration (`None` if disabled)
 	///
 	/// By default this is `None`.
@@ -370,6 +375,16 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(chain_spec.telemetry_endpoints().clone())
 	}
 
+	/// Get the configuration of the buffering of telemetry messages on disk (`None` if disabled)
+	///
+	/// By default this is `None`.
+	fn telemetry_disk_buffer(
+		&self,
+		_config_dir: &PathBuf,
+	) -> Result<Option<TelemetryDiskBufferConfig>> {
+		Ok(None)
+	}
+
 	/// Get the default value for heap pages
 	///
 	/// By default this is `None`.
@@ -400,6 +415,13 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(Default::default())
 	}
 
+	/// Returns `Ok(true)` if grandpa voting should start paused
+	///
+	/// By default this is `false`.
+	fn grandpa_paused(&self) -> Result<bool> {
+		Ok(Default::default())
+	}
+
 	/// Get the development key seed from the current object
 	///
 	/// By default this is `None`.
@@ -486,6 +508,7 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		let is_validator = role.is_authority();
 		let keystore = self.keystore_config(&config_dir)?;
 		let telemetry_endpoints = self.telemetry_endpoints(&chain_spec)?;
+		let telemetry_disk_buffer = self.telemetry_disk_buffer(&config_dir)?;
 		let runtime_cache_size = self.runtime_cache_size()?;
 
 		Ok(Configuration {
@@ -523,13 +546,16 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			rpc_message_buffer_capacity: self.rpc_buffer_capacity_per_connection()?,
 			rpc_batch_config: self.rpc_batch_config()?,
 			rpc_rate_limit: self.rpc_rate_limit()?,
+			rpc_slow_call_threshold: self.rpc_slow_call_threshold()?,
 			prometheus_config: self
 				.prometheus_config(DCV::prometheus_listen_port(), &chain_spec)?,
 			telemetry_endpoints,
+			telemetry_disk_buffer,
 			default_heap_pages: self.default_heap_pages()?,
 			offchain_worker: self.offchain_worker(&role)?,
 			force_authoring: self.force_authoring()?,
 			disable_grandpa: self.disable_grandpa()?,
+			grandpa_paused: self.grandpa_paused()?,
 			dev_key_seed: self.dev_key_seed(is_dev)?,
 			tracing_targets: self.tracing_targets()?,
 			tracing_receiver: self.tracing_receiver()?,
@@ -538,7 +564,10 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 			announce_block: self.announce_block()?,
 			role,
 			base_path,
-			informant_output_format: OutputFormat { enable_color: !self.disable_log_color()? },
+			informant_output_format: OutputFormat {
+				enable_color: !self.disable_log_color()?,
+				status_output: self.informant_status_output()?,
+			},
 			runtime_cache_size,
 		})
 	}
@@ -568,6 +597,13 @@ pub trait CliConfiguration<DCV: DefaultConfigurationValues = ()>: Sized {
 		Ok(self.shared_params().disable_log_color())
 	}
 
+	/// Get where the informant writes the periodic status to.
+	///
+	/// By default this is the human readable line in the log.
+	fn informant_status_output(&self) -> Result<StatusOutput> {
+		Ok(Default::default())
+	}
+
 	/// Initialize substrate. This must be done only once per process.
 	///
 	/// This method:
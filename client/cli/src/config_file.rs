@@ -0,0 +1,248 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Loading command line options from a TOML configuration file.
+//!
+//! Every top-level key of the file is the long name of a flag of the command the `--config`
+//! option is given to, e.g. `rpc-port = 9944` is equivalent to `--rpc-port 9944`. Boolean values
+//! toggle switches and arrays repeat the flag once per element. Flags given on the command line
+//! take precedence over the ones from the file.
+
+use clap::{
+	error::{ErrorKind, Result},
+	parser::ValueSource,
+	ArgAction, ArgMatches, Command,
+};
+use std::{ffi::OsString, path::Path};
+
+/// Name of the option pointing at the configuration file.
+pub const CONFIG_FILE_FLAG: &str = "--config";
+
+/// Name of the subcommand printing the effective configuration.
+pub const EXPORT_CONFIG_SUBCOMMAND: &str = "export-config";
+
+fn error(message: impl std::fmt::Display) -> clap::Error {
+	clap::Error::raw(ErrorKind::InvalidValue, format!("{}\n", message))
+}
+
+/// Replace `--config <file>` in `args` by the flags defined in that file.
+///
+/// Flags already present in `args` are not taken from the file.
+pub fn expand_config_file(args: Vec<OsString>) -> Result<Vec<OsString>> {
+	let mut position = None;
+	let mut path = None;
+	let mut rest = Vec::with_capacity(args.len());
+	let mut iter = args.into_iter();
+
+	while let Some(arg) = iter.next() {
+		let inline = arg.to_str().and_then(|a| a.strip_prefix("--config=")).map(OsString::from);
+		let value = if inline.is_some() {
+			inline
+		} else if arg == CONFIG_FILE_FLAG {
+			Some(iter.next().ok_or_else(|| error("`--config` requires a file path"))?)
+		} else {
+			rest.push(arg);
+			continue
+		};
+
+		if path.is_some() {
+			return Err(error("`--config` may only be given once"))
+		}
+		position = Some(rest.len());
+		path = value;
+	}
+
+	let (Some(position), Some(path)) = (position, path) else { return Ok(rest) };
+	let file_args = load_config_file(Path::new(&path), &rest)?;
+	rest.splice(position..position, file_args);
+
+	Ok(rest)
+}
+
+/// Turn the configuration file at `path` into command line flags, skipping the ones that are
+/// already part of `present`.
+fn load_config_file(path: &Path, present: &[OsString]) -> Result<Vec<OsString>> {
+	let content = std::fs::read_to_string(path)
+		.map_err(|e| error(format!("Failed to read config file {}: {}", path.display(), e)))?;
+	let table: toml::Table = content
+		.parse()
+		.map_err(|e| error(format!("Failed to parse config file {}: {}", path.display(), e)))?;
+
+	let mut args = Vec::new();
+	for (key, value) in table {
+		let flag = format!("--{}", key.replace('_', "-"));
+		let given = present.iter().any(|arg| {
+			arg.to_str().map_or(false, |arg| {
+				arg == flag || arg.strip_prefix(&flag).map_or(false, |r| r.starts_with('='))
+			})
+		});
+		if given {
+			continue
+		}
+
+		let values = match value {
+			toml::Value::Array(values) => values,
+			value => vec![value],
+		};
+		for value in values {
+			match value {
+				toml::Value::Boolean(true) => args.push(flag.clone().into()),
+				toml::Value::Boolean(false) => {},
+				toml::Value::String(s) => args.extend([flag.clone().into(), s.into()]),
+				toml::Value::Integer(i) => args.extend([flag.clone().into(), i.to_string().into()]),
+				toml::Value::Float(f) => args.extend([flag.clone().into(), f.to_string().into()]),
+				other =>
+					return Err(error(format!(
+						"Unsupported value for `{}` in config file: {}",
+						key,
+						other.type_str()
+					))),
+			}
+		}
+	}
+
+	Ok(args)
+}
+
+/// Render the options explicitly set in `matches`, either on the command line or through a
+/// configuration file, as a TOML document that can be passed back via `--config`.
+///
+/// Only the innermost subcommand is exported, as that is where the file would be loaded.
+pub fn export_config(command: &Command, matches: &ArgMatches) -> String {
+	let (mut command, mut matches) = (command, matches);
+	while let Some((name, sub_matches)) = matches.subcommand() {
+		match command.find_subcommand(name) {
+			Some(sub_command) => {
+				command = sub_command;
+				matches = sub_matches;
+			},
+			None => break,
+		}
+	}
+
+	let mut table = toml::Table::new();
+	for arg in command.get_arguments() {
+		let Some(long) = arg.get_long() else { continue };
+		let id = arg.get_id().as_str();
+		if !matches!(
+			matches.value_source(id),
+			Some(ValueSource::CommandLine) | Some(ValueSource::EnvVariable)
+		) {
+			continue
+		}
+		let Some(raw) = matches.get_raw(id) else { continue };
+		let values = raw.map(|v| v.to_string_lossy().into_owned()).collect::<Vec<_>>();
+
+		let value = match arg.get_action() {
+			ArgAction::SetTrue | ArgAction::SetFalse => toml::Value::Boolean(matches.get_flag(id)),
+			ArgAction::Append if values.len() != 1 =>
+				toml::Value::Array(values.into_iter().map(toml_scalar).collect()),
+			_ => match values.into_iter().next() {
+				Some(value) => toml_scalar(value),
+				None => continue,
+			},
+		};
+		table.insert(long.to_string(), value);
+	}
+
+	toml::to_string(&table).unwrap_or_default()
+}
+
+fn toml_scalar(value: String) -> toml::Value {
+	value
+		.parse::<i64>()
+		.map(toml::Value::Integer)
+		.unwrap_or(toml::Value::String(value))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::{Arg, Command};
+	use std::io::Write;
+
+	fn args(args: &[&str]) -> Vec<OsString> {
+		args.iter().map(OsString::from).collect()
+	}
+
+	fn config_file(content: &str) -> tempfile::NamedTempFile {
+		let mut file = tempfile::NamedTempFile::new().unwrap();
+		file.write_all(content.as_bytes()).unwrap();
+		file
+	}
+
+	#[test]
+	fn command_line_takes_precedence() {
+		let file = config_file(
+			"rpc-port = 9944\nname = \"alice\"\nvalidator = true\nbootnodes = [\"a\", \"b\"]\n",
+		);
+		let path = file.path().to_str().unwrap();
+
+		let expanded = expand_config_file(args(&["node", "--config", path, "--name=bob"])).unwrap();
+
+		assert_eq!(expanded[0], "node");
+		assert!(expanded.contains(&"--rpc-port".into()));
+		assert!(expanded.contains(&"9944".into()));
+		assert!(expanded.contains(&"--validator".into()));
+		assert_eq!(expanded.iter().filter(|a| *a == "--bootnodes").count(), 2);
+		assert!(!expanded.contains(&"alice".into()));
+		assert_eq!(expanded.last().unwrap(), "--name=bob");
+	}
+
+	#[test]
+	fn without_config_args_are_unchanged() {
+		let input = args(&["node", "--dev"]);
+		assert_eq!(expand_config_file(input.clone()).unwrap(), input);
+	}
+
+	#[test]
+	fn nested_tables_are_rejected() {
+		let file = config_file("[network]\nport = 1\n");
+		let path = file.path().to_str().unwrap();
+
+		assert!(expand_config_file(args(&["node", "--config", path])).is_err());
+	}
+
+	#[test]
+	fn exported_config_round_trips() {
+		let command = Command::new("node")
+			.arg(Arg::new("rpc-port").long("rpc-port"))
+			.arg(Arg::new("validator").long("validator").action(ArgAction::SetTrue))
+			.arg(Arg::new("bootnodes").long("bootnodes").action(ArgAction::Append))
+			.arg(Arg::new("name").long("name").default_value("node"));
+
+		let matches = command.clone().get_matches_from([
+			"node",
+			"--rpc-port",
+			"9944",
+			"--validator",
+			"--bootnodes",
+			"a",
+			"--bootnodes",
+			"b",
+		]);
+		let exported = export_config(&command, &matches);
+		assert!(!exported.contains("name"));
+
+		let file = config_file(&exported);
+		let path = file.path().to_str().unwrap();
+		let expanded = expand_config_file(args(&["node", "--config", path])).unwrap();
+		let reparsed = command.clone().get_matches_from(expanded);
+		assert_eq!(export_config(&command, &reparsed), exported);
+	}
+}
@@ -24,12 +24,13 @@
 #![warn(unused_extern_crates)]
 #![warn(unused_imports)]
 
-use clap::{CommandFactory, FromArgMatches, Parser};
+use clap::{Arg, Command, CommandFactory, FromArgMatches, Parser};
 use sc_service::Configuration;
 
 pub mod arg_enums;
 pub mod commands;
 mod config;
+mod config_file;
 mod error;
 mod params;
 mod runner;
@@ -39,6 +40,7 @@ pub use arg_enums::*;
 pub use clap;
 pub use commands::*;
 pub use config::*;
+pub use config_file::*;
 pub use error::*;
 pub use params::*;
 pub use runner::*;
@@ -115,6 +117,10 @@ pub trait SubstrateCli: Sized {
 	/// [`clap::Command::propagate_version`], [`clap::Command::args_conflicts_with_subcommands`],
 	/// [`clap::Command::subcommand_negates_reqs`].
 	///
+	/// Options can also be loaded from a TOML file given through `--config`, see
+	/// [`expand_config_file`]. When the first argument is `export-config`, the effective
+	/// configuration is printed instead and the program exits.
+	///
 	/// Creates `Self` from any iterator over arguments.
 	/// Print the error message and quit the program in case of failure.
 	fn from_iter<I>(iter: I) -> Self
@@ -123,7 +129,8 @@ pub trait SubstrateCli: Sized {
 		I: IntoIterator,
 		I::Item: Into<std::ffi::OsString> + Clone,
 	{
-		let app = <Self as CommandFactory>::command();
+		let (args, export) = prepare_args(iter).unwrap_or_else(|e| e.exit());
+		let app = with_config_file_args(<Self as CommandFactory>::command());
 
 		let mut full_version = Self::impl_version();
 		full_version.push('\n');
@@ -131,7 +138,7 @@ pub trait SubstrateCli: Sized {
 		let name = Self::executable_name();
 		let author = Self::author();
 		let about = Self::description();
-		let app = app
+		let mut app = app
 			.name(name)
 			.author(author)
 			.about(about)
@@ -140,7 +147,12 @@ pub trait SubstrateCli: Sized {
 			.args_conflicts_with_subcommands(true)
 			.subcommand_negates_reqs(true);
 
-		let matches = app.try_get_matches_from(iter).unwrap_or_else(|e| e.exit());
+		let matches = app.try_get_matches_from_mut(args).unwrap_or_else(|e| e.exit());
+
+		if export {
+			print!("{}", export_config(&app, &matches));
+			std::process::exit(0);
+		}
 
 		<Self as FromArgMatches>::from_arg_matches(&matches).unwrap_or_else(|e| e.exit())
 	}
@@ -152,6 +164,9 @@ pub trait SubstrateCli: Sized {
 	/// [`clap::Command::propagate_version`], [`clap::Command::args_conflicts_with_subcommands`],
 	/// [`clap::Command::subcommand_negates_reqs`].
 	///
+	/// Options can also be loaded from a TOML file given through `--config`, see
+	/// [`expand_config_file`].
+	///
 	/// Creates `Self` from any iterator over arguments.
 	/// Print the error message and quit the program in case of failure.
 	///
@@ -165,7 +180,8 @@ pub trait SubstrateCli: Sized {
 		I: IntoIterator,
 		I::Item: Into<std::ffi::OsString> + Clone,
 	{
-		let app = <Self as CommandFactory>::command();
+		let args = expand_config_file(iter.into_iter().map(Into::into).collect())?;
+		let app = with_config_file_args(<Self as CommandFactory>::command());
 
 		let mut full_version = Self::impl_version();
 		full_version.push('\n');
@@ -175,7 +191,7 @@ pub trait SubstrateCli: Sized {
 		let about = Self::description();
 		let app = app.name(name).author(author).about(about).version(full_version);
 
-		let matches = app.try_get_matches_from(iter)?;
+		let matches = app.try_get_matches_from(args)?;
 
 		<Self as FromArgMatches>::from_arg_matches(&matches)
 	}
@@ -251,3 +267,35 @@ pub trait SubstrateCli: Sized {
 		Runner::new(config, tokio_runtime, signals)
 	}
 }
+
+/// Expand `--config` and strip a leading `export-config` from the given arguments.
+fn prepare_args<I>(iter: I) -> clap::error::Result<(Vec<std::ffi::OsString>, bool)>
+where
+	I: IntoIterator,
+	I::Item: Into<std::ffi::OsString> + Clone,
+{
+	let mut args = iter.into_iter().map(Into::into).collect::<Vec<_>>();
+	let export = args.get(1).map_or(false, |arg| arg == EXPORT_CONFIG_SUBCOMMAND);
+	if export {
+		args.remove(1);
+	}
+
+	Ok((expand_config_file(args)?, export))
+}
+
+/// Document `--config` and `export-config` in the help output.
+///
+/// Both are handled before the arguments reach clap.
+fn with_config_file_args(app: Command) -> Command {
+	app.arg(
+		Arg::new("config")
+			.long("config")
+			.value_name("PATH")
+			.global(true)
+			.help("Load options from a TOML file; command line flags take precedence"),
+	)
+	.subcommand(
+		Command::new(EXPORT_CONFIG_SUBCOMMAND)
+			.about("Print the options given after it, merged with `--config`, as TOML"),
+	)
+}
@@ -0,0 +1,64 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::arg_enums::BackoffGrowth;
+use clap::Args;
+use sc_consensus_slots::BackoffAuthoringOnFinalizedHeadLagging;
+
+/// Parameters used to decide when block authors back off because finality is lagging.
+#[derive(Debug, Clone, Args)]
+pub struct BackoffAuthoringParams {
+	/// Never back off block authoring, regardless of how far finality is lagging behind.
+	#[arg(long)]
+	pub no_backoff_authoring: bool,
+
+	/// Maximum number of slots to wait between authored blocks while backing off.
+	#[arg(long, value_name = "SLOTS", default_value_t = 100)]
+	pub backoff_max_interval: u32,
+
+	/// Number of unfinalized blocks allowed before starting to back off block authoring.
+	#[arg(long, value_name = "BLOCKS", default_value_t = 50)]
+	pub backoff_unfinalized_slack: u32,
+
+	/// Number of unfinalized blocks beyond the slack for every step of backoff.
+	///
+	/// A higher value means backing off slower as the unfinalized head of the chain grows.
+	#[arg(
+		long,
+		value_name = "BLOCKS",
+		default_value_t = 2,
+		value_parser = clap::value_parser!(u32).range(1..)
+	)]
+	pub backoff_authoring_bias: u32,
+
+	/// How the interval between authored blocks grows while backing off.
+	#[arg(long, value_name = "GROWTH", value_enum, default_value_t = BackoffGrowth::Linear)]
+	pub backoff_growth: BackoffGrowth,
+}
+
+impl BackoffAuthoringParams {
+	/// Returns the backoff strategy, or `None` if backing off is disabled.
+	pub fn strategy<N: From<u32>>(&self) -> Option<BackoffAuthoringOnFinalizedHeadLagging<N>> {
+		(!self.no_backoff_authoring).then(|| BackoffAuthoringOnFinalizedHeadLagging {
+			max_interval: self.backoff_max_interval.into(),
+			unfinalized_slack: self.backoff_unfinalized_slack.into(),
+			authoring_bias: self.backoff_authoring_bias.into(),
+			growth: self.backoff_growth.into(),
+		})
+	}
+}
@@ -35,6 +35,15 @@ pub struct KeystoreParams {
 	#[arg(long, value_name = "PATH")]
 	pub keystore_path: Option<PathBuf>,
 
+	/// Use an external signer reachable at the given URI.
+	///
+	/// The signer is expected to speak JSON-RPC over HTTP, e.g. `http://127.0.0.1:9955`. It must
+	/// run on the local host, a signer on another host has to be reached through a local proxy
+	/// which terminates TLS. Keys found in the local keystore are still used first, so keys the
+	/// signer can't handle (e.g. BABE keys) can be kept there.
+	#[arg(long, value_name = "URI")]
+	pub keystore_uri: Option<String>,
+
 	/// Use interactive shell for entering the password used by the keystore.
 	#[arg(long, conflicts_with_all = &["password", "password_filename"])]
 	pub password_interactive: bool,
@@ -80,7 +89,10 @@ impl KeystoreParams {
 			.clone()
 			.unwrap_or_else(|| config_dir.join(DEFAULT_KEYSTORE_CONFIG_PATH));
 
-		Ok(KeystoreConfig::Path { path, password })
+		Ok(match &self.keystore_uri {
+			Some(uri) => KeystoreConfig::Remote { uri: uri.clone(), path, password },
+			None => KeystoreConfig::Path { path, password },
+		})
 	}
 
 	/// helper method to fetch password from `KeyParams` or read from stdin
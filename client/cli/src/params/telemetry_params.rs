@@ -36,6 +36,14 @@ pub struct TelemetryParams {
 	/// Expected format is 'URL VERBOSITY', e.g. `--telemetry-url 'wss://foo/bar 0'`.
 	#[arg(long = "telemetry-url", value_name = "URL VERBOSITY", value_parser = parse_telemetry_endpoints)]
 	pub telemetry_endpoints: Vec<(String, u8)>,
+
+	/// Buffer telemetry messages on disk while a telemetry server can't be reached, up to the
+	/// given number of MiB per server.
+	///
+	/// The buffered messages are sent once the server can be reached again. They are kept in the
+	/// `telemetry` directory of the chain data, so they also survive restarts.
+	#[arg(long, value_name = "MiB")]
+	pub telemetry_disk_buffer: Option<u64>,
 }
 
 #[derive(Debug)]
@@ -273,12 +273,15 @@ mod tests {
 				rpc_port: 9944,
 				rpc_batch_config: sc_service::config::RpcBatchRequestConfig::Unlimited,
 				rpc_rate_limit: None,
+				rpc_slow_call_threshold: None,
 				prometheus_config: None,
 				telemetry_endpoints: None,
+				telemetry_disk_buffer: None,
 				default_heap_pages: None,
 				offchain_worker: Default::default(),
 				force_authoring: false,
 				disable_grandpa: false,
+				grandpa_paused: false,
 				dev_key_seed: None,
 				tracing_targets: None,
 				tracing_receiver: Default::default(),
@@ -438,6 +438,12 @@ pub struct BabeParams<B: BlockT, C, SC, E, I, SO, L, CIDP, BS> {
 	/// Force authoring of blocks even if we are offline
 	pub force_authoring: bool,
 
+	/// Author blocks in secondary slots.
+	///
+	/// When disabled, the node only authors blocks in slots it won through the primary VRF
+	/// lottery, even if the current epoch allows secondary slots.
+	pub author_secondary_slots: bool,
+
 	/// Strategy and parameters for backing off block production.
 	pub backoff_authoring_blocks: Option<BS>,
 
@@ -471,6 +477,7 @@ pub fn start_babe<B, C, SC, E, I, SO, CIDP, BS, L, Error>(
 		justification_sync_link,
 		create_inherent_data_providers,
 		force_authoring,
+		author_secondary_slots,
 		backoff_authoring_blocks,
 		babe_link,
 		block_proposal_slot_portion,
@@ -507,6 +514,7 @@ where
 		sync_oracle: sync_oracle.clone(),
 		justification_sync_link,
 		force_authoring,
+		author_secondary_slots,
 		backoff_authoring_blocks,
 		keystore,
 		epoch_changes: babe_link.epoch_changes.clone(),
@@ -703,6 +711,7 @@ struct BabeSlotWorker<B: BlockT, C, E, I, SO, L, BS> {
 	sync_oracle: SO,
 	justification_sync_link: L,
 	force_authoring: bool,
+	author_secondary_slots: bool,
 	backoff_authoring_blocks: Option<BS>,
 	keystore: KeystorePtr,
 	epoch_changes: SharedEpochChanges<B, Epoch>,
@@ -779,7 +788,18 @@ where
 				.viable_epoch(epoch_descriptor, |slot| Epoch::genesis(&self.config, slot))?
 				.as_ref(),
 			&self.keystore,
-		);
+		)
+		.filter(|(pre_digest, _)| {
+			let allowed =
+				self.author_secondary_slots || matches!(pre_digest, PreDigest::Primary(_));
+			if !allowed {
+				debug!(
+					target: LOG_TARGET,
+					"Not claiming secondary slot {} as authoring secondary blocks is disabled", slot,
+				);
+			}
+			allowed
+		});
 
 		if s.is_some() {
 			debug!(target: LOG_TARGET, "Claimed slot {}", slot);
@@ -395,6 +395,7 @@ async fn run_one_test(mutator: impl Fn(&mut TestHeader, Stage) + Send + Sync + '
 					async move { Ok((InherentDataProvider::new(slot),)) }
 				}),
 				force_authoring: false,
+				author_secondary_slots: true,
 				backoff_authoring_blocks: Some(BackoffAuthoringOnFinalizedHeadLagging::default()),
 				babe_link: data.link.clone(),
 				keystore,
@@ -550,6 +551,69 @@ fn claim_epoch_slots() {
 	assert_eq!(claims, [1, 0, 0, 1, 0, 0, 0, 0, 0, 1]);
 }
 
+#[tokio::test]
+async fn secondary_slots_are_only_claimed_when_enabled() {
+	use sc_consensus_slots::SimpleSlotWorker;
+
+	let mut net = BabeTestNet::new(1);
+
+	let peer = net.peer(0);
+	let data = peer.data.as_ref().expect("babe link set up during initialization");
+	let client = peer.client().as_client();
+
+	// The test runtime allows primary and secondary plain slots.
+	assert_eq!(data.link.config.allowed_slots, AllowedSlots::PrimaryAndSecondaryPlainSlots);
+
+	let mut worker = BabeSlotWorker {
+		client: client.clone(),
+		block_import: data.block_import.lock().take().expect("import set up during init"),
+		env: DummyFactory {
+			client: client.clone(),
+			epoch_changes: data.link.epoch_changes.clone(),
+			mutator: Arc::new(|_, _| ()),
+		},
+		sync_oracle: DummyOracle,
+		justification_sync_link: (),
+		force_authoring: false,
+		author_secondary_slots: false,
+		backoff_authoring_blocks: None::<BackoffAuthoringOnFinalizedHeadLagging<u64>>,
+		keystore: create_keystore(Sr25519Keyring::Alice),
+		epoch_changes: data.link.epoch_changes.clone(),
+		slot_notification_sinks: Default::default(),
+		config: data.link.config.clone(),
+		block_proposal_slot_portion: SlotProportion::new(0.5),
+		max_block_proposal_slot_portion: None,
+		telemetry: None,
+	};
+
+	let genesis_header = client.header(client.chain_info().genesis_hash).unwrap().unwrap();
+	let mut claims = Vec::new();
+	for author_secondary_slots in [false, true] {
+		worker.author_secondary_slots = author_secondary_slots;
+		let mut claimed = Vec::new();
+		for slot in 1..=30u64 {
+			let slot = Slot::from(slot);
+			let epoch_descriptor = worker.aux_data(&genesis_header, slot).unwrap();
+			if let Some((pre_digest, _)) =
+				worker.claim_slot(&genesis_header, slot, &epoch_descriptor).await
+			{
+				claimed.push((slot, pre_digest.is_primary()));
+			}
+		}
+		claims.push(claimed);
+	}
+	let (primary_only, with_secondary) = (&claims[0], &claims[1]);
+
+	// Without the flag, only the primary slots are claimed.
+	assert!(primary_only.iter().all(|(_, primary)| *primary));
+	// With it, the secondary slots are claimed as well.
+	assert!(with_secondary.iter().any(|(_, primary)| !*primary));
+	assert_eq!(
+		with_secondary.iter().filter(|(_, primary)| *primary).collect::<Vec<_>>(),
+		primary_only.iter().collect::<Vec<_>>(),
+	);
+}
+
 #[test]
 fn claim_vrf_check() {
 	let authority = Sr25519Keyring::Alice;
@@ -33,6 +33,9 @@ pub enum Error {
 	/// GRANDPA prove finality failed.
 	#[error("GRANDPA prove finality rpc failed: {0}")]
 	ProveFinalityFailed(#[from] sc_consensus_grandpa::FinalityProofError),
+	/// Call to an unsafe RPC was denied.
+	#[error(transparent)]
+	UnsafeRpcCalled(#[from] sc_rpc_api::UnsafeRpcError),
 }
 
 /// The error codes returned by jsonrpc.
@@ -45,6 +48,8 @@ pub enum ErrorCode {
 	VoterStateTooLarge,
 	/// Failed to prove finality.
 	ProveFinality,
+	/// Call to an unsafe RPC was denied.
+	UnsafeRpcCalled,
 }
 
 impl From<Error> for ErrorCode {
@@ -54,12 +59,17 @@ impl From<Error> for ErrorCode {
 			Error::AuthoritySetIdReportedAsUnreasonablyLarge => ErrorCode::AuthoritySetTooLarge,
 			Error::VoterStateReportsUnreasonablyLargeNumbers => ErrorCode::VoterStateTooLarge,
 			Error::ProveFinalityFailed(_) => ErrorCode::ProveFinality,
+			Error::UnsafeRpcCalled(_) => ErrorCode::UnsafeRpcCalled,
 		}
 	}
 }
 
 impl From<Error> for ErrorObjectOwned {
 	fn from(error: Error) -> Self {
+		if let Error::UnsafeRpcCalled(e) = error {
+			return e.into()
+		}
+
 		let message = error.to_string();
 		let code = ErrorCode::from(error);
 		ErrorObject::owned(code as i32, message, None::<()>)
@@ -19,7 +19,7 @@
 //! RPC API for GRANDPA.
 #![warn(missing_docs)]
 
-use futures::StreamExt;
+use futures::{future, StreamExt};
 use log::warn;
 use std::sync::Arc;
 
@@ -36,9 +36,12 @@ mod report;
 use error::Error;
 use finality::{EncodedFinalityProof, RpcFinalityProofProvider};
 use notification::JustificationNotification;
-use report::{ReportAuthoritySet, ReportVoterState, ReportedRoundStates};
-use sc_consensus_grandpa::GrandpaJustificationStream;
+use report::{ReportAuthoritySet, ReportVoterState, ReportedCompletedRound, ReportedRoundStates};
+use sc_consensus_grandpa::{
+	GrandpaJustificationStream, SharedVotingPause, MAX_COMPLETED_ROUND_REPORTS,
+};
 use sc_rpc::{utils::pipe_from_stream, SubscriptionTaskExecutor};
+use sc_rpc_api::DenyUnsafe;
 use sp_runtime::traits::{Block as BlockT, NumberFor};
 
 /// Provides RPC methods for interacting with GRANDPA.
@@ -49,6 +52,23 @@ pub trait GrandpaApi<Notification, Hash, Number> {
 	#[method(name = "grandpa_roundState")]
 	async fn round_state(&self) -> Result<ReportedRoundStates, Error>;
 
+	/// Returns the prevotes and precommits, with the weight of every voter, of up to `count`
+	/// most recently completed rounds, oldest first.
+	///
+	/// Only a limited number of rounds is kept by the node, all of them are returned if `count`
+	/// is not given.
+	#[method(name = "grandpa_roundHistory")]
+	async fn round_history(&self, count: Option<u32>)
+		-> Result<Vec<ReportedCompletedRound>, Error>;
+
+	/// Subscribe to the prevotes and precommits of every round completed by the voter.
+	#[subscription(
+		name = "grandpa_subscribeCompletedRounds" => "grandpa_completedRounds",
+		unsubscribe = "grandpa_unsubscribeCompletedRounds",
+		item = ReportedCompletedRound
+	)]
+	fn subscribe_completed_rounds(&self);
+
 	/// Returns the block most recently finalized by Grandpa, alongside
 	/// side its justification.
 	#[subscription(
@@ -62,6 +82,16 @@ pub trait GrandpaApi<Notification, Hash, Number> {
 	/// in the set and all the intermediary headers to link them together.
 	#[method(name = "grandpa_proveFinality")]
 	async fn prove_finality(&self, block: Number) -> Result<Option<EncodedFinalityProof>, Error>;
+
+	/// Pause voting of the local authority, starting from the next round.
+	///
+	/// The node keeps following GRANDPA as a non-voter until voting is resumed.
+	#[method(name = "grandpa_pauseVoting")]
+	fn pause_voting(&self) -> Result<(), Error>;
+
+	/// Resume voting of the local authority, starting from the next round.
+	#[method(name = "grandpa_resumeVoting")]
+	fn resume_voting(&self) -> Result<(), Error>;
 }
 
 /// Provides RPC methods for interacting with GRANDPA.
@@ -71,6 +101,8 @@ pub struct Grandpa<AuthoritySet, VoterState, Block: BlockT, ProofProvider> {
 	voter_state: VoterState,
 	justification_stream: GrandpaJustificationStream<Block>,
 	finality_proof_provider: Arc<ProofProvider>,
+	voting_pause: SharedVotingPause,
+	deny_unsafe: DenyUnsafe,
 }
 impl<AuthoritySet, VoterState, Block: BlockT, ProofProvider>
 	Grandpa<AuthoritySet, VoterState, Block, ProofProvider>
@@ -82,8 +114,18 @@ impl<AuthoritySet, VoterState, Block: BlockT, ProofProvider>
 		voter_state: VoterState,
 		justification_stream: GrandpaJustificationStream<Block>,
 		finality_proof_provider: Arc<ProofProvider>,
+		voting_pause: SharedVotingPause,
+		deny_unsafe: DenyUnsafe,
 	) -> Self {
-		Self { executor, authority_set, voter_state, justification_stream, finality_proof_provider }
+		Self {
+			executor,
+			authority_set,
+			voter_state,
+			justification_stream,
+			finality_proof_provider,
+			voting_pause,
+			deny_unsafe,
+		}
 	}
 }
 
@@ -101,6 +143,27 @@ where
 		ReportedRoundStates::from(&self.authority_set, &self.voter_state)
 	}
 
+	async fn round_history(
+		&self,
+		count: Option<u32>,
+	) -> Result<Vec<ReportedCompletedRound>, Error> {
+		let count = count.map_or(MAX_COMPLETED_ROUND_REPORTS, |count| count as usize);
+		self.voter_state
+			.completed_rounds(count)
+			.iter()
+			.map(ReportedCompletedRound::from)
+			.collect()
+	}
+
+	fn subscribe_completed_rounds(&self, pending: PendingSubscriptionSink) {
+		let stream = self
+			.voter_state
+			.completed_round_stream()
+			.filter_map(|report| future::ready(ReportedCompletedRound::from(&report).ok()));
+
+		sc_rpc::utils::spawn_subscription_task(&self.executor, pipe_from_stream(pending, stream));
+	}
+
 	fn subscribe_justifications(&self, pending: PendingSubscriptionSink) {
 		let stream = self.justification_stream.subscribe(100_000).map(
 			|x: sc_consensus_grandpa::GrandpaJustification<Block>| {
@@ -120,6 +183,18 @@ where
 			error::Error::ProveFinalityFailed(e)
 		})
 	}
+
+	fn pause_voting(&self) -> Result<(), Error> {
+		self.deny_unsafe.check_if_safe()?;
+		self.voting_pause.pause();
+		Ok(())
+	}
+
+	fn resume_voting(&self) -> Result<(), Error> {
+		self.deny_unsafe.check_if_safe()?;
+		self.voting_pause.resume();
+		Ok(())
+	}
 }
 
 #[cfg(test)]
@@ -131,7 +206,8 @@ mod tests {
 	use parity_scale_codec::{Decode, Encode};
 	use sc_block_builder::BlockBuilderBuilder;
 	use sc_consensus_grandpa::{
-		report, AuthorityId, FinalityProof, GrandpaJustification, GrandpaJustificationSender,
+		report, AuthorityId, CompletedRoundReport, FinalityProof, GrandpaJustification,
+		GrandpaJustificationSender,
 	};
 	use sc_rpc::testing::test_executor;
 	use sp_blockchain::HeaderBackend;
@@ -226,6 +302,30 @@ mod tests {
 
 			Some(report::VoterState { background_rounds, best_round: (2, best_round_state) })
 		}
+
+		fn completed_rounds(&self, count: usize) -> Vec<CompletedRoundReport> {
+			let voter_id_1 = AuthorityId::from_slice(&[1; 32]).unwrap();
+			let voter_id_2 = AuthorityId::from_slice(&[2; 32]).unwrap();
+
+			let rounds = vec![
+				CompletedRoundReport {
+					set_id: 1,
+					round: 0,
+					total_weight: 100,
+					prevotes: vec![(voter_id_1.clone(), 50), (voter_id_2.clone(), 50)],
+					precommits: vec![(voter_id_2, 50)],
+				},
+				CompletedRoundReport {
+					set_id: 1,
+					round: 1,
+					total_weight: 100,
+					prevotes: vec![(voter_id_1.clone(), 50)],
+					precommits: vec![(voter_id_1, 50)],
+				},
+			];
+
+			rounds[rounds.len().saturating_sub(count)..].to_vec()
+		}
 	}
 
 	fn setup_io_handler<VoterState>(
@@ -260,6 +360,8 @@ mod tests {
 			voter_state,
 			justification_stream,
 			finality_proof_provider,
+			SharedVotingPause::default(),
+			DenyUnsafe::No,
 		)
 		.into_rpc();
 
@@ -298,6 +400,29 @@ mod tests {
 		assert_eq!(expected_response, response);
 	}
 
+	#[tokio::test]
+	async fn round_history_works() {
+		let (rpc, _) = setup_io_handler(TestVoterState);
+		let expected_response = "{\"jsonrpc\":\"2.0\",\"result\":[{\
+			\"setId\":1,\"round\":1,\"totalWeight\":100,\
+			\"prevotes\":{\"totalWeight\":50,\"voters\":[\
+				{\"voter\":\"5C62Ck4UrFPiBtoCmeSrgF7x9yv9mn38446dhCpsi2mLHiFT\",\"weight\":50}\
+			]},\
+			\"precommits\":{\"totalWeight\":50,\"voters\":[\
+				{\"voter\":\"5C62Ck4UrFPiBtoCmeSrgF7x9yv9mn38446dhCpsi2mLHiFT\",\"weight\":50}\
+			]}\
+		}],\"id\":0}"
+			.to_string();
+
+		let request = r#"{"jsonrpc":"2.0","method":"grandpa_roundHistory","params":[1],"id":0}"#;
+		let (response, _) = rpc.raw_json_request(&request, 1).await.unwrap();
+		assert_eq!(expected_response, response);
+
+		let request = r#"{"jsonrpc":"2.0","method":"grandpa_roundHistory","params":[],"id":0}"#;
+		let (response, _) = rpc.raw_json_request(&request, 1).await.unwrap();
+		assert_eq!(response.matches("\"setId\"").count(), 2);
+	}
+
 	#[tokio::test]
 	async fn subscribe_and_unsubscribe_with_wrong_id() {
 		let (rpc, _) = setup_io_handler(TestVoterState);
@@ -406,4 +531,39 @@ mod tests {
 		let finality_proof_rpc: FinalityProof<Header> = Decode::decode(&mut &bytes[..]).unwrap();
 		assert_eq!(finality_proof_rpc, finality_proof);
 	}
+
+	#[tokio::test]
+	async fn pause_and_resume_voting() {
+		let (_, justification_stream) = GrandpaJustificationStream::<Block>::channel();
+		let voting_pause = SharedVotingPause::default();
+		let rpc = |deny_unsafe| {
+			Grandpa::new(
+				test_executor(),
+				TestAuthoritySet,
+				TestVoterState,
+				justification_stream.clone(),
+				Arc::new(TestFinalityProofProvider { finality_proof: None }),
+				voting_pause.clone(),
+				deny_unsafe,
+			)
+			.into_rpc()
+		};
+
+		let request = r#"{"jsonrpc":"2.0","method":"grandpa_pauseVoting","params":[],"id":0}"#;
+		let (response, _) = rpc(DenyUnsafe::Yes).raw_json_request(&request, 1).await.unwrap();
+		assert!(response.contains("RPC call is unsafe to be called externally"));
+		assert!(!voting_pause.is_paused());
+
+		let _: () = rpc(DenyUnsafe::No)
+			.call("grandpa_pauseVoting", EmptyParams::new())
+			.await
+			.unwrap();
+		assert!(voting_pause.is_paused());
+
+		let _: () = rpc(DenyUnsafe::No)
+			.call("grandpa_resumeVoting", EmptyParams::new())
+			.await
+			.unwrap();
+		assert!(!voting_pause.is_paused());
+	}
 }
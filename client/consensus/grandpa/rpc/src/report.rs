@@ -22,9 +22,12 @@ use std::{
 	ops::Add,
 };
 
+use futures::{stream::BoxStream, StreamExt};
 use serde::{Deserialize, Serialize};
 
-use sc_consensus_grandpa::{report, AuthorityId, SharedAuthoritySet, SharedVoterState};
+use sc_consensus_grandpa::{
+	report, AuthorityId, CompletedRoundReport, SharedAuthoritySet, SharedVoterState,
+};
 
 use crate::error::Error;
 
@@ -36,6 +39,16 @@ pub trait ReportAuthoritySet {
 /// Utility trait to get reporting data for the current GRANDPA voter state.
 pub trait ReportVoterState {
 	fn get(&self) -> Option<report::VoterState<AuthorityId>>;
+
+	/// Get the reports of up to `count` most recently completed rounds, oldest first.
+	fn completed_rounds(&self, _count: usize) -> Vec<CompletedRoundReport> {
+		Vec::new()
+	}
+
+	/// Get a stream of reports for every round completed from now on.
+	fn completed_round_stream(&self) -> BoxStream<'static, CompletedRoundReport> {
+		futures::stream::empty().boxed()
+	}
 }
 
 impl<H, N> ReportAuthoritySet for SharedAuthoritySet<H, N>
@@ -55,6 +68,14 @@ impl ReportVoterState for SharedVoterState {
 	fn get(&self) -> Option<report::VoterState<AuthorityId>> {
 		self.voter_state()
 	}
+
+	fn completed_rounds(&self, count: usize) -> Vec<CompletedRoundReport> {
+		SharedVoterState::completed_rounds(self, count)
+	}
+
+	fn completed_round_stream(&self) -> BoxStream<'static, CompletedRoundReport> {
+		SharedVoterState::completed_round_stream(self).subscribe(100_000).boxed()
+	}
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -148,3 +169,60 @@ impl ReportedRoundStates {
 		Ok(Self { set_id, best, background })
 	}
 }
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VoterWeight {
+	voter: AuthorityId,
+	weight: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Votes {
+	total_weight: u32,
+	voters: Vec<VoterWeight>,
+}
+
+impl Votes {
+	fn from(votes: &[(AuthorityId, u64)]) -> Result<Self, Error> {
+		let voters = votes
+			.iter()
+			.map(|(voter, weight)| {
+				Ok(VoterWeight { voter: voter.clone(), weight: (*weight).try_into()? })
+			})
+			.collect::<Result<Vec<_>, Error>>()?;
+		let total_weight = voters.iter().try_fold(0u32, |total, voter| {
+			total
+				.checked_add(voter.weight)
+				.ok_or(Error::VoterStateReportsUnreasonablyLargeNumbers)
+		})?;
+
+		Ok(Self { total_weight, voters })
+	}
+}
+
+/// The prevotes and precommits cast in a completed round, with the weight of every voter, in a
+/// form suitable for serialization.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportedCompletedRound {
+	set_id: u32,
+	round: u32,
+	total_weight: u32,
+	prevotes: Votes,
+	precommits: Votes,
+}
+
+impl ReportedCompletedRound {
+	pub fn from(report: &CompletedRoundReport) -> Result<Self, Error> {
+		Ok(Self {
+			set_id: u32::try_from(report.set_id)
+				.map_err(|_| Error::AuthoritySetIdReportedAsUnreasonablyLarge)?,
+			round: report.round.try_into()?,
+			total_weight: report.total_weight.try_into()?,
+			prevotes: Votes::from(&report.prevotes)?,
+			precommits: Votes::from(&report.precommits)?,
+		})
+	}
+}
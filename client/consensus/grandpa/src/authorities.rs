@@ -718,6 +718,11 @@ impl<N: Ord + Clone> AuthoritySetChanges<N> {
 		self.0.push((set_id, block_number));
 	}
 
+	/// The id and last block number of the most recent authority set that ended.
+	pub(crate) fn last(&self) -> Option<&(u64, N)> {
+		self.0.last()
+	}
+
 	pub(crate) fn get_set_id(&self, block_number: N) -> AuthoritySetChangeId<N> {
 		if self
 			.0
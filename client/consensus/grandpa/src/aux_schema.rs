@@ -471,6 +471,11 @@ where
 	load_decode::<_, GrandpaJustification<Block>>(backend, BEST_JUSTIFICATION)
 }
 
+/// Remove the stored justification for the latest block finalized by GRANDPA.
+pub(crate) fn remove_best_justification<B: AuxStore>(backend: &B) -> ClientResult<()> {
+	backend.insert_aux(&[], &[BEST_JUSTIFICATION])
+}
+
 /// Write voter set state.
 pub(crate) fn write_voter_set_state<Block: BlockT, B: AuxStore>(
 	backend: &B,
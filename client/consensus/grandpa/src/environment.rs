@@ -58,8 +58,9 @@ use crate::{
 	notification::GrandpaJustificationSender,
 	until_imported::UntilVoteTargetImported,
 	voting_rule::VotingRule as VotingRuleT,
-	ClientForGrandpa, CommandOrError, Commit, Config, Error, NewAuthoritySet, Precommit, Prevote,
-	PrimaryPropose, SignedMessage, VoterCommand, LOG_TARGET,
+	ClientForGrandpa, CommandOrError, Commit, CompletedRoundReport, Config, Error, NewAuthoritySet,
+	Precommit, Prevote, PrimaryPropose, SharedVoterState, SharedVotingPause, SignedMessage,
+	VoterCommand, LOG_TARGET,
 };
 
 type HistoricalVotes<Block> = finality_grandpa::HistoricalVotes<
@@ -447,6 +448,8 @@ pub(crate) struct Environment<
 	pub(crate) justification_sender: Option<GrandpaJustificationSender<Block>>,
 	pub(crate) telemetry: Option<TelemetryHandle>,
 	pub(crate) offchain_tx_pool_factory: OffchainTransactionPoolFactory<Block>,
+	pub(crate) voting_pause: SharedVotingPause,
+	pub(crate) shared_voter_state: SharedVoterState,
 	pub(crate) _phantom: PhantomData<Backend>,
 }
 
@@ -730,7 +733,12 @@ where
 		let prevote_timer = Delay::new(self.config.gossip_duration * 2);
 		let precommit_timer = Delay::new(self.config.gossip_duration * 4);
 
-		let local_id = local_authority_id(&self.voters, self.config.keystore.as_ref());
+		let local_id = if self.voting_pause.is_paused() {
+			debug!(target: LOG_TARGET, "Voting is paused, not voting in round {}", round);
+			None
+		} else {
+			local_authority_id(&self.voters, self.config.keystore.as_ref())
+		};
 
 		let has_voted = match self.voter_set_state.has_voted(round) {
 			HasVoted::Yes(id, vote) =>
@@ -1032,6 +1040,13 @@ where
 		// clear any cached local authority id associated with this round
 		self.voter_set_state.finished_voting_on(round);
 
+		self.shared_voter_state.note_completed_round(CompletedRoundReport::new(
+			self.set_id,
+			round,
+			&self.voters,
+			historical_votes.seen(),
+		));
+
 		Ok(())
 	}
 
@@ -89,9 +89,13 @@ pub use finality_grandpa::BlockNumberOps;
 use finality_grandpa::{voter, voter_set::VoterSet, Error as GrandpaError};
 
 use std::{
+	collections::VecDeque,
 	fmt, io,
 	pin::Pin,
-	sync::Arc,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
 	task::{Context, Poll},
 	time::Duration,
 };
@@ -133,7 +137,10 @@ pub use finality_grandpa::voter::report;
 pub use finality_proof::{FinalityProof, FinalityProofError, FinalityProofProvider};
 pub use import::{find_forced_change, find_scheduled_change, GrandpaBlockImport};
 pub use justification::GrandpaJustification;
-pub use notification::{GrandpaJustificationSender, GrandpaJustificationStream};
+pub use notification::{
+	CompletedRoundSender, CompletedRoundStream, GrandpaJustificationSender,
+	GrandpaJustificationStream,
+};
 pub use observer::run_grandpa_observer;
 pub use voting_rule::{
 	BeforeBestBlockBy, ThreeQuartersOfTheUnfinalizedChain, VotingRule, VotingRuleResult,
@@ -176,15 +183,73 @@ type CommunicationInH<Block, H> =
 type CommunicationOutH<Block, H> =
 	voter::CommunicationOut<H, NumberFor<Block>, AuthoritySignature, AuthorityId>;
 
+/// Maximum number of completed rounds kept by [`SharedVoterState`].
+pub const MAX_COMPLETED_ROUND_REPORTS: usize = 64;
+
+/// The votes cast in a completed round.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompletedRoundReport {
+	/// The id of the authority set the round belongs to.
+	pub set_id: SetId,
+	/// The round number.
+	pub round: u64,
+	/// The total weight of the voter set.
+	pub total_weight: u64,
+	/// The voters that prevoted in the round, alongside their weight.
+	pub prevotes: Vec<(AuthorityId, u64)>,
+	/// The voters that precommitted in the round, alongside their weight.
+	pub precommits: Vec<(AuthorityId, u64)>,
+}
+
+impl CompletedRoundReport {
+	fn new<H, N, S>(
+		set_id: SetId,
+		round: u64,
+		voters: &VoterSet<AuthorityId>,
+		votes: &[finality_grandpa::SignedMessage<H, N, S, AuthorityId>],
+	) -> Self {
+		let mut prevotes = Vec::new();
+		let mut precommits = Vec::new();
+
+		for vote in votes {
+			let votes = match vote.message {
+				finality_grandpa::Message::Prevote(_) => &mut prevotes,
+				finality_grandpa::Message::Precommit(_) => &mut precommits,
+				finality_grandpa::Message::PrimaryPropose(_) => continue,
+			};
+
+			// equivocating voters are only reported once.
+			if votes.iter().any(|(id, _)| *id == vote.id) {
+				continue
+			}
+
+			if let Some(info) = voters.get(&vote.id) {
+				votes.push((vote.id.clone(), info.weight().get()));
+			}
+		}
+
+		Self { set_id, round, total_weight: voters.total_weight().get(), prevotes, precommits }
+	}
+}
+
 /// Shared voter state for querying.
 pub struct SharedVoterState {
 	inner: Arc<RwLock<Option<Box<dyn voter::VoterState<AuthorityId> + Sync + Send>>>>,
+	completed_rounds: Arc<RwLock<VecDeque<CompletedRoundReport>>>,
+	completed_round_sender: CompletedRoundSender,
+	completed_round_stream: CompletedRoundStream,
 }
 
 impl SharedVoterState {
 	/// Create a new empty `SharedVoterState` instance.
 	pub fn empty() -> Self {
-		Self { inner: Arc::new(RwLock::new(None)) }
+		let (completed_round_sender, completed_round_stream) = CompletedRoundStream::channel();
+		Self {
+			inner: Arc::new(RwLock::new(None)),
+			completed_rounds: Arc::new(RwLock::new(VecDeque::new())),
+			completed_round_sender,
+			completed_round_stream,
+		}
 	}
 
 	fn reset(
@@ -201,11 +266,74 @@ impl SharedVoterState {
 	pub fn voter_state(&self) -> Option<report::VoterState<AuthorityId>> {
 		self.inner.read().as_ref().map(|vs| vs.get())
 	}
+
+	/// Get the reports of up to `count` most recently completed rounds, oldest first.
+	///
+	/// At most [`MAX_COMPLETED_ROUND_REPORTS`] rounds are kept.
+	pub fn completed_rounds(&self, count: usize) -> Vec<CompletedRoundReport> {
+		let completed_rounds = self.completed_rounds.read();
+		let skip = completed_rounds.len().saturating_sub(count);
+		completed_rounds.iter().skip(skip).cloned().collect()
+	}
+
+	/// Get a stream of reports for every round completed from now on.
+	pub fn completed_round_stream(&self) -> CompletedRoundStream {
+		self.completed_round_stream.clone()
+	}
+
+	fn note_completed_round(&self, report: CompletedRoundReport) {
+		{
+			let mut completed_rounds = self.completed_rounds.write();
+			if completed_rounds.len() == MAX_COMPLETED_ROUND_REPORTS {
+				completed_rounds.pop_front();
+			}
+			completed_rounds.push_back(report.clone());
+		}
+
+		let _ = self.completed_round_sender.notify(|| Ok::<_, ()>(report));
+	}
 }
 
 impl Clone for SharedVoterState {
 	fn clone(&self) -> Self {
-		SharedVoterState { inner: self.inner.clone() }
+		SharedVoterState {
+			inner: self.inner.clone(),
+			completed_rounds: self.completed_rounds.clone(),
+			completed_round_sender: self.completed_round_sender.clone(),
+			completed_round_stream: self.completed_round_stream.clone(),
+		}
+	}
+}
+
+/// Shared flag to pause and resume voting of the local authority.
+///
+/// While voting is paused the voter keeps following the protocol as a non-voter, i.e. it keeps
+/// importing justifications and tracking rounds, but doesn't cast any votes. Pausing and resuming
+/// takes effect from the next round onwards.
+#[derive(Clone, Default)]
+pub struct SharedVotingPause {
+	inner: Arc<AtomicBool>,
+}
+
+impl SharedVotingPause {
+	/// Create a new `SharedVotingPause` instance, starting with voting paused if `paused` is set.
+	pub fn new(paused: bool) -> Self {
+		Self { inner: Arc::new(AtomicBool::new(paused)) }
+	}
+
+	/// Pause voting of the local authority.
+	pub fn pause(&self) {
+		self.inner.store(true, Ordering::Relaxed);
+	}
+
+	/// Resume voting of the local authority.
+	pub fn resume(&self) {
+		self.inner.store(false, Ordering::Relaxed);
+	}
+
+	/// Returns `true` if voting of the local authority is paused.
+	pub fn is_paused(&self) -> bool {
+		self.inner.load(Ordering::Relaxed)
 	}
 }
 
@@ -695,6 +823,8 @@ pub struct GrandpaParams<Block: BlockT, C, N, S, SC, VR> {
 	pub prometheus_registry: Option<prometheus_endpoint::Registry>,
 	/// The voter state is exposed at an RPC endpoint.
 	pub shared_voter_state: SharedVoterState,
+	/// Allows to pause voting of the local authority, e.g. through an RPC endpoint.
+	pub shared_voting_pause: SharedVotingPause,
 	/// TelemetryHandle instance.
 	pub telemetry: Option<TelemetryHandle>,
 	/// Offchain transaction pool factory.
@@ -750,6 +880,7 @@ where
 		voting_rule,
 		prometheus_registry,
 		shared_voter_state,
+		shared_voting_pause,
 		telemetry,
 		offchain_tx_pool_factory,
 	} = grandpa_params;
@@ -825,6 +956,7 @@ where
 		voter_commands_rx,
 		prometheus_registry,
 		shared_voter_state,
+		shared_voting_pause,
 		justification_sender,
 		telemetry,
 		offchain_tx_pool_factory,
@@ -895,6 +1027,7 @@ where
 		voter_commands_rx: TracingUnboundedReceiver<VoterCommand<Block::Hash, NumberFor<Block>>>,
 		prometheus_registry: Option<prometheus_endpoint::Registry>,
 		shared_voter_state: SharedVoterState,
+		voting_pause: SharedVotingPause,
 		justification_sender: GrandpaJustificationSender<Block>,
 		telemetry: Option<TelemetryHandle>,
 		offchain_tx_pool_factory: OffchainTransactionPoolFactory<Block>,
@@ -923,6 +1056,8 @@ where
 			justification_sender: Some(justification_sender),
 			telemetry: telemetry.clone(),
 			offchain_tx_pool_factory,
+			voting_pause,
+			shared_voter_state: shared_voter_state.clone(),
 			_phantom: PhantomData,
 		});
 
@@ -1075,6 +1210,8 @@ where
 					justification_sender: self.env.justification_sender.clone(),
 					telemetry: self.telemetry.clone(),
 					offchain_tx_pool_factory: self.env.offchain_tx_pool_factory.clone(),
+					voting_pause: self.env.voting_pause.clone(),
+					shared_voter_state: self.env.shared_voter_state.clone(),
 					_phantom: PhantomData,
 				});
 
@@ -1170,6 +1307,34 @@ fn local_authority_id(
 /// In particular, standard and forced authority set changes announced after the
 /// revert point are removed.
 pub fn revert<Block, Client>(client: Arc<Client>, blocks: NumberFor<Block>) -> ClientResult<()>
+where
+	Block: BlockT,
+	Client: AuxStore + HeaderMetadata<Block, Error = ClientError> + HeaderBackend<Block>,
+{
+	revert_inner(client, blocks, false)
+}
+
+/// Reverts protocol aux data by `blocks` blocks, going past the last finalized block if needed.
+///
+/// The voter state is reset to start from the revert point. Only blocks finalized by the current
+/// authority set can be reverted since the authorities of previous sets are not kept, an error is
+/// returned if the revert point is before the last authority set change.
+pub fn revert_finalized<Block, Client>(
+	client: Arc<Client>,
+	blocks: NumberFor<Block>,
+) -> ClientResult<()>
+where
+	Block: BlockT,
+	Client: AuxStore + HeaderMetadata<Block, Error = ClientError> + HeaderBackend<Block>,
+{
+	revert_inner(client, blocks, true)
+}
+
+fn revert_inner<Block, Client>(
+	client: Arc<Client>,
+	blocks: NumberFor<Block>,
+	revert_finalized: bool,
+) -> ClientResult<()>
 where
 	Block: BlockT,
 	Client: AuxStore + HeaderMetadata<Block, Error = ClientError> + HeaderBackend<Block>,
@@ -1177,7 +1342,11 @@ where
 	let best_number = client.info().best_number;
 	let finalized = client.info().finalized_number;
 
-	let revertible = blocks.min(best_number - finalized);
+	let revertible = if revert_finalized {
+		blocks.min(best_number)
+	} else {
+		blocks.min(best_number - finalized)
+	};
 	if revertible == Zero::zero() {
 		return Ok(())
 	}
@@ -1201,17 +1370,34 @@ where
 	let shared_authority_set = persistent_data.authority_set;
 	let mut authority_set = shared_authority_set.inner();
 
+	let (canon_hash, canon_number) = if number < info.finalized_number {
+		if let Some(&(set_id, last_block)) = authority_set.authority_set_changes.last() {
+			if number < last_block {
+				return Err(ClientError::Backend(format!(
+					"Can not revert to #{} before the end of authority set {} at #{}",
+					number, set_id, last_block,
+				)))
+			}
+		}
+
+		// Don't keep serving a justification for a block that is no longer final.
+		let best_justification = aux_schema::best_justification::<_, Block>(&*client)?;
+		if best_justification.map_or(false, |j| j.target().0 > number) {
+			aux_schema::remove_best_justification(&*client)?;
+		}
+
+		(hash, number)
+	} else {
+		(info.finalized_hash, info.finalized_number)
+	};
+
 	let is_descendent_of = is_descendent_of(&*client, None);
 	authority_set.revert(hash, number, &is_descendent_of);
 
 	// The following has the side effect to properly reset the current voter state.
 	let (set_id, set_ref) = authority_set.current();
-	let new_set = Some(NewAuthoritySet {
-		canon_hash: info.finalized_hash,
-		canon_number: info.finalized_number,
-		set_id,
-		authorities: set_ref.to_vec(),
-	});
+	let new_set =
+		Some(NewAuthoritySet { canon_hash, canon_number, set_id, authorities: set_ref.to_vec() });
 	aux_schema::update_authority_set::<Block, _, _>(&authority_set, new_set.as_ref(), |values| {
 		client.insert_aux(values, None)
 	})
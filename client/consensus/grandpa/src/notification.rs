@@ -18,7 +18,7 @@
 
 use sc_utils::notification::{NotificationSender, NotificationStream, TracingKeyStr};
 
-use crate::justification::GrandpaJustification;
+use crate::{justification::GrandpaJustification, CompletedRoundReport};
 
 /// The sending half of the Grandpa justification channel(s).
 ///
@@ -41,3 +41,18 @@ pub struct GrandpaJustificationsTracingKey;
 impl TracingKeyStr for GrandpaJustificationsTracingKey {
 	const TRACING_KEY: &'static str = "mpsc_grandpa_justification_notification_stream";
 }
+
+/// The sending half of the completed GRANDPA rounds channel(s).
+pub type CompletedRoundSender = NotificationSender<CompletedRoundReport>;
+
+/// The receiving half of the completed GRANDPA rounds channel.
+///
+/// Used to receive a [`CompletedRoundReport`] for every round completed by the voter.
+pub type CompletedRoundStream = NotificationStream<CompletedRoundReport, CompletedRoundsTracingKey>;
+
+/// Provides tracing key for the completed GRANDPA rounds stream.
+#[derive(Clone)]
+pub struct CompletedRoundsTracingKey;
+impl TracingKeyStr for CompletedRoundsTracingKey {
+	const TRACING_KEY: &'static str = "mpsc_grandpa_completed_round_notification_stream";
+}
@@ -339,6 +339,7 @@ fn initialize_grandpa(
 			voting_rule: (),
 			prometheus_registry: None,
 			shared_voter_state: SharedVoterState::empty(),
+			shared_voting_pause: SharedVotingPause::default(),
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
 			),
@@ -498,6 +499,7 @@ async fn finalize_3_voters_1_full_observer() {
 			voting_rule: (),
 			prometheus_registry: None,
 			shared_voter_state: SharedVoterState::empty(),
+			shared_voting_pause: SharedVotingPause::default(),
 			telemetry: None,
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
@@ -597,6 +599,7 @@ async fn transition_3_voters_twice_1_full_observer() {
 			voting_rule: (),
 			prometheus_registry: None,
 			shared_voter_state: SharedVoterState::empty(),
+			shared_voting_pause: SharedVotingPause::default(),
 			telemetry: None,
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
@@ -1080,6 +1083,7 @@ async fn voter_persists_its_votes() {
 			voting_rule: VotingRulesBuilder::default().build(),
 			prometheus_registry: None,
 			shared_voter_state: SharedVoterState::empty(),
+			shared_voting_pause: SharedVotingPause::default(),
 			telemetry: None,
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
@@ -1130,6 +1134,7 @@ async fn voter_persists_its_votes() {
 			voting_rule: VotingRulesBuilder::default().build(),
 			prometheus_registry: None,
 			shared_voter_state: SharedVoterState::empty(),
+			shared_voting_pause: SharedVotingPause::default(),
 			telemetry: None,
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
@@ -1351,6 +1356,7 @@ async fn voter_catches_up_to_latest_round_when_behind() {
 			voting_rule: (),
 			prometheus_registry: None,
 			shared_voter_state: SharedVoterState::empty(),
+			shared_voting_pause: SharedVotingPause::default(),
 			telemetry: None,
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(
 				RejectAllTxPool::default(),
@@ -1487,6 +1493,8 @@ where
 		telemetry: None,
 		_phantom: PhantomData,
 		offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(RejectAllTxPool::default()),
+		voting_pause: SharedVotingPause::default(),
+		shared_voter_state: SharedVoterState::empty(),
 	}
 }
 
@@ -2115,6 +2123,43 @@ async fn grandpa_environment_doesnt_send_equivocation_reports_for_itself() {
 	environment.report_equivocation(equivocation_proof).unwrap();
 }
 
+#[tokio::test]
+async fn grandpa_environment_doesnt_vote_while_paused() {
+	use finality_grandpa::voter::Environment;
+
+	let alice = Ed25519Keyring::Alice;
+	let voters = make_ids(&[alice]);
+
+	let mut net = GrandpaTestNet::new(TestApi::new(voters), 1, 0);
+	let peer = net.peer(0);
+	let network_service = peer.network_service().clone();
+	let sync_service = peer.sync_service().clone();
+	let notification_service =
+		peer.take_notification_service(&grandpa_protocol_name::NAME.into()).unwrap();
+	let link = peer.data.lock().take().unwrap();
+	let keystore = create_keystore(alice);
+	let mut environment = test_environment(
+		&link,
+		Some(keystore),
+		network_service.clone(),
+		sync_service,
+		notification_service,
+		(),
+	);
+
+	let voting_pause = SharedVotingPause::new(true);
+	environment.voting_pause = voting_pause.clone();
+
+	// while paused we don't pick up our authority id to vote
+	environment.round_data(1);
+	assert_eq!(environment.voter_set_state.voting_on(1), None);
+
+	// once resumed we vote again in the next round
+	voting_pause.resume();
+	environment.round_data(2);
+	assert_eq!(environment.voter_set_state.voting_on(2), Some(alice.public().into()));
+}
+
 #[tokio::test]
 async fn revert_prunes_authority_changes() {
 	sp_tracing::try_init_simple();
@@ -2241,3 +2286,136 @@ async fn revert_prunes_authority_changes() {
 		.collect();
 	assert_eq!(changes_num, [21, 27]);
 }
+
+#[tokio::test]
+async fn revert_finalized_resets_voter_state() {
+	sp_tracing::try_init_simple();
+
+	let peers = &[Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Charlie];
+	let api = TestApi::new(make_ids(peers));
+
+	let mut net = GrandpaTestNet::new(api, 3, 0);
+	tokio::spawn(initialize_grandpa(&mut net, peers));
+
+	let peer = net.peer(0);
+	let client = peer.client().as_client();
+
+	let hashes = peer.push_blocks(10, false);
+	peer.client().finalize_block(hashes[7], None, false).unwrap();
+
+	let commit =
+		finality_grandpa::Commit { target_hash: hashes[7], target_number: 8, precommits: vec![] };
+	let justification = GrandpaJustification::from_commit(&client, 1, commit).unwrap();
+	aux_schema::update_best_justification(&justification, |values| client.insert_aux(values, &[]))
+		.unwrap();
+
+	// without the unsafe variant finalized blocks are kept
+	revert(client.clone(), 5).unwrap();
+	let load = || {
+		aux_schema::load_persistent::<Block, _, _>(
+			&*client,
+			client.info().genesis_hash,
+			Zero::zero(),
+			|| unreachable!(),
+		)
+		.unwrap()
+	};
+	assert_eq!(load().set_state.read().last_completed_round().base.1, 8);
+	assert!(aux_schema::best_justification::<_, Block>(&*client).unwrap().is_some());
+
+	revert_finalized(client.clone(), 5).unwrap();
+	assert_eq!(load().set_state.read().last_completed_round().base, (hashes[4], 5));
+	assert!(aux_schema::best_justification::<_, Block>(&*client).unwrap().is_none());
+}
+
+#[test]
+fn completed_round_report_counts_each_voter_once() {
+	let voters = make_ids(&[Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Charlie]);
+	let voter_set = VoterSet::new(voters.iter().cloned()).unwrap();
+	let alice: AuthorityId = Ed25519Keyring::Alice.public().into();
+	let bob: AuthorityId = Ed25519Keyring::Bob.public().into();
+	let dave: AuthorityId = Ed25519Keyring::Dave.public().into();
+
+	let signed = |message, id: &AuthorityId| finality_grandpa::SignedMessage::<H256, u64, (), _> {
+		message,
+		signature: (),
+		id: id.clone(),
+	};
+	let prevote = |n: u8| {
+		finality_grandpa::Message::Prevote(finality_grandpa::Prevote {
+			target_hash: H256::repeat_byte(n),
+			target_number: n as u64,
+		})
+	};
+	let precommit = |n: u8| {
+		finality_grandpa::Message::Precommit(finality_grandpa::Precommit {
+			target_hash: H256::repeat_byte(n),
+			target_number: n as u64,
+		})
+	};
+	let primary_propose =
+		finality_grandpa::Message::PrimaryPropose(finality_grandpa::PrimaryPropose {
+			target_hash: H256::repeat_byte(1),
+			target_number: 1,
+		});
+
+	let votes = vec![
+		signed(primary_propose, &bob),
+		signed(prevote(1), &alice),
+		// an equivocation is only reported once.
+		signed(prevote(2), &alice),
+		signed(prevote(1), &bob),
+		signed(precommit(1), &alice),
+		// votes of non-voters are ignored.
+		signed(prevote(1), &dave),
+		signed(precommit(1), &dave),
+	];
+
+	assert_eq!(
+		CompletedRoundReport::new(7, 3, &voter_set, &votes),
+		CompletedRoundReport {
+			set_id: 7,
+			round: 3,
+			total_weight: 3,
+			prevotes: vec![(alice.clone(), 1), (bob, 1)],
+			precommits: vec![(alice, 1)],
+		},
+	);
+}
+
+#[tokio::test]
+async fn note_completed_round_keeps_the_most_recent_rounds() {
+	let report = |round| CompletedRoundReport {
+		set_id: 0,
+		round,
+		total_weight: 1,
+		prevotes: vec![],
+		precommits: vec![],
+	};
+	let shared_voter_state = SharedVoterState::empty();
+	let mut completed_rounds = shared_voter_state.completed_round_stream().subscribe(100_000);
+	assert!(shared_voter_state.completed_rounds(10).is_empty());
+
+	let last_round = MAX_COMPLETED_ROUND_REPORTS as u64 + 2;
+	for round in 1..=last_round {
+		shared_voter_state.note_completed_round(report(round));
+	}
+
+	// the oldest rounds are pruned.
+	let kept = shared_voter_state.completed_rounds(usize::MAX);
+	assert_eq!(kept.len(), MAX_COMPLETED_ROUND_REPORTS);
+	assert_eq!(kept.first(), Some(&report(3)));
+	assert_eq!(kept.last(), Some(&report(last_round)));
+
+	// the most recent rounds are returned, oldest first.
+	assert_eq!(
+		shared_voter_state.completed_rounds(2),
+		vec![report(last_round - 1), report(last_round)],
+	);
+	assert!(shared_voter_state.completed_rounds(0).is_empty());
+
+	// every report is published, including the pruned ones.
+	for round in 1..=last_round {
+		assert_eq!(completed_rounds.next().await, Some(report(round)));
+	}
+}
@@ -31,6 +31,11 @@
 //! mining on a standalone thread. Finally, when a seal is found, call
 //! [`MiningHandle::submit`] to build the block.
 //!
+//! Miners running outside of the node can instead use the `mining_getWork` and
+//! `mining_submitWork` methods of the [`rpc::MiningApiServer`], optionally accepting shares
+//! below the block difficulty through a [`ShareDifficulty`]. Every submitted seal is reported
+//! on the [`MiningHandle::seal_attempts`] stream.
+//!
 //! The auxiliary storage for PoW engine only stores the total difficulty.
 //! For other storage requirements for particular PoW algorithm (such as
 //! the actual difficulty for each particular blocks), you can take a client
@@ -39,9 +44,12 @@
 //! as the storage, but it is not recommended as it won't work well with light
 //! clients.
 
+pub mod rpc;
 mod worker;
 
-pub use crate::worker::{MiningBuild, MiningHandle, MiningMetadata};
+pub use crate::worker::{
+	MiningBuild, MiningHandle, MiningMetadata, SealAttempt, SealAttemptOutcome, ShareDifficulty,
+};
 
 use crate::worker::UntilImportedOrTimeout;
 use codec::{Decode, Encode};
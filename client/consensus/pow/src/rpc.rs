@@ -0,0 +1,309 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for external miners to fetch work from and submit seals to the
+//! [`MiningHandle`].
+
+use crate::{MiningHandle, PowAlgorithm, SealAttemptOutcome, ShareDifficulty};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+};
+use sc_rpc_api::DenyUnsafe;
+use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+
+/// Work for external miners, as returned by `mining_getWork`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Work<Hash, Difficulty> {
+	/// Currently known best hash which the pre-hash is built on.
+	pub best_hash: Hash,
+	/// Mining pre-hash.
+	pub pre_hash: Hash,
+	/// Pre-runtime digest item.
+	pub pre_runtime: Option<Bytes>,
+	/// Difficulty a seal must meet for the block to be imported.
+	pub difficulty: Difficulty,
+	/// Difficulty a seal must meet to be accepted as a share, if shares are accepted.
+	pub share_difficulty: Option<Difficulty>,
+}
+
+/// RPC trait that provides methods for external miners.
+#[rpc(client, server)]
+pub trait MiningApi<Hash, Difficulty> {
+	/// Get the current work. `None` if there is nothing to mine, e.g. because the node is doing
+	/// major syncing.
+	#[method(name = "mining_getWork")]
+	fn get_work(&self) -> RpcResult<Option<Work<Hash, Difficulty>>>;
+
+	/// Submit a seal mined for the work with the given `pre_hash`.
+	///
+	/// This method is unsafe: a valid seal imports the block the node is mining.
+	#[method(name = "mining_submitWork")]
+	async fn submit_work(&self, pre_hash: Hash, seal: Bytes) -> RpcResult<SealAttemptOutcome>;
+}
+
+/// A struct that implements the [`MiningApiServer`].
+pub struct Mining<Block, Algorithm, L, Proof>
+where
+	Block: BlockT,
+	Algorithm: PowAlgorithm<Block>,
+	L: sc_consensus::JustificationSyncLink<Block>,
+{
+	handle: MiningHandle<Block, Algorithm, L, Proof>,
+	share_difficulty: Option<Arc<dyn ShareDifficulty<Algorithm::Difficulty>>>,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<Block, Algorithm, L, Proof> Mining<Block, Algorithm, L, Proof>
+where
+	Block: BlockT,
+	Algorithm: PowAlgorithm<Block>,
+	L: sc_consensus::JustificationSyncLink<Block>,
+{
+	/// Create new `Mining` serving the work of the given mining worker.
+	///
+	/// Seals that don't meet the block difficulty are accepted as shares if `share_difficulty` is
+	/// given.
+	pub fn new(
+		handle: MiningHandle<Block, Algorithm, L, Proof>,
+		share_difficulty: Option<Arc<dyn ShareDifficulty<Algorithm::Difficulty>>>,
+		deny_unsafe: DenyUnsafe,
+	) -> Self {
+		Self { handle, share_difficulty, deny_unsafe }
+	}
+}
+
+#[async_trait]
+impl<Block, Algorithm, L, Proof> MiningApiServer<Block::Hash, Algorithm::Difficulty>
+	for Mining<Block, Algorithm, L, Proof>
+where
+	Block: BlockT,
+	Algorithm: PowAlgorithm<Block> + Send + Sync + 'static,
+	Algorithm::Difficulty: Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+	L: sc_consensus::JustificationSyncLink<Block> + 'static,
+	Proof: Send + 'static,
+{
+	fn get_work(&self) -> RpcResult<Option<Work<Block::Hash, Algorithm::Difficulty>>> {
+		Ok(self.handle.metadata().map(|metadata| Work {
+			best_hash: metadata.best_hash,
+			pre_hash: metadata.pre_hash,
+			pre_runtime: metadata.pre_runtime.map(Into::into),
+			difficulty: metadata.difficulty,
+			share_difficulty: self
+				.share_difficulty
+				.as_ref()
+				.map(|share| share.share_difficulty(metadata.difficulty)),
+		}))
+	}
+
+	async fn submit_work(
+		&self,
+		pre_hash: Block::Hash,
+		seal: Bytes,
+	) -> RpcResult<SealAttemptOutcome> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let share_difficulty = self.share_difficulty.as_ref().and_then(|share| {
+			self.handle
+				.metadata()
+				.map(|metadata| share.share_difficulty(metadata.difficulty))
+		});
+
+		Ok(self.handle.submit_work(pre_hash, seal.0, share_difficulty).await)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Error, MiningBuild, MiningMetadata, Seal};
+	use jsonrpsee::core::EmptyServerParams;
+	use sc_consensus::{
+		BlockCheckParams, BlockImport, BlockImportParams, BoxBlockImport, ImportResult,
+	};
+	use sp_consensus::Proposal;
+	use sp_runtime::{generic::BlockId, traits::Header as HeaderT};
+	use substrate_test_runtime_client::runtime::{Block, Hash, Header};
+
+	/// Seals are valid if their first byte is at least the difficulty.
+	struct TestAlgorithm;
+
+	impl PowAlgorithm<Block> for TestAlgorithm {
+		type Difficulty = u128;
+
+		fn difficulty(&self, _parent: Hash) -> Result<u128, Error<Block>> {
+			Ok(BLOCK_DIFFICULTY)
+		}
+
+		fn verify(
+			&self,
+			_parent: &BlockId<Block>,
+			_pre_hash: &Hash,
+			_pre_digest: Option<&[u8]>,
+			seal: &Seal,
+			difficulty: u128,
+		) -> Result<bool, Error<Block>> {
+			Ok(seal.first().map_or(false, |work| *work as u128 >= difficulty))
+		}
+	}
+
+	struct TestShareDifficulty;
+
+	impl ShareDifficulty<u128> for TestShareDifficulty {
+		fn share_difficulty(&self, difficulty: u128) -> u128 {
+			difficulty / 2
+		}
+	}
+
+	#[derive(Clone, Default)]
+	struct TestBlockImport(Arc<parking_lot::Mutex<Vec<Header>>>);
+
+	#[async_trait::async_trait]
+	impl BlockImport<Block> for TestBlockImport {
+		type Error = sp_consensus::Error;
+
+		async fn check_block(
+			&mut self,
+			_block: BlockCheckParams<Block>,
+		) -> Result<ImportResult, Self::Error> {
+			Ok(ImportResult::imported(false))
+		}
+
+		async fn import_block(
+			&mut self,
+			block: BlockImportParams<Block>,
+		) -> Result<ImportResult, Self::Error> {
+			self.0.lock().push(block.post_header());
+			Ok(ImportResult::imported(true))
+		}
+	}
+
+	const BLOCK_DIFFICULTY: u128 = 100;
+	const PRE_HASH: Hash = Hash::repeat_byte(1);
+
+	type TestMining = Mining<Block, TestAlgorithm, (), ()>;
+
+	fn test_mining_rpc_module(deny_unsafe: DenyUnsafe) -> (TestMining, TestBlockImport) {
+		let block_import = TestBlockImport::default();
+		let handle = MiningHandle::new(
+			TestAlgorithm,
+			Box::new(block_import.clone()) as BoxBlockImport<Block>,
+			(),
+		);
+
+		let header = Header::new(
+			1,
+			Default::default(),
+			Default::default(),
+			Hash::repeat_byte(2),
+			Default::default(),
+		);
+		handle.on_build(MiningBuild {
+			metadata: MiningMetadata {
+				best_hash: Hash::repeat_byte(2),
+				pre_hash: PRE_HASH,
+				pre_runtime: None,
+				difficulty: BLOCK_DIFFICULTY,
+			},
+			proposal: Proposal {
+				block: Block::new(header, Vec::new()),
+				proof: (),
+				storage_changes: Default::default(),
+			},
+		});
+
+		let mining = Mining::new(handle, Some(Arc::new(TestShareDifficulty)), deny_unsafe);
+		(mining, block_import)
+	}
+
+	async fn submit_work(pre_hash: Hash, work: u8) -> (String, TestBlockImport) {
+		let (mining, block_import) = test_mining_rpc_module(DenyUnsafe::No);
+		let api = mining.into_rpc();
+		let request = format!(
+			r#"{{"jsonrpc":"2.0","method":"mining_submitWork","params":["{:?}","0x{:02x}"],"id":1}}"#,
+			pre_hash, work,
+		);
+		let (response, _) = api.raw_json_request(&request, 1).await.unwrap();
+		(response, block_import)
+	}
+
+	#[tokio::test]
+	async fn get_work_returns_the_current_build() {
+		let (mining, _) = test_mining_rpc_module(DenyUnsafe::Yes);
+		let api = mining.into_rpc();
+
+		let work: Option<Work<Hash, u128>> =
+			api.call("mining_getWork", EmptyServerParams::new()).await.unwrap();
+		let work = work.unwrap();
+		assert_eq!(work.pre_hash, PRE_HASH);
+		assert_eq!(work.difficulty, BLOCK_DIFFICULTY);
+		assert_eq!(work.share_difficulty, Some(BLOCK_DIFFICULTY / 2));
+	}
+
+	#[tokio::test]
+	async fn submit_work_is_rpc_unsafe() {
+		let (mining, block_import) = test_mining_rpc_module(DenyUnsafe::Yes);
+		let api = mining.into_rpc();
+
+		let request = format!(
+			r#"{{"jsonrpc":"2.0","method":"mining_submitWork","params":["{:?}","0xff"],"id":1}}"#,
+			PRE_HASH,
+		);
+		let (response, _) = api.raw_json_request(&request, 1).await.unwrap();
+		let expected = r#"{"jsonrpc":"2.0","error":{"code":-32601,"message":"RPC call is unsafe to be called externally"},"id":1}"#;
+
+		assert_eq!(response, expected);
+		assert!(block_import.0.lock().is_empty());
+	}
+
+	#[tokio::test]
+	async fn submit_work_imports_blocks_meeting_the_difficulty() {
+		let (response, block_import) = submit_work(PRE_HASH, 100).await;
+
+		assert_eq!(response, r#"{"jsonrpc":"2.0","result":"imported","id":1}"#);
+		assert_eq!(block_import.0.lock().len(), 1);
+	}
+
+	#[tokio::test]
+	async fn submit_work_accepts_shares() {
+		let (response, block_import) = submit_work(PRE_HASH, 50).await;
+
+		assert_eq!(response, r#"{"jsonrpc":"2.0","result":"shareAccepted","id":1}"#);
+		assert!(block_import.0.lock().is_empty());
+	}
+
+	#[tokio::test]
+	async fn submit_work_rejects_invalid_seals() {
+		let (response, block_import) = submit_work(PRE_HASH, 49).await;
+
+		assert_eq!(response, r#"{"jsonrpc":"2.0","result":"invalid","id":1}"#);
+		assert!(block_import.0.lock().is_empty());
+	}
+
+	#[tokio::test]
+	async fn submit_work_rejects_stale_seals() {
+		let (response, block_import) = submit_work(Hash::repeat_byte(3), 100).await;
+
+		assert_eq!(response, r#"{"jsonrpc":"2.0","result":"stale","id":1}"#);
+		assert!(block_import.0.lock().is_empty());
+	}
+}
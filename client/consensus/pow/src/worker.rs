@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use futures::{
+	lock::Mutex as AsyncMutex,
 	prelude::*,
 	task::{Context, Poll},
 };
@@ -25,6 +26,8 @@ use log::*;
 use parking_lot::Mutex;
 use sc_client_api::ImportNotifications;
 use sc_consensus::{BlockImportParams, BoxBlockImport, StateAction, StorageChanges};
+use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
+use serde::{Deserialize, Serialize};
 use sp_consensus::{BlockOrigin, Proposal};
 use sp_runtime::{
 	generic::BlockId,
@@ -63,6 +66,43 @@ pub struct MiningBuild<Block: BlockT, Algorithm: PowAlgorithm<Block>, Proof> {
 	pub proposal: Proposal<Block, Proof>,
 }
 
+/// Pluggable difficulty of the shares accepted from miners, as used by mining pools.
+///
+/// A share is a seal that meets a difficulty lower than the one of the block. Valid shares prove
+/// that a miner is working on the current build, without the seal being good enough to import the
+/// block.
+pub trait ShareDifficulty<Difficulty>: Send + Sync {
+	/// Get the share difficulty for a build with the given block `difficulty`.
+	fn share_difficulty(&self, difficulty: Difficulty) -> Difficulty;
+}
+
+/// The outcome of a seal submitted to the [`MiningHandle`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SealAttemptOutcome {
+	/// The seal met the difficulty of the build and the block was imported.
+	Imported,
+	/// The seal only met the share difficulty it was submitted with.
+	ShareAccepted,
+	/// The seal is not valid for the current build.
+	Invalid,
+	/// The seal was mined for a build that is no longer the current one.
+	Stale,
+	/// There is no build to submit a seal for, e.g. because the client is doing major syncing.
+	NoBuild,
+	/// The seal met the difficulty of the build, but importing the block failed.
+	ImportFailed,
+}
+
+/// Event emitted for every seal submitted to the [`MiningHandle`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SealAttempt<H> {
+	/// Pre-hash of the build the seal was submitted for. `None` if there was no build.
+	pub pre_hash: Option<H>,
+	/// The outcome of the submission.
+	pub outcome: SealAttemptOutcome,
+}
+
 /// Version of the mining worker.
 #[derive(Eq, PartialEq, Clone, Copy)]
 pub struct Version(usize);
@@ -78,7 +118,8 @@ pub struct MiningHandle<
 	algorithm: Arc<Algorithm>,
 	justification_sync_link: Arc<L>,
 	build: Arc<Mutex<Option<MiningBuild<Block, Algorithm, Proof>>>>,
-	block_import: Arc<Mutex<BoxBlockImport<Block>>>,
+	block_import: Arc<AsyncMutex<BoxBlockImport<Block>>>,
+	seal_attempt_sinks: Arc<Mutex<Vec<TracingUnboundedSender<SealAttempt<Block::Hash>>>>>,
 }
 
 impl<Block, Algorithm, L, Proof> MiningHandle<Block, Algorithm, L, Proof>
@@ -102,7 +143,8 @@ where
 			algorithm: Arc::new(algorithm),
 			justification_sync_link: Arc::new(justification_sync_link),
 			build: Arc::new(Mutex::new(None)),
-			block_import: Arc::new(Mutex::new(block_import)),
+			block_import: Arc::new(AsyncMutex::new(block_import)),
+			seal_attempt_sinks: Arc::new(Mutex::new(Vec::new())),
 		}
 	}
 
@@ -137,46 +179,125 @@ where
 		self.build.lock().as_ref().map(|b| b.metadata.clone())
 	}
 
-	/// Submit a mined seal. The seal will be validated again. Returns true if the submission is
-	/// successful.
-	pub async fn submit(&self, seal: Seal) -> bool {
-		if let Some(metadata) = self.metadata() {
-			match self.algorithm.verify(
-				&BlockId::Hash(metadata.best_hash),
-				&metadata.pre_hash,
-				metadata.pre_runtime.as_ref().map(|v| &v[..]),
-				&seal,
-				metadata.difficulty,
-			) {
-				Ok(true) => (),
-				Ok(false) => {
-					warn!(target: LOG_TARGET, "Unable to import mined block: seal is invalid",);
-					return false
-				},
-				Err(err) => {
-					warn!(target: LOG_TARGET, "Unable to import mined block: {}", err,);
-					return false
-				},
-			}
-		} else {
-			warn!(target: LOG_TARGET, "Unable to import mined block: metadata does not exist",);
-			return false
+	/// Get a stream of [`SealAttempt`] events, one for every seal submitted from now on.
+	pub fn seal_attempts(&self) -> TracingUnboundedReceiver<SealAttempt<Block::Hash>> {
+		let (sink, stream) = tracing_unbounded("mpsc_pow_seal_attempts", 100_000);
+		self.seal_attempt_sinks.lock().push(sink);
+		stream
+	}
+
+	fn notify_seal_attempt(&self, pre_hash: Option<Block::Hash>, outcome: SealAttemptOutcome) {
+		let attempt = SealAttempt { pre_hash, outcome };
+		self.seal_attempt_sinks
+			.lock()
+			.retain(|sink| sink.unbounded_send(attempt.clone()).is_ok());
+	}
+
+	fn verify(
+		&self,
+		metadata: &MiningMetadata<Block::Hash, Algorithm::Difficulty>,
+		seal: &Seal,
+		difficulty: Algorithm::Difficulty,
+	) -> bool {
+		match self.algorithm.verify(
+			&BlockId::Hash(metadata.best_hash),
+			&metadata.pre_hash,
+			metadata.pre_runtime.as_ref().map(|v| &v[..]),
+			seal,
+			difficulty,
+		) {
+			Ok(valid) => valid,
+			Err(err) => {
+				warn!(target: LOG_TARGET, "Unable to verify mined seal: {}", err,);
+				false
+			},
 		}
+	}
 
-		let build = if let Some(build) = {
-			let mut build = self.build.lock();
-			let value = build.take();
-			if value.is_some() {
-				self.increment_version();
-			}
-			value
-		} {
-			build
+	/// Take the current build if the seal meets its difficulty.
+	///
+	/// The build stays locked while the seal is verified, so that it cannot be replaced by a
+	/// newer one in between, which would import the new proposal with a seal mined for the old
+	/// one. If `pre_hash` is given, seals for any other build are rejected as stale. Seals that
+	/// only meet the `share_difficulty` are accepted as shares.
+	fn take_verified_build(
+		&self,
+		pre_hash: Option<Block::Hash>,
+		seal: &Seal,
+		share_difficulty: Option<Algorithm::Difficulty>,
+	) -> Result<MiningBuild<Block, Algorithm, Proof>, (Option<Block::Hash>, SealAttemptOutcome)> {
+		let mut build = self.build.lock();
+		let Some(metadata) = build.as_ref().map(|b| &b.metadata) else {
+			return Err((None, SealAttemptOutcome::NoBuild))
+		};
+
+		let outcome = if pre_hash.map_or(false, |pre_hash| pre_hash != metadata.pre_hash) {
+			SealAttemptOutcome::Stale
+		} else if self.verify(metadata, seal, metadata.difficulty) {
+			let value = build.take().expect("build was checked to exist above; qed");
+			self.increment_version();
+			return Ok(value)
+		} else if share_difficulty.map_or(false, |d| self.verify(metadata, seal, d)) {
+			SealAttemptOutcome::ShareAccepted
 		} else {
-			warn!(target: LOG_TARGET, "Unable to import mined block: build does not exist",);
-			return false
+			SealAttemptOutcome::Invalid
+		};
+
+		Err((Some(metadata.pre_hash), outcome))
+	}
+
+	/// Submit a mined seal. The seal will be validated again. Returns true if the submission is
+	/// successful.
+	pub async fn submit(&self, seal: Seal) -> bool {
+		let build = match self.take_verified_build(None, &seal, None) {
+			Ok(build) => build,
+			Err((pre_hash, outcome)) => {
+				match outcome {
+					SealAttemptOutcome::NoBuild => warn!(
+						target: LOG_TARGET,
+						"Unable to import mined block: metadata does not exist",
+					),
+					_ =>
+						warn!(target: LOG_TARGET, "Unable to import mined block: seal is invalid",),
+				}
+				self.notify_seal_attempt(pre_hash, outcome);
+				return false
+			},
 		};
 
+		let pre_hash = build.metadata.pre_hash;
+		let outcome = self.import(build, seal).await;
+		self.notify_seal_attempt(Some(pre_hash), outcome);
+		outcome == SealAttemptOutcome::Imported
+	}
+
+	/// Submit a seal mined for the build with the given `pre_hash`, as done by external miners.
+	///
+	/// The block is imported if the seal meets the difficulty of the build. Otherwise, if a
+	/// `share_difficulty` is given, as used by mining pools, the seal is accepted as a share if it
+	/// meets it.
+	pub async fn submit_work(
+		&self,
+		pre_hash: Block::Hash,
+		seal: Seal,
+		share_difficulty: Option<Algorithm::Difficulty>,
+	) -> SealAttemptOutcome {
+		let (pre_hash, outcome) =
+			match self.take_verified_build(Some(pre_hash), &seal, share_difficulty) {
+				Ok(build) => (Some(build.metadata.pre_hash), self.import(build, seal).await),
+				Err(rejected) => rejected,
+			};
+
+		self.notify_seal_attempt(pre_hash, outcome);
+		outcome
+	}
+
+	/// Import the given build, taken from the worker, with its already verified seal.
+	async fn import(
+		&self,
+		build: MiningBuild<Block, Algorithm, Proof>,
+		seal: Seal,
+	) -> SealAttemptOutcome {
 		let seal = DigestItem::Seal(POW_ENGINE_ID, seal);
 		let (header, body) = build.proposal.block.deconstruct();
 
@@ -192,7 +313,7 @@ where
 		import_block.insert_intermediate(INTERMEDIATE_KEY, intermediate);
 
 		let header = import_block.post_header();
-		let mut block_import = self.block_import.lock();
+		let mut block_import = self.block_import.lock().await;
 
 		match block_import.import_block(import_block).await {
 			Ok(res) => {
@@ -206,11 +327,11 @@ where
 					target: LOG_TARGET,
 					"✅ Successfully mined block on top of: {}", build.metadata.best_hash
 				);
-				true
+				SealAttemptOutcome::Imported
 			},
 			Err(err) => {
 				warn!(target: LOG_TARGET, "Unable to import mined block: {}", err,);
-				false
+				SealAttemptOutcome::ImportFailed
 			},
 		}
 	}
@@ -229,6 +350,7 @@ where
 			justification_sync_link: self.justification_sync_link.clone(),
 			build: self.build.clone(),
 			block_import: self.block_import.clone(),
+			seal_attempt_sinks: self.seal_attempt_sinks.clone(),
 		}
 	}
 }
@@ -720,6 +720,17 @@ pub trait BackoffAuthoringBlocksStrategy<N> {
 	) -> bool;
 }
 
+/// How the backoff interval of [`BackoffAuthoringOnFinalizedHeadLagging`] grows with the number
+/// of unfinalized blocks.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BackoffGrowth {
+	/// The interval grows by one slot every `authoring_bias` unfinalized blocks.
+	#[default]
+	Linear,
+	/// The interval doubles every `authoring_bias` unfinalized blocks.
+	Exponential,
+}
+
 /// A simple default strategy for how to decide backing off authoring blocks if the number of
 /// unfinalized blocks grows too large.
 #[derive(Clone)]
@@ -733,6 +744,8 @@ pub struct BackoffAuthoringOnFinalizedHeadLagging<N> {
 	/// Scales the backoff rate. A higher value effectively means we backoff slower, taking longer
 	/// time to reach the maximum backoff as the unfinalized head of chain grows.
 	pub authoring_bias: N,
+	/// How the backoff interval grows until reaching `max_interval`.
+	pub growth: BackoffGrowth,
 }
 
 /// These parameters is supposed to be some form of sensible defaults.
@@ -749,6 +762,7 @@ impl<N: BaseArithmetic> Default for BackoffAuthoringOnFinalizedHeadLagging<N> {
 			// Effectively meaning that consider the unfinalized head suffix length to grow half as
 			// fast as in actuality.
 			authoring_bias: 2.into(),
+			growth: BackoffGrowth::Linear,
 		}
 	}
 }
@@ -773,12 +787,20 @@ where
 		// There can be race between getting the finalized number and getting the best number.
 		// So, better be safe than sorry.
 		let unfinalized_block_length = chain_head_number.saturating_sub(finalized_number);
-		let interval =
+		let steps =
 			unfinalized_block_length.saturating_sub(self.unfinalized_slack) / self.authoring_bias;
-		let interval = interval.min(self.max_interval);
 
 		// We're doing arithmetic between block and slot numbers.
-		let interval: u64 = interval.unique_saturated_into();
+		let steps: u64 = steps.unique_saturated_into();
+		let interval = match self.growth {
+			BackoffGrowth::Linear => steps,
+			BackoffGrowth::Exponential if steps == 0 => 0,
+			BackoffGrowth::Exponential => u32::try_from(steps - 1)
+				.ok()
+				.and_then(|shift| 1u64.checked_shl(shift))
+				.unwrap_or(u64::MAX),
+		};
+		let interval = interval.min(self.max_interval.unique_saturated_into());
 
 		// If interval is nonzero we backoff if the current slot isn't far enough ahead of the chain
 		// head.
@@ -927,12 +949,40 @@ mod test {
 		}
 	}
 
+	#[test]
+	fn exponential_backoff_doubles_interval_until_max() {
+		let strategy = BackoffAuthoringOnFinalizedHeadLagging::<NumberFor<Block>> {
+			max_interval: 100,
+			unfinalized_slack: 5,
+			authoring_bias: 1,
+			growth: BackoffGrowth::Exponential,
+		};
+
+		let should_backoff = |head_number: NumberFor<Block>, slot_now: u64| {
+			strategy.should_backoff(head_number, 10.into(), 0, slot_now.into(), "slots")
+		};
+
+		// No backoff within the unfinalized slack.
+		assert!(!should_backoff(5, 11));
+
+		// The interval doubles for every unfinalized block beyond the slack.
+		for (head_number, interval) in [(6, 1), (7, 2), (8, 4), (9, 8)] {
+			assert!(should_backoff(head_number, 10 + interval));
+			assert!(!should_backoff(head_number, 10 + interval + 1));
+		}
+
+		// But never grows beyond the max interval.
+		assert!(should_backoff(1_000, 110));
+		assert!(!should_backoff(1_000, 111));
+	}
+
 	#[test]
 	fn should_never_backoff_when_head_not_advancing() {
 		let strategy = BackoffAuthoringOnFinalizedHeadLagging::<NumberFor<Block>> {
 			max_interval: 100,
 			unfinalized_slack: 5,
 			authoring_bias: 2,
+			growth: BackoffGrowth::Linear,
 		};
 
 		let head_number = 1;
@@ -963,6 +1013,7 @@ mod test {
 			max_interval: 100,
 			unfinalized_slack: 5,
 			authoring_bias: 2,
+			growth: BackoffGrowth::Linear,
 		};
 
 		let mut head_number = 1;
@@ -998,6 +1049,7 @@ mod test {
 			max_interval: 100,
 			unfinalized_slack: 5,
 			authoring_bias: 2,
+			growth: BackoffGrowth::Linear,
 		};
 
 		// The limit `max_interval` is used when the unfinalized chain grows to
@@ -1037,6 +1089,7 @@ mod test {
 			max_interval: 100,
 			unfinalized_slack: 5,
 			authoring_bias: 2,
+			growth: BackoffGrowth::Linear,
 		};
 
 		let finalized_number = 2;
@@ -1099,6 +1152,7 @@ mod test {
 			max_interval: 100,
 			unfinalized_slack: 5,
 			authoring_bias: 2,
+			growth: BackoffGrowth::Linear,
 		};
 
 		let finalized_number = 2;
@@ -1220,6 +1274,7 @@ mod test {
 			max_interval: 100,
 			unfinalized_slack: 5,
 			authoring_bias: 2,
+			growth: BackoffGrowth::Linear,
 		};
 		let expected = expected_time_to_reach_max_interval(&param);
 		let (block_for_max_interval, time_to_reach_limit) = run_until_max_interval(param);
@@ -1234,6 +1289,7 @@ mod test {
 			max_interval: 100,
 			unfinalized_slack: 50,
 			authoring_bias: 2,
+			growth: BackoffGrowth::Linear,
 		};
 		let expected = expected_time_to_reach_max_interval(&param);
 		let (block_for_max_interval, time_to_reach_limit) = run_until_max_interval(param);
@@ -35,6 +35,7 @@ pub mod bench;
 mod children;
 mod parity_db;
 mod pinned_blocks_cache;
+mod purge;
 mod record_stats_state;
 mod stats;
 #[cfg(any(feature = "rocksdb", test))]
@@ -97,6 +98,7 @@ pub use sc_state_db::PruningMode;
 pub use sp_database::Database;
 
 pub use bench::BenchmarkingState;
+pub use purge::{purge_chain_data, ChainData, PurgeReport};
 
 const CACHE_HEADERS: usize = 8;
 
@@ -1714,7 +1716,8 @@ impl<Block: BlockT> Backend<Block> {
 						);
 					}
 				} else if number > best_num + One::one() &&
-					number > One::one() && self.blockchain.header(parent_hash)?.is_none()
+					number > One::one() &&
+					self.blockchain.header(parent_hash)?.is_none()
 				{
 					let gap = (best_num + One::one(), number - One::one());
 					transaction.set(columns::META, meta_keys::BLOCK_GAP, &gap.encode());
@@ -2287,6 +2290,15 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 								meta_keys::FINALIZED_BLOCK,
 								key.clone(),
 							);
+							// The block is no longer final, drop the justifications proving it
+							// was so they do not resurface if the block is imported again.
+							transaction.remove(
+								columns::JUSTIFICATIONS,
+								&utils::number_and_hash_to_lookup_key(
+									*removed.number(),
+									removed_hash,
+								)?,
+							);
 
 							reverted_finalized.insert(removed_hash);
 							if let Some((hash, _)) = self.blockchain.info().finalized_state {
@@ -3981,6 +3993,33 @@ pub(crate) mod tests {
 		}
 	}
 
+	#[test]
+	fn revert_finalized_blocks_removes_their_justifications() {
+		let backend = Backend::<Block>::new_test_with_tx_storage(BlocksPruning::KeepAll, 1);
+
+		let mut parent = Default::default();
+		for i in 0..=10 {
+			parent =
+				insert_block(&backend, i, parent, None, Default::default(), vec![], None).unwrap();
+		}
+
+		let block4 = backend.blockchain().hash(4).unwrap().unwrap();
+		let block8 = backend.blockchain().hash(8).unwrap().unwrap();
+		backend.finalize_block(block4, Some((CONS0_ENGINE_ID, vec![1]).into())).unwrap();
+		backend.finalize_block(block8, Some((CONS0_ENGINE_ID, vec![2]).into())).unwrap();
+
+		backend.revert(5, true).unwrap();
+		assert_eq!(backend.blockchain().info().finalized_number, 5);
+
+		// block #8 is no longer final, block #4 still is
+		let block8_key = utils::number_and_hash_to_lookup_key(8, block8).unwrap();
+		assert!(backend.storage.db.get(columns::JUSTIFICATIONS, &block8_key).is_none());
+		assert_eq!(
+			backend.blockchain().justifications(block4).unwrap(),
+			Some((CONS0_ENGINE_ID, vec![1]).into()),
+		);
+	}
+
 	#[test]
 	fn test_no_duplicated_leaves_allowed() {
 		let backend: Backend<Block> = Backend::new_test(10, 10);
@@ -0,0 +1,325 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Selective removal of chain data from an on-disk database.
+//!
+//! Unlike removing the whole database directory, this only clears the columns that back a given
+//! class of data, leaving headers and everything else in place.
+
+use std::{fmt, path::Path};
+
+use crate::{
+	columns,
+	utils::{meta_keys, DatabaseType},
+	DatabaseSource,
+};
+
+/// Key of the pruning mode in the state meta column, see `sc-state-db`.
+///
+/// It is kept when purging the state, as the state database refuses to open without it.
+const STATE_PRUNING_MODE_KEY: &[u8] = b"mode";
+
+/// A class of chain data that can be purged independently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChainData {
+	/// Trie nodes of all blocks and the state pruning journal.
+	///
+	/// Headers are kept, but the database no longer records a finalized state, the same as
+	/// after a warp sync that has not downloaded the state yet. The node has to re-sync the
+	/// state (e.g. with `--sync warp`) before it can import blocks on top of the finalized one.
+	State,
+	/// Block bodies, including indexed transactions.
+	Bodies,
+	/// Block justifications.
+	Justifications,
+	/// Offchain worker local storage.
+	Offchain,
+}
+
+impl ChainData {
+	/// Database columns that hold this class of data.
+	fn columns(&self) -> &'static [u32] {
+		match self {
+			ChainData::State => &[columns::STATE, columns::STATE_META],
+			ChainData::Bodies => &[columns::BODY, columns::BODY_INDEX, columns::TRANSACTION],
+			ChainData::Justifications => &[columns::JUSTIFICATIONS],
+			ChainData::Offchain => &[columns::OFFCHAIN],
+		}
+	}
+}
+
+impl fmt::Display for ChainData {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let name = match self {
+			ChainData::State => "state",
+			ChainData::Bodies => "bodies",
+			ChainData::Justifications => "justifications",
+			ChainData::Offchain => "offchain",
+		};
+		write!(f, "{}", name)
+	}
+}
+
+/// Amount of data found for a [`ChainData`] class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PurgeReport {
+	/// The class of data.
+	pub data: ChainData,
+	/// Number of database entries.
+	pub entries: u64,
+	/// Size of the entries in bytes.
+	///
+	/// ParityDb does not keep keys of hashed columns, so for it this only counts values.
+	pub bytes: u64,
+}
+
+/// Remove the given classes of data from the database at `source`.
+///
+/// The database must not be opened by anything else. When `dry_run` is set nothing is removed
+/// and the returned reports only describe how much data would be purged.
+pub fn purge_chain_data(
+	source: &DatabaseSource,
+	data: &[ChainData],
+	dry_run: bool,
+) -> sp_blockchain::Result<Vec<PurgeReport>> {
+	match source {
+		DatabaseSource::ParityDb { path } => purge_parity_db(path, data, dry_run),
+		#[cfg(feature = "rocksdb")]
+		DatabaseSource::RocksDb { path, .. } => purge_rocksdb(path, data, dry_run),
+		DatabaseSource::Auto { paritydb_path, rocksdb_path, .. } =>
+			if rocksdb_path.exists() {
+				purge_rocksdb(rocksdb_path, data, dry_run)
+			} else {
+				purge_parity_db(paritydb_path, data, dry_run)
+			},
+		DatabaseSource::Custom { .. } =>
+			Err(backend_error("Cannot purge custom database implementation")),
+	}
+}
+
+fn backend_error(e: impl ToString) -> sp_blockchain::Error {
+	sp_blockchain::Error::Backend(e.to_string())
+}
+
+fn purge_parity_db(
+	path: &Path,
+	data: &[ChainData],
+	dry_run: bool,
+) -> sp_blockchain::Result<Vec<PurgeReport>> {
+	let mut options = crate::parity_db::options(path, DatabaseType::Full);
+
+	let (reports, pruning_mode) = {
+		let db = parity_db::Db::open(&options).map_err(backend_error)?;
+		let pruning_mode = db
+			.get(columns::STATE_META as u8, STATE_PRUNING_MODE_KEY)
+			.map_err(backend_error)?;
+		let mut reports = Vec::with_capacity(data.len());
+		for class in data {
+			let mut report = PurgeReport { data: *class, entries: 0, bytes: 0 };
+			for col in class.columns() {
+				db.iter_column_while(*col as u8, |item| {
+					report.entries += 1;
+					report.bytes += item.value.len() as u64;
+					true
+				})
+				.map_err(backend_error)?;
+			}
+			reports.push(report);
+		}
+		(reports, pruning_mode)
+	};
+
+	if !dry_run {
+		// Columns can only be reset while the database is closed.
+		for class in data {
+			for col in class.columns() {
+				parity_db::Db::reset_column(&mut options, *col as u8, None)
+					.map_err(backend_error)?;
+			}
+		}
+
+		if data.contains(&ChainData::State) {
+			let db =
+				crate::parity_db::open::<crate::DbHash>(path, DatabaseType::Full, false, false)
+					.map_err(backend_error)?;
+			let mut transaction = sp_database::Transaction::new();
+			if let Some(mode) = pruning_mode {
+				transaction.set_from_vec(columns::STATE_META, STATE_PRUNING_MODE_KEY, mode);
+			}
+			transaction.remove(columns::META, meta_keys::FINALIZED_STATE);
+			db.commit(transaction).map_err(backend_error)?;
+		}
+	}
+
+	Ok(reports)
+}
+
+#[cfg(any(feature = "rocksdb", test))]
+fn purge_rocksdb(
+	path: &Path,
+	data: &[ChainData],
+	dry_run: bool,
+) -> sp_blockchain::Result<Vec<PurgeReport>> {
+	use kvdb::KeyValueDB;
+
+	let mut db_config = kvdb_rocksdb::DatabaseConfig::with_columns(crate::utils::NUM_COLUMNS);
+	db_config.create_if_missing = false;
+	let db = kvdb_rocksdb::Database::open(&db_config, path).map_err(backend_error)?;
+
+	let mut reports = Vec::with_capacity(data.len());
+	let mut transaction = kvdb::DBTransaction::new();
+	for class in data {
+		let mut report = PurgeReport { data: *class, entries: 0, bytes: 0 };
+		for col in class.columns() {
+			for item in db.iter(*col) {
+				let (key, value) = item.map_err(backend_error)?;
+				report.entries += 1;
+				report.bytes += (key.len() + value.len()) as u64;
+			}
+			transaction.delete_prefix(*col, &[]);
+		}
+		reports.push(report);
+	}
+
+	if data.contains(&ChainData::State) {
+		if let Some(mode) =
+			db.get(columns::STATE_META, STATE_PRUNING_MODE_KEY).map_err(backend_error)?
+		{
+			transaction.put_vec(columns::STATE_META, STATE_PRUNING_MODE_KEY, mode.to_vec());
+		}
+		transaction.delete(columns::META, meta_keys::FINALIZED_STATE);
+	}
+
+	if !dry_run {
+		db.write(transaction).map_err(backend_error)?;
+	}
+
+	Ok(reports)
+}
+
+#[cfg(not(any(feature = "rocksdb", test)))]
+fn purge_rocksdb(
+	_path: &Path,
+	_data: &[ChainData],
+	_dry_run: bool,
+) -> sp_blockchain::Result<Vec<PurgeReport>> {
+	Err(backend_error("`rocksdb` feature not enabled, database can not be opened"))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_database::{Database, Transaction};
+
+	fn populate(db: &dyn Database<crate::DbHash>) {
+		let mut tx = Transaction::new();
+		tx.set(columns::OFFCHAIN, b"key1", b"value1");
+		tx.set(columns::OFFCHAIN, b"key2", b"value2");
+		tx.set(columns::JUSTIFICATIONS, b"block", b"justification");
+		tx.set(columns::HEADER, b"block", b"header");
+		db.commit(tx).unwrap();
+	}
+
+	fn check_purge(
+		source: DatabaseSource,
+		open: impl Fn() -> std::sync::Arc<dyn Database<crate::DbHash>>,
+	) {
+		let reports = purge_chain_data(&source, &[ChainData::Offchain], true).unwrap();
+		assert_eq!(reports.len(), 1);
+		assert_eq!(reports[0].data, ChainData::Offchain);
+		assert_eq!(reports[0].entries, 2);
+
+		{
+			let db = open();
+			assert_eq!(db.get(columns::OFFCHAIN, b"key1"), Some(b"value1".to_vec()));
+		}
+
+		purge_chain_data(&source, &[ChainData::Offchain], false).unwrap();
+
+		let db = open();
+		assert_eq!(db.get(columns::OFFCHAIN, b"key1"), None);
+		assert_eq!(db.get(columns::OFFCHAIN, b"key2"), None);
+		assert_eq!(db.get(columns::JUSTIFICATIONS, b"block"), Some(b"justification".to_vec()));
+		assert_eq!(db.get(columns::HEADER, b"block"), Some(b"header".to_vec()));
+	}
+
+	#[test]
+	fn purges_only_selected_parity_db_columns() {
+		let dir = tempfile::TempDir::new().unwrap();
+		let path = dir.path().join("paritydb");
+		let open = || crate::parity_db::open(&path, DatabaseType::Full, true, false).unwrap();
+		populate(&*open());
+
+		check_purge(DatabaseSource::ParityDb { path: path.clone() }, open);
+	}
+
+	#[test]
+	fn database_can_be_reopened_after_state_purge() {
+		use crate::tests::{insert_header, Block};
+		use sc_client_api::{backend::Backend as _, blockchain::HeaderBackend as _};
+
+		let dir = tempfile::TempDir::new().unwrap();
+		let source = DatabaseSource::ParityDb { path: dir.path().join("paritydb") };
+		let settings = || crate::DatabaseSettings {
+			trie_cache_maximum_size: None,
+			state_pruning: Some(crate::PruningMode::blocks_pruning(16)),
+			source: source.clone(),
+			blocks_pruning: crate::BlocksPruning::KeepAll,
+		};
+
+		let block1 = {
+			let backend = crate::Backend::<Block>::new(settings(), 0).unwrap();
+			let genesis = insert_header(&backend, 0, Default::default(), None, Default::default());
+			let block1 = insert_header(&backend, 1, genesis, None, Default::default());
+			backend.finalize_block(block1, None).unwrap();
+			assert!(backend.have_state_at(block1, 1));
+			block1
+		};
+
+		purge_chain_data(&source, &[ChainData::State], false).unwrap();
+
+		let backend = crate::Backend::<Block>::new(settings(), 0).unwrap();
+		let info = backend.blockchain().info();
+		assert_eq!(info.finalized_hash, block1);
+		assert_eq!(info.finalized_state, None);
+		assert!(backend.blockchain().header(block1).unwrap().is_some());
+		assert!(!backend.have_state_at(block1, 1));
+	}
+
+	#[test]
+	fn purges_only_selected_rocksdb_columns() {
+		let dir = tempfile::TempDir::new().unwrap();
+		let path = dir.path().join("rocksdb");
+		let open = || {
+			let mut db_config =
+				kvdb_rocksdb::DatabaseConfig::with_columns(crate::utils::NUM_COLUMNS);
+			db_config.create_if_missing = true;
+			sp_database::as_database(kvdb_rocksdb::Database::open(&db_config, &path).unwrap())
+		};
+		populate(&*open());
+
+		check_purge(
+			DatabaseSource::Auto {
+				paritydb_path: dir.path().join("paritydb"),
+				rocksdb_path: path.clone(),
+				cache_size: 128,
+			},
+			open,
+		);
+	}
+}
@@ -16,12 +16,16 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::OutputFormat;
+use crate::{
+	json::{self, BandwidthRecord, BlockRecord, JsonWriter, StatusRecord},
+	OutputFormat,
+};
 use ansi_term::Colour;
 use log::info;
 use sc_client_api::ClientInfo;
 use sc_network::NetworkStatus;
 use sc_network_sync::{SyncState, SyncStatus, WarpSyncPhase, WarpSyncProgress};
+use sc_transaction_pool_api::PoolStatus;
 use sp_runtime::traits::{Block as BlockT, CheckedDiv, NumberFor, Saturating, Zero};
 use std::{fmt, time::Instant};
 
@@ -37,6 +41,8 @@ use std::{fmt, time::Instant};
 ///
 /// Call `InformantDisplay::new` to initialize the state, then regularly call `display` with the
 /// information to display.
+///
+/// If the format asks for JSON status records, a record is written instead of the line.
 pub struct InformantDisplay<B: BlockT> {
 	/// Head of chain block number from the last time `display` has been called.
 	/// `None` if `display` has never been called.
@@ -49,6 +55,8 @@ pub struct InformantDisplay<B: BlockT> {
 	last_total_bytes_outbound: u64,
 	/// The format to print output in.
 	format: OutputFormat,
+	/// Writer of JSON status records, if they replace the status line.
+	json: Option<JsonWriter>,
 }
 
 impl<B: BlockT> InformantDisplay<B> {
@@ -59,22 +67,25 @@ impl<B: BlockT> InformantDisplay<B> {
 			last_update: Instant::now(),
 			last_total_bytes_inbound: 0,
 			last_total_bytes_outbound: 0,
+			json: JsonWriter::new(&format.status_output),
 			format,
 		}
 	}
 
-	/// Displays the informant by calling `info!`.
+	/// Displays the informant by calling `info!`, or writes a JSON status record.
 	pub fn display(
 		&mut self,
 		info: &ClientInfo<B>,
 		net_status: NetworkStatus,
 		sync_status: SyncStatus<B>,
+		pool_status: PoolStatus,
 	) {
 		let best_number = info.chain.best_number;
 		let best_hash = info.chain.best_hash;
 		let finalized_number = info.chain.finalized_number;
 		let num_connected_peers = sync_status.num_connected_peers;
 		let speed = speed::<B>(best_number, self.last_number, self.last_update);
+		let import_speed = speed_bps::<B>(best_number, self.last_number, self.last_update);
 		let total_bytes_inbound = net_status.total_bytes_inbound;
 		let total_bytes_outbound = net_status.total_bytes_outbound;
 
@@ -93,6 +104,42 @@ impl<B: BlockT> InformantDisplay<B> {
 			(diff_bytes_inbound, diff_bytes_outbound)
 		};
 
+		if let Some(json) = &mut self.json {
+			let status = match (&sync_status.state, &sync_status.state_sync, &sync_status.warp_sync)
+			{
+				(_, _, Some(_)) => "warpSync",
+				(_, Some(_), _) => "stateSync",
+				(SyncState::Idle, _, _) => "idle",
+				(SyncState::Downloading { .. }, _, _) => "syncing",
+				(SyncState::Importing { .. }, _, _) => "importing",
+			};
+			let sync_target = match sync_status.state {
+				SyncState::Idle => None,
+				SyncState::Downloading { target } | SyncState::Importing { target } => Some(target),
+			};
+
+			json.write(&StatusRecord::<B> {
+				timestamp: json::timestamp(),
+				status,
+				sync_target,
+				warp_sync_phase: sync_status.warp_sync.map(|warp| warp.phase.to_string()),
+				state_sync_percentage: sync_status.state_sync.map(|state| state.percentage),
+				best: BlockRecord { number: best_number, hash: best_hash },
+				finalized: BlockRecord {
+					number: finalized_number,
+					hash: info.chain.finalized_hash,
+				},
+				peers: num_connected_peers,
+				bandwidth: BandwidthRecord {
+					inbound_bytes_per_sec: avg_bytes_per_sec_inbound,
+					outbound_bytes_per_sec: avg_bytes_per_sec_outbound,
+				},
+				transaction_pool: pool_status.into(),
+				import_speed,
+			});
+			return
+		}
+
 		let (level, status, target) =
 			match (sync_status.state, sync_status.state_sync, sync_status.warp_sync) {
 				// Do not set status to "Block history" when we are doing a major sync.
@@ -204,6 +251,21 @@ fn speed<B: BlockT>(
 	}
 }
 
+/// Calculates `(best_number - last_number) / (now - last_update)` in blocks per second.
+///
+/// Returns `None` if `display` has never been called or the number of blocks can't be converted
+/// to a regular integer.
+fn speed_bps<B: BlockT>(
+	best_number: NumberFor<B>,
+	last_number: Option<NumberFor<B>>,
+	last_update: Instant,
+) -> Option<f64> {
+	let diff: u128 = best_number.saturating_sub(last_number?).try_into().ok()?;
+	let elapsed_ms = last_update.elapsed().as_millis();
+
+	Some(diff.saturating_mul(10_000).checked_div(elapsed_ms).map_or(0.0, |s| s as f64) / 10.0)
+}
+
 /// Contains a number of bytes per second. Implements `fmt::Display` and shows this number of bytes
 /// per second in a nice way.
 struct TransferRateFormat(u64);
@@ -0,0 +1,105 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Human readable logging of the extrinsics of imported blocks.
+
+use codec::Encode;
+use log::debug;
+use sc_client_api::BlockBackend;
+use sp_api::{Core, Metadata, ProvideRuntimeApi};
+use sp_runtime::traits::Block as BlockT;
+use substrate_metadata_decoder::Decoder;
+
+/// The log target extrinsics are logged to.
+const LOG_TARGET: &str = "informant";
+
+/// Logs the extrinsics of imported blocks, decoded according to the runtime metadata.
+///
+/// Nothing is decoded unless debug logging is enabled for the `informant` target.
+#[derive(Default)]
+pub(crate) struct ExtrinsicLogger {
+	/// The decoder for the runtime with the given spec version.
+	decoder: Option<(u32, Decoder)>,
+}
+
+impl ExtrinsicLogger {
+	/// Log the extrinsics of the block with the given hash.
+	pub(crate) fn log<B: BlockT, C>(&mut self, client: &C, hash: B::Hash)
+	where
+		C: BlockBackend<B> + ProvideRuntimeApi<B>,
+		C::Api: Metadata<B>,
+	{
+		if !log::log_enabled!(target: LOG_TARGET, log::Level::Debug) {
+			return
+		}
+
+		let Some(decoder) = self.decoder::<B, C>(client, hash) else { return };
+		let extrinsics = match client.block_body(hash) {
+			Ok(Some(extrinsics)) => extrinsics,
+			Ok(None) => return,
+			Err(e) => {
+				debug!(target: LOG_TARGET, "Failed to get the body of block {}: {}", hash, e);
+				return
+			},
+		};
+
+		for (index, extrinsic) in extrinsics.iter().enumerate() {
+			match decoder.decode_extrinsic(&extrinsic.encode()) {
+				Ok(extrinsic) => debug!(
+					target: LOG_TARGET,
+					"  Extrinsic #{}: {} {}",
+					index,
+					extrinsic.call,
+					serde_json::to_string(&extrinsic.call.fields).unwrap_or_default(),
+				),
+				Err(e) => debug!(
+					target: LOG_TARGET,
+					"  Extrinsic #{}: failed to decode: {}",
+					index,
+					e,
+				),
+			}
+		}
+	}
+
+	/// Returns the decoder for the runtime at the given block, creating it on runtime upgrades.
+	fn decoder<B: BlockT, C>(&mut self, client: &C, hash: B::Hash) -> Option<&Decoder>
+	where
+		C: ProvideRuntimeApi<B>,
+		C::Api: Metadata<B>,
+	{
+		let api = client.runtime_api();
+		let spec_version = api
+			.version(hash)
+			.map_err(|e| debug!(target: LOG_TARGET, "Failed to get the runtime version: {}", e))
+			.ok()?
+			.spec_version;
+
+		if self.decoder.as_ref().map_or(true, |(version, _)| *version != spec_version) {
+			let decoder = api
+				.metadata(hash)
+				.map_err(|e| e.to_string())
+				.and_then(|metadata| Decoder::new(&metadata).map_err(|e| e.to_string()))
+				.map_err(|e| debug!(target: LOG_TARGET, "Failed to create the decoder: {}", e))
+				.ok()?;
+			self.decoder = Some((spec_version, decoder));
+		}
+
+		self.decoder.as_ref().map(|(_, decoder)| decoder)
+	}
+}
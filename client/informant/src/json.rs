@@ -0,0 +1,240 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Structured status records, written as one JSON object per line instead of the console status
+//! line.
+
+use crate::StatusOutput;
+use log::debug;
+use sc_transaction_pool_api::PoolStatus;
+use serde::Serialize;
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+use std::{
+	io::Write,
+	sync::mpsc::{self, SyncSender, TrySendError},
+	time::{SystemTime, UNIX_EPOCH},
+};
+#[cfg(unix)]
+use std::{os::unix::net::UnixStream, path::PathBuf, time::Duration};
+
+/// How long writing a record to the unix socket may block the writer thread.
+#[cfg(unix)]
+const SOCKET_WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Maximum number of records waiting for the writer thread. Further records are dropped until
+/// the output accepts writes again.
+const MAX_QUEUED_RECORDS: usize = 16;
+
+/// A block, identified by number and hash.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase", bound(serialize = ""))]
+pub(crate) struct BlockRecord<B: BlockT> {
+	pub number: NumberFor<B>,
+	pub hash: B::Hash,
+}
+
+/// Average network bandwidth since the previous record.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BandwidthRecord {
+	pub inbound_bytes_per_sec: u64,
+	pub outbound_bytes_per_sec: u64,
+}
+
+/// Size of the transaction pool.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PoolRecord {
+	pub ready: usize,
+	pub ready_bytes: usize,
+	pub future: usize,
+	pub future_bytes: usize,
+}
+
+impl From<PoolStatus> for PoolRecord {
+	fn from(status: PoolStatus) -> Self {
+		Self {
+			ready: status.ready,
+			ready_bytes: status.ready_bytes,
+			future: status.future,
+			future_bytes: status.future_bytes,
+		}
+	}
+}
+
+/// A periodic status record.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase", bound(serialize = ""))]
+pub(crate) struct StatusRecord<B: BlockT> {
+	/// Milliseconds since the unix epoch.
+	pub timestamp: u64,
+	/// One of `idle`, `syncing`, `importing`, `stateSync` or `warpSync`.
+	pub status: &'static str,
+	pub sync_target: Option<NumberFor<B>>,
+	pub warp_sync_phase: Option<String>,
+	pub state_sync_percentage: Option<u32>,
+	pub best: BlockRecord<B>,
+	pub finalized: BlockRecord<B>,
+	pub peers: usize,
+	pub bandwidth: BandwidthRecord,
+	pub transaction_pool: PoolRecord,
+	/// Imported blocks per second since the previous record. `None` for the first record.
+	pub import_speed: Option<f64>,
+}
+
+/// Milliseconds since the unix epoch.
+pub(crate) fn timestamp() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |since_epoch| since_epoch.as_millis() as u64)
+}
+
+/// Writes status records to the configured output.
+///
+/// Writing to the output may block, e.g. while the reader of the unix socket is not keeping up,
+/// so the records are written by a dedicated thread instead of the informant task.
+pub(crate) struct JsonWriter {
+	records: SyncSender<Vec<u8>>,
+}
+
+impl JsonWriter {
+	/// Returns the writer for the given output, or `None` if records are not written as JSON.
+	pub(crate) fn new(output: &StatusOutput) -> Option<Self> {
+		let mut output = match output {
+			StatusOutput::Console => return None,
+			StatusOutput::JsonStdout => Output::Stdout,
+			#[cfg(unix)]
+			StatusOutput::JsonUnixSocket(path) => Output::UnixSocket { path: path.clone(), stream: None },
+		};
+
+		let (records, receiver) = mpsc::sync_channel::<Vec<u8>>(MAX_QUEUED_RECORDS);
+		std::thread::Builder::new()
+			.name("informant-json".into())
+			.spawn(move || {
+				// Ends once the `JsonWriter` is dropped.
+				for line in receiver {
+					output.write(&line);
+				}
+			})
+			.map_err(|e| debug!(target: "informant", "Failed to spawn the JSON writer: {}", e))
+			.ok()?;
+
+		Some(Self { records })
+	}
+
+	/// Queue the record to be written, followed by a newline.
+	pub(crate) fn write(&mut self, record: &impl Serialize) {
+		let mut line = match serde_json::to_vec(record) {
+			Ok(line) => line,
+			Err(e) => {
+				debug!(target: "informant", "Failed to encode status record: {}", e);
+				return
+			},
+		};
+		line.push(b'\n');
+
+		if let Err(TrySendError::Full(_)) = self.records.try_send(line) {
+			debug!(target: "informant", "Dropping status record, the output is not keeping up");
+		}
+	}
+}
+
+/// The output status records are written to.
+enum Output {
+	Stdout,
+	#[cfg(unix)]
+	UnixSocket {
+		path: PathBuf,
+		stream: Option<UnixStream>,
+	},
+}
+
+impl Output {
+	fn write(&mut self, line: &[u8]) {
+		match self {
+			Self::Stdout => {
+				let mut stdout = std::io::stdout().lock();
+				if let Err(e) = stdout.write_all(line).and_then(|_| stdout.flush()) {
+					debug!(target: "informant", "Failed to write status record: {}", e);
+				}
+			},
+			#[cfg(unix)]
+			Self::UnixSocket { path, stream } => {
+				if stream.is_none() {
+					*stream = UnixStream::connect(&*path)
+						.and_then(|s| s.set_write_timeout(Some(SOCKET_WRITE_TIMEOUT)).map(|_| s))
+						.map_err(
+							|e| debug!(target: "informant", "Failed to connect to {:?}: {}", path, e),
+						)
+						.ok();
+				}
+
+				if let Some(s) = stream {
+					if let Err(e) = s.write_all(line) {
+						debug!(target: "informant", "Failed to write status record: {}", e);
+						// Reconnect on the next record.
+						*stream = None;
+					}
+				}
+			},
+		}
+	}
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+	use super::*;
+	use std::{
+		io::{BufRead, BufReader},
+		os::unix::net::UnixListener,
+		time::Instant,
+	};
+
+	#[test]
+	fn writes_records_to_the_unix_socket() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("informant.sock");
+		let listener = UnixListener::bind(&path).unwrap();
+
+		let mut writer = JsonWriter::new(&StatusOutput::JsonUnixSocket(path)).unwrap();
+		writer.write(&PoolRecord { ready: 1, ready_bytes: 2, future: 3, future_bytes: 4 });
+
+		let (stream, _) = listener.accept().unwrap();
+		let mut line = String::new();
+		BufReader::new(stream).read_line(&mut line).unwrap();
+		assert_eq!(line, "{\"ready\":1,\"readyBytes\":2,\"future\":3,\"futureBytes\":4}\n");
+	}
+
+	#[test]
+	fn writing_does_not_block_on_a_stalled_reader() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("informant.sock");
+		// Accepts connections, but never reads from them.
+		let _listener = UnixListener::bind(&path).unwrap();
+
+		let mut writer = JsonWriter::new(&StatusOutput::JsonUnixSocket(path)).unwrap();
+		let record = vec![0u8; 64 * 1024];
+		let started = Instant::now();
+		for _ in 0..100 {
+			writer.write(&record);
+		}
+
+		// Writing the records to the socket would block for `SOCKET_WRITE_TIMEOUT` each.
+		assert!(started.elapsed() < SOCKET_WRITE_TIMEOUT);
+	}
+}
@@ -17,25 +17,51 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 //! Console informant. Prints sync progress and block events. Runs on the calling thread.
+//!
+//! The periodic status line can be replaced by structured JSON records, see [`StatusOutput`].
+//! With debug logging enabled for the `informant` target, the extrinsics of imported blocks are
+//! logged, decoded according to the runtime metadata.
 
 use ansi_term::{Colour, Style};
 use futures::prelude::*;
 use futures_timer::Delay;
 use log::{debug, info, trace};
-use sc_client_api::{BlockchainEvents, UsageProvider};
+use sc_client_api::{BlockBackend, BlockchainEvents, UsageProvider};
 use sc_network::NetworkStatusProvider;
 use sc_network_sync::SyncStatusProvider;
+use sc_transaction_pool_api::TransactionPool;
+use sp_api::{Metadata, ProvideRuntimeApi};
 use sp_blockchain::HeaderMetadata;
 use sp_runtime::traits::{Block as BlockT, Header};
+#[cfg(unix)]
+use std::path::PathBuf;
 use std::{collections::VecDeque, fmt::Display, sync::Arc, time::Duration};
 
 mod display;
+mod extrinsics;
+mod json;
 
 /// Creates a stream that returns a new value every `duration`.
 fn interval(duration: Duration) -> impl Stream<Item = ()> + Unpin {
 	futures::stream::unfold((), move |_| Delay::new(duration).map(|_| Some(((), ())))).map(drop)
 }
 
+/// Where the informant writes the periodic status to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum StatusOutput {
+	/// Print a human readable status line to the log.
+	#[default]
+	Console,
+	/// Write a JSON status record per line to stdout.
+	JsonStdout,
+	/// Write a JSON status record per line to the unix socket at the given path.
+	///
+	/// The informant connects to the socket, which must be bound by the consumer, and reconnects
+	/// whenever the connection is lost. Records are dropped while there is no connection.
+	#[cfg(unix)]
+	JsonUnixSocket(PathBuf),
+}
+
 /// The format to print telemetry output in.
 #[derive(Clone, Debug)]
 pub struct OutputFormat {
@@ -43,11 +69,13 @@ pub struct OutputFormat {
 	///
 	/// Is enabled by default.
 	pub enable_color: bool,
+	/// Where to write the periodic status to.
+	pub status_output: StatusOutput,
 }
 
 impl Default for OutputFormat {
 	fn default() -> Self {
-		Self { enable_color: true }
+		Self { enable_color: true, status_output: StatusOutput::Console }
 	}
 }
 
@@ -93,12 +121,23 @@ impl OutputFormat {
 }
 
 /// Builds the informant and returns a `Future` that drives the informant.
-pub async fn build<B: BlockT, C, N, S>(client: Arc<C>, network: N, syncing: S, format: OutputFormat)
-where
+pub async fn build<B: BlockT, C, N, S, P>(
+	client: Arc<C>,
+	network: N,
+	syncing: S,
+	transaction_pool: Arc<P>,
+	format: OutputFormat,
+) where
 	N: NetworkStatusProvider,
 	S: SyncStatusProvider<B>,
-	C: UsageProvider<B> + HeaderMetadata<B> + BlockchainEvents<B>,
+	C: UsageProvider<B>
+		+ HeaderMetadata<B>
+		+ BlockchainEvents<B>
+		+ BlockBackend<B>
+		+ ProvideRuntimeApi<B>,
+	C::Api: Metadata<B>,
 	<C as HeaderMetadata<B>>::Error: Display,
+	P: TransactionPool<Block = B>,
 {
 	let mut display = display::InformantDisplay::new(format.clone());
 
@@ -124,7 +163,7 @@ where
 					"Usage statistics not displayed as backend does not provide it",
 				)
 			}
-			display.display(&info, net_status, sync_status);
+			display.display(&info, net_status, sync_status, transaction_pool.status());
 			future::ready(())
 		});
 
@@ -139,7 +178,12 @@ fn display_block_import<B: BlockT, C>(
 	format: OutputFormat,
 ) -> impl Future<Output = ()>
 where
-	C: UsageProvider<B> + HeaderMetadata<B> + BlockchainEvents<B>,
+	C: UsageProvider<B>
+		+ HeaderMetadata<B>
+		+ BlockchainEvents<B>
+		+ BlockBackend<B>
+		+ ProvideRuntimeApi<B>,
+	C::Api: Metadata<B>,
 	<C as HeaderMetadata<B>>::Error: Display,
 {
 	let mut last_best = {
@@ -151,6 +195,8 @@ where
 	let mut last_blocks = VecDeque::new();
 	let max_blocks_to_track = 100;
 
+	let mut extrinsic_logger = extrinsics::ExtrinsicLogger::default();
+
 	client.import_notification_stream().for_each(move |n| {
 		// detect and log reorganizations.
 		if let Some((ref last_num, ref last_hash)) = last_best {
@@ -193,6 +239,7 @@ where
 				format.print_with_color(Colour::White.bold(), n.header.number()),
 				n.hash,
 			);
+			extrinsic_logger.log(&*client, n.hash);
 		}
 
 		future::ready(())
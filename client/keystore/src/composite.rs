@@ -0,0 +1,413 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Keystore combining an external signer with a local keystore.
+//!
+//! Keys are looked up in the local keystore first and the remote signer is only asked for keys
+//! the local keystore does not hold. Operations the signer can't perform, like VRF signing, are
+//! always served locally, so keys that need them (e.g. BABE) have to be kept in the local
+//! keystore. New keys are generated and inserted on the remote signer.
+
+use sp_core::{
+	crypto::{ByteArray, KeyTypeId},
+	ecdsa, ed25519, sr25519,
+};
+use sp_keystore::{Error as TraitError, Keystore};
+use std::sync::Arc;
+
+sp_keystore::bandersnatch_experimental_enabled! {
+use sp_core::bandersnatch;
+}
+
+sp_keystore::bls_experimental_enabled! {
+use sp_core::{bls377, bls381, ecdsa_bls377};
+}
+
+use crate::{LocalKeystore, RemoteKeystore};
+
+/// A [`RemoteKeystore`] that falls back to a [`LocalKeystore`].
+pub struct CompositeKeystore {
+	remote: RemoteKeystore,
+	local: Arc<LocalKeystore>,
+}
+
+impl CompositeKeystore {
+	/// Create a keystore forwarding to `remote` all keys that `local` does not hold.
+	pub fn new(remote: RemoteKeystore, local: Arc<LocalKeystore>) -> Self {
+		Self { remote, local }
+	}
+
+	/// The remote signer.
+	pub fn remote(&self) -> &RemoteKeystore {
+		&self.remote
+	}
+
+	/// The local keystore.
+	pub fn local(&self) -> Arc<LocalKeystore> {
+		self.local.clone()
+	}
+
+	fn is_local(&self, key_type: KeyTypeId, public: &[u8]) -> bool {
+		self.local.has_keys(&[(public.to_vec(), key_type)])
+	}
+
+	/// Run `f` on the keystore holding `public`.
+	fn with_owner<R>(
+		&self,
+		key_type: KeyTypeId,
+		public: &[u8],
+		f: impl FnOnce(&dyn Keystore) -> R,
+	) -> R {
+		if self.is_local(key_type, public) {
+			f(&*self.local)
+		} else {
+			f(&self.remote)
+		}
+	}
+}
+
+/// Local keys followed by the remote keys not held locally.
+fn merge<T: PartialEq>(mut local: Vec<T>, remote: Vec<T>) -> Vec<T> {
+	for key in remote {
+		if !local.contains(&key) {
+			local.push(key);
+		}
+	}
+	local
+}
+
+impl Keystore for CompositeKeystore {
+	fn insert(
+		&self,
+		key_type: KeyTypeId,
+		suri: &str,
+		public: &[u8],
+	) -> std::result::Result<(), ()> {
+		self.remote.insert(key_type, suri, public)
+	}
+
+	fn keys(&self, key_type: KeyTypeId) -> std::result::Result<Vec<Vec<u8>>, TraitError> {
+		let local = self.local.keys(key_type)?;
+		match self.remote.keys(key_type) {
+			Ok(remote) => Ok(merge(local, remote)),
+			Err(e) => {
+				log::debug!(target: "keystore", "Listing remote keys failed: {}", e);
+				Ok(local)
+			},
+		}
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		public_keys.iter().all(|(public, key_type)| {
+			self.is_local(*key_type, public) || self.remote.has_keys(&[(public.clone(), *key_type)])
+		})
+	}
+
+	fn remove(&self, key_type: KeyTypeId, public: &[u8]) -> std::result::Result<bool, TraitError> {
+		if self.local.remove(key_type, public)? {
+			return Ok(true)
+		}
+		self.remote.remove(key_type, public)
+	}
+
+	fn sr25519_public_keys(&self, key_type: KeyTypeId) -> Vec<sr25519::Public> {
+		merge(self.local.sr25519_public_keys(key_type), self.remote.sr25519_public_keys(key_type))
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<sr25519::Public, TraitError> {
+		self.remote.sr25519_generate_new(key_type, seed)
+	}
+
+	fn sr25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<sr25519::Signature>, TraitError> {
+		self.with_owner(key_type, public.as_slice(), |k| k.sr25519_sign(key_type, public, msg))
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		data: &sr25519::vrf::VrfSignData,
+	) -> std::result::Result<Option<sr25519::vrf::VrfSignature>, TraitError> {
+		self.local.sr25519_vrf_sign(key_type, public, data)
+	}
+
+	fn sr25519_vrf_pre_output(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		input: &sr25519::vrf::VrfInput,
+	) -> std::result::Result<Option<sr25519::vrf::VrfPreOutput>, TraitError> {
+		self.local.sr25519_vrf_pre_output(key_type, public, input)
+	}
+
+	fn ed25519_public_keys(&self, key_type: KeyTypeId) -> Vec<ed25519::Public> {
+		merge(self.local.ed25519_public_keys(key_type), self.remote.ed25519_public_keys(key_type))
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ed25519::Public, TraitError> {
+		self.remote.ed25519_generate_new(key_type, seed)
+	}
+
+	fn ed25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ed25519::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ed25519::Signature>, TraitError> {
+		self.with_owner(key_type, public.as_slice(), |k| k.ed25519_sign(key_type, public, msg))
+	}
+
+	fn ecdsa_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa::Public> {
+		merge(self.local.ecdsa_public_keys(key_type), self.remote.ecdsa_public_keys(key_type))
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ecdsa::Public, TraitError> {
+		self.remote.ecdsa_generate_new(key_type, seed)
+	}
+
+	fn ecdsa_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.with_owner(key_type, public.as_slice(), |k| k.ecdsa_sign(key_type, public, msg))
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.with_owner(key_type, public.as_slice(), |k| {
+			k.ecdsa_sign_prehashed(key_type, public, msg)
+		})
+	}
+
+	sp_keystore::bandersnatch_experimental_enabled! {
+		fn bandersnatch_public_keys(&self, key_type: KeyTypeId) -> Vec<bandersnatch::Public> {
+			merge(
+				self.local.bandersnatch_public_keys(key_type),
+				self.remote.bandersnatch_public_keys(key_type),
+			)
+		}
+
+		fn bandersnatch_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<bandersnatch::Public, TraitError> {
+			self.remote.bandersnatch_generate_new(key_type, seed)
+		}
+
+		fn bandersnatch_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<bandersnatch::Signature>, TraitError> {
+			self.with_owner(key_type, public.as_slice(), |k| {
+				k.bandersnatch_sign(key_type, public, msg)
+			})
+		}
+
+		fn bandersnatch_vrf_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			data: &bandersnatch::vrf::VrfSignData,
+		) -> std::result::Result<Option<bandersnatch::vrf::VrfSignature>, TraitError> {
+			self.local.bandersnatch_vrf_sign(key_type, public, data)
+		}
+
+		fn bandersnatch_vrf_pre_output(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			input: &bandersnatch::vrf::VrfInput,
+		) -> std::result::Result<Option<bandersnatch::vrf::VrfPreOutput>, TraitError> {
+			self.local.bandersnatch_vrf_pre_output(key_type, public, input)
+		}
+
+		fn bandersnatch_ring_vrf_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			data: &bandersnatch::vrf::VrfSignData,
+			prover: &bandersnatch::ring_vrf::RingProver,
+		) -> std::result::Result<Option<bandersnatch::ring_vrf::RingVrfSignature>, TraitError> {
+			self.local.bandersnatch_ring_vrf_sign(key_type, public, data, prover)
+		}
+	}
+
+	sp_keystore::bls_experimental_enabled! {
+		fn bls381_public_keys(&self, key_type: KeyTypeId) -> Vec<bls381::Public> {
+			merge(self.local.bls381_public_keys(key_type), self.remote.bls381_public_keys(key_type))
+		}
+
+		fn bls377_public_keys(&self, key_type: KeyTypeId) -> Vec<bls377::Public> {
+			merge(self.local.bls377_public_keys(key_type), self.remote.bls377_public_keys(key_type))
+		}
+
+		fn ecdsa_bls377_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa_bls377::Public> {
+			merge(
+				self.local.ecdsa_bls377_public_keys(key_type),
+				self.remote.ecdsa_bls377_public_keys(key_type),
+			)
+		}
+
+		fn bls381_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<bls381::Public, TraitError> {
+			self.remote.bls381_generate_new(key_type, seed)
+		}
+
+		fn bls377_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<bls377::Public, TraitError> {
+			self.remote.bls377_generate_new(key_type, seed)
+		}
+
+		fn ecdsa_bls377_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<ecdsa_bls377::Public, TraitError> {
+			self.remote.ecdsa_bls377_generate_new(key_type, seed)
+		}
+
+		fn bls381_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bls381::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<bls381::Signature>, TraitError> {
+			self.with_owner(key_type, public.as_slice(), |k| k.bls381_sign(key_type, public, msg))
+		}
+
+		fn bls377_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bls377::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<bls377::Signature>, TraitError> {
+			self.with_owner(key_type, public.as_slice(), |k| k.bls377_sign(key_type, public, msg))
+		}
+
+		fn ecdsa_bls377_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &ecdsa_bls377::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<ecdsa_bls377::Signature>, TraitError> {
+			self.with_owner(key_type, public.as_slice(), |k| {
+				k.ecdsa_bls377_sign(key_type, public, msg)
+			})
+		}
+
+		fn ecdsa_bls377_sign_with_keccak256(
+			&self,
+			key_type: KeyTypeId,
+			public: &ecdsa_bls377::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<ecdsa_bls377::Signature>, TraitError> {
+			self.local.ecdsa_bls377_sign_with_keccak256(key_type, public, msg)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::remote::tests::MockSigner;
+	use sp_core::{sr25519::vrf::VrfTranscript, Pair};
+	use std::sync::atomic::Ordering;
+
+	const KEY_TYPE: KeyTypeId = KeyTypeId(*b"test");
+
+	fn keystore() -> (MockSigner, CompositeKeystore) {
+		let signer = MockSigner::new();
+		let remote = RemoteKeystore::with_transport(signer.clone());
+		(signer, CompositeKeystore::new(remote, Arc::new(LocalKeystore::in_memory())))
+	}
+
+	#[test]
+	fn lists_and_signs_with_local_and_remote_keys() {
+		let (_, keystore) = keystore();
+
+		let local = keystore.local().sr25519_generate_new(KEY_TYPE, None).unwrap();
+		let remote = keystore.sr25519_generate_new(KEY_TYPE, None).unwrap();
+		assert!(!keystore.local().has_keys(&[(remote.to_raw_vec(), KEY_TYPE)]));
+
+		assert_eq!(keystore.sr25519_public_keys(KEY_TYPE), vec![local, remote]);
+		assert!(
+			keystore.has_keys(&[(local.to_raw_vec(), KEY_TYPE), (remote.to_raw_vec(), KEY_TYPE)])
+		);
+
+		for public in [local, remote] {
+			let signature = keystore.sr25519_sign(KEY_TYPE, &public, b"message").unwrap().unwrap();
+			assert!(sr25519::Pair::verify(&signature, b"message", &public));
+		}
+	}
+
+	#[test]
+	fn vrf_signing_uses_the_local_keystore() {
+		let (_, keystore) = keystore();
+
+		let local = keystore.local().sr25519_generate_new(KEY_TYPE, None).unwrap();
+		let remote = keystore.sr25519_generate_new(KEY_TYPE, None).unwrap();
+		let data = VrfTranscript::new(b"label", &[(b"domain", b"data")]).into();
+
+		assert!(keystore.sr25519_vrf_sign(KEY_TYPE, &local, &data).unwrap().is_some());
+		assert_eq!(keystore.sr25519_vrf_sign(KEY_TYPE, &remote, &data).unwrap(), None);
+	}
+
+	#[test]
+	fn local_keys_keep_working_while_signer_is_down() {
+		let (signer, keystore) = keystore();
+
+		let local = keystore.local().sr25519_generate_new(KEY_TYPE, None).unwrap();
+		signer.online.store(false, Ordering::Relaxed);
+
+		assert_eq!(keystore.sr25519_public_keys(KEY_TYPE), vec![local]);
+		assert_eq!(keystore.keys(KEY_TYPE).unwrap(), vec![local.to_raw_vec()]);
+		assert!(keystore.sr25519_sign(KEY_TYPE, &local, b"message").unwrap().is_some());
+	}
+}
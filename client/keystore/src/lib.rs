@@ -26,6 +26,12 @@ use std::io;
 /// Local keystore implementation
 mod local;
 pub use local::LocalKeystore;
+/// Keystore forwarding to an external signer
+mod remote;
+pub use remote::{HttpTransport, RemoteKeystore, SignerTransport};
+/// Keystore combining an external signer with a local keystore
+mod composite;
+pub use composite::CompositeKeystore;
 pub use sp_keystore::Keystore;
 
 /// Keystore error.
@@ -55,6 +61,9 @@ pub enum Error {
 	/// Keystore unavailable
 	#[error("Keystore unavailable")]
 	Unavailable,
+	/// Error reported by the remote signer
+	#[error("Remote signer error: {0}")]
+	Remote(String),
 }
 
 /// Keystore Result
@@ -69,6 +78,7 @@ impl From<Error> for TraitError {
 			Error::Unavailable => TraitError::Unavailable,
 			Error::Io(e) => TraitError::Other(e.to_string()),
 			Error::Json(e) => TraitError::Other(e.to_string()),
+			Error::Remote(e) => TraitError::Other(e),
 		}
 	}
 }
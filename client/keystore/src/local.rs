@@ -169,6 +169,10 @@ impl Keystore for LocalKeystore {
 			.all(|(p, t)| self.0.read().key_phrase_by_type(p, *t).ok().flatten().is_some())
 	}
 
+	fn remove(&self, key_type: KeyTypeId, public: &[u8]) -> std::result::Result<bool, TraitError> {
+		self.0.write().remove(key_type, public).map_err(|e| e.into())
+	}
+
 	fn sr25519_public_keys(&self, key_type: KeyTypeId) -> Vec<sr25519::Public> {
 		self.public_keys::<sr25519::Pair>(key_type)
 	}
@@ -491,6 +495,23 @@ impl KeystoreInner {
 		Ok(())
 	}
 
+	/// Remove a key from the memory cache and the file system store.
+	///
+	/// Returns `true` if a key was removed.
+	fn remove(&mut self, key_type: KeyTypeId, public: &[u8]) -> Result<bool> {
+		let mut removed = self.additional.remove(&(key_type, public.to_vec())).is_some();
+
+		if let Some(path) = self.key_file_path(public, key_type) {
+			match fs::remove_file(path) {
+				Ok(()) => removed = true,
+				Err(e) if e.kind() == std::io::ErrorKind::NotFound => {},
+				Err(e) => return Err(e.into()),
+			}
+		}
+
+		Ok(removed)
+	}
+
 	/// Generate a new key.
 	///
 	/// Places it into the file system store, if a path is configured. Otherwise insert
@@ -693,6 +714,26 @@ mod tests {
 		assert!(store.has_keys(&[(key.public().to_raw_vec(), ed25519::AppPublic::ID)]));
 	}
 
+	#[test]
+	fn remove_works() {
+		let temp_dir = TempDir::new().unwrap();
+		let store = LocalKeystore::open(temp_dir.path(), None).unwrap();
+
+		let key: ed25519::AppPair = store.0.write().generate().unwrap();
+		let key = (key.public().to_raw_vec(), ed25519::AppPublic::ID);
+		let ephemeral =
+			store.ed25519_generate_new(ed25519::AppPublic::ID, Some("//Alice")).unwrap();
+		let ephemeral = (ephemeral.to_raw_vec(), ed25519::AppPublic::ID);
+		assert!(store.has_keys(&[key.clone(), ephemeral.clone()]));
+
+		assert!(store.remove(key.1, &key.0).unwrap());
+		assert!(!store.has_keys(&[key.clone()]));
+		assert!(!store.remove(key.1, &key.0).unwrap());
+
+		assert!(store.remove(ephemeral.1, &ephemeral.0).unwrap());
+		assert!(!store.has_keys(&[ephemeral]));
+	}
+
 	#[test]
 	fn test_insert_ephemeral_from_seed() {
 		let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,822 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Keystore that forwards all key operations to an external signer.
+//!
+//! The signer is reached through a [`SignerTransport`]. [`RemoteKeystore::open`] uses plain
+//! JSON-RPC 2.0 over HTTP to a signer on the local host, and any other protocol can be plugged in
+//! with [`RemoteKeystore::with_transport`]. A signer on another host must be reached through a
+//! local proxy which terminates TLS, so that requests never leave the host unencrypted. Key types,
+//! public keys, messages and signatures are `0x`-prefixed hex strings, and signature schemes are
+//! named `sr25519`, `ed25519` and `ecdsa`. The signer must implement the following methods:
+//!
+//! - `signer_health()`: any non-error result means the signer is available.
+//! - `signer_publicKeys(keyType, scheme)`: public keys of the given type; all schemes if `scheme`
+//!   is `null`.
+//! - `signer_sign(keyType, scheme, public, message)`: the signature, or `null` if the key is
+//!   unknown.
+//! - `signer_signPrehashed(keyType, public, hash)`: ECDSA signature of a 32 byte hash, or `null`.
+//! - `signer_generateKey(keyType, scheme, seed)`: generates a key and returns its public key.
+//! - `signer_insertKey(keyType, suri, public)`: imports a secret key.
+//! - `signer_removeKey(keyType, public)`: removes a key, returning whether it existed.
+//!
+//! VRF outputs are not forwarded, so keys that need them (e.g. BABE) can't be held remotely. Use
+//! a [`CompositeKeystore`](crate::CompositeKeystore) to keep such keys in a local keystore.
+
+use parking_lot::{Mutex, RwLock};
+use serde_json::{json, Value};
+use sp_core::{
+	crypto::{ByteArray, KeyTypeId, Pair as CorePair},
+	ecdsa, ed25519, sr25519,
+};
+use sp_keystore::{Error as TraitError, Keystore};
+use std::{
+	collections::HashMap,
+	io::{Read, Write},
+	net::{IpAddr, TcpStream, ToSocketAddrs},
+	sync::atomic::{AtomicBool, AtomicU64, Ordering},
+	time::{Duration, Instant},
+};
+
+sp_keystore::bandersnatch_experimental_enabled! {
+use sp_core::bandersnatch;
+}
+
+sp_keystore::bls_experimental_enabled! {
+use sp_core::{bls377, bls381, ecdsa_bls377};
+}
+
+use crate::{Error, Result};
+
+const LOG_TARGET: &str = "keystore";
+
+/// How long fetched public keys are served from the cache.
+const PUBLIC_KEY_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Minimal time between two health checks of an unavailable signer.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Timeout of a single request to the signer, including connecting to it.
+///
+/// Key operations are synchronous and may run on async tasks, so a hanging signer must not be
+/// able to block the caller for longer than this.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximal size of a response of the signer, including its headers.
+const MAX_RESPONSE_SIZE: usize = 1024 * 1024;
+
+/// Transport used to reach the external signer.
+pub trait SignerTransport: Send + Sync {
+	/// Call `method` with `params` and return the result of the call.
+	///
+	/// Failing to reach the signer must be reported as [`Error::Unavailable`].
+	fn call(&self, method: &str, params: Value) -> Result<Value>;
+}
+
+/// JSON-RPC 2.0 over HTTP.
+///
+/// Only plain `http://` to the local host is supported, TLS must be terminated by a local proxy.
+pub struct HttpTransport {
+	authority: String,
+	path: String,
+	next_id: AtomicU64,
+}
+
+impl HttpTransport {
+	/// Create a transport for the given `http://host:port/path` URI.
+	///
+	/// The host must be `localhost` or a loopback address.
+	pub fn new(uri: &str) -> Result<Self> {
+		let rest = uri
+			.strip_prefix("http://")
+			.ok_or_else(|| Error::Remote(format!("Unsupported keystore URI `{}`", uri)))?;
+		let (authority, path) = match rest.find('/') {
+			Some(i) => (&rest[..i], &rest[i..]),
+			None => (rest, "/"),
+		};
+		let (host, has_port) = match authority.strip_prefix('[') {
+			Some(rest) => match rest.split_once(']') {
+				Some((host, port)) => (host, !port.is_empty()),
+				None => return Err(Error::Remote(format!("Invalid keystore URI `{}`", uri))),
+			},
+			None => match authority.split_once(':') {
+				Some((host, _)) => (host, true),
+				None => (authority, false),
+			},
+		};
+		if host.is_empty() {
+			return Err(Error::Remote(format!("Missing host in keystore URI `{}`", uri)))
+		}
+		if !is_loopback(host) {
+			return Err(Error::Remote(format!(
+				"Keystore URI `{}` is not on the local host, reach remote signers through a local TLS proxy",
+				uri
+			)))
+		}
+		let authority = if has_port { authority.to_string() } else { format!("{}:80", authority) };
+
+		Ok(Self { authority, path: path.to_string(), next_id: AtomicU64::new(0) })
+	}
+
+	fn connect(&self, deadline: Instant) -> std::io::Result<TcpStream> {
+		let mut last_error = None;
+		for addr in self.authority.to_socket_addrs()? {
+			let timeout = remaining(deadline)?;
+			match TcpStream::connect_timeout(&addr, timeout) {
+				Ok(stream) => return Ok(stream),
+				Err(e) => last_error = Some(e),
+			}
+		}
+		Err(last_error.unwrap_or_else(|| {
+			std::io::Error::new(std::io::ErrorKind::NotFound, "Signer address did not resolve")
+		}))
+	}
+
+	fn post(&self, body: &str) -> std::io::Result<Vec<u8>> {
+		let deadline = Instant::now() + REQUEST_TIMEOUT;
+		let mut stream = self.connect(deadline)?;
+		stream.set_write_timeout(Some(remaining(deadline)?))?;
+
+		// HTTP/1.0 keeps the signer from answering with a chunked body.
+		write!(
+			stream,
+			"POST {} HTTP/1.0\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+			self.path,
+			self.authority,
+			body.len(),
+			body,
+		)?;
+
+		let mut response = Vec::new();
+		let mut buf = [0u8; 4096];
+		loop {
+			// Bound the whole response, not just every single read.
+			stream.set_read_timeout(Some(remaining(deadline)?))?;
+			match stream.read(&mut buf) {
+				Ok(0) => return Ok(response),
+				Ok(n) if response.len() + n > MAX_RESPONSE_SIZE =>
+					return Err(std::io::Error::new(
+						std::io::ErrorKind::InvalidData,
+						format!("Signer response exceeds {} bytes", MAX_RESPONSE_SIZE),
+					)),
+				Ok(n) => response.extend_from_slice(&buf[..n]),
+				Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {},
+				Err(e) => return Err(e),
+			}
+		}
+	}
+}
+
+/// Whether `host` is `localhost` or a loopback address.
+fn is_loopback(host: &str) -> bool {
+	host.eq_ignore_ascii_case("localhost") ||
+		host.parse::<IpAddr>().map_or(false, |ip| ip.is_loopback())
+}
+
+/// Time left until `deadline`, or a timeout error if it has passed.
+fn remaining(deadline: Instant) -> std::io::Result<Duration> {
+	deadline
+		.checked_duration_since(Instant::now())
+		.filter(|d| !d.is_zero())
+		.ok_or_else(|| {
+			std::io::Error::new(std::io::ErrorKind::TimedOut, "Signer request timed out")
+		})
+}
+
+impl SignerTransport for HttpTransport {
+	fn call(&self, method: &str, params: Value) -> Result<Value> {
+		let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+		let request = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+
+		let response = self.post(&request.to_string()).map_err(|e| {
+			if e.kind() == std::io::ErrorKind::InvalidData {
+				return Error::Remote(e.to_string())
+			}
+			log::debug!(target: LOG_TARGET, "Remote signer request failed: {}", e);
+			Error::Unavailable
+		})?;
+
+		let split = response
+			.windows(4)
+			.position(|w| w == b"\r\n\r\n")
+			.ok_or_else(|| Error::Remote("Malformed HTTP response".into()))?;
+		let status = String::from_utf8_lossy(&response[..split]);
+		if !status
+			.lines()
+			.next()
+			.map_or(false, |line| line.split(' ').nth(1) == Some("200"))
+		{
+			return Err(Error::Remote(format!("Unexpected HTTP status: {}", status)))
+		}
+
+		let mut response: Value = serde_json::from_slice(&response[split + 4..])?;
+		if let Some(error) = response.get("error") {
+			return Err(Error::Remote(error.to_string()))
+		}
+		Ok(response.get_mut("result").map(Value::take).unwrap_or(Value::Null))
+	}
+}
+
+type CacheKey = (KeyTypeId, Option<&'static str>);
+
+/// A keystore that keeps no secrets and forwards signing to an external signer.
+///
+/// Public keys are cached so that authorship checks keep working while the signer is briefly
+/// unreachable.
+pub struct RemoteKeystore {
+	transport: Box<dyn SignerTransport>,
+	public_keys: RwLock<HashMap<CacheKey, (Instant, Vec<Vec<u8>>)>>,
+	available: AtomicBool,
+	last_health_check: Mutex<Instant>,
+}
+
+impl RemoteKeystore {
+	/// Connect to the signer at `uri` using [`HttpTransport`].
+	///
+	/// Fails if the signer does not respond to a health check.
+	pub fn open(uri: &str) -> Result<Self> {
+		let keystore = Self::with_transport(HttpTransport::new(uri)?);
+		if !keystore.check_health() {
+			return Err(Error::Unavailable)
+		}
+		Ok(keystore)
+	}
+
+	/// Create a keystore using a custom transport.
+	pub fn with_transport(transport: impl SignerTransport + 'static) -> Self {
+		Self {
+			transport: Box::new(transport),
+			public_keys: Default::default(),
+			available: AtomicBool::new(true),
+			last_health_check: Mutex::new(Instant::now()),
+		}
+	}
+
+	/// Ask the signer whether it is available.
+	pub fn check_health(&self) -> bool {
+		*self.last_health_check.lock() = Instant::now();
+		self.call("signer_health", json!([])).is_ok()
+	}
+
+	/// Whether the last request reached the signer.
+	pub fn is_available(&self) -> bool {
+		self.available.load(Ordering::Relaxed)
+	}
+
+	fn call(&self, method: &str, params: Value) -> Result<Value> {
+		let result = self.transport.call(method, params);
+		let available = !matches!(result, Err(Error::Unavailable));
+		if self.available.swap(available, Ordering::Relaxed) != available {
+			if available {
+				log::info!(target: LOG_TARGET, "Remote signer is available again");
+			} else {
+				log::warn!(target: LOG_TARGET, "Remote signer is unavailable");
+			}
+		}
+		result
+	}
+
+	/// Fail fast while the signer is known to be down, re-checking its health periodically.
+	fn ensure_available(&self) -> Result<()> {
+		if self.is_available() {
+			return Ok(())
+		}
+		if self.last_health_check.lock().elapsed() >= HEALTH_CHECK_INTERVAL && self.check_health() {
+			return Ok(())
+		}
+		Err(Error::Unavailable)
+	}
+
+	fn raw_public_keys(
+		&self,
+		key_type: KeyTypeId,
+		scheme: Option<&'static str>,
+	) -> Result<Vec<Vec<u8>>> {
+		let cache_key = (key_type, scheme);
+		let cached = self.public_keys.read().get(&cache_key).cloned();
+		if let Some((fetched, keys)) = &cached {
+			if fetched.elapsed() < PUBLIC_KEY_CACHE_TTL {
+				return Ok(keys.clone())
+			}
+		}
+
+		let fetch = || -> Result<Vec<Vec<u8>>> {
+			self.ensure_available()?;
+			let keys = self.call("signer_publicKeys", json!([hex(&key_type.0), scheme]))?;
+			serde_json::from_value::<Vec<String>>(keys)?.iter().map(|k| unhex(k)).collect()
+		};
+
+		match fetch() {
+			Ok(keys) => {
+				self.public_keys.write().insert(cache_key, (Instant::now(), keys.clone()));
+				Ok(keys)
+			},
+			Err(e) => match cached {
+				Some((_, keys)) => {
+					log::debug!(target: LOG_TARGET, "Using cached public keys: {}", e);
+					Ok(keys)
+				},
+				None => Err(e),
+			},
+		}
+	}
+
+	fn invalidate_cache(&self, key_type: KeyTypeId) {
+		self.public_keys.write().retain(|(t, _), _| *t != key_type);
+	}
+
+	fn public_keys<T: CorePair>(
+		&self,
+		key_type: KeyTypeId,
+		scheme: &'static str,
+	) -> Vec<T::Public> {
+		self.raw_public_keys(key_type, Some(scheme))
+			.map(|keys| keys.iter().filter_map(|k| T::Public::from_slice(k).ok()).collect())
+			.unwrap_or_default()
+	}
+
+	fn generate_new<T: CorePair>(
+		&self,
+		key_type: KeyTypeId,
+		scheme: &'static str,
+		seed: Option<&str>,
+	) -> std::result::Result<T::Public, TraitError> {
+		self.ensure_available()?;
+		let public = self.call("signer_generateKey", json!([hex(&key_type.0), scheme, seed]))?;
+		self.invalidate_cache(key_type);
+
+		let public = unhex(&serde_json::from_value::<String>(public).map_err(Error::from)?)?;
+		T::Public::from_slice(&public)
+			.map_err(|_| TraitError::ValidationError("Invalid public key from signer".into()))
+	}
+
+	fn raw_sign(&self, method: &str, params: Value) -> Result<Option<Vec<u8>>> {
+		self.ensure_available()?;
+		match serde_json::from_value::<Option<String>>(self.call(method, params)?)? {
+			Some(signature) => Ok(Some(unhex(&signature)?)),
+			None => Ok(None),
+		}
+	}
+
+	fn sign<T: CorePair>(
+		&self,
+		key_type: KeyTypeId,
+		scheme: &'static str,
+		public: &T::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<T::Signature>, TraitError>
+	where
+		T::Signature: for<'a> TryFrom<&'a [u8]>,
+	{
+		let params = json!([hex(&key_type.0), scheme, hex(public.as_slice()), hex(msg)]);
+		self.raw_sign("signer_sign", params)?
+			.map(|signature| {
+				T::Signature::try_from(&signature[..]).map_err(|_| {
+					TraitError::ValidationError("Invalid signature from signer".into())
+				})
+			})
+			.transpose()
+	}
+}
+
+fn hex(bytes: &[u8]) -> String {
+	array_bytes::bytes2hex("0x", bytes)
+}
+
+fn unhex(hex: &str) -> Result<Vec<u8>> {
+	array_bytes::hex2bytes(hex).map_err(|_| Error::Remote(format!("Invalid hex `{}`", hex)))
+}
+
+fn vrf_unsupported<T>() -> std::result::Result<T, TraitError> {
+	Err(TraitError::Other("VRF signing is not supported by the remote signer".into()))
+}
+
+impl Keystore for RemoteKeystore {
+	fn insert(
+		&self,
+		key_type: KeyTypeId,
+		suri: &str,
+		public: &[u8],
+	) -> std::result::Result<(), ()> {
+		self.ensure_available().map_err(|_| ())?;
+		self.call("signer_insertKey", json!([hex(&key_type.0), suri, hex(public)]))
+			.map_err(|_| ())?;
+		self.invalidate_cache(key_type);
+		Ok(())
+	}
+
+	fn keys(&self, key_type: KeyTypeId) -> std::result::Result<Vec<Vec<u8>>, TraitError> {
+		self.raw_public_keys(key_type, None).map_err(Into::into)
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		public_keys
+			.iter()
+			.all(|(p, t)| self.raw_public_keys(*t, None).map_or(false, |keys| keys.contains(p)))
+	}
+
+	fn remove(&self, key_type: KeyTypeId, public: &[u8]) -> std::result::Result<bool, TraitError> {
+		self.ensure_available()?;
+		let removed = self.call("signer_removeKey", json!([hex(&key_type.0), hex(public)]))?;
+		self.invalidate_cache(key_type);
+		serde_json::from_value(removed).map_err(|e| Error::from(e).into())
+	}
+
+	fn sr25519_public_keys(&self, key_type: KeyTypeId) -> Vec<sr25519::Public> {
+		self.public_keys::<sr25519::Pair>(key_type, "sr25519")
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<sr25519::Public, TraitError> {
+		self.generate_new::<sr25519::Pair>(key_type, "sr25519", seed)
+	}
+
+	fn sr25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<sr25519::Signature>, TraitError> {
+		self.sign::<sr25519::Pair>(key_type, "sr25519", public, msg)
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &sr25519::Public,
+		_data: &sr25519::vrf::VrfSignData,
+	) -> std::result::Result<Option<sr25519::vrf::VrfSignature>, TraitError> {
+		vrf_unsupported()
+	}
+
+	fn sr25519_vrf_pre_output(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &sr25519::Public,
+		_input: &sr25519::vrf::VrfInput,
+	) -> std::result::Result<Option<sr25519::vrf::VrfPreOutput>, TraitError> {
+		vrf_unsupported()
+	}
+
+	fn ed25519_public_keys(&self, key_type: KeyTypeId) -> Vec<ed25519::Public> {
+		self.public_keys::<ed25519::Pair>(key_type, "ed25519")
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ed25519::Public, TraitError> {
+		self.generate_new::<ed25519::Pair>(key_type, "ed25519", seed)
+	}
+
+	fn ed25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ed25519::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ed25519::Signature>, TraitError> {
+		self.sign::<ed25519::Pair>(key_type, "ed25519", public, msg)
+	}
+
+	fn ecdsa_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.public_keys::<ecdsa::Pair>(key_type, "ecdsa")
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ecdsa::Public, TraitError> {
+		self.generate_new::<ecdsa::Pair>(key_type, "ecdsa", seed)
+	}
+
+	fn ecdsa_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.sign::<ecdsa::Pair>(key_type, "ecdsa", public, msg)
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		let params = json!([hex(&key_type.0), hex(public.as_slice()), hex(msg)]);
+		self.raw_sign("signer_signPrehashed", params)?
+			.map(|signature| {
+				ecdsa::Signature::try_from(&signature[..]).map_err(|_| {
+					TraitError::ValidationError("Invalid signature from signer".into())
+				})
+			})
+			.transpose()
+	}
+
+	sp_keystore::bandersnatch_experimental_enabled! {
+		fn bandersnatch_public_keys(&self, key_type: KeyTypeId) -> Vec<bandersnatch::Public> {
+			self.public_keys::<bandersnatch::Pair>(key_type, "bandersnatch")
+		}
+
+		fn bandersnatch_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<bandersnatch::Public, TraitError> {
+			self.generate_new::<bandersnatch::Pair>(key_type, "bandersnatch", seed)
+		}
+
+		fn bandersnatch_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<bandersnatch::Signature>, TraitError> {
+			self.sign::<bandersnatch::Pair>(key_type, "bandersnatch", public, msg)
+		}
+
+		fn bandersnatch_vrf_sign(
+			&self,
+			_key_type: KeyTypeId,
+			_public: &bandersnatch::Public,
+			_data: &bandersnatch::vrf::VrfSignData,
+		) -> std::result::Result<Option<bandersnatch::vrf::VrfSignature>, TraitError> {
+			vrf_unsupported()
+		}
+
+		fn bandersnatch_vrf_pre_output(
+			&self,
+			_key_type: KeyTypeId,
+			_public: &bandersnatch::Public,
+			_input: &bandersnatch::vrf::VrfInput,
+		) -> std::result::Result<Option<bandersnatch::vrf::VrfPreOutput>, TraitError> {
+			vrf_unsupported()
+		}
+
+		fn bandersnatch_ring_vrf_sign(
+			&self,
+			_key_type: KeyTypeId,
+			_public: &bandersnatch::Public,
+			_data: &bandersnatch::vrf::VrfSignData,
+			_prover: &bandersnatch::ring_vrf::RingProver,
+		) -> std::result::Result<Option<bandersnatch::ring_vrf::RingVrfSignature>, TraitError> {
+			vrf_unsupported()
+		}
+	}
+
+	sp_keystore::bls_experimental_enabled! {
+		fn bls381_public_keys(&self, key_type: KeyTypeId) -> Vec<bls381::Public> {
+			self.public_keys::<bls381::Pair>(key_type, "bls381")
+		}
+
+		fn bls377_public_keys(&self, key_type: KeyTypeId) -> Vec<bls377::Public> {
+			self.public_keys::<bls377::Pair>(key_type, "bls377")
+		}
+
+		fn ecdsa_bls377_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa_bls377::Public> {
+			self.public_keys::<ecdsa_bls377::Pair>(key_type, "ecdsa_bls377")
+		}
+
+		fn bls381_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<bls381::Public, TraitError> {
+			self.generate_new::<bls381::Pair>(key_type, "bls381", seed)
+		}
+
+		fn bls377_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<bls377::Public, TraitError> {
+			self.generate_new::<bls377::Pair>(key_type, "bls377", seed)
+		}
+
+		fn ecdsa_bls377_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<ecdsa_bls377::Public, TraitError> {
+			self.generate_new::<ecdsa_bls377::Pair>(key_type, "ecdsa_bls377", seed)
+		}
+
+		fn bls381_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bls381::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<bls381::Signature>, TraitError> {
+			self.sign::<bls381::Pair>(key_type, "bls381", public, msg)
+		}
+
+		fn bls377_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bls377::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<bls377::Signature>, TraitError> {
+			self.sign::<bls377::Pair>(key_type, "bls377", public, msg)
+		}
+
+		fn ecdsa_bls377_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &ecdsa_bls377::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<ecdsa_bls377::Signature>, TraitError> {
+			self.sign::<ecdsa_bls377::Pair>(key_type, "ecdsa_bls377", public, msg)
+		}
+
+		fn ecdsa_bls377_sign_with_keccak256(
+			&self,
+			_key_type: KeyTypeId,
+			_public: &ecdsa_bls377::Public,
+			_msg: &[u8],
+		) -> std::result::Result<Option<ecdsa_bls377::Signature>, TraitError> {
+			Err(TraitError::Other("Keccak256 signing is not supported by the remote signer".into()))
+		}
+	}
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use super::*;
+	use crate::LocalKeystore;
+	use sp_core::{sr25519, Pair};
+	use std::sync::Arc;
+
+	const KEY_TYPE: KeyTypeId = KeyTypeId(*b"test");
+
+	/// Signer backed by a local keystore that can be taken offline.
+	#[derive(Clone)]
+	pub(crate) struct MockSigner {
+		pub(crate) keystore: Arc<LocalKeystore>,
+		pub(crate) online: Arc<AtomicBool>,
+	}
+
+	impl MockSigner {
+		pub(crate) fn new() -> Self {
+			Self {
+				keystore: Arc::new(LocalKeystore::in_memory()),
+				online: Arc::new(AtomicBool::new(true)),
+			}
+		}
+	}
+
+	impl SignerTransport for MockSigner {
+		fn call(&self, method: &str, params: Value) -> Result<Value> {
+			if !self.online.load(Ordering::Relaxed) {
+				return Err(Error::Unavailable)
+			}
+			let str_param = |i: usize| params[i].as_str().map(ToString::to_string);
+			let key_type = || KeyTypeId(unhex(&str_param(0).unwrap()).unwrap().try_into().unwrap());
+
+			Ok(match method {
+				"signer_health" => json!(true),
+				"signer_publicKeys" => {
+					let keys = self.keystore.keys(key_type()).unwrap();
+					json!(keys.iter().map(|k| hex(k)).collect::<Vec<_>>())
+				},
+				"signer_generateKey" => {
+					let public = self.keystore.sr25519_generate_new(key_type(), None).unwrap();
+					json!(hex(public.as_slice()))
+				},
+				"signer_sign" => {
+					let public =
+						sr25519::Public::from_slice(&unhex(&str_param(2).unwrap())?).unwrap();
+					let msg = unhex(&str_param(3).unwrap())?;
+					let signature = self.keystore.sr25519_sign(key_type(), &public, &msg).unwrap();
+					json!(signature.map(|s| hex(s.as_ref())))
+				},
+				_ => return Err(Error::Remote(format!("Unknown method {}", method))),
+			})
+		}
+	}
+
+	#[test]
+	fn signs_with_remote_keys() {
+		let signer = MockSigner::new();
+		let keystore = RemoteKeystore::with_transport(signer.clone());
+
+		let public = keystore.sr25519_generate_new(KEY_TYPE, None).unwrap();
+		assert_eq!(keystore.sr25519_public_keys(KEY_TYPE), vec![public]);
+		assert!(keystore.has_keys(&[(public.to_raw_vec(), KEY_TYPE)]));
+
+		let signature = keystore.sr25519_sign(KEY_TYPE, &public, b"message").unwrap().unwrap();
+		assert!(sr25519::Pair::verify(&signature, b"message", &public));
+
+		let unknown = sr25519::Pair::generate().0.public();
+		assert_eq!(keystore.sr25519_sign(KEY_TYPE, &unknown, b"message").unwrap(), None);
+	}
+
+	#[test]
+	fn serves_cached_public_keys_while_signer_is_down() {
+		let signer = MockSigner::new();
+		let keystore = RemoteKeystore::with_transport(signer.clone());
+
+		let public = keystore.sr25519_generate_new(KEY_TYPE, None).unwrap();
+		assert_eq!(keystore.sr25519_public_keys(KEY_TYPE), vec![public]);
+
+		signer.online.store(false, Ordering::Relaxed);
+		assert!(!keystore.check_health());
+		assert!(!keystore.is_available());
+
+		assert_eq!(keystore.sr25519_public_keys(KEY_TYPE), vec![public]);
+		assert!(matches!(
+			keystore.sr25519_sign(KEY_TYPE, &public, b"message"),
+			Err(TraitError::Unavailable)
+		));
+
+		signer.online.store(true, Ordering::Relaxed);
+		assert!(keystore.check_health());
+		assert!(keystore.sr25519_sign(KEY_TYPE, &public, b"message").unwrap().is_some());
+	}
+
+	#[test]
+	fn generating_a_key_invalidates_the_cache() {
+		let signer = MockSigner::new();
+		let keystore = RemoteKeystore::with_transport(signer.clone());
+
+		assert!(keystore.sr25519_public_keys(KEY_TYPE).is_empty());
+		let public = keystore.sr25519_generate_new(KEY_TYPE, None).unwrap();
+		assert_eq!(keystore.sr25519_public_keys(KEY_TYPE), vec![public]);
+	}
+
+	#[test]
+	fn unresponsive_signer_times_out() {
+		let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+		let uri = format!("http://{}", listener.local_addr().unwrap());
+		// Accept the connection but never answer.
+		let _server = std::thread::spawn(move || listener.accept());
+
+		let transport = HttpTransport::new(&uri).unwrap();
+		let started = Instant::now();
+		assert!(matches!(transport.call("signer_health", json!([])), Err(Error::Unavailable)));
+		assert!(started.elapsed() < REQUEST_TIMEOUT + Duration::from_secs(1));
+	}
+
+	#[test]
+	fn parses_http_uris() {
+		let transport = HttpTransport::new("http://localhost:9955/signer").unwrap();
+		assert_eq!(transport.authority, "localhost:9955");
+		assert_eq!(transport.path, "/signer");
+
+		let transport = HttpTransport::new("http://127.0.0.1").unwrap();
+		assert_eq!(transport.authority, "127.0.0.1:80");
+		assert_eq!(transport.path, "/");
+
+		let transport = HttpTransport::new("http://[::1]/signer").unwrap();
+		assert_eq!(transport.authority, "[::1]:80");
+		assert_eq!(HttpTransport::new("http://[::1]:9955").unwrap().authority, "[::1]:9955");
+
+		assert!(HttpTransport::new("https://localhost").is_err());
+		assert!(HttpTransport::new("http:///path").is_err());
+		assert!(HttpTransport::new("http://[::1/path").is_err());
+	}
+
+	#[test]
+	fn rejects_signers_on_other_hosts() {
+		assert!(HttpTransport::new("http://signer").is_err());
+		assert!(HttpTransport::new("http://10.0.0.1:9955/signer").is_err());
+		assert!(HttpTransport::new("http://[2001:db8::1]:9955").is_err());
+		assert!(HttpTransport::new("http://localhost.example.com").is_err());
+	}
+
+	#[test]
+	fn oversized_responses_are_rejected() {
+		let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+		let uri = format!("http://{}", listener.local_addr().unwrap());
+		let _server = std::thread::spawn(move || {
+			let (mut stream, _) = listener.accept().unwrap();
+			let mut request = [0u8; 4096];
+			let _ = stream.read(&mut request);
+			let _ = stream.write_all(b"HTTP/1.0 200 OK\r\n\r\n");
+			let _ = stream.write_all(&vec![b' '; MAX_RESPONSE_SIZE]);
+		});
+
+		let transport = HttpTransport::new(&uri).unwrap();
+		assert!(matches!(transport.call("signer_health", json!([])), Err(Error::Remote(_))));
+	}
+}
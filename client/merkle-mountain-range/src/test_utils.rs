@@ -285,6 +285,13 @@ impl BlockchainEvents<Block> for MockClient {
 	) -> sc_client_api::blockchain::Result<StorageEventStream<Hash>> {
 		unimplemented!()
 	}
+
+	fn storage_prefix_changes_notification_stream(
+		&self,
+		_filter_prefixes: &[StorageKey],
+	) -> sc_client_api::blockchain::Result<StorageEventStream<Hash>> {
+		unimplemented!()
+	}
 }
 
 impl ProvideRuntimeApi<Block> for MockClient {
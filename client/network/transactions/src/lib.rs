@@ -33,7 +33,9 @@ use futures::{prelude::*, stream::FuturesUnordered};
 use libp2p::{multiaddr, PeerId};
 use log::{debug, trace, warn};
 
-use prometheus_endpoint::{register, Counter, PrometheusError, Registry, U64};
+use prometheus_endpoint::{
+	register, Counter, Histogram, HistogramOpts, PrometheusError, Registry, U64,
+};
 use sc_network::{
 	config::{NonDefaultSetConfig, NonReservedPeerMode, ProtocolId, SetConfig},
 	error,
@@ -78,6 +80,7 @@ mod rep {
 
 struct Metrics {
 	propagated_transactions: Counter<U64>,
+	propagation_fanout: Histogram,
 }
 
 impl Metrics {
@@ -90,6 +93,16 @@ impl Metrics {
 				)?,
 				r,
 			)?,
+			propagation_fanout: register(
+				Histogram::with_opts(
+					HistogramOpts::new(
+						"substrate_sync_transaction_propagation_fanout",
+						"Number of peers each propagated transaction was sent to",
+					)
+					.buckets(vec![1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0]),
+				)?,
+				r,
+			)?,
 		})
 	}
 }
@@ -493,7 +506,10 @@ where
 		}
 
 		if let Some(ref metrics) = self.metrics {
-			metrics.propagated_transactions.inc_by(propagated_transactions as _)
+			metrics.propagated_transactions.inc_by(propagated_transactions as _);
+			for peers in propagated_to.values() {
+				metrics.propagation_fanout.observe(peers.len() as f64);
+			}
 		}
 
 		propagated_to
@@ -19,8 +19,8 @@
 //! Prometheus basic proposer metrics.
 
 use prometheus_endpoint::{
-	prometheus::CounterVec, register, Gauge, Histogram, HistogramOpts, Opts, PrometheusError,
-	Registry, U64,
+	prometheus::CounterVec, register, Counter, Gauge, Histogram, HistogramOpts, Opts,
+	PrometheusError, Registry, U64,
 };
 
 /// Optional shareable link to basic authorship metrics.
@@ -59,6 +59,8 @@ pub struct Metrics {
 	pub end_proposing_reason: CounterVec,
 	pub create_inherents_time: Histogram,
 	pub create_block_proposal_time: Histogram,
+	pub deadline_overshoot: Histogram,
+	pub soft_deadline_skipped_transactions: Counter<U64>,
 }
 
 impl Metrics {
@@ -92,6 +94,20 @@ impl Metrics {
 				))?,
 				registry,
 			)?,
+			deadline_overshoot: register(
+				Histogram::with_opts(HistogramOpts::new(
+					"substrate_proposer_deadline_overshoot",
+					"Histogram of time by which pushing transactions overshot the proposing deadline",
+				))?,
+				registry,
+			)?,
+			soft_deadline_skipped_transactions: register(
+				Counter::new(
+					"substrate_proposer_soft_deadline_skipped_transactions",
+					"Number of transactions not considered because the soft deadline was reached",
+				)?,
+				registry,
+			)?,
 			end_proposing_reason: register(
 				CounterVec::new(
 					Opts::new(
@@ -48,6 +48,12 @@ pub enum Error {
 	/// Invalid session keys encoding.
 	#[error("Session keys are not encoded correctly")]
 	InvalidSessionKeys,
+	/// The node can't create transactions registering session keys.
+	#[error("Submitting session keys is not supported by this node")]
+	SessionKeysSubmissionUnsupported,
+	/// Creating the transaction registering session keys failed.
+	#[error("Failed to create the session keys transaction: {}", .0)]
+	SessionKeysTransaction(String),
 	/// Call to an unsafe RPC was denied.
 	#[error(transparent)]
 	UnsafeRpcCalled(#[from] crate::policy::UnsafeRpcError),
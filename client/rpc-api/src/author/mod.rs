@@ -24,8 +24,19 @@ pub mod hash;
 use error::Error;
 use jsonrpsee::proc_macros::rpc;
 use sc_transaction_pool_api::TransactionStatus;
+use serde::{Deserialize, Serialize};
 use sp_core::Bytes;
 
+/// Session keys generated and registered by `author_rotateAndSubmitKeys`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RotatedKeys<Hash> {
+	/// The SCALE encoded session keys.
+	pub keys: Bytes,
+	/// Hash of the transaction registering the keys.
+	pub transaction_hash: Hash,
+}
+
 /// Substrate authoring RPC API
 #[rpc(client, server)]
 pub trait AuthorApi<Hash, BlockHash> {
@@ -41,6 +52,15 @@ pub trait AuthorApi<Hash, BlockHash> {
 	#[method(name = "author_rotateKeys")]
 	fn rotate_keys(&self) -> Result<Bytes, Error>;
 
+	/// Generate new session keys and submit a transaction registering them on-chain.
+	///
+	/// The transaction is signed with a key of the given `key_type` from the keystore.
+	///
+	/// If the transaction can't be created or submitted the new keys are removed from the
+	/// keystore again, so either both steps happen or neither.
+	#[method(name = "author_rotateAndSubmitKeys")]
+	async fn rotate_and_submit_keys(&self, key_type: String) -> Result<RotatedKeys<Hash>, Error>;
+
 	/// Checks if the keystore has private keys for the given session public keys.
 	///
 	/// `session_keys` is the SCALE encoded session keys object from the runtime.
@@ -55,6 +75,16 @@ pub trait AuthorApi<Hash, BlockHash> {
 	#[method(name = "author_hasKey")]
 	fn has_key(&self, public_key: Bytes, key_type: String) -> Result<bool, Error>;
 
+	/// Returns the public keys of the given key type held by the keystore.
+	#[method(name = "author_listKeys")]
+	fn list_keys(&self, key_type: String) -> Result<Vec<Bytes>, Error>;
+
+	/// Removes the private key for the given public key and key type from the keystore.
+	///
+	/// Returns `true` if a key was removed.
+	#[method(name = "author_removeKey")]
+	fn remove_key(&self, public_key: Bytes, key_type: String) -> Result<bool, Error>;
+
 	/// Returns all pending extrinsics, potentially grouped by sender.
 	#[method(name = "author_pendingExtrinsics")]
 	fn pending_extrinsics(&self) -> Result<Vec<Bytes>, Error>;
@@ -139,6 +139,18 @@ pub trait StateApi<Hash> {
 	)]
 	fn subscribe_storage(&self, keys: Option<Vec<StorageKey>>);
 
+	/// New storage subscription for all keys starting with one of the given prefixes.
+	///
+	/// Only keys whose value differs from the one in the parent block are reported.
+	/// Subscribing to more than 16 prefixes, or to a prefix shorter than a pallet prefix
+	/// (16 bytes), is unsafe.
+	#[subscription(
+		name = "state_subscribeStoragePrefix" => "state_storagePrefix",
+		unsubscribe = "state_unsubscribeStoragePrefix",
+		item = StorageChangeSet<Hash>,
+	)]
+	fn subscribe_storage_prefix(&self, prefixes: Vec<StorageKey>);
+
 	/// The `traceBlock` RPC provides a way to trace the re-execution of a single
 	/// block, collecting Spans and Events from both the client and the relevant WASM runtime.
 	/// The Spans and Events are conceptually equivalent to those from the [Tracing][1] crate.
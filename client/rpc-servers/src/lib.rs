@@ -49,7 +49,7 @@ pub use jsonrpsee::{
 	},
 	server::{middleware::rpc::RpcServiceBuilder, BatchRequestConfig},
 };
-pub use middleware::{MetricsLayer, RateLimitLayer, RpcMetrics};
+pub use middleware::{MetricsLayer, RateLimitLayer, RpcLoggerLayer, RpcMetrics};
 
 const MEGABYTE: u32 = 1024 * 1024;
 
@@ -85,6 +85,8 @@ pub struct Config<'a, M: Send + Sync + 'static> {
 	pub batch_config: BatchRequestConfig,
 	/// Rate limit calls per minute.
 	pub rate_limit: Option<NonZeroU32>,
+	/// Log calls taking longer than this as warnings, including their parameters.
+	pub slow_call_threshold: Option<Duration>,
 }
 
 #[derive(Debug, Clone)]
@@ -117,6 +119,7 @@ where
 		tokio_handle,
 		rpc_api,
 		rate_limit,
+		slow_call_threshold,
 	} = config;
 
 	let std_listener = TcpListener::bind(addrs.as_slice()).await?.into_std()?;
@@ -175,11 +178,14 @@ where
 
 				let metrics = metrics.map(|m| MetricsLayer::new(m, transport_label));
 				let rate_limit = rate_limit.map(|r| RateLimitLayer::per_minute(r));
-
-				// NOTE: The metrics needs to run first to include rate-limited calls in the
-				// metrics.
-				let rpc_middleware =
-					RpcServiceBuilder::new().option_layer(metrics.clone()).option_layer(rate_limit);
+				let logger = RpcLoggerLayer::new(transport_label, slow_call_threshold);
+
+				// NOTE: The logger and metrics need to run first to include rate-limited calls
+				// in the logs and metrics.
+				let rpc_middleware = RpcServiceBuilder::new()
+					.layer(logger)
+					.option_layer(metrics.clone())
+					.option_layer(rate_limit);
 
 				let mut svc =
 					service_builder.set_rpc_middleware(rpc_middleware).build(methods, stop_handle);
@@ -0,0 +1,272 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC middleware to log RPC calls with their latency and outcome.
+
+use std::{
+	future::Future,
+	pin::Pin,
+	task::{Context, Poll},
+	time::{Duration, Instant},
+};
+
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse};
+use pin_project::pin_project;
+
+use super::rate_limit::RATE_LIMIT_ERROR_CODE;
+
+/// Maximum number of bytes of the call parameters included in the slow call log.
+const MAX_LOGGED_PARAMS_LEN: usize = 512;
+
+/// Prefixes of the methods whose parameters are included in the slow call log.
+///
+/// These methods only take public data. The parameters of all other methods are redacted, as
+/// they may contain secrets, e.g. the seed passed to `author_insertKey`.
+const LOGGED_PARAMS_METHOD_PREFIXES: &[&str] =
+	&["archive_", "chain_", "chainHead_", "childstate_", "payment_", "state_"];
+
+/// Classify the outcome of an RPC call.
+///
+/// Returns `None` for successful calls and a short error class otherwise, suitable for
+/// use as a log field or a prometheus label.
+pub fn error_class(rp: &MethodResponse) -> Option<&'static str> {
+	let code = rp.as_error_code()?;
+
+	Some(match code {
+		-32700 => "parse_error",
+		-32600 => "invalid_request",
+		-32601 => "method_not_found",
+		-32602 => "invalid_params",
+		-32603 => "internal_error",
+		RATE_LIMIT_ERROR_CODE => "rate_limited",
+		-32099..=-32000 => "server_error",
+		_ => "call_error",
+	})
+}
+
+/// Logger layer.
+#[derive(Debug, Clone)]
+pub struct RpcLoggerLayer {
+	transport_label: &'static str,
+	slow_call_threshold: Option<Duration>,
+}
+
+impl RpcLoggerLayer {
+	/// Create a new [`RpcLoggerLayer`].
+	///
+	/// Calls taking longer than `slow_call_threshold` are logged as warnings together with
+	/// their (truncated) parameters.
+	pub fn new(transport_label: &'static str, slow_call_threshold: Option<Duration>) -> Self {
+		Self { transport_label, slow_call_threshold }
+	}
+}
+
+impl<S> tower::Layer<S> for RpcLoggerLayer {
+	type Service = RpcLogger<S>;
+
+	fn layer(&self, service: S) -> Self::Service {
+		RpcLogger {
+			service,
+			transport_label: self.transport_label,
+			slow_call_threshold: self.slow_call_threshold,
+		}
+	}
+}
+
+/// Logger middleware.
+#[derive(Debug, Clone)]
+pub struct RpcLogger<S> {
+	service: S,
+	transport_label: &'static str,
+	slow_call_threshold: Option<Duration>,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcLogger<S>
+where
+	S: Send + Sync + RpcServiceT<'a>,
+{
+	type Future = ResponseFuture<'a, S::Future>;
+
+	fn call(&self, req: Request<'a>) -> Self::Future {
+		ResponseFuture {
+			fut: self.service.call(req.clone()),
+			req,
+			now: Instant::now(),
+			transport_label: self.transport_label,
+			slow_call_threshold: self.slow_call_threshold,
+		}
+	}
+}
+
+/// Response future for the logger.
+#[pin_project]
+pub struct ResponseFuture<'a, F> {
+	#[pin]
+	fut: F,
+	req: Request<'a>,
+	now: Instant,
+	transport_label: &'static str,
+	slow_call_threshold: Option<Duration>,
+}
+
+impl<'a, F> std::fmt::Debug for ResponseFuture<'a, F> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str("ResponseFuture")
+	}
+}
+
+impl<'a, F: Future<Output = MethodResponse>> Future for ResponseFuture<'a, F> {
+	type Output = F::Output;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let this = self.project();
+
+		let res = this.fut.poll(cx);
+		if let Poll::Ready(rp) = &res {
+			let method_name = this.req.method_name();
+			let transport_label = &this.transport_label;
+			let elapsed = this.now.elapsed();
+			let outcome = error_class(rp).unwrap_or("ok");
+
+			log::debug!(
+				target: "rpc_metrics",
+				"[{transport_label}] {method_name} call took {} μs, outcome={outcome}",
+				elapsed.as_micros(),
+			);
+
+			if this.slow_call_threshold.map_or(false, |threshold| elapsed >= threshold) {
+				let params = this.req.params();
+				log::warn!(
+					target: "rpc_metrics",
+					"[{transport_label}] slow call {method_name} took {} ms, outcome={outcome}, params={}",
+					elapsed.as_millis(),
+					logged_params(method_name, params.as_str().unwrap_or("[]")),
+				);
+			}
+		}
+		res
+	}
+}
+
+/// The parameters of a call to `method_name` as included in the slow call log.
+fn logged_params<'a>(method_name: &str, params: &'a str) -> std::borrow::Cow<'a, str> {
+	if LOGGED_PARAMS_METHOD_PREFIXES
+		.iter()
+		.any(|prefix| method_name.starts_with(prefix))
+	{
+		truncate(params, MAX_LOGGED_PARAMS_LEN)
+	} else {
+		"<redacted>".into()
+	}
+}
+
+/// Truncate `s` to at most `max_len` bytes without splitting a character.
+fn truncate(s: &str, max_len: usize) -> std::borrow::Cow<'_, str> {
+	if s.len() <= max_len {
+		return s.into()
+	}
+
+	let mut end = max_len;
+	while !s.is_char_boundary(end) {
+		end -= 1;
+	}
+	format!("{}…", &s[..end]).into()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use jsonrpsee::{
+		server::ResponsePayload,
+		types::{ErrorObject, Id},
+	};
+
+	fn error_response(code: i32) -> MethodResponse {
+		MethodResponse::error(Id::Number(1), ErrorObject::owned(code, "error", None::<()>))
+	}
+
+	/// Service answering every call with its method name.
+	struct EchoService;
+
+	impl<'a> RpcServiceT<'a> for EchoService {
+		type Future = futures::future::Ready<MethodResponse>;
+
+		fn call(&self, req: Request<'a>) -> Self::Future {
+			let method_name = req.method_name().to_owned();
+			futures::future::ready(MethodResponse::response(
+				req.id,
+				ResponsePayload::success(method_name),
+				usize::MAX,
+			))
+		}
+	}
+
+	#[test]
+	fn error_class_works() {
+		let success =
+			MethodResponse::response(Id::Number(1), ResponsePayload::success(()), usize::MAX);
+		assert_eq!(error_class(&success), None);
+
+		assert_eq!(error_class(&error_response(-32700)), Some("parse_error"));
+		assert_eq!(error_class(&error_response(-32600)), Some("invalid_request"));
+		assert_eq!(error_class(&error_response(-32601)), Some("method_not_found"));
+		assert_eq!(error_class(&error_response(-32602)), Some("invalid_params"));
+		assert_eq!(error_class(&error_response(-32603)), Some("internal_error"));
+		assert_eq!(error_class(&error_response(RATE_LIMIT_ERROR_CODE)), Some("rate_limited"));
+		assert_eq!(error_class(&error_response(-32000)), Some("server_error"));
+		assert_eq!(error_class(&error_response(-32099)), Some("server_error"));
+		assert_eq!(error_class(&error_response(1)), Some("call_error"));
+	}
+
+	#[test]
+	fn truncate_works() {
+		assert_eq!(truncate("[1,2]", 5), "[1,2]");
+		assert_eq!(truncate("[1,2,3]", 5), "[1,2,…");
+		// Never splits the two byte `é`.
+		assert_eq!(truncate("[\"é\"]", 3), "[\"…");
+		assert_eq!(truncate("[\"é\"]", 4), "[\"é…");
+	}
+
+	#[test]
+	fn params_of_methods_taking_secrets_are_redacted() {
+		assert_eq!(logged_params("state_getStorage", "[\"0x00\"]"), "[\"0x00\"]");
+		assert_eq!(
+			logged_params("author_insertKey", "[\"aura\",\"//Alice\",\"0x00\"]"),
+			"<redacted>"
+		);
+		assert_eq!(logged_params("system_addReservedPeer", "[\"/ip4/1.2.3.4\"]"), "<redacted>");
+
+		let long_params = format!("[\"{}\"]", "0".repeat(MAX_LOGGED_PARAMS_LEN));
+		assert_eq!(
+			logged_params("chain_getBlock", &long_params).len(),
+			MAX_LOGGED_PARAMS_LEN + "…".len()
+		);
+	}
+
+	#[test]
+	fn rpc_logger_passes_responses_through() {
+		let logger = RpcLoggerLayer::new("test", Some(Duration::ZERO));
+		let service = tower::Layer::layer(&logger, EchoService);
+
+		let request = Request::new("author_insertKey".into(), None, Id::Number(7));
+		let response = futures::executor::block_on(service.call(request));
+
+		assert!(response.is_success());
+		assert_eq!(response.as_result(), r#"{"jsonrpc":"2.0","result":"author_insertKey","id":7}"#);
+	}
+}
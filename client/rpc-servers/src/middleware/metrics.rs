@@ -57,6 +57,8 @@ pub struct RpcMetrics {
 	calls_started: CounterVec<U64>,
 	/// Number of calls completed.
 	calls_finished: CounterVec<U64>,
+	/// Number of failed calls by error class.
+	calls_failed: CounterVec<U64>,
 	/// Number of Websocket sessions opened.
 	ws_sessions_opened: Option<Counter<U64>>,
 	/// Number of Websocket sessions closed.
@@ -101,6 +103,16 @@ impl RpcMetrics {
 					)?,
 					metrics_registry,
 				)?,
+				calls_failed: register(
+					CounterVec::new(
+						Opts::new(
+							"substrate_rpc_calls_failed",
+							"Number of failed RPC calls by error class (unique un-batched requests)",
+						),
+						&["protocol", "method", "error"],
+					)?,
+					metrics_registry,
+				)?,
 				ws_sessions_opened: register(
 					Counter::new(
 						"substrate_rpc_sessions_opened",
@@ -256,11 +268,6 @@ impl<'a, F: Future<Output = MethodResponse>> Future for ResponseFuture<'a, F> {
 			log::trace!(target: "rpc_metrics::extra", "[{transport_label}] result={:?}", rp);
 
 			let micros = now.elapsed().as_micros();
-			log::debug!(
-				target: "rpc_metrics",
-				"[{transport_label}] {method_name} call took {} μs",
-				micros,
-			);
 			metrics
 				.calls_time
 				.with_label_values(&[transport_label, method_name])
@@ -275,6 +282,12 @@ impl<'a, F: Future<Output = MethodResponse>> Future for ResponseFuture<'a, F> {
 					if rp.is_success() { "false" } else { "true" },
 				])
 				.inc();
+			if let Some(error) = super::error_class(rp) {
+				metrics
+					.calls_failed
+					.with_label_values(&[transport_label, method_name, error])
+					.inc();
+			}
 		}
 		res
 	}
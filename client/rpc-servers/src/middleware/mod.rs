@@ -18,10 +18,13 @@
 
 //! JSON-RPC specific middleware.
 
+/// Call logging middleware.
+pub mod logger;
 /// Grafana metrics middleware.
 pub mod metrics;
 /// Rate limit middleware.
 pub mod rate_limit;
 
+pub use logger::{error_class, RpcLogger, RpcLoggerLayer};
 pub use metrics::*;
 pub use rate_limit::*;
@@ -38,6 +38,9 @@ type RateLimitInner = governor::RateLimiter<NotKeyed, InMemoryState, DefaultCloc
 const MAX_JITTER: Duration = Duration::from_millis(50);
 const MAX_RETRIES: usize = 10;
 
+/// JSON-RPC error code returned for calls rejected by the rate limiter.
+pub(crate) const RATE_LIMIT_ERROR_CODE: i32 = -32999;
+
 /// JSON-RPC rate limit middleware layer.
 #[derive(Debug, Clone)]
 pub struct RateLimitLayer(governor::Quota);
@@ -103,5 +106,8 @@ where
 }
 
 fn reject_too_many_calls(id: Id) -> MethodResponse {
-	MethodResponse::error(id, ErrorObject::owned(-32999, "RPC rate limit exceeded", None::<()>))
+	MethodResponse::error(
+		id,
+		ErrorObject::owned(RATE_LIMIT_ERROR_CODE, "RPC rate limit exceeded", None::<()>),
+	)
 }
@@ -30,6 +30,7 @@ pub trait ArchiveApi<Hash> {
 	///
 	/// Returns an array of strings containing the hexadecimal-encoded SCALE-codec-encoded
 	/// transactions in that block. If no block with that hash is found, null.
+	/// If the block is known but its body has been pruned, an error is returned.
 	///
 	/// # Unstable
 	///
@@ -81,6 +82,8 @@ pub trait ArchiveApi<Hash> {
 
 	/// Call into the Runtime API at a specified block's state.
 	///
+	/// If the block is known but its state has been pruned, an error is returned.
+	///
 	/// # Unstable
 	///
 	/// This method is unstable and subject to change in the future.
@@ -94,6 +97,8 @@ pub trait ArchiveApi<Hash> {
 
 	/// Returns storage entries at a specific block's state.
 	///
+	/// If the block is known but its state has been pruned, an error is returned.
+	///
 	/// # Unstable
 	///
 	/// This method is unstable and subject to change in the future.
@@ -110,6 +110,26 @@ impl<BE: Backend<Block>, Block: BlockT, Client> Archive<BE, Block, Client> {
 	}
 }
 
+impl<BE, Block, Client> Archive<BE, Block, Client>
+where
+	Block: BlockT + 'static,
+	BE: Backend<Block> + 'static,
+	Client: HeaderBackend<Block> + 'static,
+{
+	/// Returns an error if the block is known but its state has been pruned.
+	///
+	/// Unknown blocks are left to the caller, which reports them as such.
+	fn ensure_state_not_pruned(&self, hash: Block::Hash) -> Result<(), ArchiveError> {
+		let Ok(Some(number)) = self.client.number(hash) else { return Ok(()) };
+
+		if self.backend.have_state_at(hash, number) {
+			Ok(())
+		} else {
+			Err(ArchiveError::Pruned(format!("State of block {hash:?} is not available")))
+		}
+	}
+}
+
 /// Parse hex-encoded string parameter as raw bytes.
 ///
 /// If the parsing fails, returns an error propagated to the RPC method.
@@ -138,7 +158,15 @@ where
 		+ 'static,
 {
 	fn archive_unstable_body(&self, hash: Block::Hash) -> RpcResult<Option<Vec<String>>> {
-		let Ok(Some(signed_block)) = self.client.block(hash) else { return Ok(None) };
+		let Ok(Some(signed_block)) = self.client.block(hash) else {
+			// The header is kept when the body of a block is pruned.
+			return match self.client.header(hash) {
+				Ok(Some(_)) =>
+					Err(ArchiveError::Pruned(format!("Body of block {hash:?} is not available"))
+						.into()),
+				_ => Ok(None),
+			}
+		};
 
 		let extrinsics = signed_block
 			.block
@@ -222,6 +250,7 @@ where
 		call_parameters: String,
 	) -> RpcResult<MethodResult> {
 		let call_parameters = Bytes::from(parse_hex_param(call_parameters)?);
+		self.ensure_state_not_pruned(hash)?;
 
 		let result =
 			self.client
@@ -270,6 +299,8 @@ where
 			.transpose()?
 			.map(ChildInfo::new_default_from_vec);
 
+		self.ensure_state_not_pruned(hash)?;
+
 		let storage_client = ArchiveStorage::new(
 			self.client.clone(),
 			self.storage_max_descendant_responses,
@@ -838,3 +838,71 @@ async fn archive_storage_discarded_items() {
 		_ => panic!("Unexpected result"),
 	};
 }
+
+#[tokio::test]
+async fn archive_reports_pruned_blocks() {
+	let builder = TestClientBuilder::with_pruning_window(1);
+	let backend = builder.backend();
+	let mut client = Arc::new(builder.build());
+
+	let api = Archive::new(
+		client.clone(),
+		backend,
+		CHAIN_GENESIS,
+		ArchiveConfig {
+			max_descendant_responses: MAX_PAGINATION_LIMIT,
+			max_queried_items: MAX_QUERIED_LIMIT,
+		},
+	)
+	.into_rpc();
+
+	let mut hashes = Vec::new();
+	for _ in 0..5 {
+		let block = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(client.chain_info().best_hash)
+			.with_parent_block_number(client.chain_info().best_number)
+			.build()
+			.unwrap()
+			.build()
+			.unwrap()
+			.block;
+		hashes.push(block.header.hash());
+		client.import_as_final(BlockOrigin::Own, block).await.unwrap();
+	}
+
+	// The header of a pruned block is still known.
+	let pruned_hash = format!("{:?}", hashes[0]);
+	let header: Option<String> = api.call("archive_unstable_header", [&pruned_hash]).await.unwrap();
+	assert!(header.is_some());
+
+	let err = api
+		.call::<_, serde_json::Value>("archive_unstable_body", [&pruned_hash])
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == 3004 && err.message().contains("Pruned"));
+
+	let err = api
+		.call::<_, serde_json::Value>(
+			"archive_unstable_call",
+			[&pruned_hash, "AccountNonceApi_account_nonce", "0x00"],
+		)
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == 3004 && err.message().contains("Pruned"));
+
+	let items: Vec<PaginatedStorageQuery<String>> = vec![PaginatedStorageQuery {
+		key: hex_string(&KEY),
+		query_type: StorageQueryType::Value,
+		pagination_start_key: None,
+	}];
+	let err = api
+		.call::<_, serde_json::Value>("archive_unstable_storage", rpc_params![&pruned_hash, items])
+		.await
+		.unwrap_err();
+	assert_matches!(err, Error::JsonRpc(err) if err.code() == 3004 && err.message().contains("Pruned"));
+
+	// The state of the latest block is still available.
+	let latest_hash = format!("{:?}", hashes[4]);
+	let body: Vec<String> = api.call("archive_unstable_body", [&latest_hash]).await.unwrap();
+	assert!(body.is_empty());
+}
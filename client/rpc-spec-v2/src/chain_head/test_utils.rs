@@ -114,6 +114,13 @@ impl<Client> BlockchainEvents<Block> for ChainHeadMockClient<Client> {
 	) -> sp_blockchain::Result<StorageEventStream<Hash>> {
 		unimplemented!()
 	}
+
+	fn storage_prefix_changes_notification_stream(
+		&self,
+		_filter_prefixes: &[StorageKey],
+	) -> sp_blockchain::Result<StorageEventStream<Hash>> {
+		unimplemented!()
+	}
 }
 
 // The following implementations are imposed by the `chainHead` trait bounds.
@@ -38,7 +38,7 @@ use sc_transaction_pool_api::{
 };
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_core::Bytes;
+use sp_core::{crypto::KeyTypeId, Bytes};
 use sp_keystore::{KeystoreExt, KeystorePtr};
 use sp_runtime::traits::Block as BlockT;
 use sp_session::SessionKeys;
@@ -47,6 +47,20 @@ use self::error::{Error, Result};
 /// Re-export the API for backward compatibility.
 pub use sc_rpc_api::author::*;
 
+/// Creates the transaction registering new session keys on-chain, e.g. a signed
+/// `session::set_keys` call.
+pub trait SessionKeysTransactionBuilder: Send + Sync {
+	/// Return the SCALE encoded transaction registering the SCALE encoded session `keys`.
+	///
+	/// The transaction has to be signed with a key of type `key_type` from `keystore`.
+	fn build(
+		&self,
+		keys: Vec<u8>,
+		key_type: KeyTypeId,
+		keystore: KeystorePtr,
+	) -> std::result::Result<Vec<u8>, String>;
+}
+
 /// Authoring API
 pub struct Author<P, Client> {
 	/// Substrate client
@@ -59,6 +73,8 @@ pub struct Author<P, Client> {
 	deny_unsafe: DenyUnsafe,
 	/// Executor to spawn subscriptions.
 	executor: SubscriptionTaskExecutor,
+	/// Builds the transaction submitted by `author_rotateAndSubmitKeys`.
+	session_keys_tx_builder: Option<Arc<dyn SessionKeysTransactionBuilder>>,
 }
 
 impl<P, Client> Author<P, Client> {
@@ -70,7 +86,16 @@ impl<P, Client> Author<P, Client> {
 		deny_unsafe: DenyUnsafe,
 		executor: SubscriptionTaskExecutor,
 	) -> Self {
-		Author { client, pool, keystore, deny_unsafe, executor }
+		Author { client, pool, keystore, deny_unsafe, executor, session_keys_tx_builder: None }
+	}
+
+	/// Enable `author_rotateAndSubmitKeys` using the given transaction builder.
+	pub fn with_session_keys_transaction_builder(
+		mut self,
+		builder: Arc<dyn SessionKeysTransactionBuilder>,
+	) -> Self {
+		self.session_keys_tx_builder = Some(builder);
+		self
 	}
 }
 
@@ -128,6 +153,49 @@ where
 			.map_err(|api_err| Error::Client(Box::new(api_err)).into())
 	}
 
+	async fn rotate_and_submit_keys(&self, key_type: String) -> Result<RotatedKeys<TxHash<P>>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let key_type: KeyTypeId = key_type.as_str().try_into().map_err(|_| Error::BadKeyType)?;
+		let builder = self
+			.session_keys_tx_builder
+			.clone()
+			.ok_or(Error::SessionKeysSubmissionUnsupported)?;
+		let keys = self.rotate_keys()?;
+		let best_block_hash = self.client.info().best_hash;
+
+		let xt = builder
+			.build(keys.to_vec(), key_type, self.keystore.clone())
+			.map_err(Error::SessionKeysTransaction)
+			.and_then(|xt| TransactionFor::<P>::decode(&mut &xt[..]).map_err(Error::from));
+		let submitted = match xt {
+			Ok(xt) => self.pool.submit_one(best_block_hash, TX_SOURCE, xt).await.map_err(|e| {
+				e.into_pool_error()
+					.map(|e| Error::Pool(e))
+					.unwrap_or_else(|e| Error::Verification(Box::new(e)))
+			}),
+			Err(e) => Err(e),
+		};
+
+		match submitted {
+			Ok(transaction_hash) => Ok(RotatedKeys { keys, transaction_hash }),
+			Err(e) => {
+				// Don't keep keys that were never registered.
+				let generated = self
+					.client
+					.runtime_api()
+					.decode_session_keys(best_block_hash, keys.to_vec())
+					.ok()
+					.flatten()
+					.unwrap_or_default();
+				for (public, key_type) in generated {
+					let _ = self.keystore.remove(key_type, &public);
+				}
+				Err(e)
+			},
+		}
+	}
+
 	fn has_session_keys(&self, session_keys: Bytes) -> Result<bool> {
 		self.deny_unsafe.check_if_safe()?;
 
@@ -149,6 +217,23 @@ where
 		Ok(self.keystore.has_keys(&[(public_key.to_vec(), key_type)]))
 	}
 
+	fn list_keys(&self, key_type: String) -> Result<Vec<Bytes>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let key_type = key_type.as_str().try_into().map_err(|_| Error::BadKeyType)?;
+		let keys = self.keystore.keys(key_type).map_err(|_| Error::KeystoreUnavailable)?;
+		Ok(keys.into_iter().map(Into::into).collect())
+	}
+
+	fn remove_key(&self, public_key: Bytes, key_type: String) -> Result<bool> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let key_type = key_type.as_str().try_into().map_err(|_| Error::BadKeyType)?;
+		self.keystore
+			.remove(key_type, &public_key[..])
+			.map_err(|_| Error::KeystoreUnavailable)
+	}
+
 	fn pending_extrinsics(&self) -> Result<Vec<Bytes>> {
 		Ok(self.pool.ready().map(|tx| tx.data().encode().into()).collect())
 	}
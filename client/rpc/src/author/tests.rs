@@ -79,6 +79,7 @@ impl TestSetup {
 			keystore: self.keystore.clone(),
 			deny_unsafe: DenyUnsafe::No,
 			executor: test_executor(),
+			session_keys_tx_builder: None,
 		}
 	}
 
@@ -324,3 +325,92 @@ async fn author_has_key() {
 	};
 	assert!(!has_bob_ed);
 }
+
+#[tokio::test]
+async fn author_list_and_remove_keys() {
+	let setup = TestSetup::default();
+	let api = setup.author().into_rpc();
+	let key_type = String::from_utf8(ED25519.0.to_vec()).expect("Keytype is a valid string");
+
+	let public = setup.keystore.ed25519_generate_new(ED25519, None).unwrap();
+	let keys: Vec<Bytes> = api.call("author_listKeys", [key_type.clone()]).await.unwrap();
+	assert_eq!(keys, vec![Bytes::from(public.to_raw_vec())]);
+
+	let params = (Bytes::from(public.to_raw_vec()), key_type.clone());
+	assert!(api.call::<_, bool>("author_removeKey", params.clone()).await.unwrap());
+	assert!(!api.call::<_, bool>("author_removeKey", params).await.unwrap());
+
+	let keys: Vec<Bytes> = api.call("author_listKeys", [key_type]).await.unwrap();
+	assert!(keys.is_empty());
+}
+
+#[derive(Default)]
+struct TestSessionKeysTransactionBuilder {
+	xt: Option<Extrinsic>,
+	key_type: parking_lot::Mutex<Option<KeyTypeId>>,
+}
+
+impl SessionKeysTransactionBuilder for TestSessionKeysTransactionBuilder {
+	fn build(
+		&self,
+		_: Vec<u8>,
+		key_type: KeyTypeId,
+		_: KeystorePtr,
+	) -> std::result::Result<Vec<u8>, String> {
+		*self.key_type.lock() = Some(key_type);
+		self.xt.as_ref().map(Encode::encode).ok_or_else(|| "No signing key".into())
+	}
+}
+
+#[tokio::test]
+async fn author_rotate_and_submit_keys() {
+	let setup = TestSetup::default();
+	let xt = uxt(AccountKeyring::Alice, 0);
+	let builder =
+		Arc::new(TestSessionKeysTransactionBuilder { xt: Some(xt.clone()), ..Default::default() });
+	let api = setup.author().with_session_keys_transaction_builder(builder.clone()).into_rpc();
+	let key_type = String::from_utf8(SR25519.0.to_vec()).expect("Keytype is a valid string");
+
+	let rotated: RotatedKeys<H256> =
+		api.call("author_rotateAndSubmitKeys", [key_type]).await.unwrap();
+	assert_eq!(rotated.transaction_hash, blake2_256(&xt.encode()).into());
+	assert_eq!(*builder.key_type.lock(), Some(SR25519));
+
+	let session_keys = SessionKeys::decode(&mut &rotated.keys[..]).unwrap();
+	assert!(setup
+		.keystore
+		.keys(ED25519)
+		.unwrap()
+		.contains(&session_keys.ed25519.to_raw_vec()));
+	assert_eq!(setup.pool.status().ready, 1);
+}
+
+#[tokio::test]
+async fn author_rotate_and_submit_keys_removes_keys_on_failure() {
+	let setup = TestSetup::default();
+	let api = setup
+		.author()
+		.with_session_keys_transaction_builder(Arc::new(
+			TestSessionKeysTransactionBuilder::default(),
+		))
+		.into_rpc();
+	let key_type = String::from_utf8(SR25519.0.to_vec()).expect("Keytype is a valid string");
+
+	assert_matches!(
+		api.call::<_, RotatedKeys<H256>>("author_rotateAndSubmitKeys", [key_type.clone()]).await,
+		Err(RpcError::JsonRpc(err)) if err.message().contains("No signing key")
+	);
+	assert!(setup.keystore.keys(ED25519).unwrap().is_empty());
+	assert!(setup.keystore.keys(SR25519).unwrap().is_empty());
+
+	assert_matches!(
+		api.call::<_, RotatedKeys<H256>>("author_rotateAndSubmitKeys", ["bad"]).await,
+		Err(RpcError::JsonRpc(err)) if err.message().contains("Invalid key type")
+	);
+
+	let api = setup.author().into_rpc();
+	assert_matches!(
+		api.call::<_, RotatedKeys<H256>>("author_rotateAndSubmitKeys", [key_type]).await,
+		Err(RpcError::JsonRpc(err)) if err.message().contains("not supported")
+	);
+}
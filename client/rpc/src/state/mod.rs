@@ -158,6 +158,14 @@ where
 		keys: Option<Vec<StorageKey>>,
 		deny_unsafe: DenyUnsafe,
 	);
+
+	/// New storage prefix subscription
+	fn subscribe_storage_prefix(
+		&self,
+		pending: PendingSubscriptionSink,
+		prefixes: Vec<StorageKey>,
+		deny_unsafe: DenyUnsafe,
+	);
 }
 
 /// Create new state API that works on full node.
@@ -330,6 +338,14 @@ where
 	fn subscribe_storage(&self, pending: PendingSubscriptionSink, keys: Option<Vec<StorageKey>>) {
 		self.backend.subscribe_storage(pending, keys, self.deny_unsafe)
 	}
+
+	fn subscribe_storage_prefix(
+		&self,
+		pending: PendingSubscriptionSink,
+		prefixes: Vec<StorageKey>,
+	) {
+		self.backend.subscribe_storage_prefix(pending, prefixes, self.deny_unsafe)
+	}
 }
 
 /// Child state backend API.
@@ -49,12 +49,21 @@ use sp_core::{
 	traits::CallContext,
 	Bytes,
 };
-use sp_runtime::traits::Block as BlockT;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 use sp_version::RuntimeVersion;
 
 /// The maximum time allowed for an RPC call when running without unsafe RPC enabled.
 const MAXIMUM_SAFE_RPC_CALL_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// The minimum length of a storage prefix that can be subscribed to without unsafe RPC enabled.
+///
+/// This is the length of a pallet prefix.
+const MINIMUM_SAFE_STORAGE_PREFIX_LEN: usize = 16;
+
+/// The maximum number of storage prefixes that can be subscribed to at once without unsafe RPC
+/// enabled.
+const MAXIMUM_SAFE_STORAGE_PREFIXES: usize = 16;
+
 /// Ranges to query in state_queryStorage.
 struct QueryStorageRange<Block: BlockT> {
 	/// Hashes of all the blocks in the range.
@@ -460,6 +469,64 @@ where
 		spawn_subscription_task(&self.executor, pipe_from_stream(pending, stream));
 	}
 
+	fn subscribe_storage_prefix(
+		&self,
+		pending: PendingSubscriptionSink,
+		prefixes: Vec<StorageKey>,
+		deny_unsafe: DenyUnsafe,
+	) {
+		if prefixes.len() > MAXIMUM_SAFE_STORAGE_PREFIXES ||
+			prefixes.iter().any(|prefix| prefix.0.len() < MINIMUM_SAFE_STORAGE_PREFIX_LEN)
+		{
+			if let Err(err) = deny_unsafe.check_if_safe() {
+				spawn_subscription_task(&self.executor, pending.reject(ErrorObject::from(err)));
+				return
+			}
+		}
+
+		let stream = match self.client.storage_prefix_changes_notification_stream(&prefixes) {
+			Ok(stream) => stream,
+			Err(blockchain_err) => {
+				spawn_subscription_task(
+					&self.executor,
+					pending.reject(Error::Client(Box::new(blockchain_err))),
+				);
+				return
+			},
+		};
+
+		let client = self.client.clone();
+		let stream = stream
+			.then(move |storage_notif| {
+				let client = client.clone();
+				let block = storage_notif.block;
+				let changes = storage_notif
+					.changes
+					.iter()
+					.filter_map(|(o_sk, k, v)| o_sk.is_none().then(|| (k.clone(), v.cloned())))
+					.collect::<Vec<_>>();
+
+				// Only report the keys whose value actually changed compared to the parent block.
+				// This reads from the database, so keep it off the async executor.
+				tokio::task::spawn_blocking(move || {
+					let parent = client.header(block).ok().flatten().map(|h| *h.parent_hash());
+					let changes = changes
+						.into_iter()
+						.filter(|(k, v)| match parent {
+							Some(parent) => client.storage(parent, k).ok().flatten() != *v,
+							None => true,
+						})
+						.collect();
+					StorageChangeSet { block, changes }
+				})
+			})
+			.filter_map(|storage| {
+				future::ready(storage.ok().filter(|storage| !storage.changes.is_empty()))
+			});
+
+		spawn_subscription_task(&self.executor, pipe_from_stream(pending, stream));
+	}
+
 	fn trace_block(
 		&self,
 		block: Block::Hash,
@@ -302,6 +302,55 @@ async fn should_send_initial_storage_changes_and_notifications() {
 	assert_matches!(timeout_secs(1, sub.next::<StorageChangeSet<H256>>()).await, Ok(Some(_)));
 }
 
+#[tokio::test]
+async fn should_notify_about_storage_prefix_changes() {
+	init_logger();
+
+	let accounts_prefix =
+		[sp_crypto_hashing::twox_128(b"System"), sp_crypto_hashing::twox_128(b"Account")].concat();
+
+	let mut sub = {
+		let mut client = Arc::new(substrate_test_runtime_client::new());
+		let (api, _child) = new_full(client.clone(), test_executor(), DenyUnsafe::No);
+
+		let api_rpc = api.into_rpc();
+		let sub = api_rpc
+			.subscribe_unbounded(
+				"state_subscribeStoragePrefix",
+				[[StorageKey(accounts_prefix.clone())]],
+			)
+			.await
+			.unwrap();
+
+		let mut builder = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(client.chain_info().best_hash)
+			.with_parent_block_number(client.chain_info().best_number)
+			.build()
+			.unwrap();
+		builder
+			.push_transfer(Transfer {
+				from: AccountKeyring::Alice.into(),
+				to: AccountKeyring::Ferdie.into(),
+				amount: 42,
+				nonce: 0,
+			})
+			.unwrap();
+		let block = builder.build().unwrap().block;
+		client.import(BlockOrigin::Own, block).await.unwrap();
+
+		sub
+	};
+
+	// Only the changed accounts are reported.
+	let (changes, _) = timeout_secs(1, sub.next::<StorageChangeSet<H256>>())
+		.await
+		.unwrap()
+		.unwrap()
+		.unwrap();
+	assert!(!changes.changes.is_empty());
+	assert!(changes.changes.iter().all(|(key, _)| key.0.starts_with(&accounts_prefix)));
+}
+
 #[tokio::test]
 async fn should_query_storage() {
 	async fn run_tests(mut client: Arc<TestClient>) {
@@ -528,6 +577,38 @@ async fn wildcard_storage_subscriptions_are_rpc_unsafe() {
 	assert_matches!(err, Err(RpcError::JsonRpc(e)) if e.message() == "RPC call is unsafe to be called externally");
 }
 
+#[tokio::test]
+async fn short_prefix_storage_subscriptions_are_rpc_unsafe() {
+	let client = Arc::new(substrate_test_runtime_client::new());
+	let (api, _child) = new_full(client, test_executor(), DenyUnsafe::Yes);
+
+	let api_rpc = api.into_rpc();
+	let err = api_rpc
+		.subscribe_unbounded("state_subscribeStoragePrefix", [[StorageKey(vec![])]])
+		.await;
+	assert_matches!(err, Err(RpcError::JsonRpc(e)) if e.message() == "RPC call is unsafe to be called externally");
+
+	let err = api_rpc
+		.subscribe_unbounded("state_subscribeStoragePrefix", [[StorageKey(vec![1; 15])]])
+		.await;
+	assert_matches!(err, Err(RpcError::JsonRpc(e)) if e.message() == "RPC call is unsafe to be called externally");
+}
+
+#[tokio::test]
+async fn too_many_prefix_storage_subscriptions_are_rpc_unsafe() {
+	let client = Arc::new(substrate_test_runtime_client::new());
+	let (api, _child) = new_full(client, test_executor(), DenyUnsafe::Yes);
+	let api_rpc = api.into_rpc();
+
+	let prefixes = (0..17u8).map(|i| StorageKey(vec![i; 16])).collect::<Vec<_>>();
+	let err = api_rpc.subscribe_unbounded("state_subscribeStoragePrefix", [prefixes]).await;
+	assert_matches!(err, Err(RpcError::JsonRpc(e)) if e.message() == "RPC call is unsafe to be called externally");
+
+	let prefixes = (0..16u8).map(|i| StorageKey(vec![i; 16])).collect::<Vec<_>>();
+	let sub = api_rpc.subscribe_unbounded("state_subscribeStoragePrefix", [prefixes]).await;
+	assert!(sub.is_ok());
+}
+
 #[tokio::test]
 async fn concrete_storage_subscriptions_are_rpc_safe() {
 	let client = Arc::new(substrate_test_runtime_client::new());
@@ -40,7 +40,7 @@ use sc_executor::{
 	sp_wasm_interface::HostFunctions, HeapAllocStrategy, NativeElseWasmExecutor,
 	NativeExecutionDispatch, RuntimeVersionOf, WasmExecutor, DEFAULT_HEAP_ALLOC_STRATEGY,
 };
-use sc_keystore::LocalKeystore;
+use sc_keystore::{CompositeKeystore, LocalKeystore, RemoteKeystore};
 use sc_network::{
 	config::{FullNetworkConfiguration, SyncMode},
 	peer_store::PeerStore,
@@ -56,7 +56,7 @@ use sc_network_sync::{
 	warp_request_handler::RequestHandler as WarpSyncRequestHandler, SyncingService, WarpSyncParams,
 };
 use sc_rpc::{
-	author::AuthorApiServer,
+	author::{AuthorApiServer, SessionKeysTransactionBuilder},
 	chain::ChainApiServer,
 	offchain::OffchainApiServer,
 	state::{ChildStateApiServer, StateApiServer},
@@ -92,29 +92,41 @@ pub type TFullCallExecutor<TBl, TExec> = crate::client::LocalCallExecutor<TBl, B
 type TFullParts<TBl, TRtApi, TExec> =
 	(TFullClient<TBl, TRtApi, TExec>, Arc<TFullBackend<TBl>>, KeystoreContainer, TaskManager);
 
-/// Construct a local keystore shareable container
-pub struct KeystoreContainer(Arc<LocalKeystore>);
+/// Construct a keystore shareable container
+pub struct KeystoreContainer {
+	keystore: KeystorePtr,
+	local: Arc<LocalKeystore>,
+}
 
 impl KeystoreContainer {
 	/// Construct KeystoreContainer
+	///
+	/// With a remote keystore, keys missing from the local keystore are taken from the signer.
 	pub fn new(config: &KeystoreConfig) -> Result<Self, Error> {
-		let keystore = Arc::new(match config {
-			KeystoreConfig::Path { path, password } =>
+		let local = Arc::new(match config {
+			KeystoreConfig::Path { path, password } |
+			KeystoreConfig::Remote { path, password, .. } =>
 				LocalKeystore::open(path.clone(), password.clone())?,
 			KeystoreConfig::InMemory => LocalKeystore::in_memory(),
 		});
 
-		Ok(Self(keystore))
+		let keystore: KeystorePtr = match config {
+			KeystoreConfig::Remote { uri, .. } =>
+				Arc::new(CompositeKeystore::new(RemoteKeystore::open(uri)?, local.clone())),
+			_ => local.clone(),
+		};
+
+		Ok(Self { keystore, local })
 	}
 
 	/// Returns a shared reference to a dynamic `Keystore` trait implementation.
 	pub fn keystore(&self) -> KeystorePtr {
-		self.0.clone()
+		self.keystore.clone()
 	}
 
 	/// Returns a shared reference to the local keystore .
 	pub fn local_keystore(&self) -> Arc<LocalKeystore> {
-		self.0.clone()
+		self.local.clone()
 	}
 }
 
@@ -381,6 +393,10 @@ pub struct SpawnTasksParams<'a, TBl: BlockT, TCl, TExPool, TRpc, Backend> {
 	pub sync_service: Arc<SyncingService<TBl>>,
 	/// Telemetry instance for this node.
 	pub telemetry: Option<&'a mut Telemetry>,
+	/// Builds the transaction submitted by `author_rotateAndSubmitKeys`.
+	///
+	/// The RPC method is unsupported if this is `None`.
+	pub session_keys_tx_builder: Option<Arc<dyn SessionKeysTransactionBuilder>>,
 }
 
 /// Spawn the tasks that are required to run a node.
@@ -425,6 +441,7 @@ where
 		tx_handler_controller,
 		sync_service,
 		telemetry,
+		session_keys_tx_builder,
 	} = params;
 
 	let chain_info = client.usage_info().chain;
@@ -509,6 +526,7 @@ where
 			&config,
 			backend.clone(),
 			&*rpc_builder,
+			session_keys_tx_builder.clone(),
 		)
 	};
 
@@ -523,6 +541,7 @@ where
 			client.clone(),
 			network,
 			sync_service.clone(),
+			transaction_pool.clone(),
 			config.informant_output_format,
 		),
 	);
@@ -611,6 +630,7 @@ pub fn gen_rpc_module<TBl, TBackend, TCl, TRpc, TExPool>(
 	config: &Configuration,
 	backend: Arc<TBackend>,
 	rpc_builder: &(dyn Fn(DenyUnsafe, SubscriptionTaskExecutor) -> Result<RpcModule<TRpc>, Error>),
+	session_keys_tx_builder: Option<Arc<dyn SessionKeysTransactionBuilder>>,
 ) -> Result<RpcModule<()>, Error>
 where
 	TBl: BlockT,
@@ -689,14 +709,17 @@ where
 		rpc_api.merge(archive_v2).map_err(|e| Error::Application(e.into()))?;
 	}
 
-	let author = sc_rpc::author::Author::new(
+	let mut author = sc_rpc::author::Author::new(
 		client.clone(),
 		transaction_pool,
 		keystore,
 		deny_unsafe,
 		task_executor.clone(),
-	)
-	.into_rpc();
+	);
+	if let Some(builder) = session_keys_tx_builder {
+		author = author.with_session_keys_transaction_builder(builder);
+	}
+	let author = author.into_rpc();
 
 	let system = sc_rpc::system::System::new(system_info, system_rpc_tx, deny_unsafe).into_rpc();
 
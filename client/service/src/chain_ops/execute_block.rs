@@ -0,0 +1,247 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::error::Error;
+use sc_client_api::{backend::Backend, BlockBackend, HeaderBackend};
+use sp_api::{ApiExt, Core, Metadata, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder;
+use sp_core::hexdisplay::HexDisplay;
+use sp_rpc::tracing::{BlockTrace, TraceBlockResponse};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, NumberFor},
+	ApplyExtrinsicResult,
+};
+use std::{fmt, sync::Arc};
+
+/// Filters for the execution trace of [`execute_block`].
+///
+/// Each filter is a comma separated list of prefixes, an empty string matches everything. See
+/// [`sc_tracing::block::BlockExecutor`] for details.
+#[derive(Debug, Clone, Default)]
+pub struct BlockTraceFilter {
+	/// Tracing targets of the recorded spans, e.g. `pallet,frame,state`.
+	pub targets: String,
+	/// Hex encoded prefixes of the storage keys of the recorded events.
+	pub storage_keys: String,
+	/// Host function names of the recorded events.
+	pub methods: String,
+}
+
+/// A storage change, `None` marks a deletion.
+pub type StorageChange = (Vec<u8>, Option<Vec<u8>>);
+
+/// Outcome of re-executing a block on top of its parent state.
+pub struct BlockExecutionReport<B: BlockT> {
+	/// Number of the executed block.
+	pub number: NumberFor<B>,
+	/// Hash of the executed block.
+	pub hash: B::Hash,
+	/// Hash of the parent block.
+	pub parent_hash: B::Hash,
+	/// State root recorded in the block header.
+	pub expected_state_root: B::Hash,
+	/// State root computed by the runtime.
+	pub computed_state_root: B::Hash,
+	/// Error returned by `Core_execute_block`, if any.
+	pub execution_error: Option<String>,
+	/// Outcome of applying each extrinsic.
+	pub extrinsics: Vec<Result<ApplyExtrinsicResult, String>>,
+	/// Changes to the top storage, sorted by key.
+	pub storage_changes: Vec<StorageChange>,
+	/// Changes to child storages, sorted by child storage key and key.
+	pub child_storage_changes: Vec<(Vec<u8>, Vec<StorageChange>)>,
+	/// Recorded spans and events, if tracing was requested.
+	pub trace: Option<BlockTrace>,
+}
+
+impl<B: BlockT> BlockExecutionReport<B> {
+	/// Whether the computed state root matches the one of the header.
+	pub fn state_root_matches(&self) -> bool {
+		self.expected_state_root == self.computed_state_root
+	}
+}
+
+/// Re-execute the block with the given hash on top of its parent state.
+///
+/// The block is executed twice: once through `Core_execute_block`, which performs all the checks
+/// done on import, and once extrinsic by extrinsic through the `BlockBuilder` API, which yields the
+/// storage changes and state root even if the former fails. If `trace` is given, the block is
+/// executed a third time with all matching spans and events recorded.
+pub fn execute_block<B, BA, C>(
+	client: Arc<C>,
+	backend: Arc<BA>,
+	hash: B::Hash,
+	trace: Option<BlockTraceFilter>,
+) -> Result<BlockExecutionReport<B>, Error>
+where
+	B: BlockT + 'static,
+	BA: Backend<B>,
+	C: BlockBackend<B> + HeaderBackend<B> + ProvideRuntimeApi<B> + Send + Sync + 'static,
+	C::Api: Core<B> + BlockBuilder<B> + Metadata<B>,
+{
+	let mut header = client.header(hash)?.ok_or("Unknown block")?;
+	let extrinsics = client.block_body(hash)?.ok_or("Block body not found")?;
+	let number = *header.number();
+	let parent_hash = *header.parent_hash();
+	let expected_state_root = *header.state_root();
+
+	// Seals are added by the consensus engines after building the block and removed on import.
+	header.digest_mut().logs.retain(|d| d.as_seal().is_none());
+
+	let execution_error = client
+		.runtime_api()
+		.execute_block(parent_hash, B::new(header.clone(), extrinsics.clone()))
+		.err()
+		.map(|e| e.to_string());
+
+	let api = client.runtime_api();
+	api.initialize_block(parent_hash, &header).map_err(sp_blockchain::Error::from)?;
+	let extrinsics = extrinsics
+		.into_iter()
+		.map(|xt| api.apply_extrinsic(parent_hash, xt).map_err(|e| e.to_string()))
+		.collect();
+	let computed_state_root = *api
+		.finalize_block(parent_hash)
+		.map_err(sp_blockchain::Error::from)?
+		.state_root();
+
+	let state = backend.state_at(parent_hash)?;
+	let changes = api.into_storage_changes(&state, parent_hash)?;
+	let mut storage_changes = changes.main_storage_changes;
+	storage_changes.sort();
+	let mut child_storage_changes = changes.child_storage_changes;
+	child_storage_changes.sort();
+	child_storage_changes.iter_mut().for_each(|(_, changes)| changes.sort());
+
+	let trace = trace
+		.map(|filter| {
+			sc_tracing::block::BlockExecutor::new(
+				client.clone(),
+				hash,
+				Some(filter.targets),
+				Some(filter.storage_keys),
+				Some(filter.methods),
+			)
+			.trace_block()
+		})
+		.transpose()
+		.map_err(|e| Error::Other(e.to_string()))?
+		.map(|response| match response {
+			TraceBlockResponse::BlockTrace(trace) => Ok(trace),
+			TraceBlockResponse::TraceError(e) => Err(Error::Other(e.error)),
+		})
+		.transpose()?;
+
+	Ok(BlockExecutionReport {
+		number,
+		hash,
+		parent_hash,
+		expected_state_root,
+		computed_state_root,
+		execution_error,
+		extrinsics,
+		storage_changes,
+		child_storage_changes,
+		trace,
+	})
+}
+
+fn fmt_changes(f: &mut fmt::Formatter, indent: &str, changes: &[StorageChange]) -> fmt::Result {
+	for (key, value) in changes {
+		match value {
+			Some(value) => writeln!(
+				f,
+				"{}0x{} = 0x{}",
+				indent,
+				HexDisplay::from(key),
+				HexDisplay::from(value)
+			)?,
+			None => writeln!(f, "{}0x{} deleted", indent, HexDisplay::from(key))?,
+		}
+	}
+	Ok(())
+}
+
+impl<B: BlockT> fmt::Display for BlockExecutionReport<B> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "Block #{} ({:?})", self.number, self.hash)?;
+		writeln!(f, "Parent: {:?}", self.parent_hash)?;
+		writeln!(f, "Expected state root: {:?}", self.expected_state_root)?;
+		writeln!(
+			f,
+			"Computed state root: {:?}{}",
+			self.computed_state_root,
+			if self.state_root_matches() { "" } else { " (MISMATCH)" },
+		)?;
+		match &self.execution_error {
+			Some(error) => writeln!(f, "Execution: failed: {}", error)?,
+			None => writeln!(f, "Execution: ok")?,
+		}
+
+		writeln!(f, "\nExtrinsics ({}):", self.extrinsics.len())?;
+		for (index, result) in self.extrinsics.iter().enumerate() {
+			match result {
+				Ok(outcome) => writeln!(f, "  #{}: {:?}", index, outcome)?,
+				Err(error) => writeln!(f, "  #{}: call failed: {}", index, error)?,
+			}
+		}
+
+		writeln!(f, "\nStorage changes ({}):", self.storage_changes.len())?;
+		fmt_changes(f, "  ", &self.storage_changes)?;
+		for (child, changes) in &self.child_storage_changes {
+			writeln!(
+				f,
+				"\nChild storage 0x{} changes ({}):",
+				HexDisplay::from(child),
+				changes.len()
+			)?;
+			fmt_changes(f, "  ", changes)?;
+		}
+
+		if let Some(trace) = &self.trace {
+			let mut spans = trace.spans.iter().collect::<Vec<_>>();
+			spans.sort_by_key(|span| span.id);
+			writeln!(f, "\nSpans ({}):", spans.len())?;
+			for span in spans {
+				writeln!(
+					f,
+					"  [{}] parent: {:?} {}::{}{}",
+					span.id,
+					span.parent_id,
+					span.target,
+					span.name,
+					if span.wasm { " (wasm)" } else { "" },
+				)?;
+			}
+
+			writeln!(f, "\nEvents ({}):", trace.events.len())?;
+			for event in &trace.events {
+				let mut values = event.data.string_values.iter().collect::<Vec<_>>();
+				values.sort();
+				let values = values
+					.into_iter()
+					.map(|(k, v)| format!("{}={}", k, v))
+					.collect::<Vec<_>>()
+					.join(" ");
+				writeln!(f, "  parent: {:?} {} {}", event.parent_id, event.target, values)?;
+			}
+		}
+
+		Ok(())
+	}
+}
@@ -17,28 +17,40 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::error::Error;
-use log::info;
+use log::{info, warn};
 use sc_client_api::{Backend, UsageProvider};
 use sp_runtime::traits::{Block as BlockT, NumberFor, Zero};
 use std::sync::Arc;
 
 /// Performs a revert of `blocks` blocks.
+///
+/// Finalized blocks are only reverted if `revert_finalized` is set.
 pub fn revert_chain<B, BA, C>(
 	client: Arc<C>,
 	backend: Arc<BA>,
 	blocks: NumberFor<B>,
+	revert_finalized: bool,
 ) -> Result<(), Error>
 where
 	B: BlockT,
 	C: UsageProvider<B>,
 	BA: Backend<B>,
 {
-	let reverted = backend.revert(blocks, false)?;
+	let reverted = backend.revert(blocks, revert_finalized)?;
 	let info = client.usage_info().chain;
 
 	if reverted.0.is_zero() {
 		info!("There aren't any non-finalized blocks to revert.");
 	} else {
+		if !reverted.1.is_empty() {
+			warn!(
+				"Reverted {} finalized blocks. Finalized: #{} ({})",
+				reverted.1.len(),
+				info.finalized_number,
+				info.finalized_hash,
+			);
+		}
+
 		info!("Reverted {} blocks. Best: #{} ({})", reverted.0, info.best_number, info.best_hash);
 
 		if reverted.0 > blocks {
@@ -1959,6 +1959,14 @@ where
 	) -> sp_blockchain::Result<StorageEventStream<Block::Hash>> {
 		Ok(self.storage_notifications.listen(filter_keys, child_filter_keys))
 	}
+
+	/// Get storage changes event stream for the given key prefixes.
+	fn storage_prefix_changes_notification_stream(
+		&self,
+		filter_prefixes: &[StorageKey],
+	) -> sp_blockchain::Result<StorageEventStream<Block::Hash>> {
+		Ok(self.storage_notifications.listen_prefixes(filter_prefixes))
+	}
 }
 
 impl<B, E, Block, RA> BlockBackend<Block> for Client<B, E, Block, RA>
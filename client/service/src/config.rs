@@ -23,7 +23,7 @@ use prometheus_endpoint::Registry;
 use sc_chain_spec::ChainSpec;
 pub use sc_client_db::{BlocksPruning, Database, DatabaseSource, PruningMode};
 pub use sc_executor::{WasmExecutionMethod, WasmtimeInstantiationStrategy};
-pub use sc_informant::OutputFormat;
+pub use sc_informant::{OutputFormat, StatusOutput};
 pub use sc_network::{
 	config::{
 		MultiaddrWithPeerId, NetworkConfiguration, NodeKeyConfig, NonDefaultSetConfig, ProtocolId,
@@ -34,7 +34,7 @@ pub use sc_network::{
 	},
 	Multiaddr,
 };
-pub use sc_telemetry::TelemetryEndpoints;
+pub use sc_telemetry::{DiskBufferConfig as TelemetryDiskBufferConfig, TelemetryEndpoints};
 pub use sc_transaction_pool::Options as TransactionPoolOptions;
 use sp_core::crypto::SecretString;
 use std::{
@@ -42,6 +42,7 @@ use std::{
 	net::SocketAddr,
 	num::NonZeroU32,
 	path::{Path, PathBuf},
+	time::Duration,
 };
 use tempfile::TempDir;
 
@@ -108,10 +109,15 @@ pub struct Configuration {
 	pub rpc_batch_config: RpcBatchRequestConfig,
 	/// RPC rate limit per minute.
 	pub rpc_rate_limit: Option<NonZeroU32>,
+	/// Log RPC calls taking longer than this as slow.
+	pub rpc_slow_call_threshold: Option<Duration>,
 	/// Prometheus endpoint configuration. `None` if disabled.
 	pub prometheus_config: Option<PrometheusConfig>,
 	/// Telemetry service URL. `None` if disabled.
 	pub telemetry_endpoints: Option<TelemetryEndpoints>,
+	/// Buffering of telemetry messages on disk while a telemetry server can't be reached. `None`
+	/// if disabled.
+	pub telemetry_disk_buffer: Option<TelemetryDiskBufferConfig>,
 	/// The default number of 64KB pages to allocate for Wasm execution
 	pub default_heap_pages: Option<u64>,
 	/// Should offchain workers be executed.
@@ -120,6 +126,8 @@ pub struct Configuration {
 	pub force_authoring: bool,
 	/// Disable GRANDPA when running in validator mode
 	pub disable_grandpa: bool,
+	/// Start with GRANDPA voting of the local authority paused.
+	pub grandpa_paused: bool,
 	/// Development key seed.
 	///
 	/// When running in development mode, the seed will be used to generate authority keys by the
@@ -168,13 +176,25 @@ pub enum KeystoreConfig {
 	},
 	/// In-memory keystore. Recommended for in-browser nodes.
 	InMemory,
+	/// Keystore forwarding key operations to an external signer.
+	///
+	/// Keys found in the local keystore at `path` are used in preference to the signer's, which
+	/// allows keeping keys the signer can't handle (e.g. VRF keys) locally.
+	Remote {
+		/// URI of the signer.
+		uri: String,
+		/// The path of the local keystore.
+		path: PathBuf,
+		/// Node keystore's password.
+		password: Option<SecretString>,
+	},
 }
 
 impl KeystoreConfig {
 	/// Returns the path for the keystore.
 	pub fn path(&self) -> Option<&Path> {
 		match self {
-			Self::Path { path, .. } => Some(path),
+			Self::Path { path, .. } | Self::Remote { path, .. } => Some(path),
 			Self::InMemory => None,
 		}
 	}
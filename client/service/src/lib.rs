@@ -405,6 +405,7 @@ where
 		cors: config.rpc_cors.as_ref(),
 		tokio_handle: config.tokio_handle.clone(),
 		rate_limit: config.rpc_rate_limit,
+		slow_call_threshold: config.rpc_slow_call_threshold,
 	};
 
 	// TODO: https://github.com/paritytech/substrate/issues/13773
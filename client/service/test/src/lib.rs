@@ -256,12 +256,15 @@ fn node_config<
 		rpc_message_buffer_capacity: Default::default(),
 		rpc_batch_config: RpcBatchRequestConfig::Unlimited,
 		rpc_rate_limit: None,
+		rpc_slow_call_threshold: None,
 		prometheus_config: None,
 		telemetry_endpoints: None,
+		telemetry_disk_buffer: None,
 		default_heap_pages: None,
 		offchain_worker: Default::default(),
 		force_authoring: false,
 		disable_grandpa: false,
+		grandpa_paused: false,
 		dev_key_seed: key_seed,
 		tracing_targets: None,
 		tracing_receiver: Default::default(),
@@ -0,0 +1,339 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Buffering of telemetry messages on disk while a telemetry server can't be reached.
+//!
+//! Writing and reading the buffer files may block, so it is done by a dedicated thread per
+//! endpoint instead of the telemetry task.
+
+use futures::{channel::mpsc, executor::block_on, SinkExt, StreamExt};
+use libp2p::Multiaddr;
+use std::{
+	fs,
+	io::{self, BufRead, BufReader, Write},
+	path::{Path, PathBuf},
+	sync::mpsc::{self as std_mpsc, Receiver, SyncSender, TrySendError},
+	task::{Context, Poll},
+};
+
+/// Maximum number of messages waiting for the buffer thread. Further messages are dropped until
+/// it catches up.
+const MAX_QUEUED_MESSAGES: usize = 1024;
+
+/// Size above which the messages read back from a buffer file are handed over as a new chunk.
+const REPLAY_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Configuration of the buffering of telemetry messages on disk.
+#[derive(Debug, Clone)]
+pub struct DiskBufferConfig {
+	/// Directory holding one buffer file per telemetry endpoint.
+	pub directory: PathBuf,
+	/// Maximum size of the buffer file of an endpoint. Messages are dropped once it is reached.
+	pub max_bytes_per_endpoint: u64,
+}
+
+/// Messages buffered on disk for a single telemetry endpoint, one serialized message per line.
+#[derive(Debug)]
+pub(crate) struct DiskBuffer {
+	/// Commands for the buffer thread, which ends once they are dropped.
+	commands: SyncSender<Command>,
+	/// Chunks of replayed messages, oldest first. Every replay ends with an empty chunk.
+	replayed: mpsc::Receiver<Vec<Vec<u8>>>,
+}
+
+enum Command {
+	Push(Vec<u8>),
+	Replay,
+}
+
+impl DiskBuffer {
+	/// Open the buffer of the endpoint with the given address, keeping messages buffered by a
+	/// previous run.
+	pub(crate) fn new(config: &DiskBufferConfig, addr: &Multiaddr) -> io::Result<Self> {
+		let (commands, receiver) = std_mpsc::sync_channel(MAX_QUEUED_MESSAGES);
+		// A single chunk may wait for the node, any further one is read once it is taken.
+		let (sender, replayed) = mpsc::channel(0);
+		let mut file = BufferFile {
+			path: buffer_path(&config.directory, addr),
+			max_bytes: config.max_bytes_per_endpoint,
+			len: 0,
+		};
+		let directory = config.directory.clone();
+
+		std::thread::Builder::new().name("telemetry-buffer".into()).spawn(move || {
+			if let Err(err) = fs::create_dir_all(&directory) {
+				log::error!(target: "telemetry", "Could not create {:?}: {}", directory, err);
+				return
+			}
+			file.len = match file_len(&file.path) {
+				Ok(len) => len,
+				Err(err) => {
+					log::error!(target: "telemetry", "Could not open {:?}: {}", file.path, err);
+					return
+				},
+			};
+			file.run(receiver, sender);
+		})?;
+
+		Ok(Self { commands, replayed })
+	}
+
+	/// Append a serialized message to the buffer.
+	pub(crate) fn push(&mut self, message: &[u8]) {
+		if let Err(TrySendError::Full(_)) = self.commands.try_send(Command::Push(message.to_vec()))
+		{
+			log::trace!(target: "telemetry", "Dropping telemetry message, the disk buffer is busy");
+		}
+	}
+
+	/// Start reading back all buffered messages, and empty the buffer.
+	///
+	/// The messages are handed over in chunks by [`Self::poll_replayed`].
+	pub(crate) fn replay(&mut self) {
+		if let Err(TrySendError::Full(_)) = self.commands.try_send(Command::Replay) {
+			log::debug!(target: "telemetry", "Not replaying the disk buffer, it is busy");
+		}
+	}
+
+	/// Poll the next chunk of replayed messages, oldest first.
+	///
+	/// An empty chunk means that all messages buffered at the time of the replay have been
+	/// handed over.
+	pub(crate) fn poll_replayed(&mut self, cx: &mut Context) -> Poll<Option<Vec<Vec<u8>>>> {
+		self.replayed.poll_next_unpin(cx)
+	}
+}
+
+/// The buffer file of an endpoint, only accessed by its buffer thread.
+struct BufferFile {
+	path: PathBuf,
+	max_bytes: u64,
+	len: u64,
+}
+
+impl BufferFile {
+	fn run(&mut self, commands: Receiver<Command>, mut replayed: mpsc::Sender<Vec<Vec<u8>>>) {
+		for command in commands {
+			match command {
+				Command::Push(message) => self.push(&message),
+				Command::Replay =>
+					if !self.replay(&mut replayed) {
+						return
+					},
+			}
+		}
+	}
+
+	fn push(&mut self, message: &[u8]) {
+		let size = message.len() as u64 + 1;
+		if self.len.saturating_add(size) > self.max_bytes {
+			log::trace!(target: "telemetry", "Disk buffer {:?} is full", self.path);
+			return
+		}
+
+		let result = fs::OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(&self.path)
+			.and_then(|mut file| file.write_all(&[message, b"\n"].concat()));
+		match result {
+			Ok(()) => self.len += size,
+			Err(err) => log::debug!(
+				target: "telemetry",
+				"Could not buffer telemetry message in {:?}: {}",
+				self.path,
+				err,
+			),
+		}
+	}
+
+	/// Hand over the buffered messages in chunks of about `REPLAY_CHUNK_BYTES`, followed by an
+	/// empty chunk, and remove the buffer file.
+	///
+	/// Returns `false` if the `DiskBuffer` has been dropped.
+	fn replay(&mut self, replayed: &mut mpsc::Sender<Vec<Vec<u8>>>) -> bool {
+		if self.len > 0 {
+			if let Err(err) = self.read_chunks(replayed) {
+				if replayed.is_closed() {
+					// Keep the messages which were not handed over for the next run.
+					return false
+				}
+				log::debug!(
+					target: "telemetry",
+					"Could not read buffered telemetry messages from {:?}: {}",
+					self.path,
+					err,
+				);
+			}
+			if let Err(err) = fs::remove_file(&self.path) {
+				log::debug!(target: "telemetry", "Could not remove {:?}: {}", self.path, err);
+			}
+			self.len = 0;
+		}
+
+		block_on(replayed.send(Vec::new())).is_ok()
+	}
+
+	fn read_chunks(&self, replayed: &mut mpsc::Sender<Vec<Vec<u8>>>) -> io::Result<()> {
+		let mut reader = BufReader::new(fs::File::open(&self.path)?);
+		let mut chunk = Vec::new();
+		let mut chunk_bytes = 0;
+		loop {
+			let mut message = Vec::new();
+			if reader.read_until(b'\n', &mut message)? == 0 {
+				break
+			}
+			if message.last() == Some(&b'\n') {
+				message.pop();
+			}
+			if message.is_empty() {
+				continue
+			}
+
+			chunk_bytes += message.len();
+			chunk.push(message);
+			if chunk_bytes >= REPLAY_CHUNK_BYTES {
+				block_on(replayed.send(std::mem::take(&mut chunk)))
+					.map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+				chunk_bytes = 0;
+			}
+		}
+
+		if !chunk.is_empty() {
+			block_on(replayed.send(chunk))
+				.map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+		}
+		Ok(())
+	}
+}
+
+/// The path of the buffer file of the endpoint with the given address.
+fn buffer_path(directory: &Path, addr: &Multiaddr) -> PathBuf {
+	let name: String = addr
+		.to_string()
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() || c == '.' { c } else { '_' })
+		.collect();
+	directory.join(format!("{}.jsonl", name.trim_start_matches('_')))
+}
+
+fn file_len(path: &Path) -> io::Result<u64> {
+	match fs::metadata(path) {
+		Ok(metadata) => Ok(metadata.len()),
+		Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(0),
+		Err(err) => Err(err),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::future::poll_fn;
+
+	fn config(directory: &Path, max_bytes_per_endpoint: u64) -> DiskBufferConfig {
+		DiskBufferConfig { directory: directory.join("telemetry"), max_bytes_per_endpoint }
+	}
+
+	fn addr() -> Multiaddr {
+		"/dns/telemetry.example.com/tcp/443/wss".parse().unwrap()
+	}
+
+	/// Replay the buffer, returning the chunks of messages.
+	fn replay_chunks(buffer: &mut DiskBuffer) -> Vec<Vec<Vec<u8>>> {
+		buffer.replay();
+		let mut chunks = Vec::new();
+		loop {
+			let chunk = block_on(poll_fn(|cx| buffer.poll_replayed(cx))).unwrap();
+			if chunk.is_empty() {
+				return chunks
+			}
+			chunks.push(chunk);
+		}
+	}
+
+	/// Replay the buffer, returning the messages.
+	fn replay(buffer: &mut DiskBuffer) -> Vec<Vec<u8>> {
+		replay_chunks(buffer).concat()
+	}
+
+	#[test]
+	fn buffers_messages_until_replayed() {
+		let dir = tempfile::tempdir().unwrap();
+		let mut buffer = DiskBuffer::new(&config(dir.path(), 1024), &addr()).unwrap();
+		assert!(replay(&mut buffer).is_empty());
+
+		buffer.push(br#"{"msg":"first"}"#);
+		buffer.push(br#"{"msg":"second"}"#);
+
+		assert_eq!(
+			replay(&mut buffer),
+			vec![br#"{"msg":"first"}"#.to_vec(), br#"{"msg":"second"}"#.to_vec()],
+		);
+		assert!(replay(&mut buffer).is_empty());
+	}
+
+	#[test]
+	fn drops_messages_once_full() {
+		let dir = tempfile::tempdir().unwrap();
+		// Room for two messages of 4 bytes and their newlines.
+		let mut buffer = DiskBuffer::new(&config(dir.path(), 10), &addr()).unwrap();
+
+		buffer.push(b"\"a\"");
+		buffer.push(b"\"b\"");
+		buffer.push(b"\"c\"");
+
+		assert_eq!(replay(&mut buffer), vec![b"\"a\"".to_vec(), b"\"b\"".to_vec()]);
+
+		// Replaying makes room again.
+		buffer.push(b"\"c\"");
+		assert_eq!(replay(&mut buffer), vec![b"\"c\"".to_vec()]);
+	}
+
+	#[test]
+	fn replays_messages_in_bounded_chunks() {
+		let dir = tempfile::tempdir().unwrap();
+		let mut buffer = DiskBuffer::new(&config(dir.path(), u64::MAX), &addr()).unwrap();
+
+		let message = vec![b'a'; 1000];
+		let count = 3 * REPLAY_CHUNK_BYTES / message.len();
+		for _ in 0..count {
+			buffer.push(&message);
+		}
+
+		let chunks = replay_chunks(&mut buffer);
+		assert!(chunks.len() >= 3);
+		for chunk in &chunks {
+			assert!(chunk.len() * message.len() < REPLAY_CHUNK_BYTES + message.len());
+		}
+		assert_eq!(chunks.concat(), vec![message; count]);
+	}
+
+	#[test]
+	fn keeps_messages_buffered_by_a_previous_run() {
+		let dir = tempfile::tempdir().unwrap();
+		let config = config(dir.path(), 1024);
+		fs::create_dir_all(&config.directory).unwrap();
+		fs::write(buffer_path(&config.directory, &addr()), b"\"a\"\n").unwrap();
+
+		let other: Multiaddr = "/ip4/127.0.0.1/tcp/8000".parse().unwrap();
+		assert!(replay(&mut DiskBuffer::new(&config, &other).unwrap()).is_empty());
+
+		let mut buffer = DiskBuffer::new(&config, &addr()).unwrap();
+		assert_eq!(replay(&mut buffer), vec![b"\"a\"".to_vec()]);
+	}
+}
@@ -16,13 +16,17 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use libp2p::Multiaddr;
+use libp2p::{multiaddr::Protocol, Multiaddr};
 use serde::{Deserialize, Deserializer, Serialize};
+use std::path::PathBuf;
 
 /// List of telemetry servers we want to talk to. Contains the URL of the server, and the
 /// maximum verbosity level.
 ///
-/// The URL string can be either a URL or a multiaddress.
+/// The URL string can be either a URL or a multiaddress. Telemetry is sent over WebSocket to
+/// `ws://` and `wss://` URLs and multiaddresses ending with `/ws` or `/wss`, and as
+/// newline-delimited JSON over plain TCP to other TCP multiaddresses. `file://` URLs append the
+/// newline-delimited JSON to the given file.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct TelemetryEndpoints(
 	#[serde(deserialize_with = "url_or_multiaddr_deser")] pub(crate) Vec<(Multiaddr, u8)>,
@@ -56,7 +60,7 @@ impl TelemetryEndpoints {
 }
 
 /// Parses a WebSocket URL into a libp2p `Multiaddr`.
-fn url_to_multiaddr(url: &str) -> Result<Multiaddr, libp2p::multiaddr::Error> {
+pub(crate) fn url_to_multiaddr(url: &str) -> Result<Multiaddr, libp2p::multiaddr::Error> {
 	// First, assume that we have a `Multiaddr`.
 	let parse_error = match url.parse() {
 		Ok(ma) => return Ok(ma),
@@ -68,15 +72,33 @@ fn url_to_multiaddr(url: &str) -> Result<Multiaddr, libp2p::multiaddr::Error> {
 		return Ok(ma)
 	}
 
+	// Files are addressed with a single `/unix` component holding the percent-encoded path, as
+	// a multiaddress component can't contain slashes.
+	if let Some(path) = url.strip_prefix("file://") {
+		let path = path.replace('%', "%25").replace('/', "%2F");
+		return Ok(Multiaddr::empty().with(Protocol::Unix(path.into())))
+	}
+
 	// If we have no clue about the format of that string, assume that we were expecting a
 	// `Multiaddr`.
 	Err(parse_error)
 }
 
+/// Returns the path of the file to append telemetry to, if `addr` addresses a file.
+pub(crate) fn file_path(addr: &Multiaddr) -> Option<PathBuf> {
+	let mut iter = addr.iter();
+	match (iter.next(), iter.next()) {
+		(Some(Protocol::Unix(path)), None) =>
+			Some(path.replace("%2F", "/").replace("%25", "%").into()),
+		_ => None,
+	}
+}
+
 #[cfg(test)]
 mod tests {
-	use super::{url_to_multiaddr, TelemetryEndpoints};
+	use super::{file_path, url_to_multiaddr, TelemetryEndpoints};
 	use libp2p::Multiaddr;
+	use std::path::PathBuf;
 
 	#[test]
 	fn valid_endpoints() {
@@ -93,6 +115,19 @@ mod tests {
 		assert_eq!(telem.0, res);
 	}
 
+	#[test]
+	fn file_endpoints() {
+		let addr = url_to_multiaddr("file:///var/log/telemetry%1.jsonl").unwrap();
+		assert_eq!(file_path(&addr), Some(PathBuf::from("/var/log/telemetry%1.jsonl")));
+
+		// The address survives a round trip through its string form, as used in chain specs.
+		let addr: Multiaddr = addr.to_string().parse().unwrap();
+		assert_eq!(file_path(&addr), Some(PathBuf::from("/var/log/telemetry%1.jsonl")));
+
+		let addr = url_to_multiaddr("/ip4/80.123.90.4/tcp/5432").unwrap();
+		assert_eq!(file_path(&addr), None);
+	}
+
 	#[test]
 	fn invalid_endpoints() {
 		let endp = vec![
@@ -53,11 +53,13 @@ use std::{
 pub use log;
 pub use serde_json;
 
+mod buffer;
 mod endpoints;
 mod error;
 mod node;
 mod transport;
 
+pub use buffer::DiskBufferConfig;
 pub use endpoints::*;
 pub use error::*;
 use node::*;
@@ -151,6 +153,7 @@ pub struct TelemetryWorker {
 	register_receiver: TracingUnboundedReceiver<Register>,
 	register_sender: TracingUnboundedSender<Register>,
 	id_counter: Arc<atomic::AtomicU64>,
+	disk_buffer: Option<DiskBufferConfig>,
 }
 
 impl TelemetryWorker {
@@ -173,9 +176,19 @@ impl TelemetryWorker {
 			register_receiver,
 			register_sender,
 			id_counter: Arc::new(atomic::AtomicU64::new(1)),
+			disk_buffer: None,
 		})
 	}
 
+	/// Buffer the telemetry messages on disk while a telemetry server can't be reached, instead
+	/// of discarding them.
+	///
+	/// The buffered messages are sent once the connection is (re-)established.
+	pub fn with_disk_buffer(mut self, config: DiskBufferConfig) -> Self {
+		self.disk_buffer = Some(config);
+		self
+	}
+
 	/// Get a new [`TelemetryWorkerHandle`].
 	///
 	/// This is used when you want to register with the [`TelemetryWorker`].
@@ -207,6 +220,7 @@ impl TelemetryWorker {
 					&mut node_pool,
 					&mut node_map,
 					&mut pending_connection_notifications,
+					self.disk_buffer.as_ref(),
 				).await,
 			}
 		}
@@ -217,6 +231,7 @@ impl TelemetryWorker {
 		node_pool: &mut HashMap<Multiaddr, Node<WsTrans>>,
 		node_map: &mut HashMap<Id, Vec<(VerbosityLevel, Multiaddr)>>,
 		pending_connection_notifications: &mut Vec<(Multiaddr, ConnectionNotifierSender)>,
+		disk_buffer: Option<&DiskBufferConfig>,
 	) {
 		let input = input.expect("the stream is never closed; qed");
 
@@ -268,7 +283,24 @@ impl TelemetryWorker {
 									continue
 								},
 							};
-							entry.insert(Node::new(transport, addr.clone(), Vec::new(), Vec::new()))
+							let disk_buffer = disk_buffer.and_then(|config| {
+								buffer::DiskBuffer::new(config, &addr)
+									.map_err(|err| {
+										log::error!(
+											target: "telemetry",
+											"Could not initialise disk buffer: {}",
+											err,
+										)
+									})
+									.ok()
+							});
+							entry.insert(Node::new(
+								transport,
+								addr.clone(),
+								Vec::new(),
+								Vec::new(),
+								disk_buffer,
+							))
 						},
 					};
 
@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::TelemetryPayload;
+use crate::{buffer::DiskBuffer, TelemetryPayload};
 use futures::{channel::mpsc, prelude::*};
 use libp2p::{core::transport::Transport, Multiaddr};
 use rand::Rng as _;
@@ -44,9 +44,10 @@ pub(crate) fn connection_notifier_channel() -> (ConnectionNotifierSender, Connec
 ///  - It holds a list of "connection messages" which are sent automatically when the connection is
 ///    (re-)established. This is used for the "system.connected" message that needs to be send for
 ///    every substrate node that connects.
-///  - It doesn't stay in pending while waiting for connection. Instead, it moves data into the void
-///    if the connection could not be established. This is important for the `Dispatcher` `Sink`
-///    which we don't want to block if one connection is broken.
+///  - It doesn't stay in pending while waiting for connection. Instead, it moves data into the
+///    void, or into the disk buffer if there is one, if the connection could not be established.
+///    This is important for the `Dispatcher` `Sink` which we don't want to block if one connection
+///    is broken. Buffered data is sent once the connection is (re-)established.
 #[derive(Debug)]
 pub(crate) struct Node<TTrans: Transport> {
 	/// Address of the node.
@@ -59,6 +60,8 @@ pub(crate) struct Node<TTrans: Transport> {
 	pub(crate) connection_messages: Vec<TelemetryPayload>,
 	/// Notifier for when the connection (re-)establishes.
 	pub(crate) telemetry_connection_notifier: Vec<ConnectionNotifierSender>,
+	/// Buffer for the messages sent while not connected.
+	disk_buffer: Option<DiskBuffer>,
 }
 
 enum NodeSocket<TTrans: Transport> {
@@ -97,6 +100,7 @@ impl<TTrans: Transport> Node<TTrans> {
 		addr: Multiaddr,
 		connection_messages: Vec<serde_json::Map<String, serde_json::Value>>,
 		telemetry_connection_notifier: Vec<ConnectionNotifierSender>,
+		disk_buffer: Option<DiskBuffer>,
 	) -> Self {
 		Node {
 			addr,
@@ -104,6 +108,7 @@ impl<TTrans: Transport> Node<TTrans> {
 			transport,
 			connection_messages,
 			telemetry_connection_notifier,
+			disk_buffer,
 		}
 	}
 }
@@ -156,8 +161,22 @@ where
 								socket = NodeSocket::wait_reconnect();
 							},
 							Poll::Ready(Ok(())) => {
-								self.socket = NodeSocket::Connected(conn);
-								return Poll::Ready(Ok(()))
+								// Send the messages replayed from the disk buffer, a chunk at a
+								// time, without waiting for them to be read.
+								let chunk = match self.disk_buffer.as_mut() {
+									Some(disk_buffer) => disk_buffer.poll_replayed(cx),
+									None => Poll::Pending,
+								};
+								match chunk {
+									Poll::Ready(Some(chunk)) if !chunk.is_empty() => {
+										conn.buf = chunk.into_iter().rev().collect();
+										socket = NodeSocket::Connected(conn);
+									},
+									_ => {
+										self.socket = NodeSocket::Connected(conn);
+										return Poll::Ready(Ok(()))
+									},
+								}
 							},
 							Poll::Pending => {
 								self.socket = NodeSocket::Connected(conn);
@@ -219,6 +238,10 @@ where
 							})
 							.collect();
 
+						if let Some(disk_buffer) = self.disk_buffer.as_mut() {
+							disk_buffer.replay();
+						}
+
 						socket = NodeSocket::Connected(NodeSocketConnected { sink, buf });
 					},
 					Poll::Pending => break NodeSocket::Dialing(s),
@@ -261,18 +284,22 @@ where
 	}
 
 	fn start_send(mut self: Pin<&mut Self>, item: TelemetryPayload) -> Result<(), Self::Error> {
-		// Any buffered outgoing telemetry messages are discarded while (re-)connecting.
-		match &mut self.socket {
-			NodeSocket::Connected(conn) => match serde_json::to_vec(&item) {
-				Ok(data) => {
-					log::trace!(target: "telemetry", "Sending {} bytes", data.len());
-					let _ = conn.sink.start_send_unpin(data);
-				},
-				Err(err) => log::debug!(
-					target: "telemetry",
-					"Could not serialize payload: {}",
-					err,
-				),
+		let data = match serde_json::to_vec(&item) {
+			Ok(data) => data,
+			Err(err) => {
+				log::debug!(target: "telemetry", "Could not serialize payload: {}", err);
+				return Ok(())
+			},
+		};
+
+		// Outgoing telemetry messages are buffered on disk, if enabled, or discarded while
+		// (re-)connecting.
+		let this = &mut *self;
+		match &mut this.socket {
+			NodeSocket::Connected(conn) => {
+				log::trace!(target: "telemetry", "Sending {} bytes", data.len());
+				let _ = conn.sink.start_send_unpin(data);
+				return Ok(())
 			},
 			// We are currently dialing the node.
 			NodeSocket::Dialing(_) => log::trace!(target: "telemetry", "Dialing"),
@@ -283,6 +310,10 @@ where
 			// Temporary transition state.
 			NodeSocket::Poisoned => log::trace!(target: "telemetry", "Poisoned"),
 		}
+
+		if let Some(disk_buffer) = &mut this.disk_buffer {
+			disk_buffer.push(&data);
+		}
 		Ok(())
 	}
 
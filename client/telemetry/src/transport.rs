@@ -16,20 +16,40 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::endpoints::file_path;
 use futures::{
+	channel::oneshot,
+	future::BoxFuture,
 	prelude::*,
 	ready,
 	task::{Context, Poll},
 };
-use libp2p::{core::transport::timeout::TransportTimeout, Transport};
-use std::{io, pin::Pin, time::Duration};
+use libp2p::{
+	core::transport::{timeout::TransportTimeout, ListenerId, TransportError, TransportEvent},
+	Multiaddr, Transport,
+};
+use std::{
+	fs,
+	io::{self, Write},
+	pin::Pin,
+	sync::mpsc::{self, SyncSender, TrySendError},
+	time::Duration,
+};
 
 /// Timeout after which a connection attempt is considered failed. Includes the WebSocket HTTP
 /// upgrading.
 const CONNECT_TIMEOUT: Duration = Duration::from_secs(20);
 
+/// Maximum number of messages waiting to be appended to a telemetry file. Further messages are
+/// dropped until the file accepts writes again.
+const MAX_QUEUED_FILE_WRITES: usize = 1024;
+
+/// Initializes the transport used to send telemetry.
+///
+/// Depending on the address, telemetry is sent over WebSocket, as newline-delimited JSON over plain
+/// TCP or appended as newline-delimited JSON to a file.
 pub(crate) fn initialize_transport() -> Result<WsTrans, io::Error> {
-	let transport = {
+	let ws_transport = {
 		let tcp_transport = libp2p::tcp::tokio::Transport::new(libp2p::tcp::Config::new());
 		let inner = libp2p::dns::TokioDnsConfig::system(tcp_transport)?;
 		libp2p::websocket::framed::WsConfig::new(inner).and_then(|connec, _| {
@@ -47,33 +67,174 @@ pub(crate) fn initialize_transport() -> Result<WsTrans, io::Error> {
 				});
 			future::ready(Ok::<_, io::Error>(connec))
 		})
-	};
-
-	Ok(TransportTimeout::new(
-		transport.map(|out, _| {
-			let out = out
-				.map_err(|err| io::Error::new(io::ErrorKind::Other, err))
-				.sink_map_err(|err| io::Error::new(io::ErrorKind::Other, err));
-			Box::pin(out) as Pin<Box<_>>
-		}),
-		CONNECT_TIMEOUT,
-	)
-	.boxed())
+	}
+	.map(|out, _| {
+		let out = out
+			.map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+			.sink_map_err(|err| io::Error::new(io::ErrorKind::Other, err));
+		Box::pin(out) as TelemetrySink
+	});
+
+	let tcp_transport = {
+		let tcp_transport = libp2p::tcp::tokio::Transport::new(libp2p::tcp::Config::new());
+		libp2p::dns::TokioDnsConfig::system(tcp_transport)?
+	}
+	.map(|connec, _| newline_delimited(StreamSink::from(connec)));
+
+	let file_transport = FileTransport.map(|file, _| newline_delimited(StreamSink::from(file)));
+
+	let transport = ws_transport.or_transport(tcp_transport).or_transport(file_transport).map(
+		|out, _| match out {
+			future::Either::Left(future::Either::Left(out)) |
+			future::Either::Left(future::Either::Right(out)) |
+			future::Either::Right(out) => out,
+		},
+	);
+
+	Ok(TransportTimeout::new(transport, CONNECT_TIMEOUT).boxed())
+}
+
+/// Terminates every message sent to `sink` with a newline.
+fn newline_delimited<T>(sink: StreamSink<T>) -> TelemetrySink
+where
+	T: AsyncRead + AsyncWrite + Send + 'static,
+{
+	let sink = sink.with(|mut item: Vec<u8>| {
+		item.push(b'\n');
+		future::ready(Ok::<_, io::Error>(item))
+	});
+	Box::pin(sink)
+}
+
+/// Transport appending telemetry to the file given by a `/unix` address, see
+/// [`TelemetryEndpoints`](crate::TelemetryEndpoints).
+///
+/// Writing to the file may block, so every connection appends to its file from a dedicated
+/// thread instead of the telemetry task.
+#[derive(Clone, Copy)]
+struct FileTransport;
+
+impl Transport for FileTransport {
+	type Output = FileWriter;
+	type Error = io::Error;
+	type ListenerUpgrade = future::Pending<Result<Self::Output, Self::Error>>;
+	type Dial = BoxFuture<'static, Result<Self::Output, Self::Error>>;
+
+	fn listen_on(&mut self, addr: Multiaddr) -> Result<ListenerId, TransportError<Self::Error>> {
+		Err(TransportError::MultiaddrNotSupported(addr))
+	}
+
+	fn remove_listener(&mut self, _id: ListenerId) -> bool {
+		false
+	}
+
+	fn dial(&mut self, addr: Multiaddr) -> Result<Self::Dial, TransportError<Self::Error>> {
+		let path = file_path(&addr).ok_or(TransportError::MultiaddrNotSupported(addr))?;
+		let (opened_sender, opened) = oneshot::channel();
+		let (writes, receiver) = mpsc::sync_channel::<Vec<u8>>(MAX_QUEUED_FILE_WRITES);
+		let spawned = std::thread::Builder::new().name("telemetry-file".into()).spawn(move || {
+			let mut file = match fs::OpenOptions::new().create(true).append(true).open(&path) {
+				Ok(file) => file,
+				Err(err) => {
+					let _ = opened_sender.send(Err(err));
+					return
+				},
+			};
+			let _ = opened_sender.send(Ok(()));
+
+			// Ends once the `FileWriter` is dropped.
+			for data in receiver {
+				if let Err(err) = file.write_all(&data) {
+					log::debug!(target: "telemetry", "Could not write to {:?}: {}", path, err);
+				}
+			}
+		});
+
+		Ok(async move {
+			spawned?;
+			opened.await.map_err(|_| {
+				io::Error::new(io::ErrorKind::Other, "Telemetry file thread ended unexpectedly")
+			})??;
+			Ok(FileWriter { writes })
+		}
+		.boxed())
+	}
+
+	fn dial_as_listener(
+		&mut self,
+		addr: Multiaddr,
+	) -> Result<Self::Dial, TransportError<Self::Error>> {
+		self.dial(addr)
+	}
+
+	fn poll(
+		self: Pin<&mut Self>,
+		_cx: &mut Context<'_>,
+	) -> Poll<TransportEvent<Self::ListenerUpgrade, Self::Error>> {
+		Poll::Pending
+	}
+
+	fn address_translation(&self, _listen: &Multiaddr, _observed: &Multiaddr) -> Option<Multiaddr> {
+		None
+	}
+}
+
+/// A connection to a telemetry file, handing the data to the thread appending it to the file.
+pub(crate) struct FileWriter {
+	writes: SyncSender<Vec<u8>>,
+}
+
+impl AsyncWrite for FileWriter {
+	fn poll_write(
+		self: Pin<&mut Self>,
+		_cx: &mut Context<'_>,
+		buf: &[u8],
+	) -> Poll<io::Result<usize>> {
+		match self.writes.try_send(buf.to_vec()) {
+			Ok(()) => Poll::Ready(Ok(buf.len())),
+			Err(TrySendError::Full(_)) => {
+				log::trace!(target: "telemetry", "Dropping telemetry, the file is not keeping up");
+				Poll::Ready(Ok(buf.len()))
+			},
+			Err(TrySendError::Disconnected(_)) => Poll::Ready(Err(io::Error::new(
+				io::ErrorKind::BrokenPipe,
+				"Telemetry file thread ended unexpectedly",
+			))),
+		}
+	}
+
+	fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+		Poll::Ready(Ok(()))
+	}
+
+	fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+		Poll::Ready(Ok(()))
+	}
+}
+
+impl AsyncRead for FileWriter {
+	/// Nothing is ever read back from a telemetry file.
+	fn poll_read(
+		self: Pin<&mut Self>,
+		_cx: &mut Context<'_>,
+		_buf: &mut [u8],
+	) -> Poll<io::Result<usize>> {
+		Poll::Pending
+	}
 }
 
 /// A trait that implements `Stream` and `Sink`.
 pub(crate) trait StreamAndSink<I>: Stream + Sink<I> {}
 impl<T: ?Sized + Stream + Sink<I>, I> StreamAndSink<I> for T {}
 
-/// A type alias for the WebSocket transport.
-pub(crate) type WsTrans = libp2p::core::transport::Boxed<
-	Pin<
-		Box<
-			dyn StreamAndSink<Vec<u8>, Item = Result<Vec<u8>, io::Error>, Error = io::Error> + Send,
-		>,
-	>,
+/// A connection to a telemetry server.
+pub(crate) type TelemetrySink = Pin<
+	Box<dyn StreamAndSink<Vec<u8>, Item = Result<Vec<u8>, io::Error>, Error = io::Error> + Send>,
 >;
 
+/// A type alias for the telemetry transport.
+pub(crate) type WsTrans = libp2p::core::transport::Boxed<TelemetrySink>;
+
 /// Wraps around an `AsyncWrite` and implements `Sink`. Guarantees that each item being sent maps
 /// to one call of `write`.
 #[pin_project::pin_project]
@@ -147,3 +308,32 @@ impl<T: AsyncWrite> Sink<Vec<u8>> for StreamSink<T> {
 		AsyncWrite::poll_close(this.0, cx)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::endpoints::url_to_multiaddr;
+	use futures::executor::block_on;
+	use std::time::Instant;
+
+	#[test]
+	fn file_transport_appends_messages_to_the_file() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("telemetry.jsonl");
+		let addr = url_to_multiaddr(&format!("file://{}", path.display())).unwrap();
+
+		let writer = block_on(FileTransport.dial(addr).unwrap()).unwrap();
+		let mut sink = newline_delimited(StreamSink::from(writer));
+		block_on(async {
+			sink.send(b"first".to_vec()).await.unwrap();
+			sink.send(b"second".to_vec()).await.unwrap();
+		});
+
+		// The messages are appended by the thread of the connection.
+		let started = Instant::now();
+		while fs::read(&path).unwrap_or_default() != b"first\nsecond\n" {
+			assert!(started.elapsed() < Duration::from_secs(10), "messages were not appended");
+			std::thread::sleep(Duration::from_millis(10));
+		}
+	}
+}
@@ -113,11 +113,13 @@ pub struct FutureTransactions<Hash: hash::Hash + Eq, Ex> {
 	wanted_tags: HashMap<Tag, HashSet<Hash>>,
 	/// Transactions waiting for a particular other transaction
 	waiting: HashMap<Hash, WaitingTransaction<Hash, Ex>>,
+	/// Sum of encoding lengths of all transactions in `waiting`
+	bytes: usize,
 }
 
 impl<Hash: hash::Hash + Eq, Ex> Default for FutureTransactions<Hash, Ex> {
 	fn default() -> Self {
-		Self { wanted_tags: Default::default(), waiting: Default::default() }
+		Self { wanted_tags: Default::default(), waiting: Default::default(), bytes: 0 }
 	}
 }
 
@@ -149,6 +151,7 @@ impl<Hash: hash::Hash + Eq + Clone, Ex> FutureTransactions<Hash, Ex> {
 		}
 
 		// Add the transaction to a by-hash waiting map
+		self.bytes += tx.transaction.bytes;
 		self.waiting.insert(tx.transaction.hash.clone(), tx);
 	}
 
@@ -186,6 +189,7 @@ impl<Hash: hash::Hash + Eq + Clone, Ex> FutureTransactions<Hash, Ex> {
 
 					if is_ready {
 						let tx = self.waiting.remove(&hash).expect(WAITING_PROOF);
+						self.bytes -= tx.transaction.bytes;
 						became_ready.push(tx);
 					}
 				}
@@ -202,6 +206,7 @@ impl<Hash: hash::Hash + Eq + Clone, Ex> FutureTransactions<Hash, Ex> {
 		let mut removed = vec![];
 		for hash in hashes {
 			if let Some(waiting_tx) = self.waiting.remove(hash) {
+				self.bytes -= waiting_tx.transaction.bytes;
 				// remove from wanted_tags as well
 				for tag in waiting_tx.missing_tags {
 					let remove = if let Some(wanted) = self.wanted_tags.get_mut(&tag) {
@@ -237,6 +242,7 @@ impl<Hash: hash::Hash + Eq + Clone, Ex> FutureTransactions<Hash, Ex> {
 	/// Removes and returns all future transactions.
 	pub fn clear(&mut self) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		self.wanted_tags.clear();
+		self.bytes = 0;
 		self.waiting.drain().map(|(_, tx)| tx.transaction).collect()
 	}
 
@@ -247,6 +253,49 @@ impl<Hash: hash::Hash + Eq + Clone, Ex> FutureTransactions<Hash, Ex> {
 
 	/// Returns sum of encoding lengths of all transactions in this queue.
 	pub fn bytes(&self) -> usize {
-		self.waiting.values().fold(0, |acc, tx| acc + tx.transaction.bytes)
+		self.bytes
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::transaction_validity::TransactionSource;
+
+	fn waiting(hash: u64, bytes: usize, requires: &[u8]) -> WaitingTransaction<u64, ()> {
+		WaitingTransaction {
+			transaction: Arc::new(Transaction {
+				data: (),
+				bytes,
+				hash,
+				priority: 1,
+				valid_till: 2,
+				requires: requires.iter().map(|tag| vec![*tag]).collect(),
+				provides: vec![],
+				propagate: true,
+				source: TransactionSource::External,
+			}),
+			missing_tags: requires.iter().map(|tag| vec![*tag]).collect(),
+			imported_at: Instant::now(),
+		}
+	}
+
+	#[test]
+	fn tracks_the_size_of_the_queue() {
+		let mut future = FutureTransactions::default();
+		future.import(waiting(1, 10, &[1]));
+		future.import(waiting(2, 20, &[1, 2]));
+		future.import(waiting(3, 40, &[3]));
+		assert_eq!(future.bytes(), 70);
+
+		// transaction 2 is still waiting for tag 2
+		assert_eq!(future.satisfy_tags(&[vec![1]]).len(), 1);
+		assert_eq!(future.bytes(), 60);
+
+		assert_eq!(future.remove(&[3, 4]).len(), 1);
+		assert_eq!(future.bytes(), 20);
+
+		assert_eq!(future.clear().len(), 1);
+		assert_eq!(future.bytes(), 0);
 	}
 }
@@ -18,7 +18,7 @@
 
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
-use crate::LOG_TARGET;
+use crate::{metrics::MetricsLink, LOG_TARGET};
 use futures::{channel::mpsc::Receiver, Future};
 use sc_transaction_pool_api::error;
 use sp_blockchain::TreeRoute;
@@ -151,6 +151,23 @@ impl<B: ChainApi> Pool<B> {
 		Self { validated_pool: Arc::new(ValidatedPool::new(options, is_validator, api)) }
 	}
 
+	/// Create a new transaction pool reporting to the given metrics.
+	pub(crate) fn new_with_metrics(
+		options: Options,
+		is_validator: IsValidator,
+		api: Arc<B>,
+		metrics: MetricsLink,
+	) -> Self {
+		Self {
+			validated_pool: Arc::new(ValidatedPool::new_with_metrics(
+				options,
+				is_validator,
+				api,
+				metrics,
+			)),
+		}
+	}
+
 	/// Imports a bunch of unverified extrinsics to the pool
 	pub async fn submit_at(
 		&self,
@@ -491,6 +508,53 @@ mod tests {
 		assert_eq!(pool.validated_pool().ready().map(|v| v.hash).collect::<Vec<_>>(), vec![hash]);
 	}
 
+	#[test]
+	fn should_report_status_and_dropped_transactions_to_metrics() {
+		// given
+		let registry = prometheus_endpoint::Registry::new();
+		let metrics = MetricsLink::new(Some(&registry));
+		let api = Arc::new(TestApi::default());
+		let pool =
+			Pool::new_with_metrics(Default::default(), true.into(), api.clone(), metrics.clone());
+		let hash_of_block0 = api.expect_hash_from_number(0);
+		let transfer = |nonce| {
+			uxt(Transfer {
+				from: Alice.into(),
+				to: AccountId::from_h256(H256::from_low_u64_be(2)),
+				amount: 5,
+				nonce,
+			})
+		};
+
+		// when
+		block_on(pool.submit_one(hash_of_block0, SOURCE, transfer(0))).unwrap();
+		let future = block_on(pool.submit_one(hash_of_block0, SOURCE, transfer(2))).unwrap();
+
+		// then
+		let status = pool.validated_pool().status();
+		assert_eq!((status.ready, status.future), (1, 1));
+		let mut reported = false;
+		metrics.report(|metrics| {
+			assert_eq!(metrics.ready_transactions.get(), 1);
+			assert_eq!(metrics.ready_transactions_bytes.get(), status.ready_bytes as u64);
+			assert_eq!(metrics.future_transactions.get(), 1);
+			assert_eq!(metrics.future_transactions_bytes.get(), status.future_bytes as u64);
+			reported = true;
+		});
+		assert!(reported);
+
+		// when
+		pool.validated_pool().remove_invalid(&[future]);
+
+		// then
+		metrics.report(|metrics| {
+			assert_eq!(metrics.ready_transactions.get(), 1);
+			assert_eq!(metrics.future_transactions.get(), 0);
+			assert_eq!(metrics.future_transactions_bytes.get(), 0);
+			assert_eq!(metrics.dropped_transactions.with_label_values(&["invalid"]).get(), 1);
+		});
+	}
+
 	#[test]
 	fn should_reject_if_temporarily_banned() {
 		// given
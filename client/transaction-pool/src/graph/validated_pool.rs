@@ -22,7 +22,10 @@ use std::{
 	sync::Arc,
 };
 
-use crate::LOG_TARGET;
+use crate::{
+	metrics::{DropReason, MetricsLink},
+	LOG_TARGET,
+};
 use futures::channel::mpsc::{channel, Sender};
 use parking_lot::{Mutex, RwLock};
 use sc_transaction_pool_api::{error, PoolStatus, ReadyTransactions};
@@ -109,11 +112,22 @@ pub struct ValidatedPool<B: ChainApi> {
 	pub(crate) pool: RwLock<base::BasePool<ExtrinsicHash<B>, ExtrinsicFor<B>>>,
 	import_notification_sinks: Mutex<Vec<Sender<ExtrinsicHash<B>>>>,
 	rotator: PoolRotator<ExtrinsicHash<B>>,
+	metrics: MetricsLink,
 }
 
 impl<B: ChainApi> ValidatedPool<B> {
 	/// Create a new transaction pool.
 	pub fn new(options: Options, is_validator: IsValidator, api: Arc<B>) -> Self {
+		Self::new_with_metrics(options, is_validator, api, Default::default())
+	}
+
+	/// Create a new transaction pool reporting to the given metrics.
+	pub(crate) fn new_with_metrics(
+		options: Options,
+		is_validator: IsValidator,
+		api: Arc<B>,
+		metrics: MetricsLink,
+	) -> Self {
 		let base_pool = base::BasePool::new(options.reject_future_transactions);
 		let ban_time = options.ban_time;
 		Self {
@@ -124,9 +138,20 @@ impl<B: ChainApi> ValidatedPool<B> {
 			pool: RwLock::new(base_pool),
 			import_notification_sinks: Default::default(),
 			rotator: PoolRotator::new(ban_time),
+			metrics,
 		}
 	}
 
+	/// Metrics this pool reports to.
+	pub(crate) fn metrics(&self) -> &MetricsLink {
+		&self.metrics
+	}
+
+	/// Update the pool size metrics.
+	fn report_status(&self) {
+		self.metrics.report(|metrics| metrics.report_status(&self.status()));
+	}
+
 	/// Bans given set of hashes.
 	pub fn ban(&self, now: &Instant, hashes: impl IntoIterator<Item = ExtrinsicHash<B>>) {
 		self.rotator.ban(now, hashes)
@@ -172,6 +197,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 		} else {
 			Default::default()
 		};
+		self.report_status();
 
 		results
 			.into_iter()
@@ -212,7 +238,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 				}
 
 				let mut listener = self.listener.write();
-				fire_events(&mut *listener, &imported);
+				fire_events(&mut *listener, &self.metrics, &imported);
 				Ok(*imported.hash())
 			},
 			ValidatedTransaction::Invalid(hash, err) => {
@@ -221,6 +247,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 			},
 			ValidatedTransaction::Unknown(hash, err) => {
 				self.listener.write().invalid(&hash);
+				self.metrics.report(|metrics| metrics.report_dropped(DropReason::Invalid, 1));
 				Err(err)
 			},
 		}
@@ -265,6 +292,8 @@ impl<B: ChainApi> ValidatedPool<B> {
 			for h in &removed {
 				listener.dropped(h, None);
 			}
+			self.metrics
+				.report(|metrics| metrics.report_dropped(DropReason::Limits, removed.len()));
 
 			removed
 		} else {
@@ -308,6 +337,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 			Ready,
 			Failed,
 			Dropped,
+			Usurped,
 		}
 
 		let (mut initial_statuses, final_statuses) = {
@@ -375,7 +405,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 										final_statuses.insert(hash, Status::Failed);
 									}
 									for tx in removed {
-										final_statuses.insert(tx.hash, Status::Dropped);
+										final_statuses.insert(tx.hash, Status::Usurped);
 									}
 								},
 								base::Imported::Future { .. } => {
@@ -420,14 +450,35 @@ impl<B: ChainApi> ValidatedPool<B> {
 		for (hash, final_status) in final_statuses {
 			let initial_status = initial_statuses.remove(&hash);
 			if initial_status.is_none() || Some(final_status) != initial_status {
-				match final_status {
-					Status::Future => listener.future(&hash),
-					Status::Ready => listener.ready(&hash, None),
-					Status::Dropped => listener.dropped(&hash, None),
-					Status::Failed => listener.invalid(&hash),
+				let dropped = match final_status {
+					Status::Future => {
+						listener.future(&hash);
+						None
+					},
+					Status::Ready => {
+						listener.ready(&hash, None);
+						None
+					},
+					Status::Dropped => {
+						listener.dropped(&hash, None);
+						Some(DropReason::Limits)
+					},
+					Status::Usurped => {
+						listener.dropped(&hash, None);
+						Some(DropReason::Usurped)
+					},
+					Status::Failed => {
+						listener.invalid(&hash);
+						Some(DropReason::Invalid)
+					},
+				};
+				if let Some(reason) = dropped {
+					self.metrics.report(|metrics| metrics.report_dropped(reason, 1));
 				}
 			}
 		}
+		drop(listener);
+		self.report_status();
 	}
 
 	/// For each extrinsic, returns tags that it provides (if known), or None (if it is unknown).
@@ -459,12 +510,15 @@ impl<B: ChainApi> ValidatedPool<B> {
 		{
 			let mut listener = self.listener.write();
 			for promoted in &status.promoted {
-				fire_events(&mut *listener, promoted);
+				fire_events(&mut *listener, &self.metrics, promoted);
 			}
 			for f in &status.failed {
 				listener.dropped(f, None);
 			}
 		}
+		self.metrics
+			.report(|metrics| metrics.report_dropped(DropReason::Pruned, status.failed.len()));
+		self.report_status();
 
 		Ok(status)
 	}
@@ -611,6 +665,10 @@ impl<B: ChainApi> ValidatedPool<B> {
 		for tx in &invalid {
 			listener.invalid(&tx.hash);
 		}
+		drop(listener);
+		self.metrics
+			.report(|metrics| metrics.report_dropped(DropReason::Invalid, invalid.len()));
+		self.report_status();
 
 		invalid
 	}
@@ -647,8 +705,11 @@ impl<B: ChainApi> ValidatedPool<B> {
 	}
 }
 
-fn fire_events<H, B, Ex>(listener: &mut Listener<H, B>, imported: &base::Imported<H, Ex>)
-where
+fn fire_events<H, B, Ex>(
+	listener: &mut Listener<H, B>,
+	metrics: &MetricsLink,
+	imported: &base::Imported<H, Ex>,
+) where
 	H: hash::Hash + Eq + traits::Member + Serialize,
 	B: ChainApi,
 {
@@ -658,6 +719,10 @@ where
 			failed.iter().for_each(|f| listener.invalid(f));
 			removed.iter().for_each(|r| listener.dropped(&r.hash, Some(hash)));
 			promoted.iter().for_each(|p| listener.ready(p, None));
+			metrics.report(|metrics| {
+				metrics.report_dropped(DropReason::Invalid, failed.len());
+				metrics.report_dropped(DropReason::Usurped, removed.len());
+			});
 		},
 		base::Imported::Future { ref hash } => listener.future(hash),
 	}
@@ -20,7 +20,11 @@
 
 use std::sync::Arc;
 
-use prometheus_endpoint::{register, Counter, PrometheusError, Registry, U64};
+use prometheus_endpoint::{
+	exponential_buckets, register, Counter, CounterVec, Gauge, Histogram, HistogramOpts, Opts,
+	PrometheusError, Registry, U64,
+};
+use sc_transaction_pool_api::PoolStatus;
 
 #[derive(Clone, Default)]
 pub struct MetricsLink(Arc<Option<Metrics>>);
@@ -43,12 +47,43 @@ impl MetricsLink {
 	}
 }
 
+/// Reason for a transaction leaving the pool without being included in a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropReason {
+	/// The pool limits were exceeded.
+	Limits,
+	/// Replaced by another transaction providing the same tags.
+	Usurped,
+	/// Found to be invalid, either on import or during revalidation.
+	Invalid,
+	/// Could not be re-imported after its dependencies were pruned.
+	Pruned,
+}
+
+impl DropReason {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Self::Limits => "limits",
+			Self::Usurped => "usurped",
+			Self::Invalid => "invalid",
+			Self::Pruned => "pruned",
+		}
+	}
+}
+
 /// Transaction pool Prometheus metrics.
 pub struct Metrics {
 	pub submitted_transactions: Counter<U64>,
 	pub validations_invalid: Counter<U64>,
 	pub block_transactions_pruned: Counter<U64>,
 	pub block_transactions_resubmitted: Counter<U64>,
+	pub dropped_transactions: CounterVec<U64>,
+	pub ready_transactions: Gauge<U64>,
+	pub ready_transactions_bytes: Gauge<U64>,
+	pub future_transactions: Gauge<U64>,
+	pub future_transactions_bytes: Gauge<U64>,
+	pub revalidation_queue_length: Gauge<U64>,
+	pub revalidation_duration: Histogram,
 }
 
 impl Metrics {
@@ -82,8 +117,80 @@ impl Metrics {
 				)?,
 				registry,
 			)?,
+			dropped_transactions: register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_sub_txpool_dropped_transactions",
+						"Total number of transactions dropped from the pool, by reason",
+					),
+					&["reason"],
+				)?,
+				registry,
+			)?,
+			ready_transactions: register(
+				Gauge::new(
+					"substrate_sub_txpool_ready_transactions",
+					"Number of transactions in the ready queue",
+				)?,
+				registry,
+			)?,
+			ready_transactions_bytes: register(
+				Gauge::new(
+					"substrate_sub_txpool_ready_transactions_bytes",
+					"Total encoded size of transactions in the ready queue",
+				)?,
+				registry,
+			)?,
+			future_transactions: register(
+				Gauge::new(
+					"substrate_sub_txpool_future_transactions",
+					"Number of transactions in the future queue",
+				)?,
+				registry,
+			)?,
+			future_transactions_bytes: register(
+				Gauge::new(
+					"substrate_sub_txpool_future_transactions_bytes",
+					"Total encoded size of transactions in the future queue",
+				)?,
+				registry,
+			)?,
+			revalidation_queue_length: register(
+				Gauge::new(
+					"substrate_sub_txpool_revalidation_queue_length",
+					"Number of transactions waiting for background revalidation",
+				)?,
+				registry,
+			)?,
+			revalidation_duration: register(
+				Histogram::with_opts(
+					HistogramOpts::new(
+						"substrate_sub_txpool_revalidation_duration_seconds",
+						"Time spent revalidating a single batch of transactions",
+					)
+					.buckets(exponential_buckets(0.001, 4.0, 9)?),
+				)?,
+				registry,
+			)?,
 		})
 	}
+
+	/// Report transactions dropped for the given reason.
+	pub fn report_dropped(&self, reason: DropReason, count: usize) {
+		if count > 0 {
+			self.dropped_transactions
+				.with_label_values(&[reason.as_str()])
+				.inc_by(count as u64);
+		}
+	}
+
+	/// Update the pool size gauges from the given status.
+	pub fn report_status(&self, status: &PoolStatus) {
+		self.ready_transactions.set(status.ready as u64);
+		self.ready_transactions_bytes.set(status.ready_bytes as u64);
+		self.future_transactions.set(status.future as u64);
+		self.future_transactions_bytes.set(status.future_bytes as u64);
+	}
 }
 
 /// Transaction pool api Prometheus metrics.
@@ -34,7 +34,7 @@ use sp_runtime::{
 };
 
 use futures::prelude::*;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 const BACKGROUND_REVALIDATION_INTERVAL: Duration = Duration::from_millis(200);
 
@@ -196,6 +196,14 @@ impl<Api: ChainApi> RevalidationWorker<Api> {
 		queued_exts
 	}
 
+	fn report_queue_length(&self) {
+		let len = self.members.len();
+		self.pool
+			.validated_pool()
+			.metrics()
+			.report(|metrics| metrics.revalidation_queue_length.set(len as u64));
+	}
+
 	fn len(&self) -> usize {
 		self.block_ordered.iter().map(|b| b.1.len()).sum()
 	}
@@ -252,9 +260,17 @@ impl<Api: ChainApi> RevalidationWorker<Api> {
 				_ = (&mut interval_fut).fuse() => {
 					let next_batch = this.prepare_batch();
 					let batch_len = next_batch.len();
+					let started = Instant::now();
 
 					batch_revalidate(this.pool.clone(), this.api.clone(), this.best_block, next_batch).await;
 
+					this.report_queue_length();
+					if batch_len > 0 {
+						this.pool.validated_pool().metrics().report(|metrics| {
+							metrics.revalidation_duration.observe(started.elapsed().as_secs_f64())
+						});
+					}
+
 					if batch_len > 0 || this.len() > 0 {
 						log::debug!(
 							target: LOG_TARGET,
@@ -271,6 +287,7 @@ impl<Api: ChainApi> RevalidationWorker<Api> {
 						Some(worker_payload) => {
 							this.best_block = worker_payload.at;
 							this.push(worker_payload);
+							this.report_queue_length();
 
 							if this.members.len() > 0 {
 								log::debug!(
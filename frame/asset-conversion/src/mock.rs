@@ -92,6 +92,7 @@ impl pallet_assets::Config<Instance1> for Test {
 	type MetadataDepositPerByte = ConstU128<1>;
 	type ApprovalDeposit = ConstU128<1>;
 	type StringLimit = ConstU32<50>;
+	type MaxBatch = ConstU32<10>;
 	type Freezer = ();
 	type Extra = ();
 	type WeightInfo = ();
@@ -117,6 +118,7 @@ impl pallet_assets::Config<Instance2> for Test {
 	type MetadataDepositPerByte = ConstU128<0>;
 	type ApprovalDeposit = ConstU128<0>;
 	type StringLimit = ConstU32<50>;
+	type MaxBatch = ConstU32<10>;
 	type Freezer = ();
 	type Extra = ();
 	type WeightInfo = ();
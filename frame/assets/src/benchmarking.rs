@@ -553,5 +553,29 @@ benchmarks_instance_pallet! {
 		assert_last_event::<T, I>(Event::Blocked { asset_id: asset_id.into(), who: caller }.into());
 	}
 
+	transfer_approved_batch {
+		let n in 1 .. T::MaxBatch::get();
+		let (asset_id, owner, owner_lookup) = create_default_minted_asset::<T, I>(true, (100 * n).into());
+		T::Currency::make_free_balance_be(&owner, DepositBalanceOf::<T, I>::max_value());
+
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		whitelist_account!(delegate);
+		let delegate_lookup = T::Lookup::unlookup(delegate.clone());
+		let amount: T::Balance = 100u32.into();
+		let origin = SystemOrigin::Signed(owner.clone()).into();
+		Assets::<T, I>::approve_transfer(origin, asset_id.clone(), delegate_lookup, amount * n.into())?;
+
+		let transfers: BoundedVec<_, T::MaxBatch> = (0..n)
+			.map(|i| (T::Lookup::unlookup(account("dest", i, SEED)), amount))
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("n is at most MaxBatch; qed");
+	}: _(SystemOrigin::Signed(delegate.clone()), asset_id.clone(), owner_lookup, transfers)
+	verify {
+		assert!(T::Currency::reserved_balance(&owner).is_zero());
+		let dest: T::AccountId = account("dest", n - 1, SEED);
+		assert_last_event::<T, I>(Event::Transferred { asset_id: asset_id.into(), from: owner, to: dest, amount }.into());
+	}
+
 	impl_benchmark_test_suite!(Assets, crate::mock::new_test_ext(), crate::mock::Test)
 }
@@ -302,6 +302,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type StringLimit: Get<u32>;
 
+		/// The maximum number of transfers in a single `transfer_approved_batch` call.
+		#[pallet::constant]
+		type MaxBatch: Get<u32>;
+
 		/// A hook to allow a per-asset, per-account minimum balance to be enforced. This must be
 		/// respected in all permissionless operations.
 		type Freezer: FrozenBalance<Self::AssetId, Self::AccountId, Self::Balance>;
@@ -1632,6 +1636,40 @@ pub mod pallet {
 			Self::deposit_event(Event::<T, I>::Blocked { asset_id: id, who });
 			Ok(())
 		}
+
+		/// Transfer some asset balance from a previously delegated account to a number of
+		/// third-party accounts.
+		///
+		/// Origin must be Signed and there must be an approval in place by the `owner` to the
+		/// signer covering the sum of all `transfers`. Either all transfers succeed or none do.
+		///
+		/// - `id`: The identifier of the asset.
+		/// - `owner`: The account which previously approved the transfers and from which the asset
+		/// balance will be withdrawn.
+		/// - `transfers`: The destination accounts and the amounts to be transferred to each.
+		///
+		/// Emits `Transferred` for each transfer on success.
+		///
+		/// Weight: `O(T)` where `T` is the number of transfers, at most `MaxBatch`.
+		#[pallet::call_index(32)]
+		#[pallet::weight(
+			T::WeightInfo::transfer_approved().saturating_mul(transfers.len() as u64)
+		)]
+		pub fn transfer_approved_batch(
+			origin: OriginFor<T>,
+			id: T::AssetIdParameter,
+			owner: AccountIdLookupOf<T>,
+			transfers: BoundedVec<(AccountIdLookupOf<T>, T::Balance), T::MaxBatch>,
+		) -> DispatchResult {
+			let delegate = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			let id: T::AssetId = id.into();
+			for (destination, amount) in transfers {
+				let destination = T::Lookup::lookup(destination)?;
+				Self::do_transfer_approved(id.clone(), &owner, &delegate, &destination, amount)?;
+			}
+			Ok(())
+		}
 	}
 
 	/// Implements [`AccountTouch`] trait.
@@ -122,6 +122,7 @@ impl Config for Test {
 	type MetadataDepositPerByte = ConstU64<1>;
 	type ApprovalDeposit = ConstU64<1>;
 	type StringLimit = ConstU32<50>;
+	type MaxBatch = ConstU32<3>;
 	type Freezer = TestFreezer;
 	type WeightInfo = ();
 	type CallbackHandle = AssetsCallbackHandle;
@@ -26,7 +26,7 @@ use frame_support::{
 };
 use pallet_balances::Error as BalancesError;
 use sp_io::storage;
-use sp_runtime::{traits::ConvertInto, TokenError};
+use sp_runtime::{bounded_vec, traits::ConvertInto, TokenError};
 
 mod sets;
 
@@ -354,6 +354,53 @@ fn transfer_approved_all_funds() {
 	});
 }
 
+#[test]
+fn transfer_approved_batch_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 0, 1, true, 1));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(1), 0, 1, 100));
+		Balances::make_free_balance_be(&1, 2);
+		assert_ok!(Assets::approve_transfer(RuntimeOrigin::signed(1), 0, 2, 50));
+
+		// exceeding the approval in total reverts every transfer of the batch
+		assert_noop!(
+			Assets::transfer_approved_batch(
+				RuntimeOrigin::signed(2),
+				0,
+				1,
+				bounded_vec![(3, 30), (4, 30)]
+			),
+			Error::<Test>::Unapproved
+		);
+		assert_noop!(
+			Assets::transfer_approved_batch(RuntimeOrigin::signed(3), 0, 1, bounded_vec![(3, 10)]),
+			Error::<Test>::Unapproved
+		);
+
+		assert_ok!(Assets::transfer_approved_batch(
+			RuntimeOrigin::signed(2),
+			0,
+			1,
+			bounded_vec![(3, 20), (4, 10)]
+		));
+		assert_eq!(Assets::balance(0, 1), 70);
+		assert_eq!(Assets::balance(0, 3), 20);
+		assert_eq!(Assets::balance(0, 4), 10);
+		assert_eq!(Approvals::<Test>::get((0, 1, 2)).unwrap().amount, 20);
+
+		// using up the rest of the approval cleans it up
+		assert_ok!(Assets::transfer_approved_batch(
+			RuntimeOrigin::signed(2),
+			0,
+			1,
+			bounded_vec![(4, 20)]
+		));
+		assert_eq!(Asset::<Test>::get(0).unwrap().approvals, 0);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Assets::balance(0, 4), 30);
+	});
+}
+
 #[test]
 fn approval_deposits_work() {
 	new_test_ext().execute_with(|| {
@@ -1742,6 +1789,14 @@ fn weights_sane() {
 
 	let info = crate::Call::<Test>::finish_destroy { id: 10 }.get_dispatch_info();
 	assert_eq!(<() as crate::WeightInfo>::finish_destroy(), info.weight);
+
+	let info = crate::Call::<Test>::transfer_approved_batch {
+		id: 10,
+		owner: 1,
+		transfers: bounded_vec![(2, 1), (3, 1), (4, 1)],
+	}
+	.get_dispatch_info();
+	assert_eq!(<() as crate::WeightInfo>::transfer_approved().saturating_mul(3), info.weight);
 }
 
 #[test]
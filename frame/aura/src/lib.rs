@@ -24,6 +24,11 @@
 //!
 //! The Aura module extends Aura consensus by managing offline reporting.
 //!
+//! When used as a session handler, the authority set follows the validators of each session, and
+//! equivocations (authoring two blocks in the same slot) can be reported with
+//! `report_equivocation`. See the [`equivocation`] module for wiring this up with the offences
+//! pallet.
+//!
 //! ## Interface
 //!
 //! ### Public Functions
@@ -40,26 +45,38 @@
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
+	dispatch::{DispatchResultWithPostInfo, Pays},
 	traits::{DisabledValidators, FindAuthor, Get, OnTimestampSet, OneSessionHandler},
+	weights::Weight,
 	BoundedSlice, BoundedVec, ConsensusEngineId, Parameter,
 };
+use frame_system::pallet_prelude::HeaderFor;
 use log;
-use sp_consensus_aura::{AuthorityIndex, ConsensusLog, Slot, AURA_ENGINE_ID};
+use sp_consensus_aura::{AuthorityIndex, ConsensusLog, EquivocationProof, Slot, AURA_ENGINE_ID};
 use sp_runtime::{
 	generic::DigestItem,
 	traits::{IsMember, Member, SaturatedConversion, Saturating, Zero},
 	RuntimeAppPublic,
 };
+use sp_session::{GetSessionNumber, GetValidatorCount};
+use sp_staking::{offence::OffenceReportSystem, SessionIndex};
 use sp_std::prelude::*;
 
+mod default_weights;
+pub mod equivocation;
 pub mod migrations;
 mod mock;
 mod tests;
 
+pub use equivocation::{EquivocationOffence, EquivocationReportSystem};
 pub use pallet::*;
 
 const LOG_TARGET: &str = "runtime::aura";
 
+pub trait WeightInfo {
+	fn report_equivocation(validator_count: u32, max_nominators_per_validator: u32) -> Weight;
+}
+
 /// A slot duration provider which infers the slot duration from the
 /// [`pallet_timestamp::Config::MinimumPeriod`] by multiplying it by two, to ensure
 /// that authors have the majority of their slot to author within.
@@ -77,6 +94,36 @@ impl<T: pallet_timestamp::Config> Get<T::Moment> for MinimumPeriodTimesTwo<T> {
 	}
 }
 
+/// An expected timestamp provider which returns the start of the slot of the block, to be used
+/// as [`pallet_timestamp::Config::ExpectedTimestamp`].
+///
+/// When executing a block, returns `None` until the slot of the first block is known.
+pub struct SlotStartTimestamp<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> SlotStartTimestamp<T> {
+	fn slot_start(slot: Slot) -> T::Moment {
+		Pallet::<T>::slot_duration().saturating_mul((*slot).saturated_into())
+	}
+}
+
+impl<T: Config> pallet_timestamp::ProvideExpectedTimestamp<T::Moment> for SlotStartTimestamp<T> {
+	fn expected_timestamp() -> Option<T::Moment> {
+		let slot = Pallet::<T>::current_slot();
+		(*slot != 0).then(|| Self::slot_start(slot))
+	}
+
+	fn expected_timestamp_from_inherent_data(
+		data: &frame_support::inherent::InherentData,
+	) -> Option<T::Moment> {
+		use sp_consensus_aura::inherents::{InherentType, INHERENT_IDENTIFIER};
+
+		data.get_data::<InherentType>(&INHERENT_IDENTIFIER)
+			.ok()
+			.flatten()
+			.map(Self::slot_start)
+	}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -113,6 +160,35 @@ pub mod pallet {
 		/// using the same slot.
 		type AllowMultipleBlocksPerSlot: Get<bool>;
 
+		/// Helper for weights computations
+		type WeightInfo: WeightInfo;
+
+		/// The maximum number of nominators for each validator.
+		#[pallet::constant]
+		type MaxNominators: Get<u32>;
+
+		/// The maximum number of entries to keep in the session index to start slot mapping.
+		///
+		/// Since the `SessionStartSlot` map is only used for validating equivocations this
+		/// value should relate to the bonding duration of whatever staking system is
+		/// being used (if any). If equivocation handling is not enabled then this value
+		/// can be zero.
+		#[pallet::constant]
+		type MaxSessionEntries: Get<u32>;
+
+		/// The proof of key ownership, used for validating equivocation reports.
+		/// The proof must include the session index and validator count of the
+		/// session at which the equivocation occurred.
+		type KeyOwnerProof: Parameter + GetSessionNumber + GetValidatorCount;
+
+		/// The equivocation handling subsystem, defines methods to check/report an
+		/// offence and for submitting a transaction to report an equivocation
+		/// (from an offchain context).
+		type EquivocationReportSystem: OffenceReportSystem<
+			Option<Self::AccountId>,
+			(EquivocationProof<HeaderFor<Self>, Self::AuthorityId>, Self::KeyOwnerProof),
+		>;
+
 		/// The slot duration Aura should run with, expressed in milliseconds.
 		/// The effective value of this type should not change while the chain is running.
 		///
@@ -124,6 +200,16 @@ pub mod pallet {
 		type SlotDuration: Get<<Self as pallet_timestamp::Config>::Moment>;
 	}
 
+	#[pallet::error]
+	pub enum Error<T> {
+		/// An equivocation proof provided as part of an equivocation report is invalid.
+		InvalidEquivocationProof,
+		/// A key ownership proof provided as part of an equivocation report is invalid.
+		InvalidKeyOwnershipProof,
+		/// A given equivocation report is valid but already previously reported.
+		DuplicateOffenceReport,
+	}
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(sp_std::marker::PhantomData<T>);
 
@@ -179,6 +265,13 @@ pub mod pallet {
 	#[pallet::getter(fn current_slot)]
 	pub(super) type CurrentSlot<T: Config> = StorageValue<_, Slot, ValueQuery>;
 
+	/// A mapping from session index to the first slot of that session.
+	///
+	/// This is only used for validating equivocation proofs, and only kept for the last
+	/// `MaxSessionEntries` sessions.
+	#[pallet::storage]
+	pub(super) type SessionStartSlot<T: Config> = StorageMap<_, Twox64Concat, SessionIndex, Slot>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -191,6 +284,70 @@ pub mod pallet {
 			Pallet::<T>::initialize_authorities(&self.authorities);
 		}
 	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Report authority equivocation/misbehavior. This method will verify
+		/// the equivocation proof and validate the given key ownership proof
+		/// against the extracted offender. If both are valid, the offence will
+		/// be reported.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::report_equivocation(
+			key_owner_proof.validator_count(),
+			T::MaxNominators::get(),
+		))]
+		pub fn report_equivocation(
+			origin: OriginFor<T>,
+			equivocation_proof: Box<EquivocationProof<HeaderFor<T>, T::AuthorityId>>,
+			key_owner_proof: T::KeyOwnerProof,
+		) -> DispatchResultWithPostInfo {
+			let reporter = ensure_signed(origin)?;
+			T::EquivocationReportSystem::process_evidence(
+				Some(reporter),
+				(*equivocation_proof, key_owner_proof),
+			)?;
+			// Waive the fee since the report is valid and beneficial
+			Ok(Pays::No.into())
+		}
+
+		/// Report authority equivocation/misbehavior. This method will verify
+		/// the equivocation proof and validate the given key ownership proof
+		/// against the extracted offender. If both are valid, the offence will
+		/// be reported.
+		/// This extrinsic must be called unsigned and it is expected that only
+		/// block authors will call it (validated in `ValidateUnsigned`), as such
+		/// if the block author is defined it will be defined as the equivocation
+		/// reporter.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::report_equivocation(
+			key_owner_proof.validator_count(),
+			T::MaxNominators::get(),
+		))]
+		pub fn report_equivocation_unsigned(
+			origin: OriginFor<T>,
+			equivocation_proof: Box<EquivocationProof<HeaderFor<T>, T::AuthorityId>>,
+			key_owner_proof: T::KeyOwnerProof,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			T::EquivocationReportSystem::process_evidence(
+				None,
+				(*equivocation_proof, key_owner_proof),
+			)?;
+			Ok(Pays::No.into())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+		fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			Self::validate_unsigned(source, call)
+		}
+
+		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+			Self::pre_dispatch(call)
+		}
+	}
 }
 
 impl<T: Config> Pallet<T> {
@@ -248,6 +405,29 @@ impl<T: Config> Pallet<T> {
 		None
 	}
 
+	/// Whether `slot` belongs to the session `session_index`, as far as the pallet
+	/// still knows about that session.
+	pub(crate) fn is_slot_in_session(slot: Slot, session_index: SessionIndex) -> bool {
+		let start = match SessionStartSlot::<T>::get(session_index) {
+			Some(start) => start,
+			None => return false,
+		};
+		let end = SessionStartSlot::<T>::get(session_index.saturating_add(1));
+
+		start <= slot && end.map_or(true, |end| slot < end)
+	}
+
+	/// Submits an extrinsic to report an equivocation. This method will create
+	/// an unsigned extrinsic with a call to `report_equivocation_unsigned` and
+	/// will push the transaction to the pool. Only useful in an offchain
+	/// context.
+	pub fn submit_unsigned_equivocation_report(
+		equivocation_proof: EquivocationProof<HeaderFor<T>, T::AuthorityId>,
+		key_owner_proof: T::KeyOwnerProof,
+	) -> Option<()> {
+		T::EquivocationReportSystem::publish_evidence((equivocation_proof, key_owner_proof)).ok()
+	}
+
 	/// Determine the Aura slot-duration based on the Timestamp module configuration.
 	pub fn slot_duration() -> T::Moment {
 		#[cfg(feature = "experimental")]
@@ -317,7 +497,10 @@ impl<T: Config> sp_runtime::BoundToRuntimeAppPublic for Pallet<T> {
 	type Public = T::AuthorityId;
 }
 
-impl<T: Config> OneSessionHandler<T::AccountId> for Pallet<T> {
+impl<T: Config> OneSessionHandler<T::AccountId> for Pallet<T>
+where
+	T: pallet_session::Config,
+{
 	type Key = T::AuthorityId;
 
 	fn on_genesis_session<'a, I: 'a>(validators: I)
@@ -326,6 +509,7 @@ impl<T: Config> OneSessionHandler<T::AccountId> for Pallet<T> {
 	{
 		let authorities = validators.map(|(_, k)| k).collect::<Vec<_>>();
 		Self::initialize_authorities(&authorities);
+		SessionStartSlot::<T>::insert(0, Slot::from(0));
 	}
 
 	fn on_new_session<'a, I: 'a>(changed: bool, validators: I, _queued_validators: I)
@@ -348,6 +532,18 @@ impl<T: Config> OneSessionHandler<T::AccountId> for Pallet<T> {
 				Self::change_authorities(bounded);
 			}
 		}
+
+		// note the first slot of the new session. this block has been authored by the previous
+		// authorities, so the new session starts with the next slot. the session pallet may be
+		// initialized before this pallet, hence the slot is taken from the digest.
+		let session_index = <pallet_session::Pallet<T>>::current_index();
+		let slot = Self::current_slot_from_digests().unwrap_or_else(|| CurrentSlot::<T>::get());
+		SessionStartSlot::<T>::insert(session_index, slot.saturating_add(1u64));
+
+		let max_session_entries = T::MaxSessionEntries::get().max(1);
+		if session_index >= max_session_entries {
+			SessionStartSlot::<T>::remove(session_index - max_session_entries);
+		}
 	}
 
 	fn on_disabled(i: u32) {
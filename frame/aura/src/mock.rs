@@ -19,13 +19,28 @@
 
 #![cfg(test)]
 
-use crate as pallet_aura;
+use crate::{self as pallet_aura, EquivocationOffence};
+use codec::Encode;
 use frame_support::{
 	derive_impl, parameter_types,
-	traits::{ConstU32, ConstU64, DisabledValidators},
+	traits::{ConstU32, ConstU64, DisabledValidators, KeyOwnerProofSystem, OnInitialize},
+};
+use pallet_session::historical::{self as pallet_session_historical, IdentificationTuple};
+use sp_consensus_aura::{
+	ed25519::{AuthorityId, AuthorityPair},
+	AuthorityIndex, Slot, AURA_ENGINE_ID,
+};
+use sp_core::{crypto::KeyTypeId, Pair};
+use sp_runtime::{
+	impl_opaque_keys,
+	testing::{Digest, DigestItem, Header, TestXt, UintAuthorityId},
+	traits::{ConvertInto, Header as _, OpaqueKeys},
+	BuildStorage,
+};
+use sp_staking::{
+	offence::{Offence, OffenceError, ReportOffence},
+	SessionIndex,
 };
-use sp_consensus_aura::{ed25519::AuthorityId, AuthorityIndex};
-use sp_runtime::{testing::UintAuthorityId, BuildStorage};
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -37,6 +52,9 @@ frame_support::construct_runtime!(
 		System: frame_system,
 		Timestamp: pallet_timestamp,
 		Aura: pallet_aura,
+		Authorship: pallet_authorship,
+		Session: pallet_session,
+		Historical: pallet_session_historical,
 	}
 );
 
@@ -45,10 +63,21 @@ impl frame_system::Config for Test {
 	type Block = Block;
 }
 
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+	RuntimeCall: From<C>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = TestXt<RuntimeCall, ()>;
+}
+
 impl pallet_timestamp::Config for Test {
 	type Moment = u64;
 	type OnTimestampSet = Aura;
 	type MinimumPeriod = ConstU64<{ SLOT_DURATION / 2 }>;
+	type ExpectedTimestamp = ();
+	type MaxTimestampDrift = ();
+	type OnTimestampDrift = ();
 	type WeightInfo = ();
 }
 
@@ -79,11 +108,95 @@ impl DisabledValidators for MockDisabledValidators {
 	}
 }
 
+impl_opaque_keys! {
+	pub struct MockSessionKeys {
+		pub aura: Aura,
+	}
+}
+
+/// The number of blocks in a session.
+pub const SESSION_LENGTH: u64 = 3;
+
+impl pallet_session::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ValidatorId = u64;
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = pallet_session::PeriodicSessions<ConstU64<SESSION_LENGTH>, ConstU64<0>>;
+	type NextSessionRotation =
+		pallet_session::PeriodicSessions<ConstU64<SESSION_LENGTH>, ConstU64<0>>;
+	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Self, TestSessionManager>;
+	type SessionHandler = <MockSessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = MockSessionKeys;
+	type InactiveKeysExpiry = ();
+	type ValidatorIntent = ();
+	type MaxKeysPurgedPerSession = ConstU32<100>;
+	type WeightInfo = ();
+}
+
+impl pallet_session::historical::Config for Test {
+	type FullIdentification = u64;
+	type FullIdentificationOf = ConvertInto;
+}
+
+impl pallet_authorship::Config for Test {
+	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
+	type EventHandler = ();
+}
+
+parameter_types! {
+	pub static SessionValidators: Vec<u64> = Vec::new();
+	pub static ReportedOffences: Vec<(Vec<u64>, IdentificationTuple<Test>, Slot, SessionIndex)> =
+		Vec::new();
+}
+
+/// Keeps the validators of `SessionValidators` in every session.
+pub struct TestSessionManager;
+
+impl pallet_session::historical::SessionManager<u64, u64> for TestSessionManager {
+	fn end_session(_: SessionIndex) {}
+	fn start_session(_: SessionIndex) {}
+	fn new_session(_: SessionIndex) -> Option<Vec<(u64, u64)>> {
+		Some(SessionValidators::get().into_iter().map(|v| (v, v)).collect())
+	}
+}
+
+/// Records the reported offences in `ReportedOffences`.
+pub struct OffenceHandler;
+
+impl ReportOffence<u64, IdentificationTuple<Test>, EquivocationOffence<IdentificationTuple<Test>>>
+	for OffenceHandler
+{
+	fn report_offence(
+		reporters: Vec<u64>,
+		offence: EquivocationOffence<IdentificationTuple<Test>>,
+	) -> Result<(), OffenceError> {
+		if Self::is_known_offence(&offence.offenders(), &offence.slot) {
+			return Err(OffenceError::DuplicateReport)
+		}
+		ReportedOffences::mutate(|offences| {
+			offences.push((reporters, offence.offender, offence.slot, offence.session_index))
+		});
+		Ok(())
+	}
+
+	fn is_known_offence(offenders: &[IdentificationTuple<Test>], time_slot: &Slot) -> bool {
+		ReportedOffences::get()
+			.iter()
+			.any(|(_, offender, slot, _)| offenders.contains(offender) && slot == time_slot)
+	}
+}
+
 impl pallet_aura::Config for Test {
 	type AuthorityId = AuthorityId;
 	type DisabledValidators = MockDisabledValidators;
 	type MaxAuthorities = ConstU32<10>;
 	type AllowMultipleBlocksPerSlot = AllowMultipleBlocksPerSlot;
+	type WeightInfo = ();
+	type MaxNominators = ConstU32<0>;
+	type MaxSessionEntries = ConstU32<3>;
+	type KeyOwnerProof = <Historical as KeyOwnerProofSystem<(KeyTypeId, AuthorityId)>>::Proof;
+	type EquivocationReportSystem =
+		pallet_aura::EquivocationReportSystem<Self, OffenceHandler, Historical, ConstU64<100>>;
 
 	#[cfg(feature = "experimental")]
 	type SlotDuration = ConstU64<SLOT_DURATION>;
@@ -106,3 +219,52 @@ pub fn build_ext_and_execute_test(authorities: Vec<u64>, test: impl FnOnce() ->
 		Aura::do_try_state().expect("Storage invariants should hold")
 	});
 }
+
+/// The authority keys of the validators `1..=n`.
+pub fn authority_pairs(n: u64) -> Vec<AuthorityPair> {
+	(1..=n).map(|i| AuthorityPair::from_seed(&[i as u8; 32])).collect()
+}
+
+/// Build the externalities with the validators `1..=pairs.len()` as session validators, using
+/// `pairs` as their Aura keys.
+pub fn build_ext_with_session(pairs: &[AuthorityPair]) -> sp_io::TestExternalities {
+	let validators: Vec<u64> = (1..=pairs.len() as u64).collect();
+	SessionValidators::set(validators.clone());
+	ReportedOffences::take();
+
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_session::GenesisConfig::<Test> {
+		keys: validators
+			.iter()
+			.zip(pairs)
+			.map(|(v, pair)| (*v, *v, MockSessionKeys { aura: pair.public() }))
+			.collect(),
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	storage.into()
+}
+
+/// Initialize block `n` at `slot`, rotating the session when it is due.
+pub fn go_to_block(n: u64, slot: u64) {
+	let parent_hash =
+		if System::block_number() > 1 { System::finalize().hash() } else { System::parent_hash() };
+	let pre_digest =
+		Digest { logs: vec![DigestItem::PreRuntime(AURA_ENGINE_ID, Slot::from(slot).encode())] };
+
+	System::reset_events();
+	System::initialize(&n, &parent_hash, &pre_digest);
+	Aura::on_initialize(n);
+	Session::on_initialize(n);
+}
+
+/// A header at `slot`, sealed by `pair`.
+pub fn sealed_header(number: u64, slot: u64, pair: &AuthorityPair) -> Header {
+	let pre_digest =
+		Digest { logs: vec![DigestItem::PreRuntime(AURA_ENGINE_ID, Slot::from(slot).encode())] };
+	let mut header =
+		Header::new(number, Default::default(), Default::default(), Default::default(), pre_digest);
+	let seal = pair.sign(header.hash().as_ref());
+	header.digest_mut().push(DigestItem::Seal(AURA_ENGINE_ID, seal.encode()));
+	header
+}
@@ -19,11 +19,33 @@
 
 #![cfg(test)]
 
-use crate::mock::{build_ext_and_execute_test, Aura, MockDisabledValidators, System};
+use crate::{
+	mock::{
+		authority_pairs, build_ext_and_execute_test, build_ext_with_session, go_to_block,
+		sealed_header, Aura, Historical, MockDisabledValidators, ReportedOffences, RuntimeOrigin,
+		System, Test,
+	},
+	Call, Error, SessionStartSlot, SlotStartTimestamp,
+};
 use codec::Encode;
-use frame_support::traits::OnInitialize;
-use sp_consensus_aura::{Slot, AURA_ENGINE_ID};
-use sp_runtime::{Digest, DigestItem};
+use frame_support::{
+	assert_err, assert_ok,
+	inherent::InherentData,
+	traits::{KeyOwnerProofSystem, OnInitialize},
+	unsigned::{TransactionSource, TransactionValidityError, ValidateUnsigned},
+};
+use pallet_timestamp::ProvideExpectedTimestamp;
+use sp_consensus_aura::{
+	ed25519::{AuthorityId, AuthorityPair},
+	inherents::INHERENT_IDENTIFIER,
+	EquivocationProof, Slot, AURA_ENGINE_ID,
+};
+use sp_core::{crypto::key_types::AURA, Pair};
+use sp_runtime::{
+	testing::Header,
+	transaction_validity::{InvalidTransaction, TransactionPriority, ValidTransaction},
+	Digest, DigestItem,
+};
 
 #[test]
 fn initial_values() {
@@ -116,3 +138,228 @@ fn pallet_always_rejects_decreasing_slot() {
 		Aura::on_initialize(43);
 	});
 }
+
+#[test]
+fn slot_start_timestamp_follows_current_slot() {
+	build_ext_and_execute_test(vec![0, 1, 2, 3], || {
+		assert_eq!(SlotStartTimestamp::<Test>::expected_timestamp(), None);
+
+		let slot = Slot::from(5);
+		let pre_digest =
+			Digest { logs: vec![DigestItem::PreRuntime(AURA_ENGINE_ID, slot.encode())] };
+		System::initialize(&42, &System::parent_hash(), &pre_digest);
+		Aura::on_initialize(42);
+
+		assert_eq!(
+			SlotStartTimestamp::<Test>::expected_timestamp(),
+			Some(5 * Aura::slot_duration())
+		);
+	});
+}
+
+#[test]
+fn slot_start_timestamp_follows_inherent_data() {
+	build_ext_and_execute_test(vec![0, 1, 2, 3], || {
+		let mut data = InherentData::new();
+		assert_eq!(SlotStartTimestamp::<Test>::expected_timestamp_from_inherent_data(&data), None);
+
+		data.put_data(INHERENT_IDENTIFIER, &Slot::from(7)).unwrap();
+		assert_eq!(
+			SlotStartTimestamp::<Test>::expected_timestamp_from_inherent_data(&data),
+			Some(7 * Aura::slot_duration())
+		);
+	});
+}
+
+fn equivocation_proof(
+	offender: &AuthorityPair,
+	slot: u64,
+	second_slot: u64,
+) -> Box<EquivocationProof<Header, AuthorityId>> {
+	Box::new(EquivocationProof {
+		offender: offender.public(),
+		slot: Slot::from(slot),
+		first_header: sealed_header(1, slot, offender),
+		second_header: sealed_header(2, second_slot, offender),
+	})
+}
+
+#[test]
+fn report_equivocation_works() {
+	let pairs = authority_pairs(3);
+	build_ext_with_session(&pairs).execute_with(|| {
+		go_to_block(1, 1);
+		assert_eq!(
+			Aura::authorities().to_vec(),
+			pairs.iter().map(|p| p.public()).collect::<Vec<_>>()
+		);
+
+		let offender = &pairs[1];
+		let key_owner_proof = Historical::prove((AURA, offender.public())).unwrap();
+		assert_ok!(Aura::report_equivocation(
+			RuntimeOrigin::signed(3),
+			equivocation_proof(offender, 1, 1),
+			key_owner_proof.clone(),
+		));
+		assert_eq!(ReportedOffences::get(), vec![(vec![3], (2, 2), Slot::from(1), 0)]);
+
+		// the same offence can't be reported twice.
+		assert_err!(
+			Aura::report_equivocation(
+				RuntimeOrigin::signed(3),
+				equivocation_proof(offender, 1, 1),
+				key_owner_proof,
+			),
+			Error::<Test>::DuplicateOffenceReport,
+		);
+	});
+}
+
+#[test]
+fn report_equivocation_rejects_invalid_proofs() {
+	let pairs = authority_pairs(3);
+	build_ext_with_session(&pairs).execute_with(|| {
+		go_to_block(1, 1);
+		let offender = &pairs[1];
+		let key_owner_proof = Historical::prove((AURA, offender.public())).unwrap();
+		let report = |equivocation_proof| {
+			Aura::report_equivocation(
+				RuntimeOrigin::signed(3),
+				equivocation_proof,
+				key_owner_proof.clone(),
+			)
+		};
+
+		// the same header twice.
+		let mut proof = equivocation_proof(offender, 1, 1);
+		proof.second_header = proof.first_header.clone();
+		assert_err!(report(proof), Error::<Test>::InvalidEquivocationProof);
+
+		// headers at different slots.
+		assert_err!(
+			report(equivocation_proof(offender, 1, 2)),
+			Error::<Test>::InvalidEquivocationProof
+		);
+
+		// headers sealed by another authority.
+		let mut proof = equivocation_proof(&pairs[2], 1, 1);
+		proof.offender = offender.public();
+		assert_err!(report(proof), Error::<Test>::InvalidEquivocationProof);
+
+		// a key ownership proof for a different validator count.
+		let mut key_owner_proof = key_owner_proof.clone();
+		key_owner_proof.validator_count = 2;
+		assert_err!(
+			Aura::report_equivocation(
+				RuntimeOrigin::signed(3),
+				equivocation_proof(offender, 1, 1),
+				key_owner_proof,
+			),
+			Error::<Test>::InvalidKeyOwnershipProof,
+		);
+
+		assert!(ReportedOffences::get().is_empty());
+	});
+}
+
+#[test]
+fn report_equivocation_checks_the_session_of_the_slot() {
+	let pairs = authority_pairs(3);
+	build_ext_with_session(&pairs).execute_with(|| {
+		for block in 1..=crate::mock::SESSION_LENGTH {
+			go_to_block(block, block);
+		}
+		// the session rotated at slot 3, so session 1 starts at slot 4.
+		let offender = &pairs[1];
+		let key_owner_proof = Historical::prove((AURA, offender.public())).unwrap();
+		assert_eq!(key_owner_proof.session, 1);
+
+		assert_err!(
+			Aura::report_equivocation(
+				RuntimeOrigin::signed(3),
+				equivocation_proof(offender, 3, 3),
+				key_owner_proof.clone(),
+			),
+			Error::<Test>::InvalidKeyOwnershipProof,
+		);
+		assert_ok!(Aura::report_equivocation(
+			RuntimeOrigin::signed(3),
+			equivocation_proof(offender, 4, 4),
+			key_owner_proof,
+		));
+		assert_eq!(ReportedOffences::get(), vec![(vec![3], (2, 2), Slot::from(4), 1)]);
+	});
+}
+
+#[test]
+fn session_start_slots_are_pruned() {
+	let pairs = authority_pairs(3);
+	build_ext_with_session(&pairs).execute_with(|| {
+		assert_eq!(SessionStartSlot::<Test>::get(0), Some(Slot::from(0)));
+
+		for block in 1..=4 * crate::mock::SESSION_LENGTH {
+			go_to_block(block, block);
+		}
+
+		// only the last `MaxSessionEntries` sessions are kept.
+		let start_slots: Vec<_> = (0..=4).map(SessionStartSlot::<Test>::get).collect();
+		assert_eq!(
+			start_slots,
+			vec![None, None, Some(Slot::from(7)), Some(Slot::from(10)), Some(Slot::from(13))],
+		);
+		assert!(!Aura::is_slot_in_session(Slot::from(6), 2));
+		assert!(Aura::is_slot_in_session(Slot::from(7), 2));
+		assert!(Aura::is_slot_in_session(Slot::from(9), 2));
+		assert!(!Aura::is_slot_in_session(Slot::from(10), 2));
+		assert!(Aura::is_slot_in_session(Slot::from(100), 4));
+		assert!(!Aura::is_slot_in_session(Slot::from(4), 1));
+	});
+}
+
+#[test]
+fn report_equivocation_validate_unsigned_prevents_duplicates() {
+	let pairs = authority_pairs(3);
+	build_ext_with_session(&pairs).execute_with(|| {
+		go_to_block(1, 1);
+		let offender = &pairs[1];
+		let key_owner_proof = Historical::prove((AURA, offender.public())).unwrap();
+		let call = Call::report_equivocation_unsigned {
+			equivocation_proof: equivocation_proof(offender, 1, 1),
+			key_owner_proof: key_owner_proof.clone(),
+		};
+
+		// only local/inblock reports are allowed
+		assert_eq!(
+			<Aura as ValidateUnsigned>::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Call.into(),
+		);
+
+		// the transaction is valid when passed as local
+		let tx_tag = (offender.public(), 1u64);
+		assert_eq!(
+			<Aura as ValidateUnsigned>::validate_unsigned(TransactionSource::Local, &call),
+			Ok(ValidTransaction {
+				priority: TransactionPriority::max_value(),
+				requires: vec![],
+				provides: vec![("AuraEquivocation", tx_tag).encode()],
+				longevity: 100,
+				propagate: false,
+			}),
+		);
+		assert_ok!(<Aura as ValidateUnsigned>::pre_dispatch(&call));
+
+		// the block author is the reporter of an unsigned report.
+		assert_ok!(Aura::report_equivocation_unsigned(
+			RuntimeOrigin::none(),
+			equivocation_proof(offender, 1, 1),
+			key_owner_proof,
+		));
+		assert_eq!(ReportedOffences::get(), vec![(vec![2], (2, 2), Slot::from(1), 0)]);
+
+		// the report is now stale.
+		assert_err!(
+			<Aura as ValidateUnsigned>::pre_dispatch(&call),
+			TransactionValidityError::Invalid(InvalidTransaction::Stale),
+		);
+	});
+}
@@ -201,6 +201,9 @@ mod tests {
 	impl pallet_session::Config for Test {
 		type SessionManager = ();
 		type Keys = UintAuthorityId;
+		type InactiveKeysExpiry = ();
+		type ValidatorIntent = ();
+		type MaxKeysPurgedPerSession = ConstU32<100>;
 		type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
 		type SessionHandler = TestSessionHandler;
 		type RuntimeEvent = RuntimeEvent;
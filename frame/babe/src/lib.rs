@@ -105,6 +105,36 @@ impl EpochChangeTrigger for SameAuthoritiesForever {
 	}
 }
 
+/// An expected timestamp provider which returns the start of the slot of the block, to be used
+/// as [`pallet_timestamp::Config::ExpectedTimestamp`].
+///
+/// When executing a block, returns `None` until the slot of the first block is known.
+pub struct SlotStartTimestamp<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> SlotStartTimestamp<T> {
+	fn slot_start(slot: Slot) -> T::Moment {
+		Pallet::<T>::slot_duration().saturating_mul((*slot).saturated_into())
+	}
+}
+
+impl<T: Config> pallet_timestamp::ProvideExpectedTimestamp<T::Moment> for SlotStartTimestamp<T> {
+	fn expected_timestamp() -> Option<T::Moment> {
+		let slot = Pallet::<T>::current_slot();
+		(*slot != 0).then(|| Self::slot_start(slot))
+	}
+
+	fn expected_timestamp_from_inherent_data(
+		data: &frame_support::inherent::InherentData,
+	) -> Option<T::Moment> {
+		use sp_consensus_babe::inherents::{InherentType, INHERENT_IDENTIFIER};
+
+		data.get_data::<InherentType>(&INHERENT_IDENTIFIER)
+			.ok()
+			.flatten()
+			.map(Self::slot_start)
+	}
+}
+
 const UNDER_CONSTRUCTION_SEGMENT_LENGTH: u32 = 256;
 
 #[frame_support::pallet]
@@ -283,7 +313,7 @@ pub mod pallet {
 	/// entropy was fixed (i.e. it was known to chain observers). Since epochs are defined in
 	/// slots, which may be skipped, the block numbers may not line up with the slot numbers.
 	#[pallet::storage]
-	pub(super) type EpochStart<T: Config> =
+	pub type EpochStart<T: Config> =
 		StorageValue<_, (BlockNumberFor<T>, BlockNumberFor<T>), ValueQuery>;
 
 	/// How late the current block is compared to its parent.
@@ -26,6 +26,7 @@ use frame_election_provider_support::{
 use frame_support::{
 	derive_impl, parameter_types,
 	traits::{ConstU128, ConstU32, ConstU64, KeyOwnerProofSystem, OnInitialize},
+	weights::Weight,
 };
 use pallet_session::historical as pallet_session_historical;
 use pallet_staking::FixedNominationsQuota;
@@ -92,6 +93,9 @@ impl pallet_session::Config for Test {
 	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Self, Staking>;
 	type SessionHandler = <MockSessionKeys as OpaqueKeys>::KeyTypeIdProviders;
 	type Keys = MockSessionKeys;
+	type InactiveKeysExpiry = ();
+	type ValidatorIntent = ();
+	type MaxKeysPurgedPerSession = ConstU32<100>;
 	type WeightInfo = ();
 }
 
@@ -109,6 +113,9 @@ impl pallet_timestamp::Config for Test {
 	type Moment = u64;
 	type OnTimestampSet = Babe;
 	type MinimumPeriod = ConstU64<1>;
+	type ExpectedTimestamp = ();
+	type MaxTimestampDrift = ();
+	type OnTimestampDrift = ();
 	type WeightInfo = ();
 }
 
@@ -189,10 +196,15 @@ impl pallet_staking::Config for Test {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const MaxOffenceProcessingWeight: Weight = Weight::MAX;
+}
+
 impl pallet_offences::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
 	type OnOffenceHandler = Staking;
+	type MaxOffenceProcessingWeight = MaxOffenceProcessingWeight;
 }
 
 parameter_types! {
@@ -236,6 +248,7 @@ pub fn go_to_block(n: u64, s: u64) {
 	Babe::on_initialize(n);
 	Session::on_initialize(n);
 	Staking::on_initialize(n);
+	Offences::on_initialize(n);
 }
 
 /// Slots will grow accordingly to blocks
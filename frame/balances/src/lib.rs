@@ -174,7 +174,8 @@ use frame_support::{
 			Preservation::{Expendable, Preserve, Protect},
 			WithdrawConsequence,
 		},
-		Currency, Defensive, Get, OnUnbalanced, ReservableCurrency, StoredMap,
+		Currency, Defensive, Get, HeldReferences, OnUnbalanced, PalletInfoAccess, ReferenceHolder,
+		ReservableCurrency, StoredMap,
 	},
 	BoundedSlice, WeakBoundedVec,
 };
@@ -478,6 +479,7 @@ pub mod pallet {
 
 	/// Holds on account balances.
 	#[pallet::storage]
+	#[pallet::getter(fn holds)]
 	pub type Holds<T: Config<I>, I: 'static = ()> = StorageMap<
 		_,
 		Blake2_128Concat,
@@ -491,6 +493,7 @@ pub mod pallet {
 
 	/// Freeze locks on account balances.
 	#[pallet::storage]
+	#[pallet::getter(fn freezes)]
 	pub type Freezes<T: Config<I>, I: 'static = ()> = StorageMap<
 		_,
 		Blake2_128Concat,
@@ -1171,4 +1174,21 @@ pub mod pallet {
 			Ok(actual)
 		}
 	}
+
+	impl<T: Config<I>, I: 'static> ReferenceHolder<T::AccountId> for Pallet<T, I> {
+		fn held_references(who: &T::AccountId) -> Vec<HeldReferences> {
+			let a = Self::account(who);
+			let providers = a.free >= Self::ed() && Self::have_providers_or_no_zero_ed(who);
+			let consumers = !a.reserved.is_zero() || !a.frozen.is_zero();
+			if !providers && !consumers {
+				return Vec::new()
+			}
+			sp_std::vec![HeldReferences {
+				holder: <Self as PalletInfoAccess>::name().as_bytes().to_vec(),
+				providers: providers as u32,
+				consumers: consumers as u32,
+				sufficients: 0,
+			}]
+		}
+	}
 }
@@ -24,8 +24,8 @@ use frame_support::{
 		BalanceStatus::{Free, Reserved},
 		Currency,
 		ExistenceRequirement::{self, AllowDeath, KeepAlive},
-		Hooks, LockIdentifier, LockableCurrency, NamedReservableCurrency, ReservableCurrency,
-		WithdrawReasons,
+		HeldReferences, Hooks, LockIdentifier, LockableCurrency, NamedReservableCurrency,
+		ReservableCurrency, WithdrawReasons,
 	},
 	StorageNoopGuard,
 };
@@ -390,6 +390,35 @@ fn reward_should_work() {
 	});
 }
 
+#[test]
+fn account_references_report_balances() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		assert_eq!(System::account_references(&1).holders, vec![]);
+
+		let _ = Balances::deposit_creating(&1, 42);
+		assert_ok!(Balances::reserve(&1, 10));
+		assert_eq!(
+			System::account_references(&1),
+			frame_system::AccountReferences {
+				consumers: 1,
+				providers: 1,
+				sufficients: 0,
+				holders: vec![HeldReferences {
+					holder: b"Balances".to_vec(),
+					providers: 1,
+					consumers: 1,
+					sufficients: 0,
+				}],
+			}
+		);
+
+		Balances::unreserve(&1, 10);
+		let references = System::account_references(&1);
+		assert_eq!(references.consumers, 0);
+		assert_eq!(references.holders[0].consumers, 0);
+	});
+}
+
 #[test]
 fn balance_works() {
 	ExtBuilder::default().build_and_execute_with(|| {
@@ -18,6 +18,7 @@
 //! Tests regarding the functionality of the `fungible` trait set implementations.
 
 use super::*;
+use crate::IdAmount;
 use frame_support::traits::tokens::{
 	Fortitude::{Force, Polite},
 	Precision::{BestEffort, Exact},
@@ -398,6 +399,36 @@ fn unholding_frees_hold_slot() {
 		});
 }
 
+#[test]
+fn holds_and_freezes_are_enumerable() {
+	ExtBuilder::default().build_and_execute_with(|| {
+		<Balances as fungible::Mutate<_>>::set_balance(&1, 100);
+		assert_ok!(Balances::hold(&TestId::Foo, &1, 10));
+		assert_ok!(Balances::hold(&TestId::Bar, &1, 20));
+		assert_ok!(Balances::set_freeze(&TestId::Baz, &1, 30));
+		assert_eq!(
+			Balances::holds(&1).into_inner(),
+			vec![
+				IdAmount { id: TestId::Foo, amount: 10 },
+				IdAmount { id: TestId::Bar, amount: 20 }
+			]
+		);
+		assert_eq!(
+			Balances::freezes(&1).into_inner(),
+			vec![IdAmount { id: TestId::Baz, amount: 30 }]
+		);
+
+		// Releasing a hold removes it from the account's list; releasing it again is a no-op.
+		assert_ok!(Balances::release(&TestId::Foo, &1, 10, Exact));
+		assert_eq!(Balances::release(&TestId::Foo, &1, 10, BestEffort), Ok(0));
+		assert_eq!(
+			Balances::holds(&1).into_inner(),
+			vec![IdAmount { id: TestId::Bar, amount: 20 }]
+		);
+		assert!(Balances::holds(&2).is_empty());
+	});
+}
+
 #[test]
 fn sufficients_work_properly_with_reference_counting() {
 	ExtBuilder::default()
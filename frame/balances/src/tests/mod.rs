@@ -94,6 +94,7 @@ parameter_types! {
 impl frame_system::Config for Test {
 	type Block = Block;
 	type AccountData = super::AccountData<u64>;
+	type ReferenceHolders = Balances;
 }
 
 impl pallet_transaction_payment::Config for Test {
@@ -103,6 +104,7 @@ impl pallet_transaction_payment::Config for Test {
 	type WeightToFee = IdentityFee<u64>;
 	type LengthToFee = IdentityFee<u64>;
 	type FeeMultiplierUpdate = ();
+	type PriorityCurve = pallet_transaction_payment::TipPerResourcePriority;
 }
 
 pub(crate) type Balance = u64;
@@ -71,6 +71,9 @@ impl pallet_session::Config for Test {
 	type SessionManager = MockSessionManager;
 	type SessionHandler = <MockSessionKeys as OpaqueKeys>::KeyTypeIdProviders;
 	type Keys = MockSessionKeys;
+	type InactiveKeysExpiry = ();
+	type ValidatorIntent = ();
+	type MaxKeysPurgedPerSession = ConstU32<100>;
 	type WeightInfo = ();
 }
 
@@ -24,6 +24,7 @@ use frame_election_provider_support::{
 use frame_support::{
 	construct_runtime, derive_impl, parameter_types,
 	traits::{ConstU32, ConstU64, KeyOwnerProofSystem, OnFinalize, OnInitialize},
+	weights::Weight,
 };
 use pallet_session::historical as pallet_session_historical;
 use sp_core::{crypto::KeyTypeId, ConstU128};
@@ -108,6 +109,9 @@ impl pallet_session::Config for Test {
 	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Self, Staking>;
 	type SessionHandler = <MockSessionKeys as OpaqueKeys>::KeyTypeIdProviders;
 	type Keys = MockSessionKeys;
+	type InactiveKeysExpiry = ();
+	type ValidatorIntent = ();
+	type MaxKeysPurgedPerSession = ConstU32<100>;
 	type WeightInfo = ();
 }
 
@@ -141,6 +145,9 @@ impl pallet_timestamp::Config for Test {
 	type Moment = u64;
 	type OnTimestampSet = ();
 	type MinimumPeriod = ConstU64<3>;
+	type ExpectedTimestamp = ();
+	type MaxTimestampDrift = ();
+	type OnTimestampDrift = ();
 	type WeightInfo = ();
 }
 
@@ -204,10 +211,15 @@ impl pallet_staking::Config for Test {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const MaxOffenceProcessingWeight: Weight = Weight::MAX;
+}
+
 impl pallet_offences::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
 	type OnOffenceHandler = Staking;
+	type MaxOffenceProcessingWeight = MaxOffenceProcessingWeight;
 }
 
 // Note, that we can't use `UintAuthorityId` here. Reason is that the implementation
@@ -296,6 +308,7 @@ pub fn start_session(session_index: SessionIndex) {
 		System::on_initialize(System::block_number());
 		Session::on_initialize(System::block_number());
 		Staking::on_initialize(System::block_number());
+		Offences::on_initialize(System::block_number());
 		Beefy::on_initialize(System::block_number());
 	}
 
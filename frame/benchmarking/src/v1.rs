@@ -19,9 +19,20 @@
 
 pub use super::*;
 
-/// Whitelist the given account.
+/// Whitelist the given account, or with `whitelist!(storage Value)` the given storage value, for
+/// the current benchmark.
+///
+/// Reads and writes of whitelisted keys are not counted, since they are assumed to be in the cache
+/// already, e.g. because they are accessed in every block.
 #[macro_export]
 macro_rules! whitelist {
+	(storage $storage:ty) => {
+		frame_benchmarking::benchmarking::add_to_whitelist(
+			<$storage as frame_support::storage::StorageValue<_>>::hashed_key()
+				.to_vec()
+				.into(),
+		);
+	};
 	($acc:ident) => {
 		frame_benchmarking::benchmarking::add_to_whitelist(
 			frame_system::Account::<T>::hashed_key_for(&$acc).into(),
@@ -1066,7 +1077,7 @@ macro_rules! impl_benchmark {
 				);
 				whitelist.push(intrablock_entropy);
 
-				$crate::benchmarking::set_whitelist(whitelist.clone());
+				$crate::benchmarking::set_whitelist(whitelist);
 
 				let mut results: $crate::__private::Vec<$crate::BenchmarkResult> = $crate::__private::Vec::new();
 
@@ -1090,9 +1101,9 @@ macro_rules! impl_benchmark {
 					// This will enable worst case scenario for reading from the database.
 					$crate::benchmarking::commit_db();
 
-					// Access all whitelisted keys to get them into the proof recorder since the
-					// recorder does now have a whitelist.
-					for key in &whitelist {
+					// Access all whitelisted keys, including the ones whitelisted by the setup, to
+					// get them into the proof recorder since the recorder does now have a whitelist.
+					for key in &$crate::benchmarking::get_whitelist() {
 						$crate::__private::storage::unhashed::get_raw(&key.key);
 					}
 
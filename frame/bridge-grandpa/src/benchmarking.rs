@@ -0,0 +1,148 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bridge GRANDPA pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use crate::Pallet as BridgeGrandpa;
+use frame_benchmarking::{
+	impl_benchmark_test_suite,
+	v1::{whitelisted_caller, BenchmarkError},
+	v2::*,
+};
+use frame_support::traits::{EnsureOrigin, Get};
+use frame_system::RawOrigin;
+use sp_runtime::{Digest, DigestItem, RuntimeAppPublic};
+
+/// The maximum number of headers in the ancestry of a benchmarked justification.
+const MAX_VOTES_ANCESTRIES: u32 = 100;
+
+fn authority_list(count: u32) -> AuthorityList {
+	(0..count).map(|_| (AuthorityId::generate_pair(None), 1)).collect()
+}
+
+fn header<T: Config<I>, I: 'static>(
+	number: u32,
+	parent_hash: BridgedBlockHash<T, I>,
+	digest: Digest,
+) -> T::BridgedHeader {
+	T::BridgedHeader::new(
+		number.into(),
+		Default::default(),
+		Default::default(),
+		parent_hash,
+		digest,
+	)
+}
+
+// Initialize the pallet with a genesis header and `authorities`, returning the genesis hash.
+fn initialize_with<T: Config<I>, I: 'static>(
+	authorities: AuthorityList,
+) -> Result<BridgedBlockHash<T, I>, BenchmarkError> {
+	let genesis = header::<T, I>(0, Default::default(), Default::default());
+	let hash = genesis.hash();
+	let origin = T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+	BridgeGrandpa::<T, I>::initialize(
+		origin,
+		Box::new(InitializationData { header: genesis, authority_list: authorities, set_id: 0 }),
+	)?;
+	Ok(hash)
+}
+
+#[instance_benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn initialize(a: Linear<1, { T::MaxAuthorities::get() }>) -> Result<(), BenchmarkError> {
+		let genesis = header::<T, I>(0, Default::default(), Default::default());
+		let init_data =
+			InitializationData { header: genesis, authority_list: authority_list(a), set_id: 0 };
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Box::new(init_data));
+
+		assert!(BestFinalized::<T, I>::get().is_some());
+		assert_eq!(CurrentAuthorities::<T, I>::get().len(), a as usize);
+
+		Ok(())
+	}
+
+	// Every authority precommits the tip of `v` descendants of the finalized header, which also
+	// enacts a new authority set.
+	#[benchmark]
+	fn submit_finality_proof(
+		p: Linear<1, { T::MaxAuthorities::get() }>,
+		v: Linear<0, MAX_VOTES_ANCESTRIES>,
+	) -> Result<(), BenchmarkError> {
+		let authorities = authority_list(p);
+		let genesis_hash = initialize_with::<T, I>(authorities.clone())?;
+
+		let change = ConsensusLog::ScheduledChange(ScheduledChange {
+			next_authorities: authorities.clone(),
+			delay: Zero::zero(),
+		});
+		let digest =
+			Digest { logs: vec![DigestItem::Consensus(GRANDPA_ENGINE_ID, change.encode())] };
+		let target = header::<T, I>(1, genesis_hash, digest);
+		let target_hash = target.hash();
+
+		let mut votes_ancestries = Vec::new();
+		let mut tip = target_hash;
+		for number in 2..v + 2 {
+			let ancestor = header::<T, I>(number, tip, Default::default());
+			tip = ancestor.hash();
+			votes_ancestries.push(ancestor);
+		}
+
+		let precommit = grandpa::Precommit { target_hash: tip, target_number: (v + 1).into() };
+		let payload = sp_consensus_grandpa::localized_payload(
+			0,
+			0,
+			&grandpa::Message::Precommit(precommit.clone()),
+		);
+		let precommits = authorities
+			.into_iter()
+			.map(|(id, _)| grandpa::SignedPrecommit {
+				precommit: precommit.clone(),
+				signature: id.sign(&payload).expect("the key was generated in the keystore; qed"),
+				id,
+			})
+			.collect();
+		let justification = GrandpaJustification {
+			round: 0,
+			commit: grandpa::Commit { target_hash, target_number: 1u32.into(), precommits },
+			votes_ancestries,
+		};
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), Box::new(target), justification);
+
+		assert_eq!(BestFinalized::<T, I>::get(), Some((1u32.into(), target_hash)));
+		assert_eq!(CurrentSetId::<T, I>::get(), 1);
+
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(BridgeGrandpa, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,135 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of GRANDPA justifications produced by the bridged chain.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_consensus_grandpa::{AuthorityId, AuthorityWeight, Commit, Message, SetId};
+use sp_runtime::{traits::Header as HeaderT, RuntimeDebug};
+use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	prelude::*,
+};
+
+/// A GRANDPA justification proving the finality of a block of the bridged chain.
+///
+/// This has the same encoding as [`sp_consensus_grandpa::GrandpaJustification`], so the
+/// justifications stored by the nodes of the bridged chain can be submitted as they are. Unlike
+/// the latter, it can be used as a call parameter in `no_std` builds.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct GrandpaJustification<Header: HeaderT> {
+	/// The round in which the block has been finalized.
+	pub round: u64,
+	/// The commit of that round.
+	pub commit: Commit<Header>,
+	/// The headers linking the targets of the precommits to the target of the commit.
+	pub votes_ancestries: Vec<Header>,
+}
+
+/// The reasons why a justification can be rejected.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum JustificationError {
+	/// The justification finalizes another block than the expected one.
+	InvalidTarget,
+	/// A precommit is signed by an authority which is not part of the set.
+	UnknownAuthority,
+	/// An authority signed more than one precommit.
+	DuplicateAuthority,
+	/// The signature of a precommit is invalid.
+	InvalidSignature,
+	/// The target of a precommit is not a descendant of the finalized block.
+	UnrelatedAncestry,
+	/// The ancestry contains headers which are not needed to link any precommit.
+	RedundantAncestry,
+	/// The precommits do not reach a supermajority of the authority set weight, or have no
+	/// weight at all.
+	NotEnoughWeight,
+}
+
+/// Verify that `justification` proves the finality of the block `target` in the authority set
+/// `set_id`, made of `authorities`.
+pub fn verify_justification<Header: HeaderT>(
+	target: (Header::Hash, Header::Number),
+	authorities: &[(AuthorityId, AuthorityWeight)],
+	set_id: SetId,
+	justification: &GrandpaJustification<Header>,
+) -> Result<(), JustificationError> {
+	let commit = &justification.commit;
+	if (commit.target_hash, commit.target_number) != target {
+		return Err(JustificationError::InvalidTarget)
+	}
+
+	let ancestry: BTreeMap<Header::Hash, Header::Hash> = justification
+		.votes_ancestries
+		.iter()
+		.map(|header| (header.hash(), *header.parent_hash()))
+		.collect();
+	let mut used_ancestry = BTreeSet::new();
+	let mut signers = BTreeSet::new();
+	let mut signed_weight: AuthorityWeight = 0;
+	let mut buffer = Vec::new();
+
+	for signed in &commit.precommits {
+		let (index, weight) = authorities
+			.iter()
+			.enumerate()
+			.find(|(_, (id, _))| *id == signed.id)
+			.map(|(index, (_, weight))| (index, *weight))
+			.ok_or(JustificationError::UnknownAuthority)?;
+		if !signers.insert(index) {
+			return Err(JustificationError::DuplicateAuthority)
+		}
+
+		// honest voters may precommit a descendant of the finalized block, in which case the
+		// ancestry must route the precommit target back to it.
+		let mut current = signed.precommit.target_hash;
+		while current != target.0 {
+			let parent = ancestry.get(&current).ok_or(JustificationError::UnrelatedAncestry)?;
+			used_ancestry.insert(current);
+			current = *parent;
+		}
+
+		let message = Message::<Header>::Precommit(signed.precommit.clone());
+		if !sp_consensus_grandpa::check_message_signature_with_buffer(
+			&message,
+			&signed.id,
+			&signed.signature,
+			justification.round,
+			set_id,
+			&mut buffer,
+		) {
+			return Err(JustificationError::InvalidSignature)
+		}
+
+		signed_weight = signed_weight.saturating_add(weight);
+	}
+
+	if used_ancestry.len() != justification.votes_ancestries.len() {
+		return Err(JustificationError::RedundantAncestry)
+	}
+
+	let total_weight = authorities
+		.iter()
+		.fold(0 as AuthorityWeight, |total, (_, weight)| total.saturating_add(*weight));
+	let threshold = total_weight - total_weight.saturating_sub(1) / 3;
+	if signed_weight == 0 || signed_weight < threshold {
+		return Err(JustificationError::NotEnoughWeight)
+	}
+
+	Ok(())
+}
@@ -0,0 +1,383 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Bridge GRANDPA Pallet
+//!
+//! A pallet which follows the finality of a remote, GRANDPA-based Substrate chain.
+//!
+//! ## Overview
+//!
+//! The pallet is initialized by [`Config::AdminOrigin`] with a finalized header of the bridged
+//! chain and the GRANDPA authority set which is active at that header. Anyone can then import
+//! a newer finalized header by submitting it together with a GRANDPA justification proving its
+//! finality, see [`Pallet::submit_finality_proof`].
+//!
+//! Authority set changes signalled by an imported header are enacted as soon as that header is
+//! imported. Since GRANDPA always justifies the blocks which change the authority set, relayers
+//! are expected to submit each of those blocks.
+//!
+//! Only the last [`Config::HeadersToKeep`] imported headers are kept in storage. Their state
+//! roots can be used to verify storage proofs of the bridged chain, see
+//! [`Pallet::verify_storage_proof`] and the [`HeaderChain`] trait, which is meant to be used
+//! by message-passing pallets built on top of this one.
+//!
+//! ## Limitations
+//!
+//! Only authority set changes which take effect immediately can be followed, which is what
+//! `pallet-grandpa` schedules on session changes. Headers signalling a delayed or a forced
+//! change are rejected.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{dispatch::DispatchResult, ensure, BoundedVec};
+use scale_info::TypeInfo;
+use sp_consensus_grandpa::{
+	AuthorityId, AuthorityList, AuthorityWeight, ConsensusLog, ScheduledChange, SetId,
+	GRANDPA_ENGINE_ID,
+};
+use sp_runtime::{
+	traits::{Header as HeaderT, Zero},
+	DispatchError, RuntimeDebug,
+};
+use sp_std::prelude::*;
+use sp_trie::{LayoutV1, StorageProof};
+
+mod benchmarking;
+pub mod justification;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use justification::{verify_justification, GrandpaJustification, JustificationError};
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+const LOG_TARGET: &str = "runtime::bridge-grandpa";
+
+/// The block number of the bridged chain.
+pub type BridgedBlockNumber<T, I> = <<T as Config<I>>::BridgedHeader as HeaderT>::Number;
+/// The block hash of the bridged chain.
+pub type BridgedBlockHash<T, I> = <<T as Config<I>>::BridgedHeader as HeaderT>::Hash;
+/// The hasher of the bridged chain.
+pub type BridgedHasher<T, I> = <<T as Config<I>>::BridgedHeader as HeaderT>::Hashing;
+
+/// The data kept for each imported header of the bridged chain.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct StoredHeaderData<Number, Hash> {
+	/// The number of the header.
+	pub number: Number,
+	/// The state root of the header.
+	pub state_root: Hash,
+}
+
+/// The data the pallet is initialized with.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct InitializationData<Header: HeaderT> {
+	/// A finalized header of the bridged chain, which the pallet starts from.
+	pub header: Header,
+	/// The GRANDPA authorities which finalize the descendants of `header`.
+	pub authority_list: AuthorityList,
+	/// The id of the set `authority_list` belongs to.
+	pub set_id: SetId,
+}
+
+/// Access to the finalized state of a bridged chain.
+///
+/// Message-passing pallets rely on this trait to check that the messages they receive have been
+/// committed to by the bridged chain.
+pub trait HeaderChain<Hash> {
+	/// Read the value of `key` in the state of the imported block `hash`, using the storage
+	/// `proof` provided by the bridged chain.
+	///
+	/// Returns `Ok(None)` if the proof shows that `key` has no value.
+	fn verify_storage_proof(
+		hash: Hash,
+		proof: StorageProof,
+		key: &[u8],
+	) -> Result<Option<Vec<u8>>, DispatchError>;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(_);
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The header type of the bridged chain.
+		type BridgedHeader: HeaderT;
+
+		/// The origin which may initialize the pallet.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of authorities in a GRANDPA set of the bridged chain.
+		#[pallet::constant]
+		type MaxAuthorities: Get<u32>;
+
+		/// The number of most recently imported headers kept in storage.
+		#[pallet::constant]
+		type HeadersToKeep: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The GRANDPA authorities which finalize the next headers of the bridged chain.
+	#[pallet::storage]
+	pub type CurrentAuthorities<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<(AuthorityId, AuthorityWeight), T::MaxAuthorities>, ValueQuery>;
+
+	/// The id of the set of `CurrentAuthorities`.
+	#[pallet::storage]
+	pub type CurrentSetId<T: Config<I>, I: 'static = ()> = StorageValue<_, SetId, ValueQuery>;
+
+	/// The number and hash of the best imported header, if the pallet is initialized.
+	#[pallet::storage]
+	pub type BestFinalized<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, (BridgedBlockNumber<T, I>, BridgedBlockHash<T, I>), OptionQuery>;
+
+	/// The imported headers which have not been pruned yet.
+	///
+	/// Keys are hashes of headers which have been proven final, hence the `Identity` hasher.
+	#[pallet::storage]
+	pub type ImportedHeaders<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Identity,
+		BridgedBlockHash<T, I>,
+		StoredHeaderData<BridgedBlockNumber<T, I>, BridgedBlockHash<T, I>>,
+		OptionQuery,
+	>;
+
+	/// A ring buffer of the hashes of the imported headers, in import order.
+	#[pallet::storage]
+	pub type ImportedHashes<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, u32, BridgedBlockHash<T, I>, OptionQuery>;
+
+	/// The slot of `ImportedHashes` the next imported header goes to.
+	#[pallet::storage]
+	pub type ImportedHashesPointer<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u32, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// The pallet has been initialized with a header.
+		Initialized { number: BridgedBlockNumber<T, I>, hash: BridgedBlockHash<T, I> },
+		/// A finalized header has been imported.
+		HeaderImported { number: BridgedBlockNumber<T, I>, hash: BridgedBlockHash<T, I> },
+		/// The authority set of the bridged chain has changed.
+		AuthoritySetChanged { set_id: SetId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// The pallet has already been initialized.
+		AlreadyInitialized,
+		/// The pallet has not been initialized yet.
+		NotInitialized,
+		/// The authority set is empty, has no weight or has more than `MaxAuthorities` members.
+		InvalidAuthoritySet,
+		/// The header is not newer than the best imported header.
+		OldHeader,
+		/// The justification does not prove the finality of the header.
+		InvalidJustification,
+		/// The header signals an authority set change which cannot be followed.
+		UnsupportedAuthoritySetChange,
+		/// The header is not known, or has been pruned.
+		UnknownHeader,
+		/// The storage proof does not match the state root of the header.
+		InvalidStorageProof,
+	}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Initialize the pallet with a finalized header of the bridged chain and its authority
+		/// set.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		///
+		/// Emits `Initialized`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::initialize(init_data.authority_list.len() as u32))]
+		pub fn initialize(
+			origin: OriginFor<T>,
+			init_data: Box<InitializationData<T::BridgedHeader>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(BestFinalized::<T, I>::get().is_none(), Error::<T, I>::AlreadyInitialized);
+
+			let InitializationData { header, authority_list, set_id } = *init_data;
+			Self::set_authorities(authority_list, set_id)?;
+
+			let hash = header.hash();
+			Self::import_header(hash, &header);
+			Self::deposit_event(Event::Initialized { number: *header.number(), hash });
+			Ok(())
+		}
+
+		/// Import a finalized header of the bridged chain.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `finality_target`: The header to import, which must be newer than the best imported
+		///   header.
+		/// - `justification`: A GRANDPA justification of the current authority set, proving the
+		///   finality of `finality_target`.
+		///
+		/// Emits `HeaderImported`, and `AuthoritySetChanged` if the header enacts a new
+		/// authority set.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::submit_finality_proof(
+			justification.commit.precommits.len() as u32,
+			justification.votes_ancestries.len() as u32,
+		))]
+		pub fn submit_finality_proof(
+			origin: OriginFor<T>,
+			finality_target: Box<T::BridgedHeader>,
+			justification: GrandpaJustification<T::BridgedHeader>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let (best_number, _) =
+				BestFinalized::<T, I>::get().ok_or(Error::<T, I>::NotInitialized)?;
+			let number = *finality_target.number();
+			ensure!(number > best_number, Error::<T, I>::OldHeader);
+
+			let hash = finality_target.hash();
+			verify_justification::<T::BridgedHeader>(
+				(hash, number),
+				&CurrentAuthorities::<T, I>::get(),
+				CurrentSetId::<T, I>::get(),
+				&justification,
+			)
+			.map_err(|e| {
+				log::debug!(target: LOG_TARGET, "Rejected justification of {:?}: {:?}", hash, e);
+				Error::<T, I>::InvalidJustification
+			})?;
+
+			let change = Self::scheduled_change(&finality_target)?;
+			if let Some(change) = change {
+				let set_id = CurrentSetId::<T, I>::get().saturating_add(1);
+				Self::set_authorities(change.next_authorities, set_id)?;
+				Self::deposit_event(Event::AuthoritySetChanged { set_id });
+			}
+
+			Self::import_header(hash, &finality_target);
+			Self::deposit_event(Event::HeaderImported { number, hash });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The number and hash of the best imported header, if the pallet is initialized.
+	pub fn best_finalized() -> Option<(BridgedBlockNumber<T, I>, BridgedBlockHash<T, I>)> {
+		BestFinalized::<T, I>::get()
+	}
+
+	/// Read the value of `key` in the state of the imported block `hash`, using the storage
+	/// `proof` provided by the bridged chain.
+	///
+	/// Returns `Ok(None)` if the proof shows that `key` has no value.
+	pub fn verify_storage_proof(
+		hash: BridgedBlockHash<T, I>,
+		proof: StorageProof,
+		key: &[u8],
+	) -> Result<Option<Vec<u8>>, DispatchError> {
+		let header = ImportedHeaders::<T, I>::get(hash).ok_or(Error::<T, I>::UnknownHeader)?;
+		let db = proof.into_memory_db::<BridgedHasher<T, I>>();
+		sp_trie::read_trie_value::<LayoutV1<BridgedHasher<T, I>>, _>(
+			&db,
+			&header.state_root,
+			key,
+			None,
+			None,
+		)
+		.map_err(|_| Error::<T, I>::InvalidStorageProof.into())
+	}
+
+	fn set_authorities(authority_list: AuthorityList, set_id: SetId) -> DispatchResult {
+		ensure!(!authority_list.is_empty(), Error::<T, I>::InvalidAuthoritySet);
+		// a set without weight would have a zero threshold, letting any justification through.
+		ensure!(
+			authority_list.iter().any(|(_, weight)| *weight > 0),
+			Error::<T, I>::InvalidAuthoritySet
+		);
+		let authorities = BoundedVec::<_, T::MaxAuthorities>::try_from(authority_list)
+			.map_err(|_| Error::<T, I>::InvalidAuthoritySet)?;
+		CurrentAuthorities::<T, I>::put(authorities);
+		CurrentSetId::<T, I>::put(set_id);
+		Ok(())
+	}
+
+	/// The authority set change enacted by `header`, if any.
+	fn scheduled_change(
+		header: &T::BridgedHeader,
+	) -> Result<Option<ScheduledChange<BridgedBlockNumber<T, I>>>, DispatchError> {
+		let mut change = None;
+		for log in header.digest().logs() {
+			let log =
+				log.consensus_try_to::<ConsensusLog<BridgedBlockNumber<T, I>>>(&GRANDPA_ENGINE_ID);
+			match log {
+				Some(ConsensusLog::ScheduledChange(next))
+					if next.delay.is_zero() && change.is_none() =>
+					change = Some(next),
+				Some(ConsensusLog::ScheduledChange(_)) | Some(ConsensusLog::ForcedChange(..)) =>
+					return Err(Error::<T, I>::UnsupportedAuthoritySetChange.into()),
+				_ => {},
+			}
+		}
+		Ok(change)
+	}
+
+	/// Store `header` as the best imported header, pruning the oldest one if `HeadersToKeep`
+	/// headers are already stored.
+	fn import_header(hash: BridgedBlockHash<T, I>, header: &T::BridgedHeader) {
+		let pointer = ImportedHashesPointer::<T, I>::get();
+		if let Some(pruned) = ImportedHashes::<T, I>::mutate(pointer, |slot| slot.replace(hash)) {
+			ImportedHeaders::<T, I>::remove(pruned);
+		}
+		ImportedHashesPointer::<T, I>::put((pointer + 1) % T::HeadersToKeep::get().max(1));
+
+		let number = *header.number();
+		ImportedHeaders::<T, I>::insert(
+			hash,
+			StoredHeaderData { number, state_root: *header.state_root() },
+		);
+		BestFinalized::<T, I>::put((number, hash));
+	}
+}
+
+impl<T: Config<I>, I: 'static> HeaderChain<BridgedBlockHash<T, I>> for Pallet<T, I> {
+	fn verify_storage_proof(
+		hash: BridgedBlockHash<T, I>,
+		proof: StorageProof,
+		key: &[u8],
+	) -> Result<Option<Vec<u8>>, DispatchError> {
+		Self::verify_storage_proof(hash, proof, key)
+	}
+}
@@ -0,0 +1,125 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test utilities
+
+use super::*;
+use crate as pallet_bridge_grandpa;
+
+use frame_support::{assert_ok, derive_impl, traits::ConstU32};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_keyring::Ed25519Keyring;
+use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
+use sp_runtime::{testing::Header, BuildStorage, Digest};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		BridgeGrandpa: pallet_bridge_grandpa,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type BridgedHeader = Header;
+	type AdminOrigin = EnsureRoot<u64>;
+	type MaxAuthorities = ConstU32<5>;
+	type HeadersToKeep = ConstU32<3>;
+	type WeightInfo = ();
+}
+
+/// The authority set the pallet is initialized with.
+pub const AUTHORITIES: [Ed25519Keyring; 3] =
+	[Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Charlie];
+
+pub fn to_authorities(keys: &[Ed25519Keyring]) -> AuthorityList {
+	keys.iter().map(|key| (key.public().into(), 1)).collect()
+}
+
+pub fn test_header(number: u64, parent_hash: H256, digest: Digest) -> Header {
+	Header::new(number, Default::default(), Default::default(), parent_hash, digest)
+}
+
+/// Initialize the pallet with a genesis header and `AUTHORITIES`.
+pub fn initialize() -> Header {
+	let genesis = test_header(0, Default::default(), Default::default());
+	assert_ok!(BridgeGrandpa::initialize(
+		RuntimeOrigin::root(),
+		Box::new(InitializationData {
+			header: genesis.clone(),
+			authority_list: to_authorities(&AUTHORITIES),
+			set_id: 0,
+		}),
+	));
+	genesis
+}
+
+/// A justification of `header` by `signers` in the set `set_id`, where every signer precommits
+/// the last header of `ancestry`, or `header` itself if `ancestry` is empty.
+pub fn make_justification(
+	header: &Header,
+	ancestry: Vec<Header>,
+	set_id: SetId,
+	signers: &[Ed25519Keyring],
+) -> GrandpaJustification<Header> {
+	let (target_hash, target_number) = ancestry
+		.last()
+		.map(|tip| (tip.hash(), *tip.number()))
+		.unwrap_or((header.hash(), *header.number()));
+	let round = 1;
+	let precommit = grandpa::Precommit { target_hash, target_number };
+	let payload = sp_consensus_grandpa::localized_payload(
+		round,
+		set_id,
+		&grandpa::Message::Precommit(precommit.clone()),
+	);
+	let precommits = signers
+		.iter()
+		.map(|key| grandpa::SignedPrecommit {
+			precommit: precommit.clone(),
+			signature: key.sign(&payload).into(),
+			id: key.public().into(),
+		})
+		.collect();
+
+	GrandpaJustification {
+		round,
+		commit: grandpa::Commit {
+			target_hash: header.hash(),
+			target_number: *header.number(),
+			precommits,
+		},
+		votes_ancestries: ancestry,
+	}
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.register_extension(KeystoreExt::new(MemoryKeystore::new()));
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,298 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the module.
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::{assert_noop, assert_ok};
+use sp_keyring::Ed25519Keyring;
+use sp_runtime::{generic::DigestItem, testing::Header, traits::BlakeTwo256, Digest};
+use sp_trie::{
+	trie_types::{TrieDBBuilder, TrieDBMutBuilderV1},
+	MemoryDB, Recorder, Trie, TrieMut,
+};
+
+fn submit(header: &Header, justification: GrandpaJustification<Header>) -> DispatchResult {
+	BridgeGrandpa::submit_finality_proof(
+		RuntimeOrigin::signed(1),
+		Box::new(header.clone()),
+		justification,
+	)
+}
+
+fn change_digest(next: &[Ed25519Keyring], delay: u64) -> Digest {
+	let change = ConsensusLog::ScheduledChange(ScheduledChange {
+		next_authorities: to_authorities(next),
+		delay,
+	});
+	Digest { logs: vec![DigestItem::Consensus(GRANDPA_ENGINE_ID, change.encode())] }
+}
+
+#[test]
+fn initialize_works() {
+	new_test_ext().execute_with(|| {
+		let genesis = test_header(0, Default::default(), Default::default());
+		let init_data = InitializationData {
+			header: genesis.clone(),
+			authority_list: to_authorities(&AUTHORITIES),
+			set_id: 0,
+		};
+		assert_noop!(
+			BridgeGrandpa::initialize(RuntimeOrigin::signed(1), Box::new(init_data.clone())),
+			DispatchError::BadOrigin,
+		);
+
+		assert_ok!(BridgeGrandpa::initialize(RuntimeOrigin::root(), Box::new(init_data.clone())));
+		assert_eq!(BridgeGrandpa::best_finalized(), Some((0, genesis.hash())));
+		assert_eq!(CurrentAuthorities::<Test>::get().to_vec(), to_authorities(&AUTHORITIES));
+		System::assert_last_event(Event::Initialized { number: 0, hash: genesis.hash() }.into());
+
+		assert_noop!(
+			BridgeGrandpa::initialize(RuntimeOrigin::root(), Box::new(init_data)),
+			Error::<Test>::AlreadyInitialized,
+		);
+	});
+}
+
+#[test]
+fn initialize_rejects_invalid_authority_sets() {
+	new_test_ext().execute_with(|| {
+		let init_data = |authority_list| InitializationData {
+			header: test_header(0, Default::default(), Default::default()),
+			authority_list,
+			set_id: 0,
+		};
+		assert_noop!(
+			BridgeGrandpa::initialize(RuntimeOrigin::root(), Box::new(init_data(vec![]))),
+			Error::<Test>::InvalidAuthoritySet,
+		);
+		let weightless = to_authorities(&AUTHORITIES).into_iter().map(|(id, _)| (id, 0)).collect();
+		assert_noop!(
+			BridgeGrandpa::initialize(RuntimeOrigin::root(), Box::new(init_data(weightless))),
+			Error::<Test>::InvalidAuthoritySet,
+		);
+		let too_many = to_authorities(&Ed25519Keyring::iter().take(6).collect::<Vec<_>>());
+		assert_noop!(
+			BridgeGrandpa::initialize(RuntimeOrigin::root(), Box::new(init_data(too_many))),
+			Error::<Test>::InvalidAuthoritySet,
+		);
+	});
+}
+
+#[test]
+fn submit_finality_proof_imports_header() {
+	new_test_ext().execute_with(|| {
+		let header = test_header(1, Default::default(), Default::default());
+		let justification = make_justification(&header, vec![], 0, &AUTHORITIES);
+		assert_noop!(submit(&header, justification.clone()), Error::<Test>::NotInitialized);
+
+		let genesis = initialize();
+		let header = test_header(1, genesis.hash(), Default::default());
+		let justification = make_justification(&header, vec![], 0, &AUTHORITIES);
+		assert_ok!(submit(&header, justification.clone()));
+
+		assert_eq!(BridgeGrandpa::best_finalized(), Some((1, header.hash())));
+		assert_eq!(
+			ImportedHeaders::<Test>::get(header.hash()),
+			Some(StoredHeaderData { number: 1, state_root: *header.state_root() }),
+		);
+		System::assert_last_event(Event::HeaderImported { number: 1, hash: header.hash() }.into());
+
+		// the same header can't be imported twice.
+		assert_noop!(submit(&header, justification), Error::<Test>::OldHeader);
+	});
+}
+
+#[test]
+fn submit_finality_proof_rejects_invalid_justifications() {
+	new_test_ext().execute_with(|| {
+		let genesis = initialize();
+		let header = test_header(1, genesis.hash(), Default::default());
+
+		// two out of three is not a supermajority.
+		let justification = make_justification(&header, vec![], 0, &AUTHORITIES[..2]);
+		assert_noop!(submit(&header, justification), Error::<Test>::InvalidJustification);
+
+		// signed for another set.
+		let justification = make_justification(&header, vec![], 1, &AUTHORITIES);
+		assert_noop!(submit(&header, justification), Error::<Test>::InvalidJustification);
+
+		// justifying another header.
+		let other = test_header(2, genesis.hash(), Default::default());
+		let justification = make_justification(&other, vec![], 0, &AUTHORITIES);
+		assert_noop!(submit(&header, justification), Error::<Test>::InvalidJustification);
+	});
+}
+
+#[test]
+fn verify_justification_works() {
+	let header = test_header(1, Default::default(), Default::default());
+	let child = test_header(2, header.hash(), Default::default());
+	let grandchild = test_header(3, child.hash(), Default::default());
+	let authorities = to_authorities(&AUTHORITIES);
+	let target = (header.hash(), 1);
+	let verify = |justification: GrandpaJustification<Header>| {
+		verify_justification::<Header>(target, &authorities, 0, &justification)
+	};
+
+	assert_eq!(verify(make_justification(&header, vec![], 0, &AUTHORITIES)), Ok(()));
+	let ancestry = vec![child.clone(), grandchild];
+	assert_eq!(verify(make_justification(&header, ancestry, 0, &AUTHORITIES)), Ok(()));
+
+	let justification = make_justification(&child, vec![], 0, &AUTHORITIES);
+	assert_eq!(verify(justification), Err(JustificationError::InvalidTarget));
+
+	let mut justification = make_justification(&header, vec![], 0, &AUTHORITIES);
+	justification.commit.precommits[0].signature = Ed25519Keyring::Alice.sign(b"other").into();
+	assert_eq!(verify(justification), Err(JustificationError::InvalidSignature));
+
+	let mut justification = make_justification(&header, vec![], 0, &AUTHORITIES);
+	justification.commit.precommits[2] = justification.commit.precommits[0].clone();
+	assert_eq!(verify(justification), Err(JustificationError::DuplicateAuthority));
+
+	let signers = [Ed25519Keyring::Alice, Ed25519Keyring::Bob, Ed25519Keyring::Dave];
+	let justification = make_justification(&header, vec![], 0, &signers);
+	assert_eq!(verify(justification), Err(JustificationError::UnknownAuthority));
+
+	let mut justification = make_justification(&header, vec![child.clone()], 0, &AUTHORITIES);
+	justification.votes_ancestries.clear();
+	assert_eq!(verify(justification), Err(JustificationError::UnrelatedAncestry));
+
+	let mut justification = make_justification(&header, vec![], 0, &AUTHORITIES);
+	justification.votes_ancestries.push(child);
+	assert_eq!(verify(justification), Err(JustificationError::RedundantAncestry));
+
+	let justification = make_justification(&header, vec![], 0, &AUTHORITIES[..2]);
+	assert_eq!(verify(justification), Err(JustificationError::NotEnoughWeight));
+
+	// a set without weight doesn't justify anything, not even with an empty commit.
+	let weightless: Vec<_> = authorities.iter().map(|(id, _)| (id.clone(), 0)).collect();
+	let justification = make_justification(&header, vec![], 0, &[]);
+	assert_eq!(
+		verify_justification::<Header>(target, &weightless, 0, &justification),
+		Err(JustificationError::NotEnoughWeight),
+	);
+}
+
+#[test]
+fn authority_set_changes_are_followed() {
+	new_test_ext().execute_with(|| {
+		let genesis = initialize();
+		let next = [Ed25519Keyring::Dave, Ed25519Keyring::Eve];
+
+		// delayed changes can't be followed.
+		let header = test_header(1, genesis.hash(), change_digest(&next, 1));
+		let justification = make_justification(&header, vec![], 0, &AUTHORITIES);
+		assert_noop!(submit(&header, justification), Error::<Test>::UnsupportedAuthoritySetChange);
+
+		let header = test_header(1, genesis.hash(), change_digest(&next, 0));
+		let justification = make_justification(&header, vec![], 0, &AUTHORITIES);
+		assert_ok!(submit(&header, justification));
+		assert_eq!(CurrentSetId::<Test>::get(), 1);
+		assert_eq!(CurrentAuthorities::<Test>::get().to_vec(), to_authorities(&next));
+		System::assert_has_event(Event::AuthoritySetChanged { set_id: 1 }.into());
+
+		// the previous set can't finalize anything anymore.
+		let child = test_header(2, header.hash(), Default::default());
+		let justification = make_justification(&child, vec![], 0, &AUTHORITIES);
+		assert_noop!(submit(&child, justification), Error::<Test>::InvalidJustification);
+
+		let justification = make_justification(&child, vec![], 1, &next);
+		assert_ok!(submit(&child, justification));
+		assert_eq!(BridgeGrandpa::best_finalized(), Some((2, child.hash())));
+	});
+}
+
+#[test]
+fn weightless_authority_set_changes_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let genesis = initialize();
+		let next_authorities = to_authorities(&[Ed25519Keyring::Dave, Ed25519Keyring::Eve])
+			.into_iter()
+			.map(|(id, _)| (id, 0))
+			.collect();
+		let change = ConsensusLog::ScheduledChange(ScheduledChange { next_authorities, delay: 0 });
+		let digest =
+			Digest { logs: vec![DigestItem::Consensus(GRANDPA_ENGINE_ID, change.encode())] };
+
+		let header = test_header(1, genesis.hash(), digest);
+		let justification = make_justification(&header, vec![], 0, &AUTHORITIES);
+		assert_noop!(submit(&header, justification), Error::<Test>::InvalidAuthoritySet);
+		assert_eq!(CurrentAuthorities::<Test>::get().to_vec(), to_authorities(&AUTHORITIES));
+	});
+}
+
+#[test]
+fn old_headers_are_pruned() {
+	new_test_ext().execute_with(|| {
+		let genesis = initialize();
+		let mut headers = vec![genesis];
+		for number in 1..5 {
+			let header = test_header(number, headers.last().unwrap().hash(), Default::default());
+			let justification = make_justification(&header, vec![], 0, &AUTHORITIES);
+			assert_ok!(submit(&header, justification));
+			headers.push(header);
+		}
+
+		// only the last `HeadersToKeep` headers are kept.
+		let kept: Vec<_> = headers
+			.iter()
+			.map(|header| ImportedHeaders::<Test>::contains_key(header.hash()))
+			.collect();
+		assert_eq!(kept, vec![false, false, true, true, true]);
+	});
+}
+
+#[test]
+fn verify_storage_proof_works() {
+	new_test_ext().execute_with(|| {
+		let mut db = MemoryDB::<BlakeTwo256>::default();
+		let mut state_root = Default::default();
+		{
+			let mut trie = TrieDBMutBuilderV1::new(&mut db, &mut state_root).build();
+			trie.insert(b"key", b"value").unwrap();
+			trie.insert(b"other_key", b"other_value").unwrap();
+		}
+		let mut recorder = Recorder::<LayoutV1<BlakeTwo256>>::new();
+		{
+			let trie = TrieDBBuilder::new(&db, &state_root).with_recorder(&mut recorder).build();
+			trie.get(b"key").unwrap();
+		}
+		let proof = StorageProof::new(recorder.drain().into_iter().map(|record| record.data));
+
+		let genesis = initialize();
+		let header =
+			Header::new(1, Default::default(), state_root, genesis.hash(), Default::default());
+		assert_noop!(
+			BridgeGrandpa::verify_storage_proof(header.hash(), proof.clone(), b"key"),
+			Error::<Test>::UnknownHeader,
+		);
+
+		let justification = make_justification(&header, vec![], 0, &AUTHORITIES);
+		assert_ok!(submit(&header, justification));
+		assert_eq!(
+			BridgeGrandpa::verify_storage_proof(header.hash(), proof, b"key"),
+			Ok(Some(b"value".to_vec())),
+		);
+		assert_noop!(
+			BridgeGrandpa::verify_storage_proof(header.hash(), StorageProof::empty(), b"key"),
+			Error::<Test>::InvalidStorageProof,
+		);
+	});
+}
@@ -0,0 +1,104 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_bridge_grandpa
+//!
+//! These are estimates based on the storage accesses of each call and the cost of verifying one
+//! signature per precommit, not benchmark results. They are tracked in
+//! `docs/pending-benchmarks.md` until the benchmarks in `benchmarking.rs` are run on reference
+//! hardware.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_bridge_grandpa.
+pub trait WeightInfo {
+	fn initialize(a: u32, ) -> Weight;
+	fn submit_finality_proof(p: u32, v: u32, ) -> Weight;
+}
+
+/// Weights for pallet_bridge_grandpa using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: BridgeGrandpa BestFinalized (r:1 w:1)
+	// Storage: BridgeGrandpa ImportedHashesPointer (r:1 w:1)
+	// Storage: BridgeGrandpa ImportedHashes (r:1 w:1)
+	// Storage: BridgeGrandpa CurrentAuthorities (r:0 w:1)
+	// Storage: BridgeGrandpa CurrentSetId (r:0 w:1)
+	// Storage: BridgeGrandpa ImportedHeaders (r:0 w:1)
+	// The range of component `a` is `[1, 100]`.
+	fn initialize(a: u32, ) -> Weight {
+		Weight::from_parts(18_000_000, 3_501)
+			.saturating_add(Weight::from_parts(100_000, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	// Storage: BridgeGrandpa BestFinalized (r:1 w:1)
+	// Storage: BridgeGrandpa CurrentAuthorities (r:1 w:1)
+	// Storage: BridgeGrandpa CurrentSetId (r:1 w:1)
+	// Storage: BridgeGrandpa ImportedHashesPointer (r:1 w:1)
+	// Storage: BridgeGrandpa ImportedHashes (r:1 w:1)
+	// Storage: BridgeGrandpa ImportedHeaders (r:0 w:2)
+	// The range of component `p` is `[1, 100]`.
+	// The range of component `v` is `[0, 100]`.
+	fn submit_finality_proof(p: u32, v: u32, ) -> Weight {
+		Weight::from_parts(13_000_000, 5_486)
+			.saturating_add(Weight::from_parts(62_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(v.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(Weight::from_parts(0, 40).saturating_mul(p.into()))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: BridgeGrandpa BestFinalized (r:1 w:1)
+	// Storage: BridgeGrandpa ImportedHashesPointer (r:1 w:1)
+	// Storage: BridgeGrandpa ImportedHashes (r:1 w:1)
+	// Storage: BridgeGrandpa CurrentAuthorities (r:0 w:1)
+	// Storage: BridgeGrandpa CurrentSetId (r:0 w:1)
+	// Storage: BridgeGrandpa ImportedHeaders (r:0 w:1)
+	// The range of component `a` is `[1, 100]`.
+	fn initialize(a: u32, ) -> Weight {
+		Weight::from_parts(18_000_000, 3_501)
+			.saturating_add(Weight::from_parts(100_000, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	// Storage: BridgeGrandpa BestFinalized (r:1 w:1)
+	// Storage: BridgeGrandpa CurrentAuthorities (r:1 w:1)
+	// Storage: BridgeGrandpa CurrentSetId (r:1 w:1)
+	// Storage: BridgeGrandpa ImportedHashesPointer (r:1 w:1)
+	// Storage: BridgeGrandpa ImportedHashes (r:1 w:1)
+	// Storage: BridgeGrandpa ImportedHeaders (r:0 w:2)
+	// The range of component `p` is `[1, 100]`.
+	// The range of component `v` is `[0, 100]`.
+	fn submit_finality_proof(p: u32, v: u32, ) -> Weight {
+		Weight::from_parts(13_000_000, 5_486)
+			.saturating_add(Weight::from_parts(62_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(v.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(Weight::from_parts(0, 40).saturating_mul(p.into()))
+	}
+}
@@ -125,6 +125,7 @@ impl pallet_assets::Config for Runtime {
 	type AssetAccountDeposit = AssetAccountDeposit;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = AssetsStringLimit;
+	type MaxBatch = ConstU32<10>;
 	type Freezer = ();
 	type Extra = ();
 	type WeightInfo = ();
@@ -331,6 +332,9 @@ impl pallet_timestamp::Config for Runtime {
 	type Moment = u64;
 	type OnTimestampSet = ();
 	type MinimumPeriod = ConstU64<1>;
+	type ExpectedTimestamp = ();
+	type MaxTimestampDrift = ();
+	type OnTimestampDrift = ();
 	type WeightInfo = ();
 }
 
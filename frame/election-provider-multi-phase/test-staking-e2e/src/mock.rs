@@ -123,6 +123,9 @@ impl pallet_timestamp::Config for Runtime {
 	type Moment = Moment;
 	type OnTimestampSet = ();
 	type MinimumPeriod = traits::ConstU32<5>;
+	type ExpectedTimestamp = ();
+	type MaxTimestampDrift = ();
+	type OnTimestampDrift = ();
 	type WeightInfo = ();
 }
 
@@ -140,6 +143,9 @@ sp_runtime::impl_opaque_keys! {
 impl pallet_session::Config for Runtime {
 	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Runtime, Staking>;
 	type Keys = SessionKeys;
+	type InactiveKeysExpiry = ();
+	type ValidatorIntent = ();
+	type MaxKeysPurgedPerSession = ConstU32<100>;
 	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
 	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
 	type SessionHandler = (OtherSessionHandler,);
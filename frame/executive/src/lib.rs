@@ -579,17 +579,36 @@ where
 			}
 		});
 
+		Self::ensure_class_limits();
+
 		// post-extrinsics book-keeping
 		<frame_system::Pallet<System>>::note_finished_extrinsics();
 
 		Self::idle_and_finalize_hook(block_number);
 	}
 
+	/// Panics if the applied extrinsics exceed the `max_total` weight of their dispatch class.
+	///
+	/// Runs on both the import and the authoring path, so authors don't produce blocks which
+	/// are refused by their peers.
+	fn ensure_class_limits() {
+		let block_weights = <System::BlockWeights as frame_support::traits::Get<_>>::get();
+		if let Err(class) =
+			block_weights.check_consumed(&<frame_system::Pallet<System>>::block_weight())
+		{
+			panic!(
+				"Block weight of {:?} extrinsics must not exceed the limits of the class.",
+				class
+			)
+		}
+	}
+
 	/// Finalize the block - it is up the caller to ensure that all header fields are valid
 	/// except state-root.
 	pub fn finalize_block() -> frame_system::pallet_prelude::HeaderFor<System> {
 		sp_io::init_tracing();
 		sp_tracing::enter_span!(sp_tracing::Level::TRACE, "finalize_block");
+		Self::ensure_class_limits();
 		<frame_system::Pallet<System>>::note_finished_extrinsics();
 		let block_number = <frame_system::Pallet<System>>::block_number();
 
@@ -846,6 +865,15 @@ mod tests {
 				Ok(())
 			}
 
+			pub fn register_normal_weight(origin: OriginFor<T>, weight: Weight) -> DispatchResult {
+				frame_system::ensure_signed(origin)?;
+				frame_system::Pallet::<T>::register_extra_weight_unchecked(
+					weight,
+					DispatchClass::Normal,
+				);
+				Ok(())
+			}
+
 			pub fn calculate_storage_root(_origin: OriginFor<T>) -> DispatchResult {
 				let root = sp_io::storage::root(sp_runtime::StateVersion::V1);
 				sp_io::storage::set("storage_root".as_bytes(), &root);
@@ -971,6 +999,7 @@ mod tests {
 		type WeightToFee = IdentityFee<Balance>;
 		type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
 		type FeeMultiplierUpdate = ();
+		type PriorityCurve = pallet_transaction_payment::TipPerResourcePriority;
 	}
 	impl custom::Config for Runtime {}
 
@@ -1684,6 +1713,51 @@ mod tests {
 		});
 	}
 
+	#[test]
+	#[should_panic(
+		expected = "Block weight of Normal extrinsics must not exceed the limits of the class."
+	)]
+	fn block_exceeding_class_limits_fails() {
+		let xt = TestXt::new(
+			RuntimeCall::Custom(custom::Call::register_normal_weight {
+				weight: Weight::from_parts(1024, 0),
+			}),
+			sign_extra(1, 0, 0),
+		);
+
+		new_test_ext(1).execute_with(|| {
+			Executive::execute_block(Block::new(
+				Header::new(
+					1,
+					H256::default(),
+					H256::default(),
+					[69u8; 32].into(),
+					Digest::default(),
+				),
+				vec![xt],
+			));
+		});
+	}
+
+	#[test]
+	#[should_panic(
+		expected = "Block weight of Normal extrinsics must not exceed the limits of the class."
+	)]
+	fn authoring_block_exceeding_class_limits_fails() {
+		let xt = TestXt::new(
+			RuntimeCall::Custom(custom::Call::register_normal_weight {
+				weight: Weight::from_parts(1024, 0),
+			}),
+			sign_extra(1, 0, 0),
+		);
+
+		new_test_ext(1).execute_with(|| {
+			Executive::initialize_block(&Header::new_from_number(1));
+			Executive::apply_extrinsic(xt).unwrap().unwrap();
+			Executive::finalize_block();
+		});
+	}
+
 	// Inherents are created by the runtime and don't need to be validated.
 	#[test]
 	fn inherents_fail_validate_block() {
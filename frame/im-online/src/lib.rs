@@ -303,6 +303,15 @@ pub mod pallet {
 		#[pallet::constant]
 		type UnsignedPriority: Get<TransactionPriority>;
 
+		/// The fraction of the validator set that can be offline in a session before the
+		/// unresponsive validators are slashed.
+		#[pallet::constant]
+		type OfflineThreshold: Get<Perbill>;
+
+		/// The largest fraction that unresponsive validators can be slashed by.
+		#[pallet::constant]
+		type MaxOfflineSlash: Get<Perbill>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -804,7 +813,13 @@ impl<T: Config> OneSessionHandler<T::AccountId> for Pallet<T> {
 			Self::deposit_event(Event::<T>::SomeOffline { offline: offenders.clone() });
 
 			let validator_set_count = keys.len() as u32;
-			let offence = UnresponsivenessOffence { session_index, validator_set_count, offenders };
+			let offence = UnresponsivenessOffence {
+				session_index,
+				validator_set_count,
+				offenders,
+				offline_threshold: T::OfflineThreshold::get(),
+				max_slash: T::MaxOfflineSlash::get(),
+			};
 			if let Err(e) = T::ReportUnresponsiveness::report_offence(vec![], offence) {
 				sp_runtime::print(e);
 			}
@@ -829,6 +844,10 @@ pub struct UnresponsivenessOffence<Offender> {
 	pub validator_set_count: u32,
 	/// Authorities that were unresponsive during the current era.
 	pub offenders: Vec<Offender>,
+	/// The fraction of the validator set that can be offline without being slashed.
+	pub offline_threshold: Perbill,
+	/// The largest fraction the offenders can be slashed by.
+	pub max_slash: Perbill,
 }
 
 impl<Offender: Clone> Offence<Offender> for UnresponsivenessOffence<Offender> {
@@ -856,12 +875,14 @@ impl<Offender: Clone> Offence<Offender> for UnresponsivenessOffence<Offender> {
 	}
 
 	fn slash_fraction(&self, offenders: u32) -> Perbill {
-		// the formula is min((3 * (k - (n / 10 + 1))) / n, 1) * 0.07
-		// basically, 10% can be offline with no slash, but after that, it linearly climbs up to 7%
-		// when 13/30 are offline (around 5% when 1/3 are offline).
-		if let Some(threshold) = offenders.checked_sub(self.validator_set_count / 10 + 1) {
+		// the formula is min((3 * (k - (n * t + 1))) / n, 1) * s, with `t` the offline threshold
+		// and `s` the maximum slash. e.g. with a threshold of 10% and a maximum slash of 7%, 10%
+		// can be offline with no slash, but after that, it linearly climbs up to 7% when 13/30
+		// are offline (around 5% when 1/3 are offline).
+		let free_offenders = self.offline_threshold.mul_floor(self.validator_set_count);
+		if let Some(threshold) = offenders.checked_sub(free_offenders + 1) {
 			let x = Perbill::from_rational(3 * threshold, self.validator_set_count);
-			x.saturating_mul(Perbill::from_percent(7))
+			x.saturating_mul(self.max_slash)
 		} else {
 			Perbill::default()
 		}
@@ -29,7 +29,7 @@ use sp_core::H256;
 use sp_runtime::{
 	testing::{TestXt, UintAuthorityId},
 	traits::{BlakeTwo256, ConvertInto, IdentityLookup},
-	BuildStorage, Permill,
+	BuildStorage, Perbill, Permill,
 };
 use sp_staking::{
 	offence::{OffenceError, ReportOffence},
@@ -106,7 +106,9 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	result.execute_with(|| {
 		for i in 1..=6 {
 			System::inc_providers(&i);
-			assert_eq!(Session::set_keys(RuntimeOrigin::signed(i), (i - 1).into(), vec![]), Ok(()));
+			let keys = UintAuthorityId(i - 1);
+			let proof = Session::generate_ownership_proof(&i, &keys).unwrap();
+			assert_eq!(Session::set_keys(RuntimeOrigin::signed(i), keys, proof), Ok(()));
 		}
 	});
 	result
@@ -152,6 +154,9 @@ impl pallet_session::Config for Runtime {
 	type ValidatorId = u64;
 	type ValidatorIdOf = ConvertInto;
 	type Keys = UintAuthorityId;
+	type InactiveKeysExpiry = ();
+	type ValidatorIntent = ();
+	type MaxKeysPurgedPerSession = ConstU32<100>;
 	type RuntimeEvent = RuntimeEvent;
 	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
 	type WeightInfo = ();
@@ -202,6 +207,11 @@ impl frame_support::traits::EstimateNextSessionRotation<u64> for TestNextSession
 	}
 }
 
+parameter_types! {
+	pub const OfflineThreshold: Perbill = Perbill::from_percent(10);
+	pub const MaxOfflineSlash: Perbill = Perbill::from_percent(7);
+}
+
 impl Config for Runtime {
 	type AuthorityId = UintAuthorityId;
 	type RuntimeEvent = RuntimeEvent;
@@ -209,6 +219,8 @@ impl Config for Runtime {
 	type NextSessionRotation = TestNextSessionRotation;
 	type ReportUnresponsiveness = OffenceHandler;
 	type UnsignedPriority = ConstU64<{ 1 << 20 }>;
+	type OfflineThreshold = OfflineThreshold;
+	type MaxOfflineSlash = MaxOfflineSlash;
 	type WeightInfo = ();
 	type MaxKeys = ConstU32<10_000>;
 	type MaxPeerInHeartbeats = ConstU32<10_000>;
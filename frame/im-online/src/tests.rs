@@ -33,8 +33,13 @@ use sp_runtime::{
 
 #[test]
 fn test_unresponsiveness_slash_fraction() {
-	let dummy_offence =
-		UnresponsivenessOffence { session_index: 0, validator_set_count: 50, offenders: vec![()] };
+	let dummy_offence = UnresponsivenessOffence {
+		session_index: 0,
+		validator_set_count: 50,
+		offenders: vec![()],
+		offline_threshold: Perbill::from_percent(10),
+		max_slash: Perbill::from_percent(7),
+	};
 	// A single case of unresponsiveness is not slashed.
 	assert_eq!(dummy_offence.slash_fraction(1), Perbill::zero());
 
@@ -58,6 +63,27 @@ fn test_unresponsiveness_slash_fraction() {
 	assert_eq!(dummy_offence.disable_strategy(), DisableStrategy::Never);
 }
 
+#[test]
+fn unresponsiveness_slash_fraction_is_configurable() {
+	let dummy_offence = UnresponsivenessOffence {
+		session_index: 0,
+		validator_set_count: 50,
+		offenders: vec![()],
+		offline_threshold: Perbill::from_percent(20),
+		max_slash: Perbill::from_percent(10),
+	};
+	// Up to a fifth can be offline without a slash.
+	assert_eq!(dummy_offence.slash_fraction(11), Perbill::zero());
+
+	assert_eq!(
+		dummy_offence.slash_fraction(20),
+		Perbill::from_parts(54000000), // 5.4%
+	);
+
+	// The slash never exceeds the maximum.
+	assert_eq!(dummy_offence.slash_fraction(50), Perbill::from_percent(10));
+}
+
 #[test]
 fn should_report_offline_validators() {
 	new_test_ext().execute_with(|| {
@@ -85,6 +111,8 @@ fn should_report_offline_validators() {
 					session_index: 2,
 					validator_set_count: 3,
 					offenders: vec![(1, 1), (2, 2), (3, 3),],
+					offline_threshold: OfflineThreshold::get(),
+					max_slash: MaxOfflineSlash::get(),
 				}
 			)]
 		);
@@ -105,6 +133,8 @@ fn should_report_offline_validators() {
 					session_index: 3,
 					validator_set_count: 6,
 					offenders: vec![(5, 5), (6, 6),],
+					offline_threshold: OfflineThreshold::get(),
+					max_slash: MaxOfflineSlash::get(),
 				}
 			)]
 		);
@@ -92,6 +92,36 @@ benchmarks! {
 		assert_eq!(Accounts::<T>::get(account_index).unwrap().2, true);
 	}
 
+	offer {
+		let account_index = T::AccountIndex::from(SEED);
+		// Setup accounts
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+		// Claim the index
+		Indices::<T>::claim(RawOrigin::Signed(caller.clone()).into(), account_index)?;
+		let price = T::Currency::minimum_balance();
+	}: _(RawOrigin::Signed(caller.clone()), account_index, Some(price))
+	verify {
+		assert_eq!(Offers::<T>::get(account_index), Some(price));
+	}
+
+	buy {
+		let account_index = T::AccountIndex::from(SEED);
+		// Setup accounts
+		let seller: T::AccountId = account("seller", 0, SEED);
+		T::Currency::make_free_balance_be(&seller, BalanceOf::<T>::max_value() / 2u32.into());
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+		// Claim the index and put it up for sale
+		Indices::<T>::claim(RawOrigin::Signed(seller.clone()).into(), account_index)?;
+		let price = T::Currency::minimum_balance();
+		Indices::<T>::offer(RawOrigin::Signed(seller).into(), account_index, Some(price))?;
+	}: _(RawOrigin::Signed(caller.clone()), account_index, price)
+	verify {
+		assert_eq!(Accounts::<T>::get(account_index).unwrap().0, caller);
+		assert_eq!(Offers::<T>::get(account_index), None);
+	}
+
 	// TODO in another PR: lookup and unlookup trait weights (not critical)
 
 	impl_benchmark_test_suite!(Indices, crate::mock::new_test_ext(), crate::mock::Test);
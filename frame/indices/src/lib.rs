@@ -26,7 +26,9 @@ mod tests;
 pub mod weights;
 
 use codec::Codec;
-use frame_support::traits::{BalanceStatus::Reserved, Currency, ReservableCurrency};
+use frame_support::traits::{
+	BalanceStatus::Reserved, Currency, ExistenceRequirement::KeepAlive, ReservableCurrency,
+};
 use sp_runtime::{
 	traits::{AtLeast32Bit, LookupError, Saturating, StaticLookup, Zero},
 	MultiAddress,
@@ -118,7 +120,10 @@ pub mod pallet {
 		/// ## Complexity
 		/// - `O(1)`.
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::transfer())]
+		#[pallet::weight(
+			// The removal of any offer for the index is not benchmarked yet.
+			T::WeightInfo::transfer().saturating_add(T::DbWeight::get().writes(1))
+		)]
 		pub fn transfer(
 			origin: OriginFor<T>,
 			new: AccountIdLookupOf<T>,
@@ -136,6 +141,7 @@ pub mod pallet {
 				*maybe_value = Some((new.clone(), amount.saturating_sub(lost), false));
 				Ok(())
 			})?;
+			Offers::<T>::remove(index);
 			Self::deposit_event(Event::IndexAssigned { who: new, index });
 			Ok(())
 		}
@@ -153,7 +159,10 @@ pub mod pallet {
 		/// ## Complexity
 		/// - `O(1)`.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::free())]
+		#[pallet::weight(
+			// The removal of any offer for the index is not benchmarked yet.
+			T::WeightInfo::free().saturating_add(T::DbWeight::get().writes(1))
+		)]
 		pub fn free(origin: OriginFor<T>, index: T::AccountIndex) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
@@ -164,6 +173,7 @@ pub mod pallet {
 				T::Currency::unreserve(&who, amount);
 				Ok(())
 			})?;
+			Offers::<T>::remove(index);
 			Self::deposit_event(Event::IndexFreed { index });
 			Ok(())
 		}
@@ -182,7 +192,10 @@ pub mod pallet {
 		/// ## Complexity
 		/// - `O(1)`.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::force_transfer())]
+		#[pallet::weight(
+			// The removal of any offer for the index is not benchmarked yet.
+			T::WeightInfo::force_transfer().saturating_add(T::DbWeight::get().writes(1))
+		)]
 		pub fn force_transfer(
 			origin: OriginFor<T>,
 			new: AccountIdLookupOf<T>,
@@ -198,6 +211,7 @@ pub mod pallet {
 				}
 				*maybe_value = Some((new.clone(), Zero::zero(), freeze));
 			});
+			Offers::<T>::remove(index);
 			Self::deposit_event(Event::IndexAssigned { who: new, index });
 			Ok(())
 		}
@@ -215,7 +229,10 @@ pub mod pallet {
 		/// ## Complexity
 		/// - `O(1)`.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::freeze())]
+		#[pallet::weight(
+			// The removal of any offer for the index is not benchmarked yet.
+			T::WeightInfo::freeze().saturating_add(T::DbWeight::get().writes(1))
+		)]
 		pub fn freeze(origin: OriginFor<T>, index: T::AccountIndex) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
@@ -227,9 +244,94 @@ pub mod pallet {
 				*maybe_value = Some((account, Zero::zero(), true));
 				Ok(())
 			})?;
+			Offers::<T>::remove(index);
 			Self::deposit_event(Event::IndexFrozen { index, who });
 			Ok(())
 		}
+
+		/// Put an index owned by the sender up for sale, or withdraw it from sale.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must own the index.
+		///
+		/// - `index`: the index to be offered. This must be owned by the sender and not frozen.
+		/// - `price`: the price any account may pay to take over the index, or `None` to withdraw
+		///   an existing offer.
+		///
+		/// Emits `IndexOffered` or `OfferWithdrawn` if successful.
+		///
+		/// ## Complexity
+		/// - `O(1)`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(
+			// `freeze` does the same checks and a single write.
+			T::WeightInfo::freeze()
+		)]
+		pub fn offer(
+			origin: OriginFor<T>,
+			index: T::AccountIndex,
+			price: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let (account, _, perm) = Accounts::<T>::get(index).ok_or(Error::<T>::NotAssigned)?;
+			ensure!(!perm, Error::<T>::Permanent);
+			ensure!(account == who, Error::<T>::NotOwner);
+			match price {
+				Some(price) => {
+					Offers::<T>::insert(index, price);
+					Self::deposit_event(Event::IndexOffered { index, price });
+				},
+				None => {
+					ensure!(Offers::<T>::take(index).is_some(), Error::<T>::NotForSale);
+					Self::deposit_event(Event::OfferWithdrawn { index });
+				},
+			}
+			Ok(())
+		}
+
+		/// Buy an index which has been put up for sale by its owner.
+		///
+		/// Payment: The asking price is transferred to the current owner, whose deposit is
+		/// unreserved. `Deposit` is reserved from the sender account.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `index`: the index to be bought. This must be up for sale.
+		/// - `max_price`: the most the sender is willing to pay, protecting them against the owner
+		///   raising the price in the meantime.
+		///
+		/// Emits `IndexSold` if successful.
+		///
+		/// ## Complexity
+		/// - `O(1)`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(
+			// `transfer` moves the index and its deposit between two accounts, on top of which
+			// `buy` transfers the price and takes the offer.
+			T::WeightInfo::transfer().saturating_mul(2)
+		)]
+		pub fn buy(
+			origin: OriginFor<T>,
+			index: T::AccountIndex,
+			max_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let price = Offers::<T>::get(index).ok_or(Error::<T>::NotForSale)?;
+			ensure!(price <= max_price, Error::<T>::PriceTooHigh);
+
+			let (seller, amount, perm) =
+				Accounts::<T>::get(index).ok_or(Error::<T>::NotAssigned)?;
+			ensure!(!perm, Error::<T>::Permanent);
+			ensure!(seller != who, Error::<T>::NotTransfer);
+
+			T::Currency::transfer(&who, &seller, price, KeepAlive)?;
+			T::Currency::reserve(&who, T::Deposit::get())?;
+			T::Currency::unreserve(&seller, amount);
+			Accounts::<T>::insert(index, (who.clone(), T::Deposit::get(), false));
+			Offers::<T>::remove(index);
+			Self::deposit_event(Event::IndexSold { index, seller, buyer: who, price });
+			Ok(())
+		}
 	}
 
 	#[pallet::event]
@@ -241,6 +343,17 @@ pub mod pallet {
 		IndexFreed { index: T::AccountIndex },
 		/// A account index has been frozen to its current account ID.
 		IndexFrozen { index: T::AccountIndex, who: T::AccountId },
+		/// A account index was put up for sale.
+		IndexOffered { index: T::AccountIndex, price: BalanceOf<T> },
+		/// A account index is no longer for sale.
+		OfferWithdrawn { index: T::AccountIndex },
+		/// A account index was sold.
+		IndexSold {
+			index: T::AccountIndex,
+			seller: T::AccountId,
+			buyer: T::AccountId,
+			price: BalanceOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -255,6 +368,10 @@ pub mod pallet {
 		NotTransfer,
 		/// The index is permanent and may not be freed/changed.
 		Permanent,
+		/// The index is not for sale.
+		NotForSale,
+		/// The asking price is above the maximum the buyer is willing to pay.
+		PriceTooHigh,
 	}
 
 	/// The lookup from index to account.
@@ -262,6 +379,10 @@ pub mod pallet {
 	pub type Accounts<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountIndex, (T::AccountId, BalanceOf<T>, bool)>;
 
+	/// The asking price of the indices which are up for sale.
+	#[pallet::storage]
+	pub type Offers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountIndex, BalanceOf<T>>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
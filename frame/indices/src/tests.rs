@@ -119,3 +119,57 @@ fn force_transfer_index_on_free_should_work() {
 		assert_eq!(Indices::lookup_index(0), Some(3));
 	});
 }
+
+#[test]
+fn offering_index_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Indices::claim(Some(1).into(), 0));
+		assert_noop!(Indices::offer(Some(1).into(), 1, Some(5)), Error::<Test>::NotAssigned);
+		assert_noop!(Indices::offer(Some(2).into(), 0, Some(5)), Error::<Test>::NotOwner);
+		assert_noop!(Indices::offer(Some(1).into(), 0, None), Error::<Test>::NotForSale);
+		assert_ok!(Indices::offer(Some(1).into(), 0, Some(5)));
+		assert_eq!(Offers::<Test>::get(0), Some(5));
+		assert_ok!(Indices::offer(Some(1).into(), 0, None));
+		assert_eq!(Offers::<Test>::get(0), None);
+
+		assert_ok!(Indices::freeze(Some(1).into(), 0));
+		assert_noop!(Indices::offer(Some(1).into(), 0, Some(5)), Error::<Test>::Permanent);
+	});
+}
+
+#[test]
+fn buying_index_should_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Indices::claim(Some(1).into(), 0));
+		assert_noop!(Indices::buy(Some(2).into(), 0, 5), Error::<Test>::NotForSale);
+		assert_ok!(Indices::offer(Some(1).into(), 0, Some(5)));
+		assert_noop!(Indices::buy(Some(2).into(), 0, 4), Error::<Test>::PriceTooHigh);
+		assert_noop!(Indices::buy(Some(1).into(), 0, 5), Error::<Test>::NotTransfer);
+
+		assert_ok!(Indices::buy(Some(2).into(), 0, 5));
+		assert_eq!(Indices::lookup_index(0), Some(2));
+		assert_eq!(Offers::<Test>::get(0), None);
+		assert_eq!(Balances::free_balance(1), 15);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(2), 14);
+		assert_eq!(Balances::reserved_balance(2), 1);
+		System::assert_last_event(
+			Event::IndexSold { index: 0, seller: 1, buyer: 2, price: 5 }.into(),
+		);
+	});
+}
+
+#[test]
+fn offers_are_cleared_when_index_changes_hands() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Indices::claim(Some(1).into(), 0));
+		assert_ok!(Indices::offer(Some(1).into(), 0, Some(5)));
+		assert_ok!(Indices::transfer(Some(1).into(), Id(3), 0));
+		assert_noop!(Indices::buy(Some(2).into(), 0, 5), Error::<Test>::NotForSale);
+
+		assert_ok!(Indices::offer(Some(3).into(), 0, Some(5)));
+		assert_ok!(Indices::free(Some(3).into(), 0));
+		assert_eq!(Offers::<Test>::get(0), None);
+	});
+}
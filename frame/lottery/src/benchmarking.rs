@@ -34,8 +34,13 @@ use frame_support::{
 use frame_system::RawOrigin;
 use sp_runtime::traits::{Bounded, Zero};
 
+// Split the pot equally between `winners` winners.
+fn equal_split(winners: u32) -> Vec<Percent> {
+	vec![Percent::from_parts((100 / winners.max(1)) as u8); winners as usize]
+}
+
 // Set up and start a lottery
-fn setup_lottery<T: Config>(repeat: bool) -> Result<(), &'static str> {
+fn setup_lottery<T: Config>(repeat: bool, winners: u32) -> Result<(), &'static str> {
 	let price = T::Currency::minimum_balance();
 	let length = 10u32.into();
 	let delay = 5u32.into();
@@ -49,7 +54,13 @@ fn setup_lottery<T: Config>(repeat: bool) -> Result<(), &'static str> {
 	let origin = T::ManagerOrigin::try_successful_origin()
 		.expect("ManagerOrigin has no successful origin required for the benchmark");
 	Lottery::<T>::set_calls(origin.clone(), calls)?;
-	Lottery::<T>::start_lottery(origin, price, length, delay, repeat)?;
+	// Excluded calls will be maximum length, but never match.
+	let excluded_calls = vec![
+		frame_system::Call::<T>::set_code { code: vec![] }.into();
+		T::MaxCalls::get() as usize
+	];
+	Lottery::<T>::set_excluded_calls(origin.clone(), excluded_calls)?;
+	Lottery::<T>::start_lottery(origin, price, length, delay, repeat, equal_split(winners))?;
 	Ok(())
 }
 
@@ -61,7 +72,7 @@ mod benchmarks {
 	fn buy_ticket() -> Result<(), BenchmarkError> {
 		let caller = whitelisted_caller();
 		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
-		setup_lottery::<T>(false)?;
+		setup_lottery::<T>(false, 1)?;
 		// force user to have a long vec of calls participating
 		let set_code_index: CallIndex = Lottery::<T>::call_to_index(
 			&frame_system::Call::<T>::set_code { code: vec![] }.into(),
@@ -113,7 +124,7 @@ mod benchmarks {
 			T::ManagerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
 
 		#[extrinsic_call]
-		_(origin as T::RuntimeOrigin, price, end, payout, true);
+		_(origin as T::RuntimeOrigin, price, end, payout, true, equal_split(T::MaxWinners::get()));
 
 		assert!(crate::Lottery::<T>::get().is_some());
 
@@ -122,7 +133,7 @@ mod benchmarks {
 
 	#[benchmark]
 	fn stop_repeat() -> Result<(), BenchmarkError> {
-		setup_lottery::<T>(true)?;
+		setup_lottery::<T>(true, 1)?;
 		assert_eq!(crate::Lottery::<T>::get().unwrap().repeat, true);
 		let origin =
 			T::ManagerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
@@ -136,80 +147,134 @@ mod benchmarks {
 	}
 
 	#[benchmark]
-	fn on_initialize_end() -> Result<(), BenchmarkError> {
-		setup_lottery::<T>(false)?;
-		let winner = account("winner", 0, 0);
-		// User needs more than min balance to get ticket
-		T::Currency::make_free_balance_be(&winner, T::Currency::minimum_balance() * 10u32.into());
+	fn on_initialize_end(w: Linear<1, { T::MaxWinners::get() }>) -> Result<(), BenchmarkError> {
+		setup_lottery::<T>(false, w)?;
 		// Make sure lottery account has at least min balance too
 		let lottery_account = Lottery::<T>::account_id();
 		T::Currency::make_free_balance_be(
 			&lottery_account,
 			T::Currency::minimum_balance() * 10u32.into(),
 		);
-		// Buy a ticket
-		let call = frame_system::Call::<T>::remark { remark: vec![] };
-		Lottery::<T>::buy_ticket(RawOrigin::Signed(winner.clone()).into(), Box::new(call.into()))?;
-		// Kill user account for worst case
-		T::Currency::make_free_balance_be(&winner, 0u32.into());
-		// Assert that lotto is set up for winner
-		assert_eq!(TicketsCount::<T>::get(), 1);
+		let winners: Vec<T::AccountId> = (0..w).map(|i| account("winner", i, 0)).collect();
+		for winner in &winners {
+			// User needs more than min balance to get ticket
+			T::Currency::make_free_balance_be(
+				winner,
+				T::Currency::minimum_balance() * 10u32.into(),
+			);
+			// Buy a ticket
+			let call = frame_system::Call::<T>::remark { remark: vec![] };
+			Lottery::<T>::buy_ticket(
+				RawOrigin::Signed(winner.clone()).into(),
+				Box::new(call.into()),
+			)?;
+			// Kill user account for worst case
+			T::Currency::make_free_balance_be(winner, 0u32.into());
+		}
+		// Assert that lotto is set up for winners
+		assert_eq!(TicketsCount::<T>::get(), w);
 		assert!(!Lottery::<T>::pot().1.is_zero());
 
+		// Start lottery has block 15 configured for payout
+		frame_system::Pallet::<T>::set_block_number(15u32.into());
+		T::BenchmarkHelper::refresh_randomness(15u32.into());
+
 		#[block]
 		{
-			// Generate `MaxGenerateRandom` numbers for worst case scenario
-			for i in 0..T::MaxGenerateRandom::get() {
+			// Generate `MaxGenerateRandom` numbers per winner for worst case scenario
+			for i in 0..T::MaxGenerateRandom::get().saturating_mul(w) {
 				Lottery::<T>::generate_random_number(i);
 			}
-			// Start lottery has block 15 configured for payout
 			Lottery::<T>::on_initialize(15u32.into());
 		}
 
 		assert!(crate::Lottery::<T>::get().is_none());
 		assert_eq!(TicketsCount::<T>::get(), 0);
-		assert_eq!(Lottery::<T>::pot().1, 0u32.into());
-		assert!(!T::Currency::free_balance(&winner).is_zero());
+		assert!(winners.iter().all(|winner| !T::Currency::free_balance(winner).is_zero()));
 
 		Ok(())
 	}
 
 	#[benchmark]
-	fn on_initialize_repeat() -> Result<(), BenchmarkError> {
-		setup_lottery::<T>(true)?;
-		let winner = account("winner", 0, 0);
-		// User needs more than min balance to get ticket
-		T::Currency::make_free_balance_be(&winner, T::Currency::minimum_balance() * 10u32.into());
+	fn on_initialize_repeat(w: Linear<1, { T::MaxWinners::get() }>) -> Result<(), BenchmarkError> {
+		setup_lottery::<T>(true, w)?;
 		// Make sure lottery account has at least min balance too
 		let lottery_account = Lottery::<T>::account_id();
 		T::Currency::make_free_balance_be(
 			&lottery_account,
 			T::Currency::minimum_balance() * 10u32.into(),
 		);
-		// Buy a ticket
-		let call = frame_system::Call::<T>::remark { remark: vec![] };
-		Lottery::<T>::buy_ticket(RawOrigin::Signed(winner.clone()).into(), Box::new(call.into()))?;
-		// Kill user account for worst case
-		T::Currency::make_free_balance_be(&winner, 0u32.into());
-		// Assert that lotto is set up for winner
-		assert_eq!(TicketsCount::<T>::get(), 1);
+		let winners: Vec<T::AccountId> = (0..w).map(|i| account("winner", i, 0)).collect();
+		for winner in &winners {
+			// User needs more than min balance to get ticket
+			T::Currency::make_free_balance_be(
+				winner,
+				T::Currency::minimum_balance() * 10u32.into(),
+			);
+			// Buy a ticket
+			let call = frame_system::Call::<T>::remark { remark: vec![] };
+			Lottery::<T>::buy_ticket(
+				RawOrigin::Signed(winner.clone()).into(),
+				Box::new(call.into()),
+			)?;
+			// Kill user account for worst case
+			T::Currency::make_free_balance_be(winner, 0u32.into());
+		}
+		// Assert that lotto is set up for winners
+		assert_eq!(TicketsCount::<T>::get(), w);
 		assert!(!Lottery::<T>::pot().1.is_zero());
 
+		// Start lottery has block 15 configured for payout
+		frame_system::Pallet::<T>::set_block_number(15u32.into());
+		T::BenchmarkHelper::refresh_randomness(15u32.into());
+
 		#[block]
 		{
-			// Generate `MaxGenerateRandom` numbers for worst case scenario
-			for i in 0..T::MaxGenerateRandom::get() {
+			// Generate `MaxGenerateRandom` numbers per winner for worst case scenario
+			for i in 0..T::MaxGenerateRandom::get().saturating_mul(w) {
 				Lottery::<T>::generate_random_number(i);
 			}
-			// Start lottery has block 15 configured for payout
 			Lottery::<T>::on_initialize(15u32.into());
 		}
 
 		assert!(crate::Lottery::<T>::get().is_some());
 		assert_eq!(LotteryIndex::<T>::get(), 2);
 		assert_eq!(TicketsCount::<T>::get(), 0);
-		assert_eq!(Lottery::<T>::pot().1, 0u32.into());
-		assert!(!T::Currency::free_balance(&winner).is_zero());
+		assert!(winners.iter().all(|winner| !T::Currency::free_balance(winner).is_zero()));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_next_round() -> Result<(), BenchmarkError> {
+		setup_lottery::<T>(true, 1)?;
+		let price = BalanceOf::<T>::max_value();
+		let length = 20u32.into();
+		let delay = 10u32.into();
+		let origin =
+			T::ManagerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, price, length, delay, equal_split(T::MaxWinners::get()));
+
+		assert!(NextRound::<T>::get().is_some());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_excluded_calls(n: Linear<0, { T::MaxCalls::get() }>) -> Result<(), BenchmarkError> {
+		let calls = vec![frame_system::Call::<T>::remark { remark: vec![] }.into(); n as usize];
+		let origin =
+			T::ManagerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		assert!(ExcludedCalls::<T>::get().is_empty());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, calls);
+
+		if !n.is_zero() {
+			assert!(!ExcludedCalls::<T>::get().is_empty());
+		}
 
 		Ok(())
 	}
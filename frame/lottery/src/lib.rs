@@ -18,9 +18,16 @@
 //! A lottery pallet that uses participation in the network to purchase tickets.
 //!
 //! With this pallet, you can configure a lottery, which is a pot of money that
-//! users contribute to, and that is reallocated to a single user at the end of
-//! the lottery period. Just like a normal lottery system, to participate, you
-//! need to "buy a ticket", which is used to fund the pot.
+//! users contribute to, and that is reallocated to one or more winners at the
+//! end of the lottery period. Just like a normal lottery system, to participate,
+//! you need to "buy a ticket", which is used to fund the pot.
+//!
+//! Each lottery has a prize split, which is the share of the pot paid to each
+//! winner, in draw order. Any part of the pot that is not paid out, because the
+//! shares add up to less than the whole pot or because fewer tickets were sold
+//! than there are prizes, stays in the pot for the next lottery. The manager can
+//! change the price, length, delay and prize split of a repeating lottery
+//! between rounds with `set_next_round`.
 //!
 //! The unique feature of this lottery system is that tickets can only be
 //! purchased by making a "valid call" dispatched through this pallet.
@@ -36,7 +43,8 @@
 //! This pallet provides one implementation of this using the `CallIndices`
 //! storage item. You can also make your own implementation at the runtime level
 //! which can contain much more complex logic, such as validation of the
-//! parameters, which this pallet alone cannot do.
+//! parameters, which this pallet alone cannot do. Calls set with
+//! `set_excluded_calls` never buy a ticket, whatever `ValidateCall` says.
 //!
 //! This pallet uses the modulus operator to pick a random winner. It is known
 //! that this might introduce a bias if the random number chosen in a range that
@@ -48,6 +56,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod benchmarking;
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -61,14 +70,15 @@ use frame_support::{
 	pallet_prelude::MaxEncodedLen,
 	storage::bounded_vec::BoundedVec,
 	traits::{Currency, ExistenceRequirement::KeepAlive, Get, Randomness, ReservableCurrency},
-	PalletId,
+	weights::Weight,
+	CloneNoBound, EqNoBound, PalletId, PartialEqNoBound, RuntimeDebugNoBound,
 };
 pub use pallet::*;
 use sp_runtime::{
 	traits::{AccountIdConversion, Dispatchable, Saturating, Zero},
-	ArithmeticError, DispatchError, RuntimeDebug,
+	ArithmeticError, DispatchError, Percent,
 };
-use sp_std::prelude::*;
+use sp_std::{fmt::Debug, prelude::*};
 pub use weights::WeightInfo;
 
 type BalanceOf<T> =
@@ -79,9 +89,23 @@ type BalanceOf<T> =
 type CallIndex = (u8, u8);
 
 #[derive(
-	Encode, Decode, Default, Eq, PartialEq, RuntimeDebug, scale_info::TypeInfo, MaxEncodedLen,
+	Encode,
+	Decode,
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	scale_info::TypeInfo,
+	MaxEncodedLen,
 )]
-pub struct LotteryConfig<BlockNumber, Balance> {
+#[scale_info(skip_type_params(MaxWinners))]
+#[codec(mel_bound(BlockNumber: MaxEncodedLen, Balance: MaxEncodedLen))]
+pub struct LotteryConfig<BlockNumber, Balance, MaxWinners>
+where
+	BlockNumber: Clone + PartialEq + Eq + Debug,
+	Balance: Clone + PartialEq + Eq + Debug,
+	MaxWinners: Get<u32>,
+{
 	/// Price per entry.
 	price: Balance,
 	/// Starting block of the lottery.
@@ -89,10 +113,42 @@ pub struct LotteryConfig<BlockNumber, Balance> {
 	/// Length of the lottery (start + length = end).
 	length: BlockNumber,
 	/// Delay for choosing the winner of the lottery. (start + length + delay = payout).
-	/// Randomness in the "payout" block will be used to determine the winner.
+	/// Randomness in the "payout" block will be used to determine the winner, unless it was
+	/// already determinable before the lottery ended, in which case the payout is postponed.
 	delay: BlockNumber,
 	/// Whether this lottery will repeat after it completes.
 	repeat: bool,
+	/// The share of the pot paid to each winner, in draw order.
+	prize_split: BoundedVec<Percent, MaxWinners>,
+}
+
+/// Changes to apply to a repeating lottery when its next round starts.
+#[derive(
+	Encode,
+	Decode,
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	scale_info::TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(MaxWinners))]
+#[codec(mel_bound(BlockNumber: MaxEncodedLen, Balance: MaxEncodedLen))]
+pub struct NextRoundConfig<BlockNumber, Balance, MaxWinners>
+where
+	BlockNumber: Clone + PartialEq + Eq + Debug,
+	Balance: Clone + PartialEq + Eq + Debug,
+	MaxWinners: Get<u32>,
+{
+	/// Price per entry in the next round.
+	price: Balance,
+	/// Length of the next round.
+	length: BlockNumber,
+	/// Delay for choosing the winners of the next round.
+	delay: BlockNumber,
+	/// The share of the pot paid to each winner of the next round.
+	prize_split: BoundedVec<Percent, MaxWinners>,
 }
 
 pub trait ValidateCall<T: Config> {
@@ -122,9 +178,23 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
+	#[cfg(feature = "runtime-benchmarks")]
+	pub trait BenchmarkHelper<BlockNumber> {
+		/// Make `Config::Randomness` provide a seed which was not determinable before `block`.
+		fn refresh_randomness(block: BlockNumber);
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	impl<BlockNumber> BenchmarkHelper<BlockNumber> for () {
+		fn refresh_randomness(_: BlockNumber) {}
+	}
+
 	/// The pallet's config trait.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -142,6 +212,9 @@ pub mod pallet {
 		type Currency: ReservableCurrency<Self::AccountId>;
 
 		/// Something that provides randomness in the runtime.
+		///
+		/// The payout of a lottery is postponed until this provides randomness which was not
+		/// determinable before the lottery ended.
 		type Randomness: Randomness<Self::Hash, BlockNumberFor<Self>>;
 
 		/// The overarching event type.
@@ -167,8 +240,16 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxGenerateRandom: Get<u32>;
 
+		/// The max number of winners in a single lottery.
+		#[pallet::constant]
+		type MaxWinners: Get<u32>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
+
+		/// Helper to make the randomness fresh when benchmarking a payout.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<BlockNumberFor<Self>>;
 	}
 
 	#[pallet::event]
@@ -182,6 +263,10 @@ pub mod pallet {
 		Winner { winner: T::AccountId, lottery_balance: BalanceOf<T> },
 		/// A ticket has been bought!
 		TicketBought { who: T::AccountId, call_index: CallIndex },
+		/// A new set of excluded calls have been set!
+		ExcludedCallsUpdated,
+		/// The configuration of the next round has been set!
+		NextRoundSet,
 	}
 
 	#[pallet::error]
@@ -200,6 +285,10 @@ pub mod pallet {
 		TooManyCalls,
 		/// Failed to encode calls
 		EncodingFailed,
+		/// The prize split is empty, too long, or pays out more than the whole pot.
+		InvalidPrizeSplit,
+		/// The call is excluded from buying tickets.
+		ExcludedCall,
 	}
 
 	#[pallet::storage]
@@ -208,7 +297,12 @@ pub mod pallet {
 	/// The configuration for the current lottery.
 	#[pallet::storage]
 	pub(crate) type Lottery<T: Config> =
-		StorageValue<_, LotteryConfig<BlockNumberFor<T>, BalanceOf<T>>>;
+		StorageValue<_, LotteryConfig<BlockNumberFor<T>, BalanceOf<T>, T::MaxWinners>>;
+
+	/// The configuration changes to apply when the current lottery repeats.
+	#[pallet::storage]
+	pub(crate) type NextRound<T: Config> =
+		StorageValue<_, NextRoundConfig<BlockNumberFor<T>, BalanceOf<T>, T::MaxWinners>>;
 
 	/// Users who have purchased a ticket. (Lottery Index, Tickets Purchased)
 	#[pallet::storage]
@@ -237,6 +331,11 @@ pub mod pallet {
 	pub(crate) type CallIndices<T: Config> =
 		StorageValue<_, BoundedVec<CallIndex, T::MaxCalls>, ValueQuery>;
 
+	/// The calls which can never be used to buy a ticket, regardless of `Config::ValidateCall`.
+	#[pallet::storage]
+	pub(crate) type ExcludedCalls<T: Config> =
+		StorageValue<_, BoundedVec<CallIndex, T::MaxCalls>, ValueQuery>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
@@ -245,19 +344,37 @@ pub mod pallet {
 					let payout_block =
 						config.start.saturating_add(config.length).saturating_add(config.delay);
 					if payout_block <= n {
-						let (lottery_account, lottery_balance) = Self::pot();
+						// Tickets could still be bought before the lottery ended, so the winners
+						// must be chosen with randomness nobody knew back then.
+						let end = config.start.saturating_add(config.length);
+						if T::Randomness::random_at(&T::PalletId::get().encode(), end).is_none() {
+							// Reads of the lottery and of the randomness source.
+							return T::DbWeight::get().reads(3)
+						}
 
-						let winner = Self::choose_account().unwrap_or(lottery_account);
-						// Not much we can do if this fails...
-						let res = T::Currency::transfer(
-							&Self::account_id(),
-							&winner,
-							lottery_balance,
-							KeepAlive,
-						);
-						debug_assert!(res.is_ok());
+						let (lottery_account, pot) = Self::pot();
+						let winners = Self::choose_winners(config.prize_split.len() as u32);
+						let winners_count = winners.len() as u32;
 
-						Self::deposit_event(Event::<T>::Winner { winner, lottery_balance });
+						if winners.is_empty() {
+							// No tickets were sold, so the pot stays where it is.
+							Self::deposit_event(Event::<T>::Winner {
+								winner: lottery_account.clone(),
+								lottery_balance: pot,
+							});
+						}
+						for (winner, share) in winners.into_iter().zip(config.prize_split.iter()) {
+							let prize = share.mul_floor(pot);
+							// Not much we can do if this fails...
+							let res =
+								T::Currency::transfer(&lottery_account, &winner, prize, KeepAlive);
+							debug_assert!(res.is_ok());
+
+							Self::deposit_event(Event::<T>::Winner {
+								winner,
+								lottery_balance: prize,
+							});
+						}
 
 						TicketsCount::<T>::kill();
 
@@ -266,11 +383,25 @@ pub mod pallet {
 							LotteryIndex::<T>::mutate(|index| *index = index.saturating_add(1));
 							// Set a new start with the current block.
 							config.start = n;
-							return T::WeightInfo::on_initialize_repeat()
+							// Apply any changes scheduled for this round.
+							if let Some(next) = NextRound::<T>::take() {
+								config.price = next.price;
+								config.length = next.length;
+								config.delay = next.delay;
+								config.prize_split = next.prize_split;
+							}
+							return Self::payout_weight(
+								T::WeightInfo::on_initialize_repeat(),
+								winners_count,
+							)
 						} else {
 							// Else, kill the lottery storage.
 							*lottery = None;
-							return T::WeightInfo::on_initialize_end()
+							NextRound::<T>::kill();
+							return Self::payout_weight(
+								T::WeightInfo::on_initialize_end(),
+								winners_count,
+							)
 						}
 						// We choose not need to kill Participants and Tickets to avoid a large
 						// number of writes at one time. Instead, data persists between lotteries,
@@ -280,6 +411,10 @@ pub mod pallet {
 				T::DbWeight::get().reads(1)
 			})
 		}
+
+		fn integrity_test() {
+			assert!(T::MaxWinners::get() > 0, "A lottery must have at least one winner.");
+		}
 	}
 
 	#[pallet::call]
@@ -298,6 +433,8 @@ pub mod pallet {
 		#[pallet::call_index(0)]
 		#[pallet::weight(
 			T::WeightInfo::buy_ticket()
+				// The read of `ExcludedCalls`, which is not benchmarked yet.
+				.saturating_add(T::DbWeight::get().reads(1))
 				.saturating_add(call.get_dispatch_info().weight)
 		)]
 		pub fn buy_ticket(
@@ -345,6 +482,8 @@ pub mod pallet {
 		/// * `length`: How long the lottery should run for starting at the current block.
 		/// * `delay`: How long after the lottery end we should wait before picking a winner.
 		/// * `repeat`: If the lottery should repeat when completed.
+		/// * `prize_split`: The share of the pot paid to each winner, in draw order. The shares
+		///   must not add up to more than the whole pot.
 		#[pallet::call_index(2)]
 		#[pallet::weight(T::WeightInfo::start_lottery())]
 		pub fn start_lottery(
@@ -353,15 +492,17 @@ pub mod pallet {
 			length: BlockNumberFor<T>,
 			delay: BlockNumberFor<T>,
 			repeat: bool,
+			prize_split: Vec<Percent>,
 		) -> DispatchResult {
 			T::ManagerOrigin::ensure_origin(origin)?;
+			let prize_split = Self::bounded_prize_split(prize_split)?;
 			Lottery::<T>::try_mutate(|lottery| -> DispatchResult {
 				ensure!(lottery.is_none(), Error::<T>::InProgress);
 				let index = LotteryIndex::<T>::get();
 				let new_index = index.checked_add(1).ok_or(ArithmeticError::Overflow)?;
 				let start = frame_system::Pallet::<T>::block_number();
 				// Use new_index to more easily track everything with the current state.
-				*lottery = Some(LotteryConfig { price, start, length, delay, repeat });
+				*lottery = Some(LotteryConfig { price, start, length, delay, repeat, prize_split });
 				LotteryIndex::<T>::put(new_index);
 				Ok(())
 			})?;
@@ -390,10 +531,69 @@ pub mod pallet {
 			});
 			Ok(())
 		}
+
+		/// Set the price, length, delay and prize split of the next round of the current
+		/// lottery.
+		///
+		/// The changes apply when the lottery repeats, and are dropped if it does not.
+		///
+		/// This extrinsic must be called by the `ManagerOrigin`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(
+			T::WeightInfo::stop_repeat().saturating_add(T::DbWeight::get().writes(1))
+		)]
+		pub fn set_next_round(
+			origin: OriginFor<T>,
+			price: BalanceOf<T>,
+			length: BlockNumberFor<T>,
+			delay: BlockNumberFor<T>,
+			prize_split: Vec<Percent>,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			ensure!(Lottery::<T>::exists(), Error::<T>::NotConfigured);
+			let prize_split = Self::bounded_prize_split(prize_split)?;
+			NextRound::<T>::put(NextRoundConfig { price, length, delay, prize_split });
+			Self::deposit_event(Event::<T>::NextRoundSet);
+			Ok(())
+		}
+
+		/// Set calls in storage which can never be used to purchase a lottery ticket.
+		///
+		/// Unlike `set_calls`, this applies whatever the `ValidateCall` implementation is.
+		///
+		/// This extrinsic must be called by the Manager origin.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::set_calls(calls.len() as u32))]
+		pub fn set_excluded_calls(
+			origin: OriginFor<T>,
+			calls: Vec<<T as Config>::RuntimeCall>,
+		) -> DispatchResult {
+			T::ManagerOrigin::ensure_origin(origin)?;
+			ensure!(calls.len() <= T::MaxCalls::get() as usize, Error::<T>::TooManyCalls);
+			if calls.is_empty() {
+				ExcludedCalls::<T>::kill();
+			} else {
+				let indices = Self::calls_to_indices(&calls)?;
+				ExcludedCalls::<T>::put(indices);
+			}
+			Self::deposit_event(Event::<T>::ExcludedCallsUpdated);
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// The weight of paying out a round to `winners`, given the weight of paying out a single
+	/// winner.
+	///
+	/// Payouts are only benchmarked for a single winner so far, so the whole payout is charged for
+	/// every winner, on top of the `NextRound` access.
+	fn payout_weight(single_winner: Weight, winners: u32) -> Weight {
+		single_winner
+			.saturating_mul(winners.max(1).into())
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+
 	/// The account ID of the lottery pot.
 	///
 	/// This actually does computation. If you need to keep using it, then make sure you cache the
@@ -424,6 +624,18 @@ impl<T: Config> Pallet<T> {
 		Ok(indices)
 	}
 
+	/// Check that a prize split is not empty and does not pay out more than the whole pot.
+	fn bounded_prize_split(
+		prize_split: Vec<Percent>,
+	) -> Result<BoundedVec<Percent, T::MaxWinners>, DispatchError> {
+		let total = prize_split.iter().map(|share| share.deconstruct() as u32).sum::<u32>();
+		ensure!(
+			!prize_split.is_empty() && total <= Percent::one().deconstruct() as u32,
+			Error::<T>::InvalidPrizeSplit
+		);
+		BoundedVec::try_from(prize_split).map_err(|_| Error::<T>::InvalidPrizeSplit.into())
+	}
+
 	/// Convert a call to it's call index by encoding the call and taking the first two bytes.
 	fn call_to_index(call: &<T as Config>::RuntimeCall) -> Result<CallIndex, DispatchError> {
 		let encoded_call = call.encode();
@@ -444,6 +656,7 @@ impl<T: Config> Pallet<T> {
 		);
 		ensure!(T::ValidateCall::validate_call(call), Error::<T>::InvalidCall);
 		let call_index = Self::call_to_index(call)?;
+		ensure!(!ExcludedCalls::<T>::get().contains(&call_index), Error::<T>::ExcludedCall);
 		let ticket_count = TicketsCount::<T>::get();
 		let new_ticket_count = ticket_count.checked_add(1).ok_or(ArithmeticError::Overflow)?;
 		// Try to update the participant status
@@ -477,23 +690,34 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	/// Randomly choose a winning ticket and return the account that purchased it.
-	/// The more tickets an account bought, the higher are its chances of winning.
-	/// Returns `None` if there is no winner.
-	fn choose_account() -> Option<T::AccountId> {
-		match Self::choose_ticket(TicketsCount::<T>::get()) {
-			None => None,
-			Some(ticket) => Tickets::<T>::get(ticket),
+	/// Randomly choose up to `count` distinct winning tickets and return the accounts that
+	/// purchased them, in draw order.
+	/// The more tickets an account bought, the higher are its chances of winning, and an account
+	/// can win more than once if it bought more than one ticket.
+	/// Returns fewer accounts than `count` if fewer tickets were sold.
+	fn choose_winners(count: u32) -> Vec<T::AccountId> {
+		let total = TicketsCount::<T>::get();
+		let mut chosen = Vec::new();
+		for nonce in 0..count.min(total) {
+			let Some(mut ticket) = Self::choose_ticket(total, nonce) else { break };
+			// Move on to the next ticket which has not already won.
+			while chosen.contains(&ticket) {
+				ticket = (ticket + 1) % total;
+			}
+			chosen.push(ticket);
 		}
+		chosen.into_iter().filter_map(Tickets::<T>::get).collect()
 	}
 
 	/// Randomly choose a winning ticket from among the total number of tickets.
+	/// `nonce` selects the random numbers used, so that each winner is drawn independently.
 	/// Returns `None` if there are no tickets.
-	fn choose_ticket(total: u32) -> Option<u32> {
+	fn choose_ticket(total: u32, nonce: u32) -> Option<u32> {
 		if total == 0 {
 			return None
 		}
-		let mut random_number = Self::generate_random_number(0);
+		let first_seed = nonce.saturating_mul(T::MaxGenerateRandom::get());
+		let mut random_number = Self::generate_random_number(first_seed);
 
 		// Best effort attempt to remove bias from modulus operator.
 		for i in 1..T::MaxGenerateRandom::get() {
@@ -501,7 +725,7 @@ impl<T: Config> Pallet<T> {
 				break
 			}
 
-			random_number = Self::generate_random_number(i);
+			random_number = Self::generate_random_number(first_seed.saturating_add(i));
 		}
 
 		Some(random_number % total)
@@ -511,8 +735,7 @@ impl<T: Config> Pallet<T> {
 	/// Note that there is potential bias introduced by using modulus operator.
 	/// You should call this function with different seed values until the random
 	/// number lies within `u32::MAX - u32::MAX % n`.
-	/// TODO: deal with randomness freshness
-	/// https://github.com/paritytech/substrate/issues/8311
+	/// The freshness of the randomness is checked before any winner is drawn.
 	fn generate_random_number(seed: u32) -> u32 {
 		let (random_seed, _) = T::Randomness::random(&(T::PalletId::get(), seed).encode());
 		let random_number = <u32>::decode(&mut random_seed.as_ref())
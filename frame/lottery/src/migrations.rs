@@ -0,0 +1,71 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations for the lottery pallet.
+
+use super::*;
+use core::marker::PhantomData;
+use frame_support::traits::{Get, OnRuntimeUpgrade};
+use frame_system::pallet_prelude::BlockNumberFor;
+
+pub(crate) mod v1 {
+	use super::*;
+
+	/// The lottery configuration before the prize split was added.
+	#[derive(Encode, Decode)]
+	pub(crate) struct OldLotteryConfig<BlockNumber, Balance> {
+		pub(crate) price: Balance,
+		pub(crate) start: BlockNumber,
+		pub(crate) length: BlockNumber,
+		pub(crate) delay: BlockNumber,
+		pub(crate) repeat: bool,
+	}
+
+	/// Actual implementation of the storage migration.
+	///
+	/// A lottery which is in progress keeps paying the whole pot to a single winner.
+	pub struct MigrateToV1Impl<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1Impl<T> {
+		fn on_runtime_upgrade() -> frame_support::weights::Weight {
+			let res = Lottery::<T>::translate::<OldLotteryConfig<BlockNumberFor<T>, BalanceOf<T>>, _>(
+				|old| {
+					old.map(|old| LotteryConfig {
+						price: old.price,
+						start: old.start,
+						length: old.length,
+						delay: old.delay,
+						repeat: old.repeat,
+						prize_split: BoundedVec::truncate_from(vec![Percent::one()]),
+					})
+				},
+			);
+			debug_assert!(res.is_ok());
+
+			T::DbWeight::get().reads_writes(1, 1)
+		}
+	}
+}
+
+/// Migrate the pallet storage from `0` to `1`.
+pub type MigrateV0ToV1<T> = frame_support::migrations::VersionedMigration<
+	0,
+	1,
+	v1::MigrateToV1Impl<T>,
+	Pallet<T>,
+	<T as frame_system::Config>::DbWeight,
+>;
@@ -26,6 +26,7 @@ use frame_support::{
 };
 use frame_support_test::TestRandomness;
 use frame_system::EnsureRoot;
+use sp_core::H256;
 use sp_runtime::{BuildStorage, Perbill};
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -67,19 +68,32 @@ impl pallet_balances::Config for Test {
 
 parameter_types! {
 	pub const LotteryPalletId: PalletId = PalletId(*b"py/lotto");
+	pub static RandomnessKnownSince: Option<u64> = None;
+}
+
+/// [`TestRandomness`], which is determinable since `RandomnessKnownSince` if that is set.
+pub struct MockRandomness;
+impl Randomness<H256, u64> for MockRandomness {
+	fn random(subject: &[u8]) -> (H256, u64) {
+		let (random, now) = TestRandomness::<Test>::random(subject);
+		(random, RandomnessKnownSince::get().unwrap_or(now))
+	}
 }
 
 impl Config for Test {
 	type PalletId = LotteryPalletId;
 	type RuntimeCall = RuntimeCall;
 	type Currency = Balances;
-	type Randomness = TestRandomness<Self>;
+	type Randomness = MockRandomness;
 	type RuntimeEvent = RuntimeEvent;
 	type ManagerOrigin = EnsureRoot<u64>;
 	type MaxCalls = ConstU32<2>;
 	type ValidateCall = Lottery;
 	type MaxGenerateRandom = ConstU32<10>;
+	type MaxWinners = ConstU32<3>;
 	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 pub type SystemCall = frame_system::Call<Test>;
@@ -95,6 +109,11 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	t.into()
 }
 
+/// A prize split which pays the whole pot to a single winner.
+pub fn single_winner() -> Vec<Percent> {
+	vec![Percent::one()]
+}
+
 /// Run until a particular block.
 pub fn run_to_block(n: u64) {
 	while System::block_number() < n {
@@ -20,8 +20,8 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok, assert_storage_noop};
 use mock::{
-	new_test_ext, run_to_block, Balances, BalancesCall, Lottery, RuntimeCall, RuntimeOrigin,
-	SystemCall, Test,
+	new_test_ext, run_to_block, single_winner, Balances, BalancesCall, Lottery,
+	RandomnessKnownSince, RuntimeCall, RuntimeEvent, RuntimeOrigin, System, SystemCall, Test,
 };
 use sp_runtime::{traits::BadOrigin, TokenError};
 
@@ -51,7 +51,14 @@ fn basic_end_to_end_works() {
 		assert_ok!(Lottery::set_calls(RuntimeOrigin::root(), calls));
 
 		// Start lottery, it repeats
-		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), price, length, delay, true));
+		assert_ok!(Lottery::start_lottery(
+			RuntimeOrigin::root(),
+			price,
+			length,
+			delay,
+			true,
+			single_winner()
+		));
 		assert!(crate::Lottery::<Test>::get().is_some());
 
 		assert_eq!(Balances::free_balance(&1), 100);
@@ -88,7 +95,14 @@ fn basic_end_to_end_works() {
 		assert_eq!(LotteryIndex::<Test>::get(), 2);
 		assert_eq!(
 			crate::Lottery::<Test>::get().unwrap(),
-			LotteryConfig { price, start: 25, length, delay, repeat: true }
+			LotteryConfig {
+				price,
+				start: 25,
+				length,
+				delay,
+				repeat: true,
+				prize_split: BoundedVec::truncate_from(single_winner()),
+			}
 		);
 	});
 }
@@ -104,7 +118,14 @@ fn stop_repeat_works() {
 		// Set no calls for the lottery.
 		assert_ok!(Lottery::set_calls(RuntimeOrigin::root(), vec![]));
 		// Start lottery, it repeats.
-		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), price, length, delay, true));
+		assert_ok!(Lottery::start_lottery(
+			RuntimeOrigin::root(),
+			price,
+			length,
+			delay,
+			true,
+			single_winner()
+		));
 
 		// Non-manager fails to `stop_repeat`.
 		assert_noop!(Lottery::stop_repeat(RuntimeOrigin::signed(1)), DispatchError::BadOrigin);
@@ -124,6 +145,34 @@ fn stop_repeat_works() {
 	});
 }
 
+/// The payout waits for randomness which was not known before the lottery ended.
+#[test]
+fn payout_waits_for_fresh_randomness() {
+	new_test_ext().execute_with(|| {
+		let length = 20;
+		let delay = 5;
+
+		assert_ok!(Lottery::set_calls(RuntimeOrigin::root(), vec![]));
+		assert_ok!(Lottery::start_lottery(
+			RuntimeOrigin::root(),
+			10,
+			length,
+			delay,
+			false,
+			single_winner()
+		));
+
+		// Tickets could still be bought when the randomness became known.
+		RandomnessKnownSince::set(Some(length - 1));
+		run_to_block(length + delay + 5);
+		assert!(crate::Lottery::<Test>::get().is_some());
+
+		RandomnessKnownSince::set(Some(length));
+		run_to_block(length + delay + 6);
+		assert!(crate::Lottery::<Test>::get().is_none());
+	});
+}
+
 #[test]
 fn set_calls_works() {
 	new_test_ext().execute_with(|| {
@@ -184,16 +233,37 @@ fn start_lottery_works() {
 
 		// Setup ignores bad origin
 		assert_noop!(
-			Lottery::start_lottery(RuntimeOrigin::signed(1), price, length, delay, false),
+			Lottery::start_lottery(
+				RuntimeOrigin::signed(1),
+				price,
+				length,
+				delay,
+				false,
+				single_winner()
+			),
 			BadOrigin,
 		);
 
 		// All good
-		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), price, length, delay, false));
+		assert_ok!(Lottery::start_lottery(
+			RuntimeOrigin::root(),
+			price,
+			length,
+			delay,
+			false,
+			single_winner()
+		));
 
 		// Can't open another one if lottery is already present
 		assert_noop!(
-			Lottery::start_lottery(RuntimeOrigin::root(), price, length, delay, false),
+			Lottery::start_lottery(
+				RuntimeOrigin::root(),
+				price,
+				length,
+				delay,
+				false,
+				single_winner()
+			),
 			Error::<Test>::InProgress,
 		);
 	});
@@ -222,7 +292,14 @@ fn buy_ticket_works_as_simple_passthrough() {
 		assert_ok!(Lottery::set_calls(RuntimeOrigin::root(), calls));
 
 		// Ticket price of 60 would kill the user's account
-		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), 60, 10, 5, false));
+		assert_ok!(Lottery::start_lottery(
+			RuntimeOrigin::root(),
+			60,
+			10,
+			5,
+			false,
+			single_winner()
+		));
 		assert_ok!(Lottery::buy_ticket(RuntimeOrigin::signed(1), call.clone()));
 		assert_eq!(Balances::free_balance(&1), 100 - 20 - 20);
 		assert_eq!(TicketsCount::<Test>::get(), 0);
@@ -278,7 +355,7 @@ fn buy_ticket_works() {
 		assert_eq!(TicketsCount::<Test>::get(), 0);
 
 		// Start lottery
-		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), 1, 20, 5, false));
+		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), 1, 20, 5, false, single_winner()));
 
 		// Go to start, buy ticket for transfer
 		run_to_block(5);
@@ -320,7 +397,14 @@ fn do_buy_ticket_already_participating() {
 		let calls =
 			vec![RuntimeCall::Balances(BalancesCall::transfer_allow_death { dest: 0, value: 0 })];
 		assert_ok!(Lottery::set_calls(RuntimeOrigin::root(), calls.clone()));
-		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), 1, 10, 10, false));
+		assert_ok!(Lottery::start_lottery(
+			RuntimeOrigin::root(),
+			1,
+			10,
+			10,
+			false,
+			single_winner()
+		));
 
 		// Buying once works.
 		assert_ok!(Lottery::do_buy_ticket(&1, &calls[0]));
@@ -336,7 +420,14 @@ fn buy_ticket_already_participating() {
 		let calls =
 			vec![RuntimeCall::Balances(BalancesCall::transfer_allow_death { dest: 0, value: 0 })];
 		assert_ok!(Lottery::set_calls(RuntimeOrigin::root(), calls.clone()));
-		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), 1, 10, 10, false));
+		assert_ok!(Lottery::start_lottery(
+			RuntimeOrigin::root(),
+			1,
+			10,
+			10,
+			false,
+			single_winner()
+		));
 
 		// Buying once works.
 		let call = Box::new(calls[0].clone());
@@ -358,7 +449,14 @@ fn buy_ticket_insufficient_balance() {
 			vec![RuntimeCall::Balances(BalancesCall::transfer_allow_death { dest: 0, value: 0 })];
 		assert_ok!(Lottery::set_calls(RuntimeOrigin::root(), calls.clone()));
 		// Price set to 100.
-		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), 100, 10, 10, false));
+		assert_ok!(Lottery::start_lottery(
+			RuntimeOrigin::root(),
+			100,
+			10,
+			10,
+			false,
+			single_winner()
+		));
 		let call = Box::new(calls[0].clone());
 
 		// Buying a ticket returns Ok, but changes nothing.
@@ -374,7 +472,14 @@ fn do_buy_ticket_insufficient_balance() {
 			vec![RuntimeCall::Balances(BalancesCall::transfer_allow_death { dest: 0, value: 0 })];
 		assert_ok!(Lottery::set_calls(RuntimeOrigin::root(), calls.clone()));
 		// Price set to 101.
-		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), 101, 10, 10, false));
+		assert_ok!(Lottery::start_lottery(
+			RuntimeOrigin::root(),
+			101,
+			10,
+			10,
+			false,
+			single_winner()
+		));
 
 		// Buying fails with InsufficientBalance.
 		assert_noop!(Lottery::do_buy_ticket(&1, &calls[0]), TokenError::FundsUnavailable,);
@@ -389,7 +494,14 @@ fn do_buy_ticket_keep_alive() {
 			vec![RuntimeCall::Balances(BalancesCall::transfer_allow_death { dest: 0, value: 0 })];
 		assert_ok!(Lottery::set_calls(RuntimeOrigin::root(), calls.clone()));
 		// Price set to 100.
-		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), 100, 10, 10, false));
+		assert_ok!(Lottery::start_lottery(
+			RuntimeOrigin::root(),
+			100,
+			10,
+			10,
+			false,
+			single_winner()
+		));
 
 		assert_noop!(Lottery::do_buy_ticket(&1, &calls[0]), TokenError::NotExpendable);
 		assert!(TicketsCount::<Test>::get().is_zero());
@@ -406,7 +518,14 @@ fn no_participants_works() {
 		// Set no calls for the lottery.
 		assert_ok!(Lottery::set_calls(RuntimeOrigin::root(), vec![]));
 		// Start lottery.
-		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), 10, length, delay, false));
+		assert_ok!(Lottery::start_lottery(
+			RuntimeOrigin::root(),
+			10,
+			length,
+			delay,
+			false,
+			single_winner()
+		));
 
 		// End the lottery, no one wins.
 		run_to_block(length + delay);
@@ -421,7 +540,14 @@ fn start_lottery_will_create_account() {
 		let delay = 5;
 
 		assert_eq!(Balances::total_balance(&Lottery::account_id()), 0);
-		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), price, length, delay, false));
+		assert_ok!(Lottery::start_lottery(
+			RuntimeOrigin::root(),
+			price,
+			length,
+			delay,
+			false,
+			single_winner()
+		));
 		assert_eq!(Balances::total_balance(&Lottery::account_id()), 1);
 	});
 }
@@ -429,8 +555,8 @@ fn start_lottery_will_create_account() {
 #[test]
 fn choose_ticket_trivial_cases() {
 	new_test_ext().execute_with(|| {
-		assert!(Lottery::choose_ticket(0).is_none());
-		assert_eq!(Lottery::choose_ticket(1).unwrap(), 0);
+		assert!(Lottery::choose_ticket(0, 0).is_none());
+		assert_eq!(Lottery::choose_ticket(1, 0).unwrap(), 0);
 	});
 }
 
@@ -440,12 +566,224 @@ fn choose_account_one_participant() {
 		let calls =
 			vec![RuntimeCall::Balances(BalancesCall::transfer_allow_death { dest: 0, value: 0 })];
 		assert_ok!(Lottery::set_calls(RuntimeOrigin::root(), calls.clone()));
-		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), 10, 10, 10, false));
+		assert_ok!(Lottery::start_lottery(
+			RuntimeOrigin::root(),
+			10,
+			10,
+			10,
+			false,
+			single_winner()
+		));
 		let call = Box::new(calls[0].clone());
 
 		// Buy one ticket with account 1.
 		assert_ok!(Lottery::buy_ticket(RuntimeOrigin::signed(1), call));
 		// Account 1 is always the winner.
-		assert_eq!(Lottery::choose_account().unwrap(), 1);
+		assert_eq!(Lottery::choose_winners(1), vec![1]);
+	});
+}
+
+#[test]
+fn multiple_winners_split_the_pot() {
+	new_test_ext().execute_with(|| {
+		let calls = vec![RuntimeCall::System(SystemCall::remark { remark: vec![] })];
+		assert_ok!(Lottery::set_calls(RuntimeOrigin::root(), calls.clone()));
+		let prize_split = vec![Percent::from_percent(50), Percent::from_percent(30)];
+		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), 10, 20, 5, false, prize_split));
+
+		// Three accounts buy a ticket each.
+		for who in 1..=3 {
+			assert_ok!(Lottery::buy_ticket(RuntimeOrigin::signed(who), Box::new(calls[0].clone())));
+		}
+		assert_eq!(Lottery::pot().1, 30);
+
+		// Two distinct accounts win 50% and 30% of the pot, the rest stays in the pot.
+		System::reset_events();
+		run_to_block(25);
+		let mut balances: Vec<u64> = (1..=3).map(|who| Balances::free_balance(&who)).collect();
+		balances.sort();
+		assert_eq!(balances, vec![90, 90 + 9, 90 + 15]);
+		assert_eq!(Lottery::pot().1, 6);
+		let winners = System::events()
+			.into_iter()
+			.filter(|record| matches!(record.event, RuntimeEvent::Lottery(Event::Winner { .. })))
+			.count();
+		assert_eq!(winners, 2);
+	});
+}
+
+#[test]
+fn fewer_tickets_than_winners_works() {
+	new_test_ext().execute_with(|| {
+		let calls = vec![RuntimeCall::System(SystemCall::remark { remark: vec![] })];
+		assert_ok!(Lottery::set_calls(RuntimeOrigin::root(), calls.clone()));
+		let prize_split = vec![Percent::from_percent(30); 3];
+		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), 10, 20, 5, false, prize_split));
+		assert_ok!(Lottery::buy_ticket(RuntimeOrigin::signed(1), Box::new(calls[0].clone())));
+		assert_ok!(Lottery::buy_ticket(RuntimeOrigin::signed(2), Box::new(calls[0].clone())));
+
+		// Both participants win 30% of the pot, and the third prize stays in the pot.
+		run_to_block(25);
+		assert_eq!(Balances::free_balance(&1), 90 + 6);
+		assert_eq!(Balances::free_balance(&2), 90 + 6);
+		assert_eq!(Lottery::pot().1, 8);
+	});
+}
+
+#[test]
+fn invalid_prize_split_fails() {
+	new_test_ext().execute_with(|| {
+		// No winners.
+		assert_noop!(
+			Lottery::start_lottery(RuntimeOrigin::root(), 10, 20, 5, false, vec![]),
+			Error::<Test>::InvalidPrizeSplit,
+		);
+		// More than the whole pot.
+		assert_noop!(
+			Lottery::start_lottery(
+				RuntimeOrigin::root(),
+				10,
+				20,
+				5,
+				false,
+				vec![Percent::from_percent(60), Percent::from_percent(50)],
+			),
+			Error::<Test>::InvalidPrizeSplit,
+		);
+		// More than `MaxWinners` winners.
+		assert_noop!(
+			Lottery::start_lottery(
+				RuntimeOrigin::root(),
+				10,
+				20,
+				5,
+				false,
+				vec![Percent::from_percent(10); 4],
+			),
+			Error::<Test>::InvalidPrizeSplit,
+		);
+	});
+}
+
+#[test]
+fn set_next_round_works() {
+	new_test_ext().execute_with(|| {
+		let prize_split = vec![Percent::from_percent(50), Percent::from_percent(50)];
+
+		// There must be a lottery in progress.
+		assert_noop!(
+			Lottery::set_next_round(RuntimeOrigin::root(), 20, 10, 2, prize_split.clone()),
+			Error::<Test>::NotConfigured,
+		);
+
+		assert_ok!(Lottery::start_lottery(RuntimeOrigin::root(), 10, 20, 5, true, single_winner()));
+
+		// Only the manager can set the next round, and the prize split is checked.
+		assert_noop!(
+			Lottery::set_next_round(RuntimeOrigin::signed(1), 20, 10, 2, prize_split.clone()),
+			BadOrigin,
+		);
+		assert_noop!(
+			Lottery::set_next_round(RuntimeOrigin::root(), 20, 10, 2, vec![]),
+			Error::<Test>::InvalidPrizeSplit,
+		);
+		assert_ok!(Lottery::set_next_round(RuntimeOrigin::root(), 20, 10, 2, prize_split.clone()));
+
+		// The current round is not affected.
+		assert_eq!(crate::Lottery::<Test>::get().unwrap().price, 10);
+
+		// The changes apply when the lottery repeats.
+		run_to_block(25);
+		assert_eq!(
+			crate::Lottery::<Test>::get().unwrap(),
+			LotteryConfig {
+				price: 20,
+				start: 25,
+				length: 10,
+				delay: 2,
+				repeat: true,
+				prize_split: BoundedVec::truncate_from(prize_split.clone()),
+			}
+		);
+		assert!(NextRound::<Test>::get().is_none());
+
+		// Changes for a lottery which does not repeat are dropped.
+		assert_ok!(Lottery::set_next_round(RuntimeOrigin::root(), 30, 10, 2, prize_split));
+		assert_ok!(Lottery::stop_repeat(RuntimeOrigin::root()));
+		run_to_block(37);
+		assert!(crate::Lottery::<Test>::get().is_none());
+		assert!(NextRound::<Test>::get().is_none());
+	});
+}
+
+#[test]
+fn excluded_calls_cannot_buy_tickets() {
+	new_test_ext().execute_with(|| {
+		let calls = vec![
+			RuntimeCall::System(SystemCall::remark { remark: vec![] }),
+			RuntimeCall::Balances(BalancesCall::transfer_allow_death { dest: 0, value: 0 }),
+		];
+		assert_ok!(Lottery::set_calls(RuntimeOrigin::root(), calls.clone()));
+		assert_ok!(Lottery::start_lottery(
+			RuntimeOrigin::root(),
+			1,
+			10,
+			10,
+			false,
+			single_winner()
+		));
+
+		// Only the manager can exclude calls, and no more than `MaxCalls`.
+		assert_noop!(
+			Lottery::set_excluded_calls(RuntimeOrigin::signed(1), vec![calls[0].clone()]),
+			BadOrigin,
+		);
+		let too_many_calls = vec![calls[0].clone(); 3];
+		assert_noop!(
+			Lottery::set_excluded_calls(RuntimeOrigin::root(), too_many_calls),
+			Error::<Test>::TooManyCalls,
+		);
+
+		// An excluded call does not buy a ticket, even though it is a valid call.
+		assert_ok!(Lottery::set_excluded_calls(RuntimeOrigin::root(), vec![calls[0].clone()]));
+		assert_noop!(Lottery::do_buy_ticket(&1, &calls[0]), Error::<Test>::ExcludedCall);
+		assert_ok!(Lottery::do_buy_ticket(&1, &calls[1]));
+
+		// Clearing the excluded calls allows it again.
+		assert_ok!(Lottery::set_excluded_calls(RuntimeOrigin::root(), vec![]));
+		assert!(!ExcludedCalls::<Test>::exists());
+		assert_ok!(Lottery::do_buy_ticket(&1, &calls[0]));
+		assert_eq!(TicketsCount::<Test>::get(), 2);
+	});
+}
+
+#[test]
+fn migrate_to_v1_works() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		let old = migrations::v1::OldLotteryConfig::<u64, u64> {
+			price: 10,
+			start: 1,
+			length: 20,
+			delay: 5,
+			repeat: true,
+		};
+		frame_support::storage::unhashed::put(&crate::Lottery::<Test>::hashed_key(), &old);
+
+		migrations::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(
+			crate::Lottery::<Test>::get().unwrap(),
+			LotteryConfig {
+				price: 10,
+				start: 1,
+				length: 20,
+				delay: 5,
+				repeat: true,
+				prize_split: BoundedVec::truncate_from(single_winner()),
+			}
+		);
+		assert_eq!(Lottery::on_chain_storage_version(), StorageVersion::new(1));
 	});
 }
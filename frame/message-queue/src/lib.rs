@@ -110,7 +110,8 @@
 //!
 //! # Scenario: Message processing
 //!
-//! The pallet runs each block in `on_initialize` or when being manually called through
+//! The pallet runs each block in `on_initialize`, in `on_idle` if [`Config::IdleMaxServiceWeight`]
+//! is set, or when being manually called through
 //! [`frame_support::traits::ServiceQueues::service_queues`].
 //!
 //! First it tries to "rotate" the `ReadyRing` by one through advancing the `ServiceHead` to the
@@ -127,7 +128,7 @@
 //! # Scenario: Overweight execution
 //!
 //! A permanently over-weight message which was skipped by the message processing will never be
-//! executed automatically through `on_initialize` nor by calling
+//! executed automatically through `on_initialize` or `on_idle` nor by calling
 //! [`frame_support::traits::ServiceQueues::service_queues`].
 //!
 //! Manual intervention in the form of
@@ -529,6 +530,14 @@ pub mod pallet {
 		/// `ServiceQueues::service_queues` manually.
 		#[pallet::constant]
 		type ServiceWeight: Get<Option<Weight>>;
+
+		/// The maximum amount of weight (if any) of the remaining block weight which is used
+		/// `on_idle` for servicing enqueued items.
+		///
+		/// This allows to process messages in the same block in which they were enqueued, once
+		/// all extrinsics have been applied. Set it to `None` to not service queues `on_idle`.
+		#[pallet::constant]
+		type IdleMaxServiceWeight: Get<Option<Weight>>;
 	}
 
 	#[pallet::event]
@@ -641,6 +650,14 @@ pub mod pallet {
 			}
 		}
 
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			if let Some(weight_limit) = T::IdleMaxServiceWeight::get() {
+				Self::service_queues(weight_limit.min(remaining_weight))
+			} else {
+				Weight::zero()
+			}
+		}
+
 		#[cfg(feature = "try-runtime")]
 		fn try_state(_: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
 			Self::do_try_state()
@@ -861,6 +878,16 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 
+		if let Some(idle) = T::IdleMaxServiceWeight::get() {
+			if Self::max_message_weight(idle).is_none() {
+				return Err(format!(
+					"IdleMaxServiceWeight too low: {}. Must be at least {}",
+					idle,
+					Self::single_msg_overhead(),
+				))
+			}
+		}
+
 		Ok(())
 	}
 
@@ -45,6 +45,7 @@ parameter_types! {
 	pub const HeapSize: u32 = 24;
 	pub const MaxStale: u32 = 2;
 	pub const ServiceWeight: Option<Weight> = Some(Weight::from_parts(100, 100));
+	pub static IdleMaxServiceWeight: Option<Weight> = None;
 }
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
@@ -56,6 +57,7 @@ impl Config for Test {
 	type HeapSize = HeapSize;
 	type MaxStale = MaxStale;
 	type ServiceWeight = ServiceWeight;
+	type IdleMaxServiceWeight = IdleMaxServiceWeight;
 }
 
 /// Mocked `WeightInfo` impl with allows to set the weight per call.
@@ -166,6 +166,31 @@ fn service_queues_basic_works() {
 	});
 }
 
+#[test]
+fn on_idle_services_queues_within_limit() {
+	use MessageOrigin::*;
+	build_and_execute::<Test>(|| {
+		MessageQueue::enqueue_messages(vec![msg("a"), msg("ab"), msg("abc")].into_iter(), Here);
+		assert_eq!(QueueChanges::take(), vec![(Here, 3, 6)]);
+
+		// Nothing is serviced `on_idle` unless `IdleMaxServiceWeight` is set.
+		assert_eq!(MessageQueue::on_idle(1, Weight::MAX), Weight::zero());
+		assert!(MessagesProcessed::take().is_empty());
+
+		IdleMaxServiceWeight::set(Some(2.into_weight()));
+		// The remaining weight of the block is respected.
+		assert_eq!(MessageQueue::on_idle(1, 1.into_weight()), 1.into_weight());
+		assert_eq!(MessagesProcessed::take(), vec![(vmsg("a"), Here)]);
+
+		// And so is `IdleMaxServiceWeight`.
+		MessageQueue::enqueue_message(msg("abcd"), Here);
+		assert_eq!(MessageQueue::on_idle(1, Weight::MAX), 2.into_weight());
+		assert_eq!(MessagesProcessed::take(), vec![(vmsg("ab"), Here), (vmsg("abc"), Here)]);
+		assert_eq!(MessageQueue::on_idle(1, Weight::MAX), 1.into_weight());
+		assert_eq!(MessagesProcessed::take(), vec![(vmsg("abcd"), Here)]);
+	});
+}
+
 #[test]
 fn service_queues_failing_messages_works() {
 	use MessageOrigin::*;
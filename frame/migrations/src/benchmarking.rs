@@ -0,0 +1,95 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The migrations pallet's benchmarks.
+//!
+//! The weight of the migration steps is accounted for by the migrations themselves, these only
+//! measure the overhead of the executor.
+
+use super::*;
+
+use frame_benchmarking::v2::*;
+
+fn active_cursor<T: Config>() -> ActiveCursor<RawCursorOf<T>, BlockNumberFor<T>> {
+	ActiveCursor {
+		index: 0,
+		inner_cursor: None,
+		steps: 0,
+		started_at: frame_system::Pallet::<T>::block_number(),
+	}
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn onboard_migrations() {
+		#[block]
+		{
+			Pallet::<T>::onboard_migrations();
+		}
+
+		assert_eq!(Cursor::<T>::exists(), T::Migrations::len() > 0);
+	}
+
+	#[benchmark]
+	fn progress_migrations() {
+		// The executor has no migration to run, `exec_migration` accounts for the rest.
+		#[block]
+		{
+			Pallet::<T>::progress_migrations();
+		}
+
+		assert!(!Cursor::<T>::exists());
+	}
+
+	#[benchmark]
+	fn exec_migration() -> Result<(), BenchmarkError> {
+		// The most expensive path not running a step: the migration already completed in an
+		// earlier upgrade and is skipped.
+		let id = T::Migrations::nth_id(0).ok_or(BenchmarkError::Weightless)?;
+		let id = IdentifierOf::<T>::try_from(id)
+			.map_err(|_| BenchmarkError::Stop("Migration identifier too long"))?;
+		Historic::<T>::insert(&id, ());
+		let mut cursor = active_cursor::<T>();
+		let mut meter = WeightMeter::new();
+
+		#[block]
+		{
+			Pallet::<T>::exec_migration(&mut cursor, &mut meter);
+		}
+
+		assert_eq!(cursor.index, 1);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_set_cursor() -> Result<(), BenchmarkError> {
+		let origin =
+			T::ControlOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let cursor = Some(MigrationCursor::Active(active_cursor::<T>()));
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, cursor.clone());
+
+		assert_eq!(Cursor::<T>::get(), cursor);
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(vec![]), crate::mock::Test);
+}
@@ -0,0 +1,387 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Migrations Pallet
+//!
+//! Executes multi-block migrations, i.e. migrations that do not fit into a single block.
+//!
+//! ## Pallet API
+//!
+//! See the [`pallet`] module for more information about the interfaces this pallet exposes,
+//! including its configuration trait, dispatchables, storage items, events and errors.
+//!
+//! ## Overview
+//!
+//! The migrations are configured as a tuple of
+//! [`MigrationStep`](frame_support::migrations::MigrationStep)s in [`Config::Migrations`]. Each
+//! of them does a bounded amount of work per step, and returns a cursor to continue from.
+//!
+//! When the runtime is upgraded, this pallet starts executing the migrations in order. In every
+//! block, in `on_initialize`, it executes as many steps as fit into [`Config::MaxServiceWeight`],
+//! and persists the cursor of the ongoing migration until the next block. Once all migrations
+//! are done, [`Event::UpgradeCompleted`] is emitted.
+//!
+//! Completed migrations are recorded by their identifier, and skipped when they are encountered
+//! again in a later upgrade.
+//!
+//! ### Failures
+//!
+//! Every step runs in its own storage transaction, and the changes of a failing step are rolled
+//! back. If a migration fails, exceeds its maximum number of steps, or needs more weight for a
+//! single step than [`Config::MaxServiceWeight`], the upgrade is considered failed and
+//! [`Event::UpgradeFailed`] is emitted. No further migrations are executed until the cursor is
+//! reset by [`Config::ControlOrigin`] using [`Pallet::force_set_cursor`].
+//!
+//! ### Usage
+//!
+//! While migrations are ongoing, the storage is in an intermediate state. Runtimes should
+//! therefore prevent transactions that touch the migrated storage, for example by consulting
+//! [`Pallet::ongoing`] in their call filter.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(rustdoc::broken_intra_doc_links)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod mock;
+mod tests;
+pub mod weights;
+
+use frame_support::{
+	migrations::{MigrationStepError, MigrationSteps},
+	pallet_prelude::*,
+	storage::{with_transaction, TransactionOutcome},
+	weights::WeightMeter,
+};
+use frame_system::pallet_prelude::*;
+use sp_runtime::traits::Saturating;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+const LOG_TARGET: &str = "runtime::migrations";
+
+/// The encoded cursor of a [`MigrationStep`](frame_support::migrations::MigrationStep).
+pub type RawCursorOf<T> = BoundedVec<u8, <T as Config>::CursorMaxLen>;
+
+/// The encoded identifier of a [`MigrationStep`](frame_support::migrations::MigrationStep).
+pub type IdentifierOf<T> = BoundedVec<u8, <T as Config>::IdentifierMaxLen>;
+
+/// The cursor of the migration executor, as stored by this pallet.
+pub type CursorOf<T> = MigrationCursor<RawCursorOf<T>, BlockNumberFor<T>>;
+
+/// The progress of the migration executor.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum MigrationCursor<Cursor, BlockNumber> {
+	/// A migration is being executed.
+	Active(ActiveCursor<Cursor, BlockNumber>),
+	/// A migration failed, and no further migrations are executed.
+	Stuck,
+}
+
+/// The progress of the migration that is currently being executed.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ActiveCursor<Cursor, BlockNumber> {
+	/// The index of the migration in [`Config::Migrations`].
+	pub index: u32,
+	/// The cursor returned by the last step of the migration, `None` before the first step.
+	pub inner_cursor: Option<Cursor>,
+	/// The number of steps the migration took so far.
+	pub steps: u32,
+	/// The block in which the migration started.
+	pub started_at: BlockNumber,
+}
+
+impl<Cursor, BlockNumber> ActiveCursor<Cursor, BlockNumber> {
+	/// Move on to the next migration, starting in block `now`.
+	fn advance(&mut self, now: BlockNumber) {
+		self.index.saturating_inc();
+		self.inner_cursor = None;
+		self.steps = 0;
+		self.started_at = now;
+	}
+}
+
+/// What the executor should do after executing a migration step.
+enum Progress {
+	/// Carry on in this block.
+	Continue,
+	/// Carry on in the next block.
+	Yield,
+	/// All migrations are done.
+	Completed,
+	/// A migration failed.
+	Stuck,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The migrations to execute after every runtime upgrade, in order.
+		///
+		/// Migrations that already completed in an earlier upgrade are skipped.
+		type Migrations: MigrationSteps;
+
+		/// The maximum length of an encoded migration cursor.
+		#[pallet::constant]
+		type CursorMaxLen: Get<u32>;
+
+		/// The maximum length of an encoded migration identifier.
+		#[pallet::constant]
+		type IdentifierMaxLen: Get<u32>;
+
+		/// The maximum weight to spend on migrations in each block.
+		#[pallet::constant]
+		type MaxServiceWeight: Get<Weight>;
+
+		/// The origin that can override the migration cursor.
+		type ControlOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Weight information for extrinsics and hooks in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The progress of the ongoing migrations, if any.
+	#[pallet::storage]
+	pub type Cursor<T: Config> = StorageValue<_, CursorOf<T>, OptionQuery>;
+
+	/// The identifiers of all the migrations that completed.
+	#[pallet::storage]
+	pub type Historic<T: Config> = StorageMap<_, Twox64Concat, IdentifierOf<T>, (), OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A runtime upgrade started executing `migrations` migrations.
+		UpgradeStarted { migrations: u32 },
+		/// All the migrations of the runtime upgrade completed.
+		UpgradeCompleted,
+		/// A migration failed, and no further migrations will be executed.
+		UpgradeFailed,
+		/// The migration at `index` already completed in an earlier upgrade, and was skipped.
+		MigrationSkipped { index: u32 },
+		/// The migration at `index` took its `steps`th step.
+		MigrationAdvanced { index: u32, steps: u32 },
+		/// The migration at `index` completed after `took` blocks.
+		MigrationCompleted { index: u32, took: BlockNumberFor<T> },
+		/// The migration at `index` failed.
+		MigrationFailed { index: u32 },
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			Self::onboard_migrations()
+		}
+
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			Self::progress_migrations()
+		}
+
+		fn integrity_test() {
+			assert!(
+				!Self::max_step_weight().is_zero(),
+				"MaxServiceWeight must leave room for migration steps in every block",
+			);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Override the migration cursor.
+		///
+		/// Setting the cursor to `None` stops all the ongoing migrations, and can be used to
+		/// recover from a failed upgrade. Can only be called by [`Config::ControlOrigin`].
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::force_set_cursor())]
+		pub fn force_set_cursor(
+			origin: OriginFor<T>,
+			cursor: Option<CursorOf<T>>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			Cursor::<T>::set(cursor);
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Whether migrations are ongoing, or stuck after a failed migration.
+	///
+	/// In both cases, the storage may be in an intermediate state.
+	pub fn ongoing() -> bool {
+		Cursor::<T>::exists()
+	}
+
+	/// Start executing [`Config::Migrations`] after a runtime upgrade.
+	fn onboard_migrations() -> Weight {
+		if let Some(cursor) = Cursor::<T>::get() {
+			log::error!(
+				target: LOG_TARGET,
+				"Runtime upgraded while migrations are ongoing: {:?}",
+				cursor
+			);
+			Cursor::<T>::set(Some(MigrationCursor::Stuck));
+			Self::deposit_event(Event::UpgradeFailed);
+			return T::WeightInfo::onboard_migrations()
+		}
+
+		let migrations = T::Migrations::len();
+		if migrations > 0 {
+			Cursor::<T>::set(Some(MigrationCursor::Active(ActiveCursor {
+				index: 0,
+				inner_cursor: None,
+				steps: 0,
+				started_at: frame_system::Pallet::<T>::block_number(),
+			})));
+			Self::deposit_event(Event::UpgradeStarted { migrations });
+		}
+
+		T::WeightInfo::onboard_migrations()
+	}
+
+	/// Execute as many migration steps as fit into [`Config::MaxServiceWeight`].
+	fn progress_migrations() -> Weight {
+		let mut meter = WeightMeter::with_limit(T::MaxServiceWeight::get());
+		meter.consume(T::WeightInfo::progress_migrations());
+
+		let Some(MigrationCursor::Active(mut cursor)) = Cursor::<T>::get() else {
+			return meter.consumed()
+		};
+
+		loop {
+			match Self::exec_migration(&mut cursor, &mut meter) {
+				Progress::Continue => continue,
+				Progress::Yield => {
+					Cursor::<T>::set(Some(MigrationCursor::Active(cursor)));
+					break
+				},
+				Progress::Completed => {
+					Cursor::<T>::kill();
+					Self::deposit_event(Event::UpgradeCompleted);
+					break
+				},
+				Progress::Stuck => {
+					Cursor::<T>::set(Some(MigrationCursor::Stuck));
+					Self::deposit_event(Event::UpgradeFailed);
+					break
+				},
+			}
+		}
+
+		meter.consumed()
+	}
+
+	/// The maximum weight a single migration step can take in a block.
+	fn max_step_weight() -> Weight {
+		T::MaxServiceWeight::get()
+			.saturating_sub(T::WeightInfo::progress_migrations())
+			.saturating_sub(T::WeightInfo::exec_migration())
+	}
+
+	/// Execute the next step of the migration at `cursor`.
+	fn exec_migration(
+		cursor: &mut ActiveCursor<RawCursorOf<T>, BlockNumberFor<T>>,
+		meter: &mut WeightMeter,
+	) -> Progress {
+		if meter.try_consume(T::WeightInfo::exec_migration()).is_err() {
+			return Progress::Yield
+		}
+
+		let index = cursor.index;
+		let Some(id) = T::Migrations::nth_id(index) else { return Progress::Completed };
+		let Ok(id) = IdentifierOf::<T>::try_from(id) else {
+			log::error!(target: LOG_TARGET, "Identifier of migration {} is too long", index);
+			Self::deposit_event(Event::MigrationFailed { index });
+			return Progress::Stuck
+		};
+		let now = frame_system::Pallet::<T>::block_number();
+
+		if Historic::<T>::contains_key(&id) {
+			Self::deposit_event(Event::MigrationSkipped { index });
+			cursor.advance(now);
+			return Progress::Continue
+		}
+
+		let max_steps = T::Migrations::nth_max_steps(index).flatten();
+		if max_steps.map_or(false, |max_steps| cursor.steps >= max_steps) {
+			log::error!(target: LOG_TARGET, "Migration {} exceeded {:?} steps", index, max_steps);
+			Self::deposit_event(Event::MigrationFailed { index });
+			return Progress::Stuck
+		}
+
+		let inner_cursor = cursor.inner_cursor.clone().map(|c| c.into_inner());
+		let result = with_transaction(|| {
+			let result = T::Migrations::nth_step(index, inner_cursor, meter)
+				.expect("The migration exists, as it has an identifier; qed");
+			match result {
+				Ok(_) => TransactionOutcome::Commit(Ok::<_, DispatchError>(result)),
+				// Don't leave the storage changes of a failed step behind.
+				Err(_) => TransactionOutcome::Rollback(Ok(result)),
+			}
+		});
+		let result = match result {
+			Ok(result) => result,
+			Err(e) => {
+				log::error!(target: LOG_TARGET, "Migration {} can't be executed: {:?}", index, e);
+				Self::deposit_event(Event::MigrationFailed { index });
+				return Progress::Stuck
+			},
+		};
+
+		match result {
+			Ok(Some(next)) => {
+				let Ok(next) = RawCursorOf::<T>::try_from(next) else {
+					log::error!(target: LOG_TARGET, "Cursor of migration {} is too long", index);
+					Self::deposit_event(Event::MigrationFailed { index });
+					return Progress::Stuck
+				};
+				cursor.inner_cursor = Some(next);
+				cursor.steps.saturating_inc();
+				Self::deposit_event(Event::MigrationAdvanced { index, steps: cursor.steps });
+				Progress::Continue
+			},
+			Ok(None) => {
+				Historic::<T>::insert(&id, ());
+				Self::deposit_event(Event::MigrationCompleted {
+					index,
+					took: now.saturating_sub(cursor.started_at),
+				});
+				cursor.advance(now);
+				Progress::Continue
+			},
+			Err(MigrationStepError::InsufficientWeight { required })
+				if required.all_lte(Self::max_step_weight()) =>
+				Progress::Yield,
+			Err(e) => {
+				log::error!(target: LOG_TARGET, "Migration {} failed: {:?}", index, e);
+				Self::deposit_event(Event::MigrationFailed { index });
+				Progress::Stuck
+			},
+		}
+	}
+}
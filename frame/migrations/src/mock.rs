@@ -0,0 +1,182 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for the migrations pallet.
+
+#![cfg(test)]
+
+use crate as pallet_migrations;
+use crate::*;
+use frame_support::{
+	construct_runtime, derive_impl,
+	migrations::{MigrationStep, MigrationStepError},
+	parameter_types,
+	traits::{ConstU32, OnRuntimeUpgrade},
+};
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+use std::cell::RefCell;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Migrations: pallet_migrations,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+}
+
+/// The weight of a single step of a mocked migration.
+pub const STEP_WEIGHT: Weight = Weight::from_parts(10, 0);
+
+/// The maximum number of steps of a mocked migration.
+pub const MAX_STEPS: u32 = 4;
+
+parameter_types! {
+	/// Allows for two mocked migration steps per block.
+	pub const MaxServiceWeight: Weight = Weight::from_parts(25, 0);
+}
+
+/// Free weights, so that only the mocked migration steps count against the limit.
+pub struct MockedWeightInfo;
+impl WeightInfo for MockedWeightInfo {
+	fn onboard_migrations() -> Weight {
+		Weight::zero()
+	}
+	fn progress_migrations() -> Weight {
+		Weight::zero()
+	}
+	fn exec_migration() -> Weight {
+		Weight::zero()
+	}
+	fn force_set_cursor() -> Weight {
+		Weight::zero()
+	}
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Migrations = (MockedMigration<0>, MockedMigration<1>, MockedMigration<2>);
+	type CursorMaxLen = ConstU32<16>;
+	type IdentifierMaxLen = ConstU32<16>;
+	type MaxServiceWeight = MaxServiceWeight;
+	type ControlOrigin = EnsureRoot<u64>;
+	type WeightInfo = MockedWeightInfo;
+}
+
+/// How a mocked migration behaves.
+#[derive(Clone, Copy, Debug)]
+pub enum MockedMigrationKind {
+	/// Completes after the given number of steps.
+	SucceedAfter(u32),
+	/// Fails after the given number of steps.
+	FailAfter(u32),
+	/// Never completes.
+	Endless,
+	/// Needs more weight for a single step than is available in a block.
+	HighWeight,
+}
+
+thread_local! {
+	/// The behaviour of the mocked migrations, by index. Unset ones complete in one step.
+	pub static MOCKED_MIGRATIONS: RefCell<Vec<MockedMigrationKind>> = RefCell::new(vec![]);
+}
+
+/// A migration that behaves as configured at index `N` of [`MOCKED_MIGRATIONS`].
+pub struct MockedMigration<const N: u8>;
+
+impl<const N: u8> MigrationStep for MockedMigration<N> {
+	type Cursor = u32;
+	type Identifier = [u8; 2];
+
+	fn id() -> Self::Identifier {
+		[b'm', N]
+	}
+
+	fn max_steps() -> Option<u32> {
+		Some(MAX_STEPS)
+	}
+
+	fn step(
+		cursor: Option<u32>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<u32>, MigrationStepError> {
+		use MockedMigrationKind::*;
+
+		let kind = MOCKED_MIGRATIONS
+			.with(|m| m.borrow().get(N as usize).copied())
+			.unwrap_or(SucceedAfter(0));
+		let required = match kind {
+			HighWeight => MaxServiceWeight::get().saturating_add(STEP_WEIGHT),
+			_ => STEP_WEIGHT,
+		};
+		meter
+			.try_consume(required)
+			.map_err(|_| MigrationStepError::InsufficientWeight { required })?;
+
+		// Record the progress in storage, to check that failed steps are rolled back.
+		let steps = cursor.unwrap_or_default();
+		frame_support::storage::unhashed::put(&Self::id(), &steps);
+		match kind {
+			SucceedAfter(n) if steps >= n => Ok(None),
+			FailAfter(n) if steps >= n => Err(MigrationStepError::Failed),
+			_ => Ok(Some(steps + 1)),
+		}
+	}
+}
+
+pub fn new_test_ext(migrations: Vec<MockedMigrationKind>) -> sp_io::TestExternalities {
+	MOCKED_MIGRATIONS.with(|m| *m.borrow_mut() = migrations);
+
+	let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Upgrade the runtime in the current block.
+pub fn upgrade() {
+	AllPalletsWithSystem::on_runtime_upgrade();
+}
+
+/// Run `on_initialize` of all the blocks up to and including `n`.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		Migrations::on_initialize(next);
+	}
+}
+
+/// The events of this pallet since the last call, in order.
+pub fn migration_events() -> Vec<Event<Test>> {
+	let events = System::events()
+		.into_iter()
+		.filter_map(|r| match r.event {
+			RuntimeEvent::Migrations(e) => Some(e),
+			_ => None,
+		})
+		.collect();
+	System::reset_events();
+	events
+}
@@ -0,0 +1,182 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the migrations pallet.
+
+#![cfg(test)]
+
+use crate::{mock::*, *};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+use MockedMigrationKind::*;
+
+#[test]
+fn migrations_run_over_multiple_blocks() {
+	new_test_ext(vec![SucceedAfter(1), SucceedAfter(0), SucceedAfter(2)]).execute_with(|| {
+		upgrade();
+		assert!(Migrations::ongoing());
+		assert_eq!(migration_events(), vec![Event::UpgradeStarted { migrations: 3 }]);
+
+		// Two steps fit into each block.
+		run_to_block(2);
+		assert_eq!(
+			migration_events(),
+			vec![
+				Event::MigrationAdvanced { index: 0, steps: 1 },
+				Event::MigrationCompleted { index: 0, took: 1 },
+			]
+		);
+
+		run_to_block(3);
+		assert_eq!(
+			migration_events(),
+			vec![
+				Event::MigrationCompleted { index: 1, took: 1 },
+				Event::MigrationAdvanced { index: 2, steps: 1 },
+			]
+		);
+		assert_eq!(
+			Cursor::<Test>::get(),
+			Some(MigrationCursor::Active(ActiveCursor {
+				index: 2,
+				inner_cursor: Some(1u32.encode().try_into().unwrap()),
+				steps: 1,
+				started_at: 3,
+			}))
+		);
+
+		run_to_block(4);
+		assert_eq!(
+			migration_events(),
+			vec![
+				Event::MigrationAdvanced { index: 2, steps: 2 },
+				Event::MigrationCompleted { index: 2, took: 1 },
+				Event::UpgradeCompleted,
+			]
+		);
+		assert!(!Migrations::ongoing());
+		assert_eq!(Historic::<Test>::iter_keys().count(), 3);
+
+		// Nothing happens afterwards.
+		run_to_block(10);
+		assert!(migration_events().is_empty());
+	});
+}
+
+#[test]
+fn completed_migrations_are_skipped() {
+	new_test_ext(vec![]).execute_with(|| {
+		upgrade();
+		run_to_block(3);
+		assert!(!Migrations::ongoing());
+		migration_events();
+
+		upgrade();
+		run_to_block(4);
+		assert_eq!(
+			migration_events(),
+			vec![
+				Event::UpgradeStarted { migrations: 3 },
+				Event::MigrationSkipped { index: 0 },
+				Event::MigrationSkipped { index: 1 },
+				Event::MigrationSkipped { index: 2 },
+				Event::UpgradeCompleted,
+			]
+		);
+	});
+}
+
+#[test]
+fn failed_migration_gets_stuck() {
+	new_test_ext(vec![FailAfter(1)]).execute_with(|| {
+		upgrade();
+		run_to_block(2);
+		assert_eq!(
+			migration_events(),
+			vec![
+				Event::UpgradeStarted { migrations: 3 },
+				Event::MigrationAdvanced { index: 0, steps: 1 },
+				Event::MigrationFailed { index: 0 },
+				Event::UpgradeFailed,
+			]
+		);
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+		// The changes of the failed step were rolled back, those of the first step are kept.
+		assert_eq!(frame_support::storage::unhashed::get::<u32>(b"m\0"), Some(0));
+
+		// Nothing happens until the cursor is reset.
+		run_to_block(5);
+		assert!(migration_events().is_empty());
+		assert!(Migrations::ongoing());
+
+		assert_noop!(
+			Migrations::force_set_cursor(RuntimeOrigin::signed(1), None),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Migrations::force_set_cursor(RuntimeOrigin::root(), None));
+		assert!(!Migrations::ongoing());
+	});
+}
+
+#[test]
+fn migration_exceeding_max_steps_fails() {
+	new_test_ext(vec![Endless]).execute_with(|| {
+		upgrade();
+		run_to_block(10);
+
+		let events = migration_events();
+		assert_eq!(
+			events.iter().filter(|e| matches!(e, Event::MigrationAdvanced { .. })).count(),
+			MAX_STEPS as usize
+		);
+		assert_eq!(
+			events[events.len() - 2..],
+			[Event::MigrationFailed { index: 0 }, Event::UpgradeFailed]
+		);
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+	});
+}
+
+#[test]
+fn migration_exceeding_service_weight_fails() {
+	new_test_ext(vec![HighWeight]).execute_with(|| {
+		upgrade();
+		run_to_block(2);
+		assert_eq!(
+			migration_events(),
+			vec![
+				Event::UpgradeStarted { migrations: 3 },
+				Event::MigrationFailed { index: 0 },
+				Event::UpgradeFailed,
+			]
+		);
+		assert!(Historic::<Test>::iter_keys().next().is_none());
+	});
+}
+
+#[test]
+fn upgrade_while_ongoing_gets_stuck() {
+	new_test_ext(vec![SucceedAfter(3)]).execute_with(|| {
+		upgrade();
+		run_to_block(2);
+		migration_events();
+
+		upgrade();
+		assert_eq!(migration_events(), vec![Event::UpgradeFailed]);
+		assert_eq!(Cursor::<Test>::get(), Some(MigrationCursor::Stuck));
+	});
+}
@@ -0,0 +1,94 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for `pallet_migrations`.
+//!
+//! These are conservative estimates based on the storage accesses of each item, not benchmark
+//! results. They are tracked in `docs/pending-benchmarks.md` until the benchmarks in
+//! `benchmarking.rs` are run on reference hardware. The weight of the migration steps themselves
+//! is accounted for by the migrations.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use core::marker::PhantomData;
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for `pallet_migrations`.
+pub trait WeightInfo {
+	fn onboard_migrations() -> Weight;
+	fn progress_migrations() -> Weight;
+	fn exec_migration() -> Weight;
+	fn force_set_cursor() -> Weight;
+}
+
+/// Weights for `pallet_migrations` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `Migrations::Cursor` (r:1 w:1)
+	fn onboard_migrations() -> Weight {
+		Weight::from_parts(10_000_000, 1_600)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Migrations::Cursor` (r:1 w:1)
+	fn progress_migrations() -> Weight {
+		Weight::from_parts(10_000_000, 1_600)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Migrations::Historic` (r:1 w:1)
+	fn exec_migration() -> Weight {
+		Weight::from_parts(15_000_000, 3_500)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Migrations::Cursor` (r:0 w:1)
+	fn force_set_cursor() -> Weight {
+		Weight::from_parts(5_000_000, 0).saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `Migrations::Cursor` (r:1 w:1)
+	fn onboard_migrations() -> Weight {
+		Weight::from_parts(10_000_000, 1_600)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Migrations::Cursor` (r:1 w:1)
+	fn progress_migrations() -> Weight {
+		Weight::from_parts(10_000_000, 1_600)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Migrations::Historic` (r:1 w:1)
+	fn exec_migration() -> Weight {
+		Weight::from_parts(15_000_000, 3_500)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Migrations::Cursor` (r:0 w:1)
+	fn force_set_cursor() -> Weight {
+		Weight::from_parts(5_000_000, 0).saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
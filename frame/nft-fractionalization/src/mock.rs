@@ -88,6 +88,7 @@ impl pallet_assets::Config for Test {
 	type MetadataDepositPerByte = ConstU64<1>;
 	type ApprovalDeposit = ConstU64<1>;
 	type StringLimit = ConstU32<50>;
+	type MaxBatch = ConstU32<10>;
 	type Freezer = ();
 	type Extra = ();
 	type CallbackHandle = ();
@@ -23,11 +23,12 @@ use codec::{Decode, Encode};
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait NftsApi<AccountId, CollectionId, ItemId>
 	where
 		AccountId: Encode + Decode,
-		CollectionId: Encode,
-		ItemId: Encode,
+		CollectionId: Encode + Decode,
+		ItemId: Encode + Decode,
 	{
 		fn owner(collection: CollectionId, item: ItemId) -> Option<AccountId>;
 
@@ -53,5 +54,8 @@ sp_api::decl_runtime_apis! {
 		) -> Option<Vec<u8>>;
 
 		fn collection_attribute(collection: CollectionId, key: Vec<u8>) -> Option<Vec<u8>>;
+
+		#[api_version(2)]
+		fn owned_items(account: AccountId) -> Vec<(CollectionId, ItemId)>;
 	}
 }
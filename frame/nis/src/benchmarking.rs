@@ -39,6 +39,14 @@ const SEED: u32 = 0;
 type BalanceOf<T> =
 	<<T as Config>::Currency as FunInspect<<T as frame_system::Config>::AccountId>>::Balance;
 
+/// A distinct account funded for a single bid of `MinBid`, so that queues may be filled without
+/// running into `MaxBidsPerAccountPerQueue`.
+fn bidder<T: Config>(index: u32) -> T::AccountId {
+	let who: T::AccountId = account("bidder", index, SEED);
+	T::Currency::set_balance(&who, T::Currency::minimum_balance() + T::MinBid::get());
+	who
+}
+
 fn fill_queues<T: Config>() -> Result<(), DispatchError> {
 	// filling queues involves filling the first queue entirely and placing a single item in all
 	// other queues.
@@ -49,8 +57,8 @@ fn fill_queues<T: Config>() -> Result<(), DispatchError> {
 	let caller: T::AccountId = whitelisted_caller();
 	T::Currency::set_balance(&caller, T::MinBid::get() * BalanceOf::<T>::from(queues + bids));
 
-	for _ in 0..bids {
-		Nis::<T>::place_bid(RawOrigin::Signed(caller.clone()).into(), T::MinBid::get(), 1)?;
+	for i in 0..bids {
+		Nis::<T>::place_bid(RawOrigin::Signed(bidder::<T>(i)).into(), T::MinBid::get(), 1)?;
 	}
 	for d in 1..queues {
 		Nis::<T>::place_bid(RawOrigin::Signed(caller.clone()).into(), T::MinBid::get(), 1 + d)?;
@@ -60,12 +68,17 @@ fn fill_queues<T: Config>() -> Result<(), DispatchError> {
 
 benchmarks! {
 	place_bid {
-		let l in 0..(T::MaxQueueLen::get() - 1);
+		// The caller already has all but one of the bids it may place in the queue.
+		let own = T::MaxBidsPerAccountPerQueue::get() - 1;
+		let l in own..(T::MaxQueueLen::get() - 1);
 		let caller: T::AccountId = whitelisted_caller();
 		let ed = T::Currency::minimum_balance();
 		let bid = T::MinBid::get();
-		T::Currency::set_balance(&caller, (ed + bid) * BalanceOf::<T>::from(l + 1) + bid);
-		for i in 0..l {
+		T::Currency::set_balance(&caller, ed + bid * BalanceOf::<T>::from(own) + bid + bid);
+		for i in own..l {
+			Nis::<T>::place_bid(RawOrigin::Signed(bidder::<T>(i)).into(), T::MinBid::get(), 1)?;
+		}
+		for _ in 0..own {
 			Nis::<T>::place_bid(RawOrigin::Signed(caller.clone()).into(), T::MinBid::get(), 1)?;
 		}
 	}: _(RawOrigin::Signed(caller.clone()), T::MinBid::get() * BalanceOf::<T>::from(2u32), 1)
@@ -78,10 +91,9 @@ benchmarks! {
 		let origin = RawOrigin::Signed(caller.clone());
 		let ed = T::Currency::minimum_balance();
 		let bid = T::MinBid::get();
-		let ql = T::MaxQueueLen::get();
-		T::Currency::set_balance(&caller, (ed + bid) * BalanceOf::<T>::from(ql + 1) + bid);
+		T::Currency::set_balance(&caller, ed + bid + bid);
 		for i in 0..T::MaxQueueLen::get() {
-			Nis::<T>::place_bid(origin.clone().into(), T::MinBid::get(), 1)?;
+			Nis::<T>::place_bid(RawOrigin::Signed(bidder::<T>(i)).into(), T::MinBid::get(), 1)?;
 		}
 	}: place_bid(origin, T::MinBid::get() * BalanceOf::<T>::from(2u32), 1)
 	verify {
@@ -92,12 +104,17 @@ benchmarks! {
 	}
 
 	retract_bid {
-		let l in 1..T::MaxQueueLen::get();
+		// The caller has all of the bids it may place in the queue.
+		let own = T::MaxBidsPerAccountPerQueue::get();
+		let l in own..T::MaxQueueLen::get();
 		let caller: T::AccountId = whitelisted_caller();
 		let ed = T::Currency::minimum_balance();
 		let bid = T::MinBid::get();
-		T::Currency::set_balance(&caller, (ed + bid) * BalanceOf::<T>::from(l + 1) + bid);
-		for i in 0..l {
+		T::Currency::set_balance(&caller, ed + bid * BalanceOf::<T>::from(own));
+		for i in own..l {
+			Nis::<T>::place_bid(RawOrigin::Signed(bidder::<T>(i)).into(), T::MinBid::get(), 1)?;
+		}
+		for _ in 0..own {
 			Nis::<T>::place_bid(RawOrigin::Signed(caller.clone()).into(), T::MinBid::get(), 1)?;
 		}
 	}: _(RawOrigin::Signed(caller.clone()), T::MinBid::get(), 1)
@@ -105,6 +122,25 @@ benchmarks! {
 		assert_eq!(QueueTotals::<T>::get()[0], (l - 1, T::MinBid::get() * BalanceOf::<T>::from(l - 1)));
 	}
 
+	retract_all_bids {
+		// The caller has all of the bids it may place in the queue.
+		let own = T::MaxBidsPerAccountPerQueue::get();
+		let l in own..T::MaxQueueLen::get();
+		let caller: T::AccountId = whitelisted_caller();
+		let ed = T::Currency::minimum_balance();
+		let bid = T::MinBid::get();
+		T::Currency::set_balance(&caller, ed + bid * BalanceOf::<T>::from(own));
+		for i in own..l {
+			Nis::<T>::place_bid(RawOrigin::Signed(bidder::<T>(i)).into(), T::MinBid::get(), 1)?;
+		}
+		for _ in 0..own {
+			Nis::<T>::place_bid(RawOrigin::Signed(caller.clone()).into(), T::MinBid::get(), 1)?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), 1)
+	verify {
+		assert_eq!(QueueTotals::<T>::get()[0], (l - own, T::MinBid::get() * BalanceOf::<T>::from(l - own)));
+	}
+
 	fund_deficit {
 		let origin =
 			T::FundOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
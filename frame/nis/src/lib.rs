@@ -38,10 +38,12 @@
 //! bid size so that smaller bids fall off as it gets too large.
 //!
 //! Account may enqueue a balance with some number of `Period`s lock up, up to a maximum of
-//! `QueueCount`. The balance gets reserved. There's a minimum of `MinBid` to avoid dust.
+//! `QueueCount`. The balance gets reserved. There's a minimum of `MinBid` to avoid dust and a
+//! maximum of `MaxBidsPerAccountPerQueue` bids per account in any one queue to avoid a single
+//! account filling it.
 //!
 //! Until your bid is consolidated and you receive a receipt, you can retract it instantly and the
-//! funds are unreserved.
+//! funds are unreserved. All of an account's bids in a queue may be retracted at once.
 //!
 //! There's a target proportion of effective total issuance (i.e. accounting for existing receipts)
 //! which the pallet attempts to have frozen at any one time. It will likely be gradually increased
@@ -262,6 +264,12 @@ pub mod pallet {
 		#[pallet::constant]
 		type FifoQueueLen: Get<u32>;
 
+		/// Maximum number of bids that a single account may have in each duration queue.
+		///
+		/// Must be larger than zero and no greater than `MaxQueueLen`.
+		#[pallet::constant]
+		type MaxBidsPerAccountPerQueue: Get<u32>;
+
 		/// The base period for the duration queues. This is the common multiple across all
 		/// supported freezing durations that can be bid upon.
 		#[pallet::constant]
@@ -463,6 +471,8 @@ pub mod pallet {
 		AlreadyCommunal,
 		/// The receipt is already private.
 		AlreadyPrivate,
+		/// The account already has the maximum number of bids in the queue for this duration.
+		TooManyBids,
 	}
 
 	/// A reason for the NIS pallet placing a hold on funds.
@@ -517,6 +527,8 @@ pub mod pallet {
 		fn integrity_test() {
 			assert!(!T::IntakePeriod::get().is_zero());
 			assert!(!T::MaxQueueLen::get().is_zero());
+			assert!(!T::MaxBidsPerAccountPerQueue::get().is_zero());
+			assert!(T::MaxBidsPerAccountPerQueue::get() <= T::MaxQueueLen::get());
 		}
 	}
 
@@ -531,6 +543,9 @@ pub mod pallet {
 		/// - `duration`: The number of periods before which the newly consolidated bid may be
 		///   thawed. Must be greater than 1 and no more than `QueueCount`.
 		///
+		/// Fails with `TooManyBids` if the account already has `MaxBidsPerAccountPerQueue` bids in
+		/// the queue, unless the new bid replaces one of them.
+		///
 		/// Complexities:
 		/// - `Queues[duration].len()` (just take max).
 		#[pallet::call_index(0)]
@@ -552,6 +567,13 @@ pub mod pallet {
 				|q| -> Result<(u32, BalanceOf<T>), DispatchError> {
 					let queue_full = q.len() == T::MaxQueueLen::get() as usize;
 					ensure!(!queue_full || q[0].amount < amount, Error::<T>::BidTooLow);
+					// A bid which knocks out one of the account's own bids does not grow its count.
+					let replaces_own = queue_full && q[0].who == who;
+					let count = q.iter().filter(|b| b.who == who).count();
+					ensure!(
+						replaces_own || count < T::MaxBidsPerAccountPerQueue::get() as usize,
+						Error::<T>::TooManyBids
+					);
 					T::Currency::hold(&HoldReason::NftReceipt.into(), &who, amount)?;
 
 					// queue is <Ordered: Lowest ... Highest><Fifo: Last ... First>
@@ -900,6 +922,54 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Retract all of the caller's bids in the queue for `duration`.
+		///
+		/// Origin must be Signed, and the account should have at least one still-active bid for
+		/// `duration`.
+		///
+		/// - `duration`: The duration of the bids to retract.
+		#[pallet::call_index(7)]
+		// Charged as a `retract_bid` for every bid the account may have in the queue.
+		#[pallet::weight(T::WeightInfo::retract_bid(T::MaxQueueLen::get())
+			.saturating_mul(T::MaxBidsPerAccountPerQueue::get().into()))]
+		pub fn retract_all_bids(origin: OriginFor<T>, duration: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let queue_count = T::QueueCount::get() as usize;
+			let queue_index = duration.checked_sub(1).ok_or(Error::<T>::DurationTooSmall)? as usize;
+			ensure!(queue_index < queue_count, Error::<T>::DurationTooBig);
+
+			let mut queue = Queues::<T>::get(duration);
+			let mut retracted = Vec::new();
+			queue.retain(|bid| {
+				if bid.who == who {
+					retracted.push(bid.amount);
+					false
+				} else {
+					true
+				}
+			});
+			ensure!(!retracted.is_empty(), Error::<T>::UnknownBid);
+			let new_len = queue.len() as u32;
+
+			let total =
+				retracted.iter().fold(Zero::zero(), |a: BalanceOf<T>, b| a.saturating_add(*b));
+			T::Currency::release(&HoldReason::NftReceipt.into(), &who, total, BestEffort)?;
+
+			Queues::<T>::insert(duration, queue);
+			QueueTotals::<T>::mutate(|qs| {
+				qs.bounded_resize(queue_count, (0, Zero::zero()));
+				qs[queue_index].0 = new_len;
+				qs[queue_index].1.saturating_reduce(total);
+			});
+
+			for amount in retracted {
+				Self::deposit_event(Event::BidRetracted { who: who.clone(), amount, duration });
+			}
+
+			Ok(())
+		}
 	}
 
 	/// Issuance information returned by `issuance()`.
@@ -93,6 +93,7 @@ parameter_types! {
 	pub const MinReceipt: Perquintill = Perquintill::from_percent(1);
 	pub const ThawThrottle: (Perquintill, u64) = (Perquintill::from_percent(25), 5);
 	pub static MaxIntakeWeight: Weight = Weight::from_parts(2_000_000_000_000, 0);
+	pub static MaxBidsPerAccountPerQueue: u32 = 3;
 }
 
 ord_parameter_types! {
@@ -114,6 +115,7 @@ impl pallet_nis::Config for Test {
 	type QueueCount = ConstU32<3>;
 	type MaxQueueLen = ConstU32<3>;
 	type FifoQueueLen = ConstU32<1>;
+	type MaxBidsPerAccountPerQueue = MaxBidsPerAccountPerQueue;
 	type BasePeriod = ConstU64<3>;
 	type MinBid = ConstU64<2>;
 	type IntakePeriod = ConstU64<2>;
@@ -125,6 +125,29 @@ fn place_bid_fails_when_queue_full() {
 	});
 }
 
+#[test]
+fn place_bid_limits_bids_per_account() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		MaxBidsPerAccountPerQueue::set(2);
+		assert_ok!(Nis::place_bid(signed(1), 10, 2));
+		assert_ok!(Nis::place_bid(signed(1), 20, 2));
+		assert_noop!(Nis::place_bid(signed(1), 30, 2), Error::<Test>::TooManyBids);
+		// Other queues and other accounts are unaffected.
+		assert_ok!(Nis::place_bid(signed(1), 30, 3));
+		assert_ok!(Nis::place_bid(signed(2), 30, 2));
+
+		// Knocking out one of its own bids does not count against the account.
+		MaxBidsPerAccountPerQueue::set(1);
+		assert_ok!(Nis::place_bid(signed(3), 5, 1));
+		assert_ok!(Nis::place_bid(signed(4), 6, 1));
+		assert_ok!(Nis::place_bid(signed(2), 5, 1));
+		assert_ok!(Nis::place_bid(signed(2), 40, 1));
+		assert_eq!(Balances::reserved_balance(2), 70);
+		assert_noop!(Nis::place_bid(signed(3), 50, 1), Error::<Test>::TooManyBids);
+	});
+}
+
 #[test]
 fn multiple_place_bids_works() {
 	new_test_ext().execute_with(|| {
@@ -151,6 +174,28 @@ fn multiple_place_bids_works() {
 	});
 }
 
+#[test]
+fn retract_all_bids_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(Nis::place_bid(signed(1), 10, 2));
+		assert_ok!(Nis::place_bid(signed(2), 10, 2));
+		assert_ok!(Nis::place_bid(signed(1), 20, 2));
+		assert_ok!(Nis::place_bid(signed(1), 10, 3));
+
+		assert_noop!(Nis::retract_all_bids(signed(1), 0), Error::<Test>::DurationTooSmall);
+		assert_noop!(Nis::retract_all_bids(signed(1), 4), Error::<Test>::DurationTooBig);
+		assert_noop!(Nis::retract_all_bids(signed(1), 1), Error::<Test>::UnknownBid);
+		assert_ok!(Nis::retract_all_bids(signed(1), 2));
+
+		assert_eq!(Balances::reserved_balance(1), 10);
+		assert_eq!(Queues::<Test>::get(2), vec![Bid { amount: 10, who: 2 }]);
+		assert_eq!(Queues::<Test>::get(3), vec![Bid { amount: 10, who: 1 }]);
+		assert_eq!(QueueTotals::<Test>::get(), vec![(0, 0), (1, 10), (1, 10)]);
+		assert_noop!(Nis::retract_all_bids(signed(1), 2), Error::<Test>::UnknownBid);
+	});
+}
+
 #[test]
 fn retract_single_item_queue_works() {
 	new_test_ext().execute_with(|| {
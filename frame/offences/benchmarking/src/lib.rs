@@ -25,7 +25,10 @@ mod mock;
 use sp_std::{prelude::*, vec};
 
 use frame_benchmarking::v1::{account, benchmarks};
-use frame_support::traits::{Currency, Get};
+use frame_support::{
+	traits::{Currency, Get},
+	weights::Weight,
+};
 use frame_system::{Config as SystemConfig, Pallet as System, RawOrigin};
 
 use sp_runtime::{
@@ -199,6 +202,8 @@ benchmarks! {
 		assert_eq!(System::<T>::event_count(), 0);
 	}: {
 		let _ = Offences::<T>::report_offence(reporters, offence);
+		// apply the queued slashes straight away
+		Offences::<T>::process_queued_offences(Weight::MAX);
 	}
 	verify {
 		// make sure that all slashes have been applied
@@ -236,6 +241,8 @@ benchmarks! {
 		assert_eq!(System::<T>::event_count(), 0);
 	}: {
 		let _ = Offences::<T>::report_offence(reporters, offence);
+		// apply the queued slashes straight away
+		Offences::<T>::process_queued_offences(Weight::MAX);
 	}
 	verify {
 		// make sure that all slashes have been applied
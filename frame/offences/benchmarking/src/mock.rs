@@ -27,6 +27,7 @@ use frame_election_provider_support::{
 use frame_support::{
 	derive_impl, parameter_types,
 	traits::{ConstU32, ConstU64},
+	weights::Weight,
 };
 use frame_system as system;
 use pallet_session::historical as pallet_session_historical;
@@ -87,6 +88,9 @@ impl pallet_timestamp::Config for Test {
 	type Moment = u64;
 	type OnTimestampSet = ();
 	type MinimumPeriod = ConstU64<5>;
+	type ExpectedTimestamp = ();
+	type MaxTimestampDrift = ();
+	type OnTimestampDrift = ();
 	type WeightInfo = ();
 }
 impl pallet_session::historical::Config for Test {
@@ -124,6 +128,9 @@ parameter_types! {
 impl pallet_session::Config for Test {
 	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Test, Staking>;
 	type Keys = SessionKeys;
+	type InactiveKeysExpiry = ();
+	type ValidatorIntent = ();
+	type MaxKeysPurgedPerSession = ConstU32<100>;
 	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
 	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
 	type SessionHandler = TestSessionHandler;
@@ -191,6 +198,11 @@ impl pallet_staking::Config for Test {
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const OfflineThreshold: Perbill = Perbill::from_percent(10);
+	pub const MaxOfflineSlash: Perbill = Perbill::from_percent(7);
+}
+
 impl pallet_im_online::Config for Test {
 	type AuthorityId = UintAuthorityId;
 	type RuntimeEvent = RuntimeEvent;
@@ -198,15 +210,22 @@ impl pallet_im_online::Config for Test {
 	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
 	type ReportUnresponsiveness = Offences;
 	type UnsignedPriority = ();
+	type OfflineThreshold = OfflineThreshold;
+	type MaxOfflineSlash = MaxOfflineSlash;
 	type WeightInfo = ();
 	type MaxKeys = ConstU32<10_000>;
 	type MaxPeerInHeartbeats = ConstU32<10_000>;
 }
 
+parameter_types! {
+	pub const MaxOffenceProcessingWeight: Weight = Weight::MAX;
+}
+
 impl pallet_offences::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
 	type OnOffenceHandler = Staking;
+	type MaxOffenceProcessingWeight = MaxOffenceProcessingWeight;
 }
 
 impl<T> frame_system::offchain::SendTransactionTypes<T> for Test
@@ -17,7 +17,11 @@
 
 //! # Offences Pallet
 //!
-//! Tracks reported offences
+//! Tracks reported offences.
+//!
+//! Offenders are not passed to the offence handler when they are reported. Instead they are
+//! queued, and the queue is processed in `on_initialize` within a weight budget, so that a mass
+//! slashing event is spread over several blocks rather than producing an overweight block.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -28,11 +32,15 @@ mod tests;
 
 use core::marker::PhantomData;
 
-use codec::Encode;
+use codec::{Decode, Encode};
 use frame_support::weights::Weight;
-use sp_runtime::{traits::Hash, Perbill};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Hash, Perbill, RuntimeDebug};
 use sp_staking::{
-	offence::{Kind, Offence, OffenceDetails, OffenceError, OnOffenceHandler, ReportOffence},
+	offence::{
+		DisableStrategy, Kind, Offence, OffenceDetails, OffenceError, OnOffenceHandler,
+		ReportOffence,
+	},
 	SessionIndex,
 };
 use sp_std::prelude::*;
@@ -45,12 +53,30 @@ type OpaqueTimeSlot = Vec<u8>;
 /// A type alias for a report identifier.
 type ReportIdOf<T> = <T as frame_system::Config>::Hash;
 
+/// A type alias for an offender waiting in the queue.
+type QueuedOffenceOf<T> =
+	QueuedOffence<<T as frame_system::Config>::AccountId, <T as Config>::IdentificationTuple>;
+
+/// An offender that has been reported but not yet passed to the offence handler.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct QueuedOffence<Reporter, Offender> {
+	/// The offender and the accounts that reported them.
+	pub details: OffenceDetails<Reporter, Offender>,
+	/// The fraction the offender is slashed by.
+	pub slash_fraction: Perbill,
+	/// The session in which the offence was committed.
+	pub session_index: SessionIndex,
+	/// In which cases the offender is disabled.
+	pub disable_strategy: DisableStrategy,
+}
+
 const LOG_TARGET: &str = "runtime::offences";
 
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
 
 	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
@@ -68,6 +94,12 @@ pub mod pallet {
 		type IdentificationTuple: Parameter;
 		/// A handler called for every offence report.
 		type OnOffenceHandler: OnOffenceHandler<Self::AccountId, Self::IdentificationTuple, Weight>;
+		/// The weight that may be spent passing queued offenders to the offence handler in a
+		/// single block.
+		///
+		/// At least one queued offender is processed in every block, so the queue always drains.
+		#[pallet::constant]
+		type MaxOffenceProcessingWeight: Get<Weight>;
 	}
 
 	/// The primary structure that holds all offence records keyed by report identifiers.
@@ -92,6 +124,15 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Offenders that have been reported but not yet passed to the offence handler, keyed by
+	/// their position in the queue.
+	#[pallet::storage]
+	pub type QueuedOffences<T: Config> = StorageMap<_, Twox64Concat, u32, QueuedOffenceOf<T>>;
+
+	/// The position of the first queued offender and the position the next one is queued at.
+	#[pallet::storage]
+	pub type QueueBounds<T> = StorageValue<_, (u32, u32), ValueQuery>;
+
 	/// Events type.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -101,6 +142,13 @@ pub mod pallet {
 		/// \[kind, timeslot\].
 		Offence { kind: Kind, timeslot: OpaqueTimeSlot },
 	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			Self::process_queued_offences(T::MaxOffenceProcessingWeight::get())
+		}
+	}
 }
 
 impl<T, O> ReportOffence<T::AccountId, T::IdentificationTuple, O> for Pallet<T>
@@ -126,11 +174,9 @@ where
 		// The amount new offenders are slashed
 		let new_fraction = offence.slash_fraction(offenders_count);
 
-		let slash_perbill: Vec<_> = (0..concurrent_offenders.len()).map(|_| new_fraction).collect();
-
-		T::OnOffenceHandler::on_offence(
-			&concurrent_offenders,
-			&slash_perbill,
+		Self::queue_offenders(
+			concurrent_offenders,
+			new_fraction,
 			offence.session_index(),
 			offence.disable_strategy(),
 		);
@@ -152,6 +198,62 @@ where
 }
 
 impl<T: Config> Pallet<T> {
+	/// Pass queued offenders to the offence handler, oldest first, as long as the next one is
+	/// expected to fit in `budget`.
+	///
+	/// The weight of the next offender is expected to be the most spent on a single offender so
+	/// far. At least one offender is processed if the queue is not empty, even if it exceeds
+	/// `budget`. Returns the weight spent.
+	pub fn process_queued_offences(budget: Weight) -> Weight {
+		let (mut head, tail) = QueueBounds::<T>::get();
+		let mut weight = T::DbWeight::get().reads(1);
+		if head == tail {
+			return weight
+		}
+		// the queue bounds are written once done.
+		weight.saturating_accrue(T::DbWeight::get().writes(1));
+
+		let mut next = Weight::zero();
+		let mut processed_any = false;
+		while head != tail && (!processed_any || weight.saturating_add(next).all_lte(budget)) {
+			let mut spent = T::DbWeight::get().reads_writes(1, 1);
+			if let Some(queued) = QueuedOffences::<T>::take(head) {
+				let consumed = T::OnOffenceHandler::on_offence(
+					&[queued.details],
+					&[queued.slash_fraction],
+					queued.session_index,
+					queued.disable_strategy,
+				);
+				spent.saturating_accrue(consumed);
+			}
+			weight.saturating_accrue(spent);
+			next = next.max(spent);
+			head = head.wrapping_add(1);
+			processed_any = true;
+		}
+
+		QueueBounds::<T>::put((head, tail));
+		weight
+	}
+
+	/// Queue the given offenders to be slashed by `slash_fraction`.
+	fn queue_offenders(
+		offenders: Vec<OffenceDetails<T::AccountId, T::IdentificationTuple>>,
+		slash_fraction: Perbill,
+		session_index: SessionIndex,
+		disable_strategy: DisableStrategy,
+	) {
+		let (head, mut tail) = QueueBounds::<T>::get();
+		for details in offenders {
+			QueuedOffences::<T>::insert(
+				tail,
+				QueuedOffence { details, slash_fraction, session_index, disable_strategy },
+			);
+			tail = tail.wrapping_add(1);
+		}
+		QueueBounds::<T>::put((head, tail));
+	}
+
 	/// Compute the ID for the given report properties.
 	///
 	/// The report id depends on the offence kind, time slot and the id of offender.
@@ -24,7 +24,7 @@ use crate::Config;
 use codec::Encode;
 use frame_support::{
 	derive_impl, parameter_types,
-	traits::{ConstU32, ConstU64},
+	traits::{ConstU32, ConstU64, Hooks},
 	weights::{constants::RocksDbWeight, Weight},
 };
 use sp_core::H256;
@@ -42,6 +42,7 @@ pub struct OnOffenceHandler;
 parameter_types! {
 	pub static OnOffencePerbill: Vec<Perbill> = Default::default();
 	pub static OffenceWeight: Weight = Default::default();
+	pub static MaxOffenceProcessingWeight: Weight = Weight::MAX;
 }
 
 impl<Reporter, Offender> offence::OnOffenceHandler<Reporter, Offender, Weight>
@@ -54,13 +55,18 @@ impl<Reporter, Offender> offence::OnOffenceHandler<Reporter, Offender, Weight>
 		_disable_strategy: DisableStrategy,
 	) -> Weight {
 		OnOffencePerbill::mutate(|f| {
-			*f = slash_fraction.to_vec();
+			f.extend_from_slice(slash_fraction);
 		});
 
 		OffenceWeight::get()
 	}
 }
 
+/// Passes queued offenders to the offence handler, within the configured weight budget.
+pub fn process_queued_offences() {
+	Offences::on_initialize(System::block_number());
+}
+
 pub fn with_on_offence_fractions<R, F: FnOnce(&mut Vec<Perbill>) -> R>(f: F) -> R {
 	OnOffencePerbill::mutate(|fractions| f(fractions))
 }
@@ -105,6 +111,7 @@ impl Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type IdentificationTuple = u64;
 	type OnOffenceHandler = OnOffenceHandler;
+	type MaxOffenceProcessingWeight = MaxOffenceProcessingWeight;
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -21,8 +21,9 @@
 
 use super::*;
 use crate::mock::{
-	new_test_ext, offence_reports, with_on_offence_fractions, Offence, Offences, RuntimeEvent,
-	System, KIND,
+	new_test_ext, offence_reports, process_queued_offences, with_on_offence_fractions,
+	MaxOffenceProcessingWeight, Offence, OffenceWeight, Offences, Runtime, RuntimeEvent, System,
+	KIND,
 };
 use frame_system::{EventRecord, Phase};
 use sp_runtime::Perbill;
@@ -38,6 +39,7 @@ fn should_report_an_authority_and_trigger_on_offence() {
 
 		// when
 		Offences::report_offence(vec![], offence).unwrap();
+		process_queued_offences();
 
 		// then
 		with_on_offence_fractions(|f| {
@@ -55,6 +57,7 @@ fn should_not_report_the_same_authority_twice_in_the_same_slot() {
 
 		let offence = Offence { validator_set_count: 5, time_slot, offenders: vec![5] };
 		Offences::report_offence(vec![], offence.clone()).unwrap();
+		process_queued_offences();
 		with_on_offence_fractions(|f| {
 			assert_eq!(f.clone(), vec![Perbill::from_percent(25)]);
 			f.clear();
@@ -80,6 +83,7 @@ fn should_report_in_different_time_slot() {
 
 		let mut offence = Offence { validator_set_count: 5, time_slot, offenders: vec![5] };
 		Offences::report_offence(vec![], offence.clone()).unwrap();
+		process_queued_offences();
 		with_on_offence_fractions(|f| {
 			assert_eq!(f.clone(), vec![Perbill::from_percent(25)]);
 			f.clear();
@@ -89,6 +93,7 @@ fn should_report_in_different_time_slot() {
 		// report for the second time
 		offence.time_slot += 1;
 		Offences::report_offence(vec![], offence).unwrap();
+		process_queued_offences();
 
 		// then
 		with_on_offence_fractions(|f| {
@@ -108,6 +113,7 @@ fn should_deposit_event() {
 
 		// when
 		Offences::report_offence(vec![], offence).unwrap();
+		process_queued_offences();
 
 		// then
 		assert_eq!(
@@ -133,6 +139,7 @@ fn doesnt_deposit_event_for_dups() {
 
 		let offence = Offence { validator_set_count: 5, time_slot, offenders: vec![5] };
 		Offences::report_offence(vec![], offence.clone()).unwrap();
+		process_queued_offences();
 		with_on_offence_fractions(|f| {
 			assert_eq!(f.clone(), vec![Perbill::from_percent(25)]);
 			f.clear();
@@ -225,6 +232,7 @@ fn should_properly_count_offences() {
 		let offence1 = Offence { validator_set_count: 5, time_slot, offenders: vec![5] };
 		let offence2 = Offence { validator_set_count: 5, time_slot, offenders: vec![4] };
 		Offences::report_offence(vec![], offence1).unwrap();
+		process_queued_offences();
 		with_on_offence_fractions(|f| {
 			assert_eq!(f.clone(), vec![Perbill::from_percent(25)]);
 			f.clear();
@@ -245,3 +253,80 @@ fn should_properly_count_offences() {
 		);
 	});
 }
+
+#[test]
+fn should_queue_offenders_until_processed() {
+	new_test_ext().execute_with(|| {
+		// given
+		let time_slot = 42;
+		let offence = Offence { validator_set_count: 5, time_slot, offenders: vec![5] };
+
+		// when
+		Offences::report_offence(vec![], offence).unwrap();
+
+		// then
+		// nothing is slashed until the queue is processed.
+		with_on_offence_fractions(|f| {
+			assert_eq!(f.clone(), vec![]);
+		});
+		assert_eq!(QueueBounds::<Runtime>::get(), (0, 1));
+
+		process_queued_offences();
+		with_on_offence_fractions(|f| {
+			assert_eq!(f.clone(), vec![Perbill::from_percent(25)]);
+		});
+		assert_eq!(QueueBounds::<Runtime>::get(), (1, 1));
+		assert_eq!(QueuedOffences::<Runtime>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn should_process_queued_offenders_within_weight_budget() {
+	new_test_ext().execute_with(|| {
+		// given
+		OffenceWeight::set(Weight::from_parts(1_000_000_000, 0));
+		let budget = Weight::from_parts(2_500_000_000, 0);
+		MaxOffenceProcessingWeight::set(budget);
+		let time_slot = 42;
+		let offence =
+			Offence { validator_set_count: 10, time_slot, offenders: vec![1, 2, 3, 4, 5] };
+		Offences::report_offence(vec![], offence).unwrap();
+
+		// when
+		let consumed = Offences::process_queued_offences(budget);
+
+		// then
+		// the budget only allows two offenders per block, along with their storage accesses.
+		assert!(consumed.all_lte(budget));
+		with_on_offence_fractions(|f| {
+			assert_eq!(f.clone(), vec![Perbill::from_percent(55); 2]);
+		});
+
+		process_queued_offences();
+		process_queued_offences();
+		with_on_offence_fractions(|f| {
+			assert_eq!(f.clone(), vec![Perbill::from_percent(55); 5]);
+		});
+		assert_eq!(QueueBounds::<Runtime>::get(), (5, 5));
+	});
+}
+
+#[test]
+fn should_process_at_least_one_queued_offender() {
+	new_test_ext().execute_with(|| {
+		// given
+		MaxOffenceProcessingWeight::set(Weight::zero());
+		let time_slot = 42;
+		let offence = Offence { validator_set_count: 10, time_slot, offenders: vec![1, 2] };
+		Offences::report_offence(vec![], offence).unwrap();
+
+		// when
+		process_queued_offences();
+
+		// then
+		with_on_offence_fractions(|f| {
+			assert_eq!(f.clone(), vec![Perbill::from_percent(25)]);
+		});
+		assert_eq!(QueueBounds::<Runtime>::get(), (1, 2));
+	});
+}
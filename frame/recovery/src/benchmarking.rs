@@ -263,6 +263,9 @@ benchmarks! {
 
 		// Create the active recovery storage item
 		<ActiveRecoveries<T>>::insert(&lost_account, &caller, recovery_status);
+
+		// Give every friend a weight
+		<FriendWeights<T>>::insert(&lost_account, BoundedVec::truncate_from(vec![1; n as usize]));
 	}: _(
 		RawOrigin::Signed(caller.clone()),
 		lost_account_lookup
@@ -377,5 +380,42 @@ benchmarks! {
 		account_lookup
 	)
 
+	set_friend_weights {
+		let n in 1 .. T::MaxFriends::get();
+
+		let caller: T::AccountId = whitelisted_caller();
+
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+
+		// Create friends
+		let friends = generate_friends::<T>(n);
+		let bounded_friends: FriendsOf<T> = friends.try_into().unwrap();
+
+		// Get deposit for recovery
+		let total_deposit = get_total_deposit::<T>(&bounded_friends).unwrap();
+
+		let recovery_config = RecoveryConfig {
+			delay_period: DEFAULT_DELAY.into(),
+			deposit: total_deposit,
+			friends: bounded_friends.clone(),
+			threshold: n as u16,
+		};
+
+		// Create the recovery config storage item
+		<Recoverable<T>>::insert(&caller, recovery_config);
+
+		// Reserve deposit for recovery
+		T::Currency::reserve(&caller, total_deposit).unwrap();
+	}: _(
+		RawOrigin::Signed(caller.clone()),
+		vec![1; n as usize]
+	) verify {
+		assert_last_event::<T>(
+			Event::FriendWeightsSet {
+				account: caller
+			}.into()
+		);
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -40,6 +40,10 @@
 //! * `delay_period` - The minimum number of blocks after the beginning of the recovery process that
 //!   need to pass before the account can be successfully recovered.
 //!
+//! By default every friend's vouch counts once towards the threshold. With `set_friend_weights`,
+//! the account owner can instead give each friend a vouch weight, and the threshold is then met
+//! once the weights of the friends who vouched add up to it.
+//!
 //! There is a configurable deposit that all users need to pay to create a recovery
 //! configuration. This deposit is composed of a base deposit plus a multiplier for
 //! the number of friends chosen. This deposit is returned in full when the account
@@ -141,6 +145,7 @@
 //!   deposit.
 //! * `remove_recovery` - Remove the recovery configuration from the account, making it
 //!   un-recoverable.
+//! * `set_friend_weights` - Give each friend a different vouch weight.
 //!
 //! #### For Super Users
 //!
@@ -292,6 +297,8 @@ pub mod pallet {
 		AccountRecovered { lost_account: T::AccountId, rescuer_account: T::AccountId },
 		/// A recovery process has been removed for an account.
 		RecoveryRemoved { lost_account: T::AccountId },
+		/// The vouch weights of the friends of an account have been set.
+		FriendWeightsSet { account: T::AccountId },
 	}
 
 	#[pallet::error]
@@ -328,6 +335,8 @@ pub mod pallet {
 		AlreadyProxy,
 		/// Some internal state is broken.
 		BadState,
+		/// Friend weights must be non-zero, one per friend, and add up to at least the threshold.
+		InvalidWeights,
 	}
 
 	/// The set of recoverable accounts and their recovery configuration.
@@ -355,6 +364,15 @@ pub mod pallet {
 		ActiveRecovery<BlockNumberFor<T>, BalanceOf<T>, FriendsOf<T>>,
 	>;
 
+	/// The vouch weight of each friend of a recoverable account, in the same order as its
+	/// friends.
+	///
+	/// Each friend of an account without an entry has a weight of one.
+	#[pallet::storage]
+	#[pallet::getter(fn friend_weights)]
+	pub type FriendWeights<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, BoundedVec<u16, T::MaxFriends>, ValueQuery>;
+
 	/// The list of allowed proxy accounts.
 	///
 	/// Map from the user who can access it to the recovered account.
@@ -573,13 +591,17 @@ pub mod pallet {
 		///
 		/// The dispatch origin for this call must be _Signed_ and must be a "rescuer"
 		/// who has successfully completed the account recovery process: collected
-		/// `threshold` or more vouches, waited `delay_period` blocks since initiation.
+		/// vouches weighing `threshold` or more, waited `delay_period` blocks since initiation.
 		///
 		/// Parameters:
 		/// - `account`: The lost account that you want to claim has been successfully recovered by
 		///   you.
 		#[pallet::call_index(5)]
-		#[pallet::weight(T::WeightInfo::claim_recovery(T::MaxFriends::get()))]
+		#[pallet::weight(
+			T::WeightInfo::claim_recovery(T::MaxFriends::get())
+				// The read of `FriendWeights`, which is not benchmarked yet.
+				.saturating_add(T::DbWeight::get().reads(1))
+		)]
 		pub fn claim_recovery(
 			origin: OriginFor<T>,
 			account: AccountIdLookupOf<T>,
@@ -602,7 +624,12 @@ pub mod pallet {
 			ensure!(recoverable_block_number <= current_block_number, Error::<T>::DelayPeriod);
 			// Make sure the threshold is met
 			ensure!(
-				recovery_config.threshold as usize <= active_recovery.friends.len(),
+				recovery_config.threshold as u32 <=
+					Self::vouched_weight(
+						&account,
+						&recovery_config.friends,
+						&active_recovery.friends
+					),
 				Error::<T>::Threshold
 			);
 			frame_system::Pallet::<T>::inc_consumers(&who).map_err(|_| Error::<T>::BadState)?;
@@ -665,7 +692,11 @@ pub mod pallet {
 		/// The dispatch origin for this call must be _Signed_ and must be a
 		/// recoverable account (i.e. has a recovery configuration).
 		#[pallet::call_index(7)]
-		#[pallet::weight(T::WeightInfo::remove_recovery(T::MaxFriends::get()))]
+		#[pallet::weight(
+			T::WeightInfo::remove_recovery(T::MaxFriends::get())
+				// The removal of `FriendWeights`, which is not benchmarked yet.
+				.saturating_add(T::DbWeight::get().writes(1))
+		)]
 		pub fn remove_recovery(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			// Check there are no active recoveries
@@ -673,6 +704,7 @@ pub mod pallet {
 			ensure!(active_recoveries.next().is_none(), Error::<T>::StillActive);
 			// Take the recovery configuration for this account.
 			let recovery_config = <Recoverable<T>>::take(&who).ok_or(Error::<T>::NotRecoverable)?;
+			<FriendWeights<T>>::remove(&who);
 
 			// Unreserve the initial deposit for the recovery configuration.
 			T::Currency::unreserve(&who, recovery_config.deposit);
@@ -702,6 +734,46 @@ pub mod pallet {
 			frame_system::Pallet::<T>::dec_consumers(&who);
 			Ok(())
 		}
+
+		/// Set the vouch weight of each of your friends.
+		///
+		/// The dispatch origin for this call must be _Signed_ and must be a
+		/// recoverable account with no active recovery process for it.
+		///
+		/// Parameters:
+		/// - `weights`: The vouch weight of each friend, in the same order as the friends of the
+		///   recovery configuration. Every weight must be at least one, and together they must add
+		///   up to at least the threshold. If empty, each friend has a weight of one again.
+		#[pallet::call_index(9)]
+		#[pallet::weight(
+			// `create_recovery` validates and stores a list of the same size. The extra read checks
+			// for active recoveries.
+			T::WeightInfo::create_recovery(T::MaxFriends::get())
+				.saturating_add(T::DbWeight::get().reads(1))
+		)]
+		pub fn set_friend_weights(origin: OriginFor<T>, weights: Vec<u16>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let recovery_config = Self::recovery_config(&who).ok_or(Error::<T>::NotRecoverable)?;
+			// Check there are no active recoveries
+			let mut active_recoveries = <ActiveRecoveries<T>>::iter_prefix_values(&who);
+			ensure!(active_recoveries.next().is_none(), Error::<T>::StillActive);
+			if weights.is_empty() {
+				<FriendWeights<T>>::remove(&who);
+			} else {
+				let total_weight = weights.iter().map(|w| *w as u32).sum::<u32>();
+				ensure!(
+					weights.len() == recovery_config.friends.len() &&
+						!weights.contains(&0) &&
+						total_weight >= recovery_config.threshold as u32,
+					Error::<T>::InvalidWeights
+				);
+				let bounded_weights: BoundedVec<u16, T::MaxFriends> =
+					weights.try_into().map_err(|_| Error::<T>::MaxFriends)?;
+				<FriendWeights<T>>::insert(&who, bounded_weights);
+			}
+			Self::deposit_event(Event::<T>::FriendWeightsSet { account: who });
+			Ok(())
+		}
 	}
 }
 
@@ -715,4 +787,18 @@ impl<T: Config> Pallet<T> {
 	fn is_friend(friends: &Vec<T::AccountId>, friend: &T::AccountId) -> bool {
 		friends.binary_search(&friend).is_ok()
 	}
+
+	/// The total vouch weight of the `vouched` friends of `account`.
+	fn vouched_weight(
+		account: &T::AccountId,
+		friends: &Vec<T::AccountId>,
+		vouched: &Vec<T::AccountId>,
+	) -> u32 {
+		let weights = <FriendWeights<T>>::get(account);
+		vouched
+			.iter()
+			.filter_map(|friend| friends.binary_search(friend).ok())
+			.map(|index| weights.get(index).copied().unwrap_or(1) as u32)
+			.sum()
+	}
 }
@@ -495,3 +495,81 @@ fn remove_recovery_works() {
 		assert_ok!(Recovery::remove_recovery(RuntimeOrigin::signed(5)));
 	});
 }
+
+#[test]
+fn set_friend_weights_handles_basic_errors() {
+	new_test_ext().execute_with(|| {
+		// Cannot set weights on an unrecoverable account
+		assert_noop!(
+			Recovery::set_friend_weights(RuntimeOrigin::signed(5), vec![1, 1, 1]),
+			Error::<Test>::NotRecoverable
+		);
+		let friends = vec![2, 3, 4];
+		let threshold = 3;
+		let delay_period = 10;
+		assert_ok!(Recovery::create_recovery(
+			RuntimeOrigin::signed(5),
+			friends,
+			threshold,
+			delay_period
+		));
+		// One weight per friend is needed
+		assert_noop!(
+			Recovery::set_friend_weights(RuntimeOrigin::signed(5), vec![3, 1]),
+			Error::<Test>::InvalidWeights
+		);
+		// Zero weights are not allowed
+		assert_noop!(
+			Recovery::set_friend_weights(RuntimeOrigin::signed(5), vec![3, 0, 1]),
+			Error::<Test>::InvalidWeights
+		);
+		// The threshold must still be reachable
+		assert_noop!(
+			Recovery::set_friend_weights(RuntimeOrigin::signed(5), vec![1, 1, 0]),
+			Error::<Test>::InvalidWeights
+		);
+		// Cannot change weights while a recovery is active
+		assert_ok!(Recovery::initiate_recovery(RuntimeOrigin::signed(1), 5));
+		assert_noop!(
+			Recovery::set_friend_weights(RuntimeOrigin::signed(5), vec![3, 1, 1]),
+			Error::<Test>::StillActive
+		);
+	});
+}
+
+#[test]
+fn friend_weights_work() {
+	new_test_ext().execute_with(|| {
+		let friends = vec![2, 3, 4];
+		let threshold = 3;
+		let delay_period = 10;
+		assert_ok!(Recovery::create_recovery(
+			RuntimeOrigin::signed(5),
+			friends,
+			threshold,
+			delay_period
+		));
+		// Account 2 is trusted as much as the threshold
+		assert_ok!(Recovery::set_friend_weights(RuntimeOrigin::signed(5), vec![3, 1, 1]));
+		assert_eq!(Recovery::friend_weights(&5).into_inner(), vec![3, 1, 1]);
+		// Two light vouches are not enough
+		assert_ok!(Recovery::initiate_recovery(RuntimeOrigin::signed(1), 5));
+		assert_ok!(Recovery::vouch_recovery(RuntimeOrigin::signed(3), 5, 1));
+		assert_ok!(Recovery::vouch_recovery(RuntimeOrigin::signed(4), 5, 1));
+		run_to_block(11);
+		assert_noop!(
+			Recovery::claim_recovery(RuntimeOrigin::signed(1), 5),
+			Error::<Test>::Threshold
+		);
+		// A single heavy vouch is
+		assert_ok!(Recovery::initiate_recovery(RuntimeOrigin::signed(3), 5));
+		assert_ok!(Recovery::vouch_recovery(RuntimeOrigin::signed(2), 5, 3));
+		run_to_block(21);
+		assert_ok!(Recovery::claim_recovery(RuntimeOrigin::signed(3), 5));
+		// Removing the recovery configuration also removes the weights
+		assert_ok!(Recovery::close_recovery(RuntimeOrigin::signed(5), 1));
+		assert_ok!(Recovery::close_recovery(RuntimeOrigin::signed(5), 3));
+		assert_ok!(Recovery::remove_recovery(RuntimeOrigin::signed(5)));
+		assert!(!<FriendWeights<Test>>::contains_key(&5));
+	});
+}
@@ -23,12 +23,14 @@
 
 mod mock;
 
-use sp_runtime::traits::{One, StaticLookup, TrailingZeroInput};
+use sp_runtime::traits::{Convert, One, OpaqueKeys, StaticLookup};
 use sp_std::{prelude::*, vec};
 
-use codec::Decode;
 use frame_benchmarking::v1::benchmarks;
-use frame_support::traits::{Get, KeyOwnerProofSystem, OnInitialize};
+use frame_support::{
+	traits::{Get, KeyOwnerProofSystem, OnInitialize},
+	BoundedVec,
+};
 use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
 use pallet_session::{historical::Pallet as Historical, Pallet as Session, *};
 use pallet_staking::{
@@ -42,6 +44,8 @@ pub struct Pallet<T: Config>(pallet_session::Pallet<T>);
 pub trait Config:
 	pallet_session::Config + pallet_session::historical::Config + pallet_staking::Config
 {
+	/// Generate a new set of session keys, along with a proof of their ownership for `owner`.
+	fn generate_session_keys_and_proof(owner: Self::AccountId) -> (Self::Keys, Vec<u8>);
 }
 
 impl<T: Config> OnInitialize<BlockNumberFor<T>> for Pallet<T> {
@@ -62,8 +66,7 @@ benchmarks! {
 		)?;
 		let v_controller = pallet_staking::Pallet::<T>::bonded(&v_stash).ok_or("not stash")?;
 
-		let keys = T::Keys::decode(&mut TrailingZeroInput::zeroes()).unwrap();
-		let proof: Vec<u8> = vec![0,1,2,3];
+		let (keys, proof) = T::generate_session_keys_and_proof(v_controller.clone());
 		// Whitelist controller account from further DB operations.
 		let v_controller_key = frame_system::Account::<T>::hashed_key_for(&v_controller);
 		frame_benchmarking::benchmarking::add_to_whitelist(v_controller_key.into());
@@ -79,14 +82,40 @@ benchmarks! {
 			RewardDestination::Staked,
 		)?;
 		let v_controller = pallet_staking::Pallet::<T>::bonded(&v_stash).ok_or("not stash")?;
-		let keys = T::Keys::decode(&mut TrailingZeroInput::zeroes()).unwrap();
-		let proof: Vec<u8> = vec![0,1,2,3];
+		let (keys, proof) = T::generate_session_keys_and_proof(v_controller.clone());
 		Session::<T>::set_keys(RawOrigin::Signed(v_controller.clone()).into(), keys, proof)?;
 		// Whitelist controller account from further DB operations.
 		let v_controller_key = frame_system::Account::<T>::hashed_key_for(&v_controller);
 		frame_benchmarking::benchmarking::add_to_whitelist(v_controller_key.into());
 	}: _(RawOrigin::Signed(v_controller))
 
+	purge_inactive_keys {
+		let n in 0 .. <T as pallet_session::Config>::MaxKeysPurgedPerSession::get();
+
+		let expiry = <T as pallet_session::Config>::InactiveKeysExpiry::get();
+		let mut queue = Vec::new();
+		for who in create_validators::<T>(n, 1000)? {
+			let stash = T::Lookup::lookup(who).unwrap();
+			let controller = pallet_staking::Pallet::<T>::bonded(&stash).ok_or("not stash")?;
+			let (keys, proof) = T::generate_session_keys_and_proof(controller.clone());
+			Session::<T>::set_keys(RawOrigin::Signed(controller.clone()).into(), keys, proof)?;
+			// Validators that still intend to validate keep their keys.
+			pallet_staking::Pallet::<T>::chill(RawOrigin::Signed(controller).into())?;
+
+			let validator = <T as pallet_session::Config>::ValidatorIdOf::convert(stash)
+				.ok_or("no validator id")?;
+			InactiveSince::<T>::insert(&validator, 0);
+			queue.push(validator);
+		}
+		let queue = BoundedVec::try_from(queue).map_err(|_| "queue too long")?;
+		KeysPurgeQueue::<T>::insert(expiry, queue);
+	}: {
+		Session::<T>::purge_inactive_keys(expiry);
+	}
+	verify {
+		assert!(KeysPurgeQueue::<T>::get(expiry).is_empty());
+	}
+
 	#[extra]
 	check_membership_proof_current_session {
 		let n in 2 .. MAX_VALIDATORS as u32;
@@ -122,34 +151,23 @@ benchmarks! {
 }
 
 /// Sets up the benchmark for checking a membership proof. It creates the given
-/// number of validators, sets random session keys and then creates a membership
+/// number of validators, sets new session keys and then creates a membership
 /// proof for the first authority and returns its key and the proof.
 fn check_membership_proof_setup<T: Config>(
 	n: u32,
-) -> ((sp_runtime::KeyTypeId, &'static [u8; 32]), sp_session::MembershipProof) {
+) -> ((sp_runtime::KeyTypeId, Vec<u8>), sp_session::MembershipProof) {
 	pallet_staking::ValidatorCount::<T>::put(n);
 
-	// create validators and set random session keys
-	for (n, who) in create_validators::<T>(n, 1000).unwrap().into_iter().enumerate() {
-		use rand::{RngCore, SeedableRng};
+	let key_type = sp_runtime::KeyTypeId(*b"babe");
+	let mut first_key = None;
 
+	// create validators and set new session keys
+	for who in create_validators::<T>(n, 1000).unwrap() {
 		let validator = T::Lookup::lookup(who).unwrap();
 		let controller = pallet_staking::Pallet::<T>::bonded(&validator).unwrap();
 
-		let keys = {
-			let mut keys = [0u8; 128];
-
-			// we keep the keys for the first validator as 0x00000...
-			if n > 0 {
-				let mut rng = rand::rngs::StdRng::seed_from_u64(n as u64);
-				rng.fill_bytes(&mut keys);
-			}
-
-			keys
-		};
-
-		let keys: T::Keys = Decode::decode(&mut &keys[..]).unwrap();
-		let proof: Vec<u8> = vec![];
+		let (keys, proof) = T::generate_session_keys_and_proof(controller.clone());
+		first_key.get_or_insert_with(|| keys.get_raw(key_type).to_vec());
 
 		Session::<T>::set_keys(RawOrigin::Signed(controller).into(), keys, proof).unwrap();
 	}
@@ -161,7 +179,7 @@ fn check_membership_proof_setup<T: Config>(
 		Session::<T>::rotate_session();
 	}
 
-	let key = (sp_runtime::KeyTypeId(*b"babe"), &[0u8; 32]);
+	let key = (key_type, first_key.expect("at least one validator is created; qed"));
 
 	(key, Historical::<T>::prove(key).unwrap())
 }
@@ -115,12 +115,13 @@ mod mock;
 mod tests;
 pub mod weights;
 
-use codec::{Decode, MaxEncodedLen};
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	dispatch::DispatchResult,
 	ensure,
 	traits::{
-		EstimateNextNewSession, EstimateNextSessionRotation, FindAuthor, Get, OneSessionHandler,
+		Contains, EstimateNextNewSession, EstimateNextSessionRotation, FindAuthor, Get,
+		HeldReferences, OneSessionHandler, PalletInfoAccess, ReferenceHolder,
 		ValidatorRegistration, ValidatorSet,
 	},
 	weights::Weight,
@@ -133,6 +134,7 @@ use sp_runtime::{
 };
 use sp_staking::SessionIndex;
 use sp_std::{
+	collections::btree_set::BTreeSet,
 	marker::PhantomData,
 	ops::{Rem, Sub},
 	prelude::*,
@@ -141,6 +143,14 @@ use sp_std::{
 pub use pallet::*;
 pub use weights::WeightInfo;
 
+/// The context every session key signs to prove it approves of being registered by an account.
+const OWNERSHIP_PROOF_CONTEXT: &[u8] = b"session_keys_ownership";
+
+/// The message the session keys registered by `owner`, a SCALE encoded account, have to sign.
+fn ownership_proof_payload(owner: &[u8]) -> Vec<u8> {
+	(OWNERSHIP_PROOF_CONTEXT, owner).encode()
+}
+
 /// Decides whether the session should be ended.
 pub trait ShouldEndSession<BlockNumber> {
 	/// Return `true` if the session should be ended.
@@ -300,6 +310,19 @@ pub trait SessionHandler<ValidatorId> {
 
 	/// A validator got disabled. Act accordingly until a new session begins.
 	fn on_disabled(validator_index: u32);
+
+	/// Sign the session `keys` with the matching private keys from the keystore, proving that
+	/// their owner approves of them being registered by `owner`, a SCALE encoded account.
+	///
+	/// Returns `None` if a private key can not be found.
+	fn generate_ownership_proof<Ks: OpaqueKeys>(_owner: &[u8], _keys: &Ks) -> Option<Vec<u8>> {
+		Some(Vec::new())
+	}
+
+	/// Check a proof created by [`generate_ownership_proof`](Self::generate_ownership_proof).
+	fn check_ownership_proof<Ks: OpaqueKeys>(_owner: &[u8], _keys: &Ks, _proof: &[u8]) -> bool {
+		true
+	}
 }
 
 #[impl_trait_for_tuples::impl_for_tuples(1, 30)]
@@ -350,6 +373,35 @@ impl<AId> SessionHandler<AId> for Tuple {
 	fn on_disabled(i: u32) {
 		for_tuples!( #( Tuple::on_disabled(i); )* )
 	}
+
+	fn generate_ownership_proof<Ks: OpaqueKeys>(owner: &[u8], keys: &Ks) -> Option<Vec<u8>> {
+		let payload = ownership_proof_payload(owner);
+		let mut proof = Vec::new();
+		for_tuples!(
+			#(
+				let key = keys.get::<Tuple::Key>(<Tuple::Key as RuntimeAppPublic>::ID)?;
+				key.sign(&payload)?.encode_to(&mut proof);
+			)*
+		);
+		Some(proof)
+	}
+
+	fn check_ownership_proof<Ks: OpaqueKeys>(owner: &[u8], keys: &Ks, proof: &[u8]) -> bool {
+		let payload = ownership_proof_payload(owner);
+		let mut input = proof;
+		for_tuples!(
+			#(
+				let key = keys.get::<Tuple::Key>(<Tuple::Key as RuntimeAppPublic>::ID);
+				let signature =
+					<<Tuple::Key as RuntimeAppPublic>::Signature as Decode>::decode(&mut input).ok();
+				match (key, signature) {
+					(Some(key), Some(signature)) if key.verify(&payload, &signature) => {},
+					_ => return false,
+				}
+			)*
+		);
+		input.is_empty()
+	}
 }
 
 /// `SessionHandler` for tests that use `UintAuthorityId` as `Keys`.
@@ -410,6 +462,24 @@ pub mod pallet {
 		/// The keys.
 		type Keys: OpaqueKeys + Member + Parameter + MaybeSerializeDeserialize;
 
+		/// The number of sessions a validator can be out of the validator set before its session
+		/// keys are purged. Zero keeps the keys forever.
+		#[pallet::constant]
+		type InactiveKeysExpiry: Get<SessionIndex>;
+
+		/// Validators that still intend to validate, e.g. validators that are not chilled.
+		///
+		/// Their session keys are kept while they are out of the validator set, so that waiting
+		/// validators don't have to register their keys again once elected.
+		type ValidatorIntent: Contains<Self::ValidatorId>;
+
+		/// The maximum number of validators whose session keys are purged in a single session.
+		///
+		/// Validators leaving the set when the queue of a session is full keep their keys until
+		/// they purge them with `purge_keys`.
+		#[pallet::constant]
+		type MaxKeysPurgedPerSession: Get<u32>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -443,6 +513,7 @@ pub mod pallet {
 			for (account, val, keys) in self.keys.iter().cloned() {
 				<Pallet<T>>::inner_set_keys(&val, keys)
 					.expect("genesis config must not contain duplicates; qed");
+				KeysRegistrant::<T>::insert(&val, &account);
 				if frame_system::Pallet::<T>::inc_consumers_without_limit(&account).is_err() {
 					// This will leak a provider reference, however it only happens once (at
 					// genesis) so it's really not a big deal and we assume that the user wants to
@@ -519,6 +590,29 @@ pub mod pallet {
 	pub type KeyOwner<T: Config> =
 		StorageMap<_, Twox64Concat, (KeyTypeId, Vec<u8>), T::ValidatorId, OptionQuery>;
 
+	/// The account that registered the session keys of a validator.
+	///
+	/// Its consumer reference is released when the keys are purged.
+	#[pallet::storage]
+	pub type KeysRegistrant<T: Config> =
+		StorageMap<_, Twox64Concat, T::ValidatorId, T::AccountId, OptionQuery>;
+
+	/// The first session a validator with session keys is no longer part of the validator set.
+	#[pallet::storage]
+	pub type InactiveSince<T: Config> =
+		StorageMap<_, Twox64Concat, T::ValidatorId, SessionIndex, OptionQuery>;
+
+	/// The validators whose session keys are purged at the start of a session, unless they
+	/// rejoined the validator set or still intend to validate.
+	#[pallet::storage]
+	pub type KeysPurgeQueue<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		SessionIndex,
+		BoundedVec<T::ValidatorId, T::MaxKeysPurgedPerSession>,
+		ValueQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event {
@@ -548,6 +642,8 @@ pub mod pallet {
 		/// block of the current session.
 		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
 			if T::ShouldEndSession::should_end_session(n) {
+				// The whole block is used by a session rotation, this includes purging the keys of
+				// at most `MaxKeysPurgedPerSession` validators, see `integrity_test`.
 				Self::rotate_session();
 				T::BlockWeights::get().max_block
 			} else {
@@ -557,6 +653,15 @@ pub mod pallet {
 				Weight::zero()
 			}
 		}
+
+		fn integrity_test() {
+			let max_block = T::BlockWeights::get().max_block;
+			let max_purge = Self::purge_inactive_keys_weight(T::MaxKeysPurgedPerSession::get());
+			assert!(
+				max_purge.all_lte(max_block / 2),
+				"Purging `MaxKeysPurgedPerSession` session keys must take at most half a block"
+			);
+		}
 	}
 
 	#[pallet::call]
@@ -567,14 +672,22 @@ pub mod pallet {
 		///
 		/// The dispatch origin of this function must be signed.
 		///
+		/// `proof` must contain a signature of every key in `keys`, proving that their owner
+		/// approves of them being registered by the caller. It can be created with
+		/// [`SessionHandler::generate_ownership_proof`].
+		///
 		/// ## Complexity
 		/// - `O(1)`. Actual cost depends on the number of length of `T::Keys::key_ids()` which is
 		///   fixed.
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::set_keys())]
+		#[pallet::weight(Pallet::<T>::set_keys_weight())]
 		pub fn set_keys(origin: OriginFor<T>, keys: T::Keys, proof: Vec<u8>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			ensure!(keys.ownership_proof_is_valid(&proof), Error::<T>::InvalidProof);
+			ensure!(
+				T::SessionHandler::check_ownership_proof(&who.encode(), &keys, &proof),
+				Error::<T>::InvalidProof
+			);
 
 			Self::do_set_keys(&who, keys)?;
 			Ok(())
@@ -593,7 +706,8 @@ pub mod pallet {
 		/// - `O(1)` in number of key types. Actual cost depends on the number of length of
 		///   `T::Keys::key_ids()` which is fixed.
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::purge_keys())]
+		// `KeysRegistrant` and `InactiveSince` are removed too.
+		#[pallet::weight(T::WeightInfo::purge_keys().saturating_add(T::DbWeight::get().writes(2)))]
 		pub fn purge_keys(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			Self::do_purge_keys(&who)?;
@@ -645,6 +759,11 @@ impl<T: Config> Pallet<T> {
 				(Validators::<T>::get(), false)
 			};
 
+		if next_identities_changed {
+			Self::note_inactive_validators(session_index + 1, &validators, &next_validators);
+		}
+		Self::purge_inactive_keys(session_index);
+
 		// Queue next session keys.
 		let (queued_amalgamated, next_changed) = {
 			// until we are certain there has been a change, iterate the prior
@@ -687,6 +806,114 @@ impl<T: Config> Pallet<T> {
 		T::SessionHandler::on_new_session::<T::Keys>(changed, &session_keys, &queued_amalgamated);
 	}
 
+	/// Generate a proof that the owner of the session `keys` approves of them being registered by
+	/// `owner`, using the private keys in the keystore.
+	pub fn generate_ownership_proof(owner: &T::AccountId, keys: &T::Keys) -> Option<Vec<u8>> {
+		T::SessionHandler::generate_ownership_proof(&owner.encode(), keys)
+	}
+
+	/// Keep track of the validators leaving and joining the validator set from `session_index`,
+	/// queueing the session keys of those that left to be purged.
+	///
+	/// Bounded by the size of the validator sets, like the rest of the session rotation.
+	fn note_inactive_validators(
+		session_index: SessionIndex,
+		validators: &[T::ValidatorId],
+		next_validators: &[T::ValidatorId],
+	) {
+		let expiry = T::InactiveKeysExpiry::get();
+		if expiry.is_zero() {
+			return
+		}
+
+		let current = validators.iter().collect::<BTreeSet<_>>();
+		let next = next_validators.iter().collect::<BTreeSet<_>>();
+
+		let leaving = current
+			.difference(&next)
+			.filter(|validator| Self::load_keys(validator).is_some())
+			.collect::<Vec<_>>();
+		if !leaving.is_empty() {
+			KeysPurgeQueue::<T>::mutate(session_index.saturating_add(expiry), |queue| {
+				for validator in leaving {
+					if queue.try_push((*validator).clone()).is_ok() {
+						InactiveSince::<T>::insert(*validator, session_index);
+					} else {
+						log::debug!(
+							target: "runtime::session",
+							"keys purge queue is full, keeping the keys of {:?}",
+							validator,
+						);
+					}
+				}
+			});
+		}
+
+		for validator in next.difference(&current) {
+			InactiveSince::<T>::remove(*validator);
+		}
+	}
+
+	/// The weight of `set_keys`.
+	///
+	/// On top of `set_keys`, the ownership proof of every key is verified, which is estimated at
+	/// 50 µs per key, and `KeysRegistrant` is written.
+	fn set_keys_weight() -> Weight {
+		let proof_checks =
+			Weight::from_parts(50_000_000, 0).saturating_mul(T::Keys::key_ids().len() as u64);
+		T::WeightInfo::set_keys()
+			.saturating_add(proof_checks)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+
+	/// The weight of purging the keys of at most `n` inactive validators in
+	/// [`Self::purge_inactive_keys`].
+	///
+	/// Every validator is charged as a `purge_keys` with its `KeysRegistrant`, `InactiveSince` and
+	/// intent lookups, plus the `KeysPurgeQueue` entries taken and requeued.
+	fn purge_inactive_keys_weight(n: u32) -> Weight {
+		T::WeightInfo::purge_keys()
+			.saturating_add(T::DbWeight::get().reads_writes(3, 2))
+			.saturating_mul(n.into())
+			.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+
+	/// Purge the session keys of the validators that have been out of the validator set for
+	/// `InactiveKeysExpiry` sessions by `session_index`.
+	///
+	/// Validators that still intend to validate are checked again `InactiveKeysExpiry` sessions
+	/// later. At most `MaxKeysPurgedPerSession` validators are processed.
+	pub fn purge_inactive_keys(session_index: SessionIndex) {
+		let expiry = T::InactiveKeysExpiry::get();
+		let mut waiting = Vec::new();
+		for validator in KeysPurgeQueue::<T>::take(session_index) {
+			let expired = InactiveSince::<T>::get(&validator)
+				.map_or(false, |since| since.saturating_add(expiry) <= session_index);
+			if !expired {
+				continue
+			}
+			if T::ValidatorIntent::contains(&validator) {
+				waiting.push(validator);
+				continue
+			}
+			// Keys registered without a known account can't release their consumer reference,
+			// so they have to be purged with `purge_keys`.
+			if let Some(account) = KeysRegistrant::<T>::get(&validator) {
+				Self::purge_validator_keys(&account, &validator);
+			}
+		}
+
+		if !waiting.is_empty() {
+			KeysPurgeQueue::<T>::mutate(session_index.saturating_add(expiry), |queue| {
+				for validator in waiting {
+					if queue.try_push(validator).is_err() {
+						break
+					}
+				}
+			});
+		}
+	}
+
 	/// Disable the validator of index `i`, returns `false` if the validator was already disabled.
 	pub fn disable_index(i: u32) -> bool {
 		if i >= Validators::<T>::decode_len().unwrap_or(0) as u32 {
@@ -780,6 +1007,7 @@ impl<T: Config> Pallet<T> {
 		if old_keys.is_none() {
 			let assertion = frame_system::Pallet::<T>::inc_consumers(account).is_ok();
 			debug_assert!(assertion, "can_inc_consumer() returned true; no change since; qed");
+			KeysRegistrant::<T>::insert(&who, account);
 		}
 
 		Ok(())
@@ -833,14 +1061,26 @@ impl<T: Config> Pallet<T> {
 			.or_else(|| T::ValidatorId::try_from(account.clone()).ok())
 			.ok_or(Error::<T>::NoAssociatedValidatorId)?;
 
-		let old_keys = Self::take_keys(&who).ok_or(Error::<T>::NoKeys)?;
+		ensure!(Self::purge_validator_keys(account, &who), Error::<T>::NoKeys);
+		Ok(())
+	}
+
+	/// Remove the session keys of `who` and their key owners, releasing the consumer reference of
+	/// `account`. Returns `false` if `who` has no keys.
+	fn purge_validator_keys(account: &T::AccountId, who: &T::ValidatorId) -> bool {
+		let old_keys = match Self::take_keys(who) {
+			Some(keys) => keys,
+			None => return false,
+		};
 		for id in T::Keys::key_ids() {
 			let key_data = old_keys.get_raw(*id);
 			Self::clear_key_owner(*id, key_data);
 		}
+		KeysRegistrant::<T>::remove(who);
+		InactiveSince::<T>::remove(who);
 		frame_system::Pallet::<T>::dec_consumers(account);
 
-		Ok(())
+		true
 	}
 
 	fn load_keys(v: &T::ValidatorId) -> Option<T::Keys> {
@@ -910,6 +1150,22 @@ impl<T: Config> frame_support::traits::DisabledValidators for Pallet<T> {
 	}
 }
 
+impl<T: Config> ReferenceHolder<T::AccountId> for Pallet<T> {
+	fn held_references(who: &T::AccountId) -> Vec<HeldReferences> {
+		// Registering session keys holds a consumer reference on the account until they are purged.
+		let has_keys = T::ValidatorIdOf::convert(who.clone())
+			.map_or(false, |v| <NextKeys<T>>::contains_key(v));
+		if !has_keys {
+			return Vec::new()
+		}
+		sp_std::vec![HeldReferences {
+			holder: <Self as PalletInfoAccess>::name().as_bytes().to_vec(),
+			consumers: 1,
+			..Default::default()
+		}]
+	}
+}
+
 /// Wraps the author-scraping logic for consensus engines that can recover
 /// the canonical index of an author. This then transforms it into the
 /// registering account-ID of that session key index.
@@ -29,7 +29,10 @@ use sp_runtime::{impl_opaque_keys, testing::UintAuthorityId, BuildStorage};
 use sp_staking::SessionIndex;
 use sp_state_machine::BasicExternalities;
 
-use frame_support::{derive_impl, parameter_types, traits::ConstU64};
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64},
+};
 
 impl_opaque_keys! {
 	pub struct MockSessionKeys {
@@ -102,6 +105,15 @@ parameter_types! {
 	// Stores if `on_before_session_end` was called
 	pub static BeforeSessionEndCalled: bool = false;
 	pub static ValidatorAccounts: BTreeMap<u64, u64> = BTreeMap::new();
+	pub static InactiveKeysExpiry: SessionIndex = 0;
+	pub static WaitingValidators: Vec<u64> = vec![];
+}
+
+pub struct TestValidatorIntent;
+impl Contains<u64> for TestValidatorIntent {
+	fn contains(validator: &u64) -> bool {
+		WaitingValidators::get().contains(validator)
+	}
 }
 
 pub struct TestShouldEndSession;
@@ -142,6 +154,29 @@ impl SessionHandler<u64> for TestSessionHandler {
 	}
 }
 
+/// A session handler for a single key type, to test the ownership proofs of handler tuples.
+pub struct TestOneSessionHandler;
+impl sp_runtime::BoundToRuntimeAppPublic for TestOneSessionHandler {
+	type Public = UintAuthorityId;
+}
+impl OneSessionHandler<u64> for TestOneSessionHandler {
+	type Key = UintAuthorityId;
+
+	fn on_genesis_session<'a, I: 'a>(_: I)
+	where
+		I: Iterator<Item = (&'a u64, Self::Key)>,
+	{
+	}
+
+	fn on_new_session<'a, I: 'a>(_: bool, _: I, _: I)
+	where
+		I: Iterator<Item = (&'a u64, Self::Key)>,
+	{
+	}
+
+	fn on_disabled(_validator_index: u32) {}
+}
+
 pub struct TestSessionManager;
 impl SessionManager<u64> for TestSessionManager {
 	fn end_session(_: SessionIndex) {}
@@ -233,6 +268,9 @@ impl pallet_timestamp::Config for Test {
 	type Moment = u64;
 	type OnTimestampSet = ();
 	type MinimumPeriod = ConstU64<5>;
+	type ExpectedTimestamp = ();
+	type MaxTimestampDrift = ();
+	type OnTimestampDrift = ();
 	type WeightInfo = ();
 }
 
@@ -258,6 +296,9 @@ impl Config for Test {
 	type ValidatorId = u64;
 	type ValidatorIdOf = TestValidatorIdOf;
 	type Keys = MockSessionKeys;
+	type InactiveKeysExpiry = InactiveKeysExpiry;
+	type ValidatorIntent = TestValidatorIntent;
+	type MaxKeysPurgedPerSession = ConstU32<2>;
 	type RuntimeEvent = RuntimeEvent;
 	type NextSessionRotation = ();
 	type WeightInfo = ();
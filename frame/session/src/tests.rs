@@ -21,11 +21,11 @@ use super::*;
 use crate::mock::{
 	authorities, before_session_end_called, force_new_session, new_test_ext,
 	reset_before_session_end_called, session_changed, set_next_validators, set_session_length,
-	PreUpgradeMockSessionKeys, RuntimeOrigin, Session, SessionChanged, System, Test,
-	TestSessionChanged, TestValidatorIdOf,
+	InactiveKeysExpiry, MockSessionKeys, PreUpgradeMockSessionKeys, RuntimeOrigin, Session,
+	SessionChanged, System, Test, TestOneSessionHandler, TestSessionChanged, TestValidatorIdOf,
 };
 
-use codec::Decode;
+use codec::{Decode, Encode};
 use sp_core::crypto::key_types::DUMMY;
 use sp_runtime::testing::UintAuthorityId;
 
@@ -482,3 +482,144 @@ fn test_migration_v1() {
 		crate::migrations::v1::post_migrate::<Test, Historical>();
 	});
 }
+
+#[test]
+fn ownership_proof_covers_owner_and_keys() {
+	type Handler = (TestOneSessionHandler,);
+	let check = |owner: &[u8], keys: &MockSessionKeys, proof: &[u8]| {
+		<Handler as SessionHandler<u64>>::check_ownership_proof(owner, keys, proof)
+	};
+	let keys: MockSessionKeys = UintAuthorityId(10).into();
+	let owner = 1u64.encode();
+
+	let proof = <Handler as SessionHandler<u64>>::generate_ownership_proof(&owner, &keys).unwrap();
+	assert!(check(&owner, &keys, &proof));
+
+	// The proof is only valid for the account it was created for.
+	assert!(!check(&2u64.encode(), &keys, &proof));
+	// And only for the keys that signed it.
+	assert!(!check(&owner, &UintAuthorityId(11).into(), &proof));
+	// Missing and trailing signatures are rejected.
+	assert!(!check(&owner, &keys, &[]));
+	let mut long_proof = proof.clone();
+	long_proof.push(0);
+	assert!(!check(&owner, &keys, &long_proof));
+}
+
+#[test]
+fn inactive_keys_are_purged_after_expiry() {
+	new_test_ext().execute_with(|| {
+		InactiveKeysExpiry::set(2);
+		assert_eq!(System::consumers(&3), 1);
+
+		// Validator 3 leaves the set from session 2 on.
+		set_next_validators(vec![1, 2]);
+		initialize_block(2);
+		assert_eq!(InactiveSince::<Test>::get(3), Some(2));
+		assert_eq!(KeysPurgeQueue::<Test>::get(4).into_inner(), vec![3]);
+
+		initialize_block(4);
+		initialize_block(6);
+		assert_eq!(Session::load_keys(&3), Some(UintAuthorityId(3).into()));
+
+		// Two sessions later its keys are gone.
+		initialize_block(8);
+		assert_eq!(Session::load_keys(&3), None);
+		assert_eq!(Session::key_owner(DUMMY, &UintAuthorityId(3).encode()), None);
+		assert_eq!(KeysRegistrant::<Test>::get(3), None);
+		assert_eq!(InactiveSince::<Test>::get(3), None);
+		assert_eq!(System::consumers(&3), 0);
+
+		// The remaining validators keep their keys.
+		assert_eq!(Session::load_keys(&1), Some(UintAuthorityId(1).into()));
+		assert_eq!(Session::load_keys(&2), Some(UintAuthorityId(2).into()));
+	});
+}
+
+#[test]
+fn rejoining_validator_keeps_keys() {
+	new_test_ext().execute_with(|| {
+		InactiveKeysExpiry::set(2);
+
+		set_next_validators(vec![1, 2]);
+		initialize_block(2);
+		assert_eq!(InactiveSince::<Test>::get(3), Some(2));
+
+		// Validator 3 is back before its keys expire.
+		set_next_validators(vec![1, 2, 3]);
+		initialize_block(4);
+		assert_eq!(InactiveSince::<Test>::get(3), None);
+
+		initialize_block(6);
+		initialize_block(8);
+		assert_eq!(Session::load_keys(&3), Some(UintAuthorityId(3).into()));
+		assert_eq!(KeysRegistrant::<Test>::get(3), Some(3));
+		assert_eq!(System::consumers(&3), 1);
+	});
+}
+
+#[test]
+fn waiting_validator_keeps_keys_until_it_stops_validating() {
+	new_test_ext().execute_with(|| {
+		InactiveKeysExpiry::set(2);
+		WaitingValidators::set(vec![3]);
+
+		// Validator 3 is not elected but still intends to validate.
+		set_next_validators(vec![1, 2]);
+		initialize_block(2);
+		initialize_block(4);
+		initialize_block(6);
+		initialize_block(8);
+		assert_eq!(Session::load_keys(&3), Some(UintAuthorityId(3).into()));
+		assert_eq!(KeysPurgeQueue::<Test>::get(6).into_inner(), vec![3]);
+
+		// Once it stops validating its keys are purged.
+		WaitingValidators::set(vec![]);
+		initialize_block(10);
+		initialize_block(12);
+		assert_eq!(Session::load_keys(&3), None);
+		assert_eq!(System::consumers(&3), 0);
+	});
+}
+
+#[test]
+fn keys_purge_queue_is_bounded() {
+	new_test_ext().execute_with(|| {
+		InactiveKeysExpiry::set(2);
+
+		// Only two of the three validators leaving fit in the queue.
+		set_next_validators(vec![]);
+		initialize_block(2);
+		assert_eq!(KeysPurgeQueue::<Test>::get(4).into_inner(), vec![1, 2]);
+		assert_eq!(InactiveSince::<Test>::get(3), None);
+
+		initialize_block(4);
+		initialize_block(6);
+		initialize_block(8);
+		assert_eq!(Session::load_keys(&1), None);
+		assert_eq!(Session::load_keys(&2), None);
+		assert_eq!(Session::load_keys(&3), Some(UintAuthorityId(3).into()));
+	});
+}
+
+#[test]
+fn purge_keys_clears_expiry_tracking() {
+	new_test_ext().execute_with(|| {
+		InactiveKeysExpiry::set(2);
+
+		set_next_validators(vec![1, 2]);
+		initialize_block(2);
+		assert_ok!(Session::purge_keys(RuntimeOrigin::signed(3)));
+		assert_eq!(KeysRegistrant::<Test>::get(3), None);
+		assert_eq!(InactiveSince::<Test>::get(3), None);
+		assert_eq!(System::consumers(&3), 0);
+
+		// New keys are not purged by the outdated queue entry.
+		assert_ok!(Session::set_keys(RuntimeOrigin::signed(3), UintAuthorityId(30).into(), vec![]));
+		initialize_block(4);
+		initialize_block(6);
+		initialize_block(8);
+		assert_eq!(Session::load_keys(&3), Some(UintAuthorityId(30).into()));
+		assert_eq!(KeysRegistrant::<Test>::get(3), Some(3));
+	});
+}
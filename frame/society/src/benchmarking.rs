@@ -368,6 +368,44 @@ benchmarks_instance_pallet! {
 		assert_eq!(DefenderVotes::<T, I>::get(challenge_round, &defender), None);
 	}
 
+	rotate_intake {
+		let b in 1 .. T::MaxBids::get();
+		let founder = setup_funded_society::<T, I>()?;
+		let mut bids = Bids::<T, I>::get();
+		for i in 0 .. b {
+			let bidder: T::AccountId = account("bidder", i, 0);
+			Society::<T, I>::insert_bid(&mut bids, &bidder, i.into(), make_bid::<T, I>(&bidder));
+		}
+		Bids::<T, I>::put(bids);
+		let round = RoundCount::<T, I>::get();
+		let mut rng = ChaChaRng::from_seed([0u8; 32]);
+	}: {
+		Society::<T, I>::rotate_intake(&mut rng);
+	}
+	verify {
+		assert_eq!(RoundCount::<T, I>::get(), round + 1);
+		assert!(Skeptic::<T, I>::get().is_some());
+	}
+
+	rotate_challenge {
+		let founder = setup_society::<T, I>()?;
+		let defender: T::AccountId = account("defender", 0, 0);
+		let skeptic: T::AccountId = account("skeptic", 0, 0);
+		let member: T::AccountId = account("member", 0, 0);
+		let _ = Society::<T, I>::insert_member(&defender, 0u32.into());
+		let _ = Society::<T, I>::insert_member(&skeptic, 0u32.into());
+		let _ = Society::<T, I>::insert_member(&member, 0u32.into());
+		// Neither the defender nor the skeptic voted, so both get punished.
+		Defending::<T, I>::put((defender.clone(), skeptic.clone(), Tally::default()));
+		let mut rng = ChaChaRng::from_seed([0u8; 32]);
+	}: {
+		Society::<T, I>::rotate_challenge(&mut rng);
+	}
+	verify {
+		assert!(SuspendedMembers::<T, I>::contains_key(&defender));
+		assert_eq!(Defending::<T, I>::get().map(|(d, _, _)| d), Some(skeptic));
+	}
+
 	impl_benchmark_test_suite!(
 		Society,
 		sp_io::TestExternalities::from(
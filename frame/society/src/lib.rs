@@ -771,6 +771,7 @@ pub mod pallet {
 			match Self::period() {
 				Period::Voting { elapsed, .. } if elapsed.is_zero() => {
 					Self::rotate_intake(&mut rng);
+					// Charged as a flat bound until the `rotate_intake` benchmark is run.
 					weight.saturating_accrue(weights.max_block / 20);
 				},
 				_ => {},
@@ -779,6 +780,7 @@ pub mod pallet {
 			// Run a challenge rotation
 			if (n % T::ChallengePeriod::get()).is_zero() {
 				Self::rotate_challenge(&mut rng);
+				// Charged as a flat bound until the `rotate_challenge` benchmark is run.
 				weight.saturating_accrue(weights.max_block / 20);
 			}
 
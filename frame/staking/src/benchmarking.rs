@@ -953,6 +953,13 @@ benchmarks! {
 		assert_eq!(MinCommission::<T>::get(), Perbill::from_percent(100));
 	}
 
+	set_max_commission_increase {
+		let max_increase = Perbill::max_value();
+	}: _(RawOrigin::Root, Some(max_increase))
+	verify {
+		assert_eq!(MaxCommissionIncrease::<T>::get(), Some(Perbill::from_percent(100)));
+	}
+
 	impl_benchmark_test_suite!(
 		Staking,
 		crate::mock::ExtBuilder::default().has_stakers(true),
@@ -148,6 +148,9 @@ sp_runtime::impl_opaque_keys! {
 impl pallet_session::Config for Test {
 	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Test, Staking>;
 	type Keys = SessionKeys;
+	type InactiveKeysExpiry = ();
+	type ValidatorIntent = ();
+	type MaxKeysPurgedPerSession = frame_support::traits::ConstU32<100>;
 	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
 	type SessionHandler = (OtherSessionHandler,);
 	type RuntimeEvent = RuntimeEvent;
@@ -170,6 +173,9 @@ impl pallet_timestamp::Config for Test {
 	type Moment = u64;
 	type OnTimestampSet = ();
 	type MinimumPeriod = ConstU64<5>;
+	type ExpectedTimestamp = ();
+	type MaxTimestampDrift = ();
+	type OnTimestampDrift = ();
 	type WeightInfo = ();
 }
 
@@ -586,11 +592,9 @@ pub(crate) fn bond(who: AccountId, val: Balance) {
 pub(crate) fn bond_validator(who: AccountId, val: Balance) {
 	bond(who, val);
 	assert_ok!(Staking::validate(RuntimeOrigin::signed(who), ValidatorPrefs::default()));
-	assert_ok!(Session::set_keys(
-		RuntimeOrigin::signed(who),
-		SessionKeys { other: who.into() },
-		vec![]
-	));
+	let keys = SessionKeys { other: who.into() };
+	let proof = Session::generate_ownership_proof(&who, &keys).unwrap();
+	assert_ok!(Session::set_keys(RuntimeOrigin::signed(who), keys, proof));
 }
 
 pub(crate) fn bond_nominator(who: AccountId, val: Balance, target: Vec<AccountId>) {
@@ -135,7 +135,7 @@ impl<T: Config> Pallet<T> {
 				// left. We can now safely remove all staking-related information.
 				Self::kill_stash(&ledger.stash, num_slashing_spans)?;
 
-				T::WeightInfo::withdraw_unbonded_kill(num_slashing_spans)
+				Self::kill_stash_weight(num_slashing_spans)
 			} else {
 				// This was the consequence of a partial unbond. just update the ledger and move on.
 				ledger.update()?;
@@ -726,12 +726,56 @@ impl<T: Config> Pallet<T> {
 
 		Self::do_remove_validator(&stash);
 		Self::do_remove_nominator(&stash);
+		CommissionBase::<T>::remove(&stash);
 
 		frame_system::Pallet::<T>::dec_consumers(&stash);
 
 		Ok(())
 	}
 
+	/// The weight of withdrawing all unbonded funds of a stash, and so killing it.
+	///
+	/// On top of the benchmarked `withdraw_unbonded_kill`, this removes the commission base of
+	/// the stash, which is not benchmarked yet.
+	pub(crate) fn kill_stash_weight(num_slashing_spans: u32) -> Weight {
+		T::WeightInfo::withdraw_unbonded_kill(num_slashing_spans)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+
+	/// Ensure that `stash` setting its commission to `commission` does not increase it by more
+	/// than `MaxCommissionIncrease` over its commission at the start of the current era, and
+	/// record the change.
+	///
+	/// The commission of a stash that is not validating is the one it last set, so chilling does
+	/// not reset it. Only stashes that never validated are free to pick any commission.
+	pub(crate) fn ensure_commission_increase(
+		stash: &T::AccountId,
+		commission: Perbill,
+	) -> DispatchResult {
+		let era = Self::current_era().unwrap_or(0);
+		let last = CommissionBase::<T>::get(stash);
+		let base = match last {
+			Some((changed_in, base, _)) if changed_in == era => Some(base),
+			_ => Validators::<T>::contains_key(stash)
+				.then(|| Validators::<T>::get(stash).commission)
+				.or(last.map(|(_, _, last)| last)),
+		};
+
+		let base = match (MaxCommissionIncrease::<T>::get(), base) {
+			(Some(max_increase), Some(base)) => {
+				ensure!(
+					commission <= base.saturating_add(max_increase),
+					Error::<T>::CommissionIncreaseTooHigh
+				);
+				base
+			},
+			// without a limit any change is allowed, so it becomes the new base.
+			_ => commission,
+		};
+		CommissionBase::<T>::insert(stash, (era, base, commission));
+		Ok(())
+	}
+
 	/// Clear all era information for given era.
 	pub(crate) fn clear_era_information(era_index: EraIndex) {
 		// FIXME: We can possibly set a reasonable limit since we do this only once per era and
@@ -327,6 +327,23 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type MinCommission<T: Config> = StorageValue<_, Perbill, ValueQuery>;
 
+	/// The maximum amount by which a validator may increase its commission within a single era.
+	///
+	/// If `None`, no limit exists.
+	#[pallet::storage]
+	pub type MaxCommissionIncrease<T: Config> = StorageValue<_, Perbill, OptionQuery>;
+
+	/// The era in which each validator last changed its commission, the commission it had at the
+	/// start of that era and the commission it last set.
+	///
+	/// Kept when the validator chills so that it can't reset its commission by chilling and
+	/// validating again.
+	///
+	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type CommissionBase<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (EraIndex, Perbill, Perbill), OptionQuery>;
+
 	/// Map from all (unlocked) "controller" accounts to the info regarding the staking.
 	///
 	/// Note: All the reads and mutations to this storage *MUST* be done through the methods exposed
@@ -853,6 +870,8 @@ pub mod pallet {
 		BoundNotMet,
 		/// Used when attempting to use deprecated controller account logic.
 		ControllerDeprecated,
+		/// Commission increases by more than `MaxCommissionIncrease` within the current era.
+		CommissionIncreaseTooHigh,
 	}
 
 	#[pallet::hooks]
@@ -1022,7 +1041,7 @@ pub mod pallet {
 		/// See also [`Call::withdraw_unbonded`].
 		#[pallet::call_index(2)]
 		#[pallet::weight(
-            T::WeightInfo::withdraw_unbonded_kill(SPECULATIVE_NUM_SPANS).saturating_add(T::WeightInfo::unbond()))
+            Pallet::<T>::kill_stash_weight(SPECULATIVE_NUM_SPANS).saturating_add(T::WeightInfo::unbond()))
         ]
 		pub fn unbond(
 			origin: OriginFor<T>,
@@ -1135,7 +1154,7 @@ pub mod pallet {
 		/// O(S) where S is the number of slashing spans to remove
 		/// NOTE: Weight annotation is the kill scenario, we refund otherwise.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::withdraw_unbonded_kill(*num_slashing_spans))]
+		#[pallet::weight(Pallet::<T>::kill_stash_weight(*num_slashing_spans))]
 		pub fn withdraw_unbonded(
 			origin: OriginFor<T>,
 			num_slashing_spans: u32,
@@ -1152,7 +1171,11 @@ pub mod pallet {
 		///
 		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::validate())]
+		#[pallet::weight(
+			T::WeightInfo::validate()
+				// The commission limit and base of the stash, which are not benchmarked yet.
+				.saturating_add(T::DbWeight::get().reads_writes(2, 1))
+		)]
 		pub fn validate(origin: OriginFor<T>, prefs: ValidatorPrefs) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
 
@@ -1163,6 +1186,7 @@ pub mod pallet {
 
 			// ensure their commission is correct.
 			ensure!(prefs.commission >= MinCommission::<T>::get(), Error::<T>::CommissionTooLow);
+			Self::ensure_commission_increase(stash, prefs.commission)?;
 
 			// Only check limits if they are not already a validator.
 			if !Validators::<T>::contains_key(stash) {
@@ -1487,7 +1511,11 @@ pub mod pallet {
 		/// - `num_slashing_spans`: Refer to comments on [`Call::withdraw_unbonded`] for more
 		/// details.
 		#[pallet::call_index(15)]
-		#[pallet::weight(T::WeightInfo::force_unstake(*num_slashing_spans))]
+		#[pallet::weight(
+			T::WeightInfo::force_unstake(*num_slashing_spans)
+				// The removal of the commission base, which is not benchmarked yet.
+				.saturating_add(T::DbWeight::get().writes(1))
+		)]
 		pub fn force_unstake(
 			origin: OriginFor<T>,
 			stash: T::AccountId,
@@ -1632,7 +1660,11 @@ pub mod pallet {
 		/// - `num_slashing_spans`: Refer to comments on [`Call::withdraw_unbonded`] for more
 		/// details.
 		#[pallet::call_index(20)]
-		#[pallet::weight(T::WeightInfo::reap_stash(*num_slashing_spans))]
+		#[pallet::weight(
+			T::WeightInfo::reap_stash(*num_slashing_spans)
+				// The removal of the commission base, which is not benchmarked yet.
+				.saturating_add(T::DbWeight::get().writes(1))
+		)]
 		pub fn reap_stash(
 			origin: OriginFor<T>,
 			stash: T::AccountId,
@@ -1872,6 +1904,24 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Sets the maximum amount by which a validator may increase its commission within a
+		/// single era, or removes the limit if `None`.
+		///
+		/// The dispatch origin must be `T::AdminOrigin`. Root can always call this.
+		#[pallet::call_index(29)]
+		#[pallet::weight(
+			// `set_min_commission` does the same single write of a `Perbill` value.
+			T::WeightInfo::set_min_commission()
+		)]
+		pub fn set_max_commission_increase(
+			origin: OriginFor<T>,
+			new: Option<Perbill>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			MaxCommissionIncrease::<T>::set(new);
+			Ok(())
+		}
+
 		/// Pay out a page of the stakers behind a validator for the given era and page.
 		///
 		/// - `validator_stash` is the stash account of the validator.
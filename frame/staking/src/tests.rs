@@ -438,11 +438,9 @@ fn staking_should_work() {
 		// add a new candidate for being a validator. account 3 controlled by 4.
 		assert_ok!(Staking::bond(RuntimeOrigin::signed(3), 1500, RewardDestination::Account(3)));
 		assert_ok!(Staking::validate(RuntimeOrigin::signed(3), ValidatorPrefs::default()));
-		assert_ok!(Session::set_keys(
-			RuntimeOrigin::signed(3),
-			SessionKeys { other: 4.into() },
-			vec![]
-		));
+		let keys = SessionKeys { other: 4.into() };
+		let proof = Session::generate_ownership_proof(&3, &keys).unwrap();
+		assert_ok!(Session::set_keys(RuntimeOrigin::signed(3), keys, proof));
 
 		// No effects will be seen so far.
 		assert_eq_uvec!(validator_controllers(), vec![21, 11]);
@@ -1932,11 +1930,9 @@ fn switching_roles() {
 		// add a new validator candidate
 		assert_ok!(Staking::bond(RuntimeOrigin::signed(5), 1000, RewardDestination::Account(5)));
 		assert_ok!(Staking::validate(RuntimeOrigin::signed(5), ValidatorPrefs::default()));
-		assert_ok!(Session::set_keys(
-			RuntimeOrigin::signed(5),
-			SessionKeys { other: 6.into() },
-			vec![]
-		));
+		let keys = SessionKeys { other: 6.into() };
+		let proof = Session::generate_ownership_proof(&5, &keys).unwrap();
+		assert_ok!(Session::set_keys(RuntimeOrigin::signed(5), keys, proof));
 
 		mock::start_active_era(1);
 
@@ -1945,11 +1941,9 @@ fn switching_roles() {
 
 		// 2 decides to be a validator. Consequences:
 		assert_ok!(Staking::validate(RuntimeOrigin::signed(1), ValidatorPrefs::default()));
-		assert_ok!(Session::set_keys(
-			RuntimeOrigin::signed(1),
-			SessionKeys { other: 2.into() },
-			vec![]
-		));
+		let keys = SessionKeys { other: 2.into() };
+		let proof = Session::generate_ownership_proof(&1, &keys).unwrap();
+		assert_ok!(Session::set_keys(RuntimeOrigin::signed(1), keys, proof));
 		// new stakes:
 		// 11: 1000 self vote
 		// 21: 1000 self vote + 250 vote
@@ -2054,11 +2048,9 @@ fn bond_with_little_staked_value_bounded() {
 			// Stingy validator.
 			assert_ok!(Staking::bond(RuntimeOrigin::signed(1), 1, RewardDestination::Account(1)));
 			assert_ok!(Staking::validate(RuntimeOrigin::signed(1), ValidatorPrefs::default()));
-			assert_ok!(Session::set_keys(
-				RuntimeOrigin::signed(1),
-				SessionKeys { other: 1.into() },
-				vec![]
-			));
+			let keys = SessionKeys { other: 1.into() };
+			let proof = Session::generate_ownership_proof(&1, &keys).unwrap();
+			assert_ok!(Session::set_keys(RuntimeOrigin::signed(1), keys, proof));
 
 			// 1 era worth of reward. BUT, we set the timestamp after on_initialize, so outdated by
 			// one block.
@@ -5861,6 +5853,87 @@ fn min_commission_works() {
 	})
 }
 
+#[test]
+fn max_commission_increase_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		let prefs =
+			|percent| ValidatorPrefs { commission: Perbill::from_percent(percent), blocked: false };
+
+		// no limit initially.
+		assert_ok!(Staking::validate(RuntimeOrigin::signed(11), prefs(50)));
+
+		assert_noop!(
+			Staking::set_max_commission_increase(
+				RuntimeOrigin::signed(2),
+				Some(Perbill::from_percent(10))
+			),
+			BadOrigin
+		);
+		assert_ok!(Staking::set_max_commission_increase(
+			RuntimeOrigin::signed(1),
+			Some(Perbill::from_percent(10))
+		));
+
+		assert_noop!(
+			Staking::validate(RuntimeOrigin::signed(11), prefs(61)),
+			Error::<Test>::CommissionIncreaseTooHigh
+		);
+		assert_ok!(Staking::validate(RuntimeOrigin::signed(11), prefs(60)));
+		// the limit applies to the commission at the start of the era, not the latest one.
+		assert_ok!(Staking::validate(RuntimeOrigin::signed(11), prefs(55)));
+		assert_ok!(Staking::validate(RuntimeOrigin::signed(11), prefs(60)));
+		assert_noop!(
+			Staking::validate(RuntimeOrigin::signed(11), prefs(61)),
+			Error::<Test>::CommissionIncreaseTooHigh
+		);
+
+		// stashes becoming validators pick any commission.
+		assert_ok!(Staking::validate(RuntimeOrigin::signed(101), prefs(100)));
+
+		// the next era starts from the current commission.
+		start_active_era(1);
+		assert_noop!(
+			Staking::validate(RuntimeOrigin::signed(11), prefs(71)),
+			Error::<Test>::CommissionIncreaseTooHigh
+		);
+		assert_ok!(Staking::validate(RuntimeOrigin::signed(11), prefs(70)));
+
+		// removing the limit.
+		assert_ok!(Staking::set_max_commission_increase(RuntimeOrigin::root(), None));
+		assert_ok!(Staking::validate(RuntimeOrigin::signed(11), prefs(100)));
+	})
+}
+
+#[test]
+fn max_commission_increase_survives_chilling() {
+	ExtBuilder::default().build_and_execute(|| {
+		let prefs =
+			|percent| ValidatorPrefs { commission: Perbill::from_percent(percent), blocked: false };
+
+		assert_ok!(Staking::set_max_commission_increase(
+			RuntimeOrigin::root(),
+			Some(Perbill::from_percent(10))
+		));
+		assert_ok!(Staking::validate(RuntimeOrigin::signed(11), prefs(10)));
+
+		// chilling and validating again does not reset the commission.
+		assert_ok!(Staking::chill(RuntimeOrigin::signed(11)));
+		assert_noop!(
+			Staking::validate(RuntimeOrigin::signed(11), prefs(100)),
+			Error::<Test>::CommissionIncreaseTooHigh
+		);
+
+		// nor does waiting for a later era while chilled.
+		start_active_era(1);
+		assert!(!Validators::<Test>::contains_key(11));
+		assert_noop!(
+			Staking::validate(RuntimeOrigin::signed(11), prefs(21)),
+			Error::<Test>::CommissionIncreaseTooHigh
+		);
+		assert_ok!(Staking::validate(RuntimeOrigin::signed(11), prefs(20)));
+	})
+}
+
 #[test]
 #[should_panic]
 fn change_of_absolute_max_nominations() {
@@ -85,5 +85,60 @@ mod benchmarks {
 		assert_last_event::<T>(Event::KeyRemoved {});
 	}
 
+	#[benchmark]
+	fn propose_key() {
+		let caller: T::AccountId = whitelisted_caller();
+		Key::<T>::put(&caller);
+
+		let new_sudoer: T::AccountId = account("sudoer", 0, 0);
+		let new_sudoer_lookup = T::Lookup::unlookup(new_sudoer.clone());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), new_sudoer_lookup);
+
+		assert_last_event::<T>(Event::KeyProposed { new: new_sudoer });
+	}
+
+	#[benchmark]
+	fn accept_key() {
+		let old_sudoer: T::AccountId = account("sudoer", 0, 0);
+		Key::<T>::put(&old_sudoer);
+
+		let caller: T::AccountId = whitelisted_caller();
+		PendingKey::<T>::put(&caller);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert_last_event::<T>(Event::KeyChanged { old: Some(old_sudoer), new: caller });
+	}
+
+	#[benchmark]
+	fn sudo_schedule(l: Linear<0, { 1024 * 1024 }>) -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		Key::<T>::put(&caller);
+
+		let call: <T as Config>::RuntimeCall =
+			frame_system::Call::remark { remark: vec![1; l as usize] }.into();
+		let when = frame_system::Pallet::<T>::block_number() + 10u32.into();
+
+		// Runtimes without a scheduler cannot schedule calls.
+		let probe = T::Preimages::bound(call.clone()).map_err(|_| BenchmarkError::Skip)?;
+		let probe = T::Scheduler::schedule(
+			DispatchTime::At(when),
+			None,
+			HARD_DEADLINE,
+			T::RuntimeOrigin::root().into_caller(),
+			probe,
+		)
+		.map_err(|_| BenchmarkError::Skip)?;
+		T::Scheduler::cancel(probe).map_err(|_| BenchmarkError::Skip)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), when, Box::new(call));
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_bench_ext(), crate::mock::Test);
 }
@@ -15,9 +15,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{Config, Key};
+use crate::{Call, Config, Key, PendingKey};
 use codec::{Decode, Encode};
-use frame_support::{dispatch::DispatchInfo, ensure};
+use frame_support::{dispatch::DispatchInfo, ensure, traits::IsSubType};
 use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{DispatchInfoOf, Dispatchable, SignedExtension},
@@ -36,6 +36,9 @@ use sp_std::{fmt, marker::PhantomData};
 /// fail on applying them as they are not allowed/disabled/whatever. This would be some huge dos
 /// vector to any kind of chain. This extension solves the dos vector by preventing any kind of
 /// transaction entering the pool as long as it is not signed by the sudo account.
+///
+/// The only other transactions let through are calls to [`accept_key`](crate::Pallet::accept_key)
+/// signed by the pending key, so that a proposed key can complete the handover.
 #[derive(Clone, Eq, PartialEq, Encode, Decode, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 pub struct CheckOnlySudoAccount<T: Config + Send + Sync>(PhantomData<T>);
@@ -67,7 +70,7 @@ impl<T: Config + Send + Sync> CheckOnlySudoAccount<T> {
 
 impl<T: Config + Send + Sync> SignedExtension for CheckOnlySudoAccount<T>
 where
-	<T as Config>::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+	<T as Config>::RuntimeCall: Dispatchable<Info = DispatchInfo> + IsSubType<Call<T>>,
 {
 	const IDENTIFIER: &'static str = "CheckOnlySudoAccount";
 	type AccountId = T::AccountId;
@@ -82,12 +85,16 @@ where
 	fn validate(
 		&self,
 		who: &Self::AccountId,
-		_call: &Self::Call,
+		call: &Self::Call,
 		info: &DispatchInfoOf<Self::Call>,
 		_len: usize,
 	) -> TransactionValidity {
-		let sudo_key: T::AccountId = Key::<T>::get().ok_or(UnknownTransaction::CannotLookup)?;
-		ensure!(*who == sudo_key, InvalidTransaction::BadSigner);
+		let accepts_key = matches!(call.is_sub_type(), Some(Call::accept_key { .. })) &&
+			PendingKey::<T>::get().as_ref() == Some(who);
+		if !accepts_key {
+			let sudo_key: T::AccountId = Key::<T>::get().ok_or(UnknownTransaction::CannotLookup)?;
+			ensure!(*who == sudo_key, InvalidTransaction::BadSigner);
+		}
 
 		Ok(ValidTransaction {
 			priority: info.weight.ref_time() as TransactionPriority,
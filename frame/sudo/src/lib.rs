@@ -79,6 +79,11 @@
 //! [`sudo_unchecked_weight`](Pallet::sudo_unchecked_weight), which allows the sudo account to
 //! execute a call with a custom weight.
 //!
+//! To hand the key over safely, [`propose_key`](Pallet::propose_key) proposes a new sudo key,
+//! which only takes effect once the proposed account calls [`accept_key`](Pallet::accept_key).
+//! If the runtime configures a scheduler, [`sudo_schedule`](Pallet::sudo_schedule) dispatches a
+//! call with `Root` origin in a later block.
+//!
 //! <div class="example-wrap" style="display:inline-block"><pre class="compile_fail"
 //! style="white-space:normal;font:inherit;">
 //! <strong>Note:</strong> this pallet is not meant to be used inside other pallets. It is only
@@ -124,7 +129,13 @@
 use sp_runtime::{traits::StaticLookup, DispatchResult};
 use sp_std::prelude::*;
 
-use frame_support::{dispatch::GetDispatchInfo, traits::UnfilteredDispatchable};
+use frame_support::{
+	dispatch::GetDispatchInfo,
+	traits::{
+		schedule::{v3::Anon as ScheduleAnon, DispatchTime, HARD_DEADLINE},
+		OriginTrait, QueryPreimage, StorePreimage, UnfilteredDispatchable,
+	},
+};
 
 mod extension;
 #[cfg(test)]
@@ -141,6 +152,13 @@ pub use extension::CheckOnlySudoAccount;
 pub use pallet::*;
 
 type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+type PalletsOriginOf<T> =
+	<<T as frame_system::Config>::RuntimeOrigin as OriginTrait>::PalletsOrigin;
+type TaskAddressOf<T> = <<T as Config>::Scheduler as ScheduleAnon<
+	frame_system::pallet_prelude::BlockNumberFor<T>,
+	<T as Config>::RuntimeCall,
+	PalletsOriginOf<T>,
+>>::Address;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -166,6 +184,8 @@ pub mod pallet {
 			type RuntimeEvent = ();
 			#[inject_runtime_type]
 			type RuntimeCall = ();
+			type Scheduler = ();
+			type Preimages = ();
 		}
 	}
 	#[pallet::config(with_default)]
@@ -180,6 +200,21 @@ pub mod pallet {
 			+ UnfilteredDispatchable<RuntimeOrigin = Self::RuntimeOrigin>
 			+ GetDispatchInfo;
 
+		/// The scheduler used to dispatch the calls of [`Pallet::sudo_schedule`].
+		///
+		/// With `()`, scheduling is not available.
+		#[pallet::no_default_bounds]
+		type Scheduler: ScheduleAnon<
+			BlockNumberFor<Self>,
+			<Self as Config>::RuntimeCall,
+			PalletsOriginOf<Self>,
+			Hasher = Self::Hashing,
+		>;
+
+		/// The preimage provider used to store scheduled calls that are too large to be inlined.
+		#[pallet::no_default_bounds]
+		type Preimages: QueryPreimage<H = Self::Hashing> + StorePreimage;
+
 		/// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
 	}
@@ -217,7 +252,10 @@ pub mod pallet {
 		///
 		/// The dispatch origin for this call must be _Signed_.
 		#[pallet::call_index(1)]
-		#[pallet::weight((*weight, call.get_dispatch_info().class))]
+		#[pallet::weight((
+			T::WeightInfo::sudo().saturating_add(*weight),
+			call.get_dispatch_info().class,
+		))]
 		pub fn sudo_unchecked_weight(
 			origin: OriginFor<T>,
 			call: Box<<T as Config>::RuntimeCall>,
@@ -227,7 +265,9 @@ pub mod pallet {
 			let _ = weight; // We don't check the weight witness since it is a root call.
 
 			let res = call.dispatch_bypass_filter(RawOrigin::Root.into());
-			Self::deposit_event(Event::Sudid { sudo_result: res.map(|_| ()).map_err(|e| e.error) });
+			Self::deposit_event(Event::SudoUncheckedWeightDone {
+				sudo_result: res.map(|_| ()).map_err(|e| e.error),
+			});
 
 			// Sudo user does not pay a fee.
 			Ok(Pays::No.into())
@@ -236,7 +276,10 @@ pub mod pallet {
 		/// Authenticates the current sudo key and sets the given AccountId (`new`) as the new sudo
 		/// key.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::set_key())]
+		#[pallet::weight(
+			// The removal of any pending key is not benchmarked yet.
+			T::WeightInfo::set_key().saturating_add(T::DbWeight::get().writes(1))
+		)]
 		pub fn set_key(
 			origin: OriginFor<T>,
 			new: AccountIdLookupOf<T>,
@@ -246,6 +289,7 @@ pub mod pallet {
 			let new = T::Lookup::lookup(new)?;
 			Self::deposit_event(Event::KeyChanged { old: Key::<T>::get(), new: new.clone() });
 			Key::<T>::put(new);
+			PendingKey::<T>::kill();
 
 			// Sudo user does not pay a fee.
 			Ok(Pays::No.into())
@@ -284,12 +328,97 @@ pub mod pallet {
 		///
 		/// **This cannot be un-done.**
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::remove_key())]
+		#[pallet::weight(
+			// The removal of any pending key is not benchmarked yet.
+			T::WeightInfo::remove_key().saturating_add(T::DbWeight::get().writes(1))
+		)]
 		pub fn remove_key(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
 			Self::ensure_sudo(origin)?;
 
 			Self::deposit_event(Event::KeyRemoved {});
 			Key::<T>::kill();
+			PendingKey::<T>::kill();
+
+			// Sudo user does not pay a fee.
+			Ok(Pays::No.into())
+		}
+
+		/// Authenticates the current sudo key and proposes the given AccountId (`new`) as the new
+		/// sudo key.
+		///
+		/// The key only changes once `new` calls [`Pallet::accept_key`], which guards against
+		/// handing the key to an account nobody controls. A later proposal replaces this one.
+		#[pallet::call_index(5)]
+		#[pallet::weight(
+			// `set_key` reads the key and writes a single value as well.
+			T::WeightInfo::set_key()
+		)]
+		pub fn propose_key(
+			origin: OriginFor<T>,
+			new: AccountIdLookupOf<T>,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_sudo(origin)?;
+
+			let new = T::Lookup::lookup(new)?;
+			Self::deposit_event(Event::KeyProposed { new: new.clone() });
+			PendingKey::<T>::put(new);
+
+			// Sudo user does not pay a fee.
+			Ok(Pays::No.into())
+		}
+
+		/// Accepts the sudo key proposed by [`Pallet::propose_key`] and becomes the new sudo key.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the proposed account.
+		#[pallet::call_index(6)]
+		#[pallet::weight(
+			// `set_key` plus the access of the pending key.
+			T::WeightInfo::set_key().saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		)]
+		pub fn accept_key(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(PendingKey::<T>::get().as_ref() == Some(&who), Error::<T>::NotPendingKey);
+
+			PendingKey::<T>::kill();
+			Self::deposit_event(Event::KeyChanged { old: Key::<T>::get(), new: who.clone() });
+			Key::<T>::put(who);
+
+			// New sudo user does not pay a fee.
+			Ok(Pays::No.into())
+		}
+
+		/// Authenticates the sudo key and schedules a function call to be dispatched with `Root`
+		/// origin at the beginning of block `when`.
+		///
+		/// The weight of the call is accounted for by the scheduler when it is dispatched. Calls
+		/// too large to be inlined are noted as preimages, which is weighed by their length.
+		#[pallet::call_index(7)]
+		#[pallet::weight(
+			T::WeightInfo::sudo()
+				// Scheduling reads and writes the agenda. Noting a call as a preimage accesses its
+				// status and stores the call.
+				.saturating_add(T::DbWeight::get().reads_writes(3, 3))
+				// Hashing the call to note it as a preimage, estimated at 2 ns per byte.
+				.saturating_add(
+					Weight::from_parts(2_000, 0).saturating_mul(call.encoded_size() as u64)
+				)
+		)]
+		pub fn sudo_schedule(
+			origin: OriginFor<T>,
+			when: BlockNumberFor<T>,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_sudo(origin)?;
+
+			let call = T::Preimages::bound(*call)?;
+			let task = T::Scheduler::schedule(
+				DispatchTime::At(when),
+				None,
+				HARD_DEADLINE,
+				T::RuntimeOrigin::root().into_caller(),
+				call,
+			)?;
+			Self::deposit_event(Event::SudoScheduled { when, task });
 
 			// Sudo user does not pay a fee.
 			Ok(Pays::No.into())
@@ -318,6 +447,23 @@ pub mod pallet {
 			/// The result of the call made by the sudo user.
 			sudo_result: DispatchResult,
 		},
+		/// A [sudo_unchecked_weight](Pallet::sudo_unchecked_weight) call just took place.
+		SudoUncheckedWeightDone {
+			/// The result of the call made by the sudo user.
+			sudo_result: DispatchResult,
+		},
+		/// A new sudo key has been proposed and awaits [acceptance](Pallet::accept_key).
+		KeyProposed {
+			/// The proposed sudo key.
+			new: T::AccountId,
+		},
+		/// A [sudo_schedule](Pallet::sudo_schedule) call just took place.
+		SudoScheduled {
+			/// The block at which the call will be dispatched.
+			when: BlockNumberFor<T>,
+			/// The address of the scheduled task.
+			task: TaskAddressOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -325,12 +471,18 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// Sender must be the Sudo account.
 		RequireSudo,
+		/// Sender must be the proposed Sudo account.
+		NotPendingKey,
 	}
 
 	/// The `AccountId` of the sudo key.
 	#[pallet::storage]
 	pub(super) type Key<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
 
+	/// The `AccountId` proposed to become the sudo key, see [`Pallet::propose_key`].
+	#[pallet::storage]
+	pub(super) type PendingKey<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -19,9 +19,15 @@
 
 use super::*;
 use crate as sudo;
-use frame_support::{derive_impl, traits::Contains};
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{
+		schedule::{DispatchTime, Period, Priority},
+		Bounded, Contains,
+	},
+};
 use sp_io;
-use sp_runtime::BuildStorage;
+use sp_runtime::{traits::BlakeTwo256, BuildStorage, DispatchError};
 
 // Logger module to track execution.
 #[frame_support::pallet]
@@ -114,10 +120,54 @@ impl logger::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 }
 
+parameter_types! {
+	pub static Scheduled: Vec<(u64, OriginCaller, Bounded<RuntimeCall, BlakeTwo256>)> = vec![];
+}
+
+/// Records the scheduled calls in [`Scheduled`], addressed by their index.
+pub struct TestScheduler;
+impl ScheduleAnon<u64, RuntimeCall, OriginCaller> for TestScheduler {
+	type Address = u32;
+	type Hasher = BlakeTwo256;
+
+	fn schedule(
+		when: DispatchTime<u64>,
+		_: Option<Period<u64>>,
+		_: Priority,
+		origin: OriginCaller,
+		call: Bounded<RuntimeCall, BlakeTwo256>,
+	) -> Result<u32, DispatchError> {
+		let when = when.evaluate(System::block_number());
+		if when <= System::block_number() {
+			return Err(DispatchError::Other("target block number in the past"))
+		}
+		Ok(Scheduled::mutate(|s| {
+			s.push((when, origin, call));
+			s.len() as u32 - 1
+		}))
+	}
+	fn cancel(address: u32) -> Result<(), DispatchError> {
+		Scheduled::mutate(|s| s.get_mut(address as usize).map(|(when, ..)| *when = 0))
+			.ok_or(DispatchError::Unavailable)
+	}
+	fn reschedule(_: u32, _: DispatchTime<u64>) -> Result<u32, DispatchError> {
+		Err(DispatchError::Unavailable)
+	}
+	fn next_dispatch_time(address: u32) -> Result<u64, DispatchError> {
+		Scheduled::get()
+			.get(address as usize)
+			.map(|(when, ..)| *when)
+			.filter(|when| *when != 0)
+			.ok_or(DispatchError::Unavailable)
+	}
+}
+
 // Implement the sudo module's `Config` on the Test runtime.
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
+	type Scheduler = TestScheduler;
+	type Preimages = ();
 	type WeightInfo = ();
 }
 
@@ -133,6 +183,7 @@ pub fn new_test_ext(root_key: u64) -> sp_io::TestExternalities {
 		.unwrap();
 	let mut ext: sp_io::TestExternalities = t.into();
 	ext.execute_with(|| System::set_block_number(1));
+	Scheduled::take();
 	ext
 }
 
@@ -18,11 +18,13 @@
 //! Tests for the module.
 
 use super::*;
-use frame_support::{assert_noop, assert_ok, weights::Weight};
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok, traits::Bounded, weights::Weight};
 use mock::{
-	new_test_ext, Logger, LoggerCall, RuntimeCall, RuntimeEvent as TestEvent, RuntimeOrigin, Sudo,
-	SudoCall, System, Test,
+	new_test_ext, Logger, LoggerCall, OriginCaller, RuntimeCall, RuntimeEvent as TestEvent,
+	RuntimeOrigin, Scheduled, Sudo, SudoCall, System, Test,
 };
+use sp_runtime::DispatchError;
 
 #[test]
 fn test_setup_works() {
@@ -108,7 +110,7 @@ fn sudo_unchecked_weight_basics() {
 		let sudo_unchecked_weight_call =
 			SudoCall::sudo_unchecked_weight { call, weight: Weight::from_parts(1_000, 0) };
 		let info = sudo_unchecked_weight_call.get_dispatch_info();
-		assert_eq!(info.weight, Weight::from_parts(1_000, 0));
+		assert_eq!(info.weight, <() as WeightInfo>::sudo() + Weight::from_parts(1_000, 0));
 	});
 }
 
@@ -125,7 +127,9 @@ fn sudo_unchecked_weight_emits_events_correctly() {
 			call,
 			Weight::from_parts(1_000, 0)
 		));
-		System::assert_has_event(TestEvent::Sudo(Event::Sudid { sudo_result: Ok(()) }));
+		System::assert_has_event(TestEvent::Sudo(Event::SudoUncheckedWeightDone {
+			sudo_result: Ok(()),
+		}));
 	})
 }
 
@@ -157,6 +161,92 @@ fn set_key_emits_events_correctly() {
 	});
 }
 
+#[test]
+fn propose_and_accept_key_works() {
+	new_test_ext(1).execute_with(|| {
+		// Only the sudo key can propose a new key.
+		assert_noop!(Sudo::propose_key(RuntimeOrigin::signed(2), 3), Error::<Test>::RequireSudo);
+
+		assert_ok!(Sudo::propose_key(RuntimeOrigin::signed(1), 2));
+		System::assert_last_event(TestEvent::Sudo(Event::KeyProposed { new: 2 }));
+		// The key does not change until the proposal is accepted.
+		assert_eq!(Key::<Test>::get(), Some(1u64));
+
+		// A later proposal replaces the earlier one.
+		assert_ok!(Sudo::propose_key(RuntimeOrigin::signed(1), 3));
+		assert_noop!(Sudo::accept_key(RuntimeOrigin::signed(2)), Error::<Test>::NotPendingKey);
+
+		assert_ok!(Sudo::accept_key(RuntimeOrigin::signed(3)));
+		System::assert_last_event(TestEvent::Sudo(Event::KeyChanged { old: Some(1), new: 3 }));
+		assert_eq!(Key::<Test>::get(), Some(3u64));
+		assert_noop!(Sudo::accept_key(RuntimeOrigin::signed(3)), Error::<Test>::NotPendingKey);
+	});
+}
+
+#[test]
+fn check_only_sudo_account_lets_the_pending_key_accept() {
+	use frame_support::dispatch::GetDispatchInfo;
+	use sp_runtime::{traits::SignedExtension, transaction_validity::InvalidTransaction};
+
+	new_test_ext(1).execute_with(|| {
+		let accept = RuntimeCall::Sudo(SudoCall::accept_key {});
+		let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let validate = |who: u64, call: &RuntimeCall| {
+			CheckOnlySudoAccount::<Test>::new().validate(&who, call, &call.get_dispatch_info(), 0)
+		};
+
+		assert_eq!(validate(2, &accept), Err(InvalidTransaction::BadSigner.into()));
+
+		assert_ok!(Sudo::propose_key(RuntimeOrigin::signed(1), 2));
+		assert!(validate(2, &accept).is_ok());
+		// The pending key can't do anything else yet.
+		assert_eq!(validate(2, &remark), Err(InvalidTransaction::BadSigner.into()));
+		assert_eq!(validate(3, &accept), Err(InvalidTransaction::BadSigner.into()));
+		assert!(validate(1, &remark).is_ok());
+	});
+}
+
+#[test]
+fn set_and_remove_key_discard_proposal() {
+	new_test_ext(1).execute_with(|| {
+		assert_ok!(Sudo::propose_key(RuntimeOrigin::signed(1), 2));
+		assert_ok!(Sudo::set_key(RuntimeOrigin::signed(1), 3));
+		assert_noop!(Sudo::accept_key(RuntimeOrigin::signed(2)), Error::<Test>::NotPendingKey);
+
+		assert_ok!(Sudo::propose_key(RuntimeOrigin::signed(3), 2));
+		assert_ok!(Sudo::remove_key(RuntimeOrigin::signed(3)));
+		assert_noop!(Sudo::accept_key(RuntimeOrigin::signed(2)), Error::<Test>::NotPendingKey);
+	});
+}
+
+#[test]
+fn sudo_schedule_works() {
+	new_test_ext(1).execute_with(|| {
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::privileged_i32_log {
+			i: 42,
+			weight: Weight::from_parts(1_000, 0),
+		}));
+
+		assert_noop!(
+			Sudo::sudo_schedule(RuntimeOrigin::signed(2), 5, call.clone()),
+			Error::<Test>::RequireSudo
+		);
+		assert_noop!(
+			Sudo::sudo_schedule(RuntimeOrigin::signed(1), 1, call.clone()),
+			DispatchError::Other("target block number in the past")
+		);
+
+		assert_ok!(Sudo::sudo_schedule(RuntimeOrigin::signed(1), 5, call.clone()));
+		System::assert_last_event(TestEvent::Sudo(Event::SudoScheduled { when: 5, task: 0 }));
+
+		// The call is dispatched later with `Root` origin.
+		let (when, origin, bounded) = Scheduled::get().pop().unwrap();
+		assert_eq!((when, origin), (5, OriginCaller::system(frame_system::RawOrigin::Root)));
+		assert_eq!(bounded, Bounded::Inline(call.encode().try_into().unwrap()));
+		assert!(Logger::i32_log().is_empty());
+	});
+}
+
 #[test]
 fn remove_key_works() {
 	new_test_ext(1).execute_with(|| {
@@ -557,7 +557,7 @@ pub fn benchmarks(
 					);
 					whitelist.push(intrablock_entropy);
 
-					#krate::benchmarking::set_whitelist(whitelist.clone());
+					#krate::benchmarking::set_whitelist(whitelist);
 					let mut results: #krate::__private::Vec<#krate::BenchmarkResult> = #krate::__private::Vec::new();
 
 					// Always do at least one internal repeat...
@@ -580,9 +580,9 @@ pub fn benchmarks(
 						// This will enable worst case scenario for reading from the database.
 						#krate::benchmarking::commit_db();
 
-						// Access all whitelisted keys to get them into the proof recorder since the
-						// recorder does now have a whitelist.
-						for key in &whitelist {
+						// Access all whitelisted keys, including the ones whitelisted by the setup, to
+						// get them into the proof recorder since the recorder does now have a whitelist.
+						for key in &#krate::benchmarking::get_whitelist() {
 							#krate::__private::storage::unhashed::get_raw(&key.key);
 						}
 
@@ -1169,6 +1169,26 @@ pub fn feeless_if(_: TokenStream, _: TokenStream) -> TokenStream {
 	pallet_macro_stub()
 }
 
+/// Each dispatchable may be annotated with the `#[pallet::no_transaction]` attribute, which
+/// opts it out of being executed in a new storage layer.
+///
+/// By default, all dispatchables run inside of a storage layer, and any storage changes are
+/// reverted when they return an error. A dispatchable annotated with this attribute keeps all
+/// the changes it made before returning an error, so it must only be used on dispatchables that
+/// check everything before writing to storage, or that open their own storage layer.
+///
+/// ### Example
+/// ```ignore
+/// #[pallet::no_transaction]
+/// pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResult {
+///     ....
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn no_transaction(_: TokenStream, _: TokenStream) -> TokenStream {
+	pallet_macro_stub()
+}
+
 /// Allows you to define some extra constants to be added into constant metadata.
 ///
 /// Item must be defined as:
@@ -211,7 +211,12 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 
 	let capture_docs = if cfg!(feature = "no-metadata-docs") { "never" } else { "always" };
 
-	// Wrap all calls inside of storage layers
+	// Wrap all calls inside of storage layers, unless they opted out with `no_transaction`
+	let no_transaction = methods
+		.iter()
+		.filter(|method| method.no_transaction)
+		.map(|method| method.name.clone())
+		.collect::<Vec<_>>();
 	if let Some(syn::Item::Impl(item_impl)) = def
 		.call
 		.as_ref()
@@ -219,6 +224,9 @@ pub fn expand_call(def: &mut Def) -> proc_macro2::TokenStream {
 	{
 		item_impl.items.iter_mut().for_each(|i| {
 			if let syn::ImplItem::Fn(method) = i {
+				if no_transaction.contains(&method.sig.ident) {
+					return
+				}
 				let block = &method.block;
 				method.block = syn::parse_quote! {{
 					// We execute all dispatchable in a new storage layer, allowing them
@@ -33,6 +33,7 @@ mod keyword {
 	syn::custom_keyword!(T);
 	syn::custom_keyword!(pallet);
 	syn::custom_keyword!(feeless_if);
+	syn::custom_keyword!(no_transaction);
 }
 
 /// Definition of dispatchables typically `impl<T: Config> Pallet<T> { ... }`
@@ -89,6 +90,9 @@ pub struct CallVariantDef {
 	pub cfg_attrs: Vec<syn::Attribute>,
 	/// The optional `feeless_if` attribute on the `pallet::call`.
 	pub feeless_check: Option<syn::ExprClosure>,
+	/// Whether the `no_transaction` attribute is set, i.e. the call is not executed in a new
+	/// storage layer.
+	pub no_transaction: bool,
 }
 
 /// Attributes for functions in call impl block.
@@ -99,6 +103,8 @@ pub enum FunctionAttr {
 	Weight(syn::Expr),
 	/// Parse for `#[pallet::feeless_if(expr)]`
 	FeelessIf(Span, syn::ExprClosure),
+	/// Parse for `#[pallet::no_transaction]`
+	NoTransaction(Span),
 }
 
 impl syn::parse::Parse for FunctionAttr {
@@ -138,6 +144,9 @@ impl syn::parse::Parse for FunctionAttr {
 					err
 				})?,
 			))
+		} else if lookahead.peek(keyword::no_transaction) {
+			let span = content.parse::<keyword::no_transaction>()?.span();
+			Ok(FunctionAttr::NoTransaction(span))
 		} else {
 			Err(lookahead.error())
 		}
@@ -272,6 +281,7 @@ impl CallDef {
 				let mut call_idx_attrs = vec![];
 				let mut weight_attrs = vec![];
 				let mut feeless_attrs = vec![];
+				let mut no_transaction_attrs = vec![];
 				for attr in helper::take_item_pallet_attrs(&mut method.attrs)?.into_iter() {
 					match attr {
 						FunctionAttr::CallIndex(_) => {
@@ -283,6 +293,9 @@ impl CallDef {
 						FunctionAttr::FeelessIf(span, _) => {
 							feeless_attrs.push((span, attr));
 						},
+						FunctionAttr::NoTransaction(span) => {
+							no_transaction_attrs.push(span);
+						},
 					}
 				}
 
@@ -316,6 +329,12 @@ impl CallDef {
 					let msg = "Invalid pallet::call, too many call_index attributes given";
 					return Err(syn::Error::new(method.sig.span(), msg))
 				}
+				if no_transaction_attrs.len() > 1 {
+					let msg = "Invalid pallet::call, too many no_transaction attributes given";
+					return Err(syn::Error::new(no_transaction_attrs[1], msg))
+				}
+				let no_transaction = !no_transaction_attrs.is_empty();
+
 				let call_index = call_idx_attrs.pop().map(|attr| match attr {
 					FunctionAttr::CallIndex(idx) => idx,
 					_ => unreachable!("checked during creation of the let binding"),
@@ -447,6 +466,7 @@ impl CallDef {
 					attrs: method.attrs.clone(),
 					cfg_attrs,
 					feeless_check,
+					no_transaction,
 				});
 			} else {
 				let msg = "Invalid pallet::call, only method accepted";
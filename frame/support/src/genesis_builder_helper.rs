@@ -20,7 +20,7 @@
 //! Provides common logic. For more info refer to [`sp_genesis_builder::GenesisBuilder`].
 
 use frame_support::traits::BuildGenesisConfig;
-use sp_genesis_builder::Result as BuildResult;
+use sp_genesis_builder::{PresetId, Result as BuildResult};
 use sp_runtime::format_runtime_string;
 
 /// Get the default `GenesisConfig` as a JSON blob. For more info refer to
@@ -42,3 +42,20 @@ pub fn build_config<GC: BuildGenesisConfig>(json: sp_std::vec::Vec<u8>) -> Build
 	<GC as BuildGenesisConfig>::build(&gc);
 	Ok(())
 }
+
+/// Get the `GenesisConfig` preset identified by `name` as a JSON blob. For more info refer to
+/// [`sp_genesis_builder::GenesisBuilder::get_preset`].
+///
+/// `None` maps to the default config, any other name is resolved by `preset_for_name`.
+pub fn get_preset<GC>(
+	name: &Option<PresetId>,
+	preset_for_name: impl FnOnce(&PresetId) -> Option<sp_std::vec::Vec<u8>>,
+) -> Option<sp_std::vec::Vec<u8>>
+where
+	GC: BuildGenesisConfig + Default,
+{
+	match name {
+		Some(name) => preset_for_name(name),
+		None => Some(create_default_config::<GC>()),
+	}
+}
@@ -2274,9 +2274,9 @@ pub mod pallet_macros {
 	pub use frame_support_procedural::{
 		composite_enum, config, disable_frame_system_supertrait_check, error, event,
 		extra_constants, feeless_if, generate_deposit, generate_store, getter, hooks,
-		import_section, inherent, no_default, no_default_bounds, origin, pallet_section,
-		storage_prefix, storage_version, type_value, unbounded, validate_unsigned, weight,
-		whitelist_storage,
+		import_section, inherent, no_default, no_default_bounds, no_transaction, origin,
+		pallet_section, storage_prefix, storage_version, type_value, unbounded,
+		validate_unsigned, weight, whitelist_storage,
 	};
 
 	/// Allows a pallet to declare a set of functions as a *dispatchable extrinsic*. In
@@ -2371,6 +2371,8 @@ pub mod pallet_macros {
 	/// - The first argument is always an `OriginFor` (or `T::RuntimeOrigin`).
 	/// - The return type is always [`crate::dispatch::DispatchResult`] (or
 	///   [`crate::dispatch::DispatchResultWithPostInfo`]).
+	/// - The dispatchable is executed in a new storage layer, so its storage changes are reverted
+	///   if it returns an error, unless it is annotated with [`no_transaction`].
 	///
 	/// **WARNING**: modifying dispatchables, changing their order (i.e. using [`call_index`]),
 	/// removing some, etc., must be done with care. This will change the encoding of the , and
@@ -17,12 +17,13 @@
 
 use crate::{
 	traits::{GetStorageVersion, NoStorageVersionSet, PalletInfoAccess, StorageVersion},
-	weights::{RuntimeDbWeight, Weight},
+	weights::{RuntimeDbWeight, Weight, WeightMeter},
 };
+use codec::{Decode, Encode, FullCodec, MaxEncodedLen};
 use impl_trait_for_tuples::impl_for_tuples;
 use sp_core::Get;
 use sp_io::{hashing::twox_128, storage::clear_prefix, KillStorageResult};
-use sp_std::marker::PhantomData;
+use sp_std::{marker::PhantomData, vec::Vec};
 
 /// Handles storage migration pallet versioning.
 ///
@@ -344,3 +345,129 @@ impl<P: Get<&'static str>, DbWeight: Get<RuntimeDbWeight>> frame_support::traits
 		Ok(())
 	}
 }
+
+/// A migration that is executed in multiple steps, possibly spread over multiple blocks.
+///
+/// Each step does a bounded amount of work and returns a cursor, which is persisted by the
+/// migration executor and passed into the next step. This allows migrating storage that is too
+/// large to be migrated within the weight limit of a single block.
+pub trait MigrationStep {
+	/// The cursor that tracks the progress of the migration between steps.
+	type Cursor: FullCodec + MaxEncodedLen;
+
+	/// The identifier of the migration.
+	///
+	/// Must be unique, as the executor uses it to never run the same migration twice.
+	type Identifier: FullCodec + MaxEncodedLen;
+
+	/// The identifier of this migration.
+	fn id() -> Self::Identifier;
+
+	/// The maximum number of steps this migration may take.
+	///
+	/// The executor considers the migration as failed once it exceeds this number of steps.
+	/// `None` means that there is no limit.
+	fn max_steps() -> Option<u32> {
+		None
+	}
+
+	/// Execute the next step of the migration, starting from `cursor`.
+	///
+	/// `cursor` is `None` for the first step. Returns the cursor for the next step, or `None` once
+	/// the migration is complete.
+	///
+	/// All the weight used must be consumed from `meter`. If `meter` does not have enough weight
+	/// left for a single step, [`MigrationStepError::InsufficientWeight`] must be returned
+	/// without modifying storage.
+	fn step(
+		cursor: Option<Self::Cursor>,
+		meter: &mut WeightMeter,
+	) -> Result<Option<Self::Cursor>, MigrationStepError>;
+}
+
+/// An error that can occur while executing a step of a [`MigrationStep`].
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, scale_info::TypeInfo)]
+pub enum MigrationStepError {
+	/// The remaining weight is not enough for a single step.
+	InsufficientWeight {
+		/// The weight needed for the next step.
+		required: Weight,
+	},
+	/// The cursor could not be decoded or is otherwise invalid.
+	InvalidCursor,
+	/// The migration failed.
+	Failed,
+}
+
+/// A list of [`MigrationStep`]s, to be executed in order by a migration executor.
+///
+/// This is implemented for tuples of [`MigrationStep`]s. Identifiers and cursors are exposed in
+/// their encoded form, so that migrations with different types can be driven by the same
+/// executor.
+pub trait MigrationSteps {
+	/// The number of migrations in the list.
+	fn len() -> u32;
+
+	/// The encoded identifier of the `n`th migration, or `None` if there is no such migration.
+	fn nth_id(n: u32) -> Option<Vec<u8>>;
+
+	/// The maximum number of steps of the `n`th migration, or `None` if there is no such
+	/// migration.
+	fn nth_max_steps(n: u32) -> Option<Option<u32>>;
+
+	/// Execute the next step of the `n`th migration, starting from the encoded `cursor`.
+	///
+	/// Returns `None` if there is no such migration. See [`MigrationStep::step`].
+	fn nth_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, MigrationStepError>>;
+}
+
+/// Execute a step of `M`, with its cursor in encoded form.
+fn step_encoded<M: MigrationStep>(
+	cursor: Option<Vec<u8>>,
+	meter: &mut WeightMeter,
+) -> Result<Option<Vec<u8>>, MigrationStepError> {
+	let cursor = cursor
+		.map(|cursor| M::Cursor::decode(&mut &cursor[..]))
+		.transpose()
+		.map_err(|_| MigrationStepError::InvalidCursor)?;
+
+	M::step(cursor, meter).map(|next| next.map(|cursor| cursor.encode()))
+}
+
+#[cfg_attr(all(not(feature = "tuples-96"), not(feature = "tuples-128")), impl_for_tuples(64))]
+#[cfg_attr(all(feature = "tuples-96", not(feature = "tuples-128")), impl_for_tuples(96))]
+#[cfg_attr(feature = "tuples-128", impl_for_tuples(128))]
+#[tuple_types_custom_trait_bound(MigrationStep)]
+impl MigrationSteps for Tuple {
+	fn len() -> u32 {
+		let ids: &[fn() -> Vec<u8>] = &[for_tuples!( #( || Tuple::id().encode() ),* )];
+		ids.len() as u32
+	}
+
+	fn nth_id(n: u32) -> Option<Vec<u8>> {
+		let ids: &[fn() -> Vec<u8>] = &[for_tuples!( #( || Tuple::id().encode() ),* )];
+		ids.get(n as usize).map(|id| id())
+	}
+
+	fn nth_max_steps(n: u32) -> Option<Option<u32>> {
+		let max_steps: &[fn() -> Option<u32>] = &[for_tuples!( #( Tuple::max_steps ),* )];
+		max_steps.get(n as usize).map(|max_steps| max_steps())
+	}
+
+	fn nth_step(
+		n: u32,
+		cursor: Option<Vec<u8>>,
+		meter: &mut WeightMeter,
+	) -> Option<Result<Option<Vec<u8>>, MigrationStepError>> {
+		let steps: &[fn(
+			Option<Vec<u8>>,
+			&mut WeightMeter,
+		) -> Result<Option<Vec<u8>>, MigrationStepError>] =
+			&[for_tuples!( #( step_encoded::<Tuple> ),* )];
+		steps.get(n as usize).map(|step| step(cursor, meter))
+	}
+}
@@ -467,43 +467,57 @@ where
 	}
 
 	fn translate<O: Decode, F: FnMut(K1, K2, O) -> Option<V>>(mut f: F) {
-		let prefix = G::prefix_hash().to_vec();
-		let mut previous_key = prefix.clone();
-		while let Some(next) =
-			sp_io::storage::next_key(&previous_key).filter(|n| n.starts_with(&prefix))
-		{
-			previous_key = next;
-			let value = match unhashed::get::<O>(&previous_key) {
-				Some(value) => value,
-				None => {
-					log::error!("Invalid translate: fail to decode old value");
-					continue
-				},
-			};
-			let mut key_material = G::Hasher1::reverse(&previous_key[prefix.len()..]);
-			let key1 = match K1::decode(&mut key_material) {
-				Ok(key1) => key1,
-				Err(_) => {
-					log::error!("Invalid translate: fail to decode key1");
-					continue
-				},
-			};
-
-			let mut key2_material = G::Hasher2::reverse(key_material);
-			let key2 = match K2::decode(&mut key2_material) {
-				Ok(key2) => key2,
-				Err(_) => {
-					log::error!("Invalid translate: fail to decode key2");
-					continue
-				},
-			};
-
-			match f(key1, key2, value) {
-				Some(new) => unhashed::put::<V>(&previous_key, &new),
-				None => unhashed::kill(&previous_key),
+		let mut previous_key = None;
+		loop {
+			previous_key = Self::translate_next(previous_key, &mut f);
+			if previous_key.is_none() {
+				break
 			}
 		}
 	}
+
+	fn translate_next<O: Decode, F: FnMut(K1, K2, O) -> Option<V>>(
+		previous_key: Option<Vec<u8>>,
+		mut f: F,
+	) -> Option<Vec<u8>> {
+		let prefix = G::prefix_hash().to_vec();
+		let previous_key = previous_key.unwrap_or_else(|| prefix.clone());
+
+		let current_key =
+			sp_io::storage::next_key(&previous_key).filter(|n| n.starts_with(&prefix))?;
+
+		let value = match unhashed::get::<O>(&current_key) {
+			Some(value) => value,
+			None => {
+				log::error!("Invalid translate: fail to decode old value");
+				return Some(current_key)
+			},
+		};
+		let mut key_material = G::Hasher1::reverse(&current_key[prefix.len()..]);
+		let key1 = match K1::decode(&mut key_material) {
+			Ok(key1) => key1,
+			Err(_) => {
+				log::error!("Invalid translate: fail to decode key1");
+				return Some(current_key)
+			},
+		};
+
+		let mut key2_material = G::Hasher2::reverse(key_material);
+		let key2 = match K2::decode(&mut key2_material) {
+			Ok(key2) => key2,
+			Err(_) => {
+				log::error!("Invalid translate: fail to decode key2");
+				return Some(current_key)
+			},
+		};
+
+		match f(key1, key2, value) {
+			Some(new) => unhashed::put::<V>(&current_key, &new),
+			None => unhashed::kill(&current_key),
+		}
+
+		Some(current_key)
+	}
 }
 
 /// Test iterators for StorageDoubleMap
@@ -653,4 +667,31 @@ mod test_iterators {
 			);
 		})
 	}
+
+	#[test]
+	fn double_map_translate_next_resumes_from_cursor() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			type DoubleMap = self::frame_system::DoubleMap<Runtime>;
+
+			for i in 0..4 {
+				DoubleMap::insert(i as u16, i as u32, i as u64);
+			}
+
+			// Translate one element per step, removing the odd ones.
+			let mut cursor = None;
+			let mut steps = 0;
+			loop {
+				cursor = DoubleMap::translate_next(cursor, |_k1, _k2, v: u64| {
+					(v % 2 == 0).then_some(v * 10)
+				});
+				if cursor.is_none() {
+					break
+				}
+				steps += 1;
+			}
+
+			assert_eq!(steps, 4);
+			assert_eq!(DoubleMap::iter().collect::<Vec<_>>(), vec![(0, 0, 0), (2, 2, 20)]);
+		})
+	}
 }
@@ -715,6 +715,25 @@ where
 	pub fn translate<O: Decode, F: FnMut(Key1, Key2, O) -> Option<Value>>(f: F) {
 		<Self as crate::storage::IterableStorageDoubleMap<Key1, Key2, Value>>::translate(f)
 	}
+
+	/// Translate the next element following `previous_key` by a function `f`.
+	///
+	/// By returning `None` from `f` for the element, you'll remove it from the map.
+	///
+	/// Returns a cursor to pass as `previous_key` in the next call, or `None` once every element
+	/// has been translated. This allows large maps to be migrated over multiple blocks. Elements
+	/// removed by `f` do not invalidate the cursor.
+	///
+	/// NOTE: If a value fail to decode because storage is corrupted then it is skipped.
+	pub fn translate_next<O: Decode, F: FnMut(Key1, Key2, O) -> Option<Value>>(
+		previous_key: Option<Vec<u8>>,
+		f: F,
+	) -> Option<Vec<u8>> {
+		<Self as crate::storage::IterableStorageDoubleMap<Key1, Key2, Value>>::translate_next(
+			previous_key,
+			f,
+		)
+	}
 }
 
 impl<Prefix, Hasher1, Hasher2, Key1, Key2, Value, QueryKind, OnEmpty, MaxValues>
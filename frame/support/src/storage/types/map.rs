@@ -476,6 +476,23 @@ where
 	pub fn translate<O: Decode, F: FnMut(Key, O) -> Option<Value>>(f: F) {
 		<Self as crate::storage::IterableStorageMap<Key, Value>>::translate(f)
 	}
+
+	/// Translate the next element following `previous_key` by a function `f`.
+	///
+	/// By returning `None` from `f` for the element, you'll remove it from the map.
+	///
+	/// Returns a cursor to pass as `previous_key` in the next call, or `None` once every element
+	/// has been translated. This allows large maps to be migrated over multiple blocks. Elements
+	/// removed by `f` do not invalidate the cursor.
+	///
+	/// NOTE: If a value fails to decode because storage is corrupted, then it will log an error and
+	/// be skipped in production, or panic in development.
+	pub fn translate_next<O: Decode, F: FnMut(Key, O) -> Option<Value>>(
+		previous_key: Option<Vec<u8>>,
+		f: F,
+	) -> Option<Vec<u8>> {
+		<Self as crate::storage::IterableStorageMap<Key, Value>>::translate_next(previous_key, f)
+	}
 }
 
 impl<Prefix, Hasher, Key, Value, QueryKind, OnEmpty, MaxValues> StorageEntryMetadataBuilder
@@ -511,6 +511,16 @@ pub trait OnTimestampSet<Moment> {
 	fn on_timestamp_set(moment: Moment);
 }
 
+/// A trait which is called when the timestamp is set in a block for which the block production
+/// mechanism expects a timestamp, e.g. the start of the slot the block was authored in.
+#[cfg_attr(all(not(feature = "tuples-96"), not(feature = "tuples-128")), impl_for_tuples(64))]
+#[cfg_attr(all(feature = "tuples-96", not(feature = "tuples-128")), impl_for_tuples(96))]
+#[cfg_attr(feature = "tuples-128", impl_for_tuples(128))]
+pub trait OnTimestampDrift<Moment> {
+	/// Called with the timestamp set by the block author and the timestamp that was expected.
+	fn on_timestamp_drift(now: Moment, expected: Moment);
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -28,7 +28,7 @@ pub use sp_runtime::traits::{
 	ConstBool, ConstI128, ConstI16, ConstI32, ConstI64, ConstI8, ConstU128, ConstU16, ConstU32,
 	ConstU64, ConstU8, Get, GetDefault, TryCollect, TypedGet,
 };
-use sp_runtime::{traits::Block as BlockT, DispatchError};
+use sp_runtime::{traits::Block as BlockT, DispatchError, RuntimeDebug};
 use sp_std::{cmp::Ordering, prelude::*};
 
 #[doc(hidden)]
@@ -711,6 +711,38 @@ pub trait OnKilledAccount<AccountId> {
 	fn on_killed_account(who: &AccountId);
 }
 
+/// The provider, consumer and sufficient references a single pallet holds on an account.
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct HeldReferences {
+	/// The name of the pallet holding the references.
+	pub holder: Vec<u8>,
+	/// The number of provider references held.
+	pub providers: u32,
+	/// The number of consumer references held.
+	pub consumers: u32,
+	/// The number of sufficient references held.
+	pub sufficients: u32,
+}
+
+/// Something which holds reference counts on accounts and is able to report them.
+///
+/// Used to explain which pallets keep an account alive, or keep it from being reaped.
+pub trait ReferenceHolder<AccountId> {
+	/// The references held on `who`. Empty if none are held.
+	fn held_references(who: &AccountId) -> Vec<HeldReferences>;
+}
+
+#[cfg_attr(all(not(feature = "tuples-96"), not(feature = "tuples-128")), impl_for_tuples(64))]
+#[cfg_attr(all(feature = "tuples-96", not(feature = "tuples-128")), impl_for_tuples(96))]
+#[cfg_attr(feature = "tuples-128", impl_for_tuples(128))]
+impl<AccountId> ReferenceHolder<AccountId> for Tuple {
+	fn held_references(who: &AccountId) -> Vec<HeldReferences> {
+		let mut references = Vec::new();
+		for_tuples!( #( references.extend(Tuple::held_references(who)); )* );
+		references
+	}
+}
+
 /// A simple, generic one-parameter event notifier/handler.
 pub trait HandleLifetime<T> {
 	/// An account was created.
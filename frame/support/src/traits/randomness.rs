@@ -51,4 +51,17 @@ pub trait Randomness<Output, BlockNumber> {
 	fn random_seed() -> (Output, BlockNumber) {
 		Self::random(&[][..])
 	}
+
+	/// Get a random value for `subject` which was not determinable by chain observers before
+	/// block `not_before`, or `None` if the most recent random seed was determinable earlier.
+	///
+	/// Pass the block following the latest commitment the result is used to distinguish,
+	/// and call this again on later blocks for as long as it returns `None`.
+	fn random_at(subject: &[u8], not_before: BlockNumber) -> Option<Output>
+	where
+		BlockNumber: PartialOrd,
+	{
+		let (random, known_since) = Self::random(subject);
+		(known_since >= not_before).then_some(random)
+	}
 }
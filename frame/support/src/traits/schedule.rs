@@ -476,6 +476,34 @@ pub mod v3 {
 		/// Will return an `Unavailable` error if the `id` is invalid.
 		fn next_dispatch_time(id: TaskName) -> Result<BlockNumber, DispatchError>;
 	}
+
+	/// A scheduler that does not schedule anything, for runtimes without one.
+	impl<BlockNumber, Call, Origin> Anon<BlockNumber, Call, Origin> for () {
+		type Address = ();
+		type Hasher = sp_runtime::traits::BlakeTwo256;
+
+		fn schedule(
+			_: DispatchTime<BlockNumber>,
+			_: Option<Period<BlockNumber>>,
+			_: Priority,
+			_: Origin,
+			_: Bounded<Call, Self::Hasher>,
+		) -> Result<Self::Address, DispatchError> {
+			Err(DispatchError::Unavailable)
+		}
+		fn cancel(_: Self::Address) -> Result<(), DispatchError> {
+			Err(DispatchError::Unavailable)
+		}
+		fn reschedule(
+			_: Self::Address,
+			_: DispatchTime<BlockNumber>,
+		) -> Result<Self::Address, DispatchError> {
+			Err(DispatchError::Unavailable)
+		}
+		fn next_dispatch_time(_: Self::Address) -> Result<BlockNumber, DispatchError> {
+			Err(DispatchError::Unavailable)
+		}
+	}
 }
 
 pub use v1::*;
@@ -54,6 +54,15 @@ pub mod pallet {
 			ensure!(value != 1, Error::<T>::Revert);
 			Ok(())
 		}
+
+		#[pallet::call_index(1)]
+		#[pallet::weight(1)]
+		#[pallet::no_transaction]
+		pub fn set_value_no_transaction(_origin: OriginFor<T>, value: u32) -> DispatchResult {
+			Value::<T>::put(value);
+			ensure!(value != 1, Error::<T>::Revert);
+			Ok(())
+		}
 	}
 }
 
@@ -239,3 +248,18 @@ fn storage_layer_in_pallet_call() {
 		assert_noop!(call2.dispatch(RuntimeOrigin::signed(0)), Error::<Runtime>::Revert);
 	});
 }
+
+#[test]
+fn no_storage_layer_in_pallet_call_with_no_transaction() {
+	TestExternalities::default().execute_with(|| {
+		use sp_runtime::traits::Dispatchable;
+		let call1 = RuntimeCall::MyPallet(pallet::Call::set_value_no_transaction { value: 2 });
+		assert_ok!(call1.dispatch(RuntimeOrigin::signed(0)));
+		assert_eq!(Value::<Runtime>::get(), 2);
+
+		// The failing call is not reverted.
+		let call2 = RuntimeCall::MyPallet(pallet::Call::set_value_no_transaction { value: 1 });
+		assert!(call2.dispatch(RuntimeOrigin::signed(0)).is_err());
+		assert_eq!(Value::<Runtime>::get(), 1);
+	});
+}
@@ -23,6 +23,8 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use sp_std::vec::Vec;
+
 sp_api::decl_runtime_apis! {
 	/// The API to query account nonce.
 	pub trait AccountNonceApi<AccountId, Nonce> where
@@ -32,4 +34,22 @@ sp_api::decl_runtime_apis! {
 		/// Get current account nonce of given `AccountId`.
 		fn account_nonce(account: AccountId) -> Nonce;
 	}
+
+	/// The API to query the events of a single extrinsic.
+	pub trait ExtrinsicEventsApi<EventRecord> where
+		EventRecord: codec::Codec,
+	{
+		/// Get the events deposited by the extrinsic at `extrinsic_index` in the current block.
+		fn query_events(extrinsic_index: u32) -> Vec<EventRecord>;
+	}
+
+	/// The API to query the reference counters of an account.
+	pub trait AccountReferencesApi<AccountId, AccountReferences> where
+		AccountId: codec::Codec,
+		AccountReferences: codec::Codec,
+	{
+		/// Get the provider, consumer and sufficient references on `account`, along with the
+		/// pallets holding them.
+		fn account_references(account: AccountId) -> AccountReferences;
+	}
 }
@@ -133,8 +133,9 @@ use frame_support::{
 	pallet_prelude::Pays,
 	storage::{self, StorageStreamIter},
 	traits::{
-		ConstU32, Contains, EnsureOrigin, EnsureOriginWithArg, Get, HandleLifetime,
-		OnKilledAccount, OnNewAccount, OriginTrait, PalletInfo, SortedMembers, StoredMap, TypedGet,
+		ConstU32, Contains, EnsureOrigin, EnsureOriginWithArg, Get, HandleLifetime, HeldReferences,
+		OnKilledAccount, OnNewAccount, OriginTrait, PalletInfo, ReferenceHolder, SortedMembers,
+		StoredMap, TypedGet,
 	},
 	Parameter,
 };
@@ -279,6 +280,7 @@ pub mod pallet {
 			type AccountData = ();
 			type OnNewAccount = ();
 			type OnKilledAccount = ();
+			type ReferenceHolders = ();
 			type SystemWeightInfo = ();
 			type SS58Prefix = ();
 			type Version = ();
@@ -344,6 +346,9 @@ pub mod pallet {
 			/// What to do if an account is fully reaped from the system.
 			type OnKilledAccount = ();
 
+			/// No pallets report the references they hold on accounts.
+			type ReferenceHolders = ();
+
 			/// Weight information for the extrinsics of this pallet.
 			type SystemWeightInfo = ();
 
@@ -548,6 +553,12 @@ pub mod pallet {
 		/// All resources should be cleaned up associated with the given account.
 		type OnKilledAccount: OnKilledAccount<Self::AccountId>;
 
+		/// The pallets which report the provider, consumer and sufficient references they hold on
+		/// accounts, see [`Pallet::account_references`].
+		///
+		/// This is only used for introspection and has no influence on the reference counting.
+		type ReferenceHolders: ReferenceHolder<Self::AccountId>;
+
 		type SystemWeightInfo: WeightInfo;
 
 		/// The designated SS58 prefix of this chain.
@@ -1052,6 +1063,20 @@ pub struct AccountInfo<Nonce, AccountData> {
 	pub data: AccountData,
 }
 
+/// The reference counters of an account, along with the pallets known to be holding them.
+#[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct AccountReferences {
+	/// The total number of consumer references on the account.
+	pub consumers: RefCount,
+	/// The total number of provider references on the account.
+	pub providers: RefCount,
+	/// The total number of sufficient references on the account.
+	pub sufficients: RefCount,
+	/// The references reported by [`Config::ReferenceHolders`]. References which are not
+	/// accounted for here are held by pallets which do not report them.
+	pub holders: Vec<HeldReferences>,
+}
+
 /// Stores the `spec_version` and `spec_name` of when the last runtime upgrade
 /// happened.
 #[derive(sp_runtime::RuntimeDebug, Encode, Decode, TypeInfo)]
@@ -1571,6 +1596,20 @@ impl<T: Config> Pallet<T> {
 		Account::<T>::get(who).consumers
 	}
 
+	/// The reference counters of the account `who`, together with the references each of the
+	/// [`Config::ReferenceHolders`] holds on it.
+	///
+	/// Useful to find out what keeps an account from being reaped.
+	pub fn account_references(who: &T::AccountId) -> AccountReferences {
+		let a = Account::<T>::get(who);
+		AccountReferences {
+			consumers: a.consumers,
+			providers: a.providers,
+			sufficients: a.sufficients,
+			holders: T::ReferenceHolders::held_references(who),
+		}
+	}
+
 	/// True if the account has some outstanding consumer references.
 	pub fn is_provider_required(who: &T::AccountId) -> bool {
 		Account::<T>::get(who).consumers != 0
@@ -1819,6 +1858,19 @@ impl<T: Config> Pallet<T> {
 		Events::<T>::stream_iter()
 	}
 
+	/// Get the events deposited while applying the extrinsic at `extrinsic_index`.
+	///
+	/// Should only be called if you know what you are doing and outside of the runtime block
+	/// execution else it can have a large impact on the PoV size of a block.
+	pub fn extrinsic_events_no_consensus(
+		extrinsic_index: u32,
+	) -> Vec<EventRecord<T::RuntimeEvent, T::Hash>> {
+		Self::read_events_no_consensus()
+			.filter(|e| matches!(e.phase, Phase::ApplyExtrinsic(i) if i == extrinsic_index))
+			.map(|e| *e)
+			.collect()
+	}
+
 	/// Read and return the events of a specific pallet, as denoted by `E`.
 	///
 	/// This is useful for a pallet that wishes to read only the events it has deposited into
@@ -269,6 +269,15 @@ impl BlockWeights {
 				reserved,
 				base_for_class,
 			);
+			// Make sure reserved is within max_total if both are set.
+			error_assert!(
+				weights.reserved.map_or(true, |reserved| reserved.all_lte(max_for_class)),
+				&mut error,
+				"[{:?}] {:?} (reserved) can't be greater than {:?} (max for class)",
+				class,
+				weights.reserved,
+				max_for_class,
+			);
 			// Make sure max block is greater than max_total if it's set.
 			error_assert!(
 				self.max_block.all_gte(weights.max_total.unwrap_or(Weight::zero())),
@@ -296,6 +305,25 @@ impl BlockWeights {
 		}
 	}
 
+	/// Verifies that the weight consumed in a block respects the `max_total` of every
+	/// non-mandatory class.
+	///
+	/// The `reserved` allowance is not checked here: whether a class may use it depends on the
+	/// total weight of the block at the time each extrinsic is applied, which `CheckWeight`
+	/// verifies. `Mandatory` weight is never limited, since it includes the weight of hooks like
+	/// `on_initialize`.
+	///
+	/// Returns the first class which is over its limit.
+	pub fn check_consumed(&self, consumed: &PerDispatchClass<Weight>) -> Result<(), DispatchClass> {
+		for class in DispatchClass::non_mandatory() {
+			let max_total = self.per_class.get(*class).max_total;
+			if max_total.map_or(false, |max| consumed.get(*class).any_gt(max)) {
+				return Err(*class)
+			}
+		}
+		Ok(())
+	}
+
 	/// Create new weights definition, with both `Normal` and `Operational`
 	/// classes limited to given weight.
 	///
@@ -447,4 +475,77 @@ mod tests {
 	fn default_weights_are_valid() {
 		BlockWeights::default().validate().unwrap();
 	}
+
+	#[test]
+	fn reserved_must_be_within_max_total() {
+		let builder = |reserved| {
+			BlockWeights::builder()
+				.base_block(Weight::zero())
+				.for_class(DispatchClass::all(), |weights| {
+					weights.base_extrinsic = Weight::zero();
+					weights.max_total = Some(Weight::from_parts(100, 100));
+				})
+				.for_class(DispatchClass::Operational, |weights| weights.reserved = Some(reserved))
+		};
+		assert!(builder(Weight::from_parts(100, 100)).build().is_ok());
+		assert!(builder(Weight::from_parts(101, 100)).build().is_err());
+	}
+
+	#[test]
+	fn check_consumed_respects_max_total() {
+		let weights = BlockWeights::builder()
+			.base_block(Weight::zero())
+			.for_class(DispatchClass::all(), |weights| weights.base_extrinsic = Weight::zero())
+			.for_class(DispatchClass::Normal, |weights| {
+				weights.max_total = Some(Weight::from_parts(75, 100));
+			})
+			.for_class(DispatchClass::Operational, |weights| {
+				weights.max_total = Some(Weight::from_parts(100, 100));
+				weights.reserved = Some(Weight::from_parts(25, 100));
+			})
+			.build_or_panic();
+		let consumed = |normal: u64, operational: u64, mandatory: u64| {
+			PerDispatchClass::new(|class| match class {
+				DispatchClass::Normal => Weight::from_parts(normal, 0),
+				DispatchClass::Operational => Weight::from_parts(operational, 0),
+				DispatchClass::Mandatory => Weight::from_parts(mandatory, 0),
+			})
+		};
+
+		assert_eq!(weights.check_consumed(&consumed(75, 25, 0)), Ok(()));
+		// `Mandatory` weight is never limited.
+		assert_eq!(weights.check_consumed(&consumed(0, 0, 1000)), Ok(()));
+		assert_eq!(weights.check_consumed(&consumed(76, 0, 0)), Err(DispatchClass::Normal));
+		assert_eq!(weights.check_consumed(&consumed(0, 101, 0)), Err(DispatchClass::Operational));
+		// The reserved space is not checked once the block is full, `CheckWeight` has to decide
+		// when each extrinsic is applied whether it may use it.
+		assert_eq!(weights.check_consumed(&consumed(1, 0, 100)), Ok(()));
+		assert_eq!(weights.check_consumed(&consumed(0, 26, 100)), Ok(()));
+	}
+
+	#[test]
+	fn check_consumed_accepts_operational_reserve_after_normal_fills_up() {
+		let weights = BlockWeights::builder()
+			.base_block(Weight::zero())
+			.for_class(DispatchClass::all(), |weights| weights.base_extrinsic = Weight::zero())
+			.for_class(DispatchClass::Normal, |weights| {
+				weights.max_total = Some(Weight::from_parts(75, 100));
+			})
+			.for_class(DispatchClass::Operational, |weights| {
+				weights.max_total = Some(Weight::from_parts(100, 100));
+				weights.reserved = Some(Weight::from_parts(25, 100));
+			})
+			.build_or_panic();
+		let mut consumed = PerDispatchClass::<Weight>::default();
+
+		// `on_initialize` and `Normal` extrinsics fill the block up to `max_total` of `Normal`.
+		consumed.accrue(Weight::from_parts(10, 0), DispatchClass::Mandatory);
+		consumed.accrue(Weight::from_parts(75, 0), DispatchClass::Normal);
+		assert!(!consumed.total().any_gt(weights.max_block));
+		// An `Operational` extrinsic then uses its reserved space, going over `max_block`.
+		consumed.accrue(Weight::from_parts(25, 0), DispatchClass::Operational);
+		assert!(consumed.total().any_gt(weights.max_block));
+
+		assert_eq!(weights.check_consumed(&consumed), Ok(()));
+	}
 }
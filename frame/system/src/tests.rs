@@ -284,6 +284,44 @@ fn deposit_event_should_work() {
 	});
 }
 
+#[test]
+fn extrinsic_events_are_filtered_by_index() {
+	new_test_ext().execute_with(|| {
+		System::reset_events();
+		System::initialize(&1, &[0u8; 32].into(), &Default::default());
+		System::deposit_event(SysEvent::NewAccount { account: 1 });
+		System::note_finished_initialize();
+		System::deposit_event(SysEvent::NewAccount { account: 2 });
+		System::note_applied_extrinsic(&Ok(().into()), Default::default());
+		System::deposit_event(SysEvent::KilledAccount { account: 2 });
+		System::note_applied_extrinsic(&Ok(().into()), Default::default());
+		System::note_finished_extrinsics();
+		System::finalize();
+
+		let events = |index| {
+			System::extrinsic_events_no_consensus(index)
+				.into_iter()
+				.map(|e| e.event)
+				.collect::<Vec<_>>()
+		};
+		let success = |event: &RuntimeEvent| {
+			matches!(event, RuntimeEvent::System(SysEvent::ExtrinsicSuccess { .. }))
+		};
+
+		let first = events(0);
+		assert_eq!(first.len(), 2);
+		assert_eq!(first[0], SysEvent::NewAccount { account: 2 }.into());
+		assert!(success(&first[1]));
+
+		let second = events(1);
+		assert_eq!(second.len(), 2);
+		assert_eq!(second[0], SysEvent::KilledAccount { account: 2 }.into());
+		assert!(success(&second[1]));
+
+		assert!(events(2).is_empty());
+	});
+}
+
 #[test]
 fn deposit_event_uses_actual_weight_and_pays_fee() {
 	new_test_ext().execute_with(|| {
@@ -109,12 +109,20 @@
 //! The [`Config::OnTimestampSet`] configuration trait can be set to another pallet we want to
 //! notify that the timestamp has been updated, as long as it implements [`OnTimestampSet`].
 //! Examples are the Babe and Aura pallets.
+//!
+//! If the block production mechanism knows which timestamp to expect for the current block, e.g.
+//! the start of the current slot, it can be provided through [`Config::ExpectedTimestamp`]. The
+//! runtime can then bound how far the timestamp may drift from it with
+//! [`Config::MaxTimestampDrift`], which is checked both by [`ProvideInherent::check_inherent`] when
+//! importing a block and when executing it. The drift of every timestamp is reported to
+//! [`Config::OnTimestampDrift`], e.g. so that a price oracle can discount data submitted in blocks
+//! of proposers with suspicious clocks.
 //! This pallet also implements [`Time`] and [`UnixTime`] so it can be used to configure other
 //! pallets that require these types (e.g. in Staking pallet).
 //!
 //! ## Panics
 //!
-//! There are 3 cases where this pallet could cause the runtime to panic.
+//! There are 4 cases where this pallet could cause the runtime to panic.
 //!
 //! 1. If no timestamp is set at the end of a block.
 //!
@@ -123,6 +131,10 @@
 //!
 //! 3. If a timestamp is set before the [`Config::MinimumPeriod`] is elapsed:
 #![doc = docify::embed!("src/tests.rs", block_period_minimum_enforced)]
+//!
+//! 4. If a timestamp drifts from the [`Config::ExpectedTimestamp`] by more than the
+//! [`Config::MaxTimestampDrift`]:
+#![doc = docify::embed!("src/tests.rs", maximum_drift_enforced)]
 #![deny(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -133,12 +145,40 @@ mod mock;
 mod tests;
 pub mod weights;
 
-use frame_support::traits::{OnTimestampSet, Time, UnixTime};
+use frame_support::traits::{OnTimestampDrift, OnTimestampSet, Time, UnixTime};
+use sp_inherents::InherentData;
 use sp_runtime::traits::{AtLeast32Bit, SaturatedConversion, Scale, Zero};
 use sp_std::{cmp, result};
 use sp_timestamp::{InherentError, InherentType, INHERENT_IDENTIFIER};
 pub use weights::WeightInfo;
 
+/// Provides the timestamp the block production mechanism expects for a block, e.g. the start of
+/// the slot the block was authored in.
+pub trait ProvideExpectedTimestamp<Moment> {
+	/// The expected timestamp of the block being executed, if any.
+	///
+	/// This is read when the timestamp is set, so it must already be up to date for the current
+	/// block at that point.
+	fn expected_timestamp() -> Option<Moment>;
+
+	/// The expected timestamp of the block whose inherents are checked against the given inherent
+	/// data, if any.
+	///
+	/// Inherents are checked on top of the state of the parent block, so this must not rely on
+	/// storage written while executing the block itself.
+	fn expected_timestamp_from_inherent_data(data: &InherentData) -> Option<Moment>;
+}
+
+impl<Moment> ProvideExpectedTimestamp<Moment> for () {
+	fn expected_timestamp() -> Option<Moment> {
+		None
+	}
+
+	fn expected_timestamp_from_inherent_data(_: &InherentData) -> Option<Moment> {
+		None
+	}
+}
+
 pub use pallet::*;
 
 #[frame_support::pallet]
@@ -162,6 +202,9 @@ pub mod pallet {
 			type Moment = u64;
 			type OnTimestampSet = ();
 			type MinimumPeriod = frame_support::traits::ConstU64<1>;
+			type ExpectedTimestamp = ();
+			type MaxTimestampDrift = ();
+			type OnTimestampDrift = ();
 			type WeightInfo = ();
 		}
 	}
@@ -193,6 +236,24 @@ pub mod pallet {
 		#[pallet::constant]
 		type MinimumPeriod: Get<Self::Moment>;
 
+		/// The timestamp the block production mechanism expects for the current block, if any.
+		///
+		/// For example, the Aura and Babe pallets provide the start of the slot of the block.
+		type ExpectedTimestamp: ProvideExpectedTimestamp<Self::Moment>;
+
+		/// The maximum difference between the timestamp and [`Config::ExpectedTimestamp`].
+		///
+		/// Blocks whose timestamp drifts further from the expected timestamp fail the inherent
+		/// check and can't be executed. Set this to `None` to allow any drift.
+		#[pallet::constant]
+		type MaxTimestampDrift: Get<Option<Self::Moment>>;
+
+		/// Something which can be notified of the drift of the timestamp from
+		/// [`Config::ExpectedTimestamp`].
+		///
+		/// This can be set to `()` if it is not needed.
+		type OnTimestampDrift: OnTimestampDrift<Self::Moment>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -240,7 +301,8 @@ pub mod pallet {
 		/// phase, if this call hasn't been invoked by that time.
 		///
 		/// The timestamp should be greater than the previous one by the amount specified by
-		/// [`Config::MinimumPeriod`].
+		/// [`Config::MinimumPeriod`], and must not drift from [`Config::ExpectedTimestamp`] by more
+		/// than [`Config::MaxTimestampDrift`].
 		///
 		/// The dispatch origin for this call must be _None_.
 		///
@@ -249,9 +311,11 @@ pub mod pallet {
 		/// block to execute any other calls.
 		///
 		/// ## Complexity
-		/// - `O(1)` (Note that implementations of `OnTimestampSet` must also be `O(1)`)
+		/// - `O(1)` (Note that implementations of `OnTimestampSet`, `ExpectedTimestamp` and
+		///   `OnTimestampDrift` must also be `O(1)`)
 		/// - 1 storage read and 1 storage mutation (codec `O(1)` because of `DidUpdate::take` in
 		///   `on_finalize`)
+		/// - 1 event handler `on_timestamp_drift`. Must be `O(1)`.
 		/// - 1 event handler `on_timestamp_set`. Must be `O(1)`.
 		#[pallet::call_index(0)]
 		#[pallet::weight((
@@ -266,6 +330,13 @@ pub mod pallet {
 				prev.is_zero() || now >= prev + T::MinimumPeriod::get(),
 				"Timestamp must increment by at least <MinimumPeriod> between sequential blocks"
 			);
+			if let Some(expected) = T::ExpectedTimestamp::expected_timestamp() {
+				assert!(
+					Self::is_within_max_drift(now, expected),
+					"Timestamp must be within <MaxTimestampDrift> of the expected timestamp"
+				);
+				<T::OnTimestampDrift as OnTimestampDrift<_>>::on_timestamp_drift(now, expected);
+			}
 			Now::<T>::put(now);
 			DidUpdate::<T>::put(true);
 
@@ -283,6 +354,8 @@ pub mod pallet {
 	/// - [`InherentError::TooFarInFuture`]: If the timestamp is larger than the current timestamp +
 	///   minimum drift period.
 	/// - [`InherentError::TooEarly`]: If the timestamp is less than the current + minimum period.
+	/// - [`InherentError::TooFarFromExpected`]: If the timestamp drifts from the expected timestamp
+	///   by more than [`Config::MaxTimestampDrift`].
 	#[pallet::inherent]
 	impl<T: Config> ProvideInherent for Pallet<T> {
 		type Call = Call<T>;
@@ -307,10 +380,12 @@ pub mod pallet {
 			const MAX_TIMESTAMP_DRIFT_MILLIS: sp_timestamp::Timestamp =
 				sp_timestamp::Timestamp::new(30 * 1000);
 
-			let t: u64 = match call {
-				Call::set { ref now } => (*now).saturated_into::<u64>(),
+			let now = match call {
+				Call::set { ref now } => *now,
 				_ => return Ok(()),
 			};
+			let t: u64 = now.saturated_into::<u64>();
+			let expected = T::ExpectedTimestamp::expected_timestamp_from_inherent_data(data);
 
 			let data = data
 				.get_data::<InherentType>(&INHERENT_IDENTIFIER)
@@ -322,6 +397,8 @@ pub mod pallet {
 				Err(InherentError::TooFarInFuture)
 			} else if t < minimum {
 				Err(InherentError::TooEarly)
+			} else if expected.map_or(false, |expected| !Self::is_within_max_drift(now, expected)) {
+				Err(InherentError::TooFarFromExpected)
 			} else {
 				Ok(())
 			}
@@ -342,6 +419,12 @@ impl<T: Config> Pallet<T> {
 		Self::now()
 	}
 
+	/// Whether the timestamp `now` is within [`Config::MaxTimestampDrift`] of `expected`.
+	fn is_within_max_drift(now: T::Moment, expected: T::Moment) -> bool {
+		let drift = if now > expected { now - expected } else { expected - now };
+		T::MaxTimestampDrift::get().map_or(true, |max_drift| drift <= max_drift)
+	}
+
 	/// Set the timestamp to something in particular. Only used for tests.
 	#[cfg(any(feature = "runtime-benchmarks", feature = "std"))]
 	pub fn set_timestamp(now: T::Moment) {
@@ -42,6 +42,9 @@ impl frame_system::Config for Test {
 
 parameter_types! {
 	pub static CapturedMoment: Option<Moment> = None;
+	pub static ExpectedTimestamp: Option<Moment> = None;
+	pub static MaxTimestampDrift: Option<Moment> = None;
+	pub static CapturedDrift: Option<(Moment, Moment)> = None;
 }
 
 pub struct MockOnTimestampSet;
@@ -51,10 +54,31 @@ impl OnTimestampSet<Moment> for MockOnTimestampSet {
 	}
 }
 
+pub struct MockExpectedTimestamp;
+impl ProvideExpectedTimestamp<Moment> for MockExpectedTimestamp {
+	fn expected_timestamp() -> Option<Moment> {
+		ExpectedTimestamp::get()
+	}
+
+	fn expected_timestamp_from_inherent_data(_: &InherentData) -> Option<Moment> {
+		ExpectedTimestamp::get()
+	}
+}
+
+pub struct MockOnTimestampDrift;
+impl OnTimestampDrift<Moment> for MockOnTimestampDrift {
+	fn on_timestamp_drift(now: Moment, expected: Moment) {
+		CapturedDrift::mutate(|x| *x = Some((now, expected)));
+	}
+}
+
 impl Config for Test {
 	type Moment = Moment;
 	type OnTimestampSet = MockOnTimestampSet;
 	type MinimumPeriod = ConstU64<5>;
+	type ExpectedTimestamp = MockExpectedTimestamp;
+	type MaxTimestampDrift = MaxTimestampDrift;
+	type OnTimestampDrift = MockOnTimestampDrift;
 	type WeightInfo = ();
 }
 
@@ -69,5 +93,8 @@ pub(crate) fn get_captured_moment() -> Option<Moment> {
 pub(crate) fn new_test_ext() -> TestExternalities {
 	let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
 	clear_captured_moment();
+	ExpectedTimestamp::take();
+	MaxTimestampDrift::take();
+	CapturedDrift::take();
 	TestExternalities::new(t)
 }
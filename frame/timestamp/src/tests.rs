@@ -17,8 +17,10 @@
 
 //! Tests for the Timestamp module.
 
-use crate::mock::*;
-use frame_support::assert_ok;
+use crate::{mock::*, Call};
+use frame_support::{assert_ok, inherent::ProvideInherent};
+use sp_inherents::InherentData;
+use sp_timestamp::{InherentError, INHERENT_IDENTIFIER};
 
 #[test]
 fn timestamp_works() {
@@ -51,3 +53,72 @@ fn block_period_minimum_enforced() {
 		let _ = Timestamp::set(RuntimeOrigin::none(), 46);
 	});
 }
+
+#[test]
+fn drift_is_reported() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 69));
+		assert_eq!(CapturedDrift::get(), None);
+	});
+
+	new_test_ext().execute_with(|| {
+		ExpectedTimestamp::set(Some(60));
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 69));
+		assert_eq!(CapturedDrift::get(), Some((69, 60)));
+		assert_eq!(Some(69), get_captured_moment());
+	});
+}
+
+#[test]
+fn drift_within_maximum_is_allowed() {
+	new_test_ext().execute_with(|| {
+		ExpectedTimestamp::set(Some(60));
+		MaxTimestampDrift::set(Some(10));
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 50));
+		assert_eq!(CapturedDrift::get(), Some((50, 60)));
+	});
+
+	new_test_ext().execute_with(|| {
+		ExpectedTimestamp::set(Some(60));
+		MaxTimestampDrift::set(Some(10));
+		assert_ok!(Timestamp::set(RuntimeOrigin::none(), 70));
+		assert_eq!(CapturedDrift::get(), Some((70, 60)));
+	});
+}
+
+#[docify::export]
+#[test]
+#[should_panic(expected = "Timestamp must be within <MaxTimestampDrift> of the expected timestamp")]
+fn maximum_drift_enforced() {
+	new_test_ext().execute_with(|| {
+		ExpectedTimestamp::set(Some(60));
+		MaxTimestampDrift::set(Some(10));
+		let _ = Timestamp::set(RuntimeOrigin::none(), 71);
+	});
+}
+
+#[test]
+fn check_inherent_enforces_maximum_drift() {
+	new_test_ext().execute_with(|| {
+		let mut data = InherentData::new();
+		data.put_data(INHERENT_IDENTIFIER, &100u64).unwrap();
+		ExpectedTimestamp::set(Some(60));
+
+		assert_ok!(Timestamp::check_inherent(&Call::set { now: 71 }, &data));
+
+		MaxTimestampDrift::set(Some(10));
+		assert_ok!(Timestamp::check_inherent(&Call::set { now: 50 }, &data));
+		assert_ok!(Timestamp::check_inherent(&Call::set { now: 70 }, &data));
+		assert!(matches!(
+			Timestamp::check_inherent(&Call::set { now: 71 }, &data),
+			Err(InherentError::TooFarFromExpected)
+		));
+		assert!(matches!(
+			Timestamp::check_inherent(&Call::set { now: 49 }, &data),
+			Err(InherentError::TooFarFromExpected)
+		));
+
+		ExpectedTimestamp::set(None);
+		assert_ok!(Timestamp::check_inherent(&Call::set { now: 71 }, &data));
+	});
+}
@@ -0,0 +1,159 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Token bridge pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use crate::Pallet as TokenBridge;
+use frame_benchmarking::{
+	impl_benchmark_test_suite,
+	v1::{account, whitelisted_caller, BenchmarkError},
+	v2::*,
+};
+use frame_support::traits::{EnsureOrigin, Get};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+// Create a native asset which can be bridged, returning it with the funded amount.
+fn setup_asset<T: Config>(funded: &T::AccountId) -> (T::AssetId, T::Balance) {
+	let asset = T::BenchmarkHelper::create_asset();
+	let amount = T::Assets::minimum_balance(asset.clone()).saturating_mul(100u32.into());
+	T::Assets::mint_into(asset.clone(), funded, amount.saturating_mul(2u32.into()))
+		.expect("the asset exists; qed");
+	BridgedAssets::<T>::insert(
+		&asset,
+		AssetConfig { kind: AssetKind::Native, max_transfer: T::Balance::max_value() },
+	);
+	(asset, amount)
+}
+
+fn relayers<T: Config>(n: u32) -> BoundedVec<T::AccountId, T::MaxRelayers> {
+	let relayers: Vec<T::AccountId> = (0..n).map(|i| account("relayer", i, 0)).collect();
+	BoundedVec::truncate_from(relayers)
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn transfer_out() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset, amount) = setup_asset::<T>(&caller);
+		let recipient = BoundedVec::truncate_from(vec![0; T::MaxRecipientLength::get() as usize]);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), asset.clone(), amount, recipient);
+
+		assert_eq!(T::Assets::balance(asset, &TokenBridge::<T>::account_id()), amount);
+		assert_eq!(OutboundNonce::<T>::get(), 1);
+	}
+
+	// The last vote needed by a full relayer set, which executes the transfer.
+	#[benchmark]
+	fn vote_transfer_in() {
+		let relayers = relayers::<T>(T::MaxRelayers::get());
+		let threshold = relayers.len() as u32;
+		Relayers::<T>::put(&relayers);
+		Threshold::<T>::put(threshold);
+
+		let (asset, amount) = setup_asset::<T>(&TokenBridge::<T>::account_id());
+		let beneficiary: T::AccountId = account("beneficiary", 0, 0);
+		let transfer =
+			InboundTransfer { asset: asset.clone(), beneficiary: beneficiary.clone(), amount };
+		let votes = relayers[1..]
+			.iter()
+			.map(|relayer| (relayer.clone(), transfer.clone()))
+			.collect();
+		InboundVotes::<T>::insert(0, BoundedVec::truncate_from(votes));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(relayers[0].clone()), 0, transfer);
+
+		assert!(InboundProgress::<T>::get().is_finished(0));
+		assert_eq!(T::Assets::balance(asset, &beneficiary), amount);
+	}
+
+	#[benchmark]
+	fn set_relayers(n: Linear<1, { T::MaxRelayers::get() }>) -> Result<(), BenchmarkError> {
+		let relayers = relayers::<T>(n);
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, relayers.clone(), n);
+
+		assert_eq!(Relayers::<T>::get(), relayers);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_asset_config() -> Result<(), BenchmarkError> {
+		let asset = T::BenchmarkHelper::create_asset();
+		let config = AssetConfig { kind: AssetKind::Wrapped, max_transfer: 100u32.into() };
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, asset.clone(), Some(config));
+
+		assert_eq!(BridgedAssets::<T>::get(asset), Some(config));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_paused() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, true);
+
+		assert!(Paused::<T>::get());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_transfer_in() -> Result<(), BenchmarkError> {
+		let relayers = relayers::<T>(T::MaxRelayers::get());
+		let transfer = InboundTransfer {
+			asset: T::BenchmarkHelper::create_asset(),
+			beneficiary: account("beneficiary", 0, 0),
+			amount: 100u32.into(),
+		};
+		let votes = relayers.iter().map(|relayer| (relayer.clone(), transfer.clone())).collect();
+		InboundVotes::<T>::insert(0, BoundedVec::truncate_from(votes));
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, 0);
+
+		assert!(InboundProgress::<T>::get().is_finished(0));
+		assert!(!InboundVotes::<T>::contains_key(0));
+
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(TokenBridge, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,508 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Token Bridge Pallet
+//!
+//! A lock-and-mint bridge for fungible assets, operated by a set of relayers.
+//!
+//! ## Overview
+//!
+//! Every bridged asset is registered by [`Config::AdminOrigin`] with an [`AssetConfig`], which
+//! tells on which side of the bridge the asset originates:
+//!
+//! - [`AssetKind::Native`] assets are locked in the bridge account when they leave this chain and
+//!   unlocked when they come back.
+//! - [`AssetKind::Wrapped`] assets represent assets of the remote chain. They are burned when they
+//!   leave this chain and minted when they come back.
+//!
+//! Outbound transfers are started by users with [`Pallet::transfer_out`], which emits an
+//! [`Event::TransferOut`] for the relayers to pick up. Inbound transfers are proposed by the
+//! relayers with [`Pallet::vote_transfer_in`], and executed as soon as `Threshold` of the current
+//! relayers voted for the same transfer.
+//!
+//! The amount of a single transfer is capped per asset, and the whole bridge can be paused by
+//! [`Config::AdminOrigin`].
+//!
+//! Only inbound transfers with a nonce less than [`INBOUND_WINDOW`] above the lowest unfinished
+//! one are accepted, so that finished transfers can be tracked in a bounded [`InboundProgress`].
+//! An inbound transfer which can't be executed holds back this window until
+//! [`Config::AdminOrigin`] cancels it with [`Pallet::cancel_transfer_in`].
+//!
+//! ## Assets
+//!
+//! The bridge account needs to be able to hold native assets without holding any other funds,
+//! so these should be sufficient assets.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::{
+		fungibles::{Inspect, Mutate},
+		tokens::{Fortitude, Precision, Preservation},
+		Get,
+	},
+	BoundedVec, PalletId,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating},
+	RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+/// On which side of the bridge an asset originates.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum AssetKind {
+	/// The asset originates on this chain. It is locked in the bridge account while bridged.
+	Native,
+	/// The asset originates on the remote chain. It is minted while bridged.
+	Wrapped,
+}
+
+/// How an asset is bridged.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AssetConfig<Balance> {
+	/// On which side of the bridge the asset originates.
+	pub kind: AssetKind,
+	/// The largest amount which can be bridged by a single transfer, in either direction.
+	pub max_transfer: Balance,
+}
+
+/// A transfer from the remote chain, as reported by a relayer.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct InboundTransfer<AssetId, AccountId, Balance> {
+	/// The transferred asset.
+	pub asset: AssetId,
+	/// The account receiving the asset on this chain.
+	pub beneficiary: AccountId,
+	/// The transferred amount.
+	pub amount: Balance,
+}
+
+/// The number of inbound nonces, starting at the lowest unfinished one, which can be voted for.
+pub const INBOUND_WINDOW: u64 = u128::BITS as u64;
+
+/// Which inbound transfers are finished, i.e. executed or cancelled.
+#[derive(
+	Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct InboundWindow {
+	/// All transfers with a lower nonce are finished.
+	pub lowest_unfinished: u64,
+	/// Bit `i` is set if the transfer with nonce `lowest_unfinished + i` is finished.
+	pub finished: u128,
+}
+
+impl InboundWindow {
+	/// Whether the transfer with the given nonce is finished.
+	pub fn is_finished(&self, nonce: u64) -> bool {
+		match nonce.checked_sub(self.lowest_unfinished) {
+			None => true,
+			Some(offset) => offset < INBOUND_WINDOW && self.finished & (1 << offset) != 0,
+		}
+	}
+
+	/// Whether the transfer with the given nonce is within the window of nonces which can be voted
+	/// for.
+	pub fn in_window(&self, nonce: u64) -> bool {
+		nonce.saturating_sub(self.lowest_unfinished) < INBOUND_WINDOW
+	}
+
+	/// Mark the transfer with the given nonce as finished. Nonces outside the window are ignored.
+	fn finish(&mut self, nonce: u64) {
+		let Some(offset) = nonce.checked_sub(self.lowest_unfinished) else { return };
+		if offset >= INBOUND_WINDOW {
+			return
+		}
+		self.finished |= 1 << offset;
+		// slide the window past all finished transfers at its start.
+		let done = self.finished.trailing_ones();
+		self.lowest_unfinished.saturating_accrue(done.into());
+		self.finished = self.finished.checked_shr(done).unwrap_or_default();
+	}
+}
+
+/// The [`InboundTransfer`] type of a runtime.
+pub type InboundTransferOf<T> = InboundTransfer<
+	<T as Config>::AssetId,
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::Balance,
+>;
+
+/// Helper for the benchmarks, which are generic over the asset registry.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AssetId> {
+	/// Create a new asset which can be held by any account, including the bridge account.
+	fn create_asset() -> AssetId;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The identifier of a bridged asset.
+		type AssetId: frame_support::traits::tokens::AssetId;
+
+		/// The balance of a bridged asset.
+		type Balance: frame_support::traits::tokens::Balance;
+
+		/// The registry of the bridged assets.
+		type Assets: Inspect<Self::AccountId, AssetId = Self::AssetId, Balance = Self::Balance>
+			+ Mutate<Self::AccountId>;
+
+		/// The origin which manages relayers and assets, and which may pause the bridge.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The bridge's pallet id, used for deriving the account holding locked assets.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The maximum number of relayers.
+		#[pallet::constant]
+		type MaxRelayers: Get<u32>;
+
+		/// The maximum length of the encoded address of a recipient on the remote chain.
+		#[pallet::constant]
+		type MaxRecipientLength: Get<u32>;
+
+		/// A set of helper functions for benchmarking.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AssetId>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The accounts which may vote for inbound transfers.
+	#[pallet::storage]
+	pub type Relayers<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxRelayers>, ValueQuery>;
+
+	/// The number of relayer votes needed to execute an inbound transfer.
+	#[pallet::storage]
+	pub type Threshold<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The assets which can be bridged.
+	#[pallet::storage]
+	pub type BridgedAssets<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, AssetConfig<T::Balance>, OptionQuery>;
+
+	/// Whether the bridge is paused, in which case no transfer happens in either direction.
+	#[pallet::storage]
+	pub type Paused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The nonce of the next outbound transfer.
+	#[pallet::storage]
+	pub type OutboundNonce<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// The votes of the relayers for the pending inbound transfer with a given nonce.
+	#[pallet::storage]
+	pub type InboundVotes<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u64,
+		BoundedVec<(T::AccountId, InboundTransferOf<T>), T::MaxRelayers>,
+		ValueQuery,
+	>;
+
+	/// Which inbound transfers have been executed or cancelled.
+	#[pallet::storage]
+	pub type InboundProgress<T: Config> = StorageValue<_, InboundWindow, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An asset is leaving this chain, to be delivered to `recipient` by the relayers.
+		TransferOut {
+			nonce: u64,
+			asset: T::AssetId,
+			from: T::AccountId,
+			recipient: BoundedVec<u8, T::MaxRecipientLength>,
+			amount: T::Balance,
+		},
+		/// A relayer voted for an inbound transfer.
+		TransferInVoted { nonce: u64, relayer: T::AccountId },
+		/// An inbound transfer has been executed.
+		TransferIn { nonce: u64, asset: T::AssetId, beneficiary: T::AccountId, amount: T::Balance },
+		/// An inbound transfer has been cancelled and won't be executed.
+		TransferInCancelled { nonce: u64 },
+		/// The relayer set has been replaced.
+		RelayersSet { relayers: BoundedVec<T::AccountId, T::MaxRelayers>, threshold: u32 },
+		/// The configuration of an asset has been set, or removed if `None`.
+		AssetConfigSet { asset: T::AssetId, config: Option<AssetConfig<T::Balance>> },
+		/// The bridge has been paused.
+		Paused,
+		/// The bridge has been resumed.
+		Resumed,
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The bridge is paused.
+		BridgePaused,
+		/// The asset can't be bridged.
+		UnknownAsset,
+		/// The amount exceeds the transfer limit of the asset.
+		TransferTooLarge,
+		/// The origin is not a relayer.
+		NotRelayer,
+		/// The relayer already voted for this transfer.
+		AlreadyVoted,
+		/// The transfer has already been executed or cancelled.
+		AlreadyExecuted,
+		/// The nonce is too far ahead of the lowest unfinished inbound transfer.
+		NonceOutOfWindow,
+		/// The threshold is zero or larger than the number of relayers.
+		InvalidThreshold,
+		/// The relayer set contains an account twice.
+		DuplicateRelayer,
+		/// There are more votes than relayers.
+		TooManyVotes,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Send `amount` of `asset` to `recipient` on the remote chain.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Native assets are locked in the bridge account and wrapped assets are burned.
+		///
+		/// Emits `TransferOut`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::transfer_out())]
+		pub fn transfer_out(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			amount: T::Balance,
+			recipient: BoundedVec<u8, T::MaxRecipientLength>,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			ensure!(!Paused::<T>::get(), Error::<T>::BridgePaused);
+			let config = BridgedAssets::<T>::get(&asset).ok_or(Error::<T>::UnknownAsset)?;
+			ensure!(amount <= config.max_transfer, Error::<T>::TransferTooLarge);
+
+			match config.kind {
+				AssetKind::Native => T::Assets::transfer(
+					asset.clone(),
+					&from,
+					&Self::account_id(),
+					amount,
+					Preservation::Expendable,
+				)?,
+				AssetKind::Wrapped => T::Assets::burn_from(
+					asset.clone(),
+					&from,
+					amount,
+					Precision::Exact,
+					Fortitude::Polite,
+				)?,
+			};
+
+			let nonce = OutboundNonce::<T>::mutate(|next| {
+				let nonce = *next;
+				next.saturating_inc();
+				nonce
+			});
+			Self::deposit_event(Event::TransferOut { nonce, asset, from, recipient, amount });
+			Ok(())
+		}
+
+		/// Vote for the inbound transfer `nonce` of the remote chain.
+		///
+		/// The dispatch origin for this call must be _Signed_ by a relayer.
+		///
+		/// The transfer is executed once `Threshold` of the current relayers voted for the same
+		/// `transfer`. Native assets are unlocked from the bridge account and wrapped assets are
+		/// minted. If that fails, the vote is reverted, so that it can be retried later or the
+		/// transfer cancelled with [`Pallet::cancel_transfer_in`].
+		///
+		/// Emits `TransferInVoted`, and `TransferIn` if the transfer is executed.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::vote_transfer_in())]
+		pub fn vote_transfer_in(
+			origin: OriginFor<T>,
+			nonce: u64,
+			transfer: InboundTransferOf<T>,
+		) -> DispatchResult {
+			let relayer = ensure_signed(origin)?;
+			let relayers = Relayers::<T>::get();
+			ensure!(relayers.contains(&relayer), Error::<T>::NotRelayer);
+			ensure!(!Paused::<T>::get(), Error::<T>::BridgePaused);
+			let progress = InboundProgress::<T>::get();
+			ensure!(!progress.is_finished(nonce), Error::<T>::AlreadyExecuted);
+			ensure!(progress.in_window(nonce), Error::<T>::NonceOutOfWindow);
+			let config =
+				BridgedAssets::<T>::get(&transfer.asset).ok_or(Error::<T>::UnknownAsset)?;
+			ensure!(transfer.amount <= config.max_transfer, Error::<T>::TransferTooLarge);
+
+			let mut votes = InboundVotes::<T>::get(nonce);
+			// only the votes of the current relayers count.
+			votes.retain(|(voter, _)| relayers.contains(voter));
+			ensure!(votes.iter().all(|(voter, _)| *voter != relayer), Error::<T>::AlreadyVoted);
+			votes
+				.try_push((relayer.clone(), transfer.clone()))
+				.map_err(|_| Error::<T>::TooManyVotes)?;
+			Self::deposit_event(Event::TransferInVoted { nonce, relayer });
+
+			let approvals = votes.iter().filter(|(_, voted)| *voted == transfer).count() as u32;
+			if approvals >= Threshold::<T>::get() {
+				InboundVotes::<T>::remove(nonce);
+				Self::execute_inbound(nonce, config.kind, transfer)
+			} else {
+				InboundVotes::<T>::insert(nonce, votes);
+				Ok(())
+			}
+		}
+
+		/// Replace the relayer set, and the number of votes needed to execute an inbound
+		/// transfer.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		///
+		/// Emits `RelayersSet`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::set_relayers(relayers.len() as u32))]
+		pub fn set_relayers(
+			origin: OriginFor<T>,
+			relayers: BoundedVec<T::AccountId, T::MaxRelayers>,
+			threshold: u32,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				threshold > 0 && threshold as usize <= relayers.len(),
+				Error::<T>::InvalidThreshold
+			);
+			let mut sorted = relayers.to_vec();
+			sorted.sort();
+			sorted.dedup();
+			ensure!(sorted.len() == relayers.len(), Error::<T>::DuplicateRelayer);
+
+			Relayers::<T>::put(&relayers);
+			Threshold::<T>::put(threshold);
+			Self::deposit_event(Event::RelayersSet { relayers, threshold });
+			Ok(())
+		}
+
+		/// Set how `asset` is bridged, or stop bridging it if `config` is `None`.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		///
+		/// Emits `AssetConfigSet`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::set_asset_config())]
+		pub fn set_asset_config(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			config: Option<AssetConfig<T::Balance>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			BridgedAssets::<T>::set(&asset, config);
+			Self::deposit_event(Event::AssetConfigSet { asset, config });
+			Ok(())
+		}
+
+		/// Pause or resume the bridge.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		///
+		/// Emits `Paused` or `Resumed`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::set_paused())]
+		pub fn set_paused(origin: OriginFor<T>, paused: bool) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Paused::<T>::put(paused);
+			Self::deposit_event(if paused { Event::Paused } else { Event::Resumed });
+			Ok(())
+		}
+
+		/// Cancel the inbound transfer `nonce`, which then can't be executed anymore.
+		///
+		/// The dispatch origin for this call must be `AdminOrigin`.
+		///
+		/// This is meant for transfers which can't be executed, and which would otherwise keep
+		/// the window of inbound nonces from advancing.
+		///
+		/// Emits `TransferInCancelled`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::cancel_transfer_in())]
+		pub fn cancel_transfer_in(origin: OriginFor<T>, nonce: u64) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let mut progress = InboundProgress::<T>::get();
+			ensure!(!progress.is_finished(nonce), Error::<T>::AlreadyExecuted);
+			ensure!(progress.in_window(nonce), Error::<T>::NonceOutOfWindow);
+
+			progress.finish(nonce);
+			InboundProgress::<T>::put(progress);
+			InboundVotes::<T>::remove(nonce);
+			Self::deposit_event(Event::TransferInCancelled { nonce });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account holding the native assets which have been bridged.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account_truncating()
+	}
+
+	fn execute_inbound(
+		nonce: u64,
+		kind: AssetKind,
+		transfer: InboundTransferOf<T>,
+	) -> DispatchResult {
+		let InboundTransfer { asset, beneficiary, amount } = transfer;
+		match kind {
+			AssetKind::Native => T::Assets::transfer(
+				asset.clone(),
+				&Self::account_id(),
+				&beneficiary,
+				amount,
+				Preservation::Expendable,
+			)?,
+			AssetKind::Wrapped => T::Assets::mint_into(asset.clone(), &beneficiary, amount)?,
+		};
+
+		InboundProgress::<T>::mutate(|progress| progress.finish(nonce));
+		Self::deposit_event(Event::TransferIn { nonce, asset, beneficiary, amount });
+		Ok(())
+	}
+}
@@ -0,0 +1,388 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the module.
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{DispatchError, TokenError};
+
+fn recipient() -> BoundedVec<u8, <Test as Config>::MaxRecipientLength> {
+	BoundedVec::truncate_from(b"remote-account".to_vec())
+}
+
+fn balance(asset: u32, who: u64) -> u64 {
+	<Assets as Inspect<u64>>::balance(asset, &who)
+}
+
+fn transfer(asset: u32, beneficiary: u64, amount: u64) -> InboundTransferOf<Test> {
+	InboundTransfer { asset, beneficiary, amount }
+}
+
+// Register both test assets with a transfer limit of 50, and two out of three relayers.
+fn setup() {
+	for (asset, kind) in [(NATIVE, AssetKind::Native), (WRAPPED, AssetKind::Wrapped)] {
+		let config = AssetConfig { kind, max_transfer: 50 };
+		assert_ok!(TokenBridge::set_asset_config(RuntimeOrigin::root(), asset, Some(config)));
+	}
+	let relayers = BoundedVec::truncate_from(vec![10, 11, 12]);
+	assert_ok!(TokenBridge::set_relayers(RuntimeOrigin::root(), relayers, 2));
+}
+
+#[test]
+fn transfer_out_locks_native_assets() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_ok!(TokenBridge::transfer_out(RuntimeOrigin::signed(1), NATIVE, 30, recipient()));
+		assert_eq!(balance(NATIVE, 1), 70);
+		assert_eq!(balance(NATIVE, TokenBridge::account_id()), 30);
+		System::assert_last_event(
+			Event::TransferOut {
+				nonce: 0,
+				asset: NATIVE,
+				from: 1,
+				recipient: recipient(),
+				amount: 30,
+			}
+			.into(),
+		);
+
+		assert_ok!(TokenBridge::transfer_out(RuntimeOrigin::signed(1), NATIVE, 20, recipient()));
+		assert_eq!(balance(NATIVE, TokenBridge::account_id()), 50);
+		assert_eq!(OutboundNonce::<Test>::get(), 2);
+	});
+}
+
+#[test]
+fn transfer_out_burns_wrapped_assets() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_ok!(TokenBridge::transfer_out(RuntimeOrigin::signed(1), WRAPPED, 30, recipient()));
+		assert_eq!(balance(WRAPPED, 1), 70);
+		assert_eq!(balance(WRAPPED, TokenBridge::account_id()), 0);
+		assert_eq!(<Assets as Inspect<u64>>::total_issuance(WRAPPED), 70);
+
+		assert_noop!(
+			TokenBridge::transfer_out(RuntimeOrigin::signed(2), WRAPPED, 30, recipient()),
+			TokenError::FundsUnavailable,
+		);
+	});
+}
+
+#[test]
+fn transfer_out_checks_asset_config() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TokenBridge::transfer_out(RuntimeOrigin::signed(1), NATIVE, 30, recipient()),
+			Error::<Test>::UnknownAsset,
+		);
+		setup();
+		assert_noop!(
+			TokenBridge::transfer_out(RuntimeOrigin::signed(1), NATIVE, 51, recipient()),
+			Error::<Test>::TransferTooLarge,
+		);
+
+		assert_ok!(TokenBridge::set_asset_config(RuntimeOrigin::root(), NATIVE, None));
+		System::assert_last_event(Event::AssetConfigSet { asset: NATIVE, config: None }.into());
+		assert_noop!(
+			TokenBridge::transfer_out(RuntimeOrigin::signed(1), NATIVE, 30, recipient()),
+			Error::<Test>::UnknownAsset,
+		);
+	});
+}
+
+#[test]
+fn pausing_stops_transfers() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_noop!(
+			TokenBridge::set_paused(RuntimeOrigin::signed(1), true),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TokenBridge::set_paused(RuntimeOrigin::root(), true));
+		System::assert_last_event(Event::Paused.into());
+
+		assert_noop!(
+			TokenBridge::transfer_out(RuntimeOrigin::signed(1), NATIVE, 30, recipient()),
+			Error::<Test>::BridgePaused,
+		);
+		assert_noop!(
+			TokenBridge::vote_transfer_in(RuntimeOrigin::signed(10), 0, transfer(WRAPPED, 2, 10)),
+			Error::<Test>::BridgePaused,
+		);
+
+		assert_ok!(TokenBridge::set_paused(RuntimeOrigin::root(), false));
+		System::assert_last_event(Event::Resumed.into());
+		assert_ok!(TokenBridge::transfer_out(RuntimeOrigin::signed(1), NATIVE, 30, recipient()));
+	});
+}
+
+#[test]
+fn inbound_transfers_execute_at_threshold() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_ok!(TokenBridge::transfer_out(RuntimeOrigin::signed(1), NATIVE, 30, recipient()));
+
+		// native assets are unlocked from the bridge account.
+		assert_ok!(TokenBridge::vote_transfer_in(
+			RuntimeOrigin::signed(10),
+			0,
+			transfer(NATIVE, 2, 20)
+		));
+		System::assert_last_event(Event::TransferInVoted { nonce: 0, relayer: 10 }.into());
+		assert_eq!(balance(NATIVE, 2), 0);
+		assert_ok!(TokenBridge::vote_transfer_in(
+			RuntimeOrigin::signed(11),
+			0,
+			transfer(NATIVE, 2, 20)
+		));
+		System::assert_last_event(
+			Event::TransferIn { nonce: 0, asset: NATIVE, beneficiary: 2, amount: 20 }.into(),
+		);
+		assert_eq!(balance(NATIVE, 2), 20);
+		assert_eq!(balance(NATIVE, TokenBridge::account_id()), 10);
+		assert!(InboundProgress::<Test>::get().is_finished(0));
+		assert!(!InboundVotes::<Test>::contains_key(0));
+
+		// wrapped assets are minted.
+		assert_ok!(TokenBridge::vote_transfer_in(
+			RuntimeOrigin::signed(11),
+			1,
+			transfer(WRAPPED, 2, 40)
+		));
+		assert_ok!(TokenBridge::vote_transfer_in(
+			RuntimeOrigin::signed(12),
+			1,
+			transfer(WRAPPED, 2, 40)
+		));
+		assert_eq!(balance(WRAPPED, 2), 40);
+		assert_eq!(<Assets as Inspect<u64>>::total_issuance(WRAPPED), 140);
+
+		// a late vote can't execute the transfer again.
+		assert_noop!(
+			TokenBridge::vote_transfer_in(RuntimeOrigin::signed(12), 0, transfer(NATIVE, 2, 20)),
+			Error::<Test>::AlreadyExecuted,
+		);
+	});
+}
+
+#[test]
+fn only_matching_votes_count() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_ok!(TokenBridge::vote_transfer_in(
+			RuntimeOrigin::signed(10),
+			0,
+			transfer(WRAPPED, 2, 10)
+		));
+		assert_ok!(TokenBridge::vote_transfer_in(
+			RuntimeOrigin::signed(11),
+			0,
+			transfer(WRAPPED, 3, 10)
+		));
+		assert_eq!(balance(WRAPPED, 2), 0);
+		assert_eq!(balance(WRAPPED, 3), 0);
+
+		assert_ok!(TokenBridge::vote_transfer_in(
+			RuntimeOrigin::signed(12),
+			0,
+			transfer(WRAPPED, 3, 10)
+		));
+		assert_eq!(balance(WRAPPED, 2), 0);
+		assert_eq!(balance(WRAPPED, 3), 10);
+	});
+}
+
+#[test]
+fn vote_transfer_in_checks_relayers() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_noop!(
+			TokenBridge::vote_transfer_in(RuntimeOrigin::signed(1), 0, transfer(WRAPPED, 2, 10)),
+			Error::<Test>::NotRelayer,
+		);
+		assert_ok!(TokenBridge::vote_transfer_in(
+			RuntimeOrigin::signed(10),
+			0,
+			transfer(WRAPPED, 2, 10)
+		));
+		assert_noop!(
+			TokenBridge::vote_transfer_in(RuntimeOrigin::signed(10), 0, transfer(WRAPPED, 2, 10)),
+			Error::<Test>::AlreadyVoted,
+		);
+		assert_noop!(
+			TokenBridge::vote_transfer_in(RuntimeOrigin::signed(11), 0, transfer(WRAPPED, 2, 51)),
+			Error::<Test>::TransferTooLarge,
+		);
+		assert_noop!(
+			TokenBridge::vote_transfer_in(RuntimeOrigin::signed(11), 0, transfer(3, 2, 10)),
+			Error::<Test>::UnknownAsset,
+		);
+
+		// the vote of a removed relayer doesn't count anymore.
+		let relayers = BoundedVec::truncate_from(vec![11, 12]);
+		assert_ok!(TokenBridge::set_relayers(RuntimeOrigin::root(), relayers, 2));
+		assert_ok!(TokenBridge::vote_transfer_in(
+			RuntimeOrigin::signed(11),
+			0,
+			transfer(WRAPPED, 2, 10)
+		));
+		assert_eq!(balance(WRAPPED, 2), 0);
+		assert_ok!(TokenBridge::vote_transfer_in(
+			RuntimeOrigin::signed(12),
+			0,
+			transfer(WRAPPED, 2, 10)
+		));
+		assert_eq!(balance(WRAPPED, 2), 10);
+	});
+}
+
+#[test]
+fn inbound_window_slides_over_finished_transfers() {
+	let mut window = InboundWindow::default();
+	window.finish(1);
+	assert_eq!(window, InboundWindow { lowest_unfinished: 0, finished: 0b10 });
+	assert!(!window.is_finished(0));
+	assert!(window.is_finished(1));
+
+	window.finish(0);
+	assert_eq!(window, InboundWindow { lowest_unfinished: 2, finished: 0 });
+	assert!(window.is_finished(0));
+	assert!(window.in_window(2 + INBOUND_WINDOW - 1));
+	assert!(!window.in_window(2 + INBOUND_WINDOW));
+
+	// nonces outside of the window are ignored.
+	window.finish(2 + INBOUND_WINDOW);
+	assert_eq!(window, InboundWindow { lowest_unfinished: 2, finished: 0 });
+
+	// finishing the whole window in reverse order slides it by its full width.
+	for nonce in (2..2 + INBOUND_WINDOW).rev() {
+		window.finish(nonce);
+	}
+	assert_eq!(window, InboundWindow { lowest_unfinished: 2 + INBOUND_WINDOW, finished: 0 });
+}
+
+#[test]
+fn inbound_nonces_must_be_in_window() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_noop!(
+			TokenBridge::vote_transfer_in(
+				RuntimeOrigin::signed(10),
+				INBOUND_WINDOW,
+				transfer(WRAPPED, 2, 10)
+			),
+			Error::<Test>::NonceOutOfWindow,
+		);
+
+		for nonce in (0..INBOUND_WINDOW).rev() {
+			assert_ok!(TokenBridge::vote_transfer_in(
+				RuntimeOrigin::signed(10),
+				nonce,
+				transfer(WRAPPED, 2, 1)
+			));
+			assert_ok!(TokenBridge::vote_transfer_in(
+				RuntimeOrigin::signed(11),
+				nonce,
+				transfer(WRAPPED, 2, 1)
+			));
+		}
+		assert_eq!(
+			InboundProgress::<Test>::get(),
+			InboundWindow { lowest_unfinished: INBOUND_WINDOW, finished: 0 },
+		);
+		assert_ok!(TokenBridge::vote_transfer_in(
+			RuntimeOrigin::signed(10),
+			INBOUND_WINDOW,
+			transfer(WRAPPED, 2, 10)
+		));
+	});
+}
+
+#[test]
+fn failed_inbound_transfers_can_be_cancelled() {
+	new_test_ext().execute_with(|| {
+		setup();
+		// nothing has been locked in the bridge account, so the transfer can't be executed.
+		assert_ok!(TokenBridge::vote_transfer_in(
+			RuntimeOrigin::signed(10),
+			0,
+			transfer(NATIVE, 2, 20)
+		));
+		assert_noop!(
+			TokenBridge::vote_transfer_in(RuntimeOrigin::signed(11), 0, transfer(NATIVE, 2, 20)),
+			TokenError::FundsUnavailable,
+		);
+		assert_eq!(InboundVotes::<Test>::get(0).len(), 1);
+
+		assert_noop!(
+			TokenBridge::cancel_transfer_in(RuntimeOrigin::signed(10), 0),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			TokenBridge::cancel_transfer_in(RuntimeOrigin::root(), INBOUND_WINDOW),
+			Error::<Test>::NonceOutOfWindow,
+		);
+		assert_ok!(TokenBridge::cancel_transfer_in(RuntimeOrigin::root(), 0));
+		System::assert_last_event(Event::TransferInCancelled { nonce: 0 }.into());
+		assert!(!InboundVotes::<Test>::contains_key(0));
+		assert_eq!(InboundProgress::<Test>::get().lowest_unfinished, 1);
+
+		assert_noop!(
+			TokenBridge::vote_transfer_in(RuntimeOrigin::signed(11), 0, transfer(NATIVE, 2, 20)),
+			Error::<Test>::AlreadyExecuted,
+		);
+		assert_noop!(
+			TokenBridge::cancel_transfer_in(RuntimeOrigin::root(), 0),
+			Error::<Test>::AlreadyExecuted,
+		);
+	});
+}
+
+#[test]
+fn set_relayers_works() {
+	new_test_ext().execute_with(|| {
+		let relayers = BoundedVec::truncate_from(vec![10, 11]);
+		assert_noop!(
+			TokenBridge::set_relayers(RuntimeOrigin::signed(10), relayers.clone(), 1),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			TokenBridge::set_relayers(RuntimeOrigin::root(), relayers.clone(), 0),
+			Error::<Test>::InvalidThreshold,
+		);
+		assert_noop!(
+			TokenBridge::set_relayers(RuntimeOrigin::root(), relayers.clone(), 3),
+			Error::<Test>::InvalidThreshold,
+		);
+		assert_noop!(
+			TokenBridge::set_relayers(
+				RuntimeOrigin::root(),
+				BoundedVec::truncate_from(vec![10, 10]),
+				1
+			),
+			Error::<Test>::DuplicateRelayer,
+		);
+
+		assert_ok!(TokenBridge::set_relayers(RuntimeOrigin::root(), relayers.clone(), 2));
+		assert_eq!(Relayers::<Test>::get(), relayers);
+		assert_eq!(Threshold::<Test>::get(), 2);
+		System::assert_last_event(Event::RelayersSet { relayers, threshold: 2 }.into());
+	});
+}
@@ -0,0 +1,149 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_token_bridge
+//!
+//! These are estimates based on the storage accesses of each call, not benchmark results. They
+//! are tracked in `docs/pending-benchmarks.md` until the benchmarks in `benchmarking.rs` are run
+//! on reference hardware.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_token_bridge.
+pub trait WeightInfo {
+	fn transfer_out() -> Weight;
+	fn vote_transfer_in() -> Weight;
+	fn set_relayers(n: u32, ) -> Weight;
+	fn set_asset_config() -> Weight;
+	fn set_paused() -> Weight;
+	fn cancel_transfer_in() -> Weight;
+}
+
+/// Weights for pallet_token_bridge using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: TokenBridge Paused (r:1 w:0)
+	// Storage: TokenBridge BridgedAssets (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TokenBridge OutboundNonce (r:1 w:1)
+	fn transfer_out() -> Weight {
+		Weight::from_parts(61_000_000, 6_208)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	// Storage: TokenBridge Relayers (r:1 w:0)
+	// Storage: TokenBridge Paused (r:1 w:0)
+	// Storage: TokenBridge InboundProgress (r:1 w:1)
+	// Storage: TokenBridge BridgedAssets (r:1 w:0)
+	// Storage: TokenBridge InboundVotes (r:1 w:1)
+	// Storage: TokenBridge Threshold (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn vote_transfer_in() -> Weight {
+		Weight::from_parts(117_000_000, 11_884)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	// Storage: TokenBridge Relayers (r:0 w:1)
+	// Storage: TokenBridge Threshold (r:0 w:1)
+	// The range of component `n` is `[1, 100]`.
+	fn set_relayers(n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(105_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: TokenBridge BridgedAssets (r:0 w:1)
+	fn set_asset_config() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: TokenBridge Paused (r:0 w:1)
+	fn set_paused() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: TokenBridge InboundProgress (r:1 w:1)
+	// Storage: TokenBridge InboundVotes (r:0 w:1)
+	fn cancel_transfer_in() -> Weight {
+		Weight::from_parts(12_000_000, 1_509)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: TokenBridge Paused (r:1 w:0)
+	// Storage: TokenBridge BridgedAssets (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: TokenBridge OutboundNonce (r:1 w:1)
+	fn transfer_out() -> Weight {
+		Weight::from_parts(61_000_000, 6_208)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	// Storage: TokenBridge Relayers (r:1 w:0)
+	// Storage: TokenBridge Paused (r:1 w:0)
+	// Storage: TokenBridge InboundProgress (r:1 w:1)
+	// Storage: TokenBridge BridgedAssets (r:1 w:0)
+	// Storage: TokenBridge InboundVotes (r:1 w:1)
+	// Storage: TokenBridge Threshold (r:1 w:0)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn vote_transfer_in() -> Weight {
+		Weight::from_parts(117_000_000, 11_884)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	// Storage: TokenBridge Relayers (r:0 w:1)
+	// Storage: TokenBridge Threshold (r:0 w:1)
+	// The range of component `n` is `[1, 100]`.
+	fn set_relayers(n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(105_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	// Storage: TokenBridge BridgedAssets (r:0 w:1)
+	fn set_asset_config() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: TokenBridge Paused (r:0 w:1)
+	fn set_paused() -> Weight {
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	// Storage: TokenBridge InboundProgress (r:1 w:1)
+	// Storage: TokenBridge InboundVotes (r:0 w:1)
+	fn cancel_transfer_in() -> Weight {
+		Weight::from_parts(12_000_000, 1_509)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+}
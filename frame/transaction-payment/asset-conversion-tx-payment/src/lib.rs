@@ -265,7 +265,7 @@ where
 	) -> TransactionValidity {
 		use pallet_transaction_payment::ChargeTransactionPayment;
 		let (fee, _) = self.withdraw_fee(who, call, info, len)?;
-		let priority = ChargeTransactionPayment::<T>::get_priority(info, len, self.tip, fee);
+		let priority = ChargeTransactionPayment::<T>::get_priority(call, info, len, self.tip, fee);
 		Ok(ValidTransaction { priority, ..Default::default() })
 	}
 
@@ -175,6 +175,7 @@ impl pallet_transaction_payment::Config for Runtime {
 	type LengthToFee = TransactionByteFee;
 	type FeeMultiplierUpdate = ();
 	type OperationalFeeMultiplier = ConstU8<5>;
+	type PriorityCurve = pallet_transaction_payment::TipPerResourcePriority;
 }
 
 type AssetId = u32;
@@ -193,6 +194,7 @@ impl pallet_assets::Config for Runtime {
 	type MetadataDepositPerByte = ConstU64<0>;
 	type ApprovalDeposit = ConstU64<0>;
 	type StringLimit = ConstU32<20>;
+	type MaxBatch = ConstU32<10>;
 	type Freezer = ();
 	type Extra = ();
 	type CallbackHandle = ();
@@ -218,6 +220,7 @@ impl pallet_assets::Config<Instance2> for Runtime {
 	type MetadataDepositPerByte = ConstU64<0>;
 	type ApprovalDeposit = ConstU64<0>;
 	type StringLimit = ConstU32<50>;
+	type MaxBatch = ConstU32<10>;
 	type Freezer = ();
 	type Extra = ();
 	type WeightInfo = ();
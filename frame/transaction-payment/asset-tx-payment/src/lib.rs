@@ -32,6 +32,9 @@
 //! This pallet wraps FRAME's transaction payment pallet and functions as a replacement. This means
 //! you should include both pallets in your `construct_runtime` macro, but only include this
 //! pallet's [`SignedExtension`] ([`ChargeAssetTxPayment`]).
+//!
+//! Runtimes may implement [`AssetTxPaymentApi`] so that wallets can query what a transaction would
+//! cost when paid in a given asset, e.g. with [`FungiblesAdapter::asset_fee`].
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -245,7 +248,7 @@ where
 	) -> TransactionValidity {
 		use pallet_transaction_payment::ChargeTransactionPayment;
 		let (fee, _) = self.withdraw_fee(who, call, info, len)?;
-		let priority = ChargeTransactionPayment::<T>::get_priority(info, len, self.tip, fee);
+		let priority = ChargeTransactionPayment::<T>::get_priority(call, info, len, self.tip, fee);
 		Ok(ValidTransaction { priority, ..Default::default() })
 	}
 
@@ -312,3 +315,21 @@ where
 		Ok(())
 	}
 }
+
+sp_api::decl_runtime_apis! {
+	/// The API to query transaction fees when they are paid in an asset.
+	pub trait AssetTxPaymentApi<AssetId, AssetBalance>
+	where
+		AssetId: codec::Codec,
+		AssetBalance: codec::Codec,
+	{
+		/// Query the fee of `uxt`, without any tip, when it is paid in `asset_id`.
+		///
+		/// Returns `None` if the fee cannot be paid in `asset_id`.
+		fn query_fee_in_asset(
+			uxt: Block::Extrinsic,
+			len: u32,
+			asset_id: AssetId,
+		) -> Option<AssetBalance>;
+	}
+}
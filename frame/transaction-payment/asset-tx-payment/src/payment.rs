@@ -93,6 +93,26 @@ impl<A, B: Balanced<A>> HandleCredit<A, B> for () {
 /// The credit handler is given the complete fee in terms of the asset used for the transaction.
 pub struct FungiblesAdapter<CON, HC>(PhantomData<(CON, HC)>);
 
+impl<CON, HC> FungiblesAdapter<CON, HC> {
+	/// The amount of `asset_id` withdrawn to pay a `fee` given in the native currency.
+	pub fn asset_fee<T>(
+		fee: BalanceOf<T>,
+		asset_id: AssetIdOf<T>,
+	) -> Result<AssetBalanceOf<T>, TransactionValidityError>
+	where
+		T: Config,
+		CON: ConversionToAssetBalance<BalanceOf<T>, AssetIdOf<T>, AssetBalanceOf<T>>,
+	{
+		// We don't know the precision of the underlying asset. Because the converted fee could be
+		// less than one (e.g. 0.5) but gets rounded down by integer division we introduce a minimum
+		// fee.
+		let min_converted_fee = if fee.is_zero() { Zero::zero() } else { One::one() };
+		Ok(CON::to_asset_balance(fee, asset_id)
+			.map_err(|_| TransactionValidityError::from(InvalidTransaction::Payment))?
+			.max(min_converted_fee))
+	}
+}
+
 /// Default implementation for a runtime instantiating this pallet, a balance to asset converter and
 /// a credit handler.
 impl<T, CON, HC> OnChargeAssetTransaction<T> for FungiblesAdapter<CON, HC>
@@ -117,13 +137,7 @@ where
 		fee: Self::Balance,
 		_tip: Self::Balance,
 	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
-		// We don't know the precision of the underlying asset. Because the converted fee could be
-		// less than one (e.g. 0.5) but gets rounded down by integer division we introduce a minimum
-		// fee.
-		let min_converted_fee = if fee.is_zero() { Zero::zero() } else { One::one() };
-		let converted_fee = CON::to_asset_balance(fee, asset_id)
-			.map_err(|_| TransactionValidityError::from(InvalidTransaction::Payment))?
-			.max(min_converted_fee);
+		let converted_fee = Self::asset_fee::<T>(fee, asset_id)?;
 		let can_withdraw =
 			<T::Fungibles as Inspect<T::AccountId>>::can_withdraw(asset_id, who, converted_fee);
 		if can_withdraw != WithdrawConsequence::Success {
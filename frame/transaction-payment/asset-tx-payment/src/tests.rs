@@ -201,6 +201,48 @@ fn transaction_payment_in_asset_possible() {
 		});
 }
 
+#[test]
+fn asset_fee_matches_the_charged_fee() {
+	let base_weight = 5;
+	ExtBuilder::default()
+		.balance_factor(100)
+		.base_weight(Weight::from_parts(base_weight, 0))
+		.build()
+		.execute_with(|| {
+			type Adapter = <Runtime as Config>::OnChargeAssetTransaction;
+
+			// create the asset
+			let asset_id = 1;
+			let min_balance = 2;
+			assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				asset_id.into(),
+				42,   /* owner */
+				true, /* is_sufficient */
+				min_balance
+			));
+
+			// mint into the caller account
+			let caller = 1;
+			let beneficiary = <Runtime as system::Config>::Lookup::unlookup(caller);
+			let balance = 100;
+			assert_ok!(Assets::mint_into(asset_id.into(), &beneficiary, balance));
+
+			let info = info_from_weight(Weight::from_parts(5, 0));
+			let len = 10;
+			let native_fee = TransactionPayment::compute_fee(len, &info, 0);
+			let fee = Adapter::asset_fee::<Runtime>(native_fee, asset_id).unwrap();
+			assert_eq!(fee, native_fee * min_balance / ExistentialDeposit::get());
+			// an unknown asset cannot be used to pay fees
+			assert!(Adapter::asset_fee::<Runtime>(native_fee, 2).is_err());
+
+			ChargeAssetTxPayment::<Runtime>::from(0, Some(asset_id))
+				.pre_dispatch(&caller, CALL, &info, len as usize)
+				.unwrap();
+			assert_eq!(Assets::balance(asset_id, caller), balance - fee);
+		});
+}
+
 #[test]
 fn transaction_payment_without_fee() {
 	let base_weight = 5;
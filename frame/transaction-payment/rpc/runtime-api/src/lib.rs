@@ -22,7 +22,9 @@
 use codec::Codec;
 use sp_runtime::traits::MaybeDisplay;
 
-pub use pallet_transaction_payment::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
+pub use pallet_transaction_payment::{
+	FeeDetails, FeeMultiplierInfo, InclusionFee, RuntimeDispatchInfo,
+};
 
 sp_api::decl_runtime_apis! {
 	#[api_version(4)]
@@ -33,6 +35,10 @@ sp_api::decl_runtime_apis! {
 		fn query_fee_details(uxt: Block::Extrinsic, len: u32) -> FeeDetails<Balance>;
 		fn query_weight_to_fee(weight: sp_weights::Weight) -> Balance;
 		fn query_length_to_fee(length: u32) -> Balance;
+		/// Query the current fee multiplier, how far it can move with the next block and the
+		/// parameters of the length fee.
+		#[api_version(5)]
+		fn query_fee_multiplier_info() -> FeeMultiplierInfo<Balance>;
 	}
 
 	#[api_version(3)]
@@ -44,6 +44,7 @@
 //!     final state of the chain at the end of the previous block. This can be configured via
 //!     [`Config::FeeMultiplierUpdate`]
 //!   - How the fees are paid via [`Config::OnChargeTransaction`].
+//!   - How transactions are prioritized in the transaction pool via [`Config::PriorityCurve`].
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -54,23 +55,24 @@ use frame_support::{
 	dispatch::{
 		DispatchClass, DispatchInfo, DispatchResult, GetDispatchInfo, Pays, PostDispatchInfo,
 	},
-	traits::{Defensive, EstimateCallFee, Get},
+	traits::{EstimateCallFee, Get},
 	weights::{Weight, WeightToFee},
 };
 pub use pallet::*;
 pub use payment::*;
+pub use priority::*;
 use sp_runtime::{
 	traits::{
-		Convert, DispatchInfoOf, Dispatchable, One, PostDispatchInfoOf, SaturatedConversion,
-		Saturating, SignedExtension, Zero,
+		Convert, DispatchInfoOf, Dispatchable, PostDispatchInfoOf, Saturating, SignedExtension,
+		Zero,
 	},
 	transaction_validity::{
 		TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransaction,
 	},
-	FixedPointNumber, FixedU128, Perbill, Perquintill, RuntimeDebug,
+	FixedPointNumber, FixedU128, PerThing, Perbill, Perquintill, RuntimeDebug,
 };
 use sp_std::prelude::*;
-pub use types::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
+pub use types::{FeeDetails, FeeMultiplierInfo, InclusionFee, RuntimeDispatchInfo};
 
 #[cfg(test)]
 mod mock;
@@ -78,6 +80,7 @@ mod mock;
 mod tests;
 
 mod payment;
+mod priority;
 mod types;
 
 /// Fee multiplier.
@@ -244,22 +247,35 @@ where
 		// defensive only, a test case assures that the maximum weight diff can fit in Multiplier
 		// without any saturation.
 		let diff = Multiplier::saturating_from_rational(diff_abs, max_limiting_dimension.max(1));
-		let diff_squared = diff.saturating_mul(diff);
 
-		let v_squared_2 = adjustment_variable.saturating_mul(adjustment_variable) /
-			Multiplier::saturating_from_integer(2);
-
-		let first_term = adjustment_variable.saturating_mul(diff);
-		let second_term = v_squared_2.saturating_mul(diff_squared);
+		adjust_multiplier(previous, diff, positive, adjustment_variable)
+			.clamp(min_multiplier, max_multiplier)
+	}
+}
 
-		if positive {
-			let excess = first_term.saturating_add(second_term).saturating_mul(previous);
-			previous.saturating_add(excess).clamp(min_multiplier, max_multiplier)
-		} else {
-			// Defensive-only: first_term > second_term. Safe subtraction.
-			let negative = first_term.saturating_sub(second_term).saturating_mul(previous);
-			previous.saturating_sub(negative).clamp(min_multiplier, max_multiplier)
-		}
+/// Apply the adjustment of [`TargetedFeeAdjustment`] to `previous`, given the absolute difference
+/// `diff` between the block fullness and its target, and whether the block is above the target.
+fn adjust_multiplier(
+	previous: Multiplier,
+	diff: Multiplier,
+	positive: bool,
+	adjustment_variable: Multiplier,
+) -> Multiplier {
+	let diff_squared = diff.saturating_mul(diff);
+
+	let v_squared_2 = adjustment_variable.saturating_mul(adjustment_variable) /
+		Multiplier::saturating_from_integer(2);
+
+	let first_term = adjustment_variable.saturating_mul(diff);
+	let second_term = v_squared_2.saturating_mul(diff_squared);
+
+	if positive {
+		let excess = first_term.saturating_add(second_term).saturating_mul(previous);
+		previous.saturating_add(excess)
+	} else {
+		// Defensive-only: first_term > second_term. Safe subtraction.
+		let negative = first_term.saturating_sub(second_term).saturating_mul(previous);
+		previous.saturating_sub(negative)
 	}
 }
 
@@ -335,6 +351,7 @@ pub mod pallet {
 			type RuntimeEvent = ();
 			type FeeMultiplierUpdate = ();
 			type OperationalFeeMultiplier = ();
+			type PriorityCurve = TipPerResourcePriority;
 		}
 	}
 
@@ -387,6 +404,13 @@ pub mod pallet {
 		/// transactions.
 		#[pallet::constant]
 		type OperationalFeeMultiplier: Get<u8>;
+
+		/// Map a transaction to its priority in the transaction pool.
+		///
+		/// [`TipPerResourcePriority`] is the classic mapping, which [`PriorityBands`] can wrap to
+		/// reserve a band of priorities for `Operational` extrinsics and selected calls.
+		#[pallet::no_default_bounds]
+		type PriorityCurve: PriorityCurve<Self>;
 	}
 
 	#[pallet::type_value]
@@ -429,14 +453,29 @@ pub mod pallet {
 		/// A transaction fee `actual_fee`, of which `tip` was added to the minimum inclusion fee,
 		/// has been paid by `who`.
 		TransactionFeePaid { who: T::AccountId, actual_fee: BalanceOf<T>, tip: BalanceOf<T> },
+		/// The fee multiplier dropped to its minimum value.
+		FeeMultiplierAtFloor { multiplier: Multiplier },
+		/// The fee multiplier rose to its maximum value.
+		FeeMultiplierAtCeiling { multiplier: Multiplier },
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_finalize(_: frame_system::pallet_prelude::BlockNumberFor<T>) {
-			<NextFeeMultiplier<T>>::mutate(|fm| {
-				*fm = T::FeeMultiplierUpdate::convert(*fm);
-			});
+			let previous = <NextFeeMultiplier<T>>::get();
+			let next = T::FeeMultiplierUpdate::convert(previous);
+			<NextFeeMultiplier<T>>::put(next);
+
+			// Only an adjusting multiplier can saturate, and only report reaching a bound, not
+			// staying at it.
+			if T::FeeMultiplierUpdate::variability().is_zero() || next == previous {
+				return
+			}
+			if next == T::FeeMultiplierUpdate::min() {
+				Self::deposit_event(Event::FeeMultiplierAtFloor { multiplier: next });
+			} else if next == T::FeeMultiplierUpdate::max() {
+				Self::deposit_event(Event::FeeMultiplierAtCeiling { multiplier: next });
+			}
 		}
 
 		#[cfg(feature = "std")]
@@ -565,6 +604,42 @@ impl<T: Config> Pallet<T> {
 		Self::compute_fee_details(len, &dispatch_info, tip)
 	}
 
+	/// Query the current fee multiplier, the range it can move to with the next block and the
+	/// parameters of the length fee.
+	pub fn query_fee_multiplier_info() -> FeeMultiplierInfo<BalanceOf<T>> {
+		let (next_multiplier_floor, next_multiplier_ceiling) = Self::next_fee_multiplier_bounds();
+
+		FeeMultiplierInfo {
+			multiplier: Self::next_fee_multiplier(),
+			min_multiplier: T::FeeMultiplierUpdate::min(),
+			max_multiplier: T::FeeMultiplierUpdate::max(),
+			next_multiplier_floor,
+			next_multiplier_ceiling,
+			target_block_fullness: T::FeeMultiplierUpdate::target(),
+			variability: T::FeeMultiplierUpdate::variability(),
+			length_fee_per_byte: Self::length_to_fee(1),
+		}
+	}
+
+	/// The lowest and highest fee multiplier of the next block, reached if the current block stays
+	/// empty or becomes full respectively.
+	///
+	/// This follows the adjustment of [`TargetedFeeAdjustment`], using the target and variability
+	/// of `T::FeeMultiplierUpdate`.
+	pub fn next_fee_multiplier_bounds() -> (Multiplier, Multiplier) {
+		let min_multiplier = T::FeeMultiplierUpdate::min();
+		let max_multiplier = T::FeeMultiplierUpdate::max();
+		let previous = Self::next_fee_multiplier().max(min_multiplier);
+		let target = T::FeeMultiplierUpdate::target();
+		let adjustment_variable = T::FeeMultiplierUpdate::variability();
+
+		let floor = adjust_multiplier(previous, target.into(), false, adjustment_variable);
+		let ceiling =
+			adjust_multiplier(previous, target.left_from_one().into(), true, adjustment_variable);
+
+		(floor.clamp(min_multiplier, max_multiplier), ceiling.clamp(min_multiplier, max_multiplier))
+	}
+
 	/// Compute the final fee value for a particular transaction.
 	pub fn compute_fee(
 		len: u32,
@@ -731,78 +806,18 @@ where
 		.map(|i| (fee, i))
 	}
 
-	/// Get an appropriate priority for a transaction with the given `DispatchInfo`, encoded length
-	/// and user-included tip.
-	///
-	/// The priority is based on the amount of `tip` the user is willing to pay per unit of either
-	/// `weight` or `length`, depending which one is more limiting. For `Operational` extrinsics
-	/// we add a "virtual tip" to the calculations.
+	/// Get an appropriate priority for a transaction dispatching `call` with the given
+	/// `DispatchInfo`, encoded length and user-included tip.
 	///
-	/// The formula should simply be `tip / bounded_{weight|length}`, but since we are using
-	/// integer division, we have no guarantees it's going to give results in any reasonable
-	/// range (might simply end up being zero). Hence we use a scaling factor:
-	/// `tip * (max_block_{weight|length} / bounded_{weight|length})`, since given current
-	/// state of-the-art blockchains, number of per-block transactions is expected to be in a
-	/// range reasonable enough to not saturate the `Balance` type while multiplying by the tip.
+	/// The priority is computed by [`Config::PriorityCurve`].
 	pub fn get_priority(
+		call: &T::RuntimeCall,
 		info: &DispatchInfoOf<T::RuntimeCall>,
 		len: usize,
 		tip: BalanceOf<T>,
 		final_fee: BalanceOf<T>,
 	) -> TransactionPriority {
-		// Calculate how many such extrinsics we could fit into an empty block and take the
-		// limiting factor.
-		let max_block_weight = T::BlockWeights::get().max_block;
-		let max_block_length = *T::BlockLength::get().max.get(info.class) as u64;
-
-		// bounded_weight is used as a divisor later so we keep it non-zero.
-		let bounded_weight = info.weight.max(Weight::from_parts(1, 1)).min(max_block_weight);
-		let bounded_length = (len as u64).clamp(1, max_block_length);
-
-		// returns the scarce resource, i.e. the one that is limiting the number of transactions.
-		let max_tx_per_block_weight = max_block_weight
-			.checked_div_per_component(&bounded_weight)
-			.defensive_proof("bounded_weight is non-zero; qed")
-			.unwrap_or(1);
-		let max_tx_per_block_length = max_block_length / bounded_length;
-		// Given our current knowledge this value is going to be in a reasonable range - i.e.
-		// less than 10^9 (2^30), so multiplying by the `tip` value is unlikely to overflow the
-		// balance type. We still use saturating ops obviously, but the point is to end up with some
-		// `priority` distribution instead of having all transactions saturate the priority.
-		let max_tx_per_block = max_tx_per_block_length
-			.min(max_tx_per_block_weight)
-			.saturated_into::<BalanceOf<T>>();
-		let max_reward = |val: BalanceOf<T>| val.saturating_mul(max_tx_per_block);
-
-		// To distribute no-tip transactions a little bit, we increase the tip value by one.
-		// This means that given two transactions without a tip, smaller one will be preferred.
-		let tip = tip.saturating_add(One::one());
-		let scaled_tip = max_reward(tip);
-
-		match info.class {
-			DispatchClass::Normal => {
-				// For normal class we simply take the `tip_per_weight`.
-				scaled_tip
-			},
-			DispatchClass::Mandatory => {
-				// Mandatory extrinsics should be prohibited (e.g. by the [`CheckWeight`]
-				// extensions), but just to be safe let's return the same priority as `Normal` here.
-				scaled_tip
-			},
-			DispatchClass::Operational => {
-				// A "virtual tip" value added to an `Operational` extrinsic.
-				// This value should be kept high enough to allow `Operational` extrinsics
-				// to get in even during congestion period, but at the same time low
-				// enough to prevent a possible spam attack by sending invalid operational
-				// extrinsics which push away regular transactions from the pool.
-				let fee_multiplier = T::OperationalFeeMultiplier::get().saturated_into();
-				let virtual_tip = final_fee.saturating_mul(fee_multiplier);
-				let scaled_virtual_tip = max_reward(virtual_tip);
-
-				scaled_tip.saturating_add(scaled_virtual_tip)
-			},
-		}
-		.saturated_into::<TransactionPriority>()
+		T::PriorityCurve::priority(call, info, len, tip, final_fee)
 	}
 }
 
@@ -848,7 +863,7 @@ where
 		let (final_fee, _) = self.withdraw_fee(who, call, info, len)?;
 		let tip = self.0;
 		Ok(ValidTransaction {
-			priority: Self::get_priority(info, len, tip, final_fee),
+			priority: Self::get_priority(call, info, len, tip, final_fee),
 			..Default::default()
 		})
 	}
@@ -19,7 +19,7 @@ use super::*;
 use crate as pallet_transaction_payment;
 
 use sp_core::H256;
-use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+use sp_runtime::traits::{BlakeTwo256, Bounded, IdentityLookup, SaturatedConversion};
 
 use frame_support::{
 	derive_impl,
@@ -68,6 +68,10 @@ parameter_types! {
 	pub static WeightToFee: u64 = 1;
 	pub static TransactionByteFee: u64 = 1;
 	pub static OperationalFeeMultiplier: u8 = 5;
+	pub static TargetBlockFullness: Perquintill = Perquintill::zero();
+	pub static AdjustmentVariable: Multiplier = Multiplier::zero();
+	pub static MinimumMultiplier: Multiplier = Multiplier::zero();
+	pub static MaximumMultiplier: Multiplier = Bounded::max_value();
 }
 
 #[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
@@ -156,5 +160,12 @@ impl Config for Runtime {
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 	type WeightToFee = WeightToFee;
 	type LengthToFee = TransactionByteFee;
-	type FeeMultiplierUpdate = ();
+	type FeeMultiplierUpdate = TargetedFeeAdjustment<
+		Self,
+		TargetBlockFullness,
+		AdjustmentVariable,
+		MinimumMultiplier,
+		MaximumMultiplier,
+	>;
+	type PriorityCurve = TipPerResourcePriority;
 }
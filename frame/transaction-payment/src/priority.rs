@@ -0,0 +1,152 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traits and default implementations for prioritizing transactions in the pool.
+
+use crate::{BalanceOf, Config};
+
+use core::marker::PhantomData;
+use sp_runtime::{
+	traits::{One, SaturatedConversion, Saturating},
+	transaction_validity::TransactionPriority,
+};
+
+use frame_support::{
+	dispatch::{DispatchClass, DispatchInfo},
+	traits::{Contains, Defensive, Get},
+	weights::Weight,
+};
+
+/// Map a transaction to the priority it gets in the transaction pool.
+pub trait PriorityCurve<T: Config> {
+	/// The priority of a transaction dispatching `call`, given its dispatch `info`, encoded
+	/// length `len`, the `tip` included by the user and the `final_fee`, which includes the tip.
+	fn priority(
+		call: &T::RuntimeCall,
+		info: &DispatchInfo,
+		len: usize,
+		tip: BalanceOf<T>,
+		final_fee: BalanceOf<T>,
+	) -> TransactionPriority;
+}
+
+/// Prioritize transactions by the `tip` they pay per unit of `weight` or `length`, whichever is
+/// more limiting. `Operational` extrinsics are given a "virtual tip" on top, see
+/// [`Config::OperationalFeeMultiplier`].
+///
+/// The formula should simply be `tip / bounded_{weight|length}`, but since we are using
+/// integer division, we have no guarantees it's going to give results in any reasonable
+/// range (might simply end up being zero). Hence we use a scaling factor:
+/// `tip * (max_block_{weight|length} / bounded_{weight|length})`, since given current
+/// state of-the-art blockchains, number of per-block transactions is expected to be in a
+/// range reasonable enough to not saturate the `Balance` type while multiplying by the tip.
+pub struct TipPerResourcePriority;
+
+impl<T: Config> PriorityCurve<T> for TipPerResourcePriority {
+	fn priority(
+		_call: &T::RuntimeCall,
+		info: &DispatchInfo,
+		len: usize,
+		tip: BalanceOf<T>,
+		final_fee: BalanceOf<T>,
+	) -> TransactionPriority {
+		// Calculate how many such extrinsics we could fit into an empty block and take the
+		// limiting factor.
+		let max_block_weight = T::BlockWeights::get().max_block;
+		let max_block_length = *T::BlockLength::get().max.get(info.class) as u64;
+
+		// bounded_weight is used as a divisor later so we keep it non-zero.
+		let bounded_weight = info.weight.max(Weight::from_parts(1, 1)).min(max_block_weight);
+		let bounded_length = (len as u64).clamp(1, max_block_length);
+
+		// returns the scarce resource, i.e. the one that is limiting the number of transactions.
+		let max_tx_per_block_weight = max_block_weight
+			.checked_div_per_component(&bounded_weight)
+			.defensive_proof("bounded_weight is non-zero; qed")
+			.unwrap_or(1);
+		let max_tx_per_block_length = max_block_length / bounded_length;
+		// Given our current knowledge this value is going to be in a reasonable range - i.e.
+		// less than 10^9 (2^30), so multiplying by the `tip` value is unlikely to overflow the
+		// balance type. We still use saturating ops obviously, but the point is to end up with some
+		// `priority` distribution instead of having all transactions saturate the priority.
+		let max_tx_per_block = max_tx_per_block_length
+			.min(max_tx_per_block_weight)
+			.saturated_into::<BalanceOf<T>>();
+		let max_reward = |val: BalanceOf<T>| val.saturating_mul(max_tx_per_block);
+
+		// To distribute no-tip transactions a little bit, we increase the tip value by one.
+		// This means that given two transactions without a tip, smaller one will be preferred.
+		let tip = tip.saturating_add(One::one());
+		let scaled_tip = max_reward(tip);
+
+		match info.class {
+			DispatchClass::Normal => {
+				// For normal class we simply take the `tip_per_weight`.
+				scaled_tip
+			},
+			DispatchClass::Mandatory => {
+				// Mandatory extrinsics should be prohibited (e.g. by the [`CheckWeight`]
+				// extensions), but just to be safe let's return the same priority as `Normal` here.
+				scaled_tip
+			},
+			DispatchClass::Operational => {
+				// A "virtual tip" value added to an `Operational` extrinsic.
+				// This value should be kept high enough to allow `Operational` extrinsics
+				// to get in even during congestion period, but at the same time low
+				// enough to prevent a possible spam attack by sending invalid operational
+				// extrinsics which push away regular transactions from the pool.
+				let fee_multiplier = T::OperationalFeeMultiplier::get().saturated_into();
+				let virtual_tip = final_fee.saturating_mul(fee_multiplier);
+				let scaled_virtual_tip = max_reward(virtual_tip);
+
+				scaled_tip.saturating_add(scaled_virtual_tip)
+			},
+		}
+		.saturated_into::<TransactionPriority>()
+	}
+}
+
+/// Split the priorities of `Inner` into two bands, so that `Operational` extrinsics and the calls
+/// selected by `Privileged` always take precedence over all other transactions.
+///
+/// The priority of a privileged transaction is raised by `Band`, while the priority of any other
+/// transaction is capped right below `Band`. Within a band, transactions keep the order of
+/// `Inner`.
+pub struct PriorityBands<Inner, Privileged, Band>(PhantomData<(Inner, Privileged, Band)>);
+
+impl<T, Inner, Privileged, Band> PriorityCurve<T> for PriorityBands<Inner, Privileged, Band>
+where
+	T: Config,
+	Inner: PriorityCurve<T>,
+	Privileged: Contains<T::RuntimeCall>,
+	Band: Get<TransactionPriority>,
+{
+	fn priority(
+		call: &T::RuntimeCall,
+		info: &DispatchInfo,
+		len: usize,
+		tip: BalanceOf<T>,
+		final_fee: BalanceOf<T>,
+	) -> TransactionPriority {
+		let priority = Inner::priority(call, info, len, tip, final_fee);
+		if info.class == DispatchClass::Operational || Privileged::contains(call) {
+			Band::get().saturating_add(priority)
+		} else {
+			priority.min(Band::get().saturating_sub(1))
+		}
+	}
+}
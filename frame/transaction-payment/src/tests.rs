@@ -27,7 +27,7 @@ use sp_runtime::{
 use frame_support::{
 	assert_noop, assert_ok,
 	dispatch::{DispatchClass, DispatchInfo, GetDispatchInfo, PostDispatchInfo},
-	traits::Currency,
+	traits::{ConstU64, Contains, Currency, OnFinalize},
 	weights::Weight,
 };
 use frame_system as system;
@@ -841,3 +841,121 @@ fn genesis_default_works() {
 		assert_eq!(<NextFeeMultiplier<Runtime>>::get(), Multiplier::saturating_from_integer(1));
 	});
 }
+
+fn set_fee_adjustment(target: Perquintill, variability: Multiplier, min: u32, max: u32) {
+	TargetBlockFullness::set(target);
+	AdjustmentVariable::set(variability);
+	MinimumMultiplier::set(Multiplier::saturating_from_integer(min));
+	MaximumMultiplier::set(Multiplier::saturating_from_integer(max));
+}
+
+#[test]
+fn query_fee_multiplier_info_works() {
+	ExtBuilder::default()
+		.byte_fee(10)
+		.with_initial_multiplier(Multiplier::saturating_from_rational(3, 2))
+		.build()
+		.execute_with(|| {
+			let variability = Multiplier::saturating_from_rational(1, 10);
+			set_fee_adjustment(Perquintill::from_percent(25), variability, 1, 2);
+
+			let info = TransactionPayment::query_fee_multiplier_info();
+			assert_eq!(info.multiplier, Multiplier::saturating_from_rational(3, 2));
+			assert_eq!(info.min_multiplier, Multiplier::one());
+			assert_eq!(info.max_multiplier, Multiplier::saturating_from_integer(2));
+			assert_eq!(info.target_block_fullness, Perquintill::from_percent(25));
+			assert_eq!(info.variability, variability);
+			assert_eq!(info.length_fee_per_byte, 10);
+
+			// The bounds are the multipliers following an empty and a full block.
+			let empty = <Runtime as Config>::FeeMultiplierUpdate::convert(info.multiplier);
+			<frame_system::Pallet<Runtime>>::set_block_consumed_resources(
+				Weight::from_parts(1024, 0),
+				0,
+			);
+			let full = <Runtime as Config>::FeeMultiplierUpdate::convert(info.multiplier);
+			assert_eq!(info.next_multiplier_floor, empty);
+			assert_eq!(info.next_multiplier_ceiling, full);
+			assert!(empty < info.multiplier && info.multiplier < full);
+		});
+}
+
+#[test]
+fn fee_multiplier_saturation_is_reported() {
+	ExtBuilder::default()
+		.with_initial_multiplier(Multiplier::saturating_from_rational(11, 10))
+		.build()
+		.execute_with(|| {
+			set_fee_adjustment(Perquintill::from_percent(25), Multiplier::one(), 1, 2);
+			System::set_block_number(1);
+
+			// An empty block drops the multiplier to its floor.
+			TransactionPayment::on_finalize(1);
+			assert_eq!(<NextFeeMultiplier<Runtime>>::get(), Multiplier::one());
+			System::assert_last_event(
+				Event::<Runtime>::FeeMultiplierAtFloor { multiplier: Multiplier::one() }.into(),
+			);
+
+			// Staying at the floor is not reported again.
+			System::reset_events();
+			TransactionPayment::on_finalize(1);
+			assert!(System::events().is_empty());
+
+			// A full block raises it to its ceiling.
+			System::set_block_consumed_resources(Weight::from_parts(1024, 0), 0);
+			TransactionPayment::on_finalize(1);
+			let ceiling = Multiplier::saturating_from_integer(2);
+			assert_eq!(<NextFeeMultiplier<Runtime>>::get(), ceiling);
+			System::assert_last_event(
+				Event::<Runtime>::FeeMultiplierAtCeiling { multiplier: ceiling }.into(),
+			);
+		});
+}
+
+#[test]
+fn constant_fee_multiplier_does_not_report_saturation() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_fee_adjustment(Perquintill::zero(), Multiplier::zero(), 2, 2);
+		System::set_block_number(1);
+
+		// A multiplier without variability is pinned to its bounds rather than saturating.
+		TransactionPayment::on_finalize(1);
+		assert_eq!(<NextFeeMultiplier<Runtime>>::get(), Multiplier::saturating_from_integer(2));
+		assert!(System::events().is_empty());
+	});
+}
+
+pub struct IsRemark;
+impl Contains<RuntimeCall> for IsRemark {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(call, RuntimeCall::System(frame_system::Call::remark { .. }))
+	}
+}
+
+#[test]
+fn priority_bands_put_privileged_transactions_first() {
+	type Bands = PriorityBands<TipPerResourcePriority, IsRemark, ConstU64<1_000>>;
+
+	ExtBuilder::default().build().execute_with(|| {
+		let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let normal = info_from_weight(Weight::from_parts(100, 0));
+		let operational = DispatchInfo { class: DispatchClass::Operational, ..normal };
+		let (len, fee) = (10, 20);
+		let inner = |call: &RuntimeCall, info: &DispatchInfo, tip: u64| {
+			<TipPerResourcePriority as PriorityCurve<Runtime>>::priority(call, info, len, tip, fee)
+		};
+		let banded = |call: &RuntimeCall, info: &DispatchInfo, tip: u64| {
+			<Bands as PriorityCurve<Runtime>>::priority(call, info, len, tip, fee)
+		};
+
+		// Other transactions keep their priority, up to the band.
+		assert!(inner(CALL, &normal, 1) < 1_000);
+		assert_eq!(banded(CALL, &normal, 1), inner(CALL, &normal, 1));
+		assert_eq!(banded(CALL, &normal, 1_000_000), 999);
+
+		// Operational extrinsics and privileged calls are moved above the band.
+		assert_eq!(banded(CALL, &operational, 1), 1_000 + inner(CALL, &operational, 1));
+		assert_eq!(banded(&remark, &normal, 0), 1_000 + inner(&remark, &normal, 0));
+		assert!(banded(&remark, &normal, 0) > banded(CALL, &normal, 1_000_000));
+	});
+}
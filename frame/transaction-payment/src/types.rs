@@ -23,11 +23,16 @@ use serde::{Deserialize, Serialize};
 
 use scale_info::TypeInfo;
 
-use sp_runtime::traits::{AtLeast32BitUnsigned, Zero};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, Zero},
+	Perquintill,
+};
 use sp_std::prelude::*;
 
 use frame_support::dispatch::DispatchClass;
 
+use crate::Multiplier;
+
 /// The base fee and adjusted weight and length fees constitute the _inclusion fee_.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
@@ -117,6 +122,33 @@ pub struct RuntimeDispatchInfo<Balance, Weight = frame_support::weights::Weight>
 	pub partial_fee: Balance,
 }
 
+/// Information about the fee multiplier and the length fee, explaining the fees of the current
+/// block and how far they can move with the next one.
+#[derive(Eq, PartialEq, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize, Clone))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+pub struct FeeMultiplierInfo<Balance> {
+	/// The multiplier applied to the weight fee of transactions in the current block.
+	pub multiplier: Multiplier,
+	/// The lowest value the multiplier can reach.
+	pub min_multiplier: Multiplier,
+	/// The highest value the multiplier can reach.
+	pub max_multiplier: Multiplier,
+	/// The multiplier of the next block if the current block stays empty.
+	pub next_multiplier_floor: Multiplier,
+	/// The multiplier of the next block if the current block becomes full.
+	pub next_multiplier_ceiling: Multiplier,
+	/// The block fullness at which the multiplier does not change.
+	pub target_block_fullness: Perquintill,
+	/// How quickly the multiplier reacts to the block fullness.
+	pub variability: Multiplier,
+	/// The length fee paid for every byte of an extrinsic.
+	#[cfg_attr(feature = "std", serde(with = "serde_balance"))]
+	pub length_fee_per_byte: Balance,
+}
+
 #[cfg(feature = "std")]
 mod serde_balance {
 	use serde::{Deserialize, Deserializer, Serializer};
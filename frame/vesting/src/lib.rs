@@ -72,7 +72,7 @@ use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{
 		AtLeast32BitUnsigned, BlockNumberProvider, Bounded, Convert, MaybeSerializeDeserialize,
-		One, Saturating, StaticLookup, Zero,
+		One, Saturating, StaticLookup, UniqueSaturatedFrom, UniqueSaturatedInto, Zero,
 	},
 	DispatchError, RuntimeDebug,
 };
@@ -464,6 +464,16 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
+	/// The block at which all of `who`'s vesting schedules will have fully vested, or `None` if
+	/// `who` has no vesting schedules.
+	pub fn vesting_end(who: &T::AccountId) -> Option<BlockNumberFor<T>> {
+		Self::vesting(who)?
+			.iter()
+			.map(|schedule| schedule.ending_block_as_balance::<T::BlockNumberToBalance>())
+			.max()
+			.map(|end| BlockNumberFor::<T>::unique_saturated_from(end.unique_saturated_into()))
+	}
+
 	// Create a new `VestingInfo`, based off of two other `VestingInfo`s.
 	// NOTE: We assume both schedules have had funds unlocked up through the current block.
 	fn merge_vesting_info(
@@ -774,3 +784,16 @@ where
 		Ok(())
 	}
 }
+
+sp_api::decl_runtime_apis! {
+	/// The runtime API for querying the vesting state of accounts.
+	pub trait VestingApi<AccountId, BlockNumber>
+	where
+		AccountId: codec::Codec,
+		BlockNumber: codec::Codec,
+	{
+		/// The block at which all of `who`'s vesting schedules will have fully vested, if it has
+		/// any.
+		fn vesting_end(who: AccountId) -> Option<BlockNumber>;
+	}
+}
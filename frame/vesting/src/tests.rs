@@ -616,6 +616,36 @@ fn merge_schedules_that_have_not_started() {
 	});
 }
 
+#[test]
+fn vesting_end_works() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		// Account 4 has no vesting schedules.
+		assert_eq!(Vesting::vesting_end(&4), None);
+		// Account 1 vests 128 per block from block 0.
+		assert_eq!(Vesting::vesting_end(&1), Some(10));
+		// Account 2 vests `ED` per block from block 10.
+		assert_eq!(Vesting::vesting_end(&2), Some(30));
+
+		// A schedule that ends later extends the end block.
+		let sched1 = VestingInfo::new(
+			ED * 10,
+			ED, // Vest over 10 blocks.
+			25,
+		);
+		assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, sched1));
+		assert_eq!(Vesting::vesting_end(&2), Some(35));
+
+		// A schedule that ends earlier does not.
+		let sched2 = VestingInfo::new(
+			ED * 2,
+			ED, // Vest over 2 blocks.
+			5,
+		);
+		assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, sched2));
+		assert_eq!(Vesting::vesting_end(&2), Some(35));
+	});
+}
+
 #[test]
 fn merge_ongoing_schedules() {
 	// Merging two schedules that have started will vest both before merging.
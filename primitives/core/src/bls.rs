@@ -333,6 +333,26 @@ impl<T> ByteArray for Signature<T> {
 	const LEN: usize = SIGNATURE_SERIALIZED_SIZE;
 }
 
+impl<T> PassByInner for Signature<T> {
+	type Inner = [u8; SIGNATURE_SERIALIZED_SIZE];
+
+	fn into_inner(self) -> Self::Inner {
+		self.inner
+	}
+
+	fn inner(&self) -> &Self::Inner {
+		&self.inner
+	}
+
+	fn from_inner(inner: Self::Inner) -> Self {
+		Self { inner, _phantom: PhantomData }
+	}
+}
+
+impl<T> PassBy for Signature<T> {
+	type PassBy = pass_by::Inner<Self, [u8; SIGNATURE_SERIALIZED_SIZE]>;
+}
+
 impl<T> TryFrom<&[u8]> for Signature<T> {
 	type Error = ();
 
@@ -29,10 +29,16 @@
 //!
 //! Providing externalities with empty storage and putting `GenesisConfig` into storage allows to
 //! catch and build the raw storage of `GenesisConfig` which is the foundation for genesis block.
+//!
+//! Since version 2 the runtime may also ship a set of named presets, i.e. `GenesisConfig` patches
+//! for well-known networks (e.g. `development` or `local_testnet`).
 
 /// The result type alias, used in build methods. `Err` contains formatted error message.
 pub type Result = core::result::Result<(), sp_runtime::RuntimeString>;
 
+/// The type representing the name of a genesis config preset.
+pub type PresetId = sp_runtime::RuntimeString;
+
 sp_api::decl_runtime_apis! {
 	/// API to interact with GenesisConfig for the runtime
 	pub trait GenesisBuilder {
@@ -50,5 +56,17 @@ sp_api::decl_runtime_apis! {
 		///
 		/// Please note that provided json blob must contain all `GenesisConfig` fields, no defaults will be used.
 		fn build_config(json: sp_std::vec::Vec<u8>) -> Result;
+
+		/// Returns a JSON blob representation of the built-in `GenesisConfig` identified by `id`.
+		///
+		/// If `id` is `None` the function returns the JSON blob representation of the default
+		/// `GenesisConfig` struct of the runtime. The returned blob of a named preset is a patch
+		/// which is applied on top of the default config. `None` is returned for unknown ids.
+		#[api_version(2)]
+		fn get_preset(id: Option<PresetId>) -> Option<sp_std::vec::Vec<u8>>;
+
+		/// Returns a list of identifiers for available builtin `GenesisConfig` presets.
+		#[api_version(2)]
+		fn preset_names() -> sp_std::vec::Vec<PresetId>;
 	}
 }
@@ -106,7 +106,7 @@ use sp_core::{
 };
 
 #[cfg(feature = "bls-experimental")]
-use sp_core::{bls377, ecdsa_bls377};
+use sp_core::{bls377, bls381, ecdsa_bls377};
 
 #[cfg(feature = "std")]
 use sp_trie::{LayoutV0, LayoutV1, TrieConfiguration};
@@ -1192,6 +1192,19 @@ pub trait Crypto {
 		Ok(pubkey.serialize())
 	}
 
+	/// Decompress a SECP256k1 public key.
+	///
+	/// - `pubkey` is the 33-byte compressed public key, as in `ecdsa::Public`.
+	///
+	/// Returns `None` if the public key is invalid, otherwise the 64-byte uncompressed pubkey
+	/// (doesn't include the 0x04 prefix), e.g. to derive an Ethereum address from it.
+	fn secp256k1_ecdsa_decompress(pubkey: &[u8; 33]) -> Option<[u8; 64]> {
+		let pubkey = secp256k1::PublicKey::from_slice(pubkey).ok()?;
+		let mut res = [0u8; 64];
+		res.copy_from_slice(&pubkey.serialize_uncompressed()[1..]);
+		Some(res)
+	}
+
 	/// Generate an `bls12-377` key for the given key type using an optional `seed` and
 	/// store it in the keystore.
 	///
@@ -1207,6 +1220,55 @@ pub trait Crypto {
 			.expect("`bls377_generate` failed")
 	}
 
+	/// Returns all `bls12-381` public keys for the given key id from the keystore.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_public_keys(&mut self, id: KeyTypeId) -> Vec<bls381::Public> {
+		self.extension::<KeystoreExt>()
+			.expect("No `keystore` associated for the current context!")
+			.bls381_public_keys(id)
+	}
+
+	/// Generate a `bls12-381` key for the given key type using an optional `seed` and
+	/// store it in the keystore.
+	///
+	/// The `seed` needs to be a valid utf8.
+	///
+	/// Returns the public key.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_generate(&mut self, id: KeyTypeId, seed: Option<Vec<u8>>) -> bls381::Public {
+		let seed = seed.as_ref().map(|s| std::str::from_utf8(s).expect("Seed is valid utf8!"));
+		self.extension::<KeystoreExt>()
+			.expect("No `keystore` associated for the current context!")
+			.bls381_generate_new(id, seed)
+			.expect("`bls381_generate` failed")
+	}
+
+	/// Sign the given `msg` with the `bls12-381` key that corresponds to the given public key
+	/// and key type in the keystore.
+	///
+	/// Returns the signature.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_sign(
+		&mut self,
+		id: KeyTypeId,
+		pub_key: &bls381::Public,
+		msg: &[u8],
+	) -> Option<bls381::Signature> {
+		self.extension::<KeystoreExt>()
+			.expect("No `keystore` associated for the current context!")
+			.bls381_sign(id, pub_key, msg)
+			.ok()
+			.flatten()
+	}
+
+	/// Verify a `bls12-381` signature.
+	///
+	/// Returns `true` when the verification was successful.
+	#[cfg(feature = "bls-experimental")]
+	fn bls381_verify(sig: &bls381::Signature, msg: &[u8], pub_key: &bls381::Public) -> bool {
+		bls381::Pair::verify(sig, msg, pub_key)
+	}
+
 	/// Generate an `(ecdsa,bls12-377)` key for the given key type using an optional `seed` and
 	/// store it in the keystore.
 	///
@@ -1973,4 +2035,37 @@ mod tests {
 			));
 		});
 	}
+
+	#[test]
+	fn secp256k1_ecdsa_decompress_works() {
+		let pair = ecdsa::Pair::from_string("//Alice", None).unwrap();
+		let msg = [1u8; 32];
+		let signature = pair.sign_prehashed(&msg);
+		let uncompressed = crypto::secp256k1_ecdsa_recover(&signature.0, &msg)
+			.ok()
+			.expect("the signature recovers to the key of the pair");
+
+		assert_eq!(crypto::secp256k1_ecdsa_decompress(&pair.public().0), Some(uncompressed));
+		assert_eq!(crypto::secp256k1_ecdsa_decompress(&[0u8; 33]), None);
+	}
+	#[cfg(feature = "bls-experimental")]
+	#[test]
+	fn bls381_generate_sign_and_verify_round_trip() {
+		let mut ext = BasicExternalities::default();
+		ext.register_extension(KeystoreExt::new(sp_keystore::testing::MemoryKeystore::new()));
+
+		ext.execute_with(|| {
+			let key_type = KeyTypeId(*b"test");
+			let public = crypto::bls381_generate(key_type, None);
+			assert_eq!(crypto::bls381_public_keys(key_type), vec![public.clone()]);
+
+			let msg = b"bls381 round trip";
+			let signature = crypto::bls381_sign(key_type, &public, msg).unwrap();
+			assert!(crypto::bls381_verify(&signature, msg, &public));
+			assert!(!crypto::bls381_verify(&signature, b"another message", &public));
+
+			let other = crypto::bls381_generate(key_type, Some(b"//Other".to_vec()));
+			assert!(!crypto::bls381_verify(&signature, msg, &other));
+		});
+	}
 }
@@ -400,6 +400,15 @@ pub trait Keystore: Send + Sync {
 	/// Returns `true` iff all private keys could be found.
 	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool;
 
+	/// Remove the private key for the given public key and key type.
+	///
+	/// Returns `true` if a key was removed.
+	///
+	/// Keystores that can't remove keys return an error, which is the default.
+	fn remove(&self, _key_type: KeyTypeId, _public: &[u8]) -> Result<bool, Error> {
+		Err(Error::Other("Removing keys is not supported by this keystore".into()))
+	}
+
 	/// Convenience method to sign a message using the given key type and a raw public key
 	/// for secret lookup.
 	///
@@ -714,6 +723,10 @@ impl<T: Keystore + ?Sized> Keystore for Arc<T> {
 	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
 		(**self).has_keys(public_keys)
 	}
+
+	fn remove(&self, key_type: KeyTypeId, public: &[u8]) -> Result<bool, Error> {
+		(**self).remove(key_type, public)
+	}
 }
 
 /// A shared pointer to a keystore implementation.
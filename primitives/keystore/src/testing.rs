@@ -383,6 +383,15 @@ impl Keystore for MemoryKeystore {
 			.iter()
 			.all(|(k, t)| self.keys.read().get(t).and_then(|s| s.get(k)).is_some())
 	}
+
+	fn remove(&self, key_type: KeyTypeId, public: &[u8]) -> Result<bool, Error> {
+		Ok(self
+			.keys
+			.write()
+			.get_mut(&key_type)
+			.and_then(|keys| keys.remove(public))
+			.is_some())
+	}
 }
 
 impl Into<KeystorePtr> for MemoryKeystore {
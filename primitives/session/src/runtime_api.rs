@@ -34,5 +34,14 @@ sp_api::decl_runtime_apis! {
 		///
 		/// Returns the list of public raw public keys + key type.
 		fn decode_session_keys(encoded: Vec<u8>) -> Option<Vec<(Vec<u8>, sp_core::crypto::KeyTypeId)>>;
+
+		/// Generate a proof that the owner of the SCALE encoded session `keys` approves of them
+		/// being registered by `owner`, a SCALE encoded account.
+		///
+		/// The private keys need to be available within the keystore exposed via runtime
+		/// externalities. Returns `None` if the keys can not be decoded or a private key is
+		/// missing.
+		#[api_version(2)]
+		fn generate_ownership_proof(owner: Vec<u8>, keys: Vec<u8>) -> Option<Vec<u8>>;
 	}
 }
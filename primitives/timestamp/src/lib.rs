@@ -143,6 +143,12 @@ pub enum InherentError {
 	/// The block timestamp is too far in the future.
 	#[cfg_attr(feature = "std", error("The timestamp of the block is too far in the future."))]
 	TooFarInFuture,
+	/// The block timestamp drifts too far from the timestamp expected for the block.
+	#[cfg_attr(
+		feature = "std",
+		error("The timestamp of the block is too far from the expected timestamp.")
+	)]
+	TooFarFromExpected,
 }
 
 impl IsFatalError for InherentError {
@@ -150,6 +156,7 @@ impl IsFatalError for InherentError {
 		match self {
 			InherentError::TooEarly => true,
 			InherentError::TooFarInFuture => true,
+			InherentError::TooFarFromExpected => true,
 		}
 	}
 }
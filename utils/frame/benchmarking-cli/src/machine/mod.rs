@@ -20,7 +20,11 @@
 
 pub mod hardware;
 
-use std::{boxed::Box, fs, path::Path};
+use std::{
+	boxed::Box,
+	fs,
+	path::{Path, PathBuf},
+};
 
 use clap::Parser;
 use comfy_table::{Row, Table};
@@ -79,6 +83,13 @@ pub struct MachineCmd {
 	/// Time limit for each disk benchmark.
 	#[arg(long, default_value_t = 5.0, value_name = "SECONDS")]
 	pub disk_duration: f32,
+
+	/// JSON file with the hardware requirements to check against.
+	///
+	/// Replaces the reference hardware requirements of the node. Uses the same format as
+	/// `reference_hardware.json`, which lists the minimum score of each metric.
+	#[arg(long, value_name = "PATH")]
+	pub requirements: Option<PathBuf>,
 }
 
 /// Helper for the result of a concrete benchmark.
@@ -113,6 +124,10 @@ impl MachineCmd {
 	/// Execute the benchmark and print the results.
 	pub fn run(&self, cfg: &Configuration, requirements: Requirements) -> Result<()> {
 		self.validate_args()?;
+		let requirements = match &self.requirements {
+			Some(path) => Self::load_requirements(path)?,
+			None => requirements,
+		};
 		// Ensure that the dir exists since the node is not started to take care of it.
 		let dir = cfg.database.path().ok_or("No DB directory provided")?;
 		fs::create_dir_all(dir)?;
@@ -126,6 +141,19 @@ impl MachineCmd {
 		self.print_summary(requirements, results)
 	}
 
+	/// Loads custom hardware requirements from a JSON file.
+	fn load_requirements(path: &Path) -> Result<Requirements> {
+		let raw = fs::read(path)
+			.map_err(|e| format!("Failed to read requirements from {:?}: {}", path, e))?;
+		let requirements: Requirements = serde_json::from_slice(&raw)
+			.map_err(|e| format!("Failed to parse requirements from {:?}: {}", path, e))?;
+		if requirements.0.is_empty() {
+			return Err(format!("No requirements found in {:?}", path).into())
+		}
+		info!("Using the hardware requirements from {:?}", path);
+		Ok(requirements)
+	}
+
 	/// Benchmarks a specific metric of the hardware and judges the resulting score.
 	fn run_benchmark(&self, requirement: &Requirement, dir: &Path) -> Result<BenchResult> {
 		// Dispatch the concrete function from `sc-sysinfo`.
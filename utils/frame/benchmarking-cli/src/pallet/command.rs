@@ -23,6 +23,7 @@ use frame_benchmarking::{
 };
 use frame_support::traits::StorageInfo;
 use linked_hash_map::LinkedHashMap;
+use remote_externalities::{Builder, Mode, OfflineConfig, OnlineConfig, SnapshotConfig};
 use sc_cli::{execution_method_from_cli, CliConfiguration, Result, SharedParams};
 use sc_client_db::BenchmarkingState;
 use sc_executor::{HeapAllocStrategy, WasmExecutor, DEFAULT_HEAP_ALLOC_STRATEGY};
@@ -33,16 +34,23 @@ use sp_core::{
 		testing::{TestOffchainExt, TestTransactionPoolExt},
 		OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
 	},
+	storage::{well_known_keys, ChildInfo, Storage, StorageChild},
 	traits::{CallContext, ReadRuntimeVersionExt},
+	H256,
 };
 use sp_externalities::Extensions;
 use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
-use sp_runtime::traits::Hash;
-use sp_state_machine::StateMachine;
+use sp_runtime::{
+	generic,
+	traits::{BlakeTwo256, Hash},
+	OpaqueExtrinsic,
+};
+use sp_state_machine::{Backend, IterArgs, StateMachine};
 use std::{
 	collections::{BTreeMap, BTreeSet, HashMap},
 	fmt::Debug,
 	fs,
+	path::PathBuf,
 	str::FromStr,
 	time,
 };
@@ -50,6 +58,9 @@ use std::{
 /// Logging target
 const LOG_TARGET: &'static str = "frame::benchmark::pallet";
 
+/// Number of storage keys listed in the summary as dominating the database accesses.
+const DOMINANT_KEYS: usize = 5;
+
 /// The inclusive range of a component.
 #[derive(Serialize, Debug, Clone, Eq, PartialEq)]
 pub(crate) struct ComponentRange {
@@ -85,6 +96,11 @@ impl FromStr for PovEstimationMode {
 	}
 }
 
+/// Block type used to fetch the state of a live chain.
+///
+/// Only the hash of the block is needed, which is a `H256` for all chains we support.
+type OpaqueBlock = generic::Block<generic::Header<u32, BlakeTwo256>, OpaqueExtrinsic>;
+
 /// Maps (pallet, benchmark) -> ((pallet, storage) -> PovEstimationMode)
 pub(crate) type PovModesMap =
 	HashMap<(Vec<u8>, Vec<u8>), HashMap<(String, String), PovEstimationMode>>;
@@ -202,7 +218,10 @@ impl PalletCmd {
 		let extrinsic_split: Vec<&str> = extrinsic.split(',').collect();
 		let extrinsics: Vec<_> = extrinsic_split.iter().map(|x| x.trim().as_bytes()).collect();
 
-		let genesis_storage = spec.build_storage()?;
+		let mut genesis_storage = spec.build_storage()?;
+		if self.state_uri.is_some() || self.state_snapshot.is_some() {
+			genesis_storage = config.tokio_handle.block_on(self.live_storage(genesis_storage))?;
+		}
 		let mut changes = Default::default();
 		let cache_size = Some(self.database_cache_size as usize);
 		let state_with_tracking = BenchmarkingState::<Hasher>::new(
@@ -517,6 +536,68 @@ impl PalletCmd {
 		self.output(&batches, &storage_info, &component_ranges, pov_modes)
 	}
 
+	/// Fetch the state of a live chain, or load it from a snapshot.
+	///
+	/// The runtime code of the `genesis` state is kept, since the code of the live chain is not
+	/// compiled with the `runtime-benchmarks` feature.
+	async fn live_storage(&self, mut genesis: Storage) -> Result<Storage> {
+		let code = genesis
+			.top
+			.remove(well_known_keys::CODE)
+			.ok_or("The chain spec does not contain any runtime code")?;
+
+		let offline = |path: &PathBuf| OfflineConfig { state_snapshot: SnapshotConfig::new(path) };
+		let mode = match &self.state_uri {
+			Some(uri) => {
+				let at = self
+					.state_at
+					.as_ref()
+					.map(|at| array_bytes::hex_n_into::<_, H256, 32>(at))
+					.transpose()
+					.map_err(|e| format!("Invalid block hash: {:?}", e))?;
+				let online = OnlineConfig {
+					at,
+					transport: uri.clone().into(),
+					state_snapshot: self.state_snapshot.as_ref().map(SnapshotConfig::new),
+					..Default::default()
+				};
+				match &self.state_snapshot {
+					Some(path) if path.exists() => Mode::OfflineOrElseOnline(offline(path), online),
+					_ => Mode::Online(online),
+				}
+			},
+			None => Mode::Offline(offline(
+				self.state_snapshot.as_ref().expect("Only called with a uri or snapshot; qed"),
+			)),
+		};
+
+		let ext = Builder::<OpaqueBlock>::new().mode(mode).build().await?;
+		log::info!(target: LOG_TARGET, "Loaded the live state at block {:?}", ext.block_hash);
+
+		let backend = ext.as_backend();
+		let mut storage = Storage::default();
+		for pair in backend.pairs(Default::default())? {
+			let (key, value) = pair?;
+			match key.strip_prefix(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+				Some(child_key) => {
+					let child_info = ChildInfo::new_default(child_key);
+					let mut args = IterArgs::default();
+					args.child_info = Some(child_info.clone());
+					let data = backend.pairs(args)?.collect::<std::result::Result<_, _>>()?;
+					storage
+						.children_default
+						.insert(child_key.to_vec(), StorageChild { data, child_info });
+				},
+				None => {
+					storage.top.insert(key, value);
+				},
+			}
+		}
+		storage.top.insert(well_known_keys::CODE.to_vec(), code);
+
+		Ok(storage)
+	}
+
 	fn output(
 		&self,
 		batches: &[BenchmarkBatchSplitResults],
@@ -646,6 +727,12 @@ impl PalletCmd {
 					println!("{}", comment);
 				}
 				println!();
+
+				println!("Dominant Storage Keys\n========");
+				for key in writer::dominant_keys(&batch.db_results, storage_info, DOMINANT_KEYS) {
+					println!("{}", key);
+				}
+				println!();
 			}
 
 			// Conduct analysis.
@@ -174,6 +174,28 @@ pub struct PalletCmd {
 	#[arg(long = "db-cache", value_name = "MiB", default_value_t = 1024)]
 	pub database_cache_size: u32,
 
+	/// Run the benchmarks against the state of a live chain, fetched from the node at the given
+	/// URI, instead of the genesis state of the chain spec.
+	///
+	/// The runtime code is still taken from the chain spec, since it must be compiled with the
+	/// `runtime-benchmarks` feature. Its storage layout must match the one of the live chain.
+	#[arg(long, value_name = "URI")]
+	pub state_uri: Option<String>,
+
+	/// The block hash at which to fetch the live state.
+	///
+	/// If not provided, the latest finalized block is used.
+	#[arg(long, value_name = "HASH", requires = "state_uri")]
+	pub state_at: Option<String>,
+
+	/// Path of a state snapshot to run the benchmarks against.
+	///
+	/// Together with `--state-uri`, the snapshot is created from the live state if it does not
+	/// exist yet, and re-used otherwise. Snapshots created by `try-runtime create-snapshot` can be
+	/// used as well.
+	#[arg(long, value_name = "PATH")]
+	pub state_snapshot: Option<PathBuf>,
+
 	/// List and print available benchmarks in a csv-friendly format.
 	///
 	/// NOTE: `num_args` and `require_equals` are required to allow `--list`
@@ -741,6 +741,40 @@ pub(crate) fn process_storage_results(
 	comments
 }
 
+/// Lists the storage keys with the most reads and writes in the run with the highest component
+/// values, at most `limit` of them.
+///
+/// Whitelisted keys are included and marked as such, since they are not charged but still show
+/// which keys would dominate the database time if they were not already cached.
+pub(crate) fn dominant_keys(
+	results: &[BenchmarkResult],
+	storage_info: &[StorageInfo],
+	limit: usize,
+) -> Vec<String> {
+	let Some(result) = results.last() else { return Vec::new() };
+
+	result
+		.keys
+		.iter()
+		.filter(|(_, reads, writes, _)| reads + writes > 0)
+		.sorted_by_key(|(_, reads, writes, _)| std::cmp::Reverse(reads + writes))
+		.take(limit)
+		.map(|(key, reads, writes, whitelisted)| {
+			let prefix = &key[..key.len().min(32)];
+			let name = match storage_info.iter().find(|info| info.prefix == prefix) {
+				Some(info) => format!(
+					"`{}::{}`",
+					String::from_utf8_lossy(&info.pallet_name),
+					String::from_utf8_lossy(&info.storage_name),
+				),
+				None => format!("UNKNOWN KEY `0x{}`", HexDisplay::from(key)),
+			};
+			let whitelisted = if *whitelisted { " (whitelisted)" } else { "" };
+			format!("{} (r:{} w:{}){}", name, reads, writes, whitelisted)
+		})
+		.collect()
+}
+
 /// The PoV overhead when reading a key the first time out of a map with `max_values` entries.
 fn single_read_pov_overhead(max_values: Option<u32>, worst_case_map_values: u32) -> u32 {
 	let max_values = max_values.unwrap_or(worst_case_map_values);
@@ -1333,6 +1367,28 @@ mod test {
 		}
 	}
 
+	#[test]
+	fn dominant_keys_works() {
+		let result = BenchmarkResult {
+			keys: vec![
+				(b"bounded".to_vec(), 1, 1, false),
+				(b"unknown".to_vec(), 0, 0, false),
+				(b"hot".to_vec(), 5, 0, true),
+			],
+			..Default::default()
+		};
+
+		assert_eq!(
+			dominant_keys(&[Default::default(), result.clone()], &test_storage_info(), 5),
+			vec![
+				format!("UNKNOWN KEY `0x{}` (r:5 w:0) (whitelisted)", HexDisplay::from(b"hot")),
+				"`bounded::bounded` (r:1 w:1)".to_string(),
+			],
+		);
+		assert_eq!(dominant_keys(&[result], &test_storage_info(), 1).len(), 1);
+		assert!(dominant_keys(&[], &test_storage_info(), 1).is_empty());
+	}
+
 	#[test]
 	fn easy_log_16_works() {
 		assert_eq!(easy_log_16(0), 0);
@@ -26,7 +26,7 @@ use sp_state_machine::Storage;
 use sp_storage::{ChildInfo, ChildType, PrefixedStorageKey, StateVersion};
 
 use clap::{Args, Parser};
-use log::info;
+use log::{info, warn};
 use rand::prelude::*;
 use serde::Serialize;
 use sp_runtime::generic::BlockId;
@@ -96,6 +96,14 @@ pub struct StorageParams {
 	#[arg(long, default_value_t = 1)]
 	pub warmups: u32,
 
+	/// Measure with cold caches instead of warming them up first.
+	///
+	/// Skips the warmups and drops the page cache of the OS before each benchmark, such that
+	/// every access has to hit the disk. Dropping the page cache is only supported on Linux and
+	/// requires root privileges.
+	#[arg(long, conflicts_with = "warmups")]
+	pub cold: bool,
+
 	/// The `StateVersion` to use. Substrate `--dev` should use `V1` and Polkadot `V0`.
 	/// Selecting the wrong version can corrupt the DB.
 	#[arg(long, value_parser = clap::value_parser!(u8).range(0..=1))]
@@ -139,7 +147,9 @@ impl StorageCmd {
 		template.set_block_number(block_id.to_string());
 
 		if !self.params.skip_read {
-			self.bench_warmup(&client)?;
+			if !self.params.cold {
+				self.bench_warmup(&client)?;
+			}
 			let record = self.bench_read(client.clone())?;
 			if let Some(path) = &self.params.json_read_path {
 				record.save_json(&cfg, path, "read")?;
@@ -150,7 +160,9 @@ impl StorageCmd {
 		}
 
 		if !self.params.skip_write {
-			self.bench_warmup(&client)?;
+			if !self.params.cold {
+				self.bench_warmup(&client)?;
+			}
 			let record = self.bench_write(client, db, storage)?;
 			if let Some(path) = &self.params.json_write_path {
 				record.save_json(&cfg, path, "write")?;
@@ -182,6 +194,20 @@ impl StorageCmd {
 		None
 	}
 
+	/// Drop the page cache of the OS when benchmarking with `--cold`.
+	///
+	/// Must be called after preparing the keys, since that already pulls them into the cache.
+	pub(crate) fn clear_caches(&self) {
+		if !self.params.cold {
+			return
+		}
+
+		info!("Dropping the OS page cache");
+		if let Err(err) = drop_page_cache() {
+			warn!("Failed to drop the OS page cache, results may not be fully cold: {}", err);
+		}
+	}
+
 	/// Run some rounds of the (read) benchmark as warmup.
 	/// See `frame_benchmarking_cli::storage::read::bench_read` for detailed comments.
 	fn bench_warmup<B, BA, C>(&self, client: &Arc<C>) -> Result<()>
@@ -209,6 +235,18 @@ impl StorageCmd {
 	}
 }
 
+/// Flush dirty pages to disk and drop the page cache of the OS.
+#[cfg(target_os = "linux")]
+fn drop_page_cache() -> std::io::Result<()> {
+	std::process::Command::new("sync").status()?;
+	std::fs::write("/proc/sys/vm/drop_caches", "3\n")
+}
+
+#[cfg(not(target_os = "linux"))]
+fn drop_page_cache() -> std::io::Result<()> {
+	Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "only supported on Linux"))
+}
+
 // Boilerplate
 impl CliConfiguration for StorageCmd {
 	fn shared_params(&self) -> &SharedParams {
@@ -44,6 +44,7 @@ impl StorageCmd {
 		let mut keys: Vec<_> = client.storage_keys(best_hash, None, None)?.collect();
 		let (mut rng, _) = new_rng(None);
 		keys.shuffle(&mut rng);
+		self.clear_caches();
 
 		let mut child_nodes = Vec::new();
 		// Interesting part here:
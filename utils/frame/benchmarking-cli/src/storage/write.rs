@@ -64,6 +64,7 @@ impl StorageCmd {
 		let mut kvs: Vec<_> = trie.pairs(Default::default())?.collect();
 		let (mut rng, _) = new_rng(None);
 		kvs.shuffle(&mut rng);
+		self.clear_caches();
 		info!("Writing {} keys", kvs.len());
 
 		let mut child_nodes = Vec::new();
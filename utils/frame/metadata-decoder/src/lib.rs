@@ -0,0 +1,410 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding of extrinsics and events of any runtime, driven by its metadata.
+//!
+//! A [`Decoder`] is created from the SCALE encoded metadata, as returned by the
+//! `Metadata_metadata` runtime API or the `state_getMetadata` RPC. Metadata V14 and V15 are
+//! supported. Extrinsics and events are decoded into the name of the pallet and the call or
+//! event, its documentation and its fields as dynamically typed [`Value`]s. All decoded types
+//! serialize into human readable JSON.
+
+mod value;
+
+pub use value::{Composite, Value, MAX_DEPTH};
+
+use codec::{Compact, Decode};
+use frame_metadata::{v14::StorageEntryType, RuntimeMetadata, RuntimeMetadataPrefixed};
+use scale_info::{form::PortableForm, PortableRegistry, Type, TypeDef};
+use serde::Serialize;
+use value::ValueDecoder;
+
+/// Errors that can occur while decoding.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The metadata could not be decoded or misses required information.
+	#[error("Invalid metadata: {0}")]
+	Metadata(String),
+	/// A type is referenced that is not part of the metadata.
+	#[error("Type {0} is not part of the metadata")]
+	UnknownType(u32),
+	/// The encoded variant index is not part of the enum.
+	#[error("Unknown variant index {0}")]
+	UnknownVariant(u8),
+	/// The extrinsic format version does not match the metadata.
+	#[error("Unsupported extrinsic version {0}")]
+	UnsupportedVersion(u8),
+	/// Values of this kind of type cannot be decoded.
+	#[error("Decoding {0} is not supported")]
+	Unsupported(&'static str),
+	/// Values are nested deeper than [`MAX_DEPTH`].
+	#[error("Values are nested too deeply")]
+	TooDeep,
+	/// Not all bytes of the input were consumed.
+	#[error("{0} bytes left after decoding")]
+	TrailingBytes(usize),
+	/// The input is not a valid encoding.
+	#[error(transparent)]
+	Codec(#[from] codec::Error),
+}
+
+/// A variant of the outer call or event enum of a runtime.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PalletVariant {
+	/// Name of the pallet, as given in `construct_runtime!`.
+	pub pallet: String,
+	/// Name of the call or event.
+	pub name: String,
+	/// Documentation of the call or event.
+	pub docs: Vec<String>,
+	/// The fields of the call or event.
+	pub fields: Composite,
+}
+
+impl std::fmt::Display for PalletVariant {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "{}::{}", self.pallet, self.name)
+	}
+}
+
+/// The signature of a signed extrinsic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Signature {
+	/// The address of the signer.
+	pub address: Value,
+	/// The signature itself.
+	pub signature: Value,
+	/// The extra data of each signed extension, by identifier.
+	pub extra: Composite,
+}
+
+/// A decoded extrinsic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Extrinsic {
+	/// The signature, if the extrinsic is signed.
+	pub signature: Option<Signature>,
+	/// The call to dispatch.
+	pub call: PalletVariant,
+}
+
+/// A decoded record of the `System::Events` storage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EventRecord {
+	/// The phase of the block in which the event was deposited.
+	pub phase: Value,
+	/// The event itself.
+	pub event: PalletVariant,
+	/// The topics of the event.
+	pub topics: Value,
+}
+
+/// Decodes extrinsics and events according to the metadata of a runtime.
+pub struct Decoder {
+	types: PortableRegistry,
+	version: u8,
+	address_ty: u32,
+	call_ty: u32,
+	signature_ty: u32,
+	/// Identifier and extra type of each signed extension.
+	extensions: Vec<(String, u32)>,
+	/// The type of the `System::Events` storage value.
+	events_ty: Option<u32>,
+}
+
+impl Decoder {
+	/// Create a new decoder from SCALE encoded [`RuntimeMetadataPrefixed`].
+	pub fn new(metadata: &[u8]) -> Result<Self, Error> {
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+			.map_err(|e| Error::Metadata(e.to_string()))?;
+
+		match metadata.1 {
+			RuntimeMetadata::V14(m) => {
+				let extrinsic_ty = m
+					.types
+					.resolve(m.extrinsic.ty.id)
+					.ok_or(Error::UnknownType(m.extrinsic.ty.id))?;
+				let param = |name: &str| {
+					extrinsic_ty
+						.type_params
+						.iter()
+						.find(|p| p.name == name)
+						.and_then(|p| p.ty)
+						.map(|ty| ty.id)
+						.ok_or_else(|| {
+							Error::Metadata(format!("Missing extrinsic type parameter {}", name))
+						})
+				};
+				let (address_ty, call_ty, signature_ty) =
+					(param("Address")?, param("Call")?, param("Signature")?);
+				let events_ty = m
+					.pallets
+					.into_iter()
+					.find(|p| p.name == "System")
+					.and_then(|p| p.storage)
+					.and_then(|s| events_ty(s.entries.into_iter().map(|e| (e.name, e.ty))));
+
+				Ok(Self {
+					version: m.extrinsic.version,
+					address_ty,
+					call_ty,
+					signature_ty,
+					extensions: m
+						.extrinsic
+						.signed_extensions
+						.into_iter()
+						.map(|e| (e.identifier, e.ty.id))
+						.collect(),
+					events_ty,
+					types: m.types,
+				})
+			},
+			RuntimeMetadata::V15(m) => {
+				let events_ty = m
+					.pallets
+					.into_iter()
+					.find(|p| p.name == "System")
+					.and_then(|p| p.storage)
+					.and_then(|s| events_ty(s.entries.into_iter().map(|e| (e.name, e.ty))));
+
+				Ok(Self {
+					version: m.extrinsic.version,
+					address_ty: m.extrinsic.address_ty.id,
+					call_ty: m.extrinsic.call_ty.id,
+					signature_ty: m.extrinsic.signature_ty.id,
+					extensions: m
+						.extrinsic
+						.signed_extensions
+						.into_iter()
+						.map(|e| (e.identifier, e.ty.id))
+						.collect(),
+					events_ty,
+					types: m.types,
+				})
+			},
+			_ => Err(Error::Metadata("Only metadata V14 and V15 are supported".into())),
+		}
+	}
+
+	/// Decode an extrinsic, encoded with its length prefix as it appears in a block body.
+	pub fn decode_extrinsic(&self, mut bytes: &[u8]) -> Result<Extrinsic, Error> {
+		let input = &mut bytes;
+		let len = Compact::<u32>::decode(input)?.0 as usize;
+		if len != input.len() {
+			return Err(codec::Error::from("Invalid extrinsic length prefix").into())
+		}
+
+		let version = u8::decode(input)?;
+		if version & 0b0111_1111 != self.version {
+			return Err(Error::UnsupportedVersion(version & 0b0111_1111))
+		}
+
+		let values = ValueDecoder(&self.types);
+		let signature = if version & 0b1000_0000 != 0 {
+			Some(Signature {
+				address: values.decode(self.address_ty, input)?,
+				signature: values.decode(self.signature_ty, input)?,
+				extra: Composite(
+					self.extensions
+						.iter()
+						.map(|(id, ty)| Ok((Some(id.clone()), values.decode(*ty, input)?)))
+						.collect::<Result<_, Error>>()?,
+				),
+			})
+		} else {
+			None
+		};
+		let call = self.decode_pallet_variant(self.call_ty, input)?;
+
+		match input.len() {
+			0 => Ok(Extrinsic { signature, call }),
+			left => Err(Error::TrailingBytes(left)),
+		}
+	}
+
+	/// Decode the value of the `System::Events` storage.
+	pub fn decode_events(&self, mut bytes: &[u8]) -> Result<Vec<EventRecord>, Error> {
+		let events_ty = self
+			.events_ty
+			.ok_or_else(|| Error::Metadata("Missing `System::Events` storage".into()))?;
+		let record_ty = match &self.resolve(events_ty)?.type_def {
+			TypeDef::Sequence(sequence) => sequence.type_param.id,
+			_ => return Err(Error::Metadata("`System::Events` is not a sequence".into())),
+		};
+		let record_fields = match &self.resolve(record_ty)?.type_def {
+			TypeDef::Composite(composite) => &composite.fields,
+			_ => return Err(Error::Metadata("Event record is not a struct".into())),
+		};
+
+		let input = &mut bytes;
+		let values = ValueDecoder(&self.types);
+		let len = Compact::<u32>::decode(input)?.0;
+		let mut records = Vec::new();
+		for _ in 0..len {
+			let (mut phase, mut event, mut topics) = (None, None, None);
+			for field in record_fields {
+				match field.name.as_deref() {
+					Some("phase") => phase = Some(values.decode(field.ty.id, input)?),
+					Some("event") => event = Some(self.decode_pallet_variant(field.ty.id, input)?),
+					Some("topics") => topics = Some(values.decode(field.ty.id, input)?),
+					_ => {
+						values.decode(field.ty.id, input)?;
+					},
+				}
+			}
+			match (phase, event, topics) {
+				(Some(phase), Some(event), Some(topics)) =>
+					records.push(EventRecord { phase, event, topics }),
+				_ => return Err(Error::Metadata("Unexpected event record fields".into())),
+			}
+		}
+
+		match input.len() {
+			0 => Ok(records),
+			left => Err(Error::TrailingBytes(left)),
+		}
+	}
+
+	fn resolve(&self, ty: u32) -> Result<&Type<PortableForm>, Error> {
+		ValueDecoder(&self.types).resolve(ty)
+	}
+
+	/// Decode a variant of an outer enum, whose variants wrap the enum of each pallet.
+	fn decode_pallet_variant(&self, ty: u32, input: &mut &[u8]) -> Result<PalletVariant, Error> {
+		let values = ValueDecoder(&self.types);
+		let outer = match &self.resolve(ty)?.type_def {
+			TypeDef::Variant(variant) => variant,
+			_ => return Err(Error::Metadata(format!("Type {} is not an outer enum", ty))),
+		};
+		let index = u8::decode(input)?;
+		let pallet = outer
+			.variants
+			.iter()
+			.find(|v| v.index == index)
+			.ok_or(Error::UnknownVariant(index))?;
+		let inner = match &pallet.fields[..] {
+			[field] => match &self.resolve(field.ty.id)?.type_def {
+				TypeDef::Variant(variant) => variant,
+				_ => return Err(Error::Metadata(format!("{} does not wrap an enum", pallet.name))),
+			},
+			_ => return Err(Error::Metadata(format!("{} does not wrap an enum", pallet.name))),
+		};
+		let (variant, fields) = values.decode_variant(inner, input)?;
+
+		Ok(PalletVariant {
+			pallet: pallet.name.clone(),
+			name: variant.name.clone(),
+			docs: variant.docs.clone(),
+			fields,
+		})
+	}
+}
+
+/// The type of the `Events` entry among the storage entries of `System`.
+fn events_ty(
+	mut entries: impl Iterator<Item = (String, StorageEntryType<PortableForm>)>,
+) -> Option<u32> {
+	entries.find(|(name, _)| name == "Events").and_then(|(_, ty)| match ty {
+		StorageEntryType::Plain(ty) => Some(ty.id),
+		StorageEntryType::Map { .. } => None,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use frame_system::{EventRecord as SystemEventRecord, Phase};
+	use sp_keyring::AccountKeyring;
+	use substrate_test_runtime::{Hash, Runtime, RuntimeEvent, Transfer};
+
+	fn transfer() -> Vec<u8> {
+		Transfer {
+			from: AccountKeyring::Alice.pair(),
+			to: AccountKeyring::Bob.into(),
+			amount: 5,
+			nonce: 3,
+		}
+		.into_unchecked_extrinsic()
+		.encode()
+	}
+
+	#[test]
+	fn decodes_signed_extrinsic() {
+		let v14 = Runtime::metadata().encode();
+		let v15 = Runtime::metadata_at_version(15).unwrap().to_vec();
+
+		for metadata in [v14, v15] {
+			let decoder = Decoder::new(&metadata).unwrap();
+			let extrinsic = decoder.decode_extrinsic(&transfer()).unwrap();
+
+			assert_eq!(extrinsic.call.to_string(), "Balances::transfer_allow_death");
+			assert!(!extrinsic.call.docs.is_empty());
+			assert_eq!(extrinsic.call.fields.get("value"), Some(&Value::UInt(5)));
+			let signature = extrinsic.signature.unwrap();
+			assert_eq!(
+				signature.extra.0.iter().map(|(id, _)| id.clone().unwrap()).collect::<Vec<_>>(),
+				vec!["CheckNonce", "CheckWeight", "CheckSubstrateCall"],
+			);
+			assert_eq!(
+				serde_json::to_value(&signature.extra).unwrap()["CheckNonce"],
+				serde_json::json!(3),
+			);
+		}
+	}
+
+	#[test]
+	fn rejects_malformed_extrinsics() {
+		let decoder = Decoder::new(&Runtime::metadata().encode()).unwrap();
+		let ext = transfer();
+		let mut trailing = Vec::<u8>::decode(&mut &ext[..]).unwrap();
+		trailing.push(0);
+
+		assert!(matches!(decoder.decode_extrinsic(&ext[..ext.len() - 1]), Err(Error::Codec(_))));
+		assert!(matches!(
+			decoder.decode_extrinsic(&trailing.encode()),
+			Err(Error::TrailingBytes(1))
+		));
+		assert!(matches!(
+			decoder.decode_extrinsic(&[8, 0x83, 0]),
+			Err(Error::UnsupportedVersion(3))
+		));
+	}
+
+	#[test]
+	fn decodes_events() {
+		let decoder = Decoder::new(&Runtime::metadata().encode()).unwrap();
+		let records = vec![SystemEventRecord {
+			phase: Phase::ApplyExtrinsic(1),
+			event: RuntimeEvent::System(frame_system::Event::ExtrinsicSuccess {
+				dispatch_info: Default::default(),
+			}),
+			topics: vec![Hash::repeat_byte(1)],
+		}];
+
+		let decoded = decoder.decode_events(&records.encode()).unwrap();
+		assert_eq!(decoded.len(), 1);
+		assert_eq!(decoded[0].event.to_string(), "System::ExtrinsicSuccess");
+		assert_eq!(
+			decoded[0].phase,
+			Value::Variant("ApplyExtrinsic".into(), Composite(vec![(None, Value::UInt(1))])),
+		);
+		assert_eq!(
+			serde_json::to_value(&decoded[0].topics).unwrap(),
+			serde_json::json!([format!("0x{}", "01".repeat(32))]),
+		);
+	}
+}
@@ -0,0 +1,380 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dynamically typed values, decoded according to the types of a [`PortableRegistry`].
+
+use crate::Error;
+use codec::{Compact, Decode};
+use scale_info::{
+	form::PortableForm, Field, PortableRegistry, Type, TypeDef, TypeDefPrimitive, TypeDefVariant,
+	Variant,
+};
+use serde::{
+	ser::{SerializeMap, SerializeSeq},
+	Serialize, Serializer,
+};
+
+/// The maximum nesting depth of decoded values.
+///
+/// Like `MAX_EXTRINSIC_DEPTH` in `sp-runtime`, this prevents a malicious input, or a type that
+/// contains itself, from exhausting the stack.
+pub const MAX_DEPTH: u32 = 256;
+
+/// A value of any type described by the metadata.
+///
+/// Serializes the same way `subkey tx` expects its input: enum variants as `"Variant"` or
+/// `{"Variant": fields}`, bytes as hex and integers which do not fit into 64 bits as strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+	/// A struct or a tuple.
+	Composite(Composite),
+	/// The variant of an enum with the given name.
+	Variant(String, Composite),
+	/// A boolean.
+	Bool(bool),
+	/// A character.
+	Char(char),
+	/// A string.
+	Str(String),
+	/// An unsigned integer, compact encoded or not.
+	UInt(u128),
+	/// A signed integer.
+	Int(i128),
+	/// A sequence or array of bytes. 256 bit integers are represented as little endian bytes.
+	Bytes(Vec<u8>),
+	/// A sequence or array of any other type.
+	Sequence(Vec<Value>),
+}
+
+/// The fields of a struct, a tuple or an enum variant, in order of their encoding.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Composite(pub Vec<(Option<String>, Value)>);
+
+impl Composite {
+	/// Returns the value of the field with the given name.
+	pub fn get(&self, name: &str) -> Option<&Value> {
+		self.0.iter().find(|(n, _)| n.as_deref() == Some(name)).map(|(_, value)| value)
+	}
+
+	/// Returns whether there are no fields.
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+}
+
+impl Serialize for Composite {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		match &self.0[..] {
+			[(None, value)] => value.serialize(serializer),
+			fields if !fields.is_empty() && fields.iter().all(|(name, _)| name.is_some()) => {
+				let mut map = serializer.serialize_map(Some(fields.len()))?;
+				for (name, value) in fields {
+					map.serialize_entry(name.as_deref().unwrap_or_default(), value)?;
+				}
+				map.end()
+			},
+			fields => {
+				let mut seq = serializer.serialize_seq(Some(fields.len()))?;
+				for (_, value) in fields {
+					seq.serialize_element(value)?;
+				}
+				seq.end()
+			},
+		}
+	}
+}
+
+impl Serialize for Value {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		match self {
+			Value::Composite(fields) => fields.serialize(serializer),
+			Value::Variant(name, fields) if fields.is_empty() => serializer.serialize_str(name),
+			Value::Variant(name, fields) => {
+				let mut map = serializer.serialize_map(Some(1))?;
+				map.serialize_entry(name, fields)?;
+				map.end()
+			},
+			Value::Bool(b) => serializer.serialize_bool(*b),
+			Value::Char(c) => serializer.serialize_char(*c),
+			Value::Str(s) => serializer.serialize_str(s),
+			Value::UInt(n) => match u64::try_from(*n) {
+				Ok(n) => serializer.serialize_u64(n),
+				Err(_) => serializer.serialize_str(&n.to_string()),
+			},
+			Value::Int(n) => match i64::try_from(*n) {
+				Ok(n) => serializer.serialize_i64(n),
+				Err(_) => serializer.serialize_str(&n.to_string()),
+			},
+			Value::Bytes(bytes) => {
+				let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+				serializer.serialize_str(&format!("0x{}", hex))
+			},
+			Value::Sequence(items) => items.serialize(serializer),
+		}
+	}
+}
+
+/// Decodes SCALE encoded values according to the types of a [`PortableRegistry`].
+pub(crate) struct ValueDecoder<'a>(pub &'a PortableRegistry);
+
+impl<'a> ValueDecoder<'a> {
+	pub fn resolve(&self, ty: u32) -> Result<&'a Type<PortableForm>, Error> {
+		self.0.resolve(ty).ok_or(Error::UnknownType(ty))
+	}
+
+	pub fn decode(&self, ty: u32, input: &mut &[u8]) -> Result<Value, Error> {
+		self.decode_at(ty, input, 0)
+	}
+
+	/// Decodes the variant index and the fields of the matching variant.
+	pub fn decode_variant(
+		&self,
+		variant: &'a TypeDefVariant<PortableForm>,
+		input: &mut &[u8],
+	) -> Result<(&'a Variant<PortableForm>, Composite), Error> {
+		self.decode_variant_at(variant, input, 0)
+	}
+
+	fn decode_at(&self, ty: u32, input: &mut &[u8], depth: u32) -> Result<Value, Error> {
+		if depth > MAX_DEPTH {
+			return Err(Error::TooDeep)
+		}
+		let depth = depth + 1;
+
+		Ok(match &self.resolve(ty)?.type_def {
+			TypeDef::Composite(composite) =>
+				Value::Composite(self.decode_fields(&composite.fields, input, depth)?),
+			TypeDef::Variant(variant) => {
+				let (variant, fields) = self.decode_variant_at(variant, input, depth)?;
+				Value::Variant(variant.name.clone(), fields)
+			},
+			TypeDef::Sequence(sequence) => {
+				let len = Compact::<u32>::decode(input)?.0;
+				self.decode_items(sequence.type_param.id, len, input, depth)?
+			},
+			TypeDef::Array(array) =>
+				self.decode_items(array.type_param.id, array.len, input, depth)?,
+			TypeDef::Tuple(tuple) => Value::Composite(Composite(
+				tuple
+					.fields
+					.iter()
+					.map(|ty| Ok((None, self.decode_at(ty.id, input, depth)?)))
+					.collect::<Result<_, Error>>()?,
+			)),
+			TypeDef::Primitive(primitive) => decode_primitive(primitive, input)?,
+			TypeDef::Compact(compact) =>
+				self.decode_compact(compact.type_param.id, input, depth)?,
+			TypeDef::BitSequence(_) => return Err(Error::Unsupported("bit sequences")),
+		})
+	}
+
+	fn decode_variant_at(
+		&self,
+		variant: &'a TypeDefVariant<PortableForm>,
+		input: &mut &[u8],
+		depth: u32,
+	) -> Result<(&'a Variant<PortableForm>, Composite), Error> {
+		let index = u8::decode(input)?;
+		let variant = variant
+			.variants
+			.iter()
+			.find(|v| v.index == index)
+			.ok_or(Error::UnknownVariant(index))?;
+		Ok((variant, self.decode_fields(&variant.fields, input, depth)?))
+	}
+
+	fn decode_fields(
+		&self,
+		fields: &[Field<PortableForm>],
+		input: &mut &[u8],
+		depth: u32,
+	) -> Result<Composite, Error> {
+		fields
+			.iter()
+			.map(|f| Ok((f.name.clone(), self.decode_at(f.ty.id, input, depth)?)))
+			.collect::<Result<_, Error>>()
+			.map(Composite)
+	}
+
+	fn decode_items(
+		&self,
+		ty: u32,
+		len: u32,
+		input: &mut &[u8],
+		depth: u32,
+	) -> Result<Value, Error> {
+		let len = len as usize;
+		// Every item that is not zero-sized takes at least one byte. Rejecting longer sequences,
+		// even of zero-sized items, prevents a short input from making us decode billions of
+		// zero-sized items.
+		if input.len() < len {
+			return Err(codec::Error::from("Not enough data to fill buffer").into())
+		}
+		if matches!(self.resolve(ty)?.type_def, TypeDef::Primitive(TypeDefPrimitive::U8)) {
+			let (bytes, rest) = input.split_at(len);
+			*input = rest;
+			return Ok(Value::Bytes(bytes.to_vec()))
+		}
+		(0..len)
+			.map(|_| self.decode_at(ty, input, depth))
+			.collect::<Result<_, _>>()
+			.map(Value::Sequence)
+	}
+
+	fn decode_compact(&self, ty: u32, input: &mut &[u8], depth: u32) -> Result<Value, Error> {
+		if depth > MAX_DEPTH {
+			return Err(Error::TooDeep)
+		}
+
+		match &self.resolve(ty)?.type_def {
+			TypeDef::Primitive(primitive) => Ok(Value::UInt(match primitive {
+				TypeDefPrimitive::U8 => Compact::<u8>::decode(input)?.0.into(),
+				TypeDefPrimitive::U16 => Compact::<u16>::decode(input)?.0.into(),
+				TypeDefPrimitive::U32 => Compact::<u32>::decode(input)?.0.into(),
+				TypeDefPrimitive::U64 => Compact::<u64>::decode(input)?.0.into(),
+				TypeDefPrimitive::U128 => Compact::<u128>::decode(input)?.0,
+				_ => return Err(Error::Unsupported("compact encoding of this primitive")),
+			})),
+			// Types implementing `CompactAs`, e.g. `Perbill`.
+			TypeDef::Composite(composite) if composite.fields.len() == 1 => {
+				let field = &composite.fields[0];
+				let value = self.decode_compact(field.ty.id, input, depth + 1)?;
+				Ok(Value::Composite(Composite(vec![(field.name.clone(), value)])))
+			},
+			_ => Err(Error::Unsupported("compact encoding of this type")),
+		}
+	}
+}
+
+fn decode_primitive(primitive: &TypeDefPrimitive, input: &mut &[u8]) -> Result<Value, Error> {
+	Ok(match primitive {
+		TypeDefPrimitive::Bool => Value::Bool(bool::decode(input)?),
+		TypeDefPrimitive::Char => Value::Char(
+			char::from_u32(u32::decode(input)?).ok_or(codec::Error::from("Invalid char"))?,
+		),
+		TypeDefPrimitive::Str => Value::Str(String::decode(input)?),
+		TypeDefPrimitive::U8 => Value::UInt(u8::decode(input)?.into()),
+		TypeDefPrimitive::U16 => Value::UInt(u16::decode(input)?.into()),
+		TypeDefPrimitive::U32 => Value::UInt(u32::decode(input)?.into()),
+		TypeDefPrimitive::U64 => Value::UInt(u64::decode(input)?.into()),
+		TypeDefPrimitive::U128 => Value::UInt(u128::decode(input)?),
+		TypeDefPrimitive::I8 => Value::Int(i8::decode(input)?.into()),
+		TypeDefPrimitive::I16 => Value::Int(i16::decode(input)?.into()),
+		TypeDefPrimitive::I32 => Value::Int(i32::decode(input)?.into()),
+		TypeDefPrimitive::I64 => Value::Int(i64::decode(input)?.into()),
+		TypeDefPrimitive::I128 => Value::Int(i128::decode(input)?),
+		TypeDefPrimitive::U256 | TypeDefPrimitive::I256 =>
+			Value::Bytes(<[u8; 32]>::decode(input)?.to_vec()),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use scale_info::{MetaType, Registry, TypeInfo};
+	use serde_json::json;
+
+	#[derive(Encode, TypeInfo)]
+	enum Call {
+		Transfer {
+			dest: [u8; 4],
+			#[codec(compact)]
+			value: u128,
+		},
+		Remark(Vec<u8>),
+		Batch {
+			calls: Vec<Call>,
+			maybe: Option<(u32, bool)>,
+			note: String,
+		},
+	}
+
+	fn decode<T: TypeInfo + 'static>(bytes: &[u8]) -> Result<Value, Error> {
+		let mut registry = Registry::new();
+		let ty = registry.register_type(&MetaType::new::<T>());
+		let registry: PortableRegistry = registry.into();
+		ValueDecoder(&registry).decode(ty.id, &mut &bytes[..])
+	}
+
+	#[test]
+	fn decodes_and_serializes_values() {
+		let call = Call::Batch {
+			calls: vec![
+				Call::Transfer { dest: [1, 2, 3, 4], value: u128::MAX },
+				Call::Remark(vec![0xab]),
+			],
+			maybe: Some((7, true)),
+			note: "hi".into(),
+		};
+
+		let value = decode::<Call>(&call.encode()).unwrap();
+		assert_eq!(
+			serde_json::to_value(&value).unwrap(),
+			json!({ "Batch": {
+				"calls": [
+					{ "Transfer": {
+						"dest": "0x01020304",
+						"value": u128::MAX.to_string(),
+					}},
+					{ "Remark": "0xab" },
+				],
+				"maybe": { "Some": [7, true] },
+				"note": "hi",
+			}}),
+		);
+		assert_eq!(
+			serde_json::to_value(decode::<Option<i8>>(&None::<i8>.encode()).unwrap()).unwrap(),
+			json!("None"),
+		);
+	}
+
+	#[test]
+	fn rejects_invalid_encoding() {
+		assert!(matches!(decode::<Call>(&[3]), Err(Error::UnknownVariant(3))));
+		assert!(decode::<Vec<u8>>(&[8, 1]).is_err());
+		assert!(decode::<char>(&0xd800u32.encode()).is_err());
+	}
+
+	#[test]
+	fn rejects_sequences_longer_than_the_input() {
+		// A billion zero-sized items would otherwise be decoded from five bytes.
+		let encoded = Compact(1_000_000_000u32).encode();
+		assert!(matches!(decode::<Vec<()>>(&encoded), Err(Error::Codec(_))));
+		assert!(matches!(decode::<Vec<u32>>(&encoded), Err(Error::Codec(_))));
+
+		assert_eq!(
+			decode::<Vec<u32>>(&vec![7u32].encode()).unwrap(),
+			Value::Sequence(vec![Value::UInt(7)])
+		);
+	}
+
+	#[test]
+	fn rejects_too_deeply_nested_values() {
+		let nested = |depth| {
+			let mut call = Call::Remark(vec![]);
+			for _ in 0..depth {
+				call = Call::Batch { calls: vec![call], maybe: None, note: String::new() };
+			}
+			call.encode()
+		};
+
+		// Each batch is a variant and a sequence.
+		assert!(decode::<Call>(&nested(MAX_DEPTH / 2 - 1)).is_ok());
+		assert!(matches!(decode::<Call>(&nested(MAX_DEPTH)), Err(Error::TooDeep)));
+	}
+}
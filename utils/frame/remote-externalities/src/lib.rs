@@ -43,6 +43,7 @@ use sp_state_machine::TestExternalities;
 use spinners::{Spinner, Spinners};
 use std::{
 	cmp::{max, min},
+	collections::{HashMap, HashSet},
 	fs,
 	ops::{Deref, DerefMut},
 	path::{Path, PathBuf},
@@ -60,6 +61,7 @@ type SnapshotVersion = Compact<u16>;
 const LOG_TARGET: &str = "remote-ext";
 const DEFAULT_HTTP_ENDPOINT: &str = "https://rpc.polkadot.io:443";
 const SNAPSHOT_VERSION: SnapshotVersion = Compact(3);
+const CACHE_VERSION: SnapshotVersion = Compact(1);
 
 /// The snapshot that we store on disk.
 #[derive(Decode, Encode)]
@@ -103,6 +105,37 @@ impl<B: BlockT> Snapshot<B> {
 	}
 }
 
+/// A cache entry holding all the key-values under one prefix, as of `block_hash`.
+#[derive(Decode, Encode)]
+struct CacheEntry<B: BlockT> {
+	cache_version: SnapshotVersion,
+	block_hash: B::Hash,
+	key_values: Vec<KeyValue>,
+}
+
+impl<B: BlockT> CacheEntry<B> {
+	fn load(path: &Path) -> Result<CacheEntry<B>, &'static str> {
+		let bytes = fs::read(path).map_err(|_| "fs::read failed.")?;
+		let cache_version =
+			SnapshotVersion::decode(&mut &*bytes).map_err(|_| "Failed to decode cache version")?;
+
+		if cache_version != CACHE_VERSION {
+			return Err("Unsupported cache version detected.")
+		}
+
+		Decode::decode(&mut &*bytes).map_err(|_| "Decode failed")
+	}
+
+	fn save(path: &Path, block_hash: B::Hash, key_values: &[KeyValue]) -> Result<(), &'static str> {
+		if let Some(dir) = path.parent() {
+			fs::create_dir_all(dir).map_err(|_| "fs::create_dir_all failed.")?;
+		}
+		// Encodes exactly like a `CacheEntry`, without having to own the key-values.
+		let bytes = (CACHE_VERSION, block_hash, key_values).encode();
+		fs::write(path, bytes).map_err(|_| "fs::write failed.")
+	}
+}
+
 /// An externalities that acts exactly the same as [`sp_io::TestExternalities`] but has a few extra
 /// bits and pieces to it, and can be loaded remotely.
 pub struct RemoteExternalities<B: BlockT> {
@@ -238,6 +271,11 @@ pub struct OnlineConfig<B: BlockT> {
 	pub hashed_prefixes: Vec<Vec<u8>>,
 	/// Storage entry keys to be injected into the externalities. The *hashed* key must be given.
 	pub hashed_keys: Vec<Vec<u8>>,
+	/// An optional cache of downloaded key-values, both read from and written to.
+	///
+	/// If the cache was written at an older block, only the keys that changed since then are
+	/// downloaded again.
+	pub cache: Option<CacheConfig>,
 }
 
 impl<B: BlockT> OnlineConfig<B> {
@@ -263,6 +301,7 @@ impl<B: BlockT> Default for OnlineConfig<B> {
 			pallets: Default::default(),
 			hashed_keys: Default::default(),
 			hashed_prefixes: Default::default(),
+			cache: None,
 		}
 	}
 }
@@ -298,6 +337,35 @@ impl Default for SnapshotConfig {
 	}
 }
 
+/// Configuration of the key-value cache.
+#[derive(Clone)]
+pub struct CacheConfig {
+	/// The directory in which the cache files are kept, one per scraped prefix.
+	pub path: PathBuf,
+}
+
+impl CacheConfig {
+	pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+		Self { path: path.into() }
+	}
+
+	/// The path of the cache file for the given `prefix`.
+	fn entry_path(&self, prefix: &StorageKey) -> PathBuf {
+		let name = if prefix.0.is_empty() {
+			"all".to_string()
+		} else {
+			format!("{}", HexDisplay::from(&prefix.0))
+		};
+		self.path.join(format!("{}.cache", name))
+	}
+}
+
+impl From<String> for CacheConfig {
+	fn from(s: String) -> Self {
+		Self::new(s)
+	}
+}
+
 /// Builder for remote-externalities.
 #[derive(Clone)]
 pub struct Builder<B: BlockT> {
@@ -352,6 +420,7 @@ where
 	B::Header: DeserializeOwned,
 {
 	const PARALLEL_REQUESTS: usize = 4;
+	const QUERY_STORAGE_KEYS: usize = 1000;
 	const BATCH_SIZE_INCREASE_FACTOR: f32 = 1.10;
 	const BATCH_SIZE_DECREASE_FACTOR: f32 = 0.50;
 	const REQUEST_DURATION_TARGET: Duration = Duration::from_secs(15);
@@ -684,6 +753,10 @@ where
 	/// Synonym of `getPairs` that uses paged queries to first get the keys, and then
 	/// map them to values one by one.
 	///
+	/// If a cache is configured, the key-values are read from it when it was written at `at`,
+	/// and refreshed incrementally when it was written at another block. The cache is then
+	/// updated to `at`.
+	///
 	/// This can work with public nodes. But, expect it to be darn slow.
 	pub(crate) async fn rpc_get_pairs(
 		&self,
@@ -691,12 +764,59 @@ where
 		at: B::Hash,
 		pending_ext: &mut TestExternalities<HashingFor<B>>,
 	) -> Result<Vec<KeyValue>, &'static str> {
+		let cache_path = self.as_online().cache.as_ref().map(|c| c.entry_path(&prefix));
+		let cached = cache_path.as_ref().and_then(|path| {
+			if !path.exists() {
+				return None
+			}
+			CacheEntry::<B>::load(path)
+				.map_err(|e| {
+					log::warn!(target: LOG_TARGET, "ignoring cache file {:?}: {}", path, e);
+				})
+				.ok()
+		});
+
+		let key_values = match cached {
+			Some(entry) if entry.block_hash == at => {
+				log::info!(
+					target: LOG_TARGET,
+					"✅ Loaded {} cached keys at block {:?}",
+					entry.key_values.len(),
+					at
+				);
+				entry.key_values
+			},
+			cached => {
+				let keys = self.rpc_scrape_keys(&prefix, at).await?;
+				match cached {
+					Some(entry) => self.rpc_refresh_cached(entry, keys, at).await?,
+					None => self.rpc_get_values(keys, at).await?,
+				}
+			},
+		};
+
+		if let Some(path) = cache_path {
+			if let Err(e) = CacheEntry::<B>::save(&path, at, &key_values) {
+				log::warn!(target: LOG_TARGET, "failed to write cache file {:?}: {}", path, e);
+			}
+		}
+
+		self.insert_pairs(&key_values, pending_ext);
+		Ok(key_values)
+	}
+
+	/// Get all the keys starting with `prefix` at block `at`.
+	async fn rpc_scrape_keys(
+		&self,
+		prefix: &StorageKey,
+		at: B::Hash,
+	) -> Result<Vec<StorageKey>, &'static str> {
 		let start = Instant::now();
 		let mut sp = Spinner::with_timer(Spinners::Dots, "Scraping keys...".into());
 		// TODO We could start downloading when having collected the first batch of keys
 		// https://github.com/paritytech/polkadot-sdk/issues/2494
 		let keys = self
-			.rpc_get_keys_parallel(&prefix, at, Self::PARALLEL_REQUESTS)
+			.rpc_get_keys_parallel(prefix, at, Self::PARALLEL_REQUESTS)
 			.await?
 			.into_iter()
 			.collect::<Vec<_>>();
@@ -705,6 +825,98 @@ where
 			keys.len(),
 			start.elapsed().as_secs_f32()
 		));
+		Ok(keys)
+	}
+
+	/// Bring the cached `entry` up to date with the given `keys` at block `at`.
+	///
+	/// Only the keys that are new, or that changed since the block of the cache, are downloaded.
+	/// Falls back to downloading all the values if the changes cannot be queried, e.g. because
+	/// the node has pruned the state of the cached block.
+	async fn rpc_refresh_cached(
+		&self,
+		entry: CacheEntry<B>,
+		keys: Vec<StorageKey>,
+		at: B::Hash,
+	) -> Result<Vec<KeyValue>, &'static str> {
+		let cached = entry.key_values.into_iter().collect::<HashMap<_, _>>();
+		let (known, new): (Vec<_>, Vec<_>) =
+			keys.iter().cloned().partition(|key| cached.contains_key(key));
+
+		let changed = match self.rpc_query_changed_keys(&known, entry.block_hash, at).await {
+			Ok(changed) => changed,
+			Err(e) => {
+				log::warn!(
+					target: LOG_TARGET,
+					"failed to refresh cache from block {:?} ({}), downloading all values",
+					entry.block_hash,
+					e
+				);
+				return self.rpc_get_values(keys, at).await
+			},
+		};
+
+		let to_fetch = new
+			.into_iter()
+			.chain(known.into_iter().filter(|key| changed.contains(key)))
+			.collect::<Vec<_>>();
+		log::info!(
+			target: LOG_TARGET,
+			"♻️ Refreshing cache from block {:?}: {} of {} keys to download",
+			entry.block_hash,
+			to_fetch.len(),
+			keys.len()
+		);
+		let fetched =
+			self.rpc_get_values(to_fetch, at).await?.into_iter().collect::<HashMap<_, _>>();
+
+		Ok(keys
+			.into_iter()
+			.map(|key| {
+				let value = fetched
+					.get(&key)
+					.or_else(|| cached.get(&key))
+					.cloned()
+					.expect("every key is either fetched or cached; qed");
+				(key, value)
+			})
+			.collect())
+	}
+
+	/// Get the subset of `keys` whose values changed after block `from`, up to block `to`.
+	async fn rpc_query_changed_keys(
+		&self,
+		keys: &[StorageKey],
+		from: B::Hash,
+		to: B::Hash,
+	) -> Result<HashSet<StorageKey>, &'static str> {
+		let client = self.as_online().rpc_client();
+		let mut changed = HashSet::new();
+		for chunk in keys.chunks(Self::QUERY_STORAGE_KEYS) {
+			let change_sets =
+				StateApi::<B::Hash>::query_storage(client, chunk.to_vec(), from, Some(to))
+					.await
+					.map_err(|e| {
+						error!(target: LOG_TARGET, "Error = {:?}", e);
+						"rpc query_storage failed"
+					})?;
+			// The change set of `from` itself holds the initial values, not changes.
+			changed.extend(
+				change_sets
+					.into_iter()
+					.filter(|set| set.block != from)
+					.flat_map(|set| set.changes.into_iter().map(|(key, _)| key)),
+			);
+		}
+		Ok(changed)
+	}
+
+	/// Download the values of `keys` at block `at`.
+	async fn rpc_get_values(
+		&self,
+		keys: Vec<StorageKey>,
+		at: B::Hash,
+	) -> Result<Vec<KeyValue>, &'static str> {
 		if keys.is_empty() {
 			return Ok(Default::default())
 		}
@@ -759,9 +971,18 @@ where
 			})
 			.collect::<Vec<_>>();
 
+		Ok(key_values)
+	}
+
+	/// Insert the top-level `key_values` into `pending_ext`.
+	fn insert_pairs(
+		&self,
+		key_values: &[KeyValue],
+		pending_ext: &mut TestExternalities<HashingFor<B>>,
+	) {
 		let mut sp = Spinner::with_timer(Spinners::Dots, "Inserting keys into DB...".into());
 		let start = Instant::now();
-		pending_ext.batch_insert(key_values.clone().into_iter().filter_map(|(k, v)| {
+		pending_ext.batch_insert(key_values.iter().cloned().filter_map(|(k, v)| {
 			// Don't insert the child keys here, they need to be inserted seperately with all their
 			// data in the load_child_remote function.
 			match is_default_child_storage_key(&k.0) {
@@ -773,7 +994,6 @@ where
 			"✅ Inserted keys into DB ({:.2}s)",
 			start.elapsed().as_secs_f32()
 		));
-		Ok(key_values)
 	}
 
 	/// Get the values corresponding to `child_keys` at the given `prefixed_top_key`.
@@ -1258,6 +1478,25 @@ mod tests {
 			.expect("Can't read state snapshot file")
 			.execute_with(|| assert!(sp_io::storage::get(&some_key).is_none()));
 	}
+
+	#[test]
+	fn cache_entry_round_trips() {
+		let cache = CacheConfig::new("cache_entry_round_trips");
+		let path = cache.entry_path(&StorageKey(vec![1, 2]));
+		assert_eq!(path, Path::new("cache_entry_round_trips").join("0102.cache"));
+
+		let key_values = vec![
+			(StorageKey(vec![1, 2, 3]), StorageData(vec![4])),
+			(StorageKey(vec![1, 2, 5]), StorageData(vec![])),
+		];
+		CacheEntry::<Block>::save(&path, Hash::repeat_byte(1), &key_values).unwrap();
+
+		let entry = CacheEntry::<Block>::load(&path).unwrap();
+		assert_eq!(entry.block_hash, Hash::repeat_byte(1));
+		assert_eq!(entry.key_values, key_values);
+
+		std::fs::remove_dir_all(&cache.path).unwrap();
+	}
 }
 
 #[cfg(all(test, feature = "remote-test"))]
@@ -1486,6 +1725,35 @@ mod remote_tests {
 		std::fs::remove_file(to_delete.path()).unwrap();
 	}
 
+	#[tokio::test(flavor = "multi_thread")]
+	async fn can_use_and_refresh_cache() {
+		const CACHE: &'static str = "can_use_and_refresh_cache";
+		init_logger();
+
+		let build = || async {
+			Builder::<Block>::new()
+				.mode(Mode::Online(OnlineConfig {
+					transport: endpoint().clone().into(),
+					pallets: vec!["Proxy".to_owned()],
+					child_trie: false,
+					cache: Some(CacheConfig::new(CACHE)),
+					..Default::default()
+				}))
+				.build()
+				.await
+				.unwrap()
+				.execute_with(|| sp_io::storage::root(StateVersion::V1))
+		};
+
+		// first run fills the cache, second run reads or refreshes it.
+		build().await;
+		build().await;
+
+		let cached = std::fs::read_dir(Path::new(CACHE)).unwrap().collect::<Vec<_>>();
+		assert_eq!(cached.len(), 1);
+		std::fs::remove_dir_all(CACHE).unwrap();
+	}
+
 	#[tokio::test]
 	async fn can_create_child_snapshot() {
 		const CACHE: &'static str = "can_create_child_snapshot";
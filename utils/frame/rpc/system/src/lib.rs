@@ -28,7 +28,7 @@ use jsonrpsee::{
 
 use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
-use sp_api::ApiExt;
+use sp_api::{ApiExt, Metadata};
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::HeaderBackend;
 use sp_core::{hexdisplay::HexDisplay, Bytes};
@@ -50,6 +50,17 @@ pub trait SystemApi<BlockHash, AccountId, Nonce> {
 	/// Dry run an extrinsic at a given block. Return SCALE encoded ApplyExtrinsicResult.
 	#[method(name = "system_dryRun", aliases = ["system_dryRunAt"])]
 	async fn dry_run(&self, extrinsic: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
+
+	/// Decode an extrinsic using the metadata of the runtime at a given block.
+	///
+	/// Returns the call with its pallet, name, documentation and fields, together with the
+	/// signature and signed extensions for signed extrinsics.
+	#[method(name = "system_decodeExtrinsic")]
+	async fn decode_extrinsic(
+		&self,
+		extrinsic: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<serde_json::Value>;
 }
 
 /// Error type of this RPC api.
@@ -93,6 +104,7 @@ where
 	C: Send + Sync + 'static,
 	C::Api: AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: Metadata<Block>,
 	P: TransactionPool + 'static,
 	Block: traits::Block,
 	AccountId: Clone + Display + Codec + Send + 'static,
@@ -172,6 +184,49 @@ where
 
 		Ok(Encode::encode(&result).into())
 	}
+
+	async fn decode_extrinsic(
+		&self,
+		extrinsic: Bytes,
+		at: Option<<Block as traits::Block>::Hash>,
+	) -> RpcResult<serde_json::Value> {
+		self.deny_unsafe.check_if_safe()?;
+		let best_hash = at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash);
+
+		let decoder = self
+			.client
+			.runtime_api()
+			.metadata(best_hash)
+			.map_err(|e| e.to_string())
+			.and_then(|metadata| {
+				substrate_metadata_decoder::Decoder::new(&metadata).map_err(|e| e.to_string())
+			})
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to fetch runtime metadata.",
+					Some(e),
+				)
+			})?;
+
+		let decoded = decoder.decode_extrinsic(&extrinsic).map_err(|e| {
+			ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to decode extrinsic.",
+				Some(e.to_string()),
+			)
+		})?;
+
+		serde_json::to_value(decoded).map_err(|e| {
+			ErrorObject::owned(
+				Error::DecodeError.into(),
+				"Unable to decode extrinsic.",
+				Some(e.to_string()),
+			)
+		})
+	}
 }
 
 /// Adjust account nonce from state, so that tx with the nonce will be
@@ -333,4 +388,42 @@ mod tests {
 		let apply_res: ApplyExtrinsicResult = Decode::decode(&mut bytes.as_ref()).unwrap();
 		assert_eq!(apply_res, Err(TransactionValidityError::Invalid(InvalidTransaction::Future)));
 	}
+
+	#[tokio::test]
+	async fn decode_extrinsic_should_work() {
+		sp_tracing::try_init_simple();
+
+		// given
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let pool =
+			BasicPool::new_full(Default::default(), true.into(), None, spawner, client.clone());
+
+		let accounts = System::new(client, pool, DenyUnsafe::No);
+
+		let tx = Transfer {
+			from: AccountKeyring::Alice.into(),
+			to: AccountKeyring::Bob.into(),
+			amount: 5,
+			nonce: 0,
+		}
+		.into_unchecked_extrinsic();
+
+		// when
+		let decoded = accounts
+			.decode_extrinsic(tx.encode().into(), None)
+			.await
+			.expect("Call is successful");
+
+		// then
+		assert_eq!(decoded["call"]["pallet"], "Balances");
+		assert_eq!(decoded["call"]["name"], "transfer_allow_death");
+		assert_eq!(decoded["call"]["fields"]["value"], 5);
+		assert_eq!(decoded["signature"]["extra"]["CheckNonce"], 0);
+
+		let res = accounts.decode_extrinsic(vec![4, 0].into(), None).await;
+		assert_matches!(res, Err(e) => {
+			assert!(e.message().contains("Unable to decode extrinsic"));
+		});
+	}
 }
@@ -137,6 +137,7 @@ where
 				at: Some(hex::encode(header.parent_hash().encode())),
 				pallet: vec![],
 				child_tree: true,
+				cache_path: None,
 			});
 			let ext = state.into_ext::<Block, HostFns>(&shared, &executor, None, true).await?;
 			maybe_state_ext = Some(ext);
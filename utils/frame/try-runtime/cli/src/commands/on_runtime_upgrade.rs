@@ -18,10 +18,11 @@
 use crate::{build_executor, state_machine_call_with_proof, SharedParams, State, LOG_TARGET};
 use frame_try_runtime::UpgradeCheckSelect;
 use parity_scale_codec::{Decode, Encode};
+use remote_externalities::SnapshotConfig;
 use sc_executor::sp_wasm_interface::HostFunctions;
 use sp_runtime::traits::{Block as BlockT, NumberFor};
 use sp_weights::Weight;
-use std::{fmt::Debug, str::FromStr};
+use std::{fmt::Debug, path::PathBuf, str::FromStr};
 
 /// Configurations of the [`crate::Command::OnRuntimeUpgrade`].
 #[derive(Debug, Clone, clap::Parser)]
@@ -47,6 +48,17 @@ pub struct OnRuntimeUpgradeCmd {
 		require_equals = true,
 		verbatim_doc_comment)]
 	pub checks: UpgradeCheckSelect,
+
+	/// Write the state downloaded from a live chain to a snapshot at the given path.
+	///
+	/// The snapshot can be used for later runs with the `snap` state, instead of downloading the
+	/// state again.
+	#[arg(long)]
+	pub snapshot_path: Option<PathBuf>,
+
+	/// Do not fail if the migrations consume more weight than a block can hold.
+	#[arg(long)]
+	pub no_weight_warnings: bool,
 }
 
 pub(crate) async fn on_runtime_upgrade<Block, HostFns>(
@@ -62,7 +74,11 @@ where
 	HostFns: HostFunctions,
 {
 	let executor = build_executor(&shared);
-	let ext = command.state.into_ext::<Block, HostFns>(&shared, &executor, None, true).await?;
+	let state_snapshot = command.snapshot_path.map(SnapshotConfig::new);
+	let ext = command
+		.state
+		.into_ext::<Block, HostFns>(&shared, &executor, state_snapshot, true)
+		.await?;
 
 	let (_, encoded_result) = state_machine_call_with_proof::<Block, HostFns>(
 		&ext,
@@ -85,5 +101,16 @@ where
 		(weight.proof_size() as f64 / total_weight.proof_size().max(1) as f64) * 100.0,
 	);
 
+	if weight.any_gt(total_weight) {
+		if command.no_weight_warnings {
+			log::warn!(
+				target: LOG_TARGET,
+				"The migrations consume more weight than a block can hold, ignoring since --no-weight-warnings is set."
+			);
+		} else {
+			return Err("The migrations consume more weight than a block can hold. Use --no-weight-warnings to ignore this.".into())
+		}
+	}
+
 	Ok(())
 }
@@ -27,7 +27,7 @@
 use crate::block_building_info::BlockBuildingInfoProvider;
 use parity_scale_codec::Decode;
 use remote_externalities::{
-	Builder, Mode, OfflineConfig, OnlineConfig, RemoteExternalities, SnapshotConfig,
+	Builder, CacheConfig, Mode, OfflineConfig, OnlineConfig, RemoteExternalities, SnapshotConfig,
 };
 use sc_cli::{
 	execution_method_from_cli, CliConfiguration, RuntimeVersion, WasmExecutionMethod,
@@ -261,6 +261,13 @@ pub struct LiveState {
 	/// Otherwise, it must be enabled explicitly using this flag.
 	#[arg(long)]
 	child_tree: bool,
+
+	/// A directory in which to cache the downloaded key-values.
+	///
+	/// On later runs, only the keys that changed since the cached block are downloaded again.
+	/// This requires the node to still have the state of the cached block.
+	#[arg(long)]
+	cache_path: Option<PathBuf>,
 }
 
 /// The source of runtime *state* to use.
@@ -298,7 +305,7 @@ impl State {
 				Builder::<Block>::new().mode(Mode::Offline(OfflineConfig {
 					state_snapshot: SnapshotConfig::new(snapshot_path),
 				})),
-			State::Live(LiveState { pallet, uri, at, child_tree }) => {
+			State::Live(LiveState { pallet, uri, at, child_tree, cache_path }) => {
 				let at = match at {
 					Some(at_str) => Some(hash_of::<Block>(at_str)?),
 					None => None,
@@ -319,6 +326,7 @@ impl State {
 						[twox_128(b"System"), twox_128(b"Number")].concat(),
 					],
 					hashed_prefixes: vec![],
+					cache: cache_path.as_ref().map(CacheConfig::new),
 				}))
 			},
 		};